@@ -0,0 +1,37 @@
+// Generates the C header for the `capi` feature from src/capi.
+//
+// The header is always written to OUT_DIR.   The committed copy in
+// include/clarabel.h is only overwritten when the environment variable
+// CLARABEL_UPDATE_HEADER is set, e.g.
+//
+//   CLARABEL_UPDATE_HEADER=1 cargo build --features capi
+
+fn main() {
+    #[cfg(feature = "capi")]
+    generate_c_header();
+}
+
+#[cfg(feature = "capi")]
+fn generate_c_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("unable to read cbindgen.toml");
+
+    println!("cargo:rerun-if-changed=src/capi");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=CLARABEL_UPDATE_HEADER");
+
+    // parse src/capi only, so that public items elsewhere
+    // in the crate are not exported to the header
+    let bindings = cbindgen::Builder::new()
+        .with_src(format!("{}/src/capi/mod.rs", crate_dir))
+        .with_config(config)
+        .generate()
+        .expect("unable to generate the C header");
+
+    bindings.write_to_file(format!("{}/clarabel.h", out_dir));
+    if std::env::var_os("CLARABEL_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(format!("{}/include/clarabel.h", crate_dir));
+    }
+}
@@ -27,6 +27,6 @@ fn main() {
         verbose: true,
         ..DefaultSettings::default()
     };
-    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
     solver.solve();
 }
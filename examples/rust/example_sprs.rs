@@ -0,0 +1,43 @@
+#![allow(non_snake_case)]
+use clarabel::algebra::*;
+use clarabel::solver::*;
+use sprs::TriMat;
+
+fn main() {
+    // QP example, with data constructed using sprs
+
+    // upper triangle of P, assembled in CSR format
+    let mut P = TriMat::new((2, 2));
+    P.add_triplet(0, 0, 6.);
+    P.add_triplet(1, 1, 4.);
+    let P: sprs::CsMat<f64> = P.to_csr();
+
+    let q = vec![-1., -4.];
+
+    let mut A = TriMat::new((5, 2));
+    A.add_triplet(0, 0, 1.);
+    A.add_triplet(0, 1, -2.);
+    A.add_triplet(1, 0, 1.);
+    A.add_triplet(2, 1, 1.);
+    A.add_triplet(3, 0, -1.);
+    A.add_triplet(4, 1, -1.);
+    let A: sprs::CsMat<f64> = A.to_csc();
+
+    let b = vec![0., 1., 1., 1., 1.];
+
+    // convert to CscMatrix.  CSR inputs are transposed as needed.
+    let P = CscMatrix::try_from_sprs(&P).unwrap();
+    let A = CscMatrix::try_from_sprs(&A).unwrap();
+
+    let cones = [ZeroConeT(1), NonnegativeConeT(4)];
+
+    let settings = DefaultSettings::default();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+
+    solver.solve();
+
+    println!("Solution(x)     = {:?}", solver.solution.x);
+    println!("Multipliers (z) = {:?}", solver.solution.z);
+    println!("Slacks (s)      = {:?}", solver.solution.s);
+}
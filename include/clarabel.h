@@ -0,0 +1,188 @@
+/* Clarabel C interface.  Generated by cbindgen from src/capi, do not edit. */
+
+#ifndef CLARABEL_H
+#define CLARABEL_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// A required pointer argument was null.
+#define CLARABEL_ERR_NULL -1
+
+// A panic occurred inside the solver.
+#define CLARABEL_ERR_PANIC -2
+
+// Problem data, cones or settings were invalid, or an
+// output buffer had the wrong length.
+#define CLARABEL_ERR_DATA -3
+
+// Data updates are not allowed with the current settings.
+#define CLARABEL_ERR_UPDATE -4
+
+// Cone types accepted in [`ClarabelCone::tag`].
+typedef enum ClarabelConeTag {
+  ClarabelZeroCone = 0,
+  ClarabelNonnegativeCone = 1,
+  ClarabelSecondOrderCone = 2,
+  ClarabelExponentialCone = 3,
+  ClarabelPowerCone = 4,
+  ClarabelGenPowerCone = 5,
+  ClarabelPSDTriangleCone = 6,
+} ClarabelConeTag;
+
+// Solver status codes returned by `clarabel_solve`.  See
+// [`SolverStatus`] for descriptions of each status.
+typedef enum ClarabelStatus {
+  ClarabelUnsolved = 0,
+  ClarabelSolved = 1,
+  ClarabelPrimalInfeasible = 2,
+  ClarabelDualInfeasible = 3,
+  ClarabelAlmostSolved = 4,
+  ClarabelAlmostPrimalInfeasible = 5,
+  ClarabelAlmostDualInfeasible = 6,
+  ClarabelMaxIterations = 7,
+  ClarabelMaxTime = 8,
+  ClarabelNumericalError = 9,
+  ClarabelInsufficientProgress = 10,
+} ClarabelStatus;
+
+// Opaque solver handle returned by `clarabel_new`.
+typedef struct ClarabelSolver ClarabelSolver;
+
+// Solver settings.  See [`DefaultSettings`] for descriptions of each
+// field.   Use `clarabel_default_settings` to obtain default values.
+typedef struct ClarabelSettings {
+  uint32_t max_iter;
+  double time_limit;
+  bool verbose;
+  double max_step_fraction;
+  double tol_gap_abs;
+  double tol_gap_rel;
+  double tol_feas;
+  double tol_infeas_abs;
+  double tol_infeas_rel;
+  double tol_ktratio;
+  double reduced_tol_gap_abs;
+  double reduced_tol_gap_rel;
+  double reduced_tol_feas;
+  double reduced_tol_infeas_abs;
+  double reduced_tol_infeas_rel;
+  double reduced_tol_ktratio;
+  bool equilibrate_enable;
+  uint32_t equilibrate_max_iter;
+  double equilibrate_min_scaling;
+  double equilibrate_max_scaling;
+  bool static_regularization_enable;
+  double static_regularization_constant;
+  double static_regularization_proportional;
+  bool dynamic_regularization_enable;
+  double dynamic_regularization_eps;
+  double dynamic_regularization_delta;
+  bool iterative_refinement_enable;
+  double iterative_refinement_reltol;
+  double iterative_refinement_abstol;
+  uint32_t iterative_refinement_max_iter;
+  double iterative_refinement_stop_ratio;
+  bool presolve_enable;
+} ClarabelSettings;
+
+// Sparse matrix in compressed sparse column format.
+//
+// `colptr` has length `n+1`, and `rowval` and `nzval`
+// have length `colptr[n]`.
+typedef struct ClarabelCscMatrix {
+  size_t m;
+  size_t n;
+  const size_t *colptr;
+  const size_t *rowval;
+  const double *nzval;
+} ClarabelCscMatrix;
+
+// Plain description of a cone.
+//
+// `dim` is the cone dimension for zero, nonnegative and second order
+// cones, the matrix dimension for PSD cones, and the dimension of the
+// norm bounded part for generalized power cones.  `power` is the power
+// cone exponent, and `alpha` / `nalpha` hold the exponents of a
+// generalized power cone.   Unused fields are ignored.
+typedef struct ClarabelCone {
+  uint32_t tag;
+  size_t dim;
+  double power;
+  const double *alpha;
+  size_t nalpha;
+} ClarabelCone;
+
+// Solver information after a call to `clarabel_solve`.
+typedef struct ClarabelInfo {
+  uint32_t status;
+  uint32_t iterations;
+  double solve_time;
+  double obj_val;
+  double obj_val_dual;
+  double r_prim;
+  double r_dual;
+  double gap_abs;
+  double gap_rel;
+} ClarabelInfo;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Default solver settings.
+struct ClarabelSettings clarabel_default_settings(void);
+
+// Create a new solver for the problem with data `(P,q,A,b)`, where `q`
+// has length `P.n` and `b` has length `A.m`.
+//
+// Returns null if any pointer is null, the problem data or settings
+// are invalid, or the solver panics during setup.   The returned
+// solver must be released with `clarabel_free`.
+struct ClarabelSolver *clarabel_new(const struct ClarabelCscMatrix *P,
+                                    const double *q,
+                                    const struct ClarabelCscMatrix *A,
+                                    const double *b,
+                                    const struct ClarabelCone *cones,
+                                    size_t ncones,
+                                    const struct ClarabelSettings *settings);
+
+// Solve the problem, returning the solver status code or a
+// negative error code.
+int32_t clarabel_solve(struct ClarabelSolver *solver);
+
+// Write the number of variables and constraints of the problem.
+int32_t clarabel_get_dims(const struct ClarabelSolver *solver, size_t *n, size_t *m);
+
+// Copy the primal solution `x` into a buffer of length `len`, which
+// must equal the number of variables.
+int32_t clarabel_get_x(const struct ClarabelSolver *solver, double *x, size_t len);
+
+// Copy the dual solution `z` into a buffer of length `len`, which
+// must equal the number of constraints.
+int32_t clarabel_get_z(const struct ClarabelSolver *solver, double *z, size_t len);
+
+// Copy the slacks `s` into a buffer of length `len`, which
+// must equal the number of constraints.
+int32_t clarabel_get_s(const struct ClarabelSolver *solver, double *s, size_t len);
+
+// Write the status, objective values and residuals from the last solve.
+int32_t clarabel_get_info(const struct ClarabelSolver *solver, struct ClarabelInfo *info);
+
+// Overwrite the linear cost `q`.  Data updates require that
+// presolve is disabled in the solver settings.
+int32_t clarabel_update_q(struct ClarabelSolver *solver, const double *q, size_t len);
+
+// Overwrite the constraint right hand side `b`.  Data updates
+// require that presolve is disabled in the solver settings.
+int32_t clarabel_update_b(struct ClarabelSolver *solver, const double *b, size_t len);
+
+// Release a solver created with `clarabel_new`.  Null is ignored.
+void clarabel_free(struct ClarabelSolver *solver);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* CLARABEL_H */
@@ -0,0 +1,127 @@
+use crate::algebra::*;
+use std::iter::zip;
+
+// ---------------------------------------------------
+// Lane-blocked kernels for short dot products and axpy-style
+// loops.   Vectors are processed in blocks of LANES entries
+// with a separate accumulator per lane.   These are plain
+// scalar loops, generic over FloatT and without intrinsics,
+// written so that the compiler is free to vectorize the
+// reductions.   Remainders are handled with scalar loops.
+//
+// Elementwise kernels give results identical to the VectorMath
+// versions, and use fused multiply-add wherever VectorMath does
+// when the "fma" feature is enabled.   Reductions sum in a
+// different order, and so agree with the VectorMath versions
+// only up to rounding.
+// ---------------------------------------------------
+
+const LANES: usize = 8;
+
+// a*b + c, fused when VectorMath is
+#[inline]
+fn _muladd<T: FloatT>(a: T, b: T, c: T) -> T {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "fma")] {
+            T::fma(a, b, c)
+        } else {
+            a * b + c
+        }
+    }
+}
+
+// pairwise sum of the lane accumulators
+#[inline]
+fn _reduce<T: FloatT>(acc: [T; LANES]) -> T {
+    let a = [
+        acc[0] + acc[4],
+        acc[1] + acc[5],
+        acc[2] + acc[6],
+        acc[3] + acc[7],
+    ];
+    (a[0] + a[2]) + (a[1] + a[3])
+}
+
+/// xᵀy
+pub(crate) fn dot<T: FloatT>(x: &[T], y: &[T]) -> T {
+    assert_eq!(x.len(), y.len());
+
+    let mut acc = [T::zero(); LANES];
+    let (xb, yb) = (x.chunks_exact(LANES), y.chunks_exact(LANES));
+    let (xr, yr) = (xb.remainder(), yb.remainder());
+
+    for (xb, yb) in zip(xb, yb) {
+        for k in 0..LANES {
+            acc[k] = _muladd(xb[k], yb[k], acc[k]);
+        }
+    }
+    zip(xr, yr).fold(_reduce(acc), |out, (&x, &y)| _muladd(x, y, out))
+}
+
+/// xᵀx
+pub(crate) fn sumsq<T: FloatT>(x: &[T]) -> T {
+    dot(x, x)
+}
+
+/// (s + αds)ᵀ(z + αdz)
+pub(crate) fn dot_shifted<T: FloatT>(z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T {
+    assert_eq!(z.len(), s.len());
+    assert_eq!(z.len(), dz.len());
+    assert_eq!(s.len(), ds.len());
+
+    let mut acc = [T::zero(); LANES];
+    let blocks = z.len() / LANES * LANES;
+    let (zb, sb) = (
+        z[..blocks].chunks_exact(LANES),
+        s[..blocks].chunks_exact(LANES),
+    );
+    let (dzb, dsb) = (
+        dz[..blocks].chunks_exact(LANES),
+        ds[..blocks].chunks_exact(LANES),
+    );
+
+    for ((zb, sb), (dzb, dsb)) in zip(zip(zb, sb), zip(dzb, dsb)) {
+        for k in 0..LANES {
+            acc[k] += (sb[k] + α * dsb[k]) * (zb[k] + α * dzb[k]);
+        }
+    }
+
+    let mut out = _reduce(acc);
+    for i in blocks..z.len() {
+        out += (s[i] + α * ds[i]) * (z[i] + α * dz[i]);
+    }
+    out
+}
+
+/// y = a*x + b*y
+pub(crate) fn axpby<T: FloatT>(y: &mut [T], a: T, x: &[T], b: T) {
+    assert_eq!(y.len(), x.len());
+
+    let mut yb = y.chunks_exact_mut(LANES);
+    let mut xb = x.chunks_exact(LANES);
+    for (yb, xb) in zip(&mut yb, &mut xb) {
+        for k in 0..LANES {
+            yb[k] = _muladd(a, xb[k], b * yb[k]);
+        }
+    }
+    for (y, &x) in zip(yb.into_remainder(), xb.remainder()) {
+        *y = _muladd(a, x, b * (*y));
+    }
+}
+
+/// w = a*x + b*y
+pub(crate) fn waxpby<T: FloatT>(w: &mut [T], a: T, x: &[T], b: T, y: &[T]) {
+    assert_eq!(w.len(), x.len());
+    assert_eq!(w.len(), y.len());
+
+    let mut wb = w.chunks_exact_mut(LANES);
+    let (mut xb, mut yb) = (x.chunks_exact(LANES), y.chunks_exact(LANES));
+    for (wb, (xb, yb)) in zip(&mut wb, zip(&mut xb, &mut yb)) {
+        for k in 0..LANES {
+            wb[k] = a * xb[k] + b * yb[k];
+        }
+    }
+    for (w, (&x, &y)) in zip(wb.into_remainder(), zip(xb.remainder(), yb.remainder())) {
+        *w = a * x + b * y;
+    }
+}
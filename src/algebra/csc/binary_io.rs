@@ -0,0 +1,210 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::{CscMatrix, FloatT};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+// Binary layout, all values in native byte order:
+//
+//   magic     : 8 bytes
+//   floatsize : u64 (4 for f32, 8 for f64)
+//   m, n, nnz : u64
+//   colptr    : [u64; n+1]
+//   rowval    : [u64; nnz]
+//   nzval     : [f32 or f64; nnz]
+
+const CSC_BINARY_MAGIC: &[u8; 8] = b"CLRBCSC\0";
+
+impl<T> CscMatrix<T>
+where
+    T: FloatT,
+{
+    /// Write the matrix to a file in a compact binary format.
+    ///
+    /// Integer data is written as `u64` and floating point data
+    /// as `f32` or `f64` depending on the size of `T`, all in native
+    /// byte order.  The result can be read back using
+    /// [`read_binary`](CscMatrix::read_binary).
+    pub fn write_binary(&self, path: &str) -> io::Result<()> {
+        let floatsize = _float_size::<T>()?;
+        let mut w = BufWriter::new(File::create(path)?);
+
+        w.write_all(CSC_BINARY_MAGIC)?;
+        for v in [floatsize, self.m, self.n, self.nnz()] {
+            w.write_all(&(v as u64).to_ne_bytes())?;
+        }
+        for &v in self.colptr.iter().chain(self.rowval.iter()) {
+            w.write_all(&(v as u64).to_ne_bytes())?;
+        }
+        for &v in self.nzval.iter() {
+            if floatsize == 4 {
+                w.write_all(&v.to_f32().unwrap().to_ne_bytes())?;
+            } else {
+                w.write_all(&v.to_f64().unwrap().to_ne_bytes())?;
+            }
+        }
+        w.flush()
+    }
+
+    /// Read a matrix from a file written by [`write_binary`](CscMatrix::write_binary).
+    ///
+    /// Returns an error of kind [`InvalidData`](std::io::ErrorKind::InvalidData)
+    /// if the file header is not recognized, if the stored floating point size
+    /// does not match `T`, or if the stored matrix fails
+    /// [`check_format`](CscMatrix::check_format).
+    pub fn read_binary(path: &str) -> io::Result<CscMatrix<T>> {
+        let floatsize = _float_size::<T>()?;
+        let mut r = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != CSC_BINARY_MAGIC {
+            return Err(_invalid_data("unrecognized file header"));
+        }
+        if _read_usize(&mut r)? != floatsize {
+            return Err(_invalid_data("floating point size does not match"));
+        }
+        let m = _read_usize(&mut r)?;
+        let n = _read_usize(&mut r)?;
+        let nnz = _read_usize(&mut r)?;
+
+        let colptr = (0..=n)
+            .map(|_| _read_usize(&mut r))
+            .collect::<io::Result<Vec<usize>>>()?;
+        if colptr[n] != nnz {
+            return Err(_invalid_data("column pointers inconsistent with nnz"));
+        }
+        let rowval = (0..nnz)
+            .map(|_| _read_usize(&mut r))
+            .collect::<io::Result<Vec<usize>>>()?;
+
+        let mut nzval = Vec::with_capacity(nnz);
+        for _ in 0..nnz {
+            let v = if floatsize == 4 {
+                let mut buf = [0u8; 4];
+                r.read_exact(&mut buf)?;
+                T::from_f32(f32::from_ne_bytes(buf))
+            } else {
+                let mut buf = [0u8; 8];
+                r.read_exact(&mut buf)?;
+                T::from_f64(f64::from_ne_bytes(buf))
+            };
+            nzval.push(v.unwrap());
+        }
+
+        let A = CscMatrix::new(m, n, colptr, rowval, nzval);
+        A.check_format()
+            .map_err(|e| _invalid_data(&e.to_string()))?;
+        Ok(A)
+    }
+}
+
+fn _float_size<T>() -> io::Result<usize> {
+    match std::mem::size_of::<T>() {
+        s @ (4 | 8) => Ok(s),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "binary format supports only f32 or f64 data",
+        )),
+    }
+}
+
+fn _read_usize<R: Read>(r: &mut R) -> io::Result<usize> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    usize::try_from(u64::from_ne_bytes(buf)).map_err(|_| _invalid_data("index overflow"))
+}
+
+fn _invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[test]
+fn test_csc_binary_roundtrip() {
+    // 1000 x 1000 dense, i.e. 1M entries
+    let (m, n) = (1000, 1000);
+    let colptr: Vec<usize> = (0..=n).map(|j| j * m).collect();
+    let rowval: Vec<usize> = (0..n).flat_map(|_| 0..m).collect();
+    let nzval: Vec<f64> = (0..m * n).map(|k| (k as f64).sqrt() - 17.25).collect();
+    let A = CscMatrix::new(m, n, colptr, rowval, nzval);
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+    A.write_binary(path).unwrap();
+
+    // byte level checks on the layout
+    let bytes = std::fs::read(path).unwrap();
+    let nnz = A.nnz();
+    assert_eq!(bytes.len(), 8 + 4 * 8 + (n + 1 + nnz) * 8 + nnz * 8);
+    assert_eq!(&bytes[0..8], CSC_BINARY_MAGIC);
+    assert_eq!(bytes[8..16], 8u64.to_ne_bytes());
+    assert_eq!(bytes[32..40], (nnz as u64).to_ne_bytes());
+    let tail = &bytes[bytes.len() - 8..];
+    assert_eq!(tail, A.nzval[nnz - 1].to_ne_bytes());
+
+    let B = CscMatrix::<f64>::read_binary(path).unwrap();
+    assert_eq!(A, B);
+
+    // wrong floating point type should fail
+    let err = CscMatrix::<f32>::read_binary(path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_csc_binary_roundtrip_f32() {
+    let A = CscMatrix::<f32>::from(&[
+        [1.0, 0.0, 5.5], //
+        [0.0, -2.5, 0.0],
+    ]);
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+    A.write_binary(path).unwrap();
+
+    let bytes = std::fs::read(path).unwrap();
+    assert_eq!(bytes[8..16], 4u64.to_ne_bytes());
+    assert_eq!(bytes.len(), 8 + 4 * 8 + (4 + 3) * 8 + 3 * 4);
+
+    let B = CscMatrix::<f32>::read_binary(path).unwrap();
+    assert_eq!(A, B);
+}
+
+#[test]
+fn test_csc_binary_bad_header() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+    std::fs::write(path, b"not a matrix file").unwrap();
+
+    let err = CscMatrix::<f64>::read_binary(path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_csc_binary_corrupt_data() {
+    let A = CscMatrix::<f64>::from(&[
+        [1.0, 0.0, 5.5], //
+        [0.0, -2.5, 0.0],
+    ]);
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+    A.write_binary(path).unwrap();
+    let bytes = std::fs::read(path).unwrap();
+
+    // offsets of colptr[1] and rowval[0]
+    let colptr1 = 8 + 4 * 8 + 8;
+    let rowval0 = 8 + 4 * 8 + (A.n + 1) * 8;
+
+    // row index out of bounds
+    let mut bad = bytes.clone();
+    bad[rowval0..rowval0 + 8].copy_from_slice(&2u64.to_ne_bytes());
+    std::fs::write(path, &bad).unwrap();
+    let err = CscMatrix::<f64>::read_binary(path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // column pointers not monotone
+    let mut bad = bytes.clone();
+    bad[colptr1..colptr1 + 8].copy_from_slice(&3u64.to_ne_bytes());
+    std::fs::write(path, &bad).unwrap();
+    let err = CscMatrix::<f64>::read_binary(path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
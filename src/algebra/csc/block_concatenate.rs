@@ -1,7 +1,8 @@
+use crate::algebra::checked_sum;
 use crate::algebra::hvcat_dim_check;
 use crate::algebra::matrix_traits::ShapedMatrix;
 use crate::algebra::MatrixConcatenationError;
-use std::cmp::max;
+use core::cmp::max;
 
 use crate::algebra::{BlockConcatenate, CscMatrix, FloatT, MatrixShape};
 
@@ -18,9 +19,9 @@ where
         assert_eq!(A.m, B.m);
 
         //dimensions for C = [A B];
-        let nnz = A.nnz() + B.nnz();
+        let nnz = checked_sum([A.nnz(), B.nnz()]).expect("hcat: too many nonzeros");
         let m = A.m; //rows C
-        let n = A.n + B.n; //cols C
+        let n = checked_sum([A.n, B.n]).expect("hcat: too many columns"); //cols C
         let mut C = CscMatrix::spalloc((m, n), nnz);
 
         //we make dummy mapping indices since we don't care
@@ -45,8 +46,8 @@ where
         assert_eq!(A.n, B.n);
 
         //dimensions for C = [A; B];
-        let nnz = A.nnz() + B.nnz();
-        let m = A.m + B.m; //rows C
+        let nnz = checked_sum([A.nnz(), B.nnz()]).expect("vcat: too many nonzeros");
+        let m = checked_sum([A.m, B.m]).expect("vcat: too many rows"); //rows C
         let n = A.n; //cols C
         let mut C = CscMatrix::spalloc((m, n), nnz);
 
@@ -80,14 +81,10 @@ where
             return Err(MatrixConcatenationError::IncompatibleDimension);
         }
 
-        let mut nrows = 0;
-        let mut ncols = 0;
-        let mut nnzM = 0;
-        for mat in mats {
-            nrows += mat.nrows();
-            ncols += mat.ncols();
-            nnzM += mat.nnz();
-        }
+        let overflow = MatrixConcatenationError::DimensionOverflow;
+        let nrows = checked_sum(mats.iter().map(|mat| mat.nrows())).ok_or(overflow)?;
+        let ncols = checked_sum(mats.iter().map(|mat| mat.ncols())).ok_or(overflow)?;
+        let nnzM = checked_sum(mats.iter().map(|mat| mat.nnz())).ok_or(overflow)?;
         let mut M = CscMatrix::<T>::spalloc((nrows, ncols), nnzM);
 
         // assemble the column counts
@@ -130,18 +127,13 @@ where
 
         // dimensions are consistent and nonzero, so count
         // total rows and columns by counting along the border
-        let nrows = mats.iter().map(|blockrow| blockrow[0].nrows()).sum();
-        let ncols = mats[0].iter().map(|topblock| topblock.ncols()).sum();
-
-        let mut nnzM = 0;
-        let mut maxblocknnz = 0; // for dummy mapping below
-        for &blockrow in mats {
-            for mat in blockrow {
-                let blocknnz = mat.nnz();
-                maxblocknnz = max(maxblocknnz, blocknnz);
-                nnzM += blocknnz;
-            }
-        }
+        let overflow = MatrixConcatenationError::DimensionOverflow;
+        let nrows = checked_sum(mats.iter().map(|blockrow| blockrow[0].nrows())).ok_or(overflow)?;
+        let ncols = checked_sum(mats[0].iter().map(|topblock| topblock.ncols())).ok_or(overflow)?;
+
+        let blocks = || mats.iter().flat_map(|&blockrow| blockrow.iter());
+        let nnzM = checked_sum(blocks().map(|mat| mat.nnz())).ok_or(overflow)?;
+        let maxblocknnz = blocks().map(|mat| mat.nnz()).max().unwrap_or(0); // for dummy mapping below
 
         let mut M = CscMatrix::<T>::spalloc((nrows, ncols), nnzM);
 
@@ -187,3 +179,24 @@ where
 }
 
 //PJG: hvcat and blockdiag unittests here
+
+#[test]
+fn test_concatenate_overflow() {
+    // the dimensions of the result overflow, although
+    // neither block has any entries
+    let A = CscMatrix::<f64>::zeros((usize::MAX / 2 + 1, 1));
+    let B = CscMatrix::<f64>::zeros((usize::MAX / 2 + 1, 1));
+
+    assert!(matches!(
+        CscMatrix::blockdiag(&[&A, &B]),
+        Err(MatrixConcatenationError::DimensionOverflow)
+    ));
+    assert!(matches!(
+        CscMatrix::hvcat(&[&[&A], &[&B]]),
+        Err(MatrixConcatenationError::DimensionOverflow)
+    ));
+    assert!(std::panic::catch_unwind(|| CscMatrix::vcat(&A, &B)).is_err());
+
+    // and otherwise are accepted
+    assert_eq!(CscMatrix::hvcat(&[&[&A, &B]]).unwrap().size(), (A.m, 2));
+}
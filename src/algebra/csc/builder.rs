@@ -0,0 +1,139 @@
+use crate::algebra::{CscMatrix, FloatT, SparseFormatError};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+/// Incremental builder for a [`CscMatrix`], with the matrix
+/// data supplied one column at a time in column order.
+///
+/// Entries are written directly into the storage of the final matrix,
+/// so that large matrices can be generated on the fly without first
+/// assembling triplet arrays.   Each column is validated as it is
+/// pushed, and a column that fails validation is not added.
+///
+/// __Example usage__ : To construct the 3 x 3 matrix
+/// ```text
+/// A = [1.  3.  ⋅ ]
+///     [2.  ⋅   6.]
+///     [⋅   4.  ⋅ ]
+/// ```
+/// ```
+/// use clarabel::algebra::CscBuilder;
+///
+/// let mut builder = CscBuilder::new(3, 3);
+/// builder.push_column(&[0, 1], &[1., 2.]).unwrap();
+/// builder.push_column(&[0, 2], &[3., 4.]).unwrap();
+/// builder.push_column(&[1], &[6.]).unwrap();
+/// let A = builder.finish();
+///
+/// assert_eq!(A.nnz(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CscBuilder<T> {
+    M: CscMatrix<T>,
+    // number of columns pushed so far
+    ncols: usize,
+}
+
+impl<T> CscBuilder<T>
+where
+    T: FloatT,
+{
+    /// Builder for an `m` x `n` matrix.
+    pub fn new(m: usize, n: usize) -> Self {
+        Self::with_capacity(m, n, 0)
+    }
+
+    /// Builder for an `m` x `n` matrix, with space reserved
+    /// for `nnz` entries.
+    pub fn with_capacity(m: usize, n: usize, nnz: usize) -> Self {
+        let mut colptr = Vec::with_capacity(n + 1);
+        colptr.push(0);
+        let M = CscMatrix {
+            m,
+            n,
+            colptr,
+            rowval: Vec::with_capacity(nnz),
+            nzval: Vec::with_capacity(nnz),
+        };
+        Self { M, ncols: 0 }
+    }
+
+    /// Reserve space for at least `nnz_hint` additional entries.
+    pub fn reserve(&mut self, nnz_hint: usize) {
+        self.M.rowval.reserve(nnz_hint);
+        self.M.nzval.reserve(nnz_hint);
+    }
+
+    /// Number of columns pushed so far.
+    pub fn ncols_pushed(&self) -> usize {
+        self.ncols
+    }
+
+    /// Number of entries pushed so far.
+    pub fn nnz(&self) -> usize {
+        self.M.rowval.len()
+    }
+
+    /// Append the next column, with nonzeros `vals` in rows `rows`.
+    ///
+    /// Returns an error if the matrix already has all of its columns,
+    /// if `rows` and `vals` have different lengths, or if the row
+    /// indices are not strictly increasing and less than the number
+    /// of rows.
+    pub fn push_column(&mut self, rows: &[usize], vals: &[T]) -> Result<(), SparseFormatError> {
+        if self.ncols >= self.M.n || rows.len() != vals.len() {
+            return Err(SparseFormatError::IncompatibleDimension);
+        }
+        if rows.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(SparseFormatError::BadRowOrdering);
+        }
+        if matches!(rows.last(), Some(&r) if r >= self.M.m) {
+            return Err(SparseFormatError::BadRowval);
+        }
+
+        self.M.rowval.extend_from_slice(rows);
+        self.M.nzval.extend_from_slice(vals);
+        self.M.colptr.push(self.M.rowval.len());
+        self.ncols += 1;
+        Ok(())
+    }
+
+    /// Append column `col`, with any columns between the last column
+    /// pushed and `col` left empty.
+    ///
+    /// Returns an error if `col` has already been pushed, or for any
+    /// of the reasons given in [`push_column`](Self::push_column).
+    pub fn push_column_at(
+        &mut self,
+        col: usize,
+        rows: &[usize],
+        vals: &[T],
+    ) -> Result<(), SparseFormatError> {
+        if col < self.ncols {
+            return Err(SparseFormatError::BadColptr);
+        }
+        if col >= self.M.n {
+            return Err(SparseFormatError::IncompatibleDimension);
+        }
+
+        let skipped = col - self.ncols;
+        let nnz = self.M.rowval.len();
+        self.M.colptr.extend(core::iter::repeat(nnz).take(skipped));
+        self.ncols += skipped;
+
+        let result = self.push_column(rows, vals);
+        if result.is_err() {
+            // undo the empty columns
+            self.M.colptr.truncate(self.M.colptr.len() - skipped);
+            self.ncols -= skipped;
+        }
+        result
+    }
+
+    /// Finish the matrix, with any columns not yet pushed left empty.
+    pub fn finish(mut self) -> CscMatrix<T> {
+        let nnz = self.M.rowval.len();
+        self.M.colptr.resize(self.M.n + 1, nnz);
+        self.M
+    }
+}
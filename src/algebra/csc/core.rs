@@ -3,14 +3,24 @@
 use crate::algebra::permute;
 use crate::algebra::utils::sortperm_by;
 use crate::algebra::{Adjoint, MatrixShape, ShapedMatrix, SparseFormatError, Symmetric};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use alloc::borrow::Cow;
+use core::iter::{repeat, zip};
+use core::ops::Deref;
 use num_traits::Num;
-use std::iter::{repeat, zip};
 
 #[cfg(feature = "serde")]
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// Sparse matrix in standard Compressed Sparse Column (CSC) format
 ///
+/// The index vectors `colptr` and `rowval` are stored as `Vec<usize>`
+/// by default.   The storage type `S` can be any type dereferencing to
+/// `[usize]`, which allows the solver to hold a matrix whose sparsity
+/// pattern is borrowed from the user's data, e.g. with `S = Cow<[usize]>`.
+/// Methods that modify the sparsity pattern require `Vec` storage.
+///
 /// __Example usage__ : To construct the 3 x 3 matrix
 /// ```text
 /// A = [1.  3.  5.]
@@ -42,9 +52,12 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 ///
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[serde(bound = "T: Serialize + DeserializeOwned")]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "T: Serialize + DeserializeOwned, S: Serialize + DeserializeOwned")
+)]
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct CscMatrix<T = f64> {
+pub struct CscMatrix<T = f64, S = Vec<usize>> {
     /// number of rows
     pub m: usize,
     /// number of columns
@@ -54,9 +67,9 @@ pub struct CscMatrix<T = f64> {
     /// Ths field should have length `n+1`. The last entry corresponds
     /// to the the number of nonzeros and should agree with the lengths
     /// of the `rowval` and `nzval` fields.
-    pub colptr: Vec<usize>,
+    pub colptr: S,
     /// vector of row indices
-    pub rowval: Vec<usize>,
+    pub rowval: S,
     /// vector of non-zero matrix elements
     pub nzval: Vec<T>,
 }
@@ -144,6 +157,33 @@ where
         }
     }
 
+    /// `CscMatrix` constructor that validates the matrix data.
+    ///
+    /// Returns an error if the data is inconsistent with the matrix
+    /// dimensions, i.e. if `colptr` does not have length `n+1`, is not
+    /// monotonically nondecreasing or does not end at the number of
+    /// entries, or if any row index is not in `0..m`.   As for
+    /// [`CscMatrix::new`], entries within each column are not required
+    /// to be sorted by row index.   Use [`check_canonical`](CscMatrix::check_canonical)
+    /// to check their order.
+    pub fn new_checked(
+        m: usize,
+        n: usize,
+        colptr: Vec<usize>,
+        rowval: Vec<usize>,
+        nzval: Vec<T>,
+    ) -> Result<Self, SparseFormatError> {
+        let M = CscMatrix {
+            m,
+            n,
+            colptr,
+            rowval,
+            nzval,
+        };
+        M.check_structure()?;
+        Ok(M)
+    }
+
     /// `CscMatrix` constructor from data in triplet format.
     ///
     /// # Panics
@@ -236,6 +276,54 @@ where
         CscMatrix::new(n, n, colptr, rowval, nzval)
     }
 
+    /// `CscMatrix` constructor for a square banded matrix of size `n`.
+    ///
+    /// Each element of `bands` is a pair `(offset, values)`, with offset
+    /// `0` for the main diagonal, `-1` for the first subdiagonal, `1` for
+    /// the first superdiagonal and so on.   The values of each band are
+    /// given in order of increasing column.
+    ///
+    /// # Panics
+    /// Panics if a band has length other than `n - |offset|`, or if
+    /// an offset is repeated.
+    pub fn from_banded(n: usize, bands: &[(isize, Vec<T>)]) -> Self {
+        for (offset, values) in bands {
+            assert_eq!(values.len(), n.saturating_sub(offset.unsigned_abs()));
+        }
+
+        // bands in order of decreasing offset, i.e. with
+        // row indices increasing within each column
+        let mut order: Vec<usize> = (0..bands.len()).collect();
+        order.sort_by_key(|&k| core::cmp::Reverse(bands[k].0));
+        for pair in order.windows(2) {
+            assert_ne!(bands[pair[0]].0, bands[pair[1]].0, "repeated band offset");
+        }
+
+        let nnz = bands.iter().map(|(_, values)| values.len()).sum();
+        let mut colptr = Vec::with_capacity(n + 1);
+        let mut rowval = Vec::with_capacity(nnz);
+        let mut nzval = Vec::with_capacity(nnz);
+
+        colptr.push(0);
+        for col in 0..n {
+            for &k in order.iter() {
+                let (offset, values) = &bands[k];
+                let row = col as isize - offset;
+                if row < 0 || row >= n as isize {
+                    continue;
+                }
+                // bands are indexed by row above the
+                // diagonal, and by column below it
+                let idx = if *offset >= 0 { row as usize } else { col };
+                rowval.push(row as usize);
+                nzval.push(values[idx]);
+            }
+            colptr.push(rowval.len());
+        }
+
+        CscMatrix::new(n, n, colptr, rowval, nzval)
+    }
+
     /// squeeze out entries that are == T::zero()
     pub fn dropzeros(&mut self) {
         // this function could possibly be generalized to allow filtering
@@ -272,11 +360,98 @@ where
         self.nzval.resize(writeidx, T::zero());
     }
 
+    /// Sort the entries of each column by row index and sum any entries
+    /// with the same row and column, so that the matrix passes
+    /// [`check_canonical`](Self::check_canonical).   Entries that are
+    /// explicitly zero are kept.   Returns the number of entries merged
+    /// into another.
+    ///
+    /// The matrix should otherwise be correctly formatted.
+    pub fn canonicalize(&mut self) -> usize {
+        let mut column: Vec<(usize, T)> = Vec::new();
+        let mut merged = 0;
+        let mut nnz = 0;
+        let mut first = self.colptr[0];
+
+        for col in 0..self.n {
+            let last = self.colptr[col + 1];
+            column.clear();
+            let entries = zip(&self.rowval[first..last], &self.nzval[first..last]);
+            column.extend(entries.map(|(&row, &v)| (row, v)));
+
+            // the sort is stable, so that repeated entries are
+            // summed in the order they were supplied
+            column.sort_by_key(|&(row, _)| row);
+
+            // entries are written back no further along than they
+            // were read, since entries are only ever removed
+            let start = nnz;
+            for &(row, v) in &column {
+                if nnz > start && self.rowval[nnz - 1] == row {
+                    self.nzval[nnz - 1] = self.nzval[nnz - 1] + v;
+                    merged += 1;
+                } else {
+                    self.rowval[nnz] = row;
+                    self.nzval[nnz] = v;
+                    nnz += 1;
+                }
+            }
+            first = last;
+            self.colptr[col + 1] = nnz;
+        }
+        self.colptr[0] = 0;
+        self.rowval.truncate(nnz);
+        self.nzval.truncate(nnz);
+        merged
+    }
+
+    /// Sets a value at a given (row,col) index, allocating
+    /// additional space in the matrix if required.  
+    ///
+    /// # Panics
+    /// Panics if the given index is out of bounds.
+    pub fn set_entry(&mut self, idx: (usize, usize), value: T) {
+        let (row, col) = idx;
+        assert!(row < self.nrows() && col < self.ncols());
+
+        let first = self.colptr[col];
+        let last = self.colptr[col + 1];
+        let rows_in_this_column = &self.rowval[first..last];
+
+        let i = rows_in_this_column.partition_point(|&x| x < row);
+
+        if i == rows_in_this_column.len() || rows_in_this_column[i] != row {
+            // don't allocate space for insertion of new zeros
+            if value == T::zero() {
+                return;
+            }
+
+            // the element must be inserted, then col counts rebuilt
+            self.rowval.insert(first + i, row);
+            self.nzval.insert(first + i, value);
+
+            // a bit wasteful since we only really need to
+            // rebuil from the insertion point onwards
+            self.colptr_to_colcount();
+            self.colptr[col] += 1;
+            self.colcount_to_colptr();
+        } else {
+            // the element already exists, so overwrite it
+            self.nzval[first + i] = value;
+        }
+    }
+}
+
+impl<T, S> CscMatrix<T, S>
+where
+    T: Num + Copy,
+    S: Deref<Target = [usize]>,
+{
     /// Return matrix data in triplet format.
     ///
     #[cfg_attr(not(feature = "sdp"), allow(dead_code))]
     pub(crate) fn findnz(&self) -> (Vec<usize>, Vec<usize>, Vec<T>) {
-        let I = self.rowval.clone();
+        let I = self.rowval.to_vec();
         let mut J = Vec::with_capacity(self.nnz());
         let V = self.nzval.clone();
 
@@ -292,11 +467,71 @@ where
         self.colptr[self.n]
     }
 
+    // bytes used by the colptr, rowval and nzval vectors
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.nnz() * (core::mem::size_of::<T>() + core::mem::size_of::<usize>())
+            + (self.n + 1) * core::mem::size_of::<usize>()
+    }
+
+    /// Number of structural nonzeros in each column.
+    ///
+    /// Computed from `colptr` in O(n) time, but allocates a new vector.
+    pub fn column_sparsity(&self) -> Vec<usize> {
+        self.colptr.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Number of structural nonzeros in each row.
+    ///
+    /// Requires a pass over all entries, and allocates a new vector.
+    pub fn row_sparsity(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.m];
+        for &row in &self.rowval[0..self.nnz()] {
+            counts[row] += 1;
+        }
+        counts
+    }
+
+    /// Largest number of structural nonzeros in any column, or zero
+    /// if the matrix has no columns.
+    pub fn max_column_density(&self) -> usize {
+        self.colptr
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Smallest number of structural nonzeros in any column, or zero
+    /// if the matrix has no columns.
+    pub fn min_column_density(&self) -> usize {
+        self.colptr
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .min()
+            .unwrap_or(0)
+    }
+
     /// transpose
     pub fn t(&self) -> Adjoint<'_, Self> {
         Adjoint { src: self }
     }
 
+    /// Explicit transpose of the matrix, together with the position in
+    /// the transpose of each entry, i.e. `At.nzval[map[k]] == self.nzval[k]`
+    /// for each `k`.   The map can be used to update the values of
+    /// the transpose after the values of the matrix change.
+    pub fn transpose_with_map(&self) -> (CscMatrix<T>, Vec<usize>) {
+        let (m, n) = (self.n, self.m);
+        let mut At = CscMatrix::spalloc((m, n), self.nnz());
+        let mut map = vec![0usize; self.nnz()];
+
+        At.colcount_block(self, 0, MatrixShape::T);
+        At.colcount_to_colptr();
+        At.fill_block(self, &mut map, 0, 0, MatrixShape::T);
+        At.backshift_colptrs();
+        (At, map)
+    }
+
     /// symmetric view
     pub fn sym(&self) -> Symmetric<'_, Self> {
         debug_assert!(self.is_triu());
@@ -305,6 +540,23 @@ where
 
     /// Check that matrix data is correctly formatted.
     pub fn check_format(&self) -> Result<(), SparseFormatError> {
+        self.check_structure()?;
+
+        //check for rowval monotonicity within each column
+        for col in 0..self.n {
+            let rng = self.colptr[col]..self.colptr[col + 1];
+            if self.rowval[rng].windows(2).any(|c| c[0] >= c[1]) {
+                return Err(SparseFormatError::BadRowval);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Check that the matrix data is consistent with its dimensions, so
+    // that it can be indexed safely, without checking the order of rows
+    // within each column.
+    pub(crate) fn check_structure(&self) -> Result<(), SparseFormatError> {
         if self.rowval.len() != self.nzval.len() {
             return Err(SparseFormatError::IncompatibleDimension);
         }
@@ -321,13 +573,6 @@ where
             return Err(SparseFormatError::BadColptr);
         }
 
-        //check for rowval monotonicity within each column
-        for col in 0..self.n {
-            let rng = self.colptr[col]..self.colptr[col + 1];
-            if self.rowval[rng].windows(2).any(|c| c[0] >= c[1]) {
-                return Err(SparseFormatError::BadRowval);
-            }
-        }
         //check for row values out of bounds
         if !self.rowval.iter().all(|r| r < &self.m) {
             return Err(SparseFormatError::BadRowval);
@@ -335,17 +580,49 @@ where
 
         Ok(())
     }
+
+    /// Check that the entries of each column are sorted by row index
+    /// and that no entry is repeated.   The matrix should otherwise be
+    /// correctly formatted.   Unsorted columns are reported ahead of
+    /// repeated entries.
+    pub fn check_canonical(&self) -> Result<(), SparseFormatError> {
+        let mut result = Ok(());
+        for col in 0..self.n {
+            let rows = &self.rowval[self.colptr[col]..self.colptr[col + 1]];
+            for c in rows.windows(2) {
+                if c[0] > c[1] {
+                    return Err(SparseFormatError::BadRowOrdering);
+                }
+                if c[0] == c[1] {
+                    result = Err(SparseFormatError::DuplicateEntries);
+                }
+            }
+        }
+        result
+    }
+
     /// True if matrices if the same size and sparsity pattern
-    pub fn is_equal_sparsity(&self, other: &Self) -> bool {
-        self.size() == other.size() && self.colptr == other.colptr && self.rowval == other.rowval
+    pub fn is_equal_sparsity<S2>(&self, other: &CscMatrix<T, S2>) -> bool
+    where
+        S2: Deref<Target = [usize]>,
+    {
+        self.size() == other.size()
+            && *self.colptr == *other.colptr
+            && *self.rowval == *other.rowval
     }
 
     /// Same as is_equal_sparsity, but returns an error indicating the reason
     /// for failure if the matrices do not have equivalent sparsity patterns.
-    pub fn check_equal_sparsity(&self, other: &Self) -> Result<(), SparseFormatError> {
+    pub fn check_equal_sparsity<S2>(
+        &self,
+        other: &CscMatrix<T, S2>,
+    ) -> Result<(), SparseFormatError>
+    where
+        S2: Deref<Target = [usize]>,
+    {
         if self.size() != other.size() {
             Err(SparseFormatError::IncompatibleDimension)
-        } else if self.colptr != other.colptr || self.rowval != other.rowval {
+        } else if *self.colptr != *other.colptr || *self.rowval != *other.rowval {
             Err(SparseFormatError::SparsityMismatch)
         } else {
             Ok(())
@@ -356,7 +633,7 @@ where
     ///
     /// # Panics
     /// Panics if row dimensions are incompatible
-    pub fn select_rows(&self, rowidx: &Vec<bool>) -> Self {
+    pub fn select_rows(&self, rowidx: &Vec<bool>) -> CscMatrix<T> {
         //first check for compatible row dimensions
         assert_eq!(rowidx.len(), self.m);
 
@@ -396,7 +673,7 @@ where
     }
 
     /// Allocates a new matrix containing only entries from the upper triangular part
-    pub fn to_triu(&self) -> Self {
+    pub fn to_triu(&self) -> CscMatrix<T> {
         assert_eq!(self.m, self.n);
         let (m, n) = (self.m, self.n);
         let mut colptr = vec![0; n + 1];
@@ -482,42 +759,6 @@ where
         }
     }
 
-    /// Sets a value at a given (row,col) index, allocating
-    /// additional space in the matrix if required.  
-    ///
-    /// # Panics
-    /// Panics if the given index is out of bounds.
-    pub fn set_entry(&mut self, idx: (usize, usize), value: T) {
-        let (row, col) = idx;
-        assert!(row < self.nrows() && col < self.ncols());
-
-        let first = self.colptr[col];
-        let last = self.colptr[col + 1];
-        let rows_in_this_column = &self.rowval[first..last];
-
-        let i = rows_in_this_column.partition_point(|&x| x < row);
-
-        if i == rows_in_this_column.len() || rows_in_this_column[i] != row {
-            // don't allocate space for insertion of new zeros
-            if value == T::zero() {
-                return;
-            }
-
-            // the element must be inserted, then col counts rebuilt
-            self.rowval.insert(first + i, row);
-            self.nzval.insert(first + i, value);
-
-            // a bit wasteful since we only really need to
-            // rebuil from the insertion point onwards
-            self.colptr_to_colcount();
-            self.colptr[col] += 1;
-            self.colcount_to_colptr();
-        } else {
-            // the element already exists, so overwrite it
-            self.nzval[first + i] = value;
-        }
-    }
-
     /// Returns the (row,col) coordinates of the given linear index.
     ///
     /// # Panics
@@ -530,7 +771,7 @@ where
     }
 }
 
-impl<T> ShapedMatrix for CscMatrix<T> {
+impl<T, S> ShapedMatrix for CscMatrix<T, S> {
     fn nrows(&self) -> usize {
         self.m
     }
@@ -548,6 +789,34 @@ impl<T> ShapedMatrix for CscMatrix<T> {
     }
 }
 
+/// Make a [CscMatrix] with [`Cow`] index storage that owns the indices
+/// of `M`, without copying them.
+impl<'a, T> From<CscMatrix<T>> for CscMatrix<T, Cow<'a, [usize]>> {
+    fn from(M: CscMatrix<T>) -> Self {
+        CscMatrix {
+            m: M.m,
+            n: M.n,
+            colptr: Cow::Owned(M.colptr),
+            rowval: Cow::Owned(M.rowval),
+            nzval: M.nzval,
+        }
+    }
+}
+
+/// Make a [CscMatrix] with `Vec` index storage from one with [`Cow`]
+/// index storage, copying the indices only if they are borrowed.
+impl<'a, T> From<CscMatrix<T, Cow<'a, [usize]>>> for CscMatrix<T> {
+    fn from(M: CscMatrix<T, Cow<'a, [usize]>>) -> Self {
+        CscMatrix {
+            m: M.m,
+            n: M.n,
+            colptr: M.colptr.into_owned(),
+            rowval: M.rowval.into_owned(),
+            nzval: M.nzval,
+        }
+    }
+}
+
 /// Make a concrete [CscMatrix] from its [Adjoint].   This operation will
 /// allocate a new matrix and copy the data from the adjoint.
 ///
@@ -573,25 +842,13 @@ impl<T> ShapedMatrix for CscMatrix<T> {
 /// assert_eq!(A, B);
 ///
 /// ```
-impl<'a, T> From<Adjoint<'a, CscMatrix<T>>> for CscMatrix<T>
+impl<'a, T, S> From<Adjoint<'a, CscMatrix<T, S>>> for CscMatrix<T>
 where
     T: Num + Copy,
+    S: Deref<Target = [usize]>,
 {
-    fn from(M: Adjoint<'a, CscMatrix<T>>) -> CscMatrix<T> {
-        let src = M.src;
-
-        let (m, n) = (src.n, src.m);
-        let mut A = CscMatrix::spalloc((m, n), src.nnz());
-
-        //make dummy mapping indices since we don't care
-        //where the entries go
-        let mut amap = vec![0usize; src.nnz()];
-
-        A.colcount_block(src, 0, MatrixShape::T);
-        A.colcount_to_colptr();
-        A.fill_block(src, &mut amap, 0, 0, MatrixShape::T);
-        A.backshift_colptrs();
-        A
+    fn from(M: Adjoint<'a, CscMatrix<T, S>>) -> CscMatrix<T> {
+        M.src.transpose_with_map().0
     }
 }
 
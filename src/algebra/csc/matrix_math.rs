@@ -1,25 +1,37 @@
 use crate::algebra::*;
-use std::iter::zip;
+use core::iter::zip;
+use core::ops::Deref;
 
-impl<T: FloatT> MatrixVectorMultiply<T> for CscMatrix<T> {
+impl<T: FloatT, S: Deref<Target = [usize]>> MatrixVectorMultiply<T> for CscMatrix<T, S> {
+    // computed serially, since a parallel product by columns would
+    // need a workspace for each thread.   Products in parallel are
+    // computed as transposed products with an explicit transpose
     fn gemv(&self, y: &mut [T], x: &[T], a: T, b: T) {
         _csc_axpby_N(self, y, x, a, b);
     }
 }
 
-impl<T: FloatT> MatrixVectorMultiply<T> for Adjoint<'_, CscMatrix<T>> {
+impl<T: FloatT, S: Deref<Target = [usize]>> MatrixVectorMultiply<T>
+    for Adjoint<'_, CscMatrix<T, S>>
+{
     fn gemv(&self, y: &mut [T], x: &[T], a: T, b: T) {
+        #[cfg(feature = "parallel")]
+        if _use_parallel_gemv(self.src) {
+            return _csc_axpby_T_par(self.src, y, x, a, b);
+        }
         _csc_axpby_T(self.src, y, x, a, b);
     }
 }
 
-impl<T: FloatT> SymMatrixVectorMultiply<T> for Symmetric<'_, CscMatrix<T>> {
+impl<T: FloatT, S: Deref<Target = [usize]>> SymMatrixVectorMultiply<T>
+    for Symmetric<'_, CscMatrix<T, S>>
+{
     fn symv(&self, y: &mut [T], x: &[T], a: T, b: T) {
         _csc_symv_unsafe(self.src, y, x, a, b);
     }
 }
 
-impl<T: FloatT> MatrixMath<T> for CscMatrix<T> {
+impl<T: FloatT, S: Deref<Target = [usize]>> MatrixMath<T> for CscMatrix<T, S> {
     fn col_sums(&self, sums: &mut [T]) {
         assert_eq!(self.n, sums.len());
         for (col, sum) in sums.iter_mut().enumerate() {
@@ -31,7 +43,7 @@ impl<T: FloatT> MatrixMath<T> for CscMatrix<T> {
     fn row_sums(&self, sums: &mut [T]) {
         assert_eq!(self.m, sums.len());
         sums.fill(T::zero());
-        for (&row, &val) in zip(&self.rowval, &self.nzval) {
+        for (&row, &val) in zip(self.rowval.iter(), &self.nzval) {
             sums[row] += val;
         }
     }
@@ -44,6 +56,11 @@ impl<T: FloatT> MatrixMath<T> for CscMatrix<T> {
     fn col_norms_no_reset(&self, norms: &mut [T]) {
         assert_eq!(norms.len(), self.colptr.len() - 1);
 
+        #[cfg(feature = "parallel")]
+        if _use_parallel_gemv(self) {
+            return _csc_col_norms_par(self, norms);
+        }
+
         for (i, v) in norms.iter_mut().enumerate() {
             *v = self
                 .nzval
@@ -80,7 +97,13 @@ impl<T: FloatT> MatrixMath<T> for CscMatrix<T> {
     fn row_norms_no_reset(&self, norms: &mut [T]) {
         assert_eq!(self.rowval.len(), *self.colptr.last().unwrap());
 
-        for (row, val) in zip(&self.rowval, &self.nzval) {
+        #[cfg(feature = "parallel")]
+        if _use_parallel_gemv(self) {
+            let nchunks = rayon::current_num_threads();
+            return _csc_row_norms_par(self, norms, nchunks);
+        }
+
+        for (row, val) in zip(self.rowval.iter(), &self.nzval) {
             norms[*row] = T::max(norms[*row], T::abs(*val));
         }
     }
@@ -90,7 +113,7 @@ impl<T: FloatT> MatrixMath<T> for CscMatrix<T> {
     }
 }
 
-impl<T: FloatT> MatrixMathMut<T> for CscMatrix<T> {
+impl<T: FloatT, S: Deref<Target = [usize]>> MatrixMathMut<T> for CscMatrix<T, S> {
     //scalar mut operations
     fn scale(&mut self, c: T) {
         self.nzval.scale(c);
@@ -101,7 +124,7 @@ impl<T: FloatT> MatrixMathMut<T> for CscMatrix<T> {
     }
 
     fn lscale(&mut self, l: &[T]) {
-        for (val, row) in zip(&mut self.nzval, &self.rowval) {
+        for (val, row) in zip(&mut self.nzval, self.rowval.iter()) {
             *val *= l[*row];
         }
     }
@@ -119,6 +142,12 @@ impl<T: FloatT> MatrixMathMut<T> for CscMatrix<T> {
     fn lrscale(&mut self, l: &[T], r: &[T]) {
         assert_eq!(self.nzval.len(), *self.colptr.last().unwrap());
 
+        #[cfg(feature = "parallel")]
+        if _use_parallel_gemv(self) {
+            let nchunks = rayon::current_num_threads();
+            return _csc_lrscale_par(self, l, r, nchunks);
+        }
+
         for (col, &ri) in r.iter().enumerate() {
             let (first, last) = (self.colptr[col], self.colptr[col + 1]);
             let vals = &mut self.nzval[first..last];
@@ -131,8 +160,206 @@ impl<T: FloatT> MatrixMathMut<T> for CscMatrix<T> {
     }
 }
 
+impl<T: FloatT, S: Deref<Target = [usize]>> CscMatrix<T, S> {
+    /// Compute `x^T*A*y` for the matrix `A = self` in a single pass
+    /// over its nonzeros, without forming `A*y`.
+    pub fn quadratic_form(&self, x: &[T], y: &[T]) -> T {
+        _csc_quadratic_form(self, x, y, false)
+    }
+
+    /// Compute `x^T*A*y` for a symmetric matrix `A` with only one
+    /// triangle of `self` stored.   Either the upper or lower triangle
+    /// may be used, and each off-diagonal entry is counted twice.
+    pub fn quadratic_form_sym(&self, x: &[T], y: &[T]) -> T {
+        assert_eq!(self.m, self.n);
+        _csc_quadratic_form(self, x, y, true)
+    }
+
+    /// BLAS-like symmetric matrix-vector multiply.  Produces
+    /// `y = a*A*x + b*y` for the symmetric matrix `A` whose upper or
+    /// lower triangle, as given by `uplo`, is stored in `self`.   Entries
+    /// in the other triangle are ignored.
+    ///
+    /// ```
+    /// use clarabel::algebra::{CscMatrix, MatrixTriangle};
+    ///
+    /// // upper triangle of [2. 1.; 1. 3.]
+    /// let A = CscMatrix::new(2, 2, vec![0, 1, 3], vec![0, 0, 1], vec![2., 1., 3.]);
+    ///
+    /// let mut y = vec![1., 1.];
+    /// A.symv(&mut y, &[1., 2.], 1., 2., MatrixTriangle::Triu);
+    /// assert_eq!(y, vec![6., 9.]);
+    ///
+    /// // the explicit transpose holds the lower triangle
+    /// let At: CscMatrix = A.t().into();
+    /// At.symv(&mut y, &[1., 2.], 1., 0., MatrixTriangle::Tril);
+    /// assert_eq!(y, vec![4., 7.]);
+    /// ```
+    pub fn symv(&self, y: &mut [T], x: &[T], a: T, b: T, uplo: MatrixTriangle) {
+        _csc_symv_triangle(self, y, x, a, b, uplo);
+    }
+
+    /// Scale each column of the matrix to unit infinity norm.  On
+    /// return, `norms` holds the applied column scaling, i.e. the
+    /// reciprocals of the original column norms, with a unit scaling
+    /// for empty or zero columns.
+    pub fn equilibrate_cols(&mut self, norms: &mut [T]) {
+        self.col_norms(norms);
+        _recip_or_one(norms);
+        self.rscale(norms);
+    }
+
+    /// Scale each row of the matrix to unit infinity norm.  On
+    /// return, `norms` holds the applied row scaling, i.e. the
+    /// reciprocals of the original row norms, with a unit scaling
+    /// for empty or zero rows.
+    pub fn equilibrate_rows(&mut self, norms: &mut [T]) {
+        self.row_norms(norms);
+        _recip_or_one(norms);
+        self.lscale(norms);
+    }
+
+    /// Same as `lrscale(l, r)` followed by `col_norms(col_norms)` and
+    /// `row_norms(row_norms)`, with identical results, but in a single
+    /// pass over the nonzeros.   The norms are those of the scaled matrix.
+    pub fn lrscale_with_norms(
+        &mut self,
+        l: &[T],
+        r: &[T],
+        col_norms: &mut [T],
+        row_norms: &mut [T],
+    ) {
+        assert_eq!(self.nzval.len(), *self.colptr.last().unwrap());
+        assert_eq!(l.len(), self.m);
+        assert_eq!(r.len(), self.n);
+        assert_eq!(col_norms.len(), self.n);
+        assert_eq!(row_norms.len(), self.m);
+
+        #[cfg(feature = "parallel")]
+        if _use_parallel_gemv(self) {
+            let nchunks = rayon::current_num_threads();
+            return _csc_lrscale_with_norms_par(self, l, r, col_norms, row_norms, nchunks);
+        }
+
+        row_norms.fill(T::zero());
+        for (col, (&ri, cnorm)) in zip(r, col_norms.iter_mut()).enumerate() {
+            let (first, last) = (self.colptr[col], self.colptr[col + 1]);
+            let vals = &mut self.nzval[first..last];
+            let rows = &self.rowval[first..last];
+
+            let mut norm = T::zero();
+            for (val, &row) in zip(vals, rows) {
+                *val *= l[row] * ri;
+                let absval = T::abs(*val);
+                norm = T::max(norm, absval);
+                row_norms[row] = T::max(row_norms[row], absval);
+            }
+            *cnorm = norm;
+        }
+    }
+
+    /// True if the matrix is square and symmetric to within a relative
+    /// tolerance, i.e. if `|A[i,j] - A[j,i]| ≤ tol⋅max(|A[i,j]|,|A[j,i]|)`
+    /// for all `i` and `j`.   Entries missing from the sparsity pattern
+    /// are treated as zeros, and duplicate entries are summed.
+    pub fn is_symmetric(&self, tol: T) -> bool {
+        self.is_square() && self.first_asymmetric_entry(tol).is_none()
+    }
+
+    /// The first entry `(row,col)` below the diagonal, in column major
+    /// order, that differs from its transposed entry by more than the
+    /// tolerance of [`is_symmetric`](Self::is_symmetric), or `None` if
+    /// the matrix is symmetric.   The matrix must be square.
+    pub fn first_asymmetric_entry(&self, tol: T) -> Option<(usize, usize)> {
+        assert!(self.is_square());
+        let At: CscMatrix<T> = self.t().into();
+
+        // column and row j of A, scattered into dense vectors
+        let n = self.n;
+        let mut col = vec![T::zero(); n];
+        let mut row = vec![T::zero(); n];
+
+        for j in 0..n {
+            let rngA = self.colptr[j]..self.colptr[j + 1];
+            let rngAt = At.colptr[j]..At.colptr[j + 1];
+            let touched = || {
+                self.rowval[rngA.clone()]
+                    .iter()
+                    .chain(&At.rowval[rngAt.clone()])
+            };
+
+            for k in rngA.clone() {
+                col[self.rowval[k]] += self.nzval[k];
+            }
+            for k in rngAt.clone() {
+                row[At.rowval[k]] += At.nzval[k];
+            }
+
+            // each pair is checked once, in the column of its lower entry
+            let first = touched()
+                .filter(|&&i| i > j)
+                .filter(|&&i| {
+                    let (a, b) = (col[i], row[i]);
+                    T::abs(a - b) > tol * T::max(T::abs(a), T::abs(b))
+                })
+                .min();
+            if let Some(&i) = first {
+                return Some((i, j));
+            }
+
+            for &i in touched() {
+                (col[i], row[i]) = (T::zero(), T::zero());
+            }
+        }
+        None
+    }
+}
+
+fn _recip_or_one<T: FloatT>(v: &mut [T]) {
+    v.iter_mut().for_each(|x| {
+        *x = if *x == T::zero() { T::one() } else { x.recip() };
+    });
+}
+
 #[allow(non_snake_case)]
-fn _csc_symv_safe<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
+fn _csc_quadratic_form<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    x: &[T],
+    y: &[T],
+    sym: bool,
+) -> T {
+    assert_eq!(x.len(), A.m);
+    assert_eq!(y.len(), A.n);
+    assert_eq!(A.nzval.len(), *A.colptr.last().unwrap());
+
+    let mut out = T::zero();
+
+    for (col, &ycol) in y.iter().enumerate() {
+        let first = A.colptr[col];
+        let last = A.colptr[col + 1];
+        let rows = &A.rowval[first..last];
+        let nzvals = &A.nzval[first..last];
+
+        for (&row, &Aij) in zip(rows, nzvals) {
+            out += Aij * x[row] * ycol;
+
+            if sym && row != col {
+                //symmetric entry A[col,row]
+                out += Aij * x[col] * y[row];
+            }
+        }
+    }
+    out
+}
+
+#[allow(non_snake_case)]
+fn _csc_symv_safe<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    a: T,
+    b: T,
+) {
     y.scale(b);
 
     assert!(x.len() == A.n);
@@ -156,6 +383,46 @@ fn _csc_symv_safe<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T)
     }
 }
 
+// as _csc_symv_safe, but using only the entries in one triangle of A
+fn _csc_symv_triangle<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    a: T,
+    b: T,
+    uplo: MatrixTriangle,
+) {
+    y.scale(b);
+
+    assert!(x.len() == A.n);
+    assert!(y.len() == A.n);
+    assert!(A.n == A.m);
+
+    for (col, &xcol) in x.iter().enumerate() {
+        let first = A.colptr[col];
+        let last = A.colptr[col + 1];
+        let rows = &A.rowval[first..last];
+        let nzvals = &A.nzval[first..last];
+
+        for (&row, &Aij) in zip(rows, nzvals) {
+            let stored = match uplo {
+                MatrixTriangle::Triu => row <= col,
+                MatrixTriangle::Tril => row >= col,
+            };
+            if !stored {
+                continue;
+            }
+
+            y[row] += a * Aij * xcol;
+
+            if row != col {
+                //don't double up on the diagonal
+                y[col] += a * Aij * x[row];
+            }
+        }
+    }
+}
+
 // Safety: The function below checks that x and y are compatible with
 // the dimensions of A, so safety is assured so long as the the matrix
 // A as rowval and colptr arrays that are consistent with its dimension.
@@ -165,7 +432,13 @@ fn _csc_symv_safe<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T)
 // the symmetric KKT matrix, and is used heavily in iterative refinement of
 // direct linear solves.
 #[allow(non_snake_case)]
-fn _csc_symv_unsafe<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
+fn _csc_symv_unsafe<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    a: T,
+    b: T,
+) {
     y.scale(b);
 
     assert!(x.len() == A.n);
@@ -189,7 +462,11 @@ fn _csc_symv_unsafe<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b:
 
 #[allow(non_snake_case)]
 #[allow(clippy::comparison_chain)]
-fn _csc_quad_form<T: FloatT>(M: &CscMatrix<T>, y: &[T], x: &[T]) -> T {
+fn _csc_quad_form<T: FloatT>(
+    M: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &[T],
+    x: &[T],
+) -> T {
     assert_eq!(M.n, M.m);
     assert_eq!(x.len(), M.n);
     assert_eq!(y.len(), M.n);
@@ -231,10 +508,25 @@ fn _csc_quad_form<T: FloatT>(M: &CscMatrix<T>, y: &[T], x: &[T]) -> T {
     out
 }
 
-// sparse matrix-vector multiply, no transpose
-#[allow(non_snake_case)]
-fn _csc_axpby_N<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
-    //first do the b*y part
+// Dispatch a sparse matrix-vector kernel on the value of `a`, passing it
+// the scaled product of a matrix entry with an entry of x.   Each case is
+// a separate closure type, so the kernel is monomorphized for a = ±1 and
+// the test on `a` is hoisted out of its loops.   The products are the
+// same as `A[i,j]*x[j]`, `-(A[i,j]*x[j])` and `a*A[i,j]*x[j]` respectively.
+macro_rules! _dispatch_on_a {
+    ($kernel:ident, $A:expr, $y:expr, $x:expr, $a:expr) => {
+        if $a == T::one() {
+            $kernel($A, $y, $x, |v: T, xk: T| v * xk)
+        } else if $a == -T::one() {
+            $kernel($A, $y, $x, |v: T, xk: T| -(v * xk))
+        } else {
+            let a = $a;
+            $kernel($A, $y, $x, |v: T, xk: T| a * v * xk)
+        }
+    };
+}
+
+fn _scale_by_b<T: FloatT>(y: &mut [T], b: T) {
     if b == T::zero() {
         y.fill(T::zero());
     } else if b == T::one() {
@@ -243,6 +535,19 @@ fn _csc_axpby_N<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
     } else {
         y.scale(b);
     }
+}
+
+// sparse matrix-vector multiply, no transpose
+#[allow(non_snake_case)]
+pub(crate) fn _csc_axpby_N<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    a: T,
+    b: T,
+) {
+    //first do the b*y part
+    _scale_by_b(y, b);
 
     // if a is zero, we're done
     if a == T::zero() {
@@ -250,42 +555,78 @@ fn _csc_axpby_N<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
     }
 
     assert_eq!(A.nzval.len(), *A.colptr.last().unwrap());
+    assert_eq!(A.colptr.len(), A.n + 1);
     assert_eq!(x.len(), A.n);
+    assert!(y.len() >= A.m);
 
     //y += A*x
-    if a == T::one() {
-        for (j, xj) in x.iter().enumerate().take(A.n) {
-            for i in A.colptr[j]..A.colptr[j + 1] {
-                y[A.rowval[i]] += A.nzval[i] * *xj;
-            }
+    _dispatch_on_a!(_csc_axpby_N_blocked, A, y, x, a);
+}
+
+// Safety: x and y are checked above against the dimensions of A, so the
+// unchecked accesses below are safe so long as the row indices of A are
+// consistent with its dimensions, as for `_csc_symv_unsafe`.
+//
+// Columns are processed four at a time, with the column bounds and the
+// entries of x for the whole block loaded ahead of its updates, so that
+// the per column loop overhead is amortized on matrices with short
+// columns.   Updates are still made one column at a time and in column
+// order, so each y[i] accumulates its terms in the same order as in an
+// unblocked product and the result is identical to it.
+#[allow(non_snake_case)]
+#[inline(always)]
+fn _csc_axpby_N_blocked<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    term: impl Fn(T, T) -> T,
+) {
+    let (colptr, rowval, nzval) = (&A.colptr, &A.rowval, &A.nzval);
+
+    let column = |y: &mut [T], first: usize, last: usize, xj: T| unsafe {
+        for k in first..last {
+            let row = *rowval.get_unchecked(k);
+            *y.get_unchecked_mut(row) += term(*nzval.get_unchecked(k), xj);
         }
-    } else if a == -T::one() {
-        for (j, xj) in x.iter().enumerate().take(A.n) {
-            for i in A.colptr[j]..A.colptr[j + 1] {
-                y[A.rowval[i]] -= A.nzval[i] * *xj;
-            }
+    };
+
+    let nblocked = A.n - A.n % 4;
+    unsafe {
+        let mut c0 = *colptr.get_unchecked(0);
+        for j in (0..nblocked).step_by(4) {
+            let c1 = *colptr.get_unchecked(j + 1);
+            let c2 = *colptr.get_unchecked(j + 2);
+            let c3 = *colptr.get_unchecked(j + 3);
+            let c4 = *colptr.get_unchecked(j + 4);
+            let x0 = *x.get_unchecked(j);
+            let x1 = *x.get_unchecked(j + 1);
+            let x2 = *x.get_unchecked(j + 2);
+            let x3 = *x.get_unchecked(j + 3);
+            column(y, c0, c1, x0);
+            column(y, c1, c2, x1);
+            column(y, c2, c3, x2);
+            column(y, c3, c4, x3);
+            c0 = c4;
         }
-    } else {
-        for (j, xj) in x.iter().enumerate().take(A.n) {
-            for i in A.colptr[j]..A.colptr[j + 1] {
-                y[A.rowval[i]] += a * A.nzval[i] * *xj;
-            }
+        for j in nblocked..A.n {
+            let c1 = *colptr.get_unchecked(j + 1);
+            column(y, c0, c1, *x.get_unchecked(j));
+            c0 = c1;
         }
     }
 }
 
 // sparse matrix-vector multiply, transposed
 #[allow(non_snake_case)]
-fn _csc_axpby_T<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
+pub(crate) fn _csc_axpby_T<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    a: T,
+    b: T,
+) {
     //first do the b*y part
-    if b == T::zero() {
-        y.fill(T::zero());
-    } else if b == T::one() {
-    } else if b == -T::one() {
-        y.negate();
-    } else {
-        y.scale(b);
-    }
+    _scale_by_b(y, b);
 
     // if a is zero, we're done
     if a == T::zero() {
@@ -293,26 +634,61 @@ fn _csc_axpby_T<T: FloatT>(A: &CscMatrix<T>, y: &mut [T], x: &[T], a: T, b: T) {
     }
 
     assert_eq!(A.nzval.len(), *A.colptr.last().unwrap());
+    assert_eq!(A.colptr.len(), A.n + 1);
     assert_eq!(x.len(), A.m);
 
     //y += A*x
-    if a == T::one() {
-        for (j, yj) in y.iter_mut().enumerate().take(A.n) {
-            for k in A.colptr[j]..A.colptr[j + 1] {
-                *yj += A.nzval[k] * x[A.rowval[k]];
-            }
-        }
-    } else if a == -T::one() {
-        for (j, yj) in y.iter_mut().enumerate().take(A.n) {
-            for k in A.colptr[j]..A.colptr[j + 1] {
-                *yj -= A.nzval[k] * x[A.rowval[k]];
-            }
+    _dispatch_on_a!(_csc_axpby_T_blocked, A, y, x, a);
+}
+
+// Safety: as for `_csc_axpby_N_blocked`, with x checked against the
+// number of rows of A and only the first A.n entries of y updated.
+//
+// Each y[j] is the dot product of column j with x.   Columns are
+// processed four at a time, with the four dot products interleaved
+// over their common length as independent accumulations, and the
+// remainder of each column finished separately.   The terms of each
+// dot product are still accumulated in order, so the result is
+// identical to an unblocked product.
+#[allow(non_snake_case)]
+#[inline(always)]
+fn _csc_axpby_T_blocked<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    term: impl Fn(T, T) -> T,
+) {
+    let (colptr, rowval, nzval) = (&A.colptr, &A.rowval, &A.nzval);
+
+    let entry = |k: usize| unsafe {
+        let row = *rowval.get_unchecked(k);
+        term(*nzval.get_unchecked(k), *x.get_unchecked(row))
+    };
+    let column = |mut acc: T, first: usize, last: usize| {
+        for k in first..last {
+            acc += entry(k);
         }
-    } else {
-        for (j, yj) in y.iter_mut().enumerate().take(A.n) {
-            for k in A.colptr[j]..A.colptr[j + 1] {
-                *yj += a * A.nzval[k] * x[A.rowval[k]];
-            }
+        acc
+    };
+
+    let ncols = usize::min(A.n, y.len());
+    let nblocked = ncols - ncols % 4;
+    for (j, ys) in y[..nblocked].chunks_exact_mut(4).enumerate() {
+        let c: [usize; 5] = A.colptr[4 * j..4 * j + 5].try_into().unwrap();
+        let len = (0..4).map(|i| c[i + 1] - c[i]).min().unwrap();
+        let (mut s0, mut s1, mut s2, mut s3) = (ys[0], ys[1], ys[2], ys[3]);
+        for p in 0..len {
+            s0 += entry(c[0] + p);
+            s1 += entry(c[1] + p);
+            s2 += entry(c[2] + p);
+            s3 += entry(c[3] + p);
         }
+        ys[0] = column(s0, c[0] + len, c[1]);
+        ys[1] = column(s1, c[1] + len, c[2]);
+        ys[2] = column(s2, c[2] + len, c[3]);
+        ys[3] = column(s3, c[3] + len, c[4]);
+    }
+    for (j, yj) in y.iter_mut().enumerate().take(ncols).skip(nblocked) {
+        *yj = column(*yj, colptr[j], colptr[j + 1]);
     }
 }
@@ -2,6 +2,24 @@
 
 mod core;
 pub use self::core::*;
+mod builder;
+pub use self::builder::*;
+#[cfg(feature = "std")]
+mod binary_io;
 mod block_concatenate;
 mod matrix_math;
+#[cfg(test)]
+pub(crate) use matrix_math::{_csc_axpby_N, _csc_axpby_T};
+mod symbolic;
+pub use symbolic::*;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod utils;
+#[cfg(feature = "parallel")]
+pub use parallel::*;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+#[cfg(feature = "sprs")]
+mod sprs;
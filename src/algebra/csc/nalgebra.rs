@@ -0,0 +1,102 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::{checked_dims, CscMatrix, FloatT, SparseFormatError};
+use nalgebra::DMatrix;
+
+// Conversions to and from nalgebra types.
+//
+// NB: conversions into CscMatrix are provided as constructors rather
+// than `From` implementations, since these would conflict with the
+// blanket `From` implementation for slices of arrays.
+
+impl<T> CscMatrix<T>
+where
+    T: FloatT,
+{
+    /// Create a sparse matrix from a dense nalgebra matrix,
+    /// dropping all zero entries.
+    pub fn from_dmatrix(M: &DMatrix<T>) -> Self {
+        Self::from_dmatrix_with_tol(M, T::zero())
+    }
+
+    /// Create a sparse matrix from a dense nalgebra matrix, dropping
+    /// entries with absolute value no larger than `droptol`.
+    pub fn from_dmatrix_with_tol(M: &DMatrix<T>, droptol: T) -> Self {
+        let (m, n) = M.shape();
+        let mut colptr = Vec::with_capacity(n + 1);
+        let mut rowval = Vec::new();
+        let mut nzval = Vec::new();
+
+        colptr.push(0);
+        for col in M.column_iter() {
+            for (row, &v) in col.iter().enumerate() {
+                if v.abs() > droptol {
+                    rowval.push(row);
+                    nzval.push(v);
+                }
+            }
+            colptr.push(nzval.len());
+        }
+        CscMatrix::new(m, n, colptr, rowval, nzval)
+    }
+
+    /// Create a sparse matrix from an nalgebra_sparse CSC matrix.
+    ///
+    /// The storage layouts are identical, so the data is moved rather
+    /// than copied.  Use `M.clone()` to convert from a reference.
+    pub fn from_nalgebra_sparse(M: nalgebra_sparse::CscMatrix<T>) -> Self {
+        let (m, n) = (M.nrows(), M.ncols());
+        let (colptr, rowval, nzval) = M.disassemble();
+        let out = CscMatrix::new(m, n, colptr, rowval, nzval);
+        debug_assert!(out.check_format().is_ok());
+        out
+    }
+}
+
+impl<T> From<&CscMatrix<T>> for DMatrix<T>
+where
+    T: FloatT,
+{
+    /// Dense copy of a sparse matrix.  Repeated entries are added.
+    fn from(M: &CscMatrix<T>) -> Self {
+        checked_dims((M.m, M.n)).expect("dense copy has too many entries");
+        let mut out = DMatrix::zeros(M.m, M.n);
+        for col in 0..M.n {
+            for k in M.colptr[col]..M.colptr[col + 1] {
+                out[(M.rowval[k], col)] += M.nzval[k];
+            }
+        }
+        out
+    }
+}
+
+impl<T> TryFrom<CscMatrix<T>> for nalgebra_sparse::CscMatrix<T>
+where
+    T: FloatT,
+{
+    type Error = SparseFormatError;
+
+    /// Convert to an nalgebra_sparse CSC matrix without copying.  Fails
+    /// if the data is not correctly formatted, e.g. if row indices
+    /// are unsorted or repeated within a column.
+    fn try_from(M: CscMatrix<T>) -> Result<Self, Self::Error> {
+        M.check_format()?;
+        let out =
+            nalgebra_sparse::CscMatrix::try_from_csc_data(M.m, M.n, M.colptr, M.rowval, M.nzval)
+                .expect("data validated by check_format");
+        Ok(out)
+    }
+}
+
+impl<T> TryFrom<&CscMatrix<T>> for nalgebra_sparse::CscMatrix<T>
+where
+    T: FloatT,
+{
+    type Error = SparseFormatError;
+
+    /// Copy to an nalgebra_sparse CSC matrix.  See the owned
+    /// conversion for failure conditions.
+    fn try_from(M: &CscMatrix<T>) -> Result<Self, Self::Error> {
+        M.clone().try_into()
+    }
+}
@@ -0,0 +1,55 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::{CscMatrix, FloatT};
+use ndarray::{Array2, ArrayView2};
+
+// Conversions to and from ndarray types.
+//
+// NB: conversions into CscMatrix are provided as constructors rather
+// than `From` implementations, since these would conflict with the
+// blanket `From` implementation for slices of arrays.
+
+impl<T> CscMatrix<T>
+where
+    T: FloatT,
+{
+    /// Create a sparse matrix from a dense ndarray view, dropping
+    /// entries with absolute value no larger than `droptol`.
+    ///
+    /// Views in standard (row major), Fortran (column major) or
+    /// strided layouts are all read in place without copying.
+    pub fn from_array2(M: ArrayView2<'_, T>, droptol: T) -> Self {
+        let (m, n) = M.dim();
+        let mut colptr = Vec::with_capacity(n + 1);
+        let mut rowval = Vec::new();
+        let mut nzval = Vec::new();
+
+        colptr.push(0);
+        for col in M.columns() {
+            for (row, &v) in col.iter().enumerate() {
+                if v.abs() > droptol {
+                    rowval.push(row);
+                    nzval.push(v);
+                }
+            }
+            colptr.push(nzval.len());
+        }
+        CscMatrix::new(m, n, colptr, rowval, nzval)
+    }
+}
+
+impl<T> From<&CscMatrix<T>> for Array2<T>
+where
+    T: FloatT,
+{
+    /// Dense copy of a sparse matrix.  Repeated entries are added.
+    fn from(M: &CscMatrix<T>) -> Self {
+        let mut out = Array2::zeros((M.m, M.n));
+        for col in 0..M.n {
+            for k in M.colptr[col]..M.colptr[col + 1] {
+                out[(M.rowval[k], col)] += M.nzval[k];
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,282 @@
+use crate::algebra::*;
+use rayon::prelude::*;
+use std::iter::zip;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// ---------------------------------------------------
+// Multithreaded sparse matrix-vector products and the
+// norm and scaling passes used by equilibration, for
+// matrices with many nonzeros
+// ---------------------------------------------------
+
+/// Default number of nonzeros above which sparse matrix-vector products,
+/// and the row and column norms and scalings used in equilibration, are
+/// computed on the rayon thread pool.
+pub const PARALLEL_GEMV_THRESHOLD_DEFAULT: usize = 1_000_000;
+
+static PARALLEL_GEMV_THRESHOLD: AtomicUsize = AtomicUsize::new(PARALLEL_GEMV_THRESHOLD_DEFAULT);
+
+/// Revert the parallel `gemv` threshold to its default value.   The default
+/// is [`PARALLEL_GEMV_THRESHOLD_DEFAULT`].
+///
+/// See also: [`get_parallel_gemv_threshold`], [`set_parallel_gemv_threshold`]
+pub fn default_parallel_gemv_threshold() {
+    PARALLEL_GEMV_THRESHOLD.store(PARALLEL_GEMV_THRESHOLD_DEFAULT, Ordering::Relaxed);
+}
+/// Set the number of nonzeros above which sparse matrix-vector products,
+/// and the row and column norms and scalings used in equilibration, are
+/// computed in parallel.   Use `usize::MAX` to always compute them serially.
+///
+/// The norms, the scalings and the products give results identical to the
+/// serial versions.   Products with the constraint matrix `A` are computed
+/// from its explicit transpose, which the solver stores when they are
+/// computed in parallel, subject to the `explicit_transpose_max_bytes`
+/// setting.   Without it they are computed serially.
+///
+/// Setting the threshold applies at module level.
+///
+/// See also: [`get_parallel_gemv_threshold`], [`default_parallel_gemv_threshold`]
+pub fn set_parallel_gemv_threshold(nnz: usize) {
+    PARALLEL_GEMV_THRESHOLD.store(nnz, Ordering::Relaxed);
+}
+/// Get the current parallel `gemv` threshold.
+///
+/// See also: [`set_parallel_gemv_threshold`], [`default_parallel_gemv_threshold`]
+pub fn get_parallel_gemv_threshold() -> usize {
+    PARALLEL_GEMV_THRESHOLD.load(Ordering::Relaxed)
+}
+
+// minimum number of outputs computed by each parallel task
+const MIN_TASK_LEN: usize = 1024;
+
+pub(crate) fn _use_parallel_gemv<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+) -> bool {
+    A.nnz() >= get_parallel_gemv_threshold() && rayon::current_num_threads() > 1
+}
+
+// first column of each of `nchunks` contiguous blocks of columns
+// with roughly equal nonzeros, and a final end marker
+fn _column_blocks<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    nchunks: usize,
+) -> Vec<usize> {
+    let nchunks = usize::max(nchunks, 1);
+    let nnz = A.nnz();
+    let mut bounds: Vec<usize> = (0..nchunks)
+        .map(|c| A.colptr[..A.n].partition_point(|&p| p < c * nnz / nchunks))
+        .collect();
+    bounds.push(A.n);
+    bounds
+}
+
+// split v into consecutive slices, the c-th of length
+// offsets[c+1] - offsets[c], starting from offsets[0]
+fn _split_at_offsets<'a, T>(mut v: &'a mut [T], offsets: &[usize]) -> Vec<&'a mut [T]> {
+    let mut out = Vec::with_capacity(offsets.len().saturating_sub(1));
+    v = &mut v[offsets[0]..];
+    for w in offsets.windows(2) {
+        let (head, tail) = v.split_at_mut(w[1] - w[0]);
+        out.push(head);
+        v = tail;
+    }
+    out
+}
+
+// norms = max(norms, partials[c]) elementwise over all partials.
+// Maxima do not depend on the order of evaluation, so the result
+// is identical to a serial pass
+fn _merge_max<T: FloatT>(norms: &mut [T], partials: &[Vec<T>]) {
+    norms
+        .par_chunks_mut(MIN_TASK_LEN)
+        .enumerate()
+        .for_each(|(chunk, ns)| {
+            let offset = chunk * MIN_TASK_LEN;
+            for (i, ni) in ns.iter_mut().enumerate() {
+                for w in partials {
+                    *ni = T::max(*ni, w[offset + i]);
+                }
+            }
+        });
+}
+
+// y = b*y for a single entry, matching the serial kernels
+#[inline]
+fn _scale_entry<T: FloatT>(yi: &mut T, b: T) {
+    if b == T::zero() {
+        *yi = T::zero();
+    } else if b == T::one() {
+    } else if b == -T::one() {
+        *yi = -*yi;
+    } else {
+        *yi *= b;
+    }
+}
+
+// sparse matrix-vector multiply, transposed.   Each output is the
+// product of a single column with x, so outputs are computed
+// independently and exactly as in the serial kernel
+pub(crate) fn _csc_axpby_T_par<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    y: &mut [T],
+    x: &[T],
+    a: T,
+    b: T,
+) {
+    if a == T::zero() {
+        y.par_iter_mut().for_each(|yj| _scale_entry(yj, b));
+        return;
+    }
+
+    assert_eq!(A.nzval.len(), *A.colptr.last().unwrap());
+    assert_eq!(x.len(), A.m);
+
+    let (n, colptr, rowval, nzval) = (A.n, &*A.colptr, &*A.rowval, &A.nzval);
+    y.par_iter_mut()
+        .with_min_len(MIN_TASK_LEN)
+        .enumerate()
+        .for_each(|(j, yj)| {
+            _scale_entry(yj, b);
+            if j >= n {
+                return;
+            }
+            let rng = colptr[j]..colptr[j + 1];
+            if a == T::one() {
+                for k in rng {
+                    *yj += nzval[k] * x[rowval[k]];
+                }
+            } else if a == -T::one() {
+                for k in rng {
+                    *yj -= nzval[k] * x[rowval[k]];
+                }
+            } else {
+                for k in rng {
+                    *yj += a * nzval[k] * x[rowval[k]];
+                }
+            }
+        });
+}
+
+// inf norms of the columns of A, maximized with the existing
+// values of norms.   Each column is computed as in the serial kernel
+pub(crate) fn _csc_col_norms_par<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    norms: &mut [T],
+) {
+    assert_eq!(norms.len(), A.n);
+
+    let (colptr, nzval) = (&*A.colptr, &A.nzval);
+    norms
+        .par_iter_mut()
+        .with_min_len(MIN_TASK_LEN)
+        .enumerate()
+        .for_each(|(j, v)| {
+            let rng = colptr[j]..colptr[j + 1];
+            *v = nzval[rng].iter().fold(*v, |m, &x| T::max(m, T::abs(x)));
+        });
+}
+
+// inf norms of the rows of A, maximized with the existing values of
+// norms.   Each block of columns accumulates its own row norms, which
+// are then merged by elementwise max
+pub(crate) fn _csc_row_norms_par<T: FloatT>(
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    norms: &mut [T],
+    nchunks: usize,
+) {
+    assert_eq!(norms.len(), A.m);
+    assert_eq!(A.rowval.len(), *A.colptr.last().unwrap());
+
+    let bounds = _column_blocks(A, nchunks);
+    let (m, colptr, rowval, nzval) = (A.m, &*A.colptr, &*A.rowval, &A.nzval);
+    let partials: Vec<Vec<T>> = bounds
+        .par_windows(2)
+        .map(|b| {
+            let mut w = vec![T::zero(); m];
+            let rng = colptr[b[0]]..colptr[b[1]];
+            for (&row, &val) in zip(&rowval[rng.clone()], &nzval[rng]) {
+                w[row] = T::max(w[row], T::abs(val));
+            }
+            w
+        })
+        .collect();
+
+    _merge_max(norms, &partials);
+}
+
+// A = diag(l)*A*diag(r), by blocks of columns
+pub(crate) fn _csc_lrscale_par<T: FloatT>(
+    A: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
+    l: &[T],
+    r: &[T],
+    nchunks: usize,
+) {
+    assert_eq!(A.nzval.len(), *A.colptr.last().unwrap());
+    assert_eq!(r.len(), A.n);
+
+    let bounds = _column_blocks(A, nchunks);
+    let offsets: Vec<usize> = bounds.iter().map(|&j| A.colptr[j]).collect();
+    let (colptr, rowval) = (&*A.colptr, &*A.rowval);
+    let blocks = _split_at_offsets(&mut A.nzval, &offsets);
+
+    blocks
+        .into_par_iter()
+        .zip(bounds.par_windows(2))
+        .for_each(|(vals, b)| {
+            let base = colptr[b[0]];
+            for (col, &ri) in r.iter().enumerate().take(b[1]).skip(b[0]) {
+                let (first, last) = (colptr[col], colptr[col + 1]);
+                let vals = &mut vals[(first - base)..(last - base)];
+                for (val, &row) in zip(vals, &rowval[first..last]) {
+                    *val *= l[row] * ri;
+                }
+            }
+        });
+}
+
+// as lrscale_with_norms, by blocks of columns.   Column norms are
+// computed with the scaling of each column, and row norms are
+// accumulated per block and merged by elementwise max
+pub(crate) fn _csc_lrscale_with_norms_par<T: FloatT>(
+    A: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
+    l: &[T],
+    r: &[T],
+    col_norms: &mut [T],
+    row_norms: &mut [T],
+    nchunks: usize,
+) {
+    let bounds = _column_blocks(A, nchunks);
+    let offsets: Vec<usize> = bounds.iter().map(|&j| A.colptr[j]).collect();
+    let (m, colptr, rowval) = (A.m, &*A.colptr, &*A.rowval);
+    let blocks = _split_at_offsets(&mut A.nzval, &offsets);
+    let cnorm_blocks = _split_at_offsets(col_norms, &bounds);
+
+    let partials: Vec<Vec<T>> = blocks
+        .into_par_iter()
+        .zip(cnorm_blocks)
+        .zip(bounds.par_windows(2))
+        .map(|((vals, cnorms), b)| {
+            let mut w = vec![T::zero(); m];
+            let base = colptr[b[0]];
+            let cols = zip(&r[b[0]..b[1]], cnorms.iter_mut()).enumerate();
+            for (i, (&ri, cnorm)) in cols {
+                let col = b[0] + i;
+                let (first, last) = (colptr[col], colptr[col + 1]);
+                let vals = &mut vals[(first - base)..(last - base)];
+                let mut norm = T::zero();
+                for (val, &row) in zip(vals, &rowval[first..last]) {
+                    *val *= l[row] * ri;
+                    let absval = T::abs(*val);
+                    norm = T::max(norm, absval);
+                    w[row] = T::max(w[row], absval);
+                }
+                *cnorm = norm;
+            }
+            w
+        })
+        .collect();
+
+    row_norms.fill(T::zero());
+    _merge_max(row_norms, &partials);
+}
@@ -0,0 +1,77 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::{CscMatrix, FloatT, SparseFormatError};
+use sprs::{CsMatBase, CsMatI, SpIndex};
+use std::ops::Deref;
+
+// Conversions to and from sprs types.
+//
+// NB: conversion into CscMatrix is provided as a constructor rather
+// than a `TryFrom` implementation, since this would conflict with the
+// blanket `From` implementation for slices of arrays.
+
+impl<T> CscMatrix<T>
+where
+    T: FloatT,
+{
+    /// Create a sparse matrix from an sprs matrix in either CSC or
+    /// CSR format.   CSR inputs are transposed into CSC format.
+    ///
+    /// The result has row indices sorted within each column.
+    pub fn try_from_sprs<I, Iptr, IptrS, IndS, DataS>(
+        M: &CsMatBase<T, I, IptrS, IndS, DataS, Iptr>,
+    ) -> Result<Self, SparseFormatError>
+    where
+        I: SpIndex,
+        Iptr: SpIndex,
+        IptrS: Deref<Target = [Iptr]>,
+        IndS: Deref<Target = [I]>,
+        DataS: Deref<Target = [T]>,
+    {
+        let (m, n) = (M.rows(), M.cols());
+
+        let ptr: Vec<usize> = M.indptr().to_proper().iter().map(|p| p.index()).collect();
+        let idx: Vec<usize> = M.indices().iter().map(|i| i.index()).collect();
+        let data = M.data().to_vec();
+
+        // CSR data is the CSC data of the transpose
+        let out = if M.is_csc() {
+            CscMatrix::new(m, n, ptr, idx, data)
+        } else {
+            let Mt = CscMatrix::new(n, m, ptr, idx, data);
+            Mt.check_format()?;
+            CscMatrix::from(Mt.t())
+        };
+        out.check_format()?;
+        Ok(out)
+    }
+}
+
+impl<T, I, Iptr> TryFrom<&CscMatrix<T>> for CsMatI<T, I, Iptr>
+where
+    T: FloatT,
+    I: SpIndex,
+    Iptr: SpIndex,
+{
+    type Error = SparseFormatError;
+
+    /// Convert to an sprs matrix in CSC format.   Fails if the data is
+    /// not correctly formatted, or if any index is too large for the
+    /// sprs index types.
+    fn try_from(M: &CscMatrix<T>) -> Result<Self, Self::Error> {
+        M.check_format()?;
+
+        let indptr = _convert_indices(&M.colptr)?;
+        let indices = _convert_indices(&M.rowval)?;
+
+        let out = CsMatI::try_new_csc((M.m, M.n), indptr, indices, M.nzval.clone())
+            .map_err(|_| SparseFormatError::BadRowval)?;
+        Ok(out)
+    }
+}
+
+fn _convert_indices<I: SpIndex>(v: &[usize]) -> Result<Vec<I>, SparseFormatError> {
+    v.iter()
+        .map(|&i| I::try_from_usize(i).ok_or(SparseFormatError::IndexOverflow))
+        .collect()
+}
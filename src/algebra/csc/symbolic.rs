@@ -0,0 +1,254 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::*;
+use core::iter::zip;
+use core::ops::Deref;
+
+// ---------------------------------------------------
+// Symbolic / numeric splitting of sparsity pattern
+// dependent matrix operations.
+//
+// Each `*_symbolic` function works only from the sparsity
+// pattern of its inputs and returns an opaque plan.   The
+// matching `*_numeric` function executes the plan for the
+// current values of the inputs, writing into an output with
+// the pattern returned by the symbolic phase.   Executing a plan
+// costs O(nnz) with no allocation, so a plan can be kept and
+// re-executed whenever the values of the inputs change.
+//
+// A plan is only valid for inputs with the sparsity pattern
+// it was computed from.   Numeric phases check the dimensions
+// and number of nonzeros of their inputs and outputs and return
+// an error if they differ, in which case the plan should be
+// recomputed.
+// ---------------------------------------------------
+
+/// Reusable plan for the transpose of a sparse matrix.
+/// See [`transpose_symbolic`](CscMatrix::transpose_symbolic).
+#[derive(Debug, Clone)]
+pub struct TransposePlan {
+    // dimensions and nnz of the input
+    size: (usize, usize),
+    nnz: usize,
+    // position in the output of each input entry
+    map: Vec<usize>,
+}
+
+/// Reusable plan for a scaled sum of two sparse matrices.
+/// See [`add_scaled_symbolic`](CscMatrix::add_scaled_symbolic).
+#[derive(Debug, Clone)]
+pub struct AddScaledPlan {
+    // dimensions of the inputs and output, and nnz of each
+    size: (usize, usize),
+    nnz: (usize, usize, usize),
+    // position in the output of each entry of each input
+    mapA: Vec<usize>,
+    mapB: Vec<usize>,
+}
+
+/// Reusable plan for the upper triangle of `AᵀA`.
+/// See [`AtA_symbolic`](CscMatrix::AtA_symbolic).
+#[derive(Debug, Clone)]
+pub struct AtAPlan<T> {
+    // Aᵀ, with its values updated on each execution
+    At: CscMatrix<T>,
+    transpose: TransposePlan,
+    // nnz of the output
+    nnz: usize,
+    // dense accumulator for one column of the output
+    work: Vec<T>,
+}
+
+impl<T, S> CscMatrix<T, S>
+where
+    T: FloatT,
+    S: Deref<Target = [usize]>,
+{
+    /// Plan the transpose of the matrix from its sparsity pattern.
+    /// Returns the plan and the transpose itself.
+    pub fn transpose_symbolic(&self) -> (TransposePlan, CscMatrix<T>) {
+        let (At, map) = self.transpose_with_map();
+        let plan = TransposePlan {
+            size: self.size(),
+            nnz: self.nnz(),
+            map,
+        };
+        (plan, At)
+    }
+
+    /// Write the transpose of the matrix to `At`, using a plan from
+    /// [`transpose_symbolic`](CscMatrix::transpose_symbolic).
+    pub fn transpose_numeric(
+        &self,
+        plan: &TransposePlan,
+        At: &mut CscMatrix<T>,
+    ) -> Result<(), SparseFormatError> {
+        let (m, n) = plan.size;
+        if self.size() != plan.size
+            || self.nnz() != plan.nnz
+            || At.size() != (n, m)
+            || At.nnz() != plan.nnz
+        {
+            return Err(SparseFormatError::IncompatibleDimension);
+        }
+        for (&k, &v) in zip(&plan.map, &self.nzval) {
+            At.nzval[k] = v;
+        }
+        Ok(())
+    }
+}
+
+impl<T> CscMatrix<T>
+where
+    T: FloatT,
+{
+    /// Plan the sum `a*A + b*B` of two matrices of the same size from
+    /// their sparsity patterns.   Returns the plan and an output matrix
+    /// with the union of the patterns of `A` and `B` and zero values.
+    pub fn add_scaled_symbolic(
+        A: &CscMatrix<T>,
+        B: &CscMatrix<T>,
+    ) -> Result<(AddScaledPlan, CscMatrix<T>), SparseFormatError> {
+        if A.size() != B.size() {
+            return Err(SparseFormatError::IncompatibleDimension);
+        }
+        let n = A.n;
+        let mut colptr = Vec::with_capacity(n + 1);
+        let mut rowval = Vec::with_capacity(usize::max(A.nnz(), B.nnz()));
+        let mut mapA = vec![0; A.nnz()];
+        let mut mapB = vec![0; B.nnz()];
+
+        colptr.push(0);
+        for col in 0..n {
+            // merge the sorted row indices of the two columns
+            let (mut ka, enda) = (A.colptr[col], A.colptr[col + 1]);
+            let (mut kb, endb) = (B.colptr[col], B.colptr[col + 1]);
+            while ka < enda || kb < endb {
+                let ra = if ka < enda { A.rowval[ka] } else { usize::MAX };
+                let rb = if kb < endb { B.rowval[kb] } else { usize::MAX };
+                let row = usize::min(ra, rb);
+                if ra == row {
+                    mapA[ka] = rowval.len();
+                    ka += 1;
+                }
+                if rb == row {
+                    mapB[kb] = rowval.len();
+                    kb += 1;
+                }
+                rowval.push(row);
+            }
+            colptr.push(rowval.len());
+        }
+
+        let nnz = rowval.len();
+        let plan = AddScaledPlan {
+            size: A.size(),
+            nnz: (A.nnz(), B.nnz(), nnz),
+            mapA,
+            mapB,
+        };
+        let C = CscMatrix::new(A.m, n, colptr, rowval, vec![T::zero(); nnz]);
+        Ok((plan, C))
+    }
+
+    /// Write `a*A + b*B` to `C`, using a plan from
+    /// [`add_scaled_symbolic`](CscMatrix::add_scaled_symbolic).
+    pub fn add_scaled_numeric(
+        plan: &AddScaledPlan,
+        C: &mut CscMatrix<T>,
+        a: T,
+        A: &CscMatrix<T>,
+        b: T,
+        B: &CscMatrix<T>,
+    ) -> Result<(), SparseFormatError> {
+        if A.size() != plan.size
+            || B.size() != plan.size
+            || C.size() != plan.size
+            || (A.nnz(), B.nnz(), C.nnz()) != plan.nnz
+        {
+            return Err(SparseFormatError::IncompatibleDimension);
+        }
+        C.nzval.fill(T::zero());
+        for (&k, &v) in zip(&plan.mapA, &A.nzval) {
+            C.nzval[k] += a * v;
+        }
+        for (&k, &v) in zip(&plan.mapB, &B.nzval) {
+            C.nzval[k] += b * v;
+        }
+        Ok(())
+    }
+
+    /// Plan the upper triangle of `AᵀA` from the sparsity pattern of the
+    /// matrix.   Returns the plan and an output matrix with zero values.
+    /// Entries of the output are structurally nonzero whenever two
+    /// columns of `A` share a row, even if their product cancels.
+    pub fn AtA_symbolic(&self) -> (AtAPlan<T>, CscMatrix<T>) {
+        let (transpose, At) = self.transpose_symbolic();
+        let n = self.n;
+
+        // rows of each output column are the columns i ≤ j of A
+        // appearing in any row that column j of A touches
+        let mut mark = vec![usize::MAX; n];
+        let mut colptr = Vec::with_capacity(n + 1);
+        let mut rowval = Vec::new();
+        colptr.push(0);
+        for j in 0..n {
+            let first = rowval.len();
+            for &r in &self.rowval[self.colptr[j]..self.colptr[j + 1]] {
+                for &i in &At.rowval[At.colptr[r]..At.colptr[r + 1]] {
+                    if i <= j && mark[i] != j {
+                        mark[i] = j;
+                        rowval.push(i);
+                    }
+                }
+            }
+            rowval[first..].sort_unstable();
+            colptr.push(rowval.len());
+        }
+
+        let nnz = rowval.len();
+        let plan = AtAPlan {
+            At,
+            transpose,
+            nnz,
+            work: vec![T::zero(); n],
+        };
+        let C = CscMatrix::new(n, n, colptr, rowval, vec![T::zero(); nnz]);
+        (plan, C)
+    }
+
+    /// Write the upper triangle of `AᵀA` to `C`, using a plan from
+    /// [`AtA_symbolic`](CscMatrix::AtA_symbolic).   The plan holds
+    /// the workspace used by the product.
+    pub fn AtA_numeric(
+        &self,
+        plan: &mut AtAPlan<T>,
+        C: &mut CscMatrix<T>,
+    ) -> Result<(), SparseFormatError> {
+        let n = self.n;
+        if C.size() != (n, n) || C.nnz() != plan.nnz {
+            return Err(SparseFormatError::IncompatibleDimension);
+        }
+        self.transpose_numeric(&plan.transpose, &mut plan.At)?;
+
+        // C[i,j] = Σ_r A[r,i]*A[r,j], accumulated densely for each j
+        let (At, work) = (&plan.At, &mut plan.work);
+        for j in 0..n {
+            for k in self.colptr[j]..self.colptr[j + 1] {
+                let (r, v) = (self.rowval[k], self.nzval[k]);
+                for p in At.colptr[r]..At.colptr[r + 1] {
+                    let i = At.rowval[p];
+                    if i <= j {
+                        work[i] += At.nzval[p] * v;
+                    }
+                }
+            }
+            for p in C.colptr[j]..C.colptr[j + 1] {
+                let i = C.rowval[p];
+                C.nzval[p] = work[i];
+                work[i] = T::zero();
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,8 +4,9 @@
 //---------------------------------------------------------
 
 use crate::algebra::{CscMatrix, MatrixShape, MatrixTriangle};
+use core::iter::zip;
+use core::ops::Deref;
 use num_traits::Num;
-use std::iter::zip;
 
 impl<T> CscMatrix<T>
 where
@@ -42,7 +43,11 @@ where
     // same as kkt_count_diag, but counts places
     // where the input matrix M has a missing
     // diagonal entry.  M must be square and TRIU
-    pub(crate) fn colcount_missing_diag(&mut self, M: &CscMatrix<T>, initcol: usize) {
+    pub(crate) fn colcount_missing_diag(
+        &mut self,
+        M: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        initcol: usize,
+    ) {
         assert_eq!(M.colptr.len(), M.n + 1);
         assert!(self.colptr.len() >= M.n + initcol);
 
@@ -77,7 +82,12 @@ where
 
     // increment the self.colptr by the number of nonzeros in M
 
-    pub(crate) fn colcount_block(&mut self, M: &CscMatrix<T>, initcol: usize, shape: MatrixShape) {
+    pub(crate) fn colcount_block(
+        &mut self,
+        M: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        initcol: usize,
+        shape: MatrixShape,
+    ) {
         match shape {
             MatrixShape::T => {
                 for row in M.rowval.iter() {
@@ -123,7 +133,7 @@ where
     #[allow(clippy::needless_range_loop)]
     pub(crate) fn fill_block(
         &mut self,
-        M: &CscMatrix<T>,
+        M: &CscMatrix<T, impl Deref<Target = [usize]>>,
         MtoKKT: &mut [usize],
         initrow: usize,
         initcol: usize,
@@ -233,7 +243,11 @@ where
     // same as fill_diag, but only places zero
     // entries where the input matrix M has a missing
     // diagonal entry.  M must be square and TRIU
-    pub(crate) fn fill_missing_diag(&mut self, M: &CscMatrix<T>, initcol: usize) {
+    pub(crate) fn fill_missing_diag(
+        &mut self,
+        M: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        initcol: usize,
+    ) {
         for i in 0..M.n {
             // fill out missing diagonal terms only
             if M.colptr[i] == M.colptr[i+1] ||    // completely empty column
@@ -272,7 +286,12 @@ where
         self.colptr.rotate_right(1);
         self.colptr[0] = 0;
     }
+}
 
+impl<T, S> CscMatrix<T, S>
+where
+    S: Deref<Target = [usize]>,
+{
     pub(crate) fn count_diagonal_entries(&self) -> usize {
         let mut count = 0;
         for i in 0..self.n {
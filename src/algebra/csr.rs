@@ -0,0 +1,141 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::{CscMatrix, FloatT, ShapedMatrix, VectorMath};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::iter::zip;
+
+/// Sparse matrix in Compressed Sparse Row (CSR) format
+///
+/// This format is used only to pass symmetric matrices to external
+/// iterative solvers, and is produced by
+/// [`to_symmetric_csr`](CscMatrix::to_symmetric_csr).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrMatrix<T = f64> {
+    /// number of rows
+    pub m: usize,
+    /// number of columns
+    pub n: usize,
+    /// CSR format row pointer, with length `m+1`
+    pub rowptr: Vec<usize>,
+    /// vector of column indices
+    pub colval: Vec<usize>,
+    /// vector of non-zero matrix elements
+    pub nzval: Vec<T>,
+}
+
+impl<T> CsrMatrix<T>
+where
+    T: FloatT,
+{
+    /// number of nonzeros
+    pub fn nnz(&self) -> usize {
+        self.rowptr[self.m]
+    }
+
+    /// Symmetric matrix-vector multiply `y = a*A*x + b*y`.
+    ///
+    /// The matrix must be symmetric with both triangles stored and
+    /// column indices sorted within each row.   Only the upper triangle
+    /// is read, with each off diagonal entry applied twice.
+    pub fn gemv_sym(&self, y: &mut [T], x: &[T], a: T, b: T) {
+        assert_eq!(self.m, self.n);
+        assert_eq!(x.len(), self.n);
+        assert_eq!(y.len(), self.m);
+
+        if b == T::zero() {
+            y.fill(T::zero());
+        } else {
+            y.scale(b);
+        }
+
+        for row in 0..self.m {
+            let rng = self.rowptr[row]..self.rowptr[row + 1];
+            let (cols, vals) = (&self.colval[rng.clone()], &self.nzval[rng]);
+
+            // skip the lower triangle part of this row
+            let first = cols.partition_point(|&col| col < row);
+            let xrow = x[row];
+
+            for (&col, &Aij) in zip(&cols[first..], &vals[first..]) {
+                y[row] += a * Aij * x[col];
+                if col != row {
+                    y[col] += a * Aij * xrow;
+                }
+            }
+        }
+    }
+}
+
+impl<T> CscMatrix<T>
+where
+    T: FloatT,
+{
+    /// Full symmetric CSR copy of a triangular matrix.
+    ///
+    /// The matrix must be square and either upper or lower triangular,
+    /// and is taken to be the triangle of a symmetric matrix.   Off
+    /// diagonal entries are copied into the other triangle.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square or not triangular.
+    pub fn to_symmetric_csr(&self) -> CsrMatrix<T> {
+        assert!(self.is_square(), "matrix must be square");
+        let n = self.n;
+
+        let is_triu = self.is_triu();
+        let is_tril = (0..n).all(|col| {
+            let rng = self.colptr[col]..self.colptr[col + 1];
+            self.rowval[rng].iter().all(|&row| row >= col)
+        });
+        assert!(is_triu || is_tril, "matrix must be triangular");
+
+        // count entries in each row, with row pointers
+        // initially pointing to the start of each row
+        let mut rowptr = vec![0; n + 1];
+        for col in 0..n {
+            for k in self.colptr[col]..self.colptr[col + 1] {
+                let row = self.rowval[k];
+                rowptr[row + 1] += 1;
+                if row != col {
+                    rowptr[col + 1] += 1;
+                }
+            }
+        }
+        for i in 0..n {
+            rowptr[i + 1] += rowptr[i];
+        }
+
+        // fill by columns.   Within each row this places the
+        // entries in increasing column order for either triangle.
+        let nnz = rowptr[n];
+        let mut next = rowptr.clone();
+        let mut colval = vec![0; nnz];
+        let mut nzval = vec![T::zero(); nnz];
+
+        for col in 0..n {
+            for k in self.colptr[col]..self.colptr[col + 1] {
+                let row = self.rowval[k];
+                let v = self.nzval[k];
+
+                colval[next[row]] = col;
+                nzval[next[row]] = v;
+                next[row] += 1;
+
+                if row != col {
+                    colval[next[col]] = row;
+                    nzval[next[col]] = v;
+                    next[col] += 1;
+                }
+            }
+        }
+
+        CsrMatrix {
+            m: n,
+            n,
+            rowptr,
+            colval,
+            nzval,
+        }
+    }
+}
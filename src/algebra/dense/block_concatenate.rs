@@ -1,8 +1,8 @@
 #![allow(non_snake_case)]
 
 use crate::algebra::{
-    hvcat_dim_check, BlockConcatenate, DenseMatrix, FloatT, Matrix, MatrixConcatenationError,
-    ShapedMatrix,
+    checked_dims, checked_sum, hvcat_dim_check, BlockConcatenate, DenseMatrix, FloatT, Matrix,
+    MatrixConcatenationError, ShapedMatrix,
 };
 
 // PJG: Should allow for borrowed data in the
@@ -17,7 +17,7 @@ where
 
         //dimensions for C = [A B];
         let m = A.nrows(); //rows
-        let n = A.ncols() + B.ncols(); //cols s
+        let n = checked_sum([A.ncols(), B.ncols()]).expect("hcat: too many columns"); //cols C
         let mut data = A.data.clone();
         data.extend(&B.data);
         Self::new((m, n), data)
@@ -28,9 +28,10 @@ where
         assert_eq!(A.ncols(), B.ncols());
 
         //dimensions for C = [A; B];
-        let m = A.nrows() + B.nrows(); //rows C
+        let m = checked_sum([A.nrows(), B.nrows()]).expect("vcat: too many rows"); //rows C
         let n = A.ncols(); //cols C
-        let mut data = Vec::with_capacity(m * n);
+        let numel = checked_dims((m, n)).expect("vcat: too many entries");
+        let mut data = Vec::with_capacity(numel);
 
         for col in 0..A.ncols() {
             data.extend(A.col_slice(col));
@@ -45,10 +46,12 @@ where
 
         // dimensions are consistent and nonzero, so count
         // total rows and columns by counting along the border
-        let nrows = mats.iter().map(|blockrow| blockrow[0].nrows()).sum();
-        let ncols = mats[0].iter().map(|topblock| topblock.ncols()).sum();
+        let overflow = MatrixConcatenationError::DimensionOverflow;
+        let nrows = checked_sum(mats.iter().map(|blockrow| blockrow[0].nrows())).ok_or(overflow)?;
+        let ncols = checked_sum(mats[0].iter().map(|topblock| topblock.ncols())).ok_or(overflow)?;
+        let numel = checked_dims((nrows, ncols)).ok_or(overflow)?;
 
-        let mut data = Vec::with_capacity(nrows * ncols);
+        let mut data = Vec::with_capacity(numel);
 
         for blockcolidx in 0..mats[0].len() {
             //every matrix in each block-column should have the same
@@ -68,8 +71,10 @@ where
             return Err(MatrixConcatenationError::IncompatibleDimension);
         }
 
-        let nrows = mats.iter().map(|mat| mat.nrows()).sum();
-        let ncols = mats.iter().map(|mat| mat.ncols()).sum();
+        let overflow = MatrixConcatenationError::DimensionOverflow;
+        let nrows = checked_sum(mats.iter().map(|mat| mat.nrows())).ok_or(overflow)?;
+        let ncols = checked_sum(mats.iter().map(|mat| mat.ncols())).ok_or(overflow)?;
+        checked_dims((nrows, ncols)).ok_or(overflow)?;
 
         let mut M = Self::zeros((nrows, ncols));
 
@@ -123,3 +128,23 @@ fn test_dense_concatenate() {
 }
 
 //PJG: hvcat and blockdiag unittests here
+
+#[test]
+fn test_dense_concatenate_overflow() {
+    // the number of rows overflows, although neither block has any
+    // entries.  The number of entries overflows for a nonzero width
+    let A = Matrix::<f64>::zeros((usize::MAX / 2 + 1, 0));
+    let B = Matrix::<f64>::zeros((1, 2));
+    let C = Matrix::<f64>::zeros((usize::MAX / 2, 0));
+
+    assert!(matches!(
+        Matrix::blockdiag(&[&A, &A]),
+        Err(MatrixConcatenationError::DimensionOverflow)
+    ));
+    assert!(matches!(
+        Matrix::blockdiag(&[&C, &B]),
+        Err(MatrixConcatenationError::DimensionOverflow)
+    ));
+    assert!(std::panic::catch_unwind(|| Matrix::vcat(&A, &A)).is_err());
+    assert!(std::panic::catch_unwind(|| Matrix::<f64>::zeros((usize::MAX, 2))).is_err());
+}
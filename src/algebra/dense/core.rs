@@ -56,7 +56,7 @@ where
         let m = rows.len();
         let n = rows.iter().map(|r| r.len()).next().unwrap_or(0);
         assert!(rows.iter().all(|r| r.len() == n));
-        let nnz = m * n;
+        let nnz = checked_dims((m, n)).unwrap();
 
         let mut data = Vec::with_capacity(nnz);
         for c in 0..n {
@@ -77,7 +77,8 @@ where
     T: FloatT,
 {
     pub fn zeros(size: (usize, usize)) -> Self {
-        let data = vec![T::zero(); size.0 * size.1];
+        let numel = checked_dims(size).expect("matrix has too many entries");
+        let data = vec![T::zero(); numel];
         Self::new(size, data)
     }
 
@@ -88,7 +89,7 @@ where
     }
 
     pub fn new(size: (usize, usize), data: Vec<T>) -> Self {
-        assert!(size.0 * size.1 == data.len());
+        assert!(checked_dims(size) == Some(data.len()));
         Self{size, data, phantom: std::marker::PhantomData}
     }
 
@@ -99,8 +100,9 @@ where
     /// Resize a matrix, preserving or expanding allocated
     /// space.   Values are not flushed but may be garbage.
     pub fn resize(&mut self, size: (usize, usize)) {
+        let numel = checked_dims(size).expect("matrix has too many entries");
         self.size = size;
-        self.data.resize(size.0 * size.1, T::zero());
+        self.data.resize(numel, T::zero());
     }
 }
 
@@ -13,8 +13,8 @@ where
     {
         let (pp, qq) = A.size();
         let (rr, ss) = B.size();
-        assert!(self.nrows() == pp * rr);
-        assert!(self.ncols() == qq * ss);
+        assert!(checked_dims((pp, rr)) == Some(self.nrows()));
+        assert!(checked_dims((qq, ss)) == Some(self.ncols()));
 
         let mut i = 0;
         for q in 0..qq {
@@ -1,7 +1,7 @@
 #![allow(non_snake_case)]
 
 use crate::algebra::*;
-use std::ops::{Index, IndexMut};
+use core::ops::{Index, IndexMut};
 
 // 3x3 Dense matrix types are restricted to the crate
 // NB: Implements a symmetric 3x3 type to support
@@ -42,6 +42,7 @@ where
     }
 
     // y = H*x
+    #[inline]
     pub fn mul(&self, y: &mut [T], x: &[T]) {
         let H = self;
 
@@ -72,6 +73,7 @@ where
     }
 
     //returns y'*H*x
+    #[inline]
     pub fn quad_form(&self, y: &[T], x: &[T]) -> T {
         let H = self;
         let mut out = T::zero();
@@ -146,6 +148,7 @@ where
 
     // Unrolled 3x3 forward/backward substition for a Cholesky factor
 
+    #[inline]
     pub fn cholesky_3x3_explicit_solve(&self, x: &mut [T], b: &[T]) {
         let L = self;
 
@@ -186,7 +189,7 @@ fn test_3x3_matrix_index() {
     let data = [1., 2., 3., 4., 5., 6.];
 
     assert!(
-        std::iter::zip(H.data, data).all(|(a, b)| a == b),
+        core::iter::zip(H.data, data).all(|(a, b)| a == b),
         "Arrays are not equal"
     );
 
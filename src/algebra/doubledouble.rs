@@ -0,0 +1,877 @@
+#![allow(non_snake_case)]
+#![allow(clippy::suspicious_arithmetic_impl)]
+#![allow(clippy::suspicious_op_assign_impl)]
+#![allow(clippy::assign_op_pattern)]
+
+// ---------------------------------------------------
+// Double-double floating point type.
+//
+// A value is stored as an unevaluated sum hi + lo of two f64 values
+// with |lo| ≤ ulp(hi)/2, giving about 32 significant decimal digits.
+// Arithmetic follows the algorithms of the QD library (Hida, Li and
+// Bailey, "Library for double-double and quad-double arithmetic").
+// ---------------------------------------------------
+
+use num_traits::{Float, FloatConst, FromPrimitive, Num, One, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::f64::consts;
+use std::fmt::{self, Display, LowerExp};
+use std::num::FpCategory;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/// Double-double precision floating point type, for solving
+/// badly conditioned problems beyond the accuracy of `f64`.
+///
+/// `DoubleDouble` implements [`FloatT`](crate::algebra::FloatT) with
+/// a machine epsilon of `2⁻¹⁰⁴ ≈ 4.9e-32`.  Arithmetic, square roots,
+/// exponentials, logarithms and powers are computed to full double-double
+/// precision.   Trigonometric functions are evaluated in `f64` precision
+/// and are not used by the solver.   The exponent range is that of `f64`.
+///
+/// Requires the `higher-precision` feature, and can not be used with
+/// the `sdp` or `faer-sparse` features, which require native types.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DoubleDouble {
+    hi: f64,
+    lo: f64,
+}
+
+// error free transformations
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+#[inline]
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+#[inline]
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+impl DoubleDouble {
+    /// Construct from the unevaluated sum `hi + lo`.
+    pub fn new(hi: f64, lo: f64) -> Self {
+        let (hi, lo) = two_sum(hi, lo);
+        Self::renormalized(hi, lo)
+    }
+
+    /// The leading component
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    /// The trailing component
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    const fn from_parts(hi: f64, lo: f64) -> Self {
+        Self { hi, lo }
+    }
+
+    // non-finite values are carried in hi only
+    #[inline]
+    fn renormalized(hi: f64, lo: f64) -> Self {
+        if hi.is_finite() {
+            Self { hi, lo }
+        } else {
+            Self { hi, lo: 0.0 }
+        }
+    }
+
+    #[inline]
+    fn mul_f64(self, b: f64) -> Self {
+        let (p1, p2) = two_prod(self.hi, b);
+        let (hi, lo) = quick_two_sum(p1, p2 + self.lo * b);
+        Self::renormalized(hi, lo)
+    }
+
+    #[inline]
+    fn sqr(self) -> Self {
+        let (p1, p2) = two_prod(self.hi, self.hi);
+        let p2 = p2 + 2.0 * self.hi * self.lo + self.lo * self.lo;
+        let (hi, lo) = quick_two_sum(p1, p2);
+        Self::renormalized(hi, lo)
+    }
+
+    // multiply by 2^k, exactly unless the result over- or underflows
+    fn ldexp(self, k: i32) -> Self {
+        let (k1, k2) = (k / 2, k - k / 2);
+        let (f1, f2) = (f64::powi(2.0, k1), f64::powi(2.0, k2));
+        Self::renormalized(self.hi * f1 * f2, self.lo * f1 * f2)
+    }
+
+    // decimal digits d₀.d₁d₂... and exponent e with self = ±d₀.d₁d₂... × 10ᵉ,
+    // rounded to `ndigits` significant digits.  Requires a finite nonzero value.
+    fn to_digits(self, ndigits: usize) -> (Vec<u8>, i32) {
+        let ten = Self::from(10.0);
+        let mut r = self.abs();
+        let mut e = r.hi.log10().floor() as i32;
+
+        r = r / ten.powi(e);
+        if r >= ten {
+            r = r / ten;
+            e += 1;
+        } else if r < Self::one() {
+            r = r * ten;
+            e -= 1;
+        }
+
+        // one guard digit for rounding
+        let mut digits = Vec::with_capacity(ndigits + 1);
+        for _ in 0..=ndigits {
+            let d = r.hi.floor().clamp(0.0, 9.0);
+            digits.push(d as i32);
+            r = (r - Self::from(d)) * ten;
+        }
+
+        // correct for digits produced from slightly negative remainders
+        for i in (1..digits.len()).rev() {
+            if digits[i] < 0 {
+                digits[i] += 10;
+                digits[i - 1] -= 1;
+            } else if digits[i] > 9 {
+                digits[i] -= 10;
+                digits[i - 1] += 1;
+            }
+        }
+
+        // round
+        let guard = digits.pop().unwrap();
+        if guard >= 5 {
+            let mut i = digits.len();
+            while i > 0 {
+                i -= 1;
+                digits[i] += 1;
+                if digits[i] < 10 {
+                    break;
+                }
+                digits[i] = 0;
+                if i == 0 {
+                    digits.insert(0, 1);
+                    digits.pop();
+                    e += 1;
+                }
+            }
+        }
+
+        (digits.into_iter().map(|d| d as u8).collect(), e)
+    }
+
+    // formatting of special values, returning true if handled
+    fn fmt_special(&self, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+        if self.hi.is_nan() {
+            Some(f.pad("NaN"))
+        } else if self.hi.is_infinite() {
+            Some(f.pad(if self.hi > 0.0 { "inf" } else { "-inf" }))
+        } else {
+            None
+        }
+    }
+}
+
+// ---------------------------------------------------
+// conversions
+// ---------------------------------------------------
+
+impl From<f64> for DoubleDouble {
+    fn from(x: f64) -> Self {
+        Self::from_parts(x, 0.0)
+    }
+}
+
+impl From<f32> for DoubleDouble {
+    fn from(x: f32) -> Self {
+        Self::from_parts(x as f64, 0.0)
+    }
+}
+
+impl From<DoubleDouble> for f64 {
+    fn from(x: DoubleDouble) -> Self {
+        x.hi
+    }
+}
+
+impl ToPrimitive for DoubleDouble {
+    fn to_i64(&self) -> Option<i64> {
+        let t = self.trunc();
+        if !t.hi.is_finite() {
+            return None;
+        }
+        (t.hi as i128 + t.lo as i128).try_into().ok()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        let t = self.trunc();
+        if !t.hi.is_finite() {
+            return None;
+        }
+        (t.hi as i128 + t.lo as i128).try_into().ok()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.hi)
+    }
+}
+
+impl FromPrimitive for DoubleDouble {
+    fn from_i64(n: i64) -> Option<Self> {
+        let hi = n as f64;
+        let lo = (n as i128 - hi as i128) as f64;
+        Some(Self::new(hi, lo))
+    }
+    fn from_u64(n: u64) -> Option<Self> {
+        let hi = n as f64;
+        let lo = (n as i128 - hi as i128) as f64;
+        Some(Self::new(hi, lo))
+    }
+    fn from_f64(x: f64) -> Option<Self> {
+        Some(Self::from(x))
+    }
+}
+
+impl num_traits::NumCast for DoubleDouble {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        n.to_f64().map(<Self as From<f64>>::from)
+    }
+}
+
+/// Error returned when parsing a [`DoubleDouble`] from a string fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDoubleDoubleError;
+
+impl Display for ParseDoubleDoubleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid double-double literal")
+    }
+}
+
+impl std::error::Error for ParseDoubleDoubleError {}
+
+impl FromStr for DoubleDouble {
+    type Err = ParseDoubleDoubleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.to_ascii_lowercase().as_str() {
+            "nan" => return Ok(Self::nan()),
+            "inf" | "+inf" | "infinity" => return Ok(Self::infinity()),
+            "-inf" | "-infinity" => return Ok(Self::neg_infinity()),
+            _ => {}
+        }
+
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(i) => (
+                &s[..i],
+                s[i + 1..]
+                    .parse::<i32>()
+                    .map_err(|_| ParseDoubleDoubleError)?,
+            ),
+            None => (s, 0),
+        };
+        let (negative, mantissa) = match mantissa.as_bytes().first() {
+            Some(b'-') => (true, &mantissa[1..]),
+            Some(b'+') => (false, &mantissa[1..]),
+            _ => (false, mantissa),
+        };
+
+        let ten = Self::from(10.0);
+        let mut r = Self::zero();
+        let mut scale = exponent;
+        let (mut ndigits, mut seen_point) = (0, false);
+        for c in mantissa.chars() {
+            match c {
+                '0'..='9' => {
+                    r = r * ten + Self::from((c as u8 - b'0') as f64);
+                    ndigits += 1;
+                    if seen_point {
+                        scale -= 1;
+                    }
+                }
+                '.' if !seen_point => seen_point = true,
+                _ => return Err(ParseDoubleDoubleError),
+            }
+        }
+        if ndigits == 0 {
+            return Err(ParseDoubleDoubleError);
+        }
+
+        r = r * ten.powi(scale);
+        Ok(if negative { -r } else { r })
+    }
+}
+
+// ---------------------------------------------------
+// formatting
+// ---------------------------------------------------
+
+// significant digits printed by default
+const DISPLAY_DIGITS: usize = 32;
+
+impl LowerExp for DoubleDouble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(res) = self.fmt_special(f) {
+            return res;
+        }
+        let sign = if self.is_sign_negative() { "-" } else { "" };
+        let prec = f.precision();
+
+        let s = if self.is_zero() {
+            let zeros = "0".repeat(prec.unwrap_or(0));
+            let point = if zeros.is_empty() { "" } else { "." };
+            format!("{}0{}{}e0", sign, point, zeros)
+        } else {
+            let (digits, e) = self.to_digits(prec.map_or(DISPLAY_DIGITS, |p| p + 1));
+            let mut digits: String = digits.iter().map(|d| (b'0' + d) as char).collect();
+            if prec.is_none() {
+                digits.truncate(digits.trim_end_matches('0').len().max(1));
+            }
+            let (first, rest) = digits.split_at(1);
+            let point = if rest.is_empty() { "" } else { "." };
+            format!("{}{}{}{}e{}", sign, first, point, rest, e)
+        };
+        f.pad_integral(true, "", &s)
+    }
+}
+
+impl Display for DoubleDouble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(res) = self.fmt_special(f) {
+            return res;
+        }
+
+        let sign = if self.is_sign_negative() { "-" } else { "" };
+        let (digits, e) = if self.is_zero() {
+            (vec![0], 0)
+        } else {
+            match f.precision() {
+                // digits up to the requested decimal place
+                Some(p) => {
+                    let e = self.abs().hi.log10().floor() as i32;
+                    let nd = (e + 1 + p as i32).max(1) as usize;
+                    self.to_digits(nd)
+                }
+                None => self.to_digits(DISPLAY_DIGITS),
+            }
+        };
+
+        // place the decimal point
+        let mut int_part = String::new();
+        let mut frac_part = String::new();
+        for (i, d) in digits.iter().enumerate() {
+            let pos = e - i as i32;
+            let c = (b'0' + d) as char;
+            if pos >= 0 {
+                int_part.push(c);
+            } else {
+                frac_part.push(c);
+            }
+        }
+        if e >= digits.len() as i32 {
+            int_part.push_str(&"0".repeat((e + 1) as usize - digits.len()));
+        }
+        if e < 0 {
+            frac_part = "0".repeat((-e - 1) as usize) + &frac_part;
+        }
+        if int_part.is_empty() {
+            int_part.push('0');
+        }
+
+        match f.precision() {
+            Some(p) => {
+                frac_part.truncate(p);
+                frac_part.push_str(&"0".repeat(p - frac_part.len()));
+            }
+            None => frac_part.truncate(frac_part.trim_end_matches('0').len()),
+        }
+
+        let s = if frac_part.is_empty() {
+            format!("{}{}", sign, int_part)
+        } else {
+            format!("{}{}.{}", sign, int_part, frac_part)
+        };
+        f.pad_integral(true, "", &s)
+    }
+}
+
+// ---------------------------------------------------
+// arithmetic
+// ---------------------------------------------------
+
+impl PartialOrd for DoubleDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.hi.partial_cmp(&other.hi) {
+            Some(Ordering::Equal) => self.lo.partial_cmp(&other.lo),
+            ord => ord,
+        }
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_parts(-self.hi, -self.lo)
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+    fn add(self, b: Self) -> Self {
+        let (s1, s2) = two_sum(self.hi, b.hi);
+        if !s1.is_finite() {
+            return Self::from(s1);
+        }
+        let (t1, t2) = two_sum(self.lo, b.lo);
+        let (s1, s2) = quick_two_sum(s1, s2 + t1);
+        let (hi, lo) = quick_two_sum(s1, s2 + t2);
+        Self::renormalized(hi, lo)
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+    fn sub(self, b: Self) -> Self {
+        self + (-b)
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+    fn mul(self, b: Self) -> Self {
+        let (p1, p2) = two_prod(self.hi, b.hi);
+        if !p1.is_finite() {
+            return Self::from(p1);
+        }
+        let p2 = p2 + (self.hi * b.lo + self.lo * b.hi);
+        let (hi, lo) = quick_two_sum(p1, p2);
+        Self::renormalized(hi, lo)
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+    fn div(self, b: Self) -> Self {
+        let q1 = self.hi / b.hi;
+        if !q1.is_finite() {
+            return Self::from(q1);
+        }
+        let r = self - b.mul_f64(q1);
+        let q2 = r.hi / b.hi;
+        let r = r - b.mul_f64(q2);
+        let q3 = r.hi / b.hi;
+
+        let (q1, q2) = quick_two_sum(q1, q2);
+        Self::from_parts(q1, q2) + Self::from(q3)
+    }
+}
+
+impl Rem for DoubleDouble {
+    type Output = Self;
+    fn rem(self, b: Self) -> Self {
+        self - (self / b).trunc() * b
+    }
+}
+
+macro_rules! impl_assign_op {
+    ($tr:ident, $fn:ident, $op:tt) => {
+        impl $tr for DoubleDouble {
+            #[inline]
+            fn $fn(&mut self, b: Self) {
+                *self = *self $op b;
+            }
+        }
+    };
+}
+impl_assign_op!(AddAssign, add_assign, +);
+impl_assign_op!(SubAssign, sub_assign, -);
+impl_assign_op!(MulAssign, mul_assign, *);
+impl_assign_op!(DivAssign, div_assign, /);
+impl_assign_op!(RemAssign, rem_assign, %);
+
+impl Zero for DoubleDouble {
+    fn zero() -> Self {
+        Self::from(0.0)
+    }
+    fn is_zero(&self) -> bool {
+        self.hi == 0.0
+    }
+}
+
+impl One for DoubleDouble {
+    fn one() -> Self {
+        Self::from(1.0)
+    }
+}
+
+impl Num for DoubleDouble {
+    type FromStrRadixErr = ParseDoubleDoubleError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if radix != 10 {
+            return Err(ParseDoubleDoubleError);
+        }
+        s.parse()
+    }
+}
+
+// ---------------------------------------------------
+// elementary functions
+// ---------------------------------------------------
+
+// Taylor series for exp(r) - 1, accurate for |r| < 1e-3
+fn expm1_taylor(r: DoubleDouble) -> DoubleDouble {
+    let mut term = r;
+    let mut sum = r;
+    for k in 2..20 {
+        term = term * r / DoubleDouble::from(k as f64);
+        sum += term;
+        if term.hi.abs() <= f64::EPSILON * f64::EPSILON * sum.hi.abs() {
+            break;
+        }
+    }
+    sum
+}
+
+macro_rules! dd_const {
+    ($($name:ident = ($hi:expr, $lo:expr)),* $(,)?) => {
+        impl FloatConst for DoubleDouble {
+            $(
+                #[inline]
+                fn $name() -> Self {
+                    Self::from_parts($hi, $lo)
+                }
+            )*
+        }
+    };
+}
+
+dd_const! {
+    E = (consts::E, 1.4456468917292502e-16),
+    PI = (consts::PI, 1.2246467991473532e-16),
+    FRAC_1_PI = (consts::FRAC_1_PI, -1.9678676675182486e-17),
+    FRAC_1_SQRT_2 = (consts::FRAC_1_SQRT_2, -4.833646656726457e-17),
+    FRAC_2_PI = (consts::FRAC_2_PI, -3.935735335036497e-17),
+    FRAC_2_SQRT_PI = (consts::FRAC_2_SQRT_PI, 1.533545961316588e-17),
+    FRAC_PI_2 = (consts::FRAC_PI_2, 6.123233995736766e-17),
+    FRAC_PI_3 = (consts::FRAC_PI_3, -1.072081766451091e-16),
+    FRAC_PI_4 = (consts::FRAC_PI_4, 3.061616997868383e-17),
+    FRAC_PI_6 = (consts::FRAC_PI_6, -5.360408832255455e-17),
+    FRAC_PI_8 = (consts::FRAC_PI_8, 1.5308084989341915e-17),
+    LN_10 = (consts::LN_10, -2.1707562233822494e-16),
+    LN_2 = (consts::LN_2, 2.3190468138462996e-17),
+    LOG10_E = (consts::LOG10_E, 1.098319650216765e-17),
+    LOG2_E = (consts::LOG2_E, 2.0355273740931033e-17),
+    SQRT_2 = (consts::SQRT_2, -9.667293313452913e-17),
+}
+
+// evaluate a function of lower precision in f64
+macro_rules! via_f64 {
+    ($($fn:ident),*) => {
+        $(
+            #[inline]
+            fn $fn(self) -> Self {
+                Self::from(self.hi.$fn())
+            }
+        )*
+    };
+}
+
+impl Float for DoubleDouble {
+    fn nan() -> Self {
+        Self::from(f64::NAN)
+    }
+    fn infinity() -> Self {
+        Self::from(f64::INFINITY)
+    }
+    fn neg_infinity() -> Self {
+        Self::from(f64::NEG_INFINITY)
+    }
+    fn neg_zero() -> Self {
+        Self::from(-0.0)
+    }
+    fn min_value() -> Self {
+        Self::from(f64::MIN)
+    }
+    fn min_positive_value() -> Self {
+        Self::from(f64::MIN_POSITIVE)
+    }
+    fn epsilon() -> Self {
+        // 2⁻¹⁰⁴
+        Self::from(4.930380657631324e-32)
+    }
+    fn max_value() -> Self {
+        Self::from(f64::MAX)
+    }
+
+    fn is_nan(self) -> bool {
+        self.hi.is_nan() || self.lo.is_nan()
+    }
+    fn is_infinite(self) -> bool {
+        self.hi.is_infinite()
+    }
+    fn is_finite(self) -> bool {
+        self.hi.is_finite()
+    }
+    fn is_normal(self) -> bool {
+        self.hi.is_normal()
+    }
+    fn classify(self) -> FpCategory {
+        self.hi.classify()
+    }
+
+    fn floor(self) -> Self {
+        let hi = self.hi.floor();
+        if hi == self.hi {
+            Self::new(hi, self.lo.floor())
+        } else {
+            Self::from(hi)
+        }
+    }
+    fn ceil(self) -> Self {
+        let hi = self.hi.ceil();
+        if hi == self.hi {
+            Self::new(hi, self.lo.ceil())
+        } else {
+            Self::from(hi)
+        }
+    }
+    fn round(self) -> Self {
+        // round half away from zero
+        let half = Self::from(0.5);
+        if self.is_sign_negative() {
+            -((-self) + half).floor()
+        } else {
+            (self + half).floor()
+        }
+    }
+    fn trunc(self) -> Self {
+        if self.is_sign_negative() {
+            self.ceil()
+        } else {
+            self.floor()
+        }
+    }
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+
+    fn abs(self) -> Self {
+        if self.is_sign_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+    fn signum(self) -> Self {
+        Self::from(self.hi.signum())
+    }
+    fn is_sign_positive(self) -> bool {
+        self.hi.is_sign_positive()
+    }
+    fn is_sign_negative(self) -> bool {
+        self.hi.is_sign_negative()
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+    fn recip(self) -> Self {
+        Self::one() / self
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let mut base = self;
+        let mut m = n.unsigned_abs();
+        let mut out = Self::one();
+        while m > 0 {
+            if m & 1 == 1 {
+                out *= base;
+            }
+            base = base.sqr();
+            m >>= 1;
+        }
+        if n < 0 {
+            out.recip()
+        } else {
+            out
+        }
+    }
+    fn powf(self, n: Self) -> Self {
+        if self.is_zero() {
+            return if n.is_zero() {
+                Self::one()
+            } else if n.is_sign_positive() {
+                Self::zero()
+            } else {
+                Self::infinity()
+            };
+        }
+        (n * self.ln()).exp()
+    }
+
+    fn sqrt(self) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+        if self.hi < 0.0 || !self.hi.is_finite() {
+            return Self::from(self.hi.sqrt());
+        }
+        // one Newton step from the f64 estimate
+        let x = 1.0 / self.hi.sqrt();
+        let ax = self.hi * x;
+        Self::from(ax) + Self::from((self - Self::from(ax).sqr()).hi * (x * 0.5))
+    }
+
+    fn exp(self) -> Self {
+        if self.hi > 709.78 {
+            return Self::infinity();
+        }
+        if self.hi < -745.2 {
+            return Self::zero();
+        }
+        if self.is_nan() {
+            return self;
+        }
+
+        // exp(x) = 2ᵏ exp(r)ᴺ with x = k ln2 + N r, N = 2¹⁰
+        const NSQ: i32 = 10;
+        let k = (self.hi / consts::LN_2).round();
+        let r = (self - Self::LN_2().mul_f64(k)).ldexp(-NSQ);
+
+        // square exp(r) - 1 to keep full relative precision
+        let mut s = expm1_taylor(r);
+        for _ in 0..NSQ {
+            s = s.mul_f64(2.0) + s.sqr();
+        }
+        (s + Self::one()).ldexp(k as i32)
+    }
+    fn exp2(self) -> Self {
+        (self * Self::LN_2()).exp()
+    }
+    fn exp_m1(self) -> Self {
+        if self.hi.abs() < 1e-3 {
+            expm1_taylor(self)
+        } else {
+            self.exp() - Self::one()
+        }
+    }
+
+    fn ln(self) -> Self {
+        if self.is_zero() {
+            return Self::neg_infinity();
+        }
+        if self.hi < 0.0 || !self.hi.is_finite() {
+            return Self::from(self.hi.ln());
+        }
+        // one Newton step for exp(y) = x from the f64 estimate
+        let y = Self::from(self.hi.ln());
+        y + self * (-y).exp() - Self::one()
+    }
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+    fn log2(self) -> Self {
+        self.ln() * Self::LOG2_E()
+    }
+    fn log10(self) -> Self {
+        self.ln() * Self::LOG10_E()
+    }
+    fn ln_1p(self) -> Self {
+        (Self::one() + self).ln()
+    }
+
+    fn to_degrees(self) -> Self {
+        self * Self::from(180.0) / Self::PI()
+    }
+    fn to_radians(self) -> Self {
+        self * Self::PI() / Self::from(180.0)
+    }
+
+    fn max(self, other: Self) -> Self {
+        if self.is_nan() || other > self {
+            other
+        } else {
+            self
+        }
+    }
+    fn min(self, other: Self) -> Self {
+        if self.is_nan() || other < self {
+            other
+        } else {
+            self
+        }
+    }
+    fn abs_sub(self, other: Self) -> Self {
+        if self <= other {
+            Self::zero()
+        } else {
+            self - other
+        }
+    }
+
+    fn cbrt(self) -> Self {
+        if self.is_zero() || !self.hi.is_finite() {
+            return self;
+        }
+        // one Newton step for y³ = x from the f64 estimate
+        let y = Self::from(self.hi.cbrt());
+        y - (y.sqr() * y - self) / (y.sqr() * Self::from(3.0))
+    }
+    fn hypot(self, other: Self) -> Self {
+        (self.sqr() + other.sqr()).sqrt()
+    }
+
+    fn sinh(self) -> Self {
+        let e = self.exp();
+        (e - e.recip()).mul_f64(0.5)
+    }
+    fn cosh(self) -> Self {
+        let e = self.exp();
+        (e + e.recip()).mul_f64(0.5)
+    }
+    fn tanh(self) -> Self {
+        if self.hi.abs() > 40.0 {
+            return self.signum();
+        }
+        let e = self.mul_f64(2.0).exp();
+        (e - Self::one()) / (e + Self::one())
+    }
+    fn asinh(self) -> Self {
+        let a = self.abs();
+        let y = (a + (a.sqr() + Self::one()).sqrt()).ln();
+        if self.is_sign_negative() {
+            -y
+        } else {
+            y
+        }
+    }
+    fn acosh(self) -> Self {
+        (self + (self.sqr() - Self::one()).sqrt()).ln()
+    }
+    fn atanh(self) -> Self {
+        ((Self::one() + self) / (Self::one() - self))
+            .ln()
+            .mul_f64(0.5)
+    }
+
+    via_f64!(sin, cos, tan, asin, acos, atan);
+
+    fn atan2(self, other: Self) -> Self {
+        Self::from(self.hi.atan2(other.hi))
+    }
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.hi.integer_decode()
+    }
+}
@@ -1,25 +1,31 @@
 use thiserror::Error;
 
 /// Error type returned by matrix concatenation operations.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MatrixConcatenationError {
     #[error("Incompatible dimensions")]
     IncompatibleDimension,
+    #[error("Dimensions or number of entries of the result overflow usize")]
+    DimensionOverflow,
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 /// Error type returned by sparse matrix assembly operations.
 pub enum SparseFormatError {
     #[error("Matrix dimension fields and/or array lengths are incompatible")]
     IncompatibleDimension,
     #[error("Data is not sorted by row index within each column")]
     BadRowOrdering,
+    #[error("Data has repeated entries with the same row and column")]
+    DuplicateEntries,
     #[error("Row value exceeds the matrix row dimension")]
     BadRowval,
     #[error("Bad column pointer values")]
     BadColptr,
     #[error("sparsity pattern mismatch")]
     SparsityMismatch,
+    #[error("Index value exceeds the range of the target index type")]
+    IndexOverflow,
 }
 
 /// Error type returned by BLAS-like dense factorization routines.  Errors
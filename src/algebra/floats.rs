@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
+use core::fmt::{Debug, Display, LowerExp};
 use num_traits::{Float, FloatConst, FromPrimitive, NumAssign};
-use std::fmt::{Debug, Display, LowerExp};
 
 #[cfg(feature = "sdp")]
 use crate::algebra::dense::BlasFloatT;
@@ -13,6 +13,7 @@ use crate::algebra::dense::BlasFloatT;
 pub trait CoreFloatT:
     'static
     + Send
+    + Sync
     + Float
     + FloatConst
     + NumAssign
@@ -28,6 +29,7 @@ pub trait CoreFloatT:
 impl<T> CoreFloatT for T where
     T: 'static
         + Send
+        + Sync
         + Float
         + FloatConst
         + NumAssign
@@ -81,7 +83,14 @@ cfg_if::cfg_if! {
 /// floating point type provided that it satisfies the trait bounds of `CoreFloatT`.
 ///
 /// `FloatT` relies on [`num_traits`](num_traits) for most of its constituent trait bounds.
-pub trait FloatT: CoreFloatT + MaybeBlasFloatT + MaybeFaerFloatT {}
+pub trait FloatT: CoreFloatT + MaybeBlasFloatT + MaybeFaerFloatT {
+    /// Fused multiply-add.  Computes `a * b + c` with a single rounding
+    /// step, e.g. via `f64::mul_add` for native types.
+    #[inline]
+    fn fma(a: Self, b: Self, c: Self) -> Self {
+        a.mul_add(b, c)
+    }
+}
 impl<T> FloatT for T where T: CoreFloatT + MaybeBlasFloatT + MaybeFaerFloatT {}
 
 /// Trait for convering Rust primitives to [`FloatT`](crate::algebra::FloatT)
@@ -103,7 +112,7 @@ macro_rules! impl_as_FloatT {
     ($ty:ty, $ident:ident) => {
         impl<T> AsFloatT<T> for $ty
         where
-            T: std::ops::Mul<T, Output = T> + FromPrimitive + 'static,
+            T: core::ops::Mul<T, Output = T> + FromPrimitive + 'static,
         {
             #[inline]
             fn as_T(&self) -> T {
@@ -2,6 +2,9 @@
 // through these core traits, which are implemented generically
 // for floats of type FloatT.
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
 /// Scalar operations on [`FloatT`](crate::algebra::FloatT)
 
 pub trait ScalarMath {
@@ -25,6 +28,20 @@ pub trait VectorMath<T> {
     /// Make a new vector from a subset of elements
     fn select(&self, index: &[bool]) -> Vec<T>;
 
+    /// Swap the elements `self[i]` and `self[j]`
+    fn swap_elements(&mut self, i: usize, j: usize) -> &mut Self;
+
+    /// Permuted copy of a vector, i.e. a vector `y` with `y[i] = self[perm[i]]`
+    fn apply_permutation(&self, perm: &[usize]) -> Vec<T>;
+
+    /// In place version of [apply_permutation](crate::algebra::VectorMath::apply_permutation).
+    ///
+    /// Elements are moved along the cycles of `perm`, with each cycle identified
+    /// by its smallest index, so that no additional memory is allocated.   This is
+    /// O(n) time for permutations made up of short cycles, but can approach O(n²)
+    /// for a permutation with a single long cycle.
+    fn apply_permutation_inplace(&mut self, perm: &[usize]) -> &mut Self;
+
     /// Apply an elementwise operation on a vector.
     fn scalarop(&mut self, op: impl Fn(T) -> T) -> &mut Self;
 
@@ -65,9 +82,19 @@ pub trait VectorMath<T> {
     /// Dot product
     fn dot(&self, y: &Self) -> T;
 
+    /// Dot product, accumulated using fused multiply-add operations
+    fn fma_dot(&self, y: &Self) -> T;
+
     // computes dot(z + αdz,s + αds) without intermediate allocation
     fn dot_shifted(z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T;
 
+    /// Dot product with a sparse vector given by its nonzero
+    /// `indices` and `values`, i.e. `Σ self[indices[i]] * values[i]`
+    fn sparse_dot(&self, indices: &[usize], values: &[T]) -> T;
+
+    /// Standard Euclidian or 2-norm of the entries of `self` at `indices`
+    fn sparse_norm(&self, indices: &[usize]) -> T;
+
     /// Standard Euclidian or 2-norm distance from `self` to `y`
     fn dist(&self, y: &Self) -> T;
 
@@ -77,6 +104,25 @@ pub trait VectorMath<T> {
     /// Sum of squares of the elements.
     fn sumsq(&self) -> T;
 
+    /// Running sums of the products in [`dot`](crate::algebra::VectorMath::dot),
+    /// i.e. a vector with element `k` equal to `Σᵢ₌₀..ₖ self[i] * y[i]`.
+    ///
+    /// Diagnostic only, e.g. for finding where cancellation occurs in a dot product.
+    fn dot_accumulate(&self, y: &Self) -> Vec<T>;
+
+    /// Running sums of the squares in [`sumsq`](crate::algebra::VectorMath::sumsq).
+    ///
+    /// Diagnostic only, e.g. for checking the stability of norm computations.
+    fn sumsq_accumulate(&self) -> Vec<T>;
+
+    /// Bilinear form `selfᵀ M y` for a diagonal `M = diag(m)`, i.e.
+    /// `Σ self[i] * m[i] * y[i]`
+    fn bilinear_form_diag(&self, m: &Self, y: &Self) -> T;
+
+    /// Squared 2-norm of an elementwise scaling of `self` by `m`, i.e.
+    /// the square of [`norm_scaled`](crate::algebra::VectorMath::norm_scaled)
+    fn bilinear_form_diag_squared(&self, m: &Self) -> T;
+
     /// 2-norm
     fn norm(&self) -> T;
 
@@ -98,10 +144,10 @@ pub trait VectorMath<T> {
     // Inf-norm of vector difference
     fn norm_inf_diff(&self, b: &Self) -> T;
 
-    /// Minimum value in vector
+    /// Minimum value in vector, or `+∞` if the vector is empty
     fn minimum(&self) -> T;
 
-    /// Maximum value in vector
+    /// Maximum value in vector, or `-∞` if the vector is empty
     fn maximum(&self) -> T;
 
     /// Mean value in vector
@@ -116,6 +162,10 @@ pub trait VectorMath<T> {
     /// BLAS-like shift and scale in place.  Produces `self = a*x+b*self`
     fn axpby(&mut self, a: T, x: &Self, b: T) -> &mut Self;
 
+    /// Same as [axpby](crate::algebra::VectorMath::axpby), but using fused
+    /// multiply-add operations.  Produces `self = a*x+b*self`
+    fn fma_axpby(&mut self, a: T, x: &Self, b: T) -> &mut Self;
+
     /// BLAS-like shift and scale, non in-place version.  Produces `self = a*x+b*y`
     fn waxpby(&mut self, a: T, x: &Self, b: T, y: &Self) -> &mut Self;
 }
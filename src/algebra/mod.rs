@@ -19,20 +19,39 @@ mod matrix_types;
 mod scalarmath;
 mod utils;
 mod vecmath;
+mod vector_slice;
+#[cfg(not(feature = "std"))]
+pub(crate) use crate::alloc_prelude::*;
 pub use error_types::*;
 pub use floats::*;
 pub use math_traits::*;
 pub use matrix_traits::*;
 pub(crate) use matrix_types::*;
+pub use matrix_types::MatrixTriangle;
 pub(crate) use scalarmath::*;
 pub(crate) use utils::*;
+pub use vector_slice::*;
 
 // matrix implementations
 mod csc;
 pub use csc::*;
+mod csr;
+pub use csr::*;
+
+// lane-blocked vector kernels
+#[cfg(feature = "blocked-kernels")]
+pub(crate) mod blocked;
 
 mod densesym3x3;
 pub(crate) use densesym3x3::*;
+mod vec3;
+pub(crate) use vec3::*;
+
+// extended precision floating point type
+#[cfg(feature = "higher-precision")]
+mod doubledouble;
+#[cfg(feature = "higher-precision")]
+pub use doubledouble::*;
 
 #[cfg(feature = "sdp")]
 mod dense;
@@ -24,6 +24,16 @@ pub(crate) fn triangular_number(k: usize) -> usize {
     (k * (k + 1)) >> 1
 }
 
+// triangular number of k, or None if it overflows usize
+pub(crate) fn checked_triangular_number(k: usize) -> Option<usize> {
+    // one of k and k+1 is even, so halve it before the product
+    if k % 2 == 0 {
+        (k / 2).checked_mul(k.checked_add(1)?)
+    } else {
+        k.checked_mul(k / 2 + 1)
+    }
+}
+
 #[cfg_attr(not(feature = "sdp"), allow(dead_code))]
 pub(crate) fn triangular_index(k: usize) -> usize {
     // 0-based index into a packed triangle. Same as:
@@ -67,7 +77,7 @@ pub(crate) fn coord_to_upper_triangular_index(coord: (usize, usize)) -> usize {
 // for inputs < 2^53 or so (otherwise possibly off-by-one).
 #[cfg_attr(not(feature = "sdp"), allow(dead_code))]
 fn isqrt(v: usize) -> usize {
-    (v as f64).sqrt() as usize
+    num_traits::Float::sqrt(v as f64) as usize
 }
 
 #[test]
@@ -79,6 +89,21 @@ fn test_triangular_number() {
     }
 }
 
+#[test]
+fn test_checked_triangular_number() {
+    for k in 0..100 {
+        assert_eq!(checked_triangular_number(k), Some(triangular_number(k)));
+    }
+    // compare with exact values near the overflow threshold
+    let k0 = ((usize::MAX as f64) * 2.).sqrt() as usize;
+    for k in k0 - 3..k0 + 3 {
+        let exact = (k as u128) * (k as u128 + 1) / 2;
+        let expected = usize::try_from(exact).ok();
+        assert_eq!(checked_triangular_number(k), expected);
+    }
+    assert_eq!(checked_triangular_number(usize::MAX), None);
+}
+
 #[test]
 fn test_triangular_index() {
     let v = vec![0, 1, 2, 3, 4, 5, 6];
@@ -0,0 +1,49 @@
+use crate::algebra::blocked;
+use crate::algebra::*;
+
+// deterministic pseudo-random values in [-1,1)
+fn test_vector(len: usize, state: &mut u64) -> Vec<f64> {
+    (0..len)
+        .map(|_| {
+            *state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            2.0 * ((*state >> 11) as f64 / (1u64 << 53) as f64) - 1.0
+        })
+        .collect()
+}
+
+#[test]
+fn test_blocked_kernels_match_scalar() {
+    let mut state = 1;
+
+    // lengths with and without remainders after full blocks
+    for len in (0..40).chain([100, 1001]) {
+        let x = test_vector(len, &mut state);
+        let y = test_vector(len, &mut state);
+        let dx = test_vector(len, &mut state);
+        let dy = test_vector(len, &mut state);
+        let tol = 1e-14 * (len as f64 + 1.);
+
+        assert!(f64::abs(blocked::dot(&x, &y) - x.dot(&y)) <= tol);
+        assert!(f64::abs(blocked::sumsq(&x) - x.sumsq()) <= tol);
+        let shifted = <[f64] as VectorMath<f64>>::dot_shifted(&x, &y, &dx, &dy, 0.3);
+        assert!(f64::abs(blocked::dot_shifted(&x, &y, &dx, &dy, 0.3) - shifted) <= 4. * tol);
+
+        // elementwise kernels are identical
+        let (mut w1, mut w2) = (y.clone(), y.clone());
+        blocked::axpby(&mut w1, 0.7, &x, -1.3);
+        w2.axpby(0.7, &x, -1.3);
+        assert_eq!(w1, w2);
+
+        blocked::waxpby(&mut w1, -2.1, &x, 0.4, &y);
+        w2.waxpby(-2.1, &x, 0.4, &y);
+        assert_eq!(w1, w2);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_blocked_dot_bad_length() {
+    blocked::dot(&[1., 2., 3.], &[1., 2.]);
+}
@@ -0,0 +1,58 @@
+use crate::algebra::*;
+use num_traits::{Float, FloatConst, FromPrimitive, One, ToPrimitive, Zero};
+
+type DD = DoubleDouble;
+
+#[test]
+fn test_dd_arithmetic() {
+    // 1/3 * 3 = 1 to double-double precision
+    let third = DD::one() / DD::from(3.0);
+    assert!((third * DD::from(3.0) - DD::one()).abs() < DD::from(1e-31));
+    assert!(third.lo() != 0.0);
+
+    // (1 + 2⁻⁶⁰)² - 1 is lost in f64
+    let x = DD::one() + DD::from(f64::powi(2.0, -60));
+    let y = x * x - DD::one();
+    assert!((y - DD::from(f64::powi(2.0, -59))).abs() < DD::from(1e-35));
+
+    let two = DD::from(2.0);
+    assert!((two.sqrt().powi(2) - two).abs() < DD::from(1e-31));
+    assert!((two.sqrt() - DD::SQRT_2()).abs() < DD::from(1e-31));
+    assert!((DD::one().exp() - DD::E()).abs() < DD::from(1e-30));
+    assert!((DD::E().ln() - DD::one()).abs() < DD::from(1e-31));
+    assert!((two.powf(DD::from(0.5)) - DD::SQRT_2()).abs() < DD::from(1e-30));
+    assert!((DD::from(10.0).ln() - DD::LN_10()).abs() < DD::from(1e-30));
+    assert!((DD::from(27.0).cbrt() - DD::from(3.0)).abs() < DD::from(1e-30));
+    assert_eq!(two.powi(-3), DD::from(0.125));
+
+    // rounding functions respect the trailing component
+    let z = DD::new(3.0, -1e-20);
+    assert_eq!(z.floor(), DD::from(2.0));
+    assert_eq!(z.ceil(), DD::from(3.0));
+    assert_eq!(z.round(), DD::from(3.0));
+    assert_eq!((-z).trunc(), DD::from(-2.0));
+
+    assert_eq!(DD::from_i64(i64::MAX).unwrap().to_i64(), Some(i64::MAX));
+    assert!(DD::infinity() > DD::max_value());
+    assert!((DD::infinity() + DD::one()).is_infinite());
+}
+
+#[test]
+fn test_dd_formatting() {
+    let third = DD::one() / DD::from(3.0);
+    assert_eq!(format!("{:.4e}", third), "3.3333e-1");
+    assert_eq!(format!("{:.3e}", DD::from(-12345.0)), "-1.235e4");
+    assert_eq!(format!("{:.2}", DD::from(2.5)), "2.50");
+    assert_eq!(format!("{}", DD::from(0.125)), "0.125");
+    assert_eq!(format!("{}", DD::from(1e6)), "1000000");
+    assert_eq!(format!("{:e}", DD::zero()), "0e0");
+    assert_eq!(format!("{:>8.1e}", DD::from(9.96)), "   1.0e1");
+    assert_eq!(format!("{}", third), "0.33333333333333333333333333333333");
+
+    let x: DD = "0.1".parse().unwrap();
+    assert!((x * DD::from(10.0) - DD::one()).abs() < DD::from(1e-31));
+    assert!(x != DD::from(0.1));
+    let y: DD = "-1.25e-3".parse().unwrap();
+    assert!((y + DD::from(125.0) / DD::from(1e5)).abs() < DD::from(1e-34));
+    assert!("1.2.3".parse::<DD>().is_err());
+}
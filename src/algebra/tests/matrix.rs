@@ -81,6 +81,112 @@ fn test_nrows_ncols_nnz_is_square() {
     assert_eq!(B.nnz(), 8);
 }
 
+#[test]
+fn test_column_row_sparsity() {
+    let A = test_matrix_3x4();
+    assert_eq!(A.column_sparsity(), vec![2, 2, 2, 2]);
+    assert_eq!(A.row_sparsity(), vec![4, 2, 2]);
+    assert_eq!(A.column_sparsity().iter().sum::<usize>(), A.nnz());
+    assert_eq!(A.row_sparsity().iter().sum::<usize>(), A.nnz());
+
+    let B = test_matrix_4x4_triu();
+    assert_eq!(B.column_sparsity(), vec![1, 2, 3, 2]);
+    assert_eq!(B.row_sparsity(), vec![3, 2, 2, 1]);
+    assert_eq!(B.max_column_density(), 3);
+    assert_eq!(B.min_column_density(), 1);
+
+    let Z = CscMatrix::<f64>::zeros((3, 0));
+    assert!(Z.column_sparsity().is_empty());
+    assert_eq!(Z.row_sparsity(), vec![0, 0, 0]);
+    assert_eq!(Z.max_column_density(), 0);
+    assert_eq!(Z.min_column_density(), 0);
+}
+
+#[test]
+fn test_csc_builder_banded() {
+    // pentadiagonal matrix built column by column
+    let n = 2000;
+    let band = |i: usize, j: usize| (i * 7 + j * 3) as f64 + 1.;
+
+    let mut builder = CscBuilder::new(n, n);
+    builder.reserve(5 * n);
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for j in 0..n {
+        let rows: Vec<usize> = (j.saturating_sub(2)..usize::min(j + 3, n)).collect();
+        let vals: Vec<f64> = rows.iter().map(|&i| band(i, j)).collect();
+        builder.push_column(&rows, &vals).unwrap();
+
+        I.extend(&rows);
+        J.extend(std::iter::repeat(j).take(rows.len()));
+        V.extend(&vals);
+    }
+    assert_eq!(builder.ncols_pushed(), n);
+    assert_eq!(builder.nnz(), 5 * n - 6);
+
+    let A = builder.finish();
+    assert!(A.check_format().is_ok());
+    assert_eq!(A, CscMatrix::new_from_triplets(n, n, I, J, V));
+}
+
+#[test]
+fn test_csc_builder_errors() {
+    let mut builder = CscBuilder::<f64>::new(3, 4);
+
+    // rows out of order, repeated or out of bounds
+    assert!(matches!(
+        builder.push_column(&[1, 0], &[1., 2.]),
+        Err(SparseFormatError::BadRowOrdering)
+    ));
+    assert!(matches!(
+        builder.push_column(&[1, 1], &[1., 2.]),
+        Err(SparseFormatError::BadRowOrdering)
+    ));
+    assert!(matches!(
+        builder.push_column(&[0, 3], &[1., 2.]),
+        Err(SparseFormatError::BadRowval)
+    ));
+    assert!(matches!(
+        builder.push_column(&[0, 1], &[1.]),
+        Err(SparseFormatError::IncompatibleDimension)
+    ));
+    assert_eq!(builder.ncols_pushed(), 0);
+
+    // columns out of order
+    builder.push_column(&[0], &[1.]).unwrap();
+    builder.push_column_at(2, &[2], &[3.]).unwrap();
+    assert!(matches!(
+        builder.push_column_at(1, &[0], &[1.]),
+        Err(SparseFormatError::BadColptr)
+    ));
+    assert!(matches!(
+        builder.push_column_at(4, &[0], &[1.]),
+        Err(SparseFormatError::IncompatibleDimension)
+    ));
+
+    // a failed push does not add skipped columns
+    assert!(builder.push_column_at(3, &[5], &[1.]).is_err());
+    assert_eq!(builder.ncols_pushed(), 3);
+
+    // remaining columns are empty
+    let A = builder.finish();
+    assert_eq!(
+        A,
+        CscMatrix::from(&[
+            [1., 0., 0., 0.], //
+            [0., 0., 0., 0.], //
+            [0., 0., 3., 0.], //
+        ])
+    );
+
+    // too many columns
+    let mut builder = CscBuilder::<f64>::new(2, 1);
+    builder.push_column(&[], &[]).unwrap();
+    assert!(matches!(
+        builder.push_column(&[0], &[1.]),
+        Err(SparseFormatError::IncompatibleDimension)
+    ));
+}
+
 #[test]
 fn test_check_format() {
     assert!(test_matrix_3x4().check_format().is_ok());
@@ -138,6 +244,117 @@ fn test_check_format() {
     assert!(A.check_format().is_err());
 }
 
+#[test]
+fn test_new_checked() {
+    let Ap = vec![0, 2, 5, 8, 10];
+    let Ai = vec![0, 2, 0, 1, 3, 0, 1, 2, 2, 3];
+    let Ax = vec![4., 1., -3., 8., -1., 7., -1., 2., -3., 1.];
+
+    let A = CscMatrix::new_checked(4, 4, Ap.clone(), Ai.clone(), Ax.clone()).unwrap();
+    assert_eq!(A, test_matrix_4x4());
+
+    let check = |m, n, Ap: &[usize], Ai: &[usize], Ax: &[f64], error| {
+        assert_eq!(
+            CscMatrix::new_checked(m, n, Ap.to_vec(), Ai.to_vec(), Ax.to_vec()),
+            Err(error)
+        );
+    };
+
+    // colptr of the wrong length, or empty
+    check(
+        4,
+        3,
+        &Ap,
+        &Ai,
+        &Ax,
+        SparseFormatError::IncompatibleDimension,
+    );
+    check(
+        4,
+        4,
+        &[],
+        &Ai,
+        &Ax,
+        SparseFormatError::IncompatibleDimension,
+    );
+
+    // colptr not ending at the number of entries
+    check(
+        4,
+        4,
+        &[0, 2, 5, 8, 9],
+        &Ai,
+        &Ax,
+        SparseFormatError::IncompatibleDimension,
+    );
+
+    // rowval and nzval of different lengths
+    check(
+        4,
+        4,
+        &Ap,
+        &Ai,
+        &Ax[..9],
+        SparseFormatError::IncompatibleDimension,
+    );
+
+    // colptr decreasing
+    check(
+        4,
+        4,
+        &[0, 5, 2, 8, 10],
+        &Ai,
+        &Ax,
+        SparseFormatError::BadColptr,
+    );
+
+    // row index out of bounds
+    check(3, 4, &Ap, &Ai, &Ax, SparseFormatError::BadRowval);
+
+    // unsorted rows are accepted
+    let Ai_unsorted = vec![2, 0, 0, 1, 3, 0, 1, 2, 2, 3];
+    let A = CscMatrix::new_checked(4, 4, Ap, Ai_unsorted, Ax).unwrap();
+    assert_eq!(A.check_canonical(), Err(SparseFormatError::BadRowOrdering));
+}
+
+#[test]
+fn test_canonicalize() {
+    let A = test_matrix_4x4();
+    assert!(A.check_canonical().is_ok());
+
+    // columns 0 and 2 reversed, with the 7.0 in column 2 split in two
+    // and an explicit zero repeated in column 3
+    let mut B = CscMatrix::new(
+        4,
+        4,
+        vec![0, 2, 5, 9, 12],
+        vec![2, 0, 0, 1, 3, 2, 0, 1, 0, 2, 3, 2],
+        vec![1., 4., -3., 8., -1., 2., 3., -1., 4., -3., 1., 0.],
+    );
+    assert!(matches!(
+        B.check_canonical(),
+        Err(SparseFormatError::BadRowOrdering)
+    ));
+    assert!(B.check_format().is_err());
+    assert_eq!(B.canonicalize(), 2);
+    assert!(B.check_format().is_ok());
+    assert_eq!(B.colptr, A.colptr);
+    assert_eq!(B.rowval, A.rowval);
+    assert_eq!(B.nzval, A.nzval);
+
+    // sorted, but with a repeated entry in column 1
+    let mut C = A.clone();
+    C.rowval[4] = 1;
+    assert!(matches!(
+        C.check_canonical(),
+        Err(SparseFormatError::DuplicateEntries)
+    ));
+    assert_eq!(C.canonicalize(), 1);
+    assert_eq!(C.colptr, vec![0, 2, 4, 7, 9]);
+    assert_eq!(C.rowval, vec![0, 2, 0, 1, 0, 1, 2, 2, 3]);
+    assert_eq!(C.nzval, vec![4., 1., -3., 7., 7., -1., 2., -3., 1.]);
+}
+
 #[test]
 fn test_row_sums() {
     let A = test_matrix_3x4();
@@ -146,6 +363,39 @@ fn test_row_sums() {
     assert_eq!(v, vec![-2., 10., -9.]);
 }
 
+#[test]
+fn test_transpose_with_map() {
+    let A = test_matrix_3x4();
+    let (At, map) = A.transpose_with_map();
+
+    assert_eq!(At, CscMatrix::from(A.t()));
+    for (k, &v) in A.nzval.iter().enumerate() {
+        assert_eq!(At.nzval[map[k]], v);
+    }
+}
+
+#[test]
+fn test_lrscale_with_norms() {
+    let A = test_matrix_3x4();
+    let l = vec![0.5, -2., 3.];
+    let r = vec![1.5, 0.25, -1., 2.];
+
+    let mut B = A.clone();
+    let (mut cnorms, mut rnorms) = (vec![0.; 4], vec![0.; 3]);
+    B.lrscale(&l, &r);
+    B.col_norms(&mut cnorms);
+    B.row_norms(&mut rnorms);
+
+    // stale values in the outputs are overwritten
+    let mut C = A;
+    let (mut cnorms_fused, mut rnorms_fused) = (vec![-1.; 4], vec![100.; 3]);
+    C.lrscale_with_norms(&l, &r, &mut cnorms_fused, &mut rnorms_fused);
+
+    assert_eq!(B, C);
+    assert_eq!(cnorms, cnorms_fused);
+    assert_eq!(rnorms, rnorms_fused);
+}
+
 #[test]
 fn test_col_sums() {
     let A = test_matrix_3x4();
@@ -241,6 +491,87 @@ fn test_gemv() {
     assert_eq!(y, vec![-49., -220., -33., 42.]);
 }
 
+// the unblocked products replaced by the blocked kernels
+fn reference_axpby_N(A: &CscMatrix<f64>, y: &mut [f64], x: &[f64], a: f64, b: f64) {
+    y.iter_mut()
+        .for_each(|yi| *yi = if b == 0. { 0. } else { b * *yi });
+    if a == 1. {
+        for (j, xj) in x.iter().enumerate().take(A.n) {
+            for i in A.colptr[j]..A.colptr[j + 1] {
+                y[A.rowval[i]] += A.nzval[i] * *xj;
+            }
+        }
+    } else if a == -1. {
+        for (j, xj) in x.iter().enumerate().take(A.n) {
+            for i in A.colptr[j]..A.colptr[j + 1] {
+                y[A.rowval[i]] -= A.nzval[i] * *xj;
+            }
+        }
+    } else {
+        for (j, xj) in x.iter().enumerate().take(A.n) {
+            for i in A.colptr[j]..A.colptr[j + 1] {
+                y[A.rowval[i]] += a * A.nzval[i] * *xj;
+            }
+        }
+    }
+}
+
+fn reference_axpby_T(A: &CscMatrix<f64>, y: &mut [f64], x: &[f64], a: f64, b: f64) {
+    y.iter_mut()
+        .for_each(|yi| *yi = if b == 0. { 0. } else { b * *yi });
+    if a == 1. {
+        for (j, yj) in y.iter_mut().enumerate().take(A.n) {
+            for k in A.colptr[j]..A.colptr[j + 1] {
+                *yj += A.nzval[k] * x[A.rowval[k]];
+            }
+        }
+    } else if a == -1. {
+        for (j, yj) in y.iter_mut().enumerate().take(A.n) {
+            for k in A.colptr[j]..A.colptr[j + 1] {
+                *yj -= A.nzval[k] * x[A.rowval[k]];
+            }
+        }
+    } else {
+        for (j, yj) in y.iter_mut().enumerate().take(A.n) {
+            for k in A.colptr[j]..A.colptr[j + 1] {
+                *yj += a * A.nzval[k] * x[A.rowval[k]];
+            }
+        }
+    }
+}
+
+#[test]
+fn test_gemv_blocked_exact() {
+    // columns of varying length, with empty columns and a
+    // number of columns that is not a multiple of the block size
+    let (m, n) = (23, 18);
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for j in 0..n {
+        for i in (0..m).filter(|i| (i * 7 + j * 3) % (j % 5 + 1) == 0) {
+            I.push(i);
+            J.push(j);
+            V.push(((i * n + j) as f64).sin());
+        }
+    }
+    let A = CscMatrix::new_from_triplets(m, n, I, J, V);
+    let x: Vec<f64> = (0..n).map(|i| (i as f64 * 0.7).cos()).collect();
+    let z: Vec<f64> = (0..m).map(|i| (i as f64 * 0.3).cos()).collect();
+
+    for (a, b) in itertools::iproduct!([1., -1., 0.37], [0., 1., -1., 2.5]) {
+        let mut y: Vec<f64> = (0..m).map(|i| i as f64).collect();
+        let mut yref = y.clone();
+        A.gemv(&mut y, &x, a, b);
+        reference_axpby_N(&A, &mut yref, &x, a, b);
+        assert_eq!(y, yref);
+
+        let mut w: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let mut wref = w.clone();
+        A.t().gemv(&mut w, &z, a, b);
+        reference_axpby_T(&A, &mut wref, &z, a, b);
+        assert_eq!(w, wref);
+    }
+}
+
 #[test]
 fn test_symv() {
     let A = test_matrix_4x4_triu();
@@ -255,6 +586,122 @@ fn test_symv() {
     assert_eq!(y, vec![46.0, -29.0, -25.0, -4.0]);
 }
 
+// random m x n matrix with entries kept with probability 1/3, every
+// fourth column left empty, and only the upper triangle if triu
+fn random_dense_matrix(m: usize, n: usize, triu: bool, state: &mut u64) -> Vec<Vec<f64>> {
+    let mut rand = || {
+        *state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (*state >> 33) as usize
+    };
+    let mut M = vec![vec![0.; n]; m];
+    for j in (0..n).filter(|j| j % 4 != 3) {
+        for (i, row) in M.iter_mut().enumerate() {
+            if (!triu || i <= j) && rand() % 3 == 0 {
+                row[j] = (rand() % 19) as f64 - 9.;
+            }
+        }
+    }
+    M
+}
+
+fn csc_from_dense(M: &[Vec<f64>], n: usize) -> CscMatrix<f64> {
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for (i, row) in M.iter().enumerate() {
+        for (j, &v) in row.iter().enumerate().filter(|(_, &v)| v != 0.) {
+            I.push(i);
+            J.push(j);
+            V.push(v);
+        }
+    }
+    CscMatrix::new_from_triplets(M.len(), n, I, J, V)
+}
+
+#[test]
+fn test_symv_random() {
+    let mut state = 1;
+    for n in [0, 1, 2, 5, 17, 40] {
+        let M = random_dense_matrix(n, n, true, &mut state);
+        let A = csc_from_dense(&M, n);
+
+        let x: Vec<f64> = (0..n).map(|i| (i as f64 - 3.) / 2.).collect();
+        let y0: Vec<f64> = (0..n).map(|i| i as f64 % 5.).collect();
+        let (a, b) = (-2., 0.5);
+
+        // y = a*(M + M' - diag(M))*x + b*y, counting the diagonal once
+        let mut yref = y0.clone();
+        for (i, yi) in yref.iter_mut().enumerate() {
+            let Mx: f64 = (0..n)
+                .map(|j| if i <= j { M[i][j] } else { M[j][i] } * x[j])
+                .sum();
+            *yi = a * Mx + b * *yi;
+        }
+
+        let mut y = y0.clone();
+        A.sym().symv(&mut y, &x, a, b);
+        assert_eq!(y, yref);
+    }
+}
+
+#[test]
+fn test_symv_triangle() {
+    let mut state = 3;
+    for n in [0, 1, 2, 5, 17, 40] {
+        // full symmetric matrix and its two triangles
+        let U = random_dense_matrix(n, n, true, &mut state);
+        let F: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i <= j { U[i][j] } else { U[j][i] })
+                    .collect()
+            })
+            .collect();
+        let L: Vec<Vec<f64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i >= j { F[i][j] } else { 0. }).collect())
+            .collect();
+        let (Af, Au, Al) = (
+            csc_from_dense(&F, n),
+            csc_from_dense(&U, n),
+            csc_from_dense(&L, n),
+        );
+
+        let x: Vec<f64> = (0..n).map(|i| (i as f64 - 3.) / 2.).collect();
+        let y0: Vec<f64> = (0..n).map(|i| i as f64 % 5.).collect();
+        let (a, b) = (-2., 0.5);
+
+        let mut yref = y0.clone();
+        Af.gemv(&mut yref, &x, a, b);
+
+        // the full matrix gives the same result, since the
+        // entries outside of the given triangle are ignored
+        for (A, uplo) in [
+            (&Au, MatrixTriangle::Triu),
+            (&Al, MatrixTriangle::Tril),
+            (&Af, MatrixTriangle::Triu),
+            (&Af, MatrixTriangle::Tril),
+        ] {
+            let mut y = y0.clone();
+            A.symv(&mut y, &x, a, b, uplo);
+            assert_eq!(y, yref);
+        }
+    }
+}
+
+#[test]
+fn test_transpose_random() {
+    let mut state = 1;
+    for (m, n) in [(0, 0), (0, 3), (3, 0), (1, 1), (7, 12), (30, 9)] {
+        let M = random_dense_matrix(m, n, false, &mut state);
+        let A = csc_from_dense(&M, n);
+
+        let Mt: Vec<Vec<f64>> = (0..n).map(|j| (0..m).map(|i| M[i][j]).collect()).collect();
+        let At = CscMatrix::from(A.t());
+        assert!(At.check_format().is_ok());
+        assert_eq!(At, csc_from_dense(&Mt, m));
+    }
+}
+
 #[test]
 fn test_quad_form() {
     let A = test_matrix_4x4_triu();
@@ -266,6 +713,111 @@ fn test_quad_form() {
     assert_eq!(val, 15.);
 }
 
+#[test]
+fn test_quad_form_random() {
+    let mut state = 7;
+    for n in [0, 1, 2, 5, 17, 40] {
+        let M = random_dense_matrix(n, n, true, &mut state);
+        let A = csc_from_dense(&M, n);
+
+        let x: Vec<f64> = (0..n).map(|i| (i as f64 - 3.) / 2.).collect();
+        let y: Vec<f64> = (0..n).map(|i| i as f64 % 5. - 1.).collect();
+
+        // y'*M*x and y'*(M + M' - diag(M))*x, with exactly
+        // representable dense references
+        let general = |y: &[f64], x: &[f64]| -> f64 {
+            (0..n)
+                .flat_map(|i| (0..n).map(move |j| (i, j)))
+                .map(|(i, j)| y[i] * M[i][j] * x[j])
+                .sum()
+        };
+        let symmetric = |y: &[f64], x: &[f64]| -> f64 {
+            (0..n)
+                .flat_map(|i| (0..n).map(move |j| (i, j)))
+                .map(|(i, j)| y[i] * if i <= j { M[i][j] } else { M[j][i] } * x[j])
+                .sum()
+        };
+
+        for (u, v) in [(&y, &x), (&x, &x)] {
+            assert_eq!(A.quadratic_form(u, v), general(u, v));
+            assert_eq!(A.quadratic_form_sym(u, v), symmetric(u, v));
+            assert_eq!(A.quad_form(u, v), symmetric(u, v));
+        }
+    }
+}
+
+#[test]
+fn test_quadratic_form() {
+    // general matrix matches x^T*(A*y)
+    let A = test_matrix_4x4();
+    let x = vec![1., 2., -3., -4.];
+    let y = vec![0., 1., -1., 2.];
+    let mut Ay = vec![0.; 4];
+    A.gemv(&mut Ay, &y, 1.0, 0.0);
+    assert_eq!(A.quadratic_form(&x, &y), x.dot(&Ay));
+
+    // symmetric positive definite matrix
+    // A =
+    //[ 4.0  1.0  0.0]
+    //[ 1.0  3.0 -1.0]
+    //[ 0.0 -1.0  2.0]
+    let A = CscMatrix::from(&[
+        [4., 1., 0.],  //
+        [1., 3., -1.], //
+        [0., -1., 2.], //
+    ]);
+    let x = vec![1., -2., 3.];
+    let mut Ax = vec![0.; 3];
+    A.gemv(&mut Ax, &x, 1.0, 0.0);
+    let val = x.dot(&Ax);
+    assert_eq!(A.quadratic_form(&x, &x), val);
+
+    // symmetric form from either triangle
+    let Atriu = A.to_triu();
+    let Atril = CscMatrix::from(&[
+        [4., 0., 0.],  //
+        [1., 3., 0.],  //
+        [0., -1., 2.], //
+    ]);
+    assert_eq!(Atriu.quadratic_form_sym(&x, &x), val);
+    assert_eq!(Atril.quadratic_form_sym(&x, &x), val);
+    assert_eq!(Atriu.quadratic_form_sym(&x, &x), Atriu.quad_form(&x, &x));
+}
+
+#[test]
+fn test_equilibrate_cols_rows() {
+    let A0 = test_matrix_3x4();
+    let mut A = A0.clone();
+    let mut norms = vec![0.; 4];
+    A.equilibrate_cols(&mut norms);
+
+    let mut out = vec![0.; 4];
+    A.col_norms(&mut out);
+    assert!(out.iter().all(|&v| (v - 1.).abs() < 1e-15));
+
+    // norms holds the applied scaling
+    let mut B = A0.clone();
+    B.rscale(&norms);
+    assert_eq!(A, B);
+
+    let mut A = A0.clone();
+    let mut norms = vec![0.; 3];
+    A.equilibrate_rows(&mut norms);
+    let mut out = vec![0.; 3];
+    A.row_norms(&mut out);
+    assert!(out.iter().all(|&v| (v - 1.).abs() < 1e-15));
+
+    // empty columns get unit scaling
+    let mut A = CscMatrix::from(&[
+        [2., 0.], //
+        [-4., 0.],
+    ]);
+    let mut norms = vec![0.; 2];
+    A.equilibrate_cols(&mut norms);
+    assert_eq!(norms, vec![0.25, 1.]);
+    assert_eq!(A, CscMatrix::from(&[[0.5, 0.], [-1., 0.]]));
+}
+
 #[test]
 fn test_matrix_to_triu() {
     let Afull = test_matrix_4x4();
@@ -305,6 +857,39 @@ fn test_matrix_to_triu_notsquare() {
     assert_eq!(B, A);
 }
 
+#[test]
+fn test_is_symmetric() {
+    // the 4x4 test matrix has a nonsymmetric lower triangle
+    let A = test_matrix_4x4();
+    assert!(!A.is_symmetric(1e-12));
+    assert_eq!(A.first_asymmetric_entry(1e-12), Some((1, 0)));
+
+    // full symmetric matrix with the same upper triangle
+    let mut S = CscMatrix::new(
+        4,
+        4,
+        vec![0, 3, 6, 10, 12],
+        vec![0, 1, 2, 0, 1, 2, 0, 1, 2, 3, 2, 3],
+        vec![4., -3., 7., -3., 8., -1., 7., -1., 2., -3., -3., 1.],
+    );
+    assert!(S.is_symmetric(0.));
+    assert_eq!(S.first_asymmetric_entry(0.), None);
+    assert_eq!(S.to_triu(), test_matrix_4x4_triu());
+
+    // perturbation of entry (2,0) within and beyond the tolerance
+    S.nzval[2] *= 1. + 1e-10;
+    assert!(S.is_symmetric(1e-8));
+    assert!(!S.is_symmetric(1e-12));
+    assert_eq!(S.first_asymmetric_entry(1e-12), Some((2, 0)));
+
+    // triangular matrices are symmetric only if diagonal
+    let T = test_matrix_4x4_triu();
+    assert!(!T.is_symmetric(1e-8));
+    assert_eq!(T.first_asymmetric_entry(1e-8), Some((1, 0)));
+    assert!(CscMatrix::<f64>::identity(3).is_symmetric(0.));
+    assert!(!CscMatrix::<f64>::zeros((2, 3)).is_symmetric(0.));
+}
+
 #[test]
 fn test_matrix_hcat_and_vcat() {
     let n = 3;
@@ -361,3 +946,624 @@ fn test_matrix_select_rows() {
     assert_eq!(Ared.rowval, Vec::<usize>::new());
     assert_eq!(Ared.nzval, Vec::<f64>::new());
 }
+
+#[test]
+fn test_from_banded() {
+    // tridiagonal
+    let n = 5;
+    let sub = vec![-1., -2., -3., -4.];
+    let diag = vec![2., 4., 6., 8., 10.];
+    let sup = vec![1., 3., 5., 7.];
+    let A = CscMatrix::from_banded(n, &[(-1, sub.clone()), (0, diag.clone()), (1, sup.clone())]);
+
+    let mut I = vec![];
+    let mut J = vec![];
+    let mut V = vec![];
+    for (k, &d) in diag.iter().enumerate() {
+        I.push(k);
+        J.push(k);
+        V.push(d);
+    }
+    for k in 0..n - 1 {
+        I.extend([k + 1, k]);
+        J.extend([k, k + 1]);
+        V.extend([sub[k], sup[k]]);
+    }
+    let B = CscMatrix::new_from_triplets(n, n, I, J, V);
+    assert_eq!(A, B);
+    assert!(A.check_format().is_ok());
+
+    // band order doesn't matter, and bands may be non-adjacent
+    // or so far off the diagonal that they are empty
+    let A = CscMatrix::from_banded(3, &[(3, vec![]), (2, vec![9.]), (-1, vec![1., 2.])]);
+    let B = CscMatrix::from(&[
+        [0., 0., 9.], //
+        [1., 0., 0.], //
+        [0., 2., 0.], //
+    ]);
+    assert_eq!(A, B);
+}
+
+#[test]
+#[should_panic]
+fn test_from_banded_bad_length() {
+    CscMatrix::from_banded(3, &[(1, vec![1., 2., 3.])]);
+}
+
+#[test]
+fn test_to_symmetric_csr() {
+    // upper triangle of a symmetric matrix, and its transpose
+    let Atriu = test_matrix_4x4_triu();
+    let Atril: CscMatrix<f64> = Atriu.t().into();
+
+    // full symmetric matrix in CSC format
+    let Afull = CscMatrix::from(&[
+        [4., -3., 7., 0.],  //
+        [-3., 8., -1., 0.], //
+        [7., -1., 2., -3.], //
+        [0., 0., -3., 1.],  //
+    ]);
+
+    let x = vec![1., -2., 3., -4.];
+
+    for A in [&Atriu, &Atril] {
+        let B = A.to_symmetric_csr();
+        assert_eq!(B.nnz(), Afull.nnz());
+
+        // symmetric, so the CSR form of the full matrix matches its CSC form
+        assert_eq!(B.rowptr, Afull.colptr);
+        assert_eq!(B.colval, Afull.rowval);
+        assert_eq!(B.nzval, Afull.nzval);
+
+        let mut y1 = vec![1., 2., 3., 4.];
+        let mut y2 = y1.clone();
+        Afull.gemv(&mut y1, &x, -2., 3.);
+        B.gemv_sym(&mut y2, &x, -2., 3.);
+        assert_eq!(y1, y2);
+
+        let mut y3 = vec![f64::NAN; 4];
+        B.gemv_sym(&mut y3, &x, 1., 0.);
+        Atriu.sym().symv(&mut y1, &x, 1., 0.);
+        assert_eq!(y1, y3);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_to_symmetric_csr_not_triangular() {
+    test_matrix_4x4().to_symmetric_csr();
+}
+
+#[test]
+fn test_symbolic_plans() {
+    let get = |M: &CscMatrix<f64>, i, j| M.get_entry((i, j)).unwrap_or(0.);
+    let A = test_matrix_4x4();
+    let B = test_matrix_4x4_triu_2();
+
+    // transpose
+    let (tplan, mut At) = A.transpose_symbolic();
+    A.transpose_numeric(&tplan, &mut At).unwrap();
+    assert_eq!(At, CscMatrix::from(A.t()));
+
+    // a*A + b*B, with the union of the patterns
+    let (aplan, mut C) = CscMatrix::add_scaled_symbolic(&A, &B).unwrap();
+    CscMatrix::add_scaled_numeric(&aplan, &mut C, 2., &A, -3., &B).unwrap();
+    assert_eq!(C.nnz(), 10);
+    for (i, j) in itertools::iproduct!(0..4, 0..4) {
+        assert_eq!(get(&C, i, j), 2. * get(&A, i, j) - 3. * get(&B, i, j));
+    }
+
+    // upper triangle of A'A
+    let (mut pplan, mut AtA) = A.AtA_symbolic();
+    A.AtA_numeric(&mut pplan, &mut AtA).unwrap();
+    assert!(AtA.is_triu());
+    for (i, j) in itertools::iproduct!(0..4, 0..4) {
+        let expected: f64 = (0..4).map(|r| get(&A, r, i) * get(&A, r, j)).sum();
+        assert_eq!(get(&AtA, i, j), if i <= j { expected } else { 0. });
+    }
+
+    // re-executing the plans after a value update gives results
+    // identical to recomputing from scratch
+    let mut A2 = A.clone();
+    A2.nzval
+        .iter_mut()
+        .enumerate()
+        .for_each(|(k, v)| *v *= 1.5 + k as f64);
+    A2.transpose_numeric(&tplan, &mut At).unwrap();
+    CscMatrix::add_scaled_numeric(&aplan, &mut C, 2., &A2, -3., &B).unwrap();
+    A2.AtA_numeric(&mut pplan, &mut AtA).unwrap();
+
+    let (_, At_new) = A2.transpose_symbolic();
+    let (plan, mut C_new) = CscMatrix::add_scaled_symbolic(&A2, &B).unwrap();
+    CscMatrix::add_scaled_numeric(&plan, &mut C_new, 2., &A2, -3., &B).unwrap();
+    let (mut plan, mut AtA_new) = A2.AtA_symbolic();
+    A2.AtA_numeric(&mut plan, &mut AtA_new).unwrap();
+    assert_eq!(At, At_new);
+    assert_eq!(C, C_new);
+    assert_eq!(AtA, AtA_new);
+
+    // plans are invalid for matrices of other dimensions
+    let A3 = test_matrix_3x4();
+    assert!(A3.transpose_numeric(&tplan, &mut At).is_err());
+    assert!(A3.AtA_numeric(&mut pplan, &mut AtA).is_err());
+    assert!(CscMatrix::add_scaled_numeric(&aplan, &mut C, 1., &A3, 1., &B).is_err());
+    assert!(CscMatrix::add_scaled_symbolic(&A3, &B).is_err());
+}
+
+#[cfg(feature = "parallel")]
+fn test_matrix_banded_large() -> CscMatrix<f64> {
+    // banded matrix large enough to be split across several tasks
+    let n = 5000;
+    let bands: Vec<(isize, Vec<f64>)> = [-40isize, -3, 0, 2, 17]
+        .iter()
+        .map(|&k| {
+            let len = n - k.unsigned_abs();
+            let vals = (0..len).map(|i| ((i as f64) * (k as f64 + 0.5)).sin());
+            (k, vals.collect())
+        })
+        .collect();
+    CscMatrix::from_banded(n, &bands)
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn test_gemv_parallel() {
+    let A = test_matrix_banded_large();
+    let x: Vec<f64> = (0..A.n).map(|i| (i as f64).cos()).collect();
+    let y0: Vec<f64> = (0..A.m).map(|i| (i as f64 * 0.3).sin()).collect();
+
+    let At: CscMatrix<f64> = A.t().into();
+
+    for (a, b) in [(1., 0.), (-1., 1.), (2., -3.), (0., 0.5), (1., -1.)] {
+        // the transposed product is identical to the serial one
+        let mut yser = y0.clone();
+        let mut ypar = y0.clone();
+        A.t().gemv(&mut yser, &x, a, b);
+        _csc_axpby_T_par(&A, &mut ypar, &x, a, b);
+        assert_eq!(ypar, yser);
+
+        // as is the product computed by rows from the transpose
+        let mut yser = y0.clone();
+        let mut ypar = y0.clone();
+        A.gemv(&mut yser, &x, a, b);
+        _csc_axpby_T_par(&At, &mut ypar, &x, a, b);
+        assert_eq!(ypar, yser);
+    }
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn test_equilibration_norms_parallel() {
+    let banded = test_matrix_banded_large();
+    let small = test_matrix_3x4();
+
+    for A in [banded, small] {
+        let (m, n) = A.size();
+        let l: Vec<f64> = (0..m).map(|i| 1. + 0.1 * (i as f64).cos()).collect();
+        let r: Vec<f64> = (0..n).map(|i| 1. + 0.1 * (i as f64).sin()).collect();
+
+        // serial results, since A is below the parallel threshold
+        let (mut cnorms, mut rnorms) = (vec![0.; n], vec![0.; m]);
+        A.col_norms(&mut cnorms);
+        A.row_norms(&mut rnorms);
+        let mut B = A.clone();
+        B.lrscale(&l, &r);
+        let mut C = A.clone();
+        let (mut cnorms_scaled, mut rnorms_scaled) = (vec![0.; n], vec![0.; m]);
+        C.lrscale_with_norms(&l, &r, &mut cnorms_scaled, &mut rnorms_scaled);
+
+        // max is order independent, so all results are identical
+        let mut v = vec![0.; n];
+        _csc_col_norms_par(&A, &mut v);
+        assert_eq!(v, cnorms);
+
+        for nchunks in [1, 3, 8] {
+            let mut v = vec![0.; m];
+            _csc_row_norms_par(&A, &mut v, nchunks);
+            assert_eq!(v, rnorms);
+
+            // existing values are kept if larger
+            let mut v = vec![0.5; m];
+            _csc_row_norms_par(&A, &mut v, nchunks);
+            let expected: Vec<f64> = rnorms.iter().map(|&x| f64::max(x, 0.5)).collect();
+            assert_eq!(v, expected);
+
+            let mut D = A.clone();
+            _csc_lrscale_par(&mut D, &l, &r, nchunks);
+            assert_eq!(D.nzval, B.nzval);
+
+            let mut D = A.clone();
+            let (mut cn, mut rn) = (vec![f64::NAN; n], vec![f64::NAN; m]);
+            _csc_lrscale_with_norms_par(&mut D, &l, &r, &mut cn, &mut rn, nchunks);
+            assert_eq!(D.nzval, C.nzval);
+            assert_eq!(cn, cnorms_scaled);
+            assert_eq!(rn, rnorms_scaled);
+        }
+    }
+}
+
+// random 2n x n matrix with about 50 nonzeros per column and nnz
+// nonzeros in total, from a linear congruential generator
+fn random_sparse_matrix(nnz: usize) -> CscMatrix<f64> {
+    let mut state: u64 = 1;
+    let mut rand = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+    let n = usize::max(nnz / 50, 1);
+    let m = 2 * n;
+    let mut colptr = vec![0];
+    let mut rowval = Vec::with_capacity(nnz);
+    for _ in 0..n {
+        let mut rows: Vec<usize> = (0..50).map(|_| rand() % m).collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rowval.extend(rows);
+        colptr.push(rowval.len());
+    }
+    let nzval = (0..rowval.len()).map(|k| (k as f64).sin()).collect();
+    CscMatrix::new(m, n, colptr, rowval, nzval)
+}
+
+// Timing of serial and parallel products on a large random matrix,
+// with 50 million nonzeros unless CLARABEL_BENCH_NNZ is set.   Run with
+//
+//   cargo test --release --features parallel --lib bench_gemv_parallel -- --ignored --nocapture
+#[test]
+#[ignore]
+#[cfg(feature = "parallel")]
+fn bench_gemv_parallel() {
+    use std::time::Instant;
+
+    let nnz: usize = std::env::var("CLARABEL_BENCH_NNZ")
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(50_000_000);
+
+    let A = random_sparse_matrix(nnz);
+    let (m, n) = A.size();
+
+    let x: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+    let z: Vec<f64> = (0..m).map(|i| (i as f64).cos()).collect();
+    let mut y = vec![0.; m];
+    let mut w = vec![0.; n];
+
+    println!(
+        "A is {} x {} with {} nonzeros, {} threads",
+        m,
+        n,
+        A.nnz(),
+        rayon::current_num_threads()
+    );
+
+    let mut results = Vec::new();
+    for (label, threshold) in [("serial", usize::MAX), ("parallel", 0)] {
+        set_parallel_gemv_threshold(threshold);
+        let mut times = [0.; 2];
+        for _ in 0..5 {
+            let start = Instant::now();
+            A.gemv(&mut y, &x, 1., 0.);
+            times[0] += start.elapsed().as_secs_f64() / 5.;
+            let start = Instant::now();
+            A.t().gemv(&mut w, &z, 1., 0.);
+            times[1] += start.elapsed().as_secs_f64() / 5.;
+        }
+        println!(
+            "{:>10}: A*x {:8.2} ms, A'*z {:8.2} ms",
+            label,
+            times[0] * 1e3,
+            times[1] * 1e3
+        );
+        results.push((y.clone(), w.clone()));
+    }
+    default_parallel_gemv_threshold();
+
+    assert!(results[0].0.norm_inf_diff(&results[1].0) <= 1e-10);
+    assert_eq!(results[0].1, results[1].1);
+}
+
+// Timing of the blocked products against the unblocked ones on random
+// matrices with short (10 nonzeros) and long (10k nonzeros) columns and
+// 2 million nonzeros in total.   Run with
+//
+//   cargo test --release --lib bench_gemv_blocked -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_gemv_blocked() {
+    use std::time::Instant;
+
+    // random m x n matrix with nnzcol distinct nonzeros per column
+    let random_matrix = |m: usize, n: usize, nnzcol: usize| {
+        let mut state: u64 = 1;
+        let mut rand = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as usize
+        };
+        let mut colptr = vec![0];
+        let mut rowval = Vec::with_capacity(n * nnzcol);
+        for _ in 0..n {
+            let mut rows: Vec<usize> = (0..nnzcol).map(|_| rand() % m).collect();
+            rows.sort_unstable();
+            rows.dedup();
+            rowval.extend(rows);
+            colptr.push(rowval.len());
+        }
+        let nzval = (0..rowval.len()).map(|k| (k as f64).sin()).collect();
+        CscMatrix::new(m, n, colptr, rowval, nzval)
+    };
+
+    let cases = [
+        ("short", random_matrix(200_000, 200_000, 10)),
+        ("long", random_matrix(200_000, 200, 10_000)),
+    ];
+
+    for (label, A) in &cases {
+        let (m, n) = A.size();
+        let x: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+        let z: Vec<f64> = (0..m).map(|i| (i as f64).cos()).collect();
+        let (mut y, mut yref) = (vec![0.; m], vec![0.; m]);
+        let (mut w, mut wref) = (vec![0.; n], vec![0.; n]);
+
+        for a in [1., 0.5] {
+            let mut times = [0.; 4];
+            for _ in 0..20 {
+                let start = Instant::now();
+                reference_axpby_N(A, &mut yref, &x, a, 0.);
+                times[0] += start.elapsed().as_secs_f64() / 20.;
+                let start = Instant::now();
+                _csc_axpby_N(A, &mut y, &x, a, 0.);
+                times[1] += start.elapsed().as_secs_f64() / 20.;
+                let start = Instant::now();
+                reference_axpby_T(A, &mut wref, &z, a, 0.);
+                times[2] += start.elapsed().as_secs_f64() / 20.;
+                let start = Instant::now();
+                _csc_axpby_T(A, &mut w, &z, a, 0.);
+                times[3] += start.elapsed().as_secs_f64() / 20.;
+            }
+            assert_eq!(y, yref);
+            assert_eq!(w, wref);
+            println!(
+                "{:>5} columns, a = {:3}: A*x {:7.3} -> {:7.3} ms, A'*z {:7.3} -> {:7.3} ms",
+                label,
+                a,
+                times[0] * 1e3,
+                times[1] * 1e3,
+                times[2] * 1e3,
+                times[3] * 1e3
+            );
+        }
+    }
+}
+
+// Timing of the symbolic and numeric phases of the transpose, a
+// scaled sum and AᵀA for a random matrix with 5 million nonzeros,
+// unless CLARABEL_BENCH_NNZ is set.   Run with
+//
+//   cargo test --release --lib bench_symbolic_plans -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_symbolic_plans() {
+    use std::time::Instant;
+
+    let nnz: usize = std::env::var("CLARABEL_BENCH_NNZ")
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(5_000_000);
+
+    let A = random_sparse_matrix(nnz);
+    let mut B = A.clone();
+    B.nzval.reverse();
+    println!("A is {} x {} with {} nonzeros", A.m, A.n, A.nnz());
+
+    let timed = |f: &mut dyn FnMut()| {
+        let start = Instant::now();
+        f();
+        start.elapsed().as_secs_f64() * 1e3
+    };
+
+    let (mut tplan, mut At) = (None, None);
+    let t0 = timed(&mut || {
+        let (p, M) = A.transpose_symbolic();
+        (tplan, At) = (Some(p), Some(M));
+    });
+    let (tplan, mut At) = (tplan.unwrap(), At.unwrap());
+    let t1 = timed(&mut || A.transpose_numeric(&tplan, &mut At).unwrap());
+    println!("  transpose: symbolic {:8.2} ms, numeric {:8.2} ms", t0, t1);
+
+    let (mut aplan, mut C) = (None, None);
+    let t0 = timed(&mut || {
+        let (p, M) = CscMatrix::add_scaled_symbolic(&A, &B).unwrap();
+        (aplan, C) = (Some(p), Some(M));
+    });
+    let (aplan, mut C) = (aplan.unwrap(), C.unwrap());
+    let t1 = timed(&mut || CscMatrix::add_scaled_numeric(&aplan, &mut C, 2., &A, -1., &B).unwrap());
+    println!(" add scaled: symbolic {:8.2} ms, numeric {:8.2} ms", t0, t1);
+
+    let (mut pplan, mut AtA) = (None, None);
+    let t0 = timed(&mut || {
+        let (p, M) = A.AtA_symbolic();
+        (pplan, AtA) = (Some(p), Some(M));
+    });
+    let (mut pplan, mut AtA) = (pplan.unwrap(), AtA.unwrap());
+    let t1 = timed(&mut || A.AtA_numeric(&mut pplan, &mut AtA).unwrap());
+    println!(
+        "        AᵀA: symbolic {:8.2} ms, numeric {:8.2} ms, {} nonzeros",
+        t0,
+        t1,
+        AtA.nnz()
+    );
+}
+
+// Timing of serial and parallel norm and scaling passes, as used in
+// each Ruiz equilibration iteration, with 50 million nonzeros unless
+// CLARABEL_BENCH_NNZ is set.   Run with
+//
+//   cargo test --release --features parallel --lib bench_equilibration_parallel -- --ignored --nocapture
+#[test]
+#[ignore]
+#[cfg(feature = "parallel")]
+fn bench_equilibration_parallel() {
+    use std::time::Instant;
+
+    let nnz: usize = std::env::var("CLARABEL_BENCH_NNZ")
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(50_000_000);
+
+    let A = random_sparse_matrix(nnz);
+    let (m, n) = A.size();
+    let l: Vec<f64> = (0..m).map(|i| 1. + 0.1 * (i as f64).cos()).collect();
+    let r: Vec<f64> = (0..n).map(|i| 1. + 0.1 * (i as f64).sin()).collect();
+
+    println!(
+        "A is {} x {} with {} nonzeros, {} threads",
+        m,
+        n,
+        A.nnz(),
+        rayon::current_num_threads()
+    );
+
+    let mut results = Vec::new();
+    for (label, threshold) in [("serial", usize::MAX), ("parallel", 0)] {
+        set_parallel_gemv_threshold(threshold);
+        let mut B = A.clone();
+        let (mut cnorms, mut rnorms) = (vec![0.; n], vec![0.; m]);
+        let mut times = [0.; 3];
+        for _ in 0..5 {
+            let start = Instant::now();
+            B.col_norms(&mut cnorms);
+            times[0] += start.elapsed().as_secs_f64() / 5.;
+            let start = Instant::now();
+            B.row_norms(&mut rnorms);
+            times[1] += start.elapsed().as_secs_f64() / 5.;
+            let start = Instant::now();
+            B.lrscale_with_norms(&l, &r, &mut cnorms, &mut rnorms);
+            times[2] += start.elapsed().as_secs_f64() / 5.;
+        }
+        println!(
+            "{:>10}: col norms {:8.2} ms, row norms {:8.2} ms, scaling with norms {:8.2} ms",
+            label,
+            times[0] * 1e3,
+            times[1] * 1e3,
+            times[2] * 1e3
+        );
+        results.push((B.nzval, cnorms, rnorms));
+    }
+    default_parallel_gemv_threshold();
+
+    assert_eq!(results[0], results[1]);
+}
+
+// Timing of separate and fused scaling and norm computations, as
+// used in each Ruiz equilibration iteration, with 20 million
+// nonzeros unless CLARABEL_BENCH_NNZ is set.   Nonzeros are placed
+// in random rows, or in consecutive rows near the diagonal.  Run with
+//
+//   cargo test --release --lib bench_lrscale_with_norms -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_lrscale_with_norms() {
+    use std::time::Instant;
+
+    let nnz: usize = std::env::var("CLARABEL_BENCH_NNZ")
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(20_000_000);
+
+    let random = random_sparse_matrix(nnz);
+    let (m, n) = random.size();
+    let mut banded = random.clone();
+    for col in 0..n {
+        let first = banded.colptr[col];
+        for k in first..banded.colptr[col + 1] {
+            banded.rowval[k] = (2 * col + k - first) % m;
+        }
+    }
+
+    let l: Vec<f64> = (0..m).map(|i| 1. + 0.1 * (i as f64).cos()).collect();
+    let r: Vec<f64> = (0..n).map(|i| 1. + 0.1 * (i as f64).sin()).collect();
+    let (mut cnorms, mut rnorms) = (vec![0.; n], vec![0.; m]);
+    let (mut cnorms_fused, mut rnorms_fused) = (vec![0.; n], vec![0.; m]);
+
+    println!("A is {} x {} with {} nonzeros", m, n, random.nnz());
+
+    for (label, mut A) in [("random rows", random), ("banded rows", banded)] {
+        let mut B = A.clone();
+        let mut times = [0.; 2];
+        for _ in 0..5 {
+            let start = Instant::now();
+            A.lrscale(&l, &r);
+            A.col_norms(&mut cnorms);
+            A.row_norms(&mut rnorms);
+            times[0] += start.elapsed().as_secs_f64() / 5.;
+
+            let start = Instant::now();
+            B.lrscale_with_norms(&l, &r, &mut cnorms_fused, &mut rnorms_fused);
+            times[1] += start.elapsed().as_secs_f64() / 5.;
+        }
+        println!(
+            "{:>12}: separate {:8.2} ms, fused {:8.2} ms",
+            label,
+            times[0] * 1e3,
+            times[1] * 1e3
+        );
+
+        assert_eq!(A.nzval, B.nzval);
+        assert_eq!(cnorms, cnorms_fused);
+        assert_eq!(rnorms, rnorms_fused);
+    }
+}
+
+// Timing of products with the transpose of a 2M x 50k matrix with
+// 20 million nonzeros in random rows, computed with A' implicitly
+// or with an explicit transpose.   Run with
+//
+//   cargo test --release --lib bench_explicit_transpose -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_explicit_transpose() {
+    use std::time::Instant;
+
+    let (m, n, per_col) = (2_000_000, 50_000, 400);
+    let mut state: u64 = 1;
+    let mut rand = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+    let mut colptr = vec![0];
+    let mut rowval = Vec::with_capacity(n * per_col);
+    for _ in 0..n {
+        let mut rows: Vec<usize> = (0..per_col).map(|_| rand() % m).collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rowval.extend(rows);
+        colptr.push(rowval.len());
+    }
+    let nzval = (0..rowval.len()).map(|k| (k as f64).sin()).collect();
+    let A = CscMatrix::new(m, n, colptr, rowval, nzval);
+
+    let start = Instant::now();
+    let (At, _) = A.transpose_with_map();
+    let setup = start.elapsed().as_secs_f64();
+
+    let z: Vec<f64> = (0..m).map(|i| (i as f64).cos()).collect();
+    let (mut y1, mut y2) = (vec![0.; n], vec![0.; n]);
+
+    let mut times = [0.; 2];
+    for _ in 0..5 {
+        let start = Instant::now();
+        A.t().gemv(&mut y1, &z, -1., 0.);
+        times[0] += start.elapsed().as_secs_f64() / 5.;
+        let start = Instant::now();
+        At.gemv(&mut y2, &z, -1., 0.);
+        times[1] += start.elapsed().as_secs_f64() / 5.;
+    }
+    println!("A is {} x {} with {} nonzeros", m, n, A.nnz());
+    println!("transpose: {:8.2} ms", setup * 1e3);
+    println!(" implicit: {:8.2} ms per product", times[0] * 1e3);
+    println!(" explicit: {:8.2} ms per product", times[1] * 1e3);
+
+    assert_eq!(y1, y2);
+}
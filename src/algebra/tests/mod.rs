@@ -1,2 +1,6 @@
+#[cfg(feature = "higher-precision")]
+mod doubledouble;
 mod matrix;
+#[cfg(feature = "blocked-kernels")]
+mod blocked;
 mod vector;
@@ -1,4 +1,5 @@
 use crate::algebra::*;
+use std::iter::zip;
 
 #[test]
 fn test_copy_from() {
@@ -16,6 +17,38 @@ fn test_select() {
     assert_eq!(y, vec![1., 3., 4.]);
 }
 
+#[test]
+fn test_swap_elements() {
+    let mut x = vec![3., 0., 2., 1.];
+    x.swap_elements(0, 2).swap_elements(1, 1);
+    assert_eq!(x, [2., 0., 3., 1.]);
+}
+
+#[test]
+fn test_apply_permutation() {
+    let x = vec![10., 11., 12., 13., 14., 15., 16.];
+
+    // a fixed point, a 2-cycle and a 4-cycle
+    let perm = [0, 4, 6, 5, 1, 2, 3];
+    let y = x.apply_permutation(&perm);
+    assert_eq!(y, [10., 14., 16., 15., 11., 12., 13.]);
+
+    let mut z = x.clone();
+    z.apply_permutation_inplace(&perm);
+    assert_eq!(y, z);
+
+    // the inverse permutation restores the original ordering
+    let iperm = invperm(&perm);
+    assert_eq!(y.apply_permutation(&iperm), x);
+    z.apply_permutation_inplace(&iperm);
+    assert_eq!(z, x);
+
+    // and composing with the inverse gives the identity
+    let identity: Vec<f64> = (0..7).map(|i| i as f64).collect();
+    let p: Vec<f64> = perm.iter().map(|&i| i as f64).collect();
+    assert_eq!(p.apply_permutation(&iperm), identity);
+}
+
 #[test]
 fn test_scalarop() {
     let mut x = vec![3., 0., 2., 1.];
@@ -108,6 +141,23 @@ fn test_dot() {
     assert_eq!(y.dot(&x), 7.);
 }
 
+#[test]
+fn test_fma_dot() {
+    let x = vec![3., 0., 2., 1.];
+    let y = vec![-1., -2., 3., 4.];
+
+    assert_eq!(x.fma_dot(&y), 7.);
+    assert_eq!(y.fma_dot(&x), 7.);
+
+    // a single rounding step recovers the product residual that is
+    // lost when the product is rounded before accumulation
+    let a = 1. + f64::EPSILON;
+    let b = 1. - f64::EPSILON;
+    let x = [-1., a];
+    let y = [1., b];
+    assert_eq!(x.fma_dot(&y), -f64::EPSILON * f64::EPSILON);
+}
+
 #[test]
 fn test_dist() {
     let x = vec![3., 0., 2., 1.];
@@ -123,6 +173,52 @@ fn test_sumsq() {
     assert_eq!(x.sumsq(), 30.);
 }
 
+#[test]
+fn test_dot_accumulate() {
+    let x = [3., 0., 2., 1.];
+    let y = [-1., -2., 3., 4.];
+
+    let acc = x.dot_accumulate(&y);
+    assert_eq!(acc, vec![-3., -3., 3., 7.]);
+    assert_eq!(acc[acc.len() - 1], x.dot(&y));
+
+    // the second element is absorbed by the first, and
+    // its contribution is lost when the third cancels it
+    let x = [1e16, 1., -1e16];
+    let acc = x.dot_accumulate(&[1., 1., 1.]);
+    assert_eq!(acc, vec![1e16, 1e16, 0.]);
+    assert_eq!(acc[1] - acc[0], 0.);
+
+    let x = [-1., 2., -3., 4.];
+    assert_eq!(x.sumsq_accumulate(), vec![1., 5., 14., 30.]);
+    assert!(Vec::<f64>::new().sumsq_accumulate().is_empty());
+}
+
+#[test]
+fn test_sparse_dot() {
+    let x = [3., 0., 2., 1., -5.];
+    let idx = [4, 0, 2];
+    let vals = [2., -1., 3.];
+
+    // scatter into a dense vector and compare
+    let mut y = vec![0.; x.len()];
+    for (&i, &v) in idx.iter().zip(vals.iter()) {
+        y[i] = v;
+    }
+    assert_eq!(x.sparse_dot(&idx, &vals), x.dot(&y));
+    assert_eq!(x.sparse_dot(&idx, &vals), -7.);
+
+    // empty sparse vector
+    assert_eq!(x.sparse_dot(&[], &[]), 0.);
+}
+
+#[test]
+fn test_sparse_norm() {
+    let x = [-3., 7., 4., 1., -12.];
+    assert_eq!(x.sparse_norm(&[0, 2, 4]), 13.);
+    assert_eq!(x.sparse_norm(&[]), 0.);
+}
+
 #[test]
 fn test_norm() {
     let x = [-3., 4., -12.];
@@ -136,6 +232,27 @@ fn test_norm_scaled() {
     assert_eq!(x.norm_scaled(&s), 13.);
 }
 
+#[test]
+fn test_bilinear_form_diag() {
+    let x = [1., -2., 3.];
+    let m = [2., 0.5, -1.];
+    let y = [4., 2., 1.];
+    assert_eq!(x.bilinear_form_diag(&m, &y), 8. - 2. - 3.);
+    assert_eq!(x.bilinear_form_diag(&[1.; 3], &y), x.dot(&y));
+
+    let x = [-3. / 2., 4. / 3., -12. / 4.];
+    let s = [2., 3., 4.];
+    assert_eq!(x.bilinear_form_diag_squared(&s), 169.);
+    assert_eq!(x.bilinear_form_diag_squared(&[1.; 3]), x.sumsq());
+}
+
+#[test]
+#[should_panic]
+fn test_bilinear_form_diag_length_mismatch() {
+    let x = [1., 2., 3.];
+    x.bilinear_form_diag(&[1., 1.], &[1., 1., 1.]);
+}
+
 #[test]
 fn test_norm_inf() {
     let x = [-3., 4., -12.];
@@ -155,12 +272,16 @@ fn test_norm_one() {
 fn test_minimum() {
     let x = [-3., 4., -12.];
     assert_eq!(x.minimum(), -12.);
+    assert_eq!([5.].minimum(), 5.);
+    assert_eq!(<[f64]>::minimum(&[]), f64::INFINITY);
 }
 
 #[test]
 fn test_maximum() {
     let x = [-3., 4., -12.];
     assert_eq!(x.maximum(), 4.);
+    assert_eq!([-5.].maximum(), -5.);
+    assert_eq!(<[f64]>::maximum(&[]), f64::NEG_INFINITY);
 }
 
 #[test]
@@ -182,6 +303,102 @@ fn test_axpby() {
     assert_eq!(y, [3., -6., 1., 2.]);
 }
 
+#[test]
+fn test_fma_axpby() {
+    let x = vec![3., 0., 2., 1.];
+    let mut y = vec![-1., -2., -1., 0.];
+    let a = 2.;
+    let b = 3.;
+
+    //y = ax + by
+    y.fma_axpby(a, &x, b);
+
+    assert_eq!(y, [3., -6., 1., 2.]);
+}
+
+#[test]
+fn test_fma_matches_unfused() {
+    // the fused operations differ from the unfused ones only in the
+    // final digits, within the rounding error of the unfused sums
+    let n = 1000;
+    let x: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+    let y: Vec<f64> = (0..n).map(|i| (i as f64 * 0.7).cos()).collect();
+
+    let unfused = zip(&x, &y).fold(0., |acc, (&x, &y)| acc + x * y);
+    let bound = n as f64 * f64::EPSILON * zip(&x, &y).fold(0., |acc, (x, y)| acc + (x * y).abs());
+    assert!((x.fma_dot(&y) - unfused).abs() <= bound);
+
+    let (a, b) = (0.3, -1.7);
+    let mut fused = y.clone();
+    fused.fma_axpby(a, &x, b);
+    for ((&f, &x), &y) in zip(zip(&fused, &x), &y) {
+        let unfused = a * x + b * y;
+        assert!((f - unfused).abs() <= 2. * f64::EPSILON * (a * x).abs().max((b * y).abs()));
+    }
+
+    // with cancellation the unfused result loses the final digits
+    // entirely, while the fused result is exact
+    let (a, x) = (1. + f64::EPSILON, [1. - f64::EPSILON]);
+    let mut y = [-1.];
+    assert_eq!(a * x[0] + y[0], 0.);
+    y.fma_axpby(a, &x, 1.);
+    assert_eq!(y[0], -f64::EPSILON * f64::EPSILON);
+}
+
+// Timing of the fused dot and axpby against the unfused ones on vectors
+// of length 10 million.   Run with
+//
+//   cargo test --release --lib bench_fma_vector_ops -- --ignored --nocapture
+//
+// The fused operations are only faster on targets with a hardware FMA
+// instruction, e.g. with RUSTFLAGS="-C target-cpu=native".
+#[test]
+#[ignore]
+fn bench_fma_vector_ops() {
+    use std::time::Instant;
+
+    let n = 10_000_000;
+    let x: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+    let y: Vec<f64> = (0..n).map(|i| (i as f64 * 0.7).cos()).collect();
+    let (a, b) = (0.3, -1.7);
+
+    let time = |f: &mut dyn FnMut()| {
+        let start = Instant::now();
+        for _ in 0..5 {
+            f();
+        }
+        start.elapsed().as_secs_f64() / 5. * 1e3
+    };
+
+    let (mut dot, mut fma_dot) = (0., 0.);
+    let t_dot = time(&mut || dot = zip(&x, &y).fold(0., |acc, (&x, &y)| acc + x * y));
+    let t_fma_dot = time(&mut || fma_dot = x.fma_dot(&y));
+
+    let (mut w, mut fma_w) = (y.clone(), y.clone());
+    let t_axpby = time(&mut || {
+        w.copy_from(&y);
+        zip(&mut w, &x).for_each(|(w, &x)| *w = a * x + b * *w);
+    });
+    let t_fma_axpby = time(&mut || {
+        fma_w.copy_from(&y);
+        fma_w.fma_axpby(a, &x, b);
+    });
+
+    println!(
+        "{:>8}: {:8.2} ms unfused, {:8.2} ms fused",
+        "dot", t_dot, t_fma_dot
+    );
+    println!(
+        "{:>8}: {:8.2} ms unfused, {:8.2} ms fused",
+        "axpby", t_axpby, t_fma_axpby
+    );
+    println!(
+        "max difference: dot {:e}, axpby {:e}",
+        (dot - fma_dot).abs(),
+        w.norm_inf_diff(&fma_w)
+    );
+}
+
 #[test]
 fn test_waxpby() {
     let x = vec![3., 0., 2., 1.];
@@ -195,3 +412,39 @@ fn test_waxpby() {
 
     assert_eq!(w, [3., -6., 1., 2.]);
 }
+
+#[test]
+fn test_vec3_matches_slices() {
+    // fixed size operations must be bitwise identical
+    // to the slice operations they replace
+    let x: [f64; 3] = [0.1, -2.3, 3.7e-3];
+    let y = [1.3e2, 0.7, -0.45];
+    let (a, b) = (0.3, -1.7);
+
+    assert_eq!(dot3(&x, &y).to_bits(), x.dot(&y).to_bits());
+    assert_eq!(norm3(&x).to_bits(), x.norm().to_bits());
+
+    let mut w = vec![0.; 3];
+    w.waxpby(a, &x, b, &y);
+    assert_eq!(waxpby3(a, &x, b, &y).to_vec(), w);
+
+    let (mut u, mut v) = (x, x.to_vec());
+    scale3(&mut u, a);
+    v.scale(a);
+    assert_eq!(u.to_vec(), v);
+
+    let (mut u, mut v) = (y, y.to_vec());
+    assert_eq!(normalize3(&mut u), v.normalize());
+    assert_eq!(u.to_vec(), v);
+
+    let mut u = [0.; 3];
+    store3(&mut u, &cross3(&x, &y));
+    assert_eq!(
+        load3(&u),
+        [
+            x[1] * y[2] - x[2] * y[1],
+            x[2] * y[0] - x[0] * y[2],
+            x[0] * y[1] - x[1] * y[0],
+        ]
+    );
+}
@@ -3,12 +3,14 @@
 // assorted other functionality missing from std
 
 // a drop-in replacement for the julia "findall" function,
-// which serves as a vectorized version of the std::iter::position
+// which serves as a vectorized version of the core::iter::position
 // returning indices of *all* elements satisfying a predicate
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use crate::qdldl;
+use core::cmp::Ordering;
 use num_traits::Num;
-use std::cmp::Ordering;
 
 #[cfg_attr(not(feature = "sdp"), allow(dead_code))]
 pub(crate) trait PositionAll<T>: Iterator<Item = T> {
@@ -82,6 +84,23 @@ where
     p.sort_by(|&i, &j| f(&v[i], &v[j]));
 }
 
+// number of entries in a dense matrix of the given size, or
+// None if it overflows usize.   Sizes computed from user data
+// should be checked with this and `checked_sum` before any
+// allocation, since wrapped sizes would otherwise pass the
+// length checks made on the data
+#[cfg_attr(not(feature = "sdp"), allow(dead_code))]
+pub(crate) fn checked_dims(size: (usize, usize)) -> Option<usize> {
+    size.0.checked_mul(size.1)
+}
+
+// sum of a sequence of sizes, or None if it overflows usize
+pub(crate) fn checked_sum(sizes: impl IntoIterator<Item = usize>) -> Option<usize> {
+    sizes
+        .into_iter()
+        .try_fold(0usize, |acc, size| acc.checked_add(size))
+}
+
 // PJG: maybe should be a vector trait, but T needs to admit
 // non-float types (e.g. usize).  Would require partition of the
 // vector math traits into those that require FloatT and those
@@ -110,6 +129,17 @@ fn test_position_all() {
     assert_eq!(idx, Vec::<usize>::new());
 }
 
+#[test]
+fn test_checked_dims() {
+    assert_eq!(checked_dims((3, 4)), Some(12));
+    assert_eq!(checked_dims((0, usize::MAX)), Some(0));
+    assert_eq!(checked_dims((2, usize::MAX / 2 + 1)), None);
+
+    assert_eq!(checked_sum([1, 2, 3]), Some(6));
+    assert_eq!(checked_sum([]), Some(0));
+    assert_eq!(checked_sum([usize::MAX, 1]), None);
+}
+
 #[test]
 fn test_permute() {
     let mut x = vec![0; 5];
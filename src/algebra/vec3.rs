@@ -0,0 +1,83 @@
+use crate::algebra::*;
+
+// ---------------------------------------------------
+// Fixed size vector operations for the 3 dimensional
+// exponential and power cones.   Operating on [T; 3] rather
+// than slices lets the compiler fully unroll each operation
+// with no bounds checks.
+//
+// Every function here performs the same floating point
+// operations in the same order as its VectorMath counterpart,
+// so results are bitwise identical to the slice versions.
+// ---------------------------------------------------
+
+/// copy the first three entries of x into an array
+#[inline]
+pub(crate) fn load3<T: FloatT>(x: &[T]) -> [T; 3] {
+    [x[0], x[1], x[2]]
+}
+
+/// copy x into the first three entries of y
+#[inline]
+pub(crate) fn store3<T: FloatT>(y: &mut [T], x: &[T; 3]) {
+    y[..3].copy_from_slice(x);
+}
+
+/// xᵀy
+#[inline]
+pub(crate) fn dot3<T: FloatT>(x: &[T; 3], y: &[T; 3]) -> T {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "fma")] {
+            let out = T::fma(x[0], y[0], T::zero());
+            let out = T::fma(x[1], y[1], out);
+            T::fma(x[2], y[2], out)
+        } else {
+            T::zero() + x[0] * y[0] + x[1] * y[1] + x[2] * y[2]
+        }
+    }
+}
+
+/// Euclidean norm of x
+#[inline]
+pub(crate) fn norm3<T: FloatT>(x: &[T; 3]) -> T {
+    T::sqrt(dot3(x, x))
+}
+
+/// x = c*x
+#[inline]
+pub(crate) fn scale3<T: FloatT>(x: &mut [T; 3], c: T) {
+    for xi in x.iter_mut() {
+        *xi *= c;
+    }
+}
+
+/// normalize x to unit length, returning its original norm
+#[inline]
+pub(crate) fn normalize3<T: FloatT>(x: &mut [T; 3]) -> T {
+    let norm = norm3(x);
+    if norm == T::zero() {
+        return T::zero();
+    }
+    scale3(x, norm.recip());
+    norm
+}
+
+/// returns a*x + b*y
+#[inline]
+pub(crate) fn waxpby3<T: FloatT>(a: T, x: &[T; 3], b: T, y: &[T; 3]) -> [T; 3] {
+    [
+        a * x[0] + b * y[0],
+        a * x[1] + b * y[1],
+        a * x[2] + b * y[2],
+    ]
+}
+
+/// cross product x × y
+#[inline]
+pub(crate) fn cross3<T: FloatT>(x: &[T; 3], y: &[T; 3]) -> [T; 3] {
+    [
+        x[1] * y[2] - x[2] * y[1],
+        x[2] * y[0] - x[0] * y[2],
+        x[0] * y[1] - x[1] * y[0],
+    ]
+}
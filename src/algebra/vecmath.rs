@@ -1,6 +1,8 @@
 use super::{FloatT, ScalarMath, VectorMath};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::iter::zip;
 use itertools::izip;
-use std::iter::zip;
 
 impl<T: FloatT> VectorMath<T> for [T] {
     fn copy_from(&mut self, src: &[T]) -> &mut Self {
@@ -16,6 +18,46 @@ impl<T: FloatT> VectorMath<T> for [T] {
             .collect()
     }
 
+    fn swap_elements(&mut self, i: usize, j: usize) -> &mut Self {
+        self.swap(i, j);
+        self
+    }
+
+    fn apply_permutation(&self, perm: &[usize]) -> Vec<T> {
+        assert_eq!(self.len(), perm.len());
+        perm.iter().map(|&k| self[k]).collect()
+    }
+
+    fn apply_permutation_inplace(&mut self, perm: &[usize]) -> &mut Self {
+        assert_eq!(self.len(), perm.len());
+        debug_assert!(_is_permutation(perm));
+
+        for start in 0..perm.len() {
+            // only move each cycle once, starting from its smallest index
+            let mut k = perm[start];
+            while k > start {
+                k = perm[k];
+            }
+            if k < start {
+                continue;
+            }
+
+            // shift elements one step along the cycle
+            let tmp = self[start];
+            let mut j = start;
+            loop {
+                let k = perm[j];
+                if k == start {
+                    self[j] = tmp;
+                    break;
+                }
+                self[j] = self[k];
+                j = k;
+            }
+        }
+        self
+    }
+
     fn scalarop(&mut self, op: impl Fn(T) -> T) -> &mut Self {
         for x in &mut *self {
             *x = op(*x);
@@ -79,7 +121,17 @@ impl<T: FloatT> VectorMath<T> for [T] {
     }
 
     fn dot(&self, y: &[T]) -> T {
-        zip(self, y).fold(T::zero(), |acc, (&x, &y)| acc + x * y)
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "fma")] {
+                self.fma_dot(y)
+            } else {
+                zip(self, y).fold(T::zero(), |acc, (&x, &y)| acc + x * y)
+            }
+        }
+    }
+
+    fn fma_dot(&self, y: &[T]) -> T {
+        zip(self, y).fold(T::zero(), |acc, (&x, &y)| T::fma(x, y, acc))
     }
 
     fn dot_shifted(z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T {
@@ -96,6 +148,18 @@ impl<T: FloatT> VectorMath<T> for [T] {
         out
     }
 
+    fn sparse_dot(&self, indices: &[usize], values: &[T]) -> T {
+        assert_eq!(indices.len(), values.len());
+        zip(indices, values).fold(T::zero(), |acc, (&i, &v)| acc + self[i] * v)
+    }
+
+    fn sparse_norm(&self, indices: &[usize]) -> T {
+        let sumsq = indices
+            .iter()
+            .fold(T::zero(), |acc, &i| acc + self[i] * self[i]);
+        T::sqrt(sumsq)
+    }
+
     fn dist(&self, y: &Self) -> T {
         let dist2 = zip(self, y).fold(T::zero(), |acc, (&x, &y)| acc + T::powi(x - y, 2));
         T::sqrt(dist2)
@@ -109,6 +173,46 @@ impl<T: FloatT> VectorMath<T> for [T] {
         self.dot(self)
     }
 
+    fn dot_accumulate(&self, y: &[T]) -> Vec<T> {
+        assert_eq!(self.len(), y.len());
+        zip(self, y)
+            .scan(T::zero(), |acc, (&x, &y)| {
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "fma")] {
+                        *acc = T::fma(x, y, *acc);
+                    } else {
+                        *acc += x * y;
+                    }
+                }
+                Some(*acc)
+            })
+            .collect()
+    }
+
+    fn sumsq_accumulate(&self) -> Vec<T> {
+        self.dot_accumulate(self)
+    }
+
+    fn bilinear_form_diag(&self, m: &[T], y: &[T]) -> T {
+        assert_eq!(self.len(), m.len());
+        assert_eq!(self.len(), y.len());
+        if m.iter().all(|&mi| mi == T::one()) {
+            return self.dot(y);
+        }
+        zip(zip(self, m), y).fold(T::zero(), |acc, ((&x, &mi), &y)| acc + x * mi * y)
+    }
+
+    fn bilinear_form_diag_squared(&self, m: &[T]) -> T {
+        assert_eq!(self.len(), m.len());
+        if m.iter().all(|&mi| mi == T::one()) {
+            return self.sumsq();
+        }
+        zip(self, m).fold(T::zero(), |acc, (&x, &mi)| {
+            let prod = x * mi;
+            acc + prod * prod
+        })
+    }
+
     // 2-norm
     fn norm(&self) -> T {
         T::sqrt(self.sumsq())
@@ -181,9 +285,21 @@ impl<T: FloatT> VectorMath<T> for [T] {
     }
 
     fn axpby(&mut self, a: T, x: &[T], b: T) -> &mut Self {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "fma")] {
+                self.fma_axpby(a, x, b)
+            } else {
+                assert_eq!(self.len(), x.len());
+                zip(&mut *self, x).for_each(|(y, x)| *y = a * (*x) + b * (*y));
+                self
+            }
+        }
+    }
+
+    fn fma_axpby(&mut self, a: T, x: &[T], b: T) -> &mut Self {
         assert_eq!(self.len(), x.len());
 
-        zip(&mut *self, x).for_each(|(y, x)| *y = a * (*x) + b * (*y));
+        zip(&mut *self, x).for_each(|(y, x)| *y = T::fma(a, *x, b * (*y)));
         self
     }
 
@@ -197,3 +313,9 @@ impl<T: FloatT> VectorMath<T> for [T] {
         self
     }
 }
+
+fn _is_permutation(perm: &[usize]) -> bool {
+    let mut seen = vec![false; perm.len()];
+    perm.iter()
+        .all(|&k| k < perm.len() && !core::mem::replace(&mut seen[k], true))
+}
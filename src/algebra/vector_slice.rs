@@ -0,0 +1,79 @@
+use crate::algebra::FloatT;
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use alloc::borrow::Cow;
+
+/// Vector data accepted by the solver constructors and data update
+/// functions, e.g. slices, `Vec<T>` and fixed size arrays.
+///
+/// With the `nalgebra` feature this trait is also implemented for
+/// `DVector<T>`, and with the `ndarray` feature for one dimensional
+/// arrays and views such as `Array1<T>` and `ArrayView1<T>`.
+///
+/// This trait is sealed and can not be implemented outside of this crate.
+pub trait AsVectorSlice<T: FloatT>: private::AsVectorSliceSealed {
+    /// Borrow the data as a contiguous slice.
+    ///
+    /// Data that is not stored contiguously, e.g. a strided ndarray
+    /// view, is copied rather than rejected.
+    fn as_vector_slice(&self) -> Cow<'_, [T]>;
+}
+
+impl<T: FloatT> AsVectorSlice<T> for [T] {
+    fn as_vector_slice(&self) -> Cow<'_, [T]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: FloatT> AsVectorSlice<T> for Vec<T> {
+    fn as_vector_slice(&self) -> Cow<'_, [T]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: FloatT, const N: usize> AsVectorSlice<T> for [T; N] {
+    fn as_vector_slice(&self) -> Cow<'_, [T]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: FloatT, S: AsVectorSlice<T> + ?Sized> AsVectorSlice<T> for &S {
+    fn as_vector_slice(&self) -> Cow<'_, [T]> {
+        (**self).as_vector_slice()
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: FloatT> AsVectorSlice<T> for nalgebra::DVector<T> {
+    fn as_vector_slice(&self) -> Cow<'_, [T]> {
+        Cow::Borrowed(self.as_slice())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T, S> AsVectorSlice<T> for ndarray::ArrayBase<S, ndarray::Ix1>
+where
+    T: FloatT,
+    S: ndarray::Data<Elem = T>,
+{
+    fn as_vector_slice(&self) -> Cow<'_, [T]> {
+        match self.as_slice() {
+            Some(v) => Cow::Borrowed(v),
+            None => Cow::Owned(self.to_vec()),
+        }
+    }
+}
+
+mod private {
+    pub trait AsVectorSliceSealed {}
+    impl<T> AsVectorSliceSealed for [T] {}
+    impl<T> AsVectorSliceSealed for alloc::vec::Vec<T> {}
+    impl<T, const N: usize> AsVectorSliceSealed for [T; N] {}
+    impl<S: AsVectorSliceSealed + ?Sized> AsVectorSliceSealed for &S {}
+
+    #[cfg(feature = "nalgebra")]
+    impl<T> AsVectorSliceSealed for nalgebra::DVector<T> {}
+
+    #[cfg(feature = "ndarray")]
+    impl<S: ndarray::RawData> AsVectorSliceSealed for ndarray::ArrayBase<S, ndarray::Ix1> {}
+}
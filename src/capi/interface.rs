@@ -0,0 +1,219 @@
+#![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+
+use super::types::*;
+use crate::solver::{
+    core::{cones::SupportedConeT, IPSolver},
+    implementations::default::*,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// Error codes returned by the C interface.   Nonnegative return
+// values from `clarabel_solve` are solver status codes.
+
+/// A required pointer argument was null.
+pub const CLARABEL_ERR_NULL: i32 = -1;
+/// A panic occurred inside the solver.
+pub const CLARABEL_ERR_PANIC: i32 = -2;
+/// Problem data, cones or settings were invalid, or an
+/// output buffer had the wrong length.
+pub const CLARABEL_ERR_DATA: i32 = -3;
+/// Data updates are not allowed with the current settings.
+pub const CLARABEL_ERR_UPDATE: i32 = -4;
+
+// run `f`, converting any panic into an error code
+fn guard(f: impl FnOnce() -> i32) -> i32 {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(CLARABEL_ERR_PANIC)
+}
+
+/// Default solver settings.
+#[no_mangle]
+pub extern "C" fn clarabel_default_settings() -> ClarabelSettings {
+    ClarabelSettings::from(&DefaultSettings::<f64>::default())
+}
+
+/// Create a new solver for the problem with data `(P,q,A,b)`, where `q`
+/// has length `P.n` and `b` has length `A.m`.
+///
+/// Returns null if any pointer is null, the problem data or settings
+/// are invalid, or the solver panics during setup.   The returned
+/// solver must be released with `clarabel_free`.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_new(
+    P: *const ClarabelCscMatrix,
+    q: *const f64,
+    A: *const ClarabelCscMatrix,
+    b: *const f64,
+    cones: *const ClarabelCone,
+    ncones: usize,
+    settings: *const ClarabelSettings,
+) -> *mut ClarabelSolver {
+    let solver = catch_unwind(AssertUnwindSafe(|| {
+        if P.is_null() || A.is_null() || settings.is_null() {
+            return None;
+        }
+        let (P, A) = ((*P).to_CscMatrix()?, (*A).to_CscMatrix()?);
+        let q = to_slice(q, P.n)?;
+        let b = to_slice(b, A.m)?;
+
+        let cones = to_slice(cones, ncones)?
+            .iter()
+            .map(|&cone| cone.to_SupportedConeT())
+            .collect::<Option<Vec<SupportedConeT<f64>>>>()?;
+
+        let settings = DefaultSettings::from(&*settings);
+
+        let solver = DefaultSolver::new(&P, q, &A, b, &cones, settings).ok()?;
+        Some(Box::new(ClarabelSolver { solver }))
+    }));
+
+    match solver {
+        Ok(Some(solver)) => Box::into_raw(solver),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// Solve the problem, returning the solver status code or a
+/// negative error code.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_solve(solver: *mut ClarabelSolver) -> i32 {
+    let Some(s) = solver.as_mut() else {
+        return CLARABEL_ERR_NULL;
+    };
+    guard(|| {
+        s.solver.solve();
+        status_code(s.solver.solution.status)
+    })
+}
+
+/// Write the number of variables and constraints of the problem.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_get_dims(
+    solver: *const ClarabelSolver,
+    n: *mut usize,
+    m: *mut usize,
+) -> i32 {
+    let (Some(s), false, false) = (solver.as_ref(), n.is_null(), m.is_null()) else {
+        return CLARABEL_ERR_NULL;
+    };
+    *n = s.solver.solution.x.len();
+    *m = s.solver.solution.z.len();
+    0
+}
+
+// copy a solution vector into a caller supplied buffer
+unsafe fn copy_out(src: &[f64], dst: *mut f64, len: usize) -> i32 {
+    if dst.is_null() {
+        return CLARABEL_ERR_NULL;
+    }
+    if len != src.len() {
+        return CLARABEL_ERR_DATA;
+    }
+    std::ptr::copy_nonoverlapping(src.as_ptr(), dst, len);
+    0
+}
+
+/// Copy the primal solution `x` into a buffer of length `len`, which
+/// must equal the number of variables.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_get_x(
+    solver: *const ClarabelSolver,
+    x: *mut f64,
+    len: usize,
+) -> i32 {
+    match solver.as_ref() {
+        Some(s) => copy_out(&s.solver.solution.x, x, len),
+        None => CLARABEL_ERR_NULL,
+    }
+}
+
+/// Copy the dual solution `z` into a buffer of length `len`, which
+/// must equal the number of constraints.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_get_z(
+    solver: *const ClarabelSolver,
+    z: *mut f64,
+    len: usize,
+) -> i32 {
+    match solver.as_ref() {
+        Some(s) => copy_out(&s.solver.solution.z, z, len),
+        None => CLARABEL_ERR_NULL,
+    }
+}
+
+/// Copy the slacks `s` into a buffer of length `len`, which
+/// must equal the number of constraints.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_get_s(
+    solver: *const ClarabelSolver,
+    s: *mut f64,
+    len: usize,
+) -> i32 {
+    match solver.as_ref() {
+        Some(solver) => copy_out(&solver.solver.solution.s, s, len),
+        None => CLARABEL_ERR_NULL,
+    }
+}
+
+/// Write the status, objective values and residuals from the last solve.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_get_info(
+    solver: *const ClarabelSolver,
+    info: *mut ClarabelInfo,
+) -> i32 {
+    let (Some(s), false) = (solver.as_ref(), info.is_null()) else {
+        return CLARABEL_ERR_NULL;
+    };
+    *info = ClarabelInfo::from(&s.solver);
+    0
+}
+
+// update q or b from a C array
+unsafe fn update_vector(
+    solver: *mut ClarabelSolver,
+    v: *const f64,
+    len: usize,
+    update: impl FnOnce(&mut DefaultSolver<f64>, &[f64]) -> Result<(), DataUpdateError>,
+) -> i32 {
+    let Some(s) = solver.as_mut() else {
+        return CLARABEL_ERR_NULL;
+    };
+    let Some(v) = to_slice(v, len) else {
+        return CLARABEL_ERR_NULL;
+    };
+    guard(|| match update(&mut s.solver, v) {
+        Ok(()) => 0,
+        Err(DataUpdateError::BadFormat(_)) => CLARABEL_ERR_DATA,
+        Err(_) => CLARABEL_ERR_UPDATE,
+    })
+}
+
+/// Overwrite the linear cost `q`.  Data updates require that
+/// presolve is disabled in the solver settings.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_update_q(
+    solver: *mut ClarabelSolver,
+    q: *const f64,
+    len: usize,
+) -> i32 {
+    update_vector(solver, q, len, |s, v| s.update_q(&v.to_vec()))
+}
+
+/// Overwrite the constraint right hand side `b`.  Data updates
+/// require that presolve is disabled in the solver settings.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_update_b(
+    solver: *mut ClarabelSolver,
+    b: *const f64,
+    len: usize,
+) -> i32 {
+    update_vector(solver, b, len, |s, v| s.update_b(&v.to_vec()))
+}
+
+/// Release a solver created with `clarabel_new`.  Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn clarabel_free(solver: *mut ClarabelSolver) {
+    if !solver.is_null() {
+        drop(Box::from_raw(solver));
+    }
+}
@@ -0,0 +1,15 @@
+//! C interface to the solver.
+//!
+//! Functions in this module use the C calling convention and plain C
+//! data types, and are declared in the header `include/clarabel.h`.
+//! The header is generated with cbindgen by the build script, which
+//! describes how to update the committed copy.   Solvers use `f64`
+//! data only.
+//!
+//! All pointer arguments are checked for null, and panics inside the
+//! solver are caught and returned as error codes.
+
+mod interface;
+mod types;
+pub use interface::*;
+pub use types::*;
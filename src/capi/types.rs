@@ -0,0 +1,316 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::CscMatrix;
+use crate::solver::core::cones::SupportedConeT::{self, *};
+use crate::solver::implementations::default::*;
+use crate::solver::SolverStatus;
+use std::slice;
+
+// The types defined here are for exchanging data
+// between Rust and C.   All fields are primitives or
+// raw pointers so that they can be used from plain C.
+
+/// Sparse matrix in compressed sparse column format.
+///
+/// `colptr` has length `n+1`, and `rowval` and `nzval`
+/// have length `colptr[n]`.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ClarabelCscMatrix {
+    pub m: usize,
+    pub n: usize,
+    pub colptr: *const usize,
+    pub rowval: *const usize,
+    pub nzval: *const f64,
+}
+
+/// Cone types accepted in [`ClarabelCone::tag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub enum ClarabelConeTag {
+    ClarabelZeroCone = 0,
+    ClarabelNonnegativeCone = 1,
+    ClarabelSecondOrderCone = 2,
+    ClarabelExponentialCone = 3,
+    ClarabelPowerCone = 4,
+    ClarabelGenPowerCone = 5,
+    ClarabelPSDTriangleCone = 6,
+}
+
+/// Plain description of a cone.
+///
+/// `dim` is the cone dimension for zero, nonnegative and second order
+/// cones, the matrix dimension for PSD cones, and the dimension of the
+/// norm bounded part for generalized power cones.  `power` is the power
+/// cone exponent, and `alpha` / `nalpha` hold the exponents of a
+/// generalized power cone.   Unused fields are ignored.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ClarabelCone {
+    pub tag: u32,
+    pub dim: usize,
+    pub power: f64,
+    pub alpha: *const f64,
+    pub nalpha: usize,
+}
+
+/// Solver settings.  See [`DefaultSettings`] for descriptions of each
+/// field.   Use `clarabel_default_settings` to obtain default values.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ClarabelSettings {
+    pub max_iter: u32,
+    pub time_limit: f64,
+    pub verbose: bool,
+    pub max_step_fraction: f64,
+    pub tol_gap_abs: f64,
+    pub tol_gap_rel: f64,
+    pub tol_feas: f64,
+    pub tol_infeas_abs: f64,
+    pub tol_infeas_rel: f64,
+    pub tol_ktratio: f64,
+    pub reduced_tol_gap_abs: f64,
+    pub reduced_tol_gap_rel: f64,
+    pub reduced_tol_feas: f64,
+    pub reduced_tol_infeas_abs: f64,
+    pub reduced_tol_infeas_rel: f64,
+    pub reduced_tol_ktratio: f64,
+    pub equilibrate_enable: bool,
+    pub equilibrate_max_iter: u32,
+    pub equilibrate_min_scaling: f64,
+    pub equilibrate_max_scaling: f64,
+    pub static_regularization_enable: bool,
+    pub static_regularization_constant: f64,
+    pub static_regularization_proportional: f64,
+    pub dynamic_regularization_enable: bool,
+    pub dynamic_regularization_eps: f64,
+    pub dynamic_regularization_delta: f64,
+    pub iterative_refinement_enable: bool,
+    pub iterative_refinement_reltol: f64,
+    pub iterative_refinement_abstol: f64,
+    pub iterative_refinement_max_iter: u32,
+    pub iterative_refinement_stop_ratio: f64,
+    pub presolve_enable: bool,
+}
+
+/// Solver information after a call to `clarabel_solve`.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ClarabelInfo {
+    pub status: u32,
+    pub iterations: u32,
+    pub solve_time: f64,
+    pub obj_val: f64,
+    pub obj_val_dual: f64,
+    pub r_prim: f64,
+    pub r_dual: f64,
+    pub gap_abs: f64,
+    pub gap_rel: f64,
+}
+
+/// Opaque solver handle returned by `clarabel_new`.
+pub struct ClarabelSolver {
+    pub(crate) solver: DefaultSolver<f64>,
+}
+
+/// Solver status codes returned by `clarabel_solve`.  See
+/// [`SolverStatus`] for descriptions of each status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub enum ClarabelStatus {
+    ClarabelUnsolved = 0,
+    ClarabelSolved = 1,
+    ClarabelPrimalInfeasible = 2,
+    ClarabelDualInfeasible = 3,
+    ClarabelAlmostSolved = 4,
+    ClarabelAlmostPrimalInfeasible = 5,
+    ClarabelAlmostDualInfeasible = 6,
+    ClarabelMaxIterations = 7,
+    ClarabelMaxTime = 8,
+    ClarabelNumericalError = 9,
+    ClarabelInsufficientProgress = 10,
+}
+
+impl From<SolverStatus> for ClarabelStatus {
+    fn from(status: SolverStatus) -> Self {
+        use ClarabelStatus::*;
+        match status {
+            SolverStatus::Unsolved => ClarabelUnsolved,
+            SolverStatus::Solved => ClarabelSolved,
+            SolverStatus::PrimalInfeasible => ClarabelPrimalInfeasible,
+            SolverStatus::DualInfeasible => ClarabelDualInfeasible,
+            SolverStatus::AlmostSolved => ClarabelAlmostSolved,
+            SolverStatus::AlmostPrimalInfeasible => ClarabelAlmostPrimalInfeasible,
+            SolverStatus::AlmostDualInfeasible => ClarabelAlmostDualInfeasible,
+            SolverStatus::MaxIterations => ClarabelMaxIterations,
+            SolverStatus::MaxTime => ClarabelMaxTime,
+            SolverStatus::NumericalError => ClarabelNumericalError,
+            SolverStatus::InsufficientProgress => ClarabelInsufficientProgress,
+        }
+    }
+}
+
+pub(crate) fn status_code(status: SolverStatus) -> i32 {
+    ClarabelStatus::from(status) as i32
+}
+
+// conversions from C data into Rust types.   Pointers are checked
+// for null, but their lengths are trusted.
+
+pub(crate) unsafe fn to_slice<'a, T>(p: *const T, len: usize) -> Option<&'a [T]> {
+    if len == 0 {
+        Some(&[])
+    } else if p.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(p, len))
+    }
+}
+
+impl ClarabelCscMatrix {
+    pub(crate) unsafe fn to_CscMatrix(self) -> Option<CscMatrix<f64>> {
+        let colptr = to_slice(self.colptr, self.n + 1)?;
+        let nnz = colptr[self.n];
+        let rowval = to_slice(self.rowval, nnz)?;
+        let nzval = to_slice(self.nzval, nnz)?;
+        let M = CscMatrix::new(
+            self.m,
+            self.n,
+            colptr.to_vec(),
+            rowval.to_vec(),
+            nzval.to_vec(),
+        );
+        M.check_format().ok()?;
+        Some(M)
+    }
+}
+
+impl ClarabelCone {
+    pub(crate) unsafe fn to_SupportedConeT(self) -> Option<SupportedConeT<f64>> {
+        use ClarabelConeTag::*;
+        let tag = [
+            ClarabelZeroCone,
+            ClarabelNonnegativeCone,
+            ClarabelSecondOrderCone,
+            ClarabelExponentialCone,
+            ClarabelPowerCone,
+            ClarabelGenPowerCone,
+            ClarabelPSDTriangleCone,
+        ]
+        .into_iter()
+        .find(|&t| t as u32 == self.tag)?;
+
+        let cone = match tag {
+            ClarabelZeroCone => ZeroConeT(self.dim),
+            ClarabelNonnegativeCone => NonnegativeConeT(self.dim),
+            ClarabelSecondOrderCone => SecondOrderConeT(self.dim),
+            ClarabelExponentialCone => ExponentialConeT(),
+            ClarabelPowerCone => PowerConeT(self.power),
+            ClarabelGenPowerCone => {
+                let alpha = to_slice(self.alpha, self.nalpha)?.to_vec();
+                GenPowerConeT(alpha, self.dim)
+            }
+            #[cfg(feature = "sdp")]
+            ClarabelPSDTriangleCone => PSDTriangleConeT(self.dim),
+            #[cfg(not(feature = "sdp"))]
+            ClarabelPSDTriangleCone => return None,
+        };
+        Some(cone)
+    }
+}
+
+impl From<&DefaultSettings<f64>> for ClarabelSettings {
+    fn from(set: &DefaultSettings<f64>) -> Self {
+        Self {
+            max_iter: set.max_iter,
+            time_limit: set.time_limit,
+            verbose: set.verbose,
+            max_step_fraction: set.max_step_fraction,
+            tol_gap_abs: set.tol_gap_abs,
+            tol_gap_rel: set.tol_gap_rel,
+            tol_feas: set.tol_feas,
+            tol_infeas_abs: set.tol_infeas_abs,
+            tol_infeas_rel: set.tol_infeas_rel,
+            tol_ktratio: set.tol_ktratio,
+            reduced_tol_gap_abs: set.reduced_tol_gap_abs,
+            reduced_tol_gap_rel: set.reduced_tol_gap_rel,
+            reduced_tol_feas: set.reduced_tol_feas,
+            reduced_tol_infeas_abs: set.reduced_tol_infeas_abs,
+            reduced_tol_infeas_rel: set.reduced_tol_infeas_rel,
+            reduced_tol_ktratio: set.reduced_tol_ktratio,
+            equilibrate_enable: set.equilibrate_enable,
+            equilibrate_max_iter: set.equilibrate_max_iter,
+            equilibrate_min_scaling: set.equilibrate_min_scaling,
+            equilibrate_max_scaling: set.equilibrate_max_scaling,
+            static_regularization_enable: set.static_regularization_enable,
+            static_regularization_constant: set.static_regularization_constant,
+            static_regularization_proportional: set.static_regularization_proportional,
+            dynamic_regularization_enable: set.dynamic_regularization_enable,
+            dynamic_regularization_eps: set.dynamic_regularization_eps,
+            dynamic_regularization_delta: set.dynamic_regularization_delta,
+            iterative_refinement_enable: set.iterative_refinement_enable,
+            iterative_refinement_reltol: set.iterative_refinement_reltol,
+            iterative_refinement_abstol: set.iterative_refinement_abstol,
+            iterative_refinement_max_iter: set.iterative_refinement_max_iter,
+            iterative_refinement_stop_ratio: set.iterative_refinement_stop_ratio,
+            presolve_enable: set.presolve_enable,
+        }
+    }
+}
+
+impl From<&ClarabelSettings> for DefaultSettings<f64> {
+    fn from(set: &ClarabelSettings) -> Self {
+        DefaultSettings::<f64> {
+            max_iter: set.max_iter,
+            time_limit: set.time_limit,
+            verbose: set.verbose,
+            max_step_fraction: set.max_step_fraction,
+            tol_gap_abs: set.tol_gap_abs,
+            tol_gap_rel: set.tol_gap_rel,
+            tol_feas: set.tol_feas,
+            tol_infeas_abs: set.tol_infeas_abs,
+            tol_infeas_rel: set.tol_infeas_rel,
+            tol_ktratio: set.tol_ktratio,
+            reduced_tol_gap_abs: set.reduced_tol_gap_abs,
+            reduced_tol_gap_rel: set.reduced_tol_gap_rel,
+            reduced_tol_feas: set.reduced_tol_feas,
+            reduced_tol_infeas_abs: set.reduced_tol_infeas_abs,
+            reduced_tol_infeas_rel: set.reduced_tol_infeas_rel,
+            reduced_tol_ktratio: set.reduced_tol_ktratio,
+            equilibrate_enable: set.equilibrate_enable,
+            equilibrate_max_iter: set.equilibrate_max_iter,
+            equilibrate_min_scaling: set.equilibrate_min_scaling,
+            equilibrate_max_scaling: set.equilibrate_max_scaling,
+            static_regularization_enable: set.static_regularization_enable,
+            static_regularization_constant: set.static_regularization_constant,
+            static_regularization_proportional: set.static_regularization_proportional,
+            dynamic_regularization_enable: set.dynamic_regularization_enable,
+            dynamic_regularization_eps: set.dynamic_regularization_eps,
+            dynamic_regularization_delta: set.dynamic_regularization_delta,
+            iterative_refinement_enable: set.iterative_refinement_enable,
+            iterative_refinement_reltol: set.iterative_refinement_reltol,
+            iterative_refinement_abstol: set.iterative_refinement_abstol,
+            iterative_refinement_max_iter: set.iterative_refinement_max_iter,
+            iterative_refinement_stop_ratio: set.iterative_refinement_stop_ratio,
+            presolve_enable: set.presolve_enable,
+            ..DefaultSettings::default()
+        }
+    }
+}
+
+impl From<&DefaultSolver<f64>> for ClarabelInfo {
+    fn from(solver: &DefaultSolver<f64>) -> Self {
+        let (sol, info) = (&solver.solution, &solver.info);
+        Self {
+            status: status_code(sol.status) as u32,
+            iterations: sol.iterations,
+            solve_time: sol.solve_time,
+            obj_val: sol.obj_val,
+            obj_val_dual: sol.obj_val_dual,
+            r_prim: sol.r_prim,
+            r_dual: sol.r_dual,
+            gap_abs: info.gap_abs,
+            gap_rel: info.gap_rel,
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Harness for running the solver over directories of benchmark problems,
+//! e.g. the Maros–Mészáros QP set in QPS format or CBLIB problems in CBF
+//! format, and reporting the results in CSV or JSON form.
+//!
+//! Each problem is loaded and solved in isolation, so that a problem
+//! that fails to load or panics in the solver is recorded in the report
+//! and does not stop the run.
+//!
+//! ```no_run
+//! use clarabel::io::bench::*;
+//! use clarabel::solver::DefaultSettings;
+//!
+//! let files = problem_files("maros_meszaros/").unwrap();
+//! let settings = DefaultSettings { verbose: false, ..DefaultSettings::default() };
+//! let report = run_benchmarks(&files, &settings, 60.);
+//! report.write_csv(std::io::stdout()).unwrap();
+//! println!("{:?}", report.summary(10.));
+//! ```
+
+use super::{read_cbf, read_qps, ConicProblem};
+use crate::solver::{ClarabelError, DefaultSettings, IPSolver, SolverStatus};
+use serde::Serialize;
+use std::any::Any;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Result of loading and solving a single benchmark problem.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    /// problem name, i.e. the file name without its extension
+    pub name: String,
+    /// solver status, or `LoadError`, `SetupError` or `Panicked` if the
+    /// problem could not be loaded, the solver could not be created
+    /// or the solver panicked
+    pub status: String,
+    /// objective value, including any constant term
+    pub objective: Option<f64>,
+    pub iterations: Option<u32>,
+    /// time to read the problem file (seconds)
+    pub load_time: f64,
+    /// solver time (seconds)
+    pub solve_time: Option<f64>,
+    pub r_prim: Option<f64>,
+    pub r_dual: Option<f64>,
+    /// load error or panic message
+    pub error: Option<String>,
+}
+
+impl BenchResult {
+    fn failed(name: &str, status: &str, load_time: f64, error: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: status.to_string(),
+            objective: None,
+            iterations: None,
+            load_time,
+            solve_time: None,
+            r_prim: None,
+            r_dual: None,
+            error: Some(error),
+        }
+    }
+
+    /// True if the problem was solved to full or reduced accuracy.
+    pub fn is_solved(&self) -> bool {
+        self.status == SolverStatus::Solved.to_string()
+            || self.status == SolverStatus::AlmostSolved.to_string()
+    }
+}
+
+/// Results of a benchmark run, in the order the problems were given.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    /// per-problem time limit used for the run (seconds)
+    pub time_limit: f64,
+    pub results: Vec<BenchResult>,
+}
+
+/// Summary statistics of a [`BenchReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchSummary {
+    pub problems: usize,
+    pub solved: usize,
+    /// shifted geometric mean of solve times, with unsolved problems
+    /// counted at the time limit
+    pub sgm_solve_time: f64,
+    /// shifted geometric mean of iteration counts of solved problems
+    pub sgm_iterations: f64,
+}
+
+/// Shifted geometric mean `(Π (vᵢ + shift))^(1/n) - shift` of `values`.
+///
+/// The shift reduces the influence of very small values, e.g. a shift
+/// of 10 seconds is commonly used for solve times.   Returns NaN for
+/// an empty input.
+pub fn shifted_geometric_mean(values: &[f64], shift: f64) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    let n = values.len() as f64;
+    let logsum: f64 = values.iter().map(|v| f64::ln(v + shift)).sum();
+    f64::exp(logsum / n) - shift
+}
+
+/// Problem files in `dir` with a `.qps`, `.mps` or `.cbf` extension
+/// (in any case), sorted by name.
+pub fn problem_files(dir: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && _problem_format(&path).is_some() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Load and solve each of `files` with `settings`, limiting the solve
+/// time of each problem to `time_limit` seconds.
+///
+/// Files in QPS/MPS format are read with [`read_qps`], and files in CBF
+/// format with [`read_cbf`].   Solver panics are caught and recorded
+/// as a `Panicked` status.
+pub fn run_benchmarks(
+    files: &[PathBuf],
+    settings: &DefaultSettings<f64>,
+    time_limit: f64,
+) -> BenchReport {
+    let mut settings = settings.clone();
+    settings.time_limit = f64::min(settings.time_limit, time_limit);
+
+    let results = files.iter().map(|f| _run_one(f, &settings)).collect();
+
+    BenchReport {
+        time_limit: settings.time_limit,
+        results,
+    }
+}
+
+impl BenchReport {
+    /// Summary statistics, with solve times shifted by `time_shift`
+    /// seconds in the geometric mean.
+    pub fn summary(&self, time_shift: f64) -> BenchSummary {
+        let solved: Vec<&BenchResult> = self.results.iter().filter(|r| r.is_solved()).collect();
+
+        let times: Vec<f64> = self
+            .results
+            .iter()
+            .map(|r| match (r.is_solved(), r.solve_time) {
+                (true, Some(t)) => t,
+                _ => self.time_limit,
+            })
+            .collect();
+        let iterations: Vec<f64> = solved
+            .iter()
+            .filter_map(|r| r.iterations.map(f64::from))
+            .collect();
+
+        BenchSummary {
+            problems: self.results.len(),
+            solved: solved.len(),
+            sgm_solve_time: shifted_geometric_mean(&times, time_shift),
+            sgm_iterations: shifted_geometric_mean(&iterations, 1.),
+        }
+    }
+
+    /// Write the results in CSV format, with a header row.   Missing
+    /// values are written as empty fields.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "name,status,objective,iterations,load_time,solve_time,r_prim,r_dual,error"
+        )?;
+
+        fn opt<T: ToString>(v: Option<T>) -> String {
+            v.map_or(String::new(), |v| v.to_string())
+        }
+
+        for r in &self.results {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{}",
+                _csv_escape(&r.name),
+                r.status,
+                opt(r.objective),
+                opt(r.iterations),
+                r.load_time,
+                opt(r.solve_time),
+                opt(r.r_prim),
+                opt(r.r_dual),
+                _csv_escape(r.error.as_deref().unwrap_or("")),
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Write the report in JSON format.
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+}
+
+// ---------------------------------------------------
+// internal helpers
+// ---------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProblemFormat {
+    Qps,
+    Cbf,
+}
+
+fn _problem_format(path: &Path) -> Option<ProblemFormat> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "qps" | "mps" => Some(ProblemFormat::Qps),
+        "cbf" => Some(ProblemFormat::Cbf),
+        _ => None,
+    }
+}
+
+fn _load_problem(path: &Path) -> Result<ConicProblem<f64>, String> {
+    let format = _problem_format(path).ok_or("unknown file format")?;
+    let reader = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+    match format {
+        ProblemFormat::Qps => read_qps(reader)
+            .map(|qp| qp.to_conic())
+            .map_err(|e| e.to_string()),
+        ProblemFormat::Cbf => read_cbf(reader).map_err(|e| e.to_string()),
+    }
+}
+
+fn _run_one(path: &Path, settings: &DefaultSettings<f64>) -> BenchResult {
+    let name = path
+        .file_stem()
+        .map_or(String::new(), |s| s.to_string_lossy().into_owned());
+
+    let start = Instant::now();
+    let loaded = catch_unwind(|| _load_problem(path));
+    let load_time = start.elapsed().as_secs_f64();
+
+    let problem = match loaded {
+        Ok(Ok(problem)) => problem,
+        Ok(Err(msg)) => return BenchResult::failed(&name, "LoadError", load_time, msg),
+        Err(e) => return BenchResult::failed(&name, "Panicked", load_time, _panic_message(e)),
+    };
+
+    let solved = catch_unwind(AssertUnwindSafe(|| {
+        let mut solver = problem.solver(settings.clone())?;
+        solver.solve();
+        Ok::<_, ClarabelError>(solver.solution)
+    }));
+
+    match solved {
+        Ok(Ok(solution)) => BenchResult {
+            name,
+            status: solution.status.to_string(),
+            objective: Some(solution.obj_val + problem.objective_constant),
+            iterations: Some(solution.iterations),
+            load_time,
+            solve_time: Some(solution.solve_time),
+            r_prim: Some(solution.r_prim),
+            r_dual: Some(solution.r_dual),
+            error: None,
+        },
+        Ok(Err(e)) => BenchResult::failed(&name, "SetupError", load_time, e.to_string()),
+        Err(e) => BenchResult::failed(&name, "Panicked", load_time, _panic_message(e)),
+    }
+}
+
+fn _panic_message(e: Box<dyn Any + Send>) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn _csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
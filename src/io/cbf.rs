@@ -0,0 +1,501 @@
+use super::ConicProblem;
+use crate::algebra::*;
+use crate::solver::{OptimizationSense, SupportedConeT, SupportedConeT::*};
+use std::io::{BufRead, Lines, Write};
+use thiserror::Error;
+
+// ---------------------------------------------------
+// Reader and writer for the Conic Benchmark Format (CBF), as used by
+// the CBLIB problem library.   CBF problems have the form
+//
+//   min/max  cᵀx + c₀
+//   s.t.     Ax + b ∈ K,  x ∈ K_var
+//
+// where K and K_var are products of cones given in the CON
+// and VAR sections.   We convert to the standard form
+// Ax + s = b, s ∈ K by writing each conic constraint row
+// as s = (Ax + b), i.e. with A negated, and adding rows for
+// any variable cones other than the free cone.   The writer
+// does the reverse, with all variables free.
+// ---------------------------------------------------
+
+/// Error type returned when reading or writing CBF files.
+#[derive(Error, Debug)]
+pub enum CbfError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("line {line}: {msg}")]
+    Parse { line: usize, msg: String },
+    #[error("line {line}: unsupported {what}")]
+    Unsupported { line: usize, what: String },
+    #[error("quadratic objective terms cannot be written in CBF format")]
+    QuadraticObjective,
+    #[error("cone {0} cannot be written in CBF format")]
+    UnsupportedCone(String),
+}
+
+// cone types supported in VAR and CON sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CbfCone {
+    Free,
+    NonNeg,
+    NonPos,
+    Zero,
+    Quad,
+    QuadRot,
+    Exp,
+}
+
+/// Read a problem in the Conic Benchmark Format (CBF).
+///
+/// Supports the VER, OBJSENSE, VAR, CON, OBJACOORD, OBJBCOORD,
+/// ACOORD and BCOORD sections, with free (F), linear (L+, L-, L=),
+/// quadratic (Q, QR) and exponential (EXP) cones.   Integer
+/// variables, power cones and semidefinite data are not supported
+/// and produce a [`CbfError::Unsupported`] error.
+///
+/// Rotated quadratic cones are converted to second order cones, and
+/// the element order of exponential cones is reversed to match the
+/// [`ExponentialConeT`](crate::solver::SupportedConeT::ExponentialConeT)
+/// convention.
+pub fn read_cbf<T, R>(reader: R) -> Result<ConicProblem<T>, CbfError>
+where
+    T: FloatT,
+    R: BufRead,
+{
+    let mut input = CbfLines {
+        lines: reader.lines(),
+        lineno: 0,
+    };
+
+    let mut version = None;
+    let mut sense = OptimizationSense::Minimize;
+    let mut n = 0;
+    let mut m = 0;
+    let mut varcones = Vec::new();
+    let mut concones = Vec::new();
+    let mut c = Vec::new();
+    let mut c0 = T::zero();
+    let mut arows: Vec<Vec<(usize, T)>> = Vec::new();
+    let mut bcbf = Vec::new();
+
+    while let Some(keyword) = input.next_line()? {
+        match keyword.as_str() {
+            "VER" => {
+                let ver = input.parse_count()?;
+                if !(1..=3).contains(&ver) {
+                    return Err(input.unsupported(format!("CBF version {}", ver)));
+                }
+                version = Some(ver);
+            }
+            "OBJSENSE" => {
+                sense = match input.expect_line()?.as_str() {
+                    "MIN" => OptimizationSense::Minimize,
+                    "MAX" => OptimizationSense::Maximize,
+                    s => return Err(input.error(&format!("invalid objective sense {}", s))),
+                };
+            }
+            "VAR" => {
+                (n, varcones) = input.parse_cone_list()?;
+                c = vec![T::zero(); n];
+            }
+            "CON" => {
+                (m, concones) = input.parse_cone_list()?;
+                arows = vec![Vec::new(); m];
+                bcbf = vec![T::zero(); m];
+            }
+            "OBJACOORD" => {
+                let nnz = input.parse_count()?;
+                for _ in 0..nnz {
+                    let line = input.expect_line()?;
+                    let [j, v] = input.split_fields(&line)?;
+                    let j = input.parse_index(j, n)?;
+                    c[j] = input.parse_float(v)?;
+                }
+            }
+            "OBJBCOORD" => {
+                let line = input.expect_line()?;
+                c0 = input.parse_float(&line)?;
+            }
+            "ACOORD" => {
+                let nnz = input.parse_count()?;
+                for _ in 0..nnz {
+                    let line = input.expect_line()?;
+                    let [i, j, v] = input.split_fields(&line)?;
+                    let i = input.parse_index(i, m)?;
+                    let j = input.parse_index(j, n)?;
+                    arows[i].push((j, input.parse_float(v)?));
+                }
+            }
+            "BCOORD" => {
+                let nnz = input.parse_count()?;
+                for _ in 0..nnz {
+                    let line = input.expect_line()?;
+                    let [i, v] = input.split_fields(&line)?;
+                    let i = input.parse_index(i, m)?;
+                    bcbf[i] = input.parse_float(v)?;
+                }
+            }
+            "INT" => {
+                return Err(input.unsupported("integer variables (INT)".to_string()));
+            }
+            "POWCONES" | "POW*CONES" | "PSDVAR" | "PSDCON" | "OBJFCOORD" | "FCOORD" | "HCOORD"
+            | "DCOORD" | "CHANGE" => {
+                return Err(input.unsupported(format!("section {}", keyword)));
+            }
+            _ => {
+                return Err(input.error(&format!("unrecognized keyword {}", keyword)));
+            }
+        }
+    }
+
+    if version.is_none() {
+        return Err(input.error("missing VER section"));
+    }
+
+    // assemble the constraints in standard form
+    let mut asm = Assembly::<T>::default();
+
+    let mut start = 0;
+    for &(kind, dim) in concones.iter() {
+        if let Some((cone, rows)) = cone_rows::<T>(kind, dim) {
+            for combo in rows {
+                let mut bi = T::zero();
+                for (off, coef) in combo {
+                    let i = start + off;
+                    bi += coef * bcbf[i];
+                    for &(j, v) in arows[i].iter() {
+                        asm.push_entry(j, -coef * v);
+                    }
+                }
+                asm.finish_row(bi);
+            }
+            asm.push_cone(cone);
+        }
+        start += dim;
+    }
+
+    let mut start = 0;
+    for &(kind, dim) in varcones.iter() {
+        if let Some((cone, rows)) = cone_rows::<T>(kind, dim) {
+            for combo in rows {
+                for (off, coef) in combo {
+                    asm.push_entry(start + off, -coef);
+                }
+                asm.finish_row(T::zero());
+            }
+            asm.push_cone(cone);
+        }
+        start += dim;
+    }
+
+    let A = CscMatrix::new_from_triplets(asm.b.len(), n, asm.I, asm.J, asm.V);
+
+    Ok(ConicProblem {
+        P: CscMatrix::zeros((n, n)),
+        q: c,
+        A,
+        b: asm.b,
+        cones: asm.cones,
+        sense,
+        objective_constant: c0,
+    })
+}
+
+/// Write a problem in the Conic Benchmark Format (CBF).
+///
+/// Zero, nonnegative, second order and exponential cones are
+/// supported.  All variables are written as free, and the constraints
+/// `Ax + s = b, s ∈ K` as `-Ax + b ∈ K`.   Problems with other cone
+/// types produce a [`CbfError::UnsupportedCone`] error.
+///
+/// CBF does not support quadratic objectives, and a problem with
+/// `P ≠ 0` produces a [`CbfError::QuadraticObjective`] error.  Such
+/// problems can be written after moving the quadratic term into a
+/// conic constraint using
+/// [`lift_quadratic_objective`](ConicProblem::lift_quadratic_objective).
+pub fn write_cbf<T, W>(problem: &ConicProblem<T>, mut writer: W) -> Result<(), CbfError>
+where
+    T: FloatT,
+    W: Write,
+{
+    let ConicProblem { P, q, A, b, .. } = problem;
+
+    if P.nzval.iter().any(|&v| v != T::zero()) {
+        return Err(CbfError::QuadraticObjective);
+    }
+
+    // CBF cone for each block, and the CBF row for each
+    // row of A.   Only exponential cones are reordered.
+    let mut concones = Vec::with_capacity(problem.cones.len());
+    let mut rowmap = Vec::with_capacity(A.m);
+    for cone in problem.cones.iter() {
+        let start = rowmap.len();
+        let (name, dim) = match cone {
+            ZeroConeT(dim) => ("L=", *dim),
+            NonnegativeConeT(dim) => ("L+", *dim),
+            SecondOrderConeT(dim) => ("Q", *dim),
+            ExponentialConeT() => ("EXP", 3),
+            _ => return Err(CbfError::UnsupportedCone(format!("{:?}", cone))),
+        };
+        if matches!(cone, ExponentialConeT()) {
+            rowmap.extend((start..start + 3).rev());
+        } else {
+            rowmap.extend(start..start + dim);
+        }
+        concones.push((name, dim));
+    }
+    assert_eq!(rowmap.len(), A.m);
+
+    let sense = match problem.sense {
+        OptimizationSense::Minimize => "MIN",
+        OptimizationSense::Maximize => "MAX",
+    };
+
+    writeln!(writer, "VER\n3\n")?;
+    writeln!(writer, "OBJSENSE\n{}\n", sense)?;
+
+    writeln!(writer, "VAR\n{} {}", A.n, usize::from(A.n > 0))?;
+    if A.n > 0 {
+        writeln!(writer, "F {}", A.n)?;
+    }
+    writeln!(writer)?;
+
+    writeln!(writer, "CON\n{} {}", A.m, concones.len())?;
+    for (name, dim) in concones {
+        writeln!(writer, "{} {}", name, dim)?;
+    }
+    writeln!(writer)?;
+
+    let qnz: Vec<_> = q
+        .iter()
+        .enumerate()
+        .filter(|(_, &v)| v != T::zero())
+        .collect();
+    if !qnz.is_empty() {
+        writeln!(writer, "OBJACOORD\n{}", qnz.len())?;
+        for (j, v) in qnz {
+            writeln!(writer, "{} {}", j, v)?;
+        }
+        writeln!(writer)?;
+    }
+
+    if problem.objective_constant != T::zero() {
+        writeln!(writer, "OBJBCOORD\n{}\n", problem.objective_constant)?;
+    }
+
+    if A.nnz() > 0 {
+        writeln!(writer, "ACOORD\n{}", A.nnz())?;
+        for j in 0..A.n {
+            for k in A.colptr[j]..A.colptr[j + 1] {
+                writeln!(writer, "{} {} {}", rowmap[A.rowval[k]], j, -A.nzval[k])?;
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    let bnz: Vec<_> = b
+        .iter()
+        .enumerate()
+        .filter(|(_, &v)| v != T::zero())
+        .collect();
+    if !bnz.is_empty() {
+        writeln!(writer, "BCOORD\n{}", bnz.len())?;
+        for (i, v) in bnz {
+            writeln!(writer, "{} {}", rowmap[i], v)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+// For each row of a CBF cone block, the rows of the standard form
+// slack as linear combinations (offset, coefficient) of the block's
+// rows.   Returns None for the free cone, which produces no rows.
+
+#[allow(clippy::type_complexity)]
+fn cone_rows<T: FloatT>(
+    kind: CbfCone,
+    dim: usize,
+) -> Option<(SupportedConeT<T>, Vec<Vec<(usize, T)>>)> {
+    let identity = |dim: usize, coef: T| -> Vec<Vec<(usize, T)>> {
+        (0..dim).map(|k| vec![(k, coef)]).collect()
+    };
+
+    match kind {
+        CbfCone::Free => None,
+        CbfCone::NonNeg => Some((NonnegativeConeT(dim), identity(dim, T::one()))),
+        CbfCone::NonPos => Some((NonnegativeConeT(dim), identity(dim, -T::one()))),
+        CbfCone::Zero => Some((ZeroConeT(dim), identity(dim, T::one()))),
+        CbfCone::Quad => Some((SecondOrderConeT(dim), identity(dim, T::one()))),
+        CbfCone::QuadRot => {
+            // 2x₁x₂ ≥ ‖x₃..‖² is equivalent to
+            // ((x₁+x₂)/√2, (x₁-x₂)/√2, x₃..) ∈ SOC
+            let r = T::FRAC_1_SQRT_2();
+            let mut rows = identity(dim, T::one());
+            rows[0] = vec![(0, r), (1, r)];
+            rows[1] = vec![(0, r), (1, -r)];
+            Some((SecondOrderConeT(dim), rows))
+        }
+        CbfCone::Exp => {
+            // CBF: x₁ ≥ x₂exp(x₃/x₂).   Ours: z ≥ y exp(x/y)
+            let rows = (0..3).rev().map(|k| vec![(k, T::one())]).collect();
+            Some((ExponentialConeT(), rows))
+        }
+    }
+}
+
+// standard form constraint data in triplet form
+
+struct Assembly<T> {
+    I: Vec<usize>,
+    J: Vec<usize>,
+    V: Vec<T>,
+    b: Vec<T>,
+    cones: Vec<SupportedConeT<T>>,
+}
+
+impl<T> Default for Assembly<T> {
+    fn default() -> Self {
+        Self {
+            I: Vec::new(),
+            J: Vec::new(),
+            V: Vec::new(),
+            b: Vec::new(),
+            cones: Vec::new(),
+        }
+    }
+}
+
+impl<T: FloatT> Assembly<T> {
+    fn push_entry(&mut self, col: usize, v: T) {
+        self.I.push(self.b.len());
+        self.J.push(col);
+        self.V.push(v);
+    }
+
+    fn finish_row(&mut self, bi: T) {
+        self.b.push(bi);
+    }
+
+    // merge consecutive linear cones of the same type
+    fn push_cone(&mut self, cone: SupportedConeT<T>) {
+        match (self.cones.last_mut(), &cone) {
+            (Some(ZeroConeT(d)), ZeroConeT(k)) => *d += k,
+            (Some(NonnegativeConeT(d)), NonnegativeConeT(k)) => *d += k,
+            _ => self.cones.push(cone),
+        }
+    }
+}
+
+// line oriented reader, skipping comments and blank lines
+
+struct CbfLines<R> {
+    lines: Lines<R>,
+    lineno: usize,
+}
+
+impl<R: BufRead> CbfLines<R> {
+    fn next_line(&mut self) -> Result<Option<String>, CbfError> {
+        for line in self.lines.by_ref() {
+            self.lineno += 1;
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            return Ok(Some(line.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn expect_line(&mut self) -> Result<String, CbfError> {
+        self.next_line()?
+            .ok_or_else(|| self.error("unexpected end of file"))
+    }
+
+    fn error(&self, msg: &str) -> CbfError {
+        CbfError::Parse {
+            line: self.lineno,
+            msg: msg.to_string(),
+        }
+    }
+
+    fn unsupported(&self, what: String) -> CbfError {
+        CbfError::Unsupported {
+            line: self.lineno,
+            what,
+        }
+    }
+
+    fn split_fields<'a, const N: usize>(&self, line: &'a str) -> Result<[&'a str; N], CbfError> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        fields
+            .try_into()
+            .map_err(|_| self.error(&format!("expected {} fields", N)))
+    }
+
+    fn parse_usize(&self, s: &str) -> Result<usize, CbfError> {
+        s.parse::<usize>()
+            .map_err(|_| self.error(&format!("invalid integer {}", s)))
+    }
+
+    fn parse_float<T: FloatT>(&self, s: &str) -> Result<T, CbfError> {
+        s.parse::<f64>()
+            .map(|v| v.as_T())
+            .map_err(|_| self.error(&format!("invalid number {}", s)))
+    }
+
+    fn parse_index(&self, s: &str, len: usize) -> Result<usize, CbfError> {
+        let idx = self.parse_usize(s)?;
+        if idx >= len {
+            return Err(self.error(&format!("index {} out of range", idx)));
+        }
+        Ok(idx)
+    }
+
+    fn parse_count(&mut self) -> Result<usize, CbfError> {
+        let line = self.expect_line()?;
+        self.parse_usize(&line)
+    }
+
+    // a "total count" header line followed by one line per cone
+    fn parse_cone_list(&mut self) -> Result<(usize, Vec<(CbfCone, usize)>), CbfError> {
+        let line = self.expect_line()?;
+        let [total, ncones] = self.split_fields(&line)?;
+        let total = self.parse_usize(total)?;
+        let ncones = self.parse_usize(ncones)?;
+
+        let mut cones = Vec::with_capacity(ncones);
+        for _ in 0..ncones {
+            let line = self.expect_line()?;
+            let [name, dim] = self.split_fields(&line)?;
+            let dim = self.parse_usize(dim)?;
+            let kind = match name {
+                "F" => CbfCone::Free,
+                "L+" => CbfCone::NonNeg,
+                "L-" => CbfCone::NonPos,
+                "L=" => CbfCone::Zero,
+                "Q" => CbfCone::Quad,
+                "QR" => CbfCone::QuadRot,
+                "EXP" => CbfCone::Exp,
+                _ => return Err(self.unsupported(format!("cone {}", name))),
+            };
+            let valid = match kind {
+                CbfCone::QuadRot => dim >= 2,
+                CbfCone::Exp => dim == 3,
+                _ => true,
+            };
+            if !valid {
+                return Err(self.error(&format!("invalid dimension {} for cone {}", dim, name)));
+            }
+            cones.push((kind, dim));
+        }
+
+        if cones.iter().map(|&(_, dim)| dim).sum::<usize>() != total {
+            return Err(self.error("cone dimensions do not match total"));
+        }
+        Ok((total, cones))
+    }
+}
@@ -0,0 +1,354 @@
+use super::ConicProblem;
+use crate::algebra::*;
+use crate::solver::SupportedConeT;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+
+// ---------------------------------------------------
+// Human readable printing of problem data, and comparison
+// of two problems that are expected to be the same.
+// ---------------------------------------------------
+
+/// A dimension of some part of a problem that differs between
+/// two problems, as `(rows, columns)` with vectors as single columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimensionDiff {
+    pub name: &'static str,
+    pub left: (usize, usize),
+    pub right: (usize, usize),
+}
+
+/// A cone that differs between two problems, with `None` where one
+/// cone list is shorter than the other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConeDiff<T> {
+    pub index: usize,
+    pub left: Option<SupportedConeT<T>>,
+    pub right: Option<SupportedConeT<T>>,
+}
+
+/// An entry of `P`, `A`, `q` or `b` that differs between two problems.
+///
+/// The `index` is `(row, column)`, with column zero for vectors.
+/// Values are `None` for entries outside the sparsity pattern of a
+/// matrix, which compare as zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryDiff<T> {
+    pub name: &'static str,
+    pub index: (usize, usize),
+    pub left: Option<T>,
+    pub right: Option<T>,
+}
+
+impl<T> EntryDiff<T>
+where
+    T: FloatT,
+{
+    /// Absolute difference of the two values.
+    pub fn deviation(&self) -> T {
+        let left = self.left.unwrap_or_else(T::zero);
+        let right = self.right.unwrap_or_else(T::zero);
+        T::abs(left - right)
+    }
+}
+
+/// Differences between two problems, as returned by [`ConicProblem::diff`].
+#[derive(Debug, Clone)]
+pub struct ProblemDiff<T> {
+    /// problem parts of different dimensions, which are
+    /// excluded from the pattern and value comparisons
+    pub dimensions: Vec<DimensionDiff>,
+    pub cones: Vec<ConeDiff<T>>,
+    /// matrix entries in the sparsity pattern of only one problem,
+    /// by matrix and then in column major order
+    pub pattern: Vec<EntryDiff<T>>,
+    /// entries whose values differ by more than the comparison
+    /// tolerance, largest deviation first
+    pub values: Vec<EntryDiff<T>>,
+    pub sense_differs: bool,
+    pub objective_constant: Option<(T, T)>,
+}
+
+impl<T> ProblemDiff<T>
+where
+    T: FloatT,
+{
+    /// True if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_empty()
+            && self.cones.is_empty()
+            && self.pattern.is_empty()
+            && self.values.is_empty()
+            && !self.sense_differs
+            && self.objective_constant.is_none()
+    }
+
+    /// Largest deviation of any compared entry, or zero if no values differ.
+    pub fn max_deviation(&self) -> T {
+        self.values.first().map_or(T::zero(), |e| e.deviation())
+    }
+}
+
+impl<T> fmt::Display for ProblemDiff<T>
+where
+    T: FloatT,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "problems are identical");
+        }
+        if self.sense_differs {
+            writeln!(f, "objective sense differs")?;
+        }
+        if let Some((left, right)) = self.objective_constant {
+            writeln!(f, "objective constant differs: {:e} vs {:e}", left, right)?;
+        }
+        for d in &self.dimensions {
+            writeln!(
+                f,
+                "dimension of {} differs: {} x {} vs {} x {}",
+                d.name, d.left.0, d.left.1, d.right.0, d.right.1
+            )?;
+        }
+        for c in &self.cones {
+            writeln!(
+                f,
+                "cone {} differs: {} vs {}",
+                c.index,
+                _opt_string(c.left.as_ref()),
+                _opt_string(c.right.as_ref())
+            )?;
+        }
+        if !self.pattern.is_empty() {
+            writeln!(f, "{} sparsity pattern differences", self.pattern.len())?;
+        }
+        if !self.values.is_empty() {
+            writeln!(f, "{} value differences", self.values.len())?;
+            writeln!(
+                f,
+                "  {:>6} {:>8} {:>8} {:>14} {:>14} {:>12}",
+                "", "row", "col", "left", "right", "deviation"
+            )?;
+            for e in &self.values {
+                writeln!(
+                    f,
+                    "  {:>6} {:>8} {:>8} {:>14} {:>14} {:>12.4e}",
+                    e.name,
+                    e.index.0,
+                    e.index.1,
+                    _opt_value(e.left),
+                    _opt_value(e.right),
+                    e.deviation()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> ConicProblem<T>
+where
+    T: FloatT,
+{
+    /// Write a summary of the problem, with its dimensions, the rows
+    /// of each cone and the `max_entries` entries of largest magnitude
+    /// in each of `P`, `A`, `q` and `b`.
+    pub fn pretty_print<W: Write>(&self, mut writer: W, max_entries: usize) -> std::io::Result<()> {
+        let (m, n) = (self.A.m, self.A.n);
+        writeln!(writer, "problem ({:?})", self.sense)?;
+        writeln!(writer, "  variables   n = {}", n)?;
+        writeln!(writer, "  constraints m = {}", m)?;
+        writeln!(
+            writer,
+            "  nnz(P) = {}, nnz(A) = {}",
+            self.P.nnz(),
+            self.A.nnz()
+        )?;
+        writeln!(
+            writer,
+            "  objective constant = {:e}",
+            self.objective_constant
+        )?;
+
+        writeln!(writer, "cones ({})", self.cones.len())?;
+        let mut start = 0;
+        for cone in &self.cones {
+            let stop = start + cone.nvars();
+            writeln!(writer, "  rows {:>8} ..{:>8}  {:?}", start, stop, cone)?;
+            start = stop;
+        }
+
+        let vector = |v: &[T]| -> Vec<((usize, usize), T)> {
+            v.iter().enumerate().map(|(i, &x)| ((i, 0), x)).collect()
+        };
+        let parts = [
+            ("P", _matrix_entries(&self.P)),
+            ("A", _matrix_entries(&self.A)),
+            ("q", vector(&self.q)),
+            ("b", vector(&self.b)),
+        ];
+
+        for (name, mut entries) in parts {
+            let count = usize::min(max_entries, entries.len());
+            if count == 0 {
+                continue;
+            }
+            entries.sort_by(|a, b| _cmp_desc(T::abs(a.1), T::abs(b.1)));
+            writeln!(
+                writer,
+                "largest entries of {} ({} of {})",
+                name,
+                count,
+                entries.len()
+            )?;
+            writeln!(writer, "  {:>8} {:>8} {:>14}", "row", "col", "value")?;
+            for ((i, j), v) in &entries[..count] {
+                writeln!(writer, "  {:>8} {:>8} {:>14.6e}", i, j, v)?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Compare this problem with `other`, reporting values that
+    /// differ by more than `tol` in absolute terms or are NaN.
+    pub fn diff(&self, other: &ConicProblem<T>, tol: T) -> ProblemDiff<T> {
+        let mut diff = ProblemDiff {
+            dimensions: Vec::new(),
+            cones: Vec::new(),
+            pattern: Vec::new(),
+            values: Vec::new(),
+            sense_differs: self.sense != other.sense,
+            objective_constant: None,
+        };
+
+        if _exceeds(
+            T::abs(self.objective_constant - other.objective_constant),
+            tol,
+        ) {
+            diff.objective_constant = Some((self.objective_constant, other.objective_constant));
+        }
+
+        let ncones = usize::max(self.cones.len(), other.cones.len());
+        for index in 0..ncones {
+            let (left, right) = (self.cones.get(index), other.cones.get(index));
+            if left != right {
+                diff.cones.push(ConeDiff {
+                    index,
+                    left: left.cloned(),
+                    right: right.cloned(),
+                });
+            }
+        }
+
+        for (name, left, right) in [("P", &self.P, &other.P), ("A", &self.A, &other.A)] {
+            if (left.m, left.n) != (right.m, right.n) {
+                diff.dimensions.push(DimensionDiff {
+                    name,
+                    left: (left.m, left.n),
+                    right: (right.m, right.n),
+                });
+            } else {
+                _diff_matrix(name, left, right, tol, &mut diff);
+            }
+        }
+
+        for (name, left, right) in [("q", &self.q, &other.q), ("b", &self.b, &other.b)] {
+            if left.len() != right.len() {
+                diff.dimensions.push(DimensionDiff {
+                    name,
+                    left: (left.len(), 1),
+                    right: (right.len(), 1),
+                });
+                continue;
+            }
+            for (i, (&l, &r)) in left.iter().zip(right.iter()).enumerate() {
+                if _exceeds(T::abs(l - r), tol) {
+                    diff.values.push(EntryDiff {
+                        name,
+                        index: (i, 0),
+                        left: Some(l),
+                        right: Some(r),
+                    });
+                }
+            }
+        }
+
+        diff.values
+            .sort_by(|a, b| _cmp_desc(a.deviation(), b.deviation()));
+        diff
+    }
+}
+
+// ---------------------------------------------------
+// internal helpers
+// ---------------------------------------------------
+
+fn _matrix_entries<T: FloatT>(M: &CscMatrix<T>) -> Vec<((usize, usize), T)> {
+    let mut entries = Vec::with_capacity(M.nnz());
+    for j in 0..M.n {
+        for k in M.colptr[j]..M.colptr[j + 1] {
+            entries.push(((M.rowval[k], j), M.nzval[k]));
+        }
+    }
+    entries
+}
+
+// compare matrices of equal dimension entry by entry.   Entries are
+// merged by coordinate so that unsorted or duplicate entries are
+// handled consistently, with duplicates summed
+fn _diff_matrix<T: FloatT>(
+    name: &'static str,
+    left: &CscMatrix<T>,
+    right: &CscMatrix<T>,
+    tol: T,
+    diff: &mut ProblemDiff<T>,
+) {
+    let mut merged: BTreeMap<(usize, usize), (Option<T>, Option<T>)> = BTreeMap::new();
+    for ((i, j), v) in _matrix_entries(left) {
+        let entry = &mut merged.entry((j, i)).or_default().0;
+        *entry = Some(entry.unwrap_or_else(T::zero) + v);
+    }
+    for ((i, j), v) in _matrix_entries(right) {
+        let entry = &mut merged.entry((j, i)).or_default().1;
+        *entry = Some(entry.unwrap_or_else(T::zero) + v);
+    }
+
+    for ((j, i), (l, r)) in merged {
+        let entry = EntryDiff {
+            name,
+            index: (i, j),
+            left: l,
+            right: r,
+        };
+        if l.is_none() || r.is_none() {
+            diff.pattern.push(entry.clone());
+        }
+        if _exceeds(entry.deviation(), tol) {
+            diff.values.push(entry);
+        }
+    }
+}
+
+fn _exceeds<T: FloatT>(deviation: T, tol: T) -> bool {
+    deviation > tol || deviation.is_nan()
+}
+
+// descending order, with NaNs first so that they are always reported
+fn _cmp_desc<T: FloatT>(a: T, b: T) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => b.partial_cmp(&a).unwrap(),
+    }
+}
+
+fn _opt_string<C: fmt::Debug>(c: Option<&C>) -> String {
+    c.map_or("(none)".to_string(), |c| format!("{:?}", c))
+}
+
+fn _opt_value<T: FloatT>(v: Option<T>) -> String {
+    v.map_or("(none)".to_string(), |v| format!("{:.6e}", v))
+}
@@ -0,0 +1,255 @@
+//! Readers and writers for conic problems stored in standard benchmark file formats.
+//!
+//! Problems are returned as a [`ConicProblem`], holding data in the
+//! standard form used by the [`DefaultSolver`](crate::solver::DefaultSolver),
+//! or as a [`QpProblem`] for quadratic programs with two-sided constraints.
+
+#![allow(non_snake_case)]
+
+use crate::algebra::*;
+use crate::solver::{
+    ClarabelError, DefaultSettings, DefaultSolver, OptimizationSense, SupportedConeT,
+};
+
+mod cbf;
+mod diff;
+mod mtx;
+mod npy;
+mod osqp_compat;
+mod qps;
+mod scs;
+pub use cbf::*;
+pub use diff::*;
+pub use mtx::*;
+pub use npy::*;
+pub use osqp_compat::*;
+pub use qps::*;
+pub use scs::*;
+
+// benchmark harness
+#[cfg(feature = "bench-harness")]
+pub mod bench;
+
+// SDPA problems have PSD constraints
+#[cfg(feature = "sdp")]
+mod sdpa;
+#[cfg(feature = "sdp")]
+pub use sdpa::*;
+
+/// A conic optimization problem in the solver's standard form
+///
+/// ```text
+/// min  ½xᵀPx + qᵀx + c
+/// s.t. Ax + s = b, s ∈ K
+/// ```
+///
+/// or the corresponding maximization of `qᵀx - ½xᵀPx + c` when
+/// `sense` is [`Maximize`](OptimizationSense::Maximize).   The
+/// objective constant `c` is not passed to the solver.
+
+#[derive(Debug, Clone)]
+pub struct ConicProblem<T: FloatT = f64> {
+    pub P: CscMatrix<T>,
+    pub q: Vec<T>,
+    pub A: CscMatrix<T>,
+    pub b: Vec<T>,
+    pub cones: Vec<SupportedConeT<T>>,
+    pub sense: OptimizationSense,
+    pub objective_constant: T,
+}
+
+impl<T> ConicProblem<T>
+where
+    T: FloatT,
+{
+    /// Create a solver for this problem.   The objective sense
+    /// in `settings` is overwritten with that of the problem.
+    pub fn solver(
+        &self,
+        mut settings: DefaultSettings<T>,
+    ) -> Result<DefaultSolver<T>, ClarabelError> {
+        settings.sense = self.sense;
+        DefaultSolver::new(&self.P, &self.q, &self.A, &self.b, &self.cones, settings)
+    }
+
+    /// Return an equivalent problem with a linear objective, given a
+    /// factor `F` of the quadratic term with `P = FᵀF`, e.g. a Cholesky
+    /// factor.   A new last variable `t ≥ ½‖Fx‖²` replaces the quadratic
+    /// term in the objective, with the bound imposed as a second order
+    /// cone constraint `((t+1)/√2, (t-1)/√2, Fx) ∈ K`.
+    ///
+    /// This allows problems with quadratic objectives to be written to
+    /// formats such as CBF that support only linear objectives.
+    pub fn lift_quadratic_objective(&self, F: &CscMatrix<T>) -> ConicProblem<T> {
+        let (m, n) = (self.A.m, self.A.n);
+        assert_eq!(F.n, n);
+
+        let r = T::FRAC_1_SQRT_2();
+        let nnz = self.A.nnz() + F.nnz() + 2;
+        let mut I = Vec::with_capacity(nnz);
+        let mut J = Vec::with_capacity(nnz);
+        let mut V = Vec::with_capacity(nnz);
+
+        for (M, rowoffset, sign) in [(&self.A, 0, T::one()), (F, m + 2, -T::one())] {
+            for j in 0..M.n {
+                for k in M.colptr[j]..M.colptr[j + 1] {
+                    I.push(M.rowval[k] + rowoffset);
+                    J.push(j);
+                    V.push(sign * M.nzval[k]);
+                }
+            }
+        }
+        I.extend([m, m + 1]);
+        J.extend([n, n]);
+        V.extend([-r, -r]);
+
+        let A = CscMatrix::new_from_triplets(m + 2 + F.m, n + 1, I, J, V);
+
+        let mut b = self.b.clone();
+        b.extend([r, -r]);
+        b.resize(A.m, T::zero());
+
+        // the epigraph variable enters with the sign of
+        // the quadratic term in the original objective
+        let mut q = self.q.clone();
+        q.push(match self.sense {
+            OptimizationSense::Minimize => T::one(),
+            OptimizationSense::Maximize => -T::one(),
+        });
+
+        let mut cones = self.cones.clone();
+        cones.push(SupportedConeT::SecondOrderConeT(F.m + 2));
+
+        ConicProblem {
+            P: CscMatrix::zeros((n + 1, n + 1)),
+            q,
+            A,
+            b,
+            cones,
+            sense: self.sense,
+            objective_constant: self.objective_constant,
+        }
+    }
+}
+
+/// A quadratic program with two-sided linear constraints
+///
+/// ```text
+/// min  ½xᵀPx + qᵀx + c
+/// s.t. l ≤ Ax ≤ u
+/// ```
+///
+/// with `P` upper triangular, or the corresponding maximization of
+/// `qᵀx - ½xᵀPx + c` when `sense` is [`Maximize`](OptimizationSense::Maximize).
+/// Entries of `l` and `u` may be infinite, and equality constraints
+/// have `l = u`.
+
+#[derive(Debug, Clone)]
+pub struct QpProblem<T: FloatT = f64> {
+    pub P: CscMatrix<T>,
+    pub q: Vec<T>,
+    pub A: CscMatrix<T>,
+    pub l: Vec<T>,
+    pub u: Vec<T>,
+    pub sense: OptimizationSense,
+    pub objective_constant: T,
+}
+
+impl<T> QpProblem<T>
+where
+    T: FloatT,
+{
+    /// Convert to the solver's standard form.   Rows with `l = u`
+    /// become zero cone constraints, and each finite side of
+    /// the remaining rows a nonnegative cone constraint.   Rows
+    /// with both sides infinite are dropped.
+    pub fn to_conic(&self) -> ConicProblem<T> {
+        let n = self.A.n;
+        let (rowmap, b, neq) = self._conic_rows();
+
+        let (mut I, mut J, mut V) = (Vec::new(), Vec::new(), Vec::new());
+        for j in 0..n {
+            for k in self.A.colptr[j]..self.A.colptr[j + 1] {
+                for &(row, sign) in rowmap[self.A.rowval[k]].iter() {
+                    I.push(row);
+                    J.push(j);
+                    V.push(sign * self.A.nzval[k]);
+                }
+            }
+        }
+        let A = CscMatrix::new_from_triplets(b.len(), n, I, J, V);
+
+        let mut cones = Vec::new();
+        if neq > 0 {
+            cones.push(SupportedConeT::ZeroConeT(neq));
+        }
+        if b.len() > neq {
+            cones.push(SupportedConeT::NonnegativeConeT(b.len() - neq));
+        }
+
+        ConicProblem {
+            P: self.P.clone(),
+            q: self.q.clone(),
+            A,
+            b,
+            cones,
+            sense: self.sense,
+            objective_constant: self.objective_constant,
+        }
+    }
+
+    /// Multipliers `y` of the constraints `l ≤ Ax ≤ u`, given the dual
+    /// variables `z` of the problem returned by [`to_conic`](Self::to_conic).
+    ///
+    /// For each row, `y` is the difference of the multipliers of the upper
+    /// and lower bounds, so that `Px + q + Aᵀy = 0` for a minimization at
+    /// optimality, `y ≥ 0` at an active upper bound and `y ≤ 0` at an
+    /// active lower bound.   Rows with both sides infinite have `y = 0`.
+    pub fn constraint_duals(&self, z: &[T]) -> Vec<T> {
+        let (rowmap, _, _) = self._conic_rows();
+        rowmap
+            .iter()
+            .map(|rows| {
+                rows.iter()
+                    .fold(T::zero(), |y, &(row, sign)| y + sign * z[row])
+            })
+            .collect()
+    }
+
+    // rows of the standard form for each row of A, as (row, sign)
+    // pairs with equalities first, along with the standard form
+    // right hand side and the number of equalities
+    #[allow(clippy::type_complexity)]
+    fn _conic_rows(&self) -> (Vec<Vec<(usize, T)>>, Vec<T>, usize) {
+        let m = self.A.m;
+        let mut rowmap: Vec<Vec<(usize, T)>> = vec![Vec::new(); m];
+        let mut b = Vec::with_capacity(2 * m);
+
+        for (i, (&li, &ui)) in self.l.iter().zip(self.u.iter()).enumerate() {
+            if li == ui {
+                rowmap[i].push((b.len(), T::one()));
+                b.push(ui);
+            }
+        }
+        let neq = b.len();
+        for (i, (&li, &ui)) in self.l.iter().zip(self.u.iter()).enumerate() {
+            if li != ui && ui.is_finite() {
+                rowmap[i].push((b.len(), T::one()));
+                b.push(ui);
+            }
+        }
+        for (i, (&li, &ui)) in self.l.iter().zip(self.u.iter()).enumerate() {
+            if li != ui && li.is_finite() {
+                rowmap[i].push((b.len(), -T::one()));
+                b.push(-li);
+            }
+        }
+        (rowmap, b, neq)
+    }
+
+    /// Create a solver for this problem.   The objective sense
+    /// in `settings` is overwritten with that of the problem.
+    pub fn solver(&self, settings: DefaultSettings<T>) -> Result<DefaultSolver<T>, ClarabelError> {
+        self.to_conic().solver(settings)
+    }
+}
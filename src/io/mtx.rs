@@ -0,0 +1,97 @@
+use crate::algebra::*;
+use crate::solver::KktSnapshot;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+// ---------------------------------------------------
+// Writer for the Matrix Market exchange format, in the
+// coordinate format for sparse matrices and the array
+// format for dense vectors.   Indices are 1-based.
+// ---------------------------------------------------
+
+/// Write a sparse matrix in Matrix Market coordinate format.
+///
+/// If `symmetric` is set then `A` must be upper triangular, and is
+/// written as a symmetric matrix, i.e. as its (transposed) lower triangle.
+pub fn write_mtx<T, W>(A: &CscMatrix<T>, symmetric: bool, mut writer: W) -> std::io::Result<()>
+where
+    T: FloatT,
+    W: Write,
+{
+    let shape = if symmetric { "symmetric" } else { "general" };
+    writeln!(writer, "%%MatrixMarket matrix coordinate real {}", shape)?;
+    writeln!(writer, "{} {} {}", A.m, A.n, A.nnz())?;
+
+    for j in 0..A.n {
+        for k in A.colptr[j]..A.colptr[j + 1] {
+            let i = A.rowval[k];
+            if symmetric {
+                assert!(i <= j, "symmetric matrix must be upper triangular");
+                writeln!(writer, "{} {} {}", j + 1, i + 1, A.nzval[k])?;
+            } else {
+                writeln!(writer, "{} {} {}", i + 1, j + 1, A.nzval[k])?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+/// Write a vector as a single column in Matrix Market array format.
+pub fn write_mtx_vector<T, W>(v: &[T], mut writer: W) -> std::io::Result<()>
+where
+    T: FloatT,
+    W: Write,
+{
+    writeln!(writer, "%%MatrixMarket matrix array real general")?;
+    writeln!(writer, "{} 1", v.len())?;
+    for x in v {
+        writeln!(writer, "{}", x)?;
+    }
+    writer.flush()
+}
+
+impl<T> KktSnapshot<T>
+where
+    T: FloatT,
+{
+    /// Write the snapshot to the directory `dir` in Matrix Market format.
+    ///
+    /// The KKT matrix is written as the symmetric matrix `K.mtx`, as given
+    /// by [`matrix`](KktSnapshot::matrix), and each nonempty RHS and
+    /// solution vector to a file of the same name, e.g. `rhs_predictor.mtx`,
+    /// permuted consistently with the matrix.
+    pub fn write_mtx_dir(
+        &self,
+        dir: impl AsRef<Path>,
+        regularized: bool,
+        permuted: bool,
+    ) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let create = |name: &str| -> std::io::Result<BufWriter<File>> {
+            Ok(BufWriter::new(File::create(dir.join(name))?))
+        };
+
+        let K = self.matrix(regularized, permuted);
+        write_mtx(&K, true, create("K.mtx")?)?;
+
+        let vectors = [
+            ("rhs_constant", &self.rhs_constant),
+            ("lhs_constant", &self.lhs_constant),
+            ("rhs_predictor", &self.rhs_predictor),
+            ("lhs_predictor", &self.lhs_predictor),
+            ("rhs_corrector", &self.rhs_corrector),
+            ("lhs_corrector", &self.lhs_corrector),
+        ];
+        for (name, v) in vectors {
+            if !v.is_empty() {
+                let v = self.vector(v, permuted);
+                write_mtx_vector(&v, create(&format!("{}.mtx", name))?)?;
+            }
+        }
+        Ok(())
+    }
+}
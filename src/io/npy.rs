@@ -0,0 +1,432 @@
+use super::{ConicProblem, ScsConeSpec, ScsError};
+use crate::algebra::*;
+use crate::solver::OptimizationSense;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use thiserror::Error;
+
+// ---------------------------------------------------
+// Readers for NumPy .npy arrays and .npz archives.
+//
+// Only the simple cases produced by np.save / np.savez for
+// vector data are supported, i.e. little-endian f64, f32, i64
+// and i32 arrays of at most one dimension in C order, stored
+// uncompressed in the archive.   Anything else is rejected
+// rather than guessed at.
+// ---------------------------------------------------
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Error type returned when reading NumPy `.npy` and `.npz` files.
+#[derive(Error, Debug)]
+pub enum NpyError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a .npy file")]
+    BadMagic,
+    #[error("unsupported .npy format version {0}.{1}")]
+    UnsupportedVersion(u8, u8),
+    #[error("malformed .npy header: {0}")]
+    BadHeader(&'static str),
+    #[error("unsupported dtype \"{0}\", expected one of <f8, <f4, <i8, <i4")]
+    UnsupportedDtype(String),
+    #[error("Fortran ordered arrays are not supported")]
+    FortranOrder,
+    #[error("only arrays of at most one dimension are supported, found shape {0:?}")]
+    UnsupportedShape(Vec<usize>),
+    #[error("array data is truncated: expected {expected} bytes, found {found}")]
+    Truncated { expected: usize, found: usize },
+    #[error("malformed .npz archive: {0}")]
+    BadArchive(&'static str),
+    #[error("compressed .npz member \"{0}\" is not supported, save with np.savez")]
+    CompressedMember(String),
+    #[error("missing .npz member \"{0}\"")]
+    MissingMember(String),
+    #[error("member \"{0}\" must contain nonnegative integers")]
+    BadIndices(String),
+    #[error("invalid matrix {0}: {1}")]
+    BadMatrix(&'static str, SparseFormatError),
+    #[error("incompatible dimensions: {0}")]
+    IncompatibleDimension(&'static str),
+    #[error("P must be upper triangular")]
+    NotUpperTriangular,
+    #[error("invalid cones: {0}")]
+    Cones(#[from] ScsError),
+}
+
+/// A one-dimensional array read from a `.npy` file, in its stored type.
+///
+/// Zero-dimensional (scalar) arrays are read as arrays of length one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NpyArray {
+    F64(Vec<f64>),
+    F32(Vec<f32>),
+    I64(Vec<i64>),
+    I32(Vec<i32>),
+}
+
+impl NpyArray {
+    /// Number of elements in the array.
+    pub fn len(&self) -> usize {
+        match self {
+            NpyArray::F64(v) => v.len(),
+            NpyArray::F32(v) => v.len(),
+            NpyArray::I64(v) => v.len(),
+            NpyArray::I32(v) => v.len(),
+        }
+    }
+
+    /// True if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The array values converted to floating point.
+    pub fn to_float<T: FloatT>(&self) -> Vec<T> {
+        match self {
+            NpyArray::F64(v) => v.iter().map(|&x| x.as_T()).collect(),
+            NpyArray::F32(v) => v.iter().map(|&x| (x as f64).as_T()).collect(),
+            NpyArray::I64(v) => v.iter().map(|&x| (x as f64).as_T()).collect(),
+            NpyArray::I32(v) => v.iter().map(|&x| (x as f64).as_T()).collect(),
+        }
+    }
+
+    /// The array values as indices, or `None` if the array has a
+    /// floating point type or contains negative values.
+    pub fn to_indices(&self) -> Option<Vec<usize>> {
+        match self {
+            NpyArray::I64(v) => v.iter().map(|&x| usize::try_from(x).ok()).collect(),
+            NpyArray::I32(v) => v.iter().map(|&x| usize::try_from(x).ok()).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Read a one-dimensional array from a file in NumPy `.npy` format.
+pub fn read_npy<R: Read>(mut reader: R) -> Result<NpyArray, NpyError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    _parse_npy(&bytes)
+}
+
+/// Read all arrays from an archive in NumPy `.npz` format, keyed by
+/// member name without the `.npy` extension.
+///
+/// Archives written by `np.savez` are supported.   Archives written by
+/// `np.savez_compressed` produce a [`NpyError::CompressedMember`] error.
+pub fn read_npz<R: Read>(mut reader: R) -> Result<BTreeMap<String, NpyArray>, NpyError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let mut arrays = BTreeMap::new();
+    for (name, data) in _zip_members(&bytes)? {
+        let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+        arrays.insert(name, _parse_npy(data)?);
+    }
+    Ok(arrays)
+}
+
+/// Read a problem from a `.npz` archive with members
+///
+/// - `P_data`, `P_indices`, `P_indptr` : upper triangular `P` in CSC
+///   form, as in a scipy `csc_matrix`.   Optional, with `P = 0` if omitted.
+/// - `A_data`, `A_indices`, `A_indptr` : `A` in CSC form
+/// - `q`, `b` : linear cost and constraint vectors
+/// - `cone_z`, `cone_l`, `cone_q`, `cone_ep`, `cone_p` : cones, with the
+///   meanings of the fields of [`ScsConeSpec`].   Each is optional.
+///
+/// The cones are applied to the rows of `A` in the order listed.
+pub fn read_problem_npz<T: FloatT>(path: impl AsRef<Path>) -> Result<ConicProblem<T>, NpyError> {
+    let file = File::open(path)?;
+    let arrays = read_npz(BufReader::new(file))?;
+
+    let member = |name: &str| -> Result<&NpyArray, NpyError> {
+        arrays
+            .get(name)
+            .ok_or_else(|| NpyError::MissingMember(name.to_string()))
+    };
+    let indices = |name: &str| -> Result<Vec<usize>, NpyError> {
+        member(name)?
+            .to_indices()
+            .ok_or_else(|| NpyError::BadIndices(name.to_string()))
+    };
+    let count = |name: &str| -> Result<usize, NpyError> {
+        match arrays.get(name) {
+            None => Ok(0),
+            Some(_) => match indices(name)?[..] {
+                [k] => Ok(k),
+                _ => Err(NpyError::BadIndices(name.to_string())),
+            },
+        }
+    };
+    let matrix = |name: &'static str, m: usize, n: usize| -> Result<CscMatrix<T>, NpyError> {
+        let colptr = indices(&format!("{}_indptr", name))?;
+        let rowval = indices(&format!("{}_indices", name))?;
+        let nzval = member(&format!("{}_data", name))?.to_float();
+        if colptr.len() != n + 1 || rowval.len() != nzval.len() {
+            return Err(NpyError::IncompatibleDimension(name));
+        }
+        let M = CscMatrix::new(m, n, colptr, rowval, nzval);
+        M.check_format().map_err(|e| NpyError::BadMatrix(name, e))?;
+        Ok(M)
+    };
+
+    let q: Vec<T> = member("q")?.to_float();
+    let b: Vec<T> = member("b")?.to_float();
+    let n = q.len();
+
+    let P = if arrays.contains_key("P_data") {
+        matrix("P", n, n)?
+    } else {
+        CscMatrix::zeros((n, n))
+    };
+    if !P.is_triu() {
+        return Err(NpyError::NotUpperTriangular);
+    }
+    let A = matrix("A", b.len(), n)?;
+
+    let spec = ScsConeSpec::<T> {
+        z: count("cone_z")?,
+        l: count("cone_l")?,
+        q: match arrays.get("cone_q") {
+            Some(_) => indices("cone_q")?,
+            None => Vec::new(),
+        },
+        ep: count("cone_ep")?,
+        p: arrays.get("cone_p").map_or(Vec::new(), |p| p.to_float()),
+        ..ScsConeSpec::default()
+    };
+    let cones = spec.to_cones()?;
+    if spec.dim() != b.len() {
+        return Err(NpyError::IncompatibleDimension("cones and b"));
+    }
+
+    Ok(ConicProblem {
+        P,
+        q,
+        A,
+        b,
+        cones,
+        sense: OptimizationSense::Minimize,
+        objective_constant: T::zero(),
+    })
+}
+
+// ---------------------------------------------------
+// internal helpers
+// ---------------------------------------------------
+
+fn _parse_npy(bytes: &[u8]) -> Result<NpyArray, NpyError> {
+    if bytes.len() < 8 || &bytes[0..6] != NPY_MAGIC {
+        return Err(NpyError::BadMagic);
+    }
+
+    // version 1.0 has a 2 byte header length, versions 2.0 and
+    // 3.0 a 4 byte length.   3.0 only allows utf8 field names
+    let (major, minor) = (bytes[6], bytes[7]);
+    let (lensize, start) = match major {
+        1 => (2, 10),
+        2 | 3 => (4, 12),
+        _ => return Err(NpyError::UnsupportedVersion(major, minor)),
+    };
+    if bytes.len() < start {
+        return Err(NpyError::BadHeader("header is truncated"));
+    }
+    let headerlen = _le_uint(&bytes[8..8 + lensize]);
+    let end = start + headerlen;
+    if bytes.len() < end {
+        return Err(NpyError::BadHeader("header is truncated"));
+    }
+    let header = std::str::from_utf8(&bytes[start..end])
+        .map_err(|_| NpyError::BadHeader("header is not valid text"))?;
+
+    let descr = _header_value(header, "descr")?;
+    let descr = descr
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .ok_or(NpyError::BadHeader("descr is not a string"))?;
+
+    match _header_value(header, "fortran_order")? {
+        "False" => {}
+        "True" => return Err(NpyError::FortranOrder),
+        _ => return Err(NpyError::BadHeader("fortran_order is not a boolean")),
+    }
+
+    let shape = _parse_shape(_header_value(header, "shape")?)?;
+    let len = match shape[..] {
+        [] => 1,
+        [len] => len,
+        _ => return Err(NpyError::UnsupportedShape(shape)),
+    };
+
+    let itemsize = match descr {
+        "<f8" | "<i8" => 8,
+        "<f4" | "<i4" => 4,
+        _ => return Err(NpyError::UnsupportedDtype(descr.to_string())),
+    };
+    let data = &bytes[end..];
+    let expected = len
+        .checked_mul(itemsize)
+        .ok_or(NpyError::BadHeader("array size overflows usize"))?;
+    if data.len() < expected {
+        return Err(NpyError::Truncated {
+            expected,
+            found: data.len(),
+        });
+    }
+    let chunks = data[..expected].chunks_exact(itemsize);
+
+    let array = match descr {
+        "<f8" => NpyArray::F64(
+            chunks
+                .map(|c| f64::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+        "<f4" => NpyArray::F32(
+            chunks
+                .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+        "<i8" => NpyArray::I64(
+            chunks
+                .map(|c| i64::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+        _ => NpyArray::I32(
+            chunks
+                .map(|c| i32::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+    };
+    Ok(array)
+}
+
+// the text of the value of `key` in the python dict literal of an
+// npy header, e.g. {'descr': '<f8', 'fortran_order': False, 'shape': (3,), }
+fn _header_value<'a>(header: &'a str, key: &'static str) -> Result<&'a str, NpyError> {
+    let pattern = format!("'{}':", key);
+    let start = header
+        .find(&pattern)
+        .ok_or(NpyError::BadHeader("missing key"))?
+        + pattern.len();
+    let rest = header[start..].trim_start();
+
+    // the shape tuple contains commas, so ends at the closing paren
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find([',', '}'])
+    };
+    let end = end.ok_or(NpyError::BadHeader("unterminated value"))?;
+    Ok(rest[..end].trim())
+}
+
+fn _parse_shape(shape: &str) -> Result<Vec<usize>, NpyError> {
+    let inner = shape
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or(NpyError::BadHeader("shape is not a tuple"))?;
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .map_err(|_| NpyError::BadHeader("shape is not a tuple of integers"))
+        })
+        .collect()
+}
+
+fn _le_uint(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |acc, &byte| (acc << 8) | byte as usize)
+}
+
+// Names and data of the members of a zip archive, located via the
+// central directory.   Only stored (uncompressed) members are
+// supported, and their CRCs are checked.
+fn _zip_members(bytes: &[u8]) -> Result<Vec<(String, &[u8])>, NpyError> {
+    const EOCD_SIGNATURE: u32 = 0x06054b50;
+    const CDIR_SIGNATURE: u32 = 0x02014b50;
+    const LOCAL_SIGNATURE: u32 = 0x04034b50;
+    const EOCD_LEN: usize = 22;
+    const CDIR_LEN: usize = 46;
+    const LOCAL_LEN: usize = 30;
+
+    let field = |at: usize, len: usize| -> Result<usize, NpyError> {
+        bytes
+            .get(at..at + len)
+            .map(_le_uint)
+            .ok_or(NpyError::BadArchive("unexpected end of archive"))
+    };
+
+    // the end of central directory record is followed only by
+    // a comment of at most 64k bytes, so search backwards for it
+    let lowest = bytes.len().saturating_sub(EOCD_LEN + 0xFFFF);
+    let eocd = (lowest..=bytes.len().saturating_sub(EOCD_LEN))
+        .rev()
+        .find(|&i| field(i, 4).ok() == Some(EOCD_SIGNATURE as usize))
+        .ok_or(NpyError::BadArchive("end of central directory not found"))?;
+
+    let count = field(eocd + 10, 2)?;
+    let mut entry = field(eocd + 16, 4)?;
+    if count == 0xFFFF || entry == 0xFFFFFFFF {
+        return Err(NpyError::BadArchive("zip64 archives are not supported"));
+    }
+
+    let mut members = Vec::with_capacity(count);
+    for _ in 0..count {
+        if field(entry, 4)? != CDIR_SIGNATURE as usize {
+            return Err(NpyError::BadArchive("bad central directory entry"));
+        }
+        let method = field(entry + 10, 2)?;
+        let crc = field(entry + 16, 4)? as u32;
+        let size = field(entry + 20, 4)?;
+        let namelen = field(entry + 28, 2)?;
+        let extralen = field(entry + 30, 2)?;
+        let commentlen = field(entry + 32, 2)?;
+        let offset = field(entry + 42, 4)?;
+        let name = bytes
+            .get(entry + CDIR_LEN..entry + CDIR_LEN + namelen)
+            .ok_or(NpyError::BadArchive("unexpected end of archive"))?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        entry += CDIR_LEN + namelen + extralen + commentlen;
+
+        if method != 0 {
+            return Err(NpyError::CompressedMember(name));
+        }
+        if size == 0xFFFFFFFF || offset == 0xFFFFFFFF {
+            return Err(NpyError::BadArchive("zip64 archives are not supported"));
+        }
+
+        // the local header has its own name and extra field lengths
+        if field(offset, 4)? != LOCAL_SIGNATURE as usize {
+            return Err(NpyError::BadArchive("bad local file header"));
+        }
+        let start = offset + LOCAL_LEN + field(offset + 26, 2)? + field(offset + 28, 2)?;
+        let data = bytes
+            .get(start..start + size)
+            .ok_or(NpyError::BadArchive("unexpected end of archive"))?;
+        if _crc32(data) != crc {
+            return Err(NpyError::BadArchive("CRC mismatch"));
+        }
+        members.push((name, data));
+    }
+    Ok(members)
+}
+
+fn _crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB88320 & mask);
+        }
+    }
+    !crc
+}
@@ -0,0 +1,184 @@
+use super::QpProblem;
+use crate::algebra::*;
+use crate::solver::{DefaultSettings, DefaultSolution, OptimizationSense};
+use thiserror::Error;
+
+// ---------------------------------------------------
+// Compatibility layer for problems and settings in the form
+// used by the OSQP solver, i.e.
+//
+//   min   ½xᵀPx + qᵀx
+//   s.t.  l ≤ Ax ≤ u
+//
+// with P upper triangular.   Problems are converted to a
+// QpProblem, and the duals of the solution can be mapped
+// back to multipliers of the two-sided constraints.
+// ---------------------------------------------------
+
+/// Error type returned by [`from_osqp_data`].
+#[derive(Error, Debug)]
+pub enum OsqpDataError {
+    #[error("incompatible dimensions: {0}")]
+    IncompatibleDimension(&'static str),
+    #[error("P must be upper triangular")]
+    NotUpperTriangular,
+    #[error("lower bound exceeds upper bound in row {0}")]
+    InfeasibleBounds(usize),
+}
+
+// bounds at least this large in magnitude are treated as infinite,
+// as for OSQP_INFTY
+const OSQP_INFINITY: f64 = 1e30;
+
+/// Create a [`QpProblem`] from data in OSQP form.
+///
+/// `P` must be upper triangular, and bounds of magnitude `1e30` or
+/// larger are treated as infinite.   The resulting problem can be
+/// solved using [`QpProblem::solver`], e.g. with settings from
+/// [`OsqpSettings::to_default_settings`], and the OSQP form of the
+/// solution recovered using [`osqp_solution`].
+pub fn from_osqp_data<T: FloatT>(
+    P: &CscMatrix<T>,
+    q: &[T],
+    A: &CscMatrix<T>,
+    l: &[T],
+    u: &[T],
+) -> Result<QpProblem<T>, OsqpDataError> {
+    let n = q.len();
+    if P.m != n || P.n != n {
+        return Err(OsqpDataError::IncompatibleDimension("P and q"));
+    }
+    if A.n != n {
+        return Err(OsqpDataError::IncompatibleDimension("A and q"));
+    }
+    if l.len() != A.m || u.len() != A.m {
+        return Err(OsqpDataError::IncompatibleDimension("A and bounds"));
+    }
+    if !P.is_triu() {
+        return Err(OsqpDataError::NotUpperTriangular);
+    }
+    if let Some(i) = l.iter().zip(u).position(|(li, ui)| li > ui) {
+        return Err(OsqpDataError::InfeasibleBounds(i));
+    }
+
+    let inf: T = OSQP_INFINITY.as_T();
+    let to_infinity = |v: &T| {
+        if *v >= inf {
+            T::infinity()
+        } else if *v <= -inf {
+            -T::infinity()
+        } else {
+            *v
+        }
+    };
+
+    Ok(QpProblem {
+        P: P.clone(),
+        q: q.to_vec(),
+        A: A.clone(),
+        l: l.iter().map(to_infinity).collect(),
+        u: u.iter().map(to_infinity).collect(),
+        sense: OptimizationSense::Minimize,
+        objective_constant: T::zero(),
+    })
+}
+
+/// A subset of the OSQP settings, with OSQP defaults.
+///
+/// Convert to solver settings with [`to_default_settings`](Self::to_default_settings).
+#[derive(Debug, Clone)]
+pub struct OsqpSettings<T: FloatT = f64> {
+    /// absolute convergence tolerance
+    pub eps_abs: T,
+    /// relative convergence tolerance
+    pub eps_rel: T,
+    /// maximum number of iterations
+    pub max_iter: u32,
+    /// number of scaling iterations, with 0 disabling scaling
+    pub scaling: u32,
+    /// solution polishing
+    pub polish: bool,
+}
+
+impl<T> Default for OsqpSettings<T>
+where
+    T: FloatT,
+{
+    fn default() -> Self {
+        Self {
+            eps_abs: (1e-3).as_T(),
+            eps_rel: (1e-3).as_T(),
+            max_iter: 4000,
+            scaling: 10,
+            polish: false,
+        }
+    }
+}
+
+impl<T> OsqpSettings<T>
+where
+    T: FloatT,
+{
+    /// Translate to [`DefaultSettings`], starting from the solver defaults.
+    ///
+    /// The settings do not have the same meaning in both solvers:
+    ///
+    /// - `eps_abs` and `eps_rel` become the absolute and relative duality
+    ///   gap tolerances, and `eps_rel` the feasibility tolerance, which is
+    ///   always relative here.  The OSQP defaults are far looser than the
+    ///   defaults of this solver, and little time is saved by using them.
+    /// - `max_iter` limits interior point iterations, each of which is
+    ///   far more expensive than an OSQP iteration.  Problems rarely need
+    ///   more than 50, so the OSQP limit is effectively no limit.
+    /// - `scaling` is the maximum number of equilibration iterations,
+    ///   with `0` disabling equilibration.
+    /// - `polish` is ignored, since interior point solutions are already
+    ///   accurate.
+    pub fn to_default_settings(&self) -> DefaultSettings<T> {
+        let defaults = DefaultSettings::<T>::default();
+        DefaultSettings {
+            tol_gap_abs: self.eps_abs,
+            tol_gap_rel: self.eps_rel,
+            tol_feas: self.eps_rel,
+            max_iter: self.max_iter,
+            equilibrate_enable: self.scaling > 0,
+            equilibrate_max_iter: if self.scaling > 0 {
+                self.scaling
+            } else {
+                defaults.equilibrate_max_iter
+            },
+            ..defaults
+        }
+    }
+}
+
+/// Primal and dual solution of a problem in OSQP form.
+#[derive(Debug, Clone)]
+pub struct OsqpSolution<T: FloatT = f64> {
+    /// primal solution
+    pub x: Vec<T>,
+    /// multipliers of the constraints `l ≤ Ax ≤ u`
+    pub y: Vec<T>,
+}
+
+/// Recover the solution of a problem created by [`from_osqp_data`]
+/// from the solution of the solver returned by [`QpProblem::solver`].
+///
+/// With `osqp_signs` set, the multipliers `y` follow the OSQP sign
+/// convention, i.e. `Px + q + Aᵀy = 0`, with `y ≥ 0` at active upper
+/// bounds and `y ≤ 0` at active lower bounds.  Otherwise they have the
+/// opposite sign, as for the Lagrangian `½xᵀPx + qᵀx - yᵀAx`.
+pub fn osqp_solution<T: FloatT>(
+    problem: &QpProblem<T>,
+    solution: &DefaultSolution<T>,
+    osqp_signs: bool,
+) -> OsqpSolution<T> {
+    let mut y = problem.constraint_duals(&solution.z);
+    if !osqp_signs {
+        y.negate();
+    }
+    OsqpSolution {
+        x: solution.x.clone(),
+        y,
+    }
+}
@@ -0,0 +1,406 @@
+use super::QpProblem;
+use crate::algebra::*;
+use crate::solver::OptimizationSense;
+use std::collections::HashMap;
+use std::io::{BufRead, Lines};
+use thiserror::Error;
+
+// ---------------------------------------------------
+// Reader for the QPS format, i.e. the MPS format for linear programs
+// extended with a QUADOBJ or QMATRIX section, as used by the
+// Maros-Mészáros QP test set.   Problems have the form
+//
+//   min   ½xᵀQx + cᵀx + c₀
+//   s.t.  row bounds on Ax given by the ROWS, RHS and RANGES sections
+//         lb ≤ x ≤ ub as given by the BOUNDS section
+//
+// and are converted to the form l ≤ Ax ≤ u, with the variable bounds
+// appended as extra rows of A.   Only free format files are supported,
+// i.e. names may not contain spaces.
+// ---------------------------------------------------
+
+/// Error type returned when reading QPS files.
+#[derive(Error, Debug)]
+pub enum QpsError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("line {line}: {msg}")]
+    Parse { line: usize, msg: String },
+    #[error("line {line}: unsupported {what}")]
+    Unsupported { line: usize, what: String },
+}
+
+// values at least this large in magnitude are treated as infinite
+const QPS_INFINITY: f64 = 1e20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowKind {
+    Equal,
+    Less,
+    Greater,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    None,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+    Quadratic { full: bool },
+}
+
+// a reference to a row by name, resolved to either the
+// objective, a free row to be ignored, or a constraint row
+#[derive(Debug, Clone, Copy)]
+enum RowRef {
+    Objective,
+    Free,
+    Constraint(usize),
+}
+
+/// Read a quadratic program in free format QPS.
+///
+/// Supports the NAME, OBJSENSE, ROWS, COLUMNS, RHS, RANGES, BOUNDS,
+/// QUADOBJ and QMATRIX sections.   QUADOBJ lists only the lower
+/// triangular part of the objective Hessian, while QMATRIX lists the
+/// full matrix.   Integer markers, integer bound types and quadratic
+/// constraints (QSECTION, QCMATRIX) produce a [`QpsError::Unsupported`]
+/// error.
+///
+/// Variables have default bounds `0 ≤ x < ∞`, with the lower bound
+/// set to `-∞` if only a negative upper bound is given.   Values of
+/// magnitude `1e20` or larger are treated as infinite.  The RHS of
+/// the objective row gives the negated objective constant.
+///
+/// The constraints are returned as `l ≤ Ax ≤ u`, with the
+/// constraint rows first and a row for each variable with a finite
+/// bound appended.
+pub fn read_qps<T, R>(reader: R) -> Result<QpProblem<T>, QpsError>
+where
+    T: FloatT,
+    R: BufRead,
+{
+    let mut input = QpsLines {
+        lines: reader.lines(),
+        lineno: 0,
+    };
+
+    let mut sense = OptimizationSense::Minimize;
+    let mut section = Section::None;
+
+    let mut objname: Option<String> = None;
+    let mut rows: HashMap<String, RowRef> = HashMap::new();
+    let mut rowkinds = Vec::new();
+    let mut rhs: Vec<T> = Vec::new();
+    let mut ranges: Vec<Option<T>> = Vec::new();
+
+    let mut cols: HashMap<String, usize> = HashMap::new();
+    let mut q: Vec<T> = Vec::new();
+    let mut lb: Vec<Option<T>> = Vec::new();
+    let mut ub: Vec<Option<T>> = Vec::new();
+    let mut c0 = T::zero();
+
+    let (mut Ai, mut Aj, mut Av) = (Vec::new(), Vec::new(), Vec::new());
+    let (mut Pi, mut Pj, mut Pv) = (Vec::new(), Vec::new(), Vec::new());
+
+    while let Some((line, is_header)) = input.next_line()? {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        if is_header {
+            section = match fields[0] {
+                "NAME" => Section::None,
+                "OBJSENSE" => {
+                    // sense given either on the same line or the next
+                    let next;
+                    let s = match fields.get(1) {
+                        Some(s) => *s,
+                        None => {
+                            next = input.expect_line()?;
+                            next.trim()
+                        }
+                    };
+                    sense = match s {
+                        "MIN" | "MINIMIZE" => OptimizationSense::Minimize,
+                        "MAX" | "MAXIMIZE" => OptimizationSense::Maximize,
+                        _ => return Err(input.error(&format!("invalid objective sense {}", s))),
+                    };
+                    Section::None
+                }
+                "ROWS" => Section::Rows,
+                "COLUMNS" => Section::Columns,
+                "RHS" => Section::Rhs,
+                "RANGES" => Section::Ranges,
+                "BOUNDS" => Section::Bounds,
+                "QUADOBJ" => Section::Quadratic { full: false },
+                "QMATRIX" => Section::Quadratic { full: true },
+                "ENDATA" => break,
+                "QSECTION" | "QCMATRIX" | "CSECTION" | "SOS" => {
+                    return Err(input.unsupported(format!("section {}", fields[0])));
+                }
+                _ => {
+                    return Err(input.error(&format!("unrecognized section {}", fields[0])));
+                }
+            };
+            continue;
+        }
+
+        match section {
+            Section::None => {
+                return Err(input.error("data line outside of a section"));
+            }
+            Section::Rows => {
+                let [kind, name] = input.split_fields(&fields)?;
+                let rowref = match kind {
+                    "N" if objname.is_none() => {
+                        objname = Some(name.to_string());
+                        RowRef::Objective
+                    }
+                    "N" => RowRef::Free,
+                    "E" | "L" | "G" => {
+                        rowkinds.push(match kind {
+                            "E" => RowKind::Equal,
+                            "L" => RowKind::Less,
+                            _ => RowKind::Greater,
+                        });
+                        rhs.push(T::zero());
+                        ranges.push(None);
+                        RowRef::Constraint(rowkinds.len() - 1)
+                    }
+                    _ => return Err(input.error(&format!("invalid row type {}", kind))),
+                };
+                if rows.insert(name.to_string(), rowref).is_some() {
+                    return Err(input.error(&format!("duplicate row {}", name)));
+                }
+            }
+            Section::Columns => {
+                if fields.contains(&"'MARKER'") {
+                    return Err(input.unsupported("integer variables (MARKER)".to_string()));
+                }
+                if fields.len() != 3 && fields.len() != 5 {
+                    return Err(input.error("expected 3 or 5 fields"));
+                }
+                let next = cols.len();
+                let j = *cols.entry(fields[0].to_string()).or_insert(next);
+                if j == next {
+                    q.push(T::zero());
+                    lb.push(None);
+                    ub.push(None);
+                }
+                for pair in fields[1..].chunks(2) {
+                    let v = input.parse_float(pair[1])?;
+                    match input.lookup_row(&rows, pair[0])? {
+                        RowRef::Objective => q[j] += v,
+                        RowRef::Free => {}
+                        RowRef::Constraint(i) => {
+                            Ai.push(i);
+                            Aj.push(j);
+                            Av.push(v);
+                        }
+                    }
+                }
+            }
+            Section::Rhs | Section::Ranges => {
+                // the leading vector name is optional
+                let pairs = match fields.len() {
+                    2 | 4 => &fields[..],
+                    3 | 5 => &fields[1..],
+                    _ => return Err(input.error("expected 2 to 5 fields")),
+                };
+                for pair in pairs.chunks(2) {
+                    let v: T = input.parse_float(pair[1])?;
+                    match (section, input.lookup_row(&rows, pair[0])?) {
+                        (Section::Rhs, RowRef::Objective) => c0 = -v,
+                        (Section::Rhs, RowRef::Constraint(i)) => rhs[i] = v,
+                        (_, RowRef::Constraint(i)) => ranges[i] = Some(v),
+                        _ => {}
+                    }
+                }
+            }
+            Section::Bounds => {
+                let kind = fields[0];
+                let has_value = matches!(kind, "UP" | "LO" | "FX");
+                // the bound vector name is optional, and some
+                // writers include a value for bounds without one
+                let (col, val) = match (has_value, fields.len()) {
+                    (true, 3) => (fields[1], Some(fields[2])),
+                    (true, 4) => (fields[2], Some(fields[3])),
+                    (false, 2) => (fields[1], None),
+                    (false, 3) | (false, 4) => (fields[2], None),
+                    _ => return Err(input.error("invalid number of fields")),
+                };
+                let j = input.lookup_col(&cols, col)?;
+                let v = val.map(|s| input.parse_float::<T>(s)).transpose()?;
+                match (kind, v) {
+                    ("UP", Some(v)) => {
+                        if v < T::zero() && lb[j].is_none() {
+                            lb[j] = Some(-T::infinity());
+                        }
+                        ub[j] = Some(v);
+                    }
+                    ("LO", Some(v)) => lb[j] = Some(v),
+                    ("FX", Some(v)) => (lb[j], ub[j]) = (Some(v), Some(v)),
+                    ("FR", _) => (lb[j], ub[j]) = (Some(-T::infinity()), Some(T::infinity())),
+                    ("MI", _) => lb[j] = Some(-T::infinity()),
+                    ("PL", _) => ub[j] = Some(T::infinity()),
+                    ("BV" | "LI" | "UI" | "SC", _) => {
+                        return Err(input.unsupported(format!("bound type {}", kind)));
+                    }
+                    _ => return Err(input.error(&format!("invalid bound type {}", kind))),
+                }
+            }
+            Section::Quadratic { full } => {
+                let [ci, cj, v] = input.split_fields(&fields)?;
+                let i = input.lookup_col(&cols, ci)?;
+                let j = input.lookup_col(&cols, cj)?;
+                let v = input.parse_float(v)?;
+                // QMATRIX lists both triangles, so keep only
+                // the upper one.  QUADOBJ lists only one of them.
+                if full && i > j {
+                    continue;
+                }
+                Pi.push(usize::min(i, j));
+                Pj.push(usize::max(i, j));
+                Pv.push(v);
+            }
+        }
+    }
+
+    if objname.is_none() {
+        return Err(input.error("missing objective row"));
+    }
+
+    let (m, n) = (rowkinds.len(), cols.len());
+
+    // row bounds from the row type, RHS and ranges
+    let mut l = Vec::with_capacity(m + n);
+    let mut u = Vec::with_capacity(m + n);
+    for ((&kind, &r), range) in rowkinds.iter().zip(rhs.iter()).zip(ranges) {
+        let (li, ui) = match (kind, range) {
+            (RowKind::Equal, None) => (r, r),
+            (RowKind::Equal, Some(R)) if R < T::zero() => (r + R, r),
+            (RowKind::Equal, Some(R)) => (r, r + R),
+            (RowKind::Less, None) => (-T::infinity(), r),
+            (RowKind::Less, Some(R)) => (r - R.abs(), r),
+            (RowKind::Greater, None) => (r, T::infinity()),
+            (RowKind::Greater, Some(R)) => (r, r + R.abs()),
+        };
+        l.push(li);
+        u.push(ui);
+    }
+
+    // append a row for each variable with a finite bound
+    for (j, (lbj, ubj)) in lb.iter().zip(ub.iter()).enumerate() {
+        let lbj = _to_infinity(lbj.unwrap_or(T::zero()));
+        let ubj = _to_infinity(ubj.unwrap_or(T::infinity()));
+        if lbj.is_finite() || ubj.is_finite() {
+            Ai.push(l.len());
+            Aj.push(j);
+            Av.push(T::one());
+            l.push(lbj);
+            u.push(ubj);
+        }
+    }
+    l.iter_mut().for_each(|v| *v = _to_infinity(*v));
+    u.iter_mut().for_each(|v| *v = _to_infinity(*v));
+
+    // our maximization form is max qᵀx - ½xᵀPx
+    if sense == OptimizationSense::Maximize {
+        Pv.negate();
+    }
+
+    let P = CscMatrix::new_from_triplets(n, n, Pi, Pj, Pv);
+    let A = CscMatrix::new_from_triplets(l.len(), n, Ai, Aj, Av);
+
+    Ok(QpProblem {
+        P,
+        q,
+        A,
+        l,
+        u,
+        sense,
+        objective_constant: c0,
+    })
+}
+
+fn _to_infinity<T: FloatT>(v: T) -> T {
+    let inf: T = QPS_INFINITY.as_T();
+    if v >= inf {
+        T::infinity()
+    } else if v <= -inf {
+        -T::infinity()
+    } else {
+        v
+    }
+}
+
+// line oriented reader, skipping comments and blank lines.
+// Section headers are the lines starting in the first column.
+
+struct QpsLines<R> {
+    lines: Lines<R>,
+    lineno: usize,
+}
+
+impl<R: BufRead> QpsLines<R> {
+    fn next_line(&mut self) -> Result<Option<(String, bool)>, QpsError> {
+        for line in self.lines.by_ref() {
+            self.lineno += 1;
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('*') {
+                continue;
+            }
+            let is_header = !line.starts_with(char::is_whitespace);
+            return Ok(Some((line.trim_end().to_string(), is_header)));
+        }
+        Ok(None)
+    }
+
+    fn expect_line(&mut self) -> Result<String, QpsError> {
+        self.next_line()?
+            .map(|(line, _)| line)
+            .ok_or_else(|| self.error("unexpected end of file"))
+    }
+
+    fn error(&self, msg: &str) -> QpsError {
+        QpsError::Parse {
+            line: self.lineno,
+            msg: msg.to_string(),
+        }
+    }
+
+    fn unsupported(&self, what: String) -> QpsError {
+        QpsError::Unsupported {
+            line: self.lineno,
+            what,
+        }
+    }
+
+    fn split_fields<'a, const N: usize>(
+        &self,
+        fields: &[&'a str],
+    ) -> Result<[&'a str; N], QpsError> {
+        fields
+            .try_into()
+            .map_err(|_| self.error(&format!("expected {} fields", N)))
+    }
+
+    fn parse_float<T: FloatT>(&self, s: &str) -> Result<T, QpsError> {
+        s.parse::<f64>()
+            .map(|v| v.as_T())
+            .map_err(|_| self.error(&format!("invalid number {}", s)))
+    }
+
+    fn lookup_row(&self, rows: &HashMap<String, RowRef>, name: &str) -> Result<RowRef, QpsError> {
+        rows.get(name)
+            .copied()
+            .ok_or_else(|| self.error(&format!("unknown row {}", name)))
+    }
+
+    fn lookup_col(&self, cols: &HashMap<String, usize>, name: &str) -> Result<usize, QpsError> {
+        cols.get(name)
+            .copied()
+            .ok_or_else(|| self.error(&format!("unknown column {}", name)))
+    }
+}
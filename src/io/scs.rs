@@ -0,0 +1,294 @@
+use super::ConicProblem;
+use crate::algebra::*;
+use crate::solver::{OptimizationSense, SupportedConeT, SupportedConeT::*};
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+#[cfg(feature = "serde")]
+use std::io::Read;
+
+// ---------------------------------------------------
+// Import of problem data in the form used by the SCS solver, i.e.
+//
+//   min   ½xᵀPx + cᵀx
+//   s.t.  Ax + s = b, s ∈ K
+//
+// with P upper triangular.   This is the same as our standard form,
+// so only the cone specification needs translation.   SCS orders
+// the rows of A by cone type, as zero, nonnegative, second order,
+// semidefinite, exponential, dual exponential and then power cones.
+// ---------------------------------------------------
+
+/// Error type returned when importing SCS problem data.
+#[derive(Error, Debug)]
+pub enum ScsError {
+    #[cfg(feature = "serde")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported SCS cone \"{0}\"")]
+    UnsupportedCone(String),
+    #[error("incompatible dimensions: {0}")]
+    IncompatibleDimension(&'static str),
+    #[error("invalid matrix {0}: {1}")]
+    BadMatrix(&'static str, SparseFormatError),
+    #[error("P must be upper triangular")]
+    NotUpperTriangular,
+}
+
+/// Cone specification in SCS form, with fields named as in the SCS
+/// cone dictionary.
+///
+/// Semidefinite (`s`) and dual exponential (`ed`) cones, and dual power
+/// cones (negative entries of `p`), have no equivalent in the supported
+/// cones and produce a [`ScsError::UnsupportedCone`] error.
+#[derive(Debug, Clone, Default)]
+pub struct ScsConeSpec<T: FloatT = f64> {
+    /// dimension of the zero cone
+    pub z: usize,
+    /// dimension of the nonnegative cone
+    pub l: usize,
+    /// dimensions of the second order cones
+    pub q: Vec<usize>,
+    /// dimensions of the semidefinite cones
+    pub s: Vec<usize>,
+    /// number of exponential cones
+    pub ep: usize,
+    /// number of dual exponential cones
+    pub ed: usize,
+    /// exponents of the 3-dimensional power cones
+    pub p: Vec<T>,
+}
+
+impl<T> ScsConeSpec<T>
+where
+    T: FloatT,
+{
+    /// Equivalent list of cones, in the SCS row order.
+    pub fn to_cones(&self) -> Result<Vec<SupportedConeT<T>>, ScsError> {
+        if !self.s.is_empty() {
+            return Err(ScsError::UnsupportedCone("s".to_string()));
+        }
+        if self.ed > 0 {
+            return Err(ScsError::UnsupportedCone("ed".to_string()));
+        }
+        if self.p.iter().any(|&α| α < T::zero()) {
+            return Err(ScsError::UnsupportedCone("p (dual power)".to_string()));
+        }
+
+        let mut cones = Vec::new();
+        if self.z > 0 {
+            cones.push(ZeroConeT(self.z));
+        }
+        if self.l > 0 {
+            cones.push(NonnegativeConeT(self.l));
+        }
+        for &dim in &self.q {
+            // SCS allows 1-dimensional second order cones, i.e. t ≥ 0
+            match dim {
+                0 => {}
+                1 => cones.push(NonnegativeConeT(1)),
+                _ => cones.push(SecondOrderConeT(dim)),
+            }
+        }
+        cones.extend((0..self.ep).map(|_| ExponentialConeT()));
+        cones.extend(self.p.iter().map(|&α| PowerConeT(α)));
+
+        Ok(cones)
+    }
+
+    /// Total dimension of the cones, i.e. the number of rows of `A`.
+    /// Saturates at `usize::MAX` if the dimension overflows.
+    pub fn dim(&self) -> usize {
+        let triangle = |k: usize| {
+            k.checked_mul(k.saturating_add(1))
+                .map_or(usize::MAX, |t| t / 2)
+        };
+        [
+            self.z,
+            self.l,
+            self.q
+                .iter()
+                .fold(0, |acc: usize, &k| acc.saturating_add(k)),
+            self.s
+                .iter()
+                .fold(0, |acc: usize, &k| acc.saturating_add(triangle(k))),
+            (self.ep + self.ed + self.p.len()).saturating_mul(3),
+        ]
+        .iter()
+        .fold(0, |acc: usize, &k| acc.saturating_add(k))
+    }
+}
+
+/// Create a [`ConicProblem`] from linear problem data in SCS form.
+///
+/// SCS and this solver use the same constraint form `Ax + s = b` and
+/// the same conventions for the exponential and power cones, so the
+/// data is used unchanged and only the cones are translated.   Use
+/// [`from_scs_data_qp`] for problems with a quadratic objective.
+pub fn from_scs_data<T: FloatT>(
+    A: CscMatrix<T>,
+    b: &[T],
+    c: &[T],
+    cones: ScsConeSpec<T>,
+) -> Result<ConicProblem<T>, ScsError> {
+    let P = CscMatrix::zeros((c.len(), c.len()));
+    from_scs_data_qp(P, A, b, c, cones)
+}
+
+/// As [`from_scs_data`], with an upper triangular quadratic cost `P`.
+pub fn from_scs_data_qp<T: FloatT>(
+    P: CscMatrix<T>,
+    A: CscMatrix<T>,
+    b: &[T],
+    c: &[T],
+    cones: ScsConeSpec<T>,
+) -> Result<ConicProblem<T>, ScsError> {
+    P.check_format().map_err(|e| ScsError::BadMatrix("P", e))?;
+    A.check_format().map_err(|e| ScsError::BadMatrix("A", e))?;
+
+    let n = c.len();
+    if P.m != n || P.n != n {
+        return Err(ScsError::IncompatibleDimension("P and c"));
+    }
+    if !P.is_triu() {
+        return Err(ScsError::NotUpperTriangular);
+    }
+    if A.n != n {
+        return Err(ScsError::IncompatibleDimension("A and c"));
+    }
+    if A.m != b.len() {
+        return Err(ScsError::IncompatibleDimension("A and b"));
+    }
+
+    let supported = cones.to_cones()?;
+    if cones.dim() != b.len() {
+        return Err(ScsError::IncompatibleDimension("cones and b"));
+    }
+
+    Ok(ConicProblem {
+        P,
+        q: c.to_vec(),
+        A,
+        b: b.to_vec(),
+        cones: supported,
+        sense: OptimizationSense::Minimize,
+        objective_constant: T::zero(),
+    })
+}
+
+// ---------------------------------------------------
+// JSON dumps of the SCS data and cone dictionaries
+// ---------------------------------------------------
+
+// sparse matrix in scipy CSC form
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ScsJsonMatrix {
+    shape: (usize, usize),
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ScsJsonData {
+    A: ScsJsonMatrix,
+    #[serde(default)]
+    P: Option<ScsJsonMatrix>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ScsJsonDump {
+    data: ScsJsonData,
+    cone: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    offset: f64,
+}
+
+#[cfg(feature = "serde")]
+impl ScsJsonMatrix {
+    fn into_csc<T: FloatT>(self) -> CscMatrix<T> {
+        let (m, n) = self.shape;
+        let nzval = self.data.iter().map(|&v| v.as_T()).collect();
+        CscMatrix::new(m, n, self.indptr, self.indices, nzval)
+    }
+}
+
+/// Read SCS problem data from a JSON dump of the SCS data and cone
+/// dictionaries, e.g. of the problem data returned by CVXPY from
+/// `problem.get_problem_data(cp.SCS)`.   The expected layout is
+///
+/// ```text
+/// {
+///   "data":   {"A": <csc>, "b": [...], "c": [...], "P": <csc> (optional)},
+///   "cone":   {"z": 1, "l": 2, "q": [3], "ep": 1, ...},
+///   "offset": 0.0 (optional)
+/// }
+/// ```
+///
+/// where each `<csc>` matrix is given in scipy CSC form as
+/// `{"shape": [m, n], "indptr": [...], "indices": [...], "data": [...]}`.
+/// The `offset` is the constant term of the objective.   The legacy
+/// name `f` is accepted for the zero cone, and cone entries that are
+/// zero or empty are ignored.  Any other cone produces an
+/// [`ScsError::UnsupportedCone`] error naming it.
+#[cfg(feature = "serde")]
+pub fn read_scs_json<T, R>(reader: R) -> Result<ConicProblem<T>, ScsError>
+where
+    T: FloatT,
+    R: Read,
+{
+    let dump: ScsJsonDump = serde_json::from_reader(reader)?;
+
+    let mut spec = ScsConeSpec::<T>::default();
+    for (name, value) in dump.cone {
+        if _is_empty_json(&value) {
+            continue;
+        }
+        match name.as_str() {
+            "z" | "f" => spec.z += serde_json::from_value::<usize>(value)?,
+            "l" => spec.l = serde_json::from_value(value)?,
+            "ep" => spec.ep = serde_json::from_value(value)?,
+            "ed" => spec.ed = serde_json::from_value(value)?,
+            "q" => spec.q = serde_json::from_value(value)?,
+            "s" => spec.s = serde_json::from_value(value)?,
+            "p" => {
+                let p: Vec<f64> = serde_json::from_value(value)?;
+                spec.p = p.iter().map(|&α| α.as_T()).collect();
+            }
+            _ => return Err(ScsError::UnsupportedCone(name)),
+        }
+    }
+
+    let data = dump.data;
+    let A = data.A.into_csc();
+    let P = match data.P {
+        Some(P) => P.into_csc(),
+        None => CscMatrix::zeros((data.c.len(), data.c.len())),
+    };
+    let b: Vec<T> = data.b.iter().map(|&v| v.as_T()).collect();
+    let c: Vec<T> = data.c.iter().map(|&v| v.as_T()).collect();
+
+    let mut problem = from_scs_data_qp(P, A, &b, &c, spec)?;
+    problem.objective_constant = dump.offset.as_T();
+    Ok(problem)
+}
+
+// cone entries such as "s": [] or "ep": 0 are present in
+// many dumps even when the problem has no such cones
+#[cfg(feature = "serde")]
+fn _is_empty_json(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Number(x) => x.as_f64() == Some(0.),
+        serde_json::Value::Array(v) => v.is_empty(),
+        _ => false,
+    }
+}
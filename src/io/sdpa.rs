@@ -0,0 +1,230 @@
+use super::ConicProblem;
+use crate::algebra::*;
+use crate::solver::{OptimizationSense, SupportedConeT, SupportedConeT::*};
+use std::io::{BufRead, Lines};
+use thiserror::Error;
+
+// ---------------------------------------------------
+// Reader for the sparse SDPA format, as used by the SDPLIB problem
+// library.   SDPA problems have the form
+//
+//   min   cᵀx
+//   s.t.  F₁x₁ + ... + Fₘxₘ - F₀ ⪰ 0
+//
+// where the Fᵢ are symmetric and block diagonal, with each block
+// either dense (a PSD constraint) or diagonal (linear inequalities).
+// We convert to the standard form Ax + s = b, s ∈ K, with each dense
+// block written in the triangular packed form used by the
+// PSDTriangleConeT, i.e. the upper triangle by columns with the off
+// diagonal entries scaled by √2.
+// ---------------------------------------------------
+
+/// Error type returned when reading SDPA files.
+#[derive(Error, Debug)]
+pub enum SdpaError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("line {line}: {msg}")]
+    Parse { line: usize, msg: String },
+}
+
+/// Read a problem in the sparse SDPA format.
+///
+/// Comment lines starting with `"` or `*` are skipped, and the
+/// punctuation `,{}()=` is treated as whitespace in the header.
+/// Blocks with negative size in the block structure are diagonal,
+/// and are converted to nonnegative cones.   All other blocks are
+/// converted to [`PSDTriangleConeT`](crate::solver::SupportedConeT::PSDTriangleConeT)
+/// constraints.
+///
+/// Entries may be given in either triangle of a block, and
+/// repeated entries are added.
+pub fn read_sdpa<T, R>(reader: R) -> Result<ConicProblem<T>, SdpaError>
+where
+    T: FloatT,
+    R: BufRead,
+{
+    let mut input = SdpaLines {
+        lines: reader.lines(),
+        lineno: 0,
+    };
+
+    // header : m, number of blocks, block structure and c
+    let m = input.parse_count()?;
+    let nblocks = input.parse_count()?;
+    let blocksizes: Vec<isize> = input
+        .parse_numbers::<f64>(nblocks)?
+        .into_iter()
+        .map(|v| v as isize)
+        .collect();
+    let c: Vec<T> = input.parse_numbers(m)?;
+
+    if blocksizes.contains(&0) {
+        return Err(input.error("invalid block size 0"));
+    }
+
+    // first row of each block in the standard form,
+    // and the cone for each block
+    let mut offsets = Vec::with_capacity(nblocks);
+    let mut cones: Vec<SupportedConeT<T>> = Vec::with_capacity(nblocks);
+    let mut nrows = 0;
+    for &size in blocksizes.iter() {
+        offsets.push(nrows);
+        let k = size.unsigned_abs();
+        let rows = if size < 0 {
+            Some(k)
+        } else {
+            checked_triangular_number(k)
+        };
+        nrows = rows
+            .and_then(|rows| checked_sum([nrows, rows]))
+            .ok_or_else(|| input.error(&format!("block size {} is too large", size)))?;
+        if size < 0 {
+            match cones.last_mut() {
+                Some(NonnegativeConeT(d)) => *d += k,
+                _ => cones.push(NonnegativeConeT(k)),
+            }
+        } else {
+            cones.push(PSDTriangleConeT(k));
+        }
+    }
+
+    let (mut I, mut J, mut V) = (Vec::new(), Vec::new(), Vec::new());
+    let mut b = vec![T::zero(); nrows];
+
+    while let Some(line) = input.next_line()? {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(input.error("expected 5 fields"));
+        }
+        let mat = input.parse_usize(fields[0])?;
+        let blk = input.parse_usize(fields[1])?;
+        let i = input.parse_usize(fields[2])?;
+        let j = input.parse_usize(fields[3])?;
+        let v: T = input.parse_float(fields[4])?;
+
+        if mat > m {
+            return Err(input.error(&format!("matrix index {} out of range", mat)));
+        }
+        if blk == 0 || blk > nblocks {
+            return Err(input.error(&format!("block index {} out of range", blk)));
+        }
+        let size = blocksizes[blk - 1];
+        let k = size.unsigned_abs();
+        if i == 0 || j == 0 || i > k || j > k {
+            return Err(input.error(&format!("entry ({},{}) out of range", i, j)));
+        }
+        let (row, col) = (usize::min(i, j) - 1, usize::max(i, j) - 1);
+
+        // row of the standard form, and scaling
+        // for off diagonal entries of dense blocks
+        let (idx, v) = if size < 0 {
+            if row != col {
+                return Err(input.error("off diagonal entry in a diagonal block"));
+            }
+            (offsets[blk - 1] + row, v)
+        } else if row == col {
+            (offsets[blk - 1] + triangular_number(col) + row, v)
+        } else {
+            (
+                offsets[blk - 1] + triangular_number(col) + row,
+                v * T::SQRT_2(),
+            )
+        };
+
+        // s = F₁x₁ + ... + Fₘxₘ - F₀ = b - Ax
+        if mat == 0 {
+            b[idx] -= v;
+        } else {
+            I.push(idx);
+            J.push(mat - 1);
+            V.push(-v);
+        }
+    }
+
+    let A = CscMatrix::new_from_triplets(nrows, m, I, J, V);
+
+    Ok(ConicProblem {
+        P: CscMatrix::zeros((m, m)),
+        q: c,
+        A,
+        b,
+        cones,
+        sense: OptimizationSense::Minimize,
+        objective_constant: T::zero(),
+    })
+}
+
+// line oriented reader, skipping comments and blank lines
+
+struct SdpaLines<R> {
+    lines: Lines<R>,
+    lineno: usize,
+}
+
+impl<R: BufRead> SdpaLines<R> {
+    fn next_line(&mut self) -> Result<Option<String>, SdpaError> {
+        for line in self.lines.by_ref() {
+            self.lineno += 1;
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('"') || line.starts_with('*') {
+                continue;
+            }
+            return Ok(Some(line.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn expect_line(&mut self) -> Result<String, SdpaError> {
+        self.next_line()?
+            .ok_or_else(|| self.error("unexpected end of file"))
+    }
+
+    fn error(&self, msg: &str) -> SdpaError {
+        SdpaError::Parse {
+            line: self.lineno,
+            msg: msg.to_string(),
+        }
+    }
+
+    fn parse_usize(&self, s: &str) -> Result<usize, SdpaError> {
+        s.parse::<usize>()
+            .map_err(|_| self.error(&format!("invalid integer {}", s)))
+    }
+
+    fn parse_float<T: FloatT>(&self, s: &str) -> Result<T, SdpaError> {
+        s.parse::<f64>()
+            .map(|v| v.as_T())
+            .map_err(|_| self.error(&format!("invalid number {}", s)))
+    }
+
+    fn parse_count(&mut self) -> Result<usize, SdpaError> {
+        let v: f64 = self.parse_numbers(1)?[0];
+        if v < 0.0 || v.fract() != 0.0 {
+            return Err(self.error(&format!("invalid count {}", v)));
+        }
+        Ok(v as usize)
+    }
+
+    // read `count` numbers from one or more header lines, ignoring
+    // punctuation and any trailing text on each line, e.g. "3 = mDIM"
+    fn parse_numbers<T: FloatT>(&mut self, count: usize) -> Result<Vec<T>, SdpaError> {
+        let mut values = Vec::with_capacity(count);
+        while values.len() < count {
+            let line = self.expect_line()?;
+            let line = line.replace([',', '{', '}', '(', ')', '='], " ");
+            let before = values.len();
+            for s in line.split_whitespace() {
+                match s.parse::<f64>() {
+                    Ok(v) if values.len() < count => values.push(v.as_T()),
+                    _ => break,
+                }
+            }
+            if values.len() == before {
+                return Err(self.error("expected a number"));
+            }
+        }
+        Ok(values)
+    }
+}
@@ -69,17 +69,13 @@ pub(crate) extern "C" fn solver_new_jlrs(
     let cones = ccall_arrays_to_cones(jlcones);
     let settings = settings_from_json(json_settings);
 
-    // manually validate settings from Julia side
-    match settings.validate() {
-        Ok(_) => (),
+    match DefaultSolver::new(&P, &q, &A, &b, &cones, settings) {
+        Ok(solver) => to_ptr(Box::new(solver)),
         Err(e) => {
-            println!("Invalid settings: {}", e);
-            return std::ptr::null_mut();
+            println!("Invalid problem: {}", e);
+            std::ptr::null_mut()
         }
-    };
-
-    let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
-    to_ptr(Box::new(solver))
+    }
 }
 
 #[no_mangle]
@@ -97,10 +93,10 @@ pub(crate) extern "C" fn solver_solve_jlrs(ptr: *mut c_void) -> SolutionJLRS {
 }
 
 #[no_mangle]
-pub(crate) extern "C" fn solver_get_info_jlrs(ptr: *mut c_void) -> DefaultInfo<f64> {
+pub(crate) extern "C" fn solver_get_info_jlrs(ptr: *mut c_void) -> InfoJLRS {
     let solver = from_ptr(ptr);
 
-    let info = solver.info.clone();
+    let info = InfoJLRS::from(&solver.info);
 
     // don't drop, since the memory is owned by
     // Julia and we might want to solve again
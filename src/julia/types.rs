@@ -134,6 +134,63 @@ impl From<&DefaultSolution<f64>> for SolutionJLRS {
     }
 }
 
+// Mirrors the layout of the Julia side DefaultInfo type.  Kept
+// separate from DefaultInfo so that Rust-only info fields do not
+// alter the layout expected by Julia.
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct InfoJLRS {
+    pub μ: f64,
+    pub sigma: f64,
+    pub step_length: f64,
+    pub iterations: u32,
+    pub cost_primal: f64,
+    pub cost_dual: f64,
+    pub res_primal: f64,
+    pub res_dual: f64,
+    pub res_primal_inf: f64,
+    pub res_dual_inf: f64,
+    pub gap_abs: f64,
+    pub gap_rel: f64,
+    pub ktratio: f64,
+    pub prev_cost_primal: f64,
+    pub prev_cost_dual: f64,
+    pub prev_res_primal: f64,
+    pub prev_res_dual: f64,
+    pub prev_gap_abs: f64,
+    pub prev_gap_rel: f64,
+    pub solve_time: f64,
+    pub status: u32, //0 indexed enum in RS/JL
+}
+
+impl From<&DefaultInfo<f64>> for InfoJLRS {
+    fn from(info: &DefaultInfo<f64>) -> Self {
+        InfoJLRS {
+            μ: info.μ,
+            sigma: info.sigma,
+            step_length: info.step_length,
+            iterations: info.iterations,
+            cost_primal: info.cost_primal,
+            cost_dual: info.cost_dual,
+            res_primal: info.res_primal,
+            res_dual: info.res_dual,
+            res_primal_inf: info.res_primal_inf,
+            res_dual_inf: info.res_dual_inf,
+            gap_abs: info.gap_abs,
+            gap_rel: info.gap_rel,
+            ktratio: info.ktratio,
+            prev_cost_primal: info.prev_cost_primal,
+            prev_cost_dual: info.prev_cost_dual,
+            prev_res_primal: info.prev_res_primal,
+            prev_res_dual: info.prev_res_dual,
+            prev_gap_abs: info.prev_gap_abs,
+            prev_gap_rel: info.prev_gap_rel,
+            solve_time: info.solve_time,
+            status: info.status as u32,
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(FromPrimitive)]
 pub(crate) enum ConeEnumJLRS {
@@ -55,13 +55,32 @@
 
 //Rust hates greek characters
 #![allow(confusable_idents)]
+// without the std feature only core and alloc are used
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+
+// the parts of the std prelude that live in alloc
+#[cfg(not(feature = "std"))]
+mod alloc_prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+}
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub mod algebra;
+#[cfg(feature = "io")]
+pub mod io;
+#[cfg(feature = "testing")]
+pub mod qa;
 pub mod qdldl;
 pub mod solver;
 pub(crate) mod stdio;
+#[cfg(not(feature = "std"))]
+pub use stdio::set_print_callback;
 pub mod timers;
 
 #[cfg(feature = "python")]
@@ -69,3 +88,6 @@ pub mod python;
 
 #[cfg(feature = "julia")]
 pub mod julia;
+
+#[cfg(feature = "capi")]
+pub mod capi;
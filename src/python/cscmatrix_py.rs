@@ -2,7 +2,7 @@
 
 use crate::algebra::CscMatrix;
 use core::ops::Deref;
-use pyo3::prelude::*;
+use pyo3::{exceptions::PyValueError, prelude::*};
 
 //We can't implement the foreign trait FromPyObject directly on CscMatrix
 //since it is outside the crate, so put a dummy wrapper around it here.
@@ -22,12 +22,24 @@ impl From<PyCscMatrix> for CscMatrix<f64> {
 
 impl<'a> FromPyObject<'a> for PyCscMatrix {
     fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        // scipy does not require sorted row indices within columns
+        let obj = match obj.getattr("has_sorted_indices")?.is_true()? {
+            true => obj,
+            false => obj.call_method0("sorted_indices")?,
+        };
+
+        // The arrays are copied rather than viewed in place.   The module
+        // is built against the stable ABI for python 3.7 (pyo3 "abi3-py37"),
+        // which has no buffer protocol, and the solver outlives this call
+        // and scales its own copy of the values during setup in any case.
         let nzval: Vec<f64> = obj.getattr("data")?.extract()?;
         let rowval: Vec<usize> = obj.getattr("indices")?.extract()?;
         let colptr: Vec<usize> = obj.getattr("indptr")?.extract()?;
         let shape: Vec<usize> = obj.getattr("shape")?.extract()?;
 
         let mat = CscMatrix::new(shape[0], shape[1], colptr, rowval, nzval);
+        mat.check_format()
+            .map_err(|e| PyValueError::new_err(format!("Invalid CSC matrix: {}", e)))?;
 
         Ok(PyCscMatrix(mat))
     }
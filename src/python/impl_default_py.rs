@@ -7,7 +7,7 @@ use super::*;
 use crate::solver::{
     core::{
         traits::{InfoPrint, Settings},
-        IPSolver, SolverStatus,
+        IPSolver, ScalingStrategy, SolverStatus,
     },
     implementations::default::*,
     SolverJSONReadWrite,
@@ -57,6 +57,8 @@ pub struct PyDefaultSolution {
     pub r_prim: f64,
     #[pyo3(get)]
     pub r_dual: f64,
+    #[pyo3(get)]
+    pub recovery_attempt: u32,
 }
 
 impl PyDefaultSolution {
@@ -76,6 +78,7 @@ impl PyDefaultSolution {
             iterations: result.iterations,
             r_prim: result.r_prim,
             r_dual: result.r_dual,
+            recovery_attempt: result.recovery_attempt,
         }
     }
 }
@@ -150,6 +153,161 @@ impl PySolverStatus {
     }
 }
 
+#[derive(PartialEq, Debug, Clone)]
+#[pyclass(name = "OptimizationSense")]
+pub enum PyOptimizationSense {
+    Minimize,
+    Maximize,
+}
+
+impl PyOptimizationSense {
+    pub(crate) fn new_from_internal(sense: &OptimizationSense) -> Self {
+        match sense {
+            OptimizationSense::Minimize => PyOptimizationSense::Minimize,
+            OptimizationSense::Maximize => PyOptimizationSense::Maximize,
+        }
+    }
+
+    pub(crate) fn to_internal(&self) -> OptimizationSense {
+        match self {
+            PyOptimizationSense::Minimize => OptimizationSense::Minimize,
+            PyOptimizationSense::Maximize => OptimizationSense::Maximize,
+        }
+    }
+}
+
+#[pymethods]
+impl PyOptimizationSense {
+    pub fn __repr__(&self) -> String {
+        match self {
+            PyOptimizationSense::Minimize => "Minimize",
+            PyOptimizationSense::Maximize => "Maximize",
+        }
+        .to_string()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[pyclass(name = "ScalingStrategy")]
+pub enum PyScalingStrategy {
+    PrimalDual,
+    Dual,
+    PrimalOnly,
+    DualOnly,
+}
+
+impl PyScalingStrategy {
+    pub(crate) fn new_from_internal(strategy: &ScalingStrategy) -> Self {
+        match strategy {
+            ScalingStrategy::PrimalDual => PyScalingStrategy::PrimalDual,
+            ScalingStrategy::Dual => PyScalingStrategy::Dual,
+            ScalingStrategy::PrimalOnly => PyScalingStrategy::PrimalOnly,
+            ScalingStrategy::DualOnly => PyScalingStrategy::DualOnly,
+        }
+    }
+
+    pub(crate) fn to_internal(&self) -> ScalingStrategy {
+        match self {
+            PyScalingStrategy::PrimalDual => ScalingStrategy::PrimalDual,
+            PyScalingStrategy::Dual => ScalingStrategy::Dual,
+            PyScalingStrategy::PrimalOnly => ScalingStrategy::PrimalOnly,
+            PyScalingStrategy::DualOnly => ScalingStrategy::DualOnly,
+        }
+    }
+}
+
+#[pymethods]
+impl PyScalingStrategy {
+    pub fn __repr__(&self) -> String {
+        match self {
+            PyScalingStrategy::PrimalDual => "PrimalDual",
+            PyScalingStrategy::Dual => "Dual",
+            PyScalingStrategy::PrimalOnly => "PrimalOnly",
+            PyScalingStrategy::DualOnly => "DualOnly",
+        }
+        .to_string()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[pyclass(name = "TolerancePolicy")]
+pub enum PyTolerancePolicy {
+    Clamp,
+    Strict,
+}
+
+impl PyTolerancePolicy {
+    pub(crate) fn new_from_internal(policy: &TolerancePolicy) -> Self {
+        match policy {
+            TolerancePolicy::Clamp => PyTolerancePolicy::Clamp,
+            TolerancePolicy::Strict => PyTolerancePolicy::Strict,
+        }
+    }
+
+    pub(crate) fn to_internal(&self) -> TolerancePolicy {
+        match self {
+            PyTolerancePolicy::Clamp => TolerancePolicy::Clamp,
+            PyTolerancePolicy::Strict => TolerancePolicy::Strict,
+        }
+    }
+}
+
+#[pymethods]
+impl PyTolerancePolicy {
+    pub fn __repr__(&self) -> String {
+        match self {
+            PyTolerancePolicy::Clamp => "Clamp",
+            PyTolerancePolicy::Strict => "Strict",
+        }
+        .to_string()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[pyclass(name = "RegularizationSchedule")]
+pub struct PyRegularizationSchedule {
+    schedule: RegularizationSchedule<f64>,
+}
+
+impl PyRegularizationSchedule {
+    pub(crate) fn new_from_internal(schedule: RegularizationSchedule<f64>) -> Self {
+        PyRegularizationSchedule { schedule }
+    }
+
+    pub(crate) fn to_internal(&self) -> RegularizationSchedule<f64> {
+        self.schedule
+    }
+}
+
+#[pymethods]
+impl PyRegularizationSchedule {
+    #[staticmethod]
+    #[pyo3(name = "Constant")]
+    pub fn constant(value: f64) -> Self {
+        Self::new_from_internal(RegularizationSchedule::Constant(value))
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "Decreasing")]
+    pub fn decreasing(initial: f64, decay: f64) -> Self {
+        Self::new_from_internal(RegularizationSchedule::Decreasing { initial, decay })
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "Adaptive")]
+    pub fn adaptive(initial: f64) -> Self {
+        Self::new_from_internal(RegularizationSchedule::Adaptive { initial })
+    }
+
+    pub fn value_at_iteration(&self, k: usize) -> f64 {
+        self.schedule.value_at_iteration(k)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self.schedule)
+    }
+}
+
 // ----------------------------------
 // Solver Settings
 // ----------------------------------
@@ -162,8 +320,12 @@ pub struct PyDefaultSettings {
     #[pyo3(get, set)]
     pub time_limit: f64,
     #[pyo3(get, set)]
+    pub timing: bool,
+    #[pyo3(get, set)]
     pub verbose: bool,
     #[pyo3(get, set)]
+    pub sense: PyOptimizationSense,
+    #[pyo3(get, set)]
     pub max_step_fraction: f64,
 
     //full accuracy solution tolerances
@@ -193,6 +355,16 @@ pub struct PyDefaultSettings {
     pub reduced_tol_infeas_rel: f64,
     #[pyo3(get, set)]
     pub reduced_tol_ktratio: f64,
+    #[pyo3(get, set)]
+    pub tolerance_policy: PyTolerancePolicy,
+
+    // checks on P and A
+    #[pyo3(get, set)]
+    pub input_sparse_canonicalize: bool,
+    #[pyo3(get, set)]
+    pub p_auto_triu: bool,
+    #[pyo3(get, set)]
+    pub check_convexity: bool,
 
     // data equilibration
     #[pyo3(get, set)]
@@ -203,14 +375,28 @@ pub struct PyDefaultSettings {
     pub equilibrate_min_scaling: f64,
     #[pyo3(get, set)]
     pub equilibrate_max_scaling: f64,
+    #[pyo3(get, set)]
+    pub equilibrate_skip_threshold: f64,
 
     //step size settings
     #[pyo3(get, set)]
+    pub adaptive_step_backoff: bool,
+    #[pyo3(get, set)]
+    pub step_backoff_factor: f64,
+    #[pyo3(get, set)]
+    pub conservative_steps_enable: bool,
+    #[pyo3(get, set)]
+    pub conservative_steps_gap_rel: f64,
+    #[pyo3(get, set)]
+    pub conservative_step_fraction: f64,
+    #[pyo3(get, set)]
     pub linesearch_backtrack_step: f64,
     #[pyo3(get, set)]
     pub min_switch_step_length: f64,
     #[pyo3(get, set)]
     pub min_terminate_step_length: f64,
+    #[pyo3(get, set)]
+    pub scaling_fallback: PyScalingStrategy,
 
     // KKT settings incomplete
     #[pyo3(get, set)]
@@ -225,6 +411,8 @@ pub struct PyDefaultSettings {
     pub static_regularization_constant: f64,
     #[pyo3(get, set)]
     pub static_regularization_proportional: f64,
+    #[pyo3(get, set)]
+    pub static_regularization_schedule: Option<PyRegularizationSchedule>,
 
     // dynamic regularization parameters
     #[pyo3(get, set)]
@@ -245,10 +433,44 @@ pub struct PyDefaultSettings {
     pub iterative_refinement_max_iter: u32,
     #[pyo3(get, set)]
     pub iterative_refinement_stop_ratio: f64,
+    #[pyo3(get, set)]
+    pub iterative_refinement_refactor: bool,
+    #[pyo3(get, set)]
+    pub batched_kkt_solves: bool,
+    #[pyo3(get, set)]
+    pub incremental_residuals: bool,
 
     // preprocessing
     #[pyo3(get, set)]
     pub presolve_enable: bool,
+    #[pyo3(get, set)]
+    pub infinite_bound_threshold: f64,
+    #[pyo3(get, set)]
+    pub presolve_reductions_enable: bool,
+    #[pyo3(get, set)]
+    pub auto_dualize: bool,
+    #[pyo3(get, set)]
+    pub explicit_transpose_min_nnz: usize,
+    #[pyo3(get, set)]
+    pub explicit_transpose_max_bytes: usize,
+
+    // diagnostics
+    #[pyo3(get, set)]
+    pub postmortem_window: usize,
+    #[pyo3(get, set)]
+    pub kkt_snapshot_iteration: u32,
+
+    // numerical failure recovery
+    #[pyo3(get, set)]
+    pub auto_recovery: bool,
+    #[pyo3(get, set)]
+    pub max_recovery_attempts: u32,
+    #[pyo3(get, set)]
+    pub catch_panics: bool,
+
+    // memory budget
+    #[pyo3(get, set)]
+    pub max_memory_bytes: Option<usize>,
 
     //chordal decomposition (python must be built with "sdp" feature)
     #[pyo3(get, set)]
@@ -293,7 +515,9 @@ impl PyDefaultSettings {
         PyDefaultSettings {
             max_iter: set.max_iter,
             time_limit: set.time_limit,
+            timing: set.timing,
             verbose: set.verbose,
+            sense: PyOptimizationSense::new_from_internal(&set.sense),
             tol_gap_abs: set.tol_gap_abs,
             tol_gap_rel: set.tol_gap_rel,
             tol_feas: set.tol_feas,
@@ -306,19 +530,33 @@ impl PyDefaultSettings {
             reduced_tol_infeas_abs: set.reduced_tol_infeas_abs,
             reduced_tol_infeas_rel: set.reduced_tol_infeas_rel,
             reduced_tol_ktratio: set.reduced_tol_ktratio,
+            tolerance_policy: PyTolerancePolicy::new_from_internal(&set.tolerance_policy),
             max_step_fraction: set.max_step_fraction,
+            input_sparse_canonicalize: set.input_sparse_canonicalize,
+            p_auto_triu: set.p_auto_triu,
+            check_convexity: set.check_convexity,
             equilibrate_enable: set.equilibrate_enable,
             equilibrate_max_iter: set.equilibrate_max_iter,
             equilibrate_min_scaling: set.equilibrate_min_scaling,
             equilibrate_max_scaling: set.equilibrate_max_scaling,
+            equilibrate_skip_threshold: set.equilibrate_skip_threshold,
+            adaptive_step_backoff: set.adaptive_step_backoff,
+            step_backoff_factor: set.step_backoff_factor,
+            conservative_steps_enable: set.conservative_steps_enable,
+            conservative_steps_gap_rel: set.conservative_steps_gap_rel,
+            conservative_step_fraction: set.conservative_step_fraction,
             linesearch_backtrack_step: set.linesearch_backtrack_step,
             min_switch_step_length: set.min_switch_step_length,
             min_terminate_step_length: set.min_terminate_step_length,
+            scaling_fallback: PyScalingStrategy::new_from_internal(&set.scaling_fallback),
             direct_kkt_solver: set.direct_kkt_solver,
             direct_solve_method: set.direct_solve_method.clone(),
             static_regularization_enable: set.static_regularization_enable,
             static_regularization_constant: set.static_regularization_constant,
             static_regularization_proportional: set.static_regularization_proportional,
+            static_regularization_schedule: set
+                .static_regularization_schedule
+                .map(PyRegularizationSchedule::new_from_internal),
             dynamic_regularization_enable: set.dynamic_regularization_enable,
             dynamic_regularization_eps: set.dynamic_regularization_eps,
             dynamic_regularization_delta: set.dynamic_regularization_delta,
@@ -327,7 +565,21 @@ impl PyDefaultSettings {
             iterative_refinement_abstol: set.iterative_refinement_abstol,
             iterative_refinement_max_iter: set.iterative_refinement_max_iter,
             iterative_refinement_stop_ratio: set.iterative_refinement_stop_ratio,
+            iterative_refinement_refactor: set.iterative_refinement_refactor,
+            batched_kkt_solves: set.batched_kkt_solves,
+            incremental_residuals: set.incremental_residuals,
             presolve_enable: set.presolve_enable,
+            infinite_bound_threshold: set.infinite_bound_threshold,
+            presolve_reductions_enable: set.presolve_reductions_enable,
+            auto_dualize: set.auto_dualize,
+            explicit_transpose_min_nnz: set.explicit_transpose_min_nnz,
+            explicit_transpose_max_bytes: set.explicit_transpose_max_bytes,
+            postmortem_window: set.postmortem_window,
+            kkt_snapshot_iteration: set.kkt_snapshot_iteration,
+            auto_recovery: set.auto_recovery,
+            max_recovery_attempts: set.max_recovery_attempts,
+            catch_panics: set.catch_panics,
+            max_memory_bytes: set.max_memory_bytes,
             chordal_decomposition_enable: set.chordal_decomposition_enable,
             chordal_decomposition_merge_method: set.chordal_decomposition_merge_method.clone(),
             chordal_decomposition_compact: set.chordal_decomposition_compact,
@@ -341,7 +593,9 @@ impl PyDefaultSettings {
         DefaultSettings::<f64> {
             max_iter: self.max_iter,
             time_limit: self.time_limit,
+            timing: self.timing,
             verbose: self.verbose,
+            sense: self.sense.to_internal(),
             tol_gap_abs: self.tol_gap_abs,
             tol_gap_rel: self.tol_gap_rel,
             tol_feas: self.tol_feas,
@@ -354,19 +608,34 @@ impl PyDefaultSettings {
             reduced_tol_infeas_abs: self.reduced_tol_infeas_abs,
             reduced_tol_infeas_rel: self.reduced_tol_infeas_rel,
             reduced_tol_ktratio: self.reduced_tol_ktratio,
+            tolerance_policy: self.tolerance_policy.to_internal(),
             max_step_fraction: self.max_step_fraction,
+            input_sparse_canonicalize: self.input_sparse_canonicalize,
+            p_auto_triu: self.p_auto_triu,
+            check_convexity: self.check_convexity,
             equilibrate_enable: self.equilibrate_enable,
             equilibrate_max_iter: self.equilibrate_max_iter,
             equilibrate_min_scaling: self.equilibrate_min_scaling,
             equilibrate_max_scaling: self.equilibrate_max_scaling,
+            equilibrate_skip_threshold: self.equilibrate_skip_threshold,
+            adaptive_step_backoff: self.adaptive_step_backoff,
+            step_backoff_factor: self.step_backoff_factor,
+            conservative_steps_enable: self.conservative_steps_enable,
+            conservative_steps_gap_rel: self.conservative_steps_gap_rel,
+            conservative_step_fraction: self.conservative_step_fraction,
             linesearch_backtrack_step: self.linesearch_backtrack_step,
             min_switch_step_length: self.min_switch_step_length,
             min_terminate_step_length: self.min_terminate_step_length,
+            scaling_fallback: self.scaling_fallback.to_internal(),
             direct_kkt_solver: self.direct_kkt_solver,
             direct_solve_method: self.direct_solve_method.clone(),
             static_regularization_enable: self.static_regularization_enable,
             static_regularization_constant: self.static_regularization_constant,
             static_regularization_proportional: self.static_regularization_proportional,
+            static_regularization_schedule: self
+                .static_regularization_schedule
+                .as_ref()
+                .map(|s| s.to_internal()),
             dynamic_regularization_enable: self.dynamic_regularization_enable,
             dynamic_regularization_eps: self.dynamic_regularization_eps,
             dynamic_regularization_delta: self.dynamic_regularization_delta,
@@ -375,7 +644,21 @@ impl PyDefaultSettings {
             iterative_refinement_abstol: self.iterative_refinement_abstol,
             iterative_refinement_max_iter: self.iterative_refinement_max_iter,
             iterative_refinement_stop_ratio: self.iterative_refinement_stop_ratio,
+            iterative_refinement_refactor: self.iterative_refinement_refactor,
+            batched_kkt_solves: self.batched_kkt_solves,
+            incremental_residuals: self.incremental_residuals,
             presolve_enable: self.presolve_enable,
+            infinite_bound_threshold: self.infinite_bound_threshold,
+            presolve_reductions_enable: self.presolve_reductions_enable,
+            auto_dualize: self.auto_dualize,
+            explicit_transpose_min_nnz: self.explicit_transpose_min_nnz,
+            explicit_transpose_max_bytes: self.explicit_transpose_max_bytes,
+            postmortem_window: self.postmortem_window,
+            kkt_snapshot_iteration: self.kkt_snapshot_iteration,
+            auto_recovery: self.auto_recovery,
+            max_recovery_attempts: self.max_recovery_attempts,
+            catch_panics: self.catch_panics,
+            max_memory_bytes: self.max_memory_bytes,
             chordal_decomposition_enable: self.chordal_decomposition_enable,
             chordal_decomposition_merge_method: self.chordal_decomposition_merge_method.clone(),
             chordal_decomposition_compact: self.chordal_decomposition_compact,
@@ -407,15 +690,8 @@ impl PyDefaultSolver {
         let cones = _py_to_native_cones(cones);
         let settings = settings.to_internal();
 
-        //manually validate settings from Python side
-        match settings.validate() {
-            Ok(_) => (),
-            Err(e) => {
-                return Err(PyException::new_err(format!("Invalid settings: {}", e)));
-            }
-        }
-
-        let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+        let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings)
+            .map_err(|e| PyException::new_err(e.to_string()))?;
         Ok(Self { inner: solver })
     }
 
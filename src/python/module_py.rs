@@ -53,6 +53,10 @@ fn clarabel(_py: Python, m: &PyModule) -> PyResult<()> {
 
     //other API data types
     m.add_class::<PySolverStatus>()?;
+    m.add_class::<PyOptimizationSense>()?;
+    m.add_class::<PyScalingStrategy>()?;
+    m.add_class::<PyTolerancePolicy>()?;
+    m.add_class::<PyRegularizationSchedule>()?;
     m.add_class::<PyDefaultSolution>()?;
     m.add_class::<PyDefaultSettings>()?;
 
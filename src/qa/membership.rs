@@ -0,0 +1,105 @@
+use crate::algebra::*;
+use crate::solver::SupportedConeT;
+use std::iter::zip;
+
+// ---------------------------------------------------
+// Approximate cone membership, as a violation measure
+// that is zero for points in the closed cone and grows
+// with the distance of a point outside it.
+// ---------------------------------------------------
+
+/// Violation of the constraint `s ∈ K` for the cone `K` described by `cone`.
+///
+/// Returns zero if `s` is in the closure of `K`, and otherwise a positive
+/// measure of the violation in the units of `s`, e.g. the most negative
+/// entry of `s` for the nonnegative cone.   The measure is not generally
+/// the distance to `K`.
+pub fn primal_cone_violation<T: FloatT>(cone: &SupportedConeT<T>, s: &[T]) -> T {
+    assert_eq!(s.len(), cone.nvars());
+    match cone {
+        SupportedConeT::ZeroConeT(_) => s.norm_inf(),
+        SupportedConeT::NonnegativeConeT(_) => nonnegative_violation(s),
+        SupportedConeT::SecondOrderConeT(_) => soc_violation(s),
+        SupportedConeT::ExponentialConeT() => expcone_primal_violation(s),
+        SupportedConeT::PowerConeT(α) => genpow_violation(&[*α, T::one() - *α], s, false),
+        SupportedConeT::GenPowerConeT(α, _) => genpow_violation(α, s, false),
+        #[cfg(feature = "sdp")]
+        SupportedConeT::PSDTriangleConeT(n) => psd_violation(*n, s),
+    }
+}
+
+/// Violation of the constraint `z ∈ K*` for the dual of the cone `K`
+/// described by `cone`, measured as for [`primal_cone_violation`].
+pub fn dual_cone_violation<T: FloatT>(cone: &SupportedConeT<T>, z: &[T]) -> T {
+    assert_eq!(z.len(), cone.nvars());
+    match cone {
+        SupportedConeT::ZeroConeT(_) => T::zero(),
+        SupportedConeT::NonnegativeConeT(_) => nonnegative_violation(z),
+        SupportedConeT::SecondOrderConeT(_) => soc_violation(z),
+        SupportedConeT::ExponentialConeT() => expcone_dual_violation(z),
+        SupportedConeT::PowerConeT(α) => genpow_violation(&[*α, T::one() - *α], z, true),
+        SupportedConeT::GenPowerConeT(α, _) => genpow_violation(α, z, true),
+        #[cfg(feature = "sdp")]
+        SupportedConeT::PSDTriangleConeT(n) => psd_violation(*n, z),
+    }
+}
+
+fn nonnegative_violation<T: FloatT>(s: &[T]) -> T {
+    s.iter().fold(T::zero(), |v, &si| T::max(v, -si))
+}
+
+fn soc_violation<T: FloatT>(s: &[T]) -> T {
+    match s.split_first() {
+        Some((&t, x)) => T::max(T::zero(), x.norm() - t),
+        None => T::zero(),
+    }
+}
+
+// K = cl{(x,y,z) | y > 0, y·exp(x/y) ≤ z}
+fn expcone_primal_violation<T: FloatT>(s: &[T]) -> T {
+    let (x, y, z) = (s[0], s[1], s[2]);
+    if y > T::zero() && z > T::zero() {
+        T::max(T::zero(), x - y * T::ln(z / y))
+    } else if y > T::zero() {
+        T::max(-z, y)
+    } else {
+        // on the boundary y = 0, where x ≤ 0 and z ≥ 0
+        T::max(T::zero(), T::max(-y, T::max(x, -z)))
+    }
+}
+
+// K* = cl{(u,v,w) | u < 0, -u·exp(v/u) ≤ e·w}
+fn expcone_dual_violation<T: FloatT>(z: &[T]) -> T {
+    let (u, v, w) = (z[0], z[1], z[2]);
+    if u < T::zero() && w > T::zero() {
+        T::max(T::zero(), u - v + u * T::ln(-w / u))
+    } else if u < T::zero() {
+        T::max(-w, -u)
+    } else {
+        // on the boundary u = 0, where v ≥ 0 and w ≥ 0
+        T::max(T::zero(), T::max(u, T::max(-v, -w)))
+    }
+}
+
+// K = {(x,z) | ∏ xᵢ^αᵢ ≥ ‖z‖, x ≥ 0}, and for the dual cone
+// K* = {(u,w) | ∏ (uᵢ/αᵢ)^αᵢ ≥ ‖w‖, u ≥ 0}
+fn genpow_violation<T: FloatT>(α: &[T], s: &[T], dual: bool) -> T {
+    let (x, z) = s.split_at(α.len());
+    let prod = zip(α, x).fold(T::one(), |p, (&αi, &xi)| {
+        let xi = if dual { xi / αi } else { xi };
+        p * T::powf(T::max(xi, T::zero()), αi)
+    });
+    T::max(nonnegative_violation(x), z.norm() - prod).max(T::zero())
+}
+
+#[cfg(feature = "sdp")]
+fn psd_violation<T: FloatT>(n: usize, s: &[T]) -> T {
+    if n == 0 {
+        return T::zero();
+    }
+    let mut S = Matrix::<T>::zeros((n, n));
+    svec_to_mat(&mut S, s);
+    let mut eig = EigEngine::<T>::new(n);
+    eig.eigvals(&mut S).expect("Eigval error");
+    T::max(T::zero(), -eig.λ.minimum())
+}
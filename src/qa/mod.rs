@@ -0,0 +1,278 @@
+//! Verification of solver results, for use in tests of the solver
+//! and of downstream crates.
+//!
+//! [`verify_solution`] checks the optimality conditions of a solution
+//! against the original problem data, and [`assert_solution_close`]
+//! compares two solutions of the same problem.
+//!
+//! Requires the `testing` feature.
+
+#![allow(non_snake_case)]
+
+use crate::algebra::*;
+use crate::solver::{DefaultSolution, SolverStatus, SupportedConeT};
+use std::borrow::Cow;
+use std::fmt;
+
+mod membership;
+pub use membership::*;
+
+/// Data `(P,q,A,b,cones)` of a problem in the standard form
+///
+/// ```text
+/// min  ½xᵀPx + qᵀx
+/// s.t. Ax + s = b, s ∈ K
+/// ```
+///
+/// as passed to [`DefaultSolver::new`](crate::solver::DefaultSolver::new).
+/// `P` may be supplied as its upper triangle or as a full symmetric matrix.
+
+#[derive(Debug, Clone, Copy)]
+pub struct Problem<'a, T: FloatT = f64> {
+    pub P: &'a CscMatrix<T>,
+    pub q: &'a [T],
+    pub A: &'a CscMatrix<T>,
+    pub b: &'a [T],
+    pub cones: &'a [SupportedConeT<T>],
+}
+
+impl<'a, T> Problem<'a, T>
+where
+    T: FloatT,
+{
+    pub fn new(
+        P: &'a CscMatrix<T>,
+        q: &'a [T],
+        A: &'a CscMatrix<T>,
+        b: &'a [T],
+        cones: &'a [SupportedConeT<T>],
+    ) -> Self {
+        Self { P, q, A, b, cones }
+    }
+}
+
+/// An optimality condition not met by a solution.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// the equality `Ax + s = b` does not hold
+    PrimalResidual,
+    /// the stationarity condition `Px + q + Aᵀz = 0` does not hold
+    Stationarity,
+    /// the slacks `s` of a cone are not in the cone
+    PrimalCone { index: usize },
+    /// the duals `z` of a cone are not in the dual cone
+    DualCone { index: usize },
+    /// the complementarity condition `sᵀz = 0` does not hold
+    Complementarity,
+    /// the primal and dual objectives differ
+    DualityGap,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::PrimalResidual => write!(f, "Ax + s = b violated"),
+            Violation::Stationarity => write!(f, "Px + q + Aᵀz = 0 violated"),
+            Violation::PrimalCone { index } => write!(f, "s outside cone {}", index),
+            Violation::DualCone { index } => write!(f, "z outside dual cone {}", index),
+            Violation::Complementarity => write!(f, "sᵀz = 0 violated"),
+            Violation::DualityGap => write!(f, "duality gap too large"),
+        }
+    }
+}
+
+/// Measures of the optimality conditions of a solution, returned by
+/// [`verify_solution`].
+///
+/// Residuals are infinity norms in the units of the original problem.
+/// Cone violations are as computed by [`primal_cone_violation`] and
+/// [`dual_cone_violation`].
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationReport<T> {
+    /// `‖Ax + s - b‖∞`
+    pub primal_residual: T,
+    /// `‖Px + q + Aᵀz‖∞`
+    pub stationarity: T,
+    /// `|sᵀz|`
+    pub complementarity: T,
+    /// difference between the primal and dual objectives
+    pub gap: T,
+    /// violation of the primal cone constraint, by cone
+    pub primal_cone_violations: Vec<T>,
+    /// violation of the dual cone constraint, by cone
+    pub dual_cone_violations: Vec<T>,
+    /// conditions not met to within the tolerance
+    pub violations: Vec<Violation>,
+}
+
+impl<T> VerificationReport<T>
+where
+    T: FloatT,
+{
+    /// True if all optimality conditions are met.
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Panic with a description of the conditions not met, if any.
+    pub fn assert_ok(&self) {
+        assert!(self.is_ok(), "solution verification failed:\n{}", self);
+    }
+}
+
+impl<T> fmt::Display for VerificationReport<T>
+where
+    T: FloatT,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  primal residual = {:e}", self.primal_residual)?;
+        writeln!(f, "  stationarity    = {:e}", self.stationarity)?;
+        writeln!(f, "  complementarity = {:e}", self.complementarity)?;
+        writeln!(f, "  gap             = {:e}", self.gap)?;
+        for v in &self.violations {
+            writeln!(f, "  violation: {}", v)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the optimality conditions of a solution of `problem`, i.e.
+/// primal feasibility, dual feasibility, complementarity and a zero
+/// duality gap.
+///
+/// Each condition is met if its measure in the report is at most `tol`
+/// relative to the largest of the terms involved, or at most `tol` if
+/// these are smaller than one.  Only solutions with status
+/// [`Solved`](SolverStatus::Solved) or
+/// [`AlmostSolved`](SolverStatus::AlmostSolved) can be expected to
+/// pass, and infeasibility certificates are not checked.
+///
+/// Rows of `b` with infinite entries, i.e. absent bounds, are ignored.
+pub fn verify_solution<T: FloatT>(
+    problem: &Problem<T>,
+    solution: &DefaultSolution<T>,
+    tol: T,
+) -> VerificationReport<T> {
+    let Problem { P, q, A, b, cones } = *problem;
+    let (x, z, s) = (&solution.x, &solution.z, &solution.s);
+    let P: Cow<CscMatrix<T>> = if P.is_triu() {
+        Cow::Borrowed(P)
+    } else {
+        Cow::Owned(P.to_triu())
+    };
+    let finite: Vec<bool> = b.iter().map(|v| v.is_finite()).collect();
+    let masked = |v: &[T]| -> Vec<T> {
+        v.iter()
+            .zip(&finite)
+            .map(|(&v, &f)| if f { v } else { T::zero() })
+            .collect()
+    };
+    let (b, s, z) = (masked(b), masked(s), masked(z));
+    let exceeds = |v: T, scale: T| v.is_nan() || v > tol * T::max(T::one(), scale);
+    let mut violations = vec![];
+
+    // primal residual Ax + s - b
+    let mut Ax = vec![T::zero(); b.len()];
+    A.gemv(&mut Ax, x, T::one(), T::zero());
+    let mut r = masked(&Ax);
+    r.axpby(T::one(), &s, T::one())
+        .axpby(-T::one(), &b, T::one());
+    let primal_residual = r.norm_inf();
+    let scale = T::max(b.norm_inf(), T::max(masked(&Ax).norm_inf(), s.norm_inf()));
+    if exceeds(primal_residual, scale) {
+        violations.push(Violation::PrimalResidual);
+    }
+
+    // stationarity Px + q + Aᵀz
+    let mut Px = vec![T::zero(); q.len()];
+    P.sym().symv(&mut Px, x, T::one(), T::zero());
+    let mut Atz = vec![T::zero(); q.len()];
+    A.t().gemv(&mut Atz, &z, T::one(), T::zero());
+    let mut r = Px.clone();
+    r.axpby(T::one(), q, T::one())
+        .axpby(T::one(), &Atz, T::one());
+    let stationarity = r.norm_inf();
+    let scale = T::max(q.norm_inf(), T::max(Px.norm_inf(), Atz.norm_inf()));
+    if exceeds(stationarity, scale) {
+        violations.push(Violation::Stationarity);
+    }
+
+    // cone membership
+    let mut primal_cone_violations = vec![];
+    let mut dual_cone_violations = vec![];
+    let mut start = 0;
+    for (index, cone) in cones.iter().enumerate() {
+        let rng = start..start + cone.nvars();
+        start = rng.end;
+        let (si, zi) = (&s[rng.clone()], &z[rng]);
+        let vs = primal_cone_violation(cone, si);
+        let vz = dual_cone_violation(cone, zi);
+        if exceeds(vs, si.norm_inf()) {
+            violations.push(Violation::PrimalCone { index });
+        }
+        if exceeds(vz, zi.norm_inf()) {
+            violations.push(Violation::DualCone { index });
+        }
+        primal_cone_violations.push(vs);
+        dual_cone_violations.push(vz);
+    }
+
+    // complementarity and gap
+    let xPx = x.dot(&Px);
+    let half: T = (0.5).as_T();
+    let cost_primal = half * xPx + q.dot(x);
+    let cost_dual = -half * xPx - b.dot(&z);
+    let scale = T::max(cost_primal.abs(), cost_dual.abs());
+    let complementarity = s.dot(&z).abs();
+    if exceeds(complementarity, scale) {
+        violations.push(Violation::Complementarity);
+    }
+    let gap = (cost_primal - cost_dual).abs();
+    if exceeds(gap, scale) {
+        violations.push(Violation::DualityGap);
+    }
+
+    VerificationReport {
+        primal_residual,
+        stationarity,
+        complementarity,
+        gap,
+        primal_cone_violations,
+        dual_cone_violations,
+        violations,
+    }
+}
+
+/// Assert that two solutions of the same problem agree to within `tol`,
+/// relative to their magnitude with a minimum of one.
+///
+/// The statuses, objective values and primal solutions `x` are compared.
+/// Dual solutions are not compared, since they need not be unique.
+pub fn assert_solution_close<T: FloatT>(a: &DefaultSolution<T>, b: &DefaultSolution<T>, tol: T) {
+    assert_eq!(a.status, b.status, "solution statuses differ");
+    assert_eq!(a.x.len(), b.x.len(), "solution dimensions differ");
+
+    if !matches!(a.status, SolverStatus::Solved | SolverStatus::AlmostSolved) {
+        return;
+    }
+
+    let scale = T::max(T::one(), T::max(a.obj_val.abs(), b.obj_val.abs()));
+    let diff = (a.obj_val - b.obj_val).abs();
+    assert!(
+        diff <= tol * scale,
+        "objective values {:e} and {:e} differ by {:e}",
+        a.obj_val,
+        b.obj_val,
+        diff
+    );
+
+    let scale = T::max(T::one(), T::max(a.x.norm_inf(), b.x.norm_inf()));
+    let diff = a.x.norm_inf_diff(&b.x);
+    assert!(
+        diff <= tol * scale,
+        "primal solutions differ by {:e} in the infinity norm",
+        diff
+    );
+}
@@ -1,8 +1,8 @@
 #![allow(non_snake_case)]
 use crate::algebra::*;
 use core::cmp::{max, min};
+use core::iter::zip;
 use derive_builder::Builder;
-use std::iter::zip;
 use thiserror::Error;
 
 /// Error codes returnable from [`QDLDLFactorisation`](QDLDLFactorisation) factor operations
@@ -24,6 +24,7 @@ pub enum QDLDLError {
 /// Required settings for [`QDLDLFactorisation`](QDLDLFactorisation)
 
 #[derive(Builder, Debug, Clone)]
+#[cfg_attr(not(feature = "std"), builder(no_std))]
 pub struct QDLDLSettings<T: FloatT> {
     #[builder(default = "1.0")]
     amd_dense_scale: f64,
@@ -116,6 +117,67 @@ where
         ipermute(b, tmp, &self.perm);
     }
 
+    // Solves AX = B for the nrhs columns of B, stored contiguously
+    // in b.  Solves in place (X replaces B), making a single pass over
+    // the factors for each block of up to RHS_BLOCK columns.  Results
+    // are identical to solving each column separately.
+    pub fn solve_multiple(&mut self, b: &mut [T], nrhs: usize) {
+        // bomb if logical factorisation only
+        assert!(!self.is_logical);
+
+        // bomb if b is the wrong size
+        let n = self.D.len();
+        assert_eq!(b.len(), n * nrhs);
+
+        let mut b = b;
+        while !b.is_empty() {
+            let cols = b.len() / n;
+            let (block, rest) = if cols >= RHS_BLOCK {
+                b.split_at_mut(RHS_BLOCK * n)
+            } else if cols >= 2 {
+                b.split_at_mut(2 * n)
+            } else {
+                b.split_at_mut(n)
+            };
+            match block.len() / n {
+                RHS_BLOCK => self._solve_block::<RHS_BLOCK>(block),
+                2 => self._solve_block::<2>(block),
+                _ => self.solve(block),
+            }
+            b = rest;
+        }
+    }
+
+    fn _solve_block<const K: usize>(&mut self, b: &mut [T]) {
+        let n = self.D.len();
+
+        // permute the columns of b into a row-wise block, so
+        // that the K values for each row are adjacent
+        let tmp = &mut self.workspace.fwork_block;
+        tmp.resize(n * K, T::zero());
+        for (i, &p) in self.perm.iter().enumerate() {
+            for k in 0..K {
+                tmp[i * K + k] = b[k * n + p];
+            }
+        }
+
+        _lsolve_block_unsafe::<T, K>(&self.L.colptr, &self.L.rowval, &self.L.nzval, tmp);
+        _dltsolve_block_unsafe::<T, K>(
+            &self.L.colptr,
+            &self.L.rowval,
+            &self.L.nzval,
+            &self.Dinv,
+            tmp,
+        );
+
+        // inverse permutation back to the columns of b
+        for (i, &p) in self.perm.iter().enumerate() {
+            for k in 0..K {
+                b[k * n + p] = tmp[i * K + k];
+            }
+        }
+    }
+
     pub fn update_values(&mut self, indices: &[usize], values: &[T]) {
         let nzval = &mut self.workspace.triuA.nzval; // post perm internal data
         let AtoPAPt = &self.workspace.AtoPAPt; //mapping from input matrix entries to triuA
@@ -161,6 +223,32 @@ where
     }
 }
 
+/// Number of nonzeros in the factor `L` of the factorization of `A` made by
+/// [`QDLDLFactorisation::new`] with an AMD ordering, computed from the
+/// sparsity pattern of `A` only.   No memory is allocated for the factors.
+pub fn symbolic_factor_nnz<T: FloatT>(
+    A: &CscMatrix<T>,
+    amd_dense_scale: f64,
+) -> Result<usize, QDLDLError> {
+    check_structure(A)?;
+    let n = A.nrows();
+    let (_, iperm) = get_amd_ordering(A, amd_dense_scale);
+    let (PAPt, _) = permute_symmetric(A, &iperm);
+
+    let mut work = vec![0; n];
+    let mut Lnz = vec![0; n];
+    let mut etree = vec![0; n];
+    _etree(
+        n,
+        &PAPt.colptr,
+        &PAPt.rowval,
+        &mut work,
+        &mut Lnz,
+        &mut etree,
+    )?;
+    Ok(Lnz.iter().sum())
+}
+
 fn check_structure<T: FloatT>(A: &CscMatrix<T>) -> Result<(), QDLDLError> {
     if !A.is_square() {
         return Err(QDLDLError::IncompatibleDimension);
@@ -254,6 +342,10 @@ struct QDLDLWorkspace<T> {
     bwork: Vec<bool>,
     fwork: Vec<T>,
 
+    // row-wise workspace for blocked multiple RHS solves,
+    // allocated on first use
+    fwork_block: Vec<T>,
+
     // number of positive values in D
     positive_inertia: usize,
 
@@ -318,6 +410,7 @@ where
             iwork,
             bwork,
             fwork,
+            fwork_block: Vec::new(),
             positive_inertia,
             triuA,
             AtoPAPt,
@@ -702,6 +795,70 @@ fn _dltsolve_unsafe<T: FloatT>(Lp: &[usize], Li: &[usize], Lx: &[T], Dinv: &[T],
     }
 }
 
+// -------------------------------------
+// Blocked versions of L\x and D(L+I)' \ x for K right hand sides,
+// held row-wise in x so that x[i*K + k] is the ith entry of the
+// kth RHS.   Each factor entry is loaded once for all K columns,
+// and each column sees the same operations in the same order as
+// in the single RHS versions above.
+//
+// Safety : as for the single RHS unchecked versions, with the
+// length of x equal to K times the factor dimension
+// -------------------------------------
+
+// number of columns solved together in multiple RHS solves
+const RHS_BLOCK: usize = 4;
+
+// Solves (L+I)X = B, with X replacing B.  Unchecked version
+fn _lsolve_block_unsafe<T: FloatT, const K: usize>(
+    Lp: &[usize],
+    Li: &[usize],
+    Lx: &[T],
+    x: &mut [T],
+) {
+    unsafe {
+        for i in 0..(x.len() / K) {
+            let xi: [T; K] = core::array::from_fn(|k| *x.get_unchecked(i * K + k));
+            let f = *Lp.get_unchecked(i);
+            let l = *Lp.get_unchecked(i + 1);
+            for (&Lxj, &Lij) in zip(&Lx[f..l], &Li[f..l]) {
+                for (k, &xik) in xi.iter().enumerate() {
+                    *(x.get_unchecked_mut(Lij * K + k)) -= Lxj * xik;
+                }
+            }
+        }
+    }
+}
+
+// Solves D(L+I)'X = B, with X replacing B.  Unchecked version.
+fn _dltsolve_block_unsafe<T: FloatT, const K: usize>(
+    Lp: &[usize],
+    Li: &[usize],
+    Lx: &[T],
+    Dinv: &[T],
+    x: &mut [T],
+) {
+    unsafe {
+        for i in (0..(x.len() / K)).rev() {
+            let mut s = [T::zero(); K];
+            let f = *Lp.get_unchecked(i);
+            let l = *Lp.get_unchecked(i + 1);
+            for (&Lxj, &Lij) in zip(&Lx[f..l], &Li[f..l]) {
+                for (k, sk) in s.iter_mut().enumerate() {
+                    *sk += Lxj * (*x.get_unchecked(Lij * K + k));
+                }
+            }
+
+            let d = *Dinv.get_unchecked(i);
+            for (k, &sk) in s.iter().enumerate() {
+                let xi = x.get_unchecked_mut(i * K + k);
+                *xi *= d;
+                *xi -= sk;
+            }
+        }
+    }
+}
+
 // Solves Ax = b where A has given LDL factors, with x replacing b
 fn _solve<T: FloatT>(Lp: &[usize], Li: &[usize], Lx: &[T], Dinv: &[T], b: &mut [T]) {
     // We call the `unsafe`d version of the forward and backward substitution
@@ -738,14 +895,14 @@ fn _invperm(p: &[usize]) -> Result<Vec<usize>, QDLDLError> {
 // in both cases for safety
 
 pub(crate) fn permute<T: Copy>(x: &mut [T], b: &[T], p: &[usize]) {
-    debug_assert!(*p.iter().max().unwrap_or(&0) < x.len());
+    debug_assert!(p.iter().all(|&i| i < x.len()));
     unsafe {
         zip(p, x).for_each(|(p, x)| *x = *b.get_unchecked(*p));
     }
 }
 
 pub(crate) fn ipermute<T: Copy>(x: &mut [T], b: &[T], p: &[usize]) {
-    debug_assert!(*p.iter().max().unwrap_or(&0) < x.len());
+    debug_assert!(p.iter().all(|&i| i < x.len()));
     unsafe {
         zip(p, b).for_each(|(p, b)| *x.get_unchecked_mut(*p) = *b);
     }
@@ -853,6 +1010,7 @@ fn _permute_symmetric_inner<T: FloatT>(
     }
 }
 
+#[cfg(feature = "std")]
 pub(crate) fn get_amd_ordering<T: FloatT>(
     A: &CscMatrix<T>,
     amd_dense_scale: f64,
@@ -867,6 +1025,17 @@ pub(crate) fn get_amd_ordering<T: FloatT>(
     (perm, iperm)
 }
 
+// the amd crate needs std, so without it the natural
+// ordering is used unless a permutation is supplied
+#[cfg(not(feature = "std"))]
+pub(crate) fn get_amd_ordering<T: FloatT>(
+    A: &CscMatrix<T>,
+    _amd_dense_scale: f64,
+) -> (Vec<usize>, Vec<usize>) {
+    let perm: Vec<usize> = (0..A.nrows()).collect();
+    (perm.clone(), perm)
+}
+
 //configure tests of internals
 #[path = "test.rs"]
 #[cfg(test)]
@@ -129,6 +129,24 @@ fn test_amd() {
     assert_eq!(iperm, [1, 2, 3, 0]);
 }
 
+#[test]
+fn test_symbolic_factor_nnz() {
+    let A = test_matrix_4x4();
+    let nnz = symbolic_factor_nnz(&A, 1.5).unwrap();
+
+    let opts = QDLDLSettingsBuilder::default()
+        .amd_dense_scale(1.5)
+        .build()
+        .unwrap();
+    let factors = QDLDLFactorisation::new(&A, Some(opts)).unwrap();
+    assert_eq!(nnz, factors.L.nnz());
+
+    //fails on a non-triu input
+    let mut B = A.clone();
+    B.rowval[0] = 3;
+    assert!(symbolic_factor_nnz(&B, 1.5).is_err());
+}
+
 #[test]
 fn test_permute_symmetric() {
     //no permutation at all
@@ -230,6 +248,30 @@ fn test_solve_basic() {
     assert!(inf_norm_diff(&x, &b) <= 1e-8);
 }
 
+#[test]
+fn test_solve_multiple() {
+    let A = test_matrix_4x4();
+    let opts = QDLDLSettingsBuilder::<f64>::default()
+        .perm(vec![3, 0, 2, 1])
+        .build()
+        .unwrap();
+    let mut factors = QDLDLFactorisation::new(&A, Some(opts)).unwrap();
+
+    // every block width, plus a remainder column, for 1 to 7 RHS
+    for nrhs in 1..=7 {
+        let B: Vec<f64> = (0..4 * nrhs).map(|i| ((i + 1) as f64).sin()).collect();
+        let mut X = B.clone();
+        factors.solve_multiple(&mut X, nrhs);
+
+        // identical to separate solves of each column
+        for (x, b) in zip(X.chunks(4), B.chunks(4)) {
+            let mut y = b.to_vec();
+            factors.solve(&mut y);
+            assert_eq!(x, y);
+        }
+    }
+}
+
 #[test]
 #[should_panic]
 fn test_solve_logical() {
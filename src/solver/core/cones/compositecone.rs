@@ -1,8 +1,12 @@
 use super::*;
-use crate::algebra::triangular_number;
-use std::collections::HashMap;
-use std::iter::zip;
-use std::ops::Range;
+use crate::algebra::*;
+use alloc::collections::BTreeMap;
+use core::iter::zip;
+use core::ops::Range;
+use thiserror::Error;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 // -------------------------------------
 // default composite cone type
@@ -12,7 +16,7 @@ pub struct CompositeCone<T: FloatT = f64> {
     cones: Vec<SupportedCone<T>>,
 
     //Type count for each cone type
-    pub(crate) type_counts: HashMap<SupportedConeTag, usize>,
+    pub(crate) type_counts: [usize; SupportedConeTag::COUNT],
 
     //overall size of the composite cone
     pub(crate) numel: usize,
@@ -25,8 +29,25 @@ pub struct CompositeCone<T: FloatT = f64> {
     //associated with each cone
     pub(crate) rng_blocks: Vec<Range<usize>>,
 
+    //ranges of the Hs blocks of cones whose scaling is not
+    //constant, with adjacent blocks merged
+    pub(crate) rng_blocks_variable: Vec<Range<usize>>,
+
+    // true once cones with constant scaling have been updated,
+    // and whether get_Hs may then leave their blocks unchanged
+    constant_scaling_set: bool,
+    skip_constant_Hs: bool,
+
     // the flag for symmetric cone check
     _is_symmetric: bool,
+
+    // user supplied Hs blocks, by cone index, and whether they
+    // are applied in place of those computed by the cones
+    initial_Hs: Vec<(usize, Vec<T>)>,
+    initial_Hs_active: bool,
+
+    // the cone that limited the most recent step length, if any
+    binding_cone: Option<usize>,
 }
 
 impl<T> CompositeCone<T>
@@ -40,10 +61,7 @@ where
         let mut cones: Vec<SupportedCone<T>> = Vec::with_capacity(ncones);
 
         // Count for the number of each cone type, indexed by SupportedConeTag
-        // NB: ideally we could fix max capacity here,  but Enum::variant_count is not
-        // yet a stable feature.  Capacity should be number of SupportedCone variants.
-        // See: https://github.com/rust-lang/rust/issues/73662
-        let mut type_counts = HashMap::new();
+        let mut type_counts = [0; SupportedConeTag::COUNT];
 
         // assumed symmetric to start
         let mut _is_symmetric = true;
@@ -57,7 +75,7 @@ where
             _is_symmetric = _is_symmetric && cone.is_symmetric();
 
             //increment type counts
-            *type_counts.entry(cone.as_tag()).or_insert(0) += 1;
+            type_counts[cone.as_tag() as usize] += 1;
 
             cones.push(cone);
         }
@@ -72,6 +90,7 @@ where
 
         let rng_cones = make_rng_cones(&cones);
         let rng_blocks = make_rng_blocks(&cones);
+        let rng_blocks_variable = make_rng_blocks_variable(&cones, &rng_blocks);
 
         Self {
             cones,
@@ -81,11 +100,69 @@ where
             degree,
             rng_cones,
             rng_blocks,
+            rng_blocks_variable,
+            constant_scaling_set: false,
+            skip_constant_Hs: false,
             _is_symmetric,
+            initial_Hs: Vec::new(),
+            initial_Hs_active: false,
+            binding_cone: None,
+        }
+    }
+}
+
+impl<T> CompositeCone<T>
+where
+    T: FloatT,
+{
+    // Approximate distance of (z,s) from the cone boundary, taken as the
+    // worst centrality over all cones relative to the mean complementarity
+    // ⟨s,z⟩/ν.  The centrality of a cone is ⟨sᵢ,zᵢ⟩/νᵢ, or min(zᵢ∘sᵢ) for a
+    // nonnegative cone.  Returns a value in [0,1], or None if no cone has
+    // positive degree or if the complementarity is not positive.
+    fn distance_to_boundary(&self, z: &[T], s: &[T]) -> Option<T> {
+        let mut worst = T::infinity();
+        let mut total = T::zero();
+        let mut degree = 0;
+
+        for (cone, rng) in zip(&self.cones, &self.rng_cones) {
+            let νi = cone.degree();
+            if νi == 0 {
+                continue;
+            }
+            let (zi, si) = (&z[rng.clone()], &s[rng.clone()]);
+            let dot = zi.dot(si);
+            let centrality = match cone {
+                SupportedCone::NonnegativeCone(_) => {
+                    zip(zi, si).fold(T::infinity(), |m, (&z, &s)| T::min(m, z * s))
+                }
+                _ => dot / νi.as_T(),
+            };
+            worst = T::min(worst, centrality);
+            total += dot;
+            degree += νi;
         }
+
+        if degree == 0 || total <= T::zero() {
+            return None;
+        }
+        let mean = total / degree.as_T();
+        Some((worst / mean).clip(T::zero(), T::one()))
     }
 }
 
+/// Step length back-off for problems with nonsymmetric cones, as a function
+/// of the distance of the current iterate from the cone boundary.  Returns a
+/// factor in `[0.9, 0.999]`, approaching `0.9` near the boundary.
+pub(crate) fn adaptive_backoff_factor<T: FloatT>(distance_to_boundary: T) -> T {
+    // iterates at least this far from the boundary are considered well centered
+    let dcenter: T = (0.1_f64).as_T();
+    let (lo, hi): (T, T) = ((0.9_f64).as_T(), (0.999_f64).as_T());
+
+    let t = (distance_to_boundary / dcenter).clip(T::zero(), T::one());
+    lo + (hi - lo) * t
+}
+
 fn make_rng_cones<T>(cones: &[SupportedCone<T>]) -> Vec<Range<usize>>
 where
     T: FloatT,
@@ -110,16 +187,17 @@ where
     let mut rngs = Vec::with_capacity(cones.len());
 
     if !cones.is_empty() {
-        let mut start = 0;
+        let mut start = 0usize;
         for cone in cones {
             let nvars = cone.numel();
-            let stop = start + {
-                if cone.Hs_is_diagonal() {
-                    nvars
-                } else {
-                    triangular_number(nvars)
-                }
+            let nHs = if cone.Hs_is_diagonal() {
+                Some(nvars)
+            } else {
+                checked_triangular_number(nvars)
             };
+            let stop = nHs
+                .and_then(|nHs| start.checked_add(nHs))
+                .expect("size of the Hs blocks overflows usize");
             rngs.push(start..stop);
             start = stop;
         }
@@ -127,6 +205,27 @@ where
     rngs
 }
 
+fn make_rng_blocks_variable<T>(
+    cones: &[SupportedCone<T>],
+    rng_blocks: &[Range<usize>],
+) -> Vec<Range<usize>>
+where
+    T: FloatT,
+{
+    let mut rngs: Vec<Range<usize>> = Vec::new();
+
+    for (cone, rng) in zip(cones, rng_blocks) {
+        if cone.scaling_is_constant() || rng.is_empty() {
+            continue;
+        }
+        match rngs.last_mut() {
+            Some(last) if last.end == rng.start => last.end = rng.end,
+            _ => rngs.push(rng.clone()),
+        }
+    }
+    rngs
+}
+
 impl<T> CompositeCone<T>
 where
     T: FloatT,
@@ -137,19 +236,264 @@ where
     pub fn is_empty(&self) -> bool {
         self.cones.is_empty()
     }
-    pub fn iter(&self) -> std::slice::Iter<'_, SupportedCone<T>> {
+    pub fn iter(&self) -> core::slice::Iter<'_, SupportedCone<T>> {
         self.cones.iter()
     }
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, SupportedCone<T>> {
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, SupportedCone<T>> {
         self.cones.iter_mut()
     }
     pub(crate) fn get_type_count(&self, tag: SupportedConeTag) -> usize {
-        if self.type_counts.contains_key(&tag) {
-            self.type_counts[&tag]
+        self.type_counts[tag as usize]
+    }
+
+    // true if the last call to get_Hs left the blocks of cones
+    // with constant scaling as written by the call before it
+    pub(crate) fn skips_constant_Hs(&self) -> bool {
+        self.skip_constant_Hs
+    }
+
+    // true if get_Hs returns user supplied blocks in place of
+    // those from the most recent scaling update
+    pub(crate) fn uses_initial_Hs(&self) -> bool {
+        self.initial_Hs_active
+    }
+
+    /// Number of cones of each type present, sorted by cone type name.
+    pub fn type_count_sorted(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+        for cone in self.cones.iter() {
+            *counts.entry(cone.as_tag().as_str()).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    /// One line summary of the cones by type and dimension, e.g.
+    /// `"1×ExponentialCone, 2×SecondOrderCone(dim=10), 3×ZeroCone(dim=5)"`.
+    ///
+    /// Cone types are listed as in [`type_count_sorted`](Self::type_count_sorted),
+    /// and cones of the same type by increasing dimension.  Dimensions are
+    /// omitted for the 3 dimensional exponential and power cones.
+    pub fn cone_type_summary(&self) -> String {
+        let mut counts: BTreeMap<(&'static str, usize), usize> = BTreeMap::new();
+        for cone in self.cones.iter() {
+            *counts
+                .entry((cone.as_tag().as_str(), cone.numel()))
+                .or_insert(0) += 1;
+        }
+
+        let terms: Vec<String> = counts
+            .into_iter()
+            .map(|((name, dim), count)| match name {
+                "ExponentialCone" | "PowerCone" => format!("{}×{}", count, name),
+                _ => format!("{}×{}(dim={})", count, name, dim),
+            })
+            .collect();
+        terms.join(", ")
+    }
+}
+
+/// Error type returned by [`CompositeCone::verify_scaling_consistency`]
+/// and when reading or setting the scaling of individual cones.
+#[derive(Error, Debug)]
+pub enum ScalingError {
+    #[error("cone index {0} is out of range")]
+    BadConeIndex(usize),
+    #[error("scaling data for cone {index} has length {found}, expected {expected}")]
+    BadLength {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("scaling data for cone {0} is not finite")]
+    NotFinite(usize),
+    #[error("scaling cannot be set for cone {index} ({cone}) with a sparse expansion")]
+    SparseExpandable { index: usize, cone: &'static str },
+    #[error("scaling update failed for cone {index} ({cone})")]
+    UpdateFailed { index: usize, cone: &'static str },
+    #[error("inconsistent Hs for cone {index} ({cone}): get_Hs and mul_Hs differ by {error:e}")]
+    InconsistentHs {
+        index: usize,
+        cone: &'static str,
+        error: f64,
+    },
+}
+
+impl<T> CompositeCone<T>
+where
+    T: FloatT,
+{
+    /// Debugging utility for cone implementations.   Updates the
+    /// scaling of each cone at the point `(s,z)`, and checks that the
+    /// Hessian block returned by `get_Hs` agrees with `mul_Hs` applied
+    /// to each unit vector, to within `tol` relative to the largest
+    /// entry of the block.
+    ///
+    /// For sparse expandable cones, the low rank terms omitted from
+    /// `get_Hs` are added back before comparison.
+    pub fn verify_scaling_consistency(
+        &mut self,
+        s: &[T],
+        z: &[T],
+        tol: T,
+    ) -> Result<(), ScalingError> {
+        assert_eq!(s.len(), self.numel);
+        assert_eq!(z.len(), self.numel);
+
+        let μ = s.dot(z) / T::max(T::one(), self.degree.as_T());
+        let strategy = if self.allows_primal_dual_scaling() {
+            ScalingStrategy::PrimalDual
         } else {
-            0
+            ScalingStrategy::Dual
+        };
+
+        for (index, (cone, rng)) in zip(&mut self.cones, &self.rng_cones).enumerate() {
+            let name = cone.as_tag().as_str();
+            let (si, zi) = (&s[rng.clone()], &z[rng.clone()]);
+
+            if !cone.update_scaling(si, zi, μ, strategy) {
+                return Err(ScalingError::UpdateFailed { index, cone: name });
+            }
+
+            let H = _dense_Hs(cone);
+
+            let n = rng.len();
+            let (mut x, mut y, mut work) =
+                (vec![T::zero(); n], vec![T::zero(); n], vec![T::zero(); n]);
+            let mut error = T::zero();
+            for j in 0..n {
+                x[j] = T::one();
+                cone.mul_Hs(&mut y, &x, &mut work);
+                x[j] = T::zero();
+                for (i, &yi) in y.iter().enumerate() {
+                    error = T::max(error, T::abs(yi - H[i + j * n]));
+                }
+            }
+
+            let scale = T::max(T::one(), H.norm_inf());
+            if error > tol * scale {
+                return Err(ScalingError::InconsistentHs {
+                    index,
+                    cone: name,
+                    error: error.to_f64().unwrap(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> CompositeCone<T>
+where
+    T: FloatT,
+{
+    /// The Hessian block of cone `index` at its current scaling, in the
+    /// format of `get_Hs`, i.e. the diagonal for cones with diagonal
+    /// blocks, and otherwise the packed upper triangle by columns (the
+    /// lower triangle by rows).
+    pub fn get_Hs_block(&self, index: usize) -> Result<Vec<T>, ScalingError> {
+        let cone = self
+            .cones
+            .get(index)
+            .ok_or(ScalingError::BadConeIndex(index))?;
+        let mut block = vec![T::zero(); self.rng_blocks[index].len()];
+        cone.get_Hs(&mut block);
+        Ok(block)
+    }
+
+    /// Use `block` as the Hessian block of cone `index`, in the format
+    /// of [`get_Hs_block`](Self::get_Hs_block), in place of the block
+    /// computed by the next scaling update.   Later updates are computed
+    /// by the cone as usual.
+    ///
+    /// Cones that are assembled into the KKT system with a sparse
+    /// expansion, e.g. large second order cones, are not supported.
+    pub fn set_initial_Hs_block(&mut self, index: usize, block: &[T]) -> Result<(), ScalingError> {
+        let cone = self
+            .cones
+            .get(index)
+            .ok_or(ScalingError::BadConeIndex(index))?;
+        if cone.is_sparse_expandable() {
+            let cone = cone.as_tag().as_str();
+            return Err(ScalingError::SparseExpandable { index, cone });
+        }
+        let expected = self.rng_blocks[index].len();
+        if block.len() != expected {
+            return Err(ScalingError::BadLength {
+                index,
+                expected,
+                found: block.len(),
+            });
+        }
+        if !block.is_finite() {
+            return Err(ScalingError::NotFinite(index));
+        }
+
+        self.initial_Hs.retain(|(i, _)| *i != index);
+        self.initial_Hs.push((index, block.to_vec()));
+        self.initial_Hs_active = false;
+        Ok(())
+    }
+}
+
+// the full Hessian block of a cone as a dense column major matrix,
+// assembled from get_Hs and the low rank terms of any sparse expansion
+
+fn _dense_Hs<T: FloatT>(cone: &SupportedCone<T>) -> Vec<T> {
+    let n = cone.numel();
+    let mut H = vec![T::zero(); n * n];
+
+    if cone.Hs_is_diagonal() {
+        let mut Hsblock = vec![T::zero(); n];
+        cone.get_Hs(&mut Hsblock);
+        for (i, &v) in Hsblock.iter().enumerate() {
+            H[i + i * n] = v;
+        }
+    } else {
+        // packed upper triangle, by columns
+        let mut Hsblock = vec![T::zero(); triangular_number(n)];
+        cone.get_Hs(&mut Hsblock);
+        let mut k = 0;
+        for j in 0..n {
+            for i in 0..=j {
+                H[i + j * n] = Hsblock[k];
+                H[j + i * n] = Hsblock[k];
+                k += 1;
+            }
+        }
+    }
+
+    let mut add_outer = |a: &[T], c: T| {
+        for j in 0..n {
+            for i in 0..n {
+                H[i + j * n] += c * a[i] * a[j];
+            }
+        }
+    };
+
+    match cone {
+        SupportedCone::SecondOrderCone(c) => {
+            // Hs = η²(D + uuᵀ - vvᵀ)
+            if let Some(sparse_data) = &c.sparse_data {
+                let (_, η) = c.Hs_factors();
+                add_outer(&sparse_data.u, η * η);
+                add_outer(&sparse_data.v, -η * η);
+            }
+        }
+        SupportedCone::GenPowerCone(c) => {
+            // Hs = μ(D + ppᵀ - qqᵀ - rrᵀ), with q and r
+            // supported on the first and second blocks
+            let data = &c.data;
+            let mut q = vec![T::zero(); n];
+            let mut r = vec![T::zero(); n];
+            q[..c.dim1()].copy_from(&data.q);
+            r[c.dim1()..].copy_from(&data.r);
+            add_outer(&data.p, data.μ);
+            add_outer(&q, -data.μ);
+            add_outer(&r, -data.μ);
         }
+        _ => {}
     }
+    H
 }
 
 impl<T> Cone<T> for CompositeCone<T>
@@ -180,6 +524,10 @@ where
             .all(|cone| cone.allows_primal_dual_scaling())
     }
 
+    fn scaling_differs(&self, a: ScalingStrategy, b: ScalingStrategy) -> bool {
+        self.cones.iter().any(|cone| cone.scaling_differs(a, b))
+    }
+
     fn rectify_equilibration(&self, δ: &mut [T], e: &[T]) -> bool {
         let mut any_changed = false;
 
@@ -217,10 +565,23 @@ where
         }
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        zip(&mut self.cones, &self.rng_cones)
+            .all(|(cone, rng)| cone.is_in_cone_interior(&s[rng.clone()]))
+    }
+
     fn set_identity_scaling(&mut self) {
         for cone in self.iter_mut() {
             cone.set_identity_scaling();
         }
+        // discard user supplied Hs blocks left over from a
+        // solve that terminated before they were replaced
+        if self.initial_Hs_active {
+            self.initial_Hs.clear();
+            self.initial_Hs_active = false;
+        }
+        self.constant_scaling_set = false;
+        self.skip_constant_Hs = false;
     }
 
     fn update_scaling(
@@ -230,18 +591,57 @@ where
         μ: T,
         scaling_strategy: ScalingStrategy,
     ) -> bool {
-        let mut is_scaling_success;
-        for (cone, rng) in zip(&mut self.cones, &self.rng_cones) {
+        // user supplied Hs blocks replace those from the first
+        // scaling update after they are set, and are then discarded
+        let was_initial_Hs_active = self.initial_Hs_active;
+        if self.initial_Hs_active {
+            self.initial_Hs.clear();
+            self.initial_Hs_active = false;
+        } else if !self.initial_Hs.is_empty() {
+            self.initial_Hs_active = true;
+        }
+
+        // cones with constant scaling are only updated once
+        let skip_constant = self.constant_scaling_set;
+        self.skip_constant_Hs = false;
+
+        let update = |cone: &mut SupportedCone<T>, rng: &Range<usize>| {
+            if skip_constant && cone.scaling_is_constant() {
+                return true;
+            }
             let si = &s[rng.clone()];
             let zi = &z[rng.clone()];
-            is_scaling_success = cone.update_scaling(si, zi, μ, scaling_strategy);
-            if !is_scaling_success {
-                return false;
-            }
+            cone.update_scaling(si, zi, μ, scaling_strategy)
+        };
+
+        #[cfg(feature = "parallel")]
+        let is_scaling_success = if _use_parallel_cones(self.cones.len()) {
+            self.cones
+                .par_iter_mut()
+                .zip(&self.rng_cones)
+                .all(|(cone, rng)| update(cone, rng))
+        } else {
+            zip(&mut self.cones, &self.rng_cones).all(|(cone, rng)| update(cone, rng))
+        };
+        #[cfg(not(feature = "parallel"))]
+        let is_scaling_success =
+            zip(&mut self.cones, &self.rng_cones).all(|(cone, rng)| update(cone, rng));
+
+        if !is_scaling_success {
+            return false;
         }
+
+        // Hs blocks for constant cones from the previous get_Hs can
+        // be kept, unless they were replaced by user supplied blocks
+        self.constant_scaling_set = true;
+        self.skip_constant_Hs = skip_constant && !was_initial_Hs_active;
         true
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        self.cones.iter().all(|cone| cone.scaling_is_constant())
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         //This function should probably never be called since
         //we only us it to interrogate the blocks, but we can
@@ -249,11 +649,22 @@ where
         self.cones.iter().all(|cone| cone.Hs_is_diagonal())
     }
 
+    // After the first scaling update, blocks for cones with constant
+    // scaling are not written, and Hsblock should hold the values from
+    // the previous call.   See skips_constant_Hs
     #[allow(non_snake_case)]
     fn get_Hs(&self, Hsblock: &mut [T]) {
         for (cone, rng) in zip(&self.cones, &self.rng_blocks) {
+            if self.skip_constant_Hs && cone.scaling_is_constant() {
+                continue;
+            }
             cone.get_Hs(&mut Hsblock[rng.clone()]);
         }
+        if self.initial_Hs_active {
+            for (index, block) in &self.initial_Hs {
+                Hsblock[self.rng_blocks[*index].clone()].copy_from(block);
+            }
+        }
     }
 
     fn mul_Hs(&mut self, y: &mut [T], x: &[T], work: &mut [T]) {
@@ -262,6 +673,27 @@ where
         }
     }
 
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        //scaling factors are only available blockwise
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, x: &mut [T]) {
+        for (cone, rng) in zip(&mut self.cones, &self.rng_cones) {
+            cone.apply_W(&mut x[rng.clone()]);
+        }
+    }
+
+    fn apply_W_inv(&mut self, x: &mut [T]) {
+        for (cone, rng) in zip(&mut self.cones, &self.rng_cones) {
+            cone.apply_W_inv(&mut x[rng.clone()]);
+        }
+    }
+
     fn affine_ds(&self, ds: &mut [T], s: &[T]) {
         for (cone, rng) in zip(&self.cones, &self.rng_cones) {
             let dsi = &mut ds[rng.clone()];
@@ -279,6 +711,22 @@ where
         // nonsymmetric cones modify their internal state when
         // computing the ds_shift
 
+        #[cfg(feature = "parallel")]
+        if _use_parallel_cones(self.cones.len()) {
+            let shift = _split_at_ranges(shift, &self.rng_cones);
+            let step_z = _split_at_ranges(step_z, &self.rng_cones);
+            let step_s = _split_at_ranges(step_s, &self.rng_cones);
+            self.cones
+                .par_iter_mut()
+                .zip(shift)
+                .zip(step_z)
+                .zip(step_s)
+                .for_each(|(((cone, shifti), step_zi), step_si)| {
+                    cone.combined_ds_shift(shifti, step_zi, step_si, σμ);
+                });
+            return;
+        }
+
         for (cone, rng) in zip(&mut self.cones, &self.rng_cones) {
             let shifti = &mut shift[rng.clone()];
             let step_zi = &mut step_z[rng.clone()];
@@ -309,36 +757,103 @@ where
         let mut α = αmax;
         let all_symmetric = self.is_symmetric();
 
-        let mut innerfcn = |α: T, symcond: bool| -> T {
+        // back-off from full steps for nonsymmetric cones.  See below.
+        let backoff = {
+            if settings.adaptive_step_backoff && !all_symmetric {
+                self.distance_to_boundary(z, s)
+            } else {
+                None
+            }
+        }
+        .map_or(settings.step_backoff_factor, adaptive_backoff_factor);
+        let backoff = T::min(settings.max_step_fraction, backoff);
+
+        // the index of the cone giving the smallest step is kept,
+        // and cleared if the backoff is smaller still
+        let mut binding = None;
+
+        let cone_step = |cone: &mut SupportedCone<T>, rng: &Range<usize>, α: T| -> T {
+            let (dzi, dsi) = (&dz[rng.clone()], &ds[rng.clone()]);
+            let (zi, si) = (&z[rng.clone()], &s[rng.clone()]);
+            let (nextαz, nextαs) = cone.step_length(dzi, dsi, zi, si, settings, α);
+            T::min(nextαz, nextαs)
+        };
+
+        let mut innerfcn = |α: T, symcond: bool, binding: &mut Option<usize>| -> T {
             let mut α = α;
-            for (cone, rng) in zip(&mut self.cones, &self.rng_cones) {
+
+            // in parallel, every cone starts from the same step
+            #[cfg(feature = "parallel")]
+            if _use_parallel_cones(self.cones.len()) {
+                let steps: Vec<Option<T>> = self
+                    .cones
+                    .par_iter_mut()
+                    .zip(&self.rng_cones)
+                    .map(|(cone, rng)| {
+                        (cone.is_symmetric() != symcond).then(|| cone_step(cone, rng, α))
+                    })
+                    .collect();
+                for (i, nextα) in steps.into_iter().enumerate() {
+                    if let Some(nextα) = nextα.filter(|&nextα| nextα < α) {
+                        α = nextα;
+                        *binding = Some(i);
+                    }
+                }
+                return α;
+            }
+
+            for (i, (cone, rng)) in zip(&mut self.cones, &self.rng_cones).enumerate() {
                 if cone.is_symmetric() == symcond {
                     continue;
                 }
-                let (dzi, dsi) = (&dz[rng.clone()], &ds[rng.clone()]);
-                let (zi, si) = (&z[rng.clone()], &s[rng.clone()]);
-                let (nextαz, nextαs) = cone.step_length(dzi, dsi, zi, si, settings, α);
-                α = T::min(α, T::min(nextαz, nextαs));
+                let nextα = cone_step(cone, rng, α);
+                if nextα < α {
+                    α = nextα;
+                    *binding = Some(i);
+                }
             }
             α
         };
 
         // Force symmetric cones first.
-        α = innerfcn(α, true);
+        α = innerfcn(α, true, &mut binding);
 
         // if we have any nonsymmetric cones, then back off from full steps slightly
         // so that centrality checks and logarithms don't fail right at the boundaries
-        if !all_symmetric {
-            α = T::min(settings.max_step_fraction, α);
+        if !all_symmetric && backoff < α {
+            α = backoff;
+            binding = None;
         }
 
         // Force asymmetric cones last.
-        α = innerfcn(α, false);
+        α = innerfcn(α, false, &mut binding);
 
+        self.binding_cone = binding;
         (α, α)
     }
 
+    fn binding_cone(&self) -> Option<usize> {
+        self.binding_cone
+    }
+
     fn compute_barrier(&mut self, z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T {
+        // the barriers of the cones are summed in order, so
+        // that the result doesn't depend on the thread count
+        #[cfg(feature = "parallel")]
+        if _use_parallel_cones(self.cones.len()) {
+            let barriers: Vec<T> = self
+                .cones
+                .par_iter_mut()
+                .zip(&self.rng_cones)
+                .map(|(cone, rng)| {
+                    let (zi, si) = (&z[rng.clone()], &s[rng.clone()]);
+                    let (dzi, dsi) = (&dz[rng.clone()], &ds[rng.clone()]);
+                    cone.compute_barrier(zi, si, dzi, dsi, α)
+                })
+                .collect();
+            return barriers.into_iter().fold(T::zero(), |acc, b| acc + b);
+        }
+
         let mut barrier = T::zero();
         for (cone, rng) in zip(&mut self.cones, &self.rng_cones) {
             let zi = &z[rng.clone()];
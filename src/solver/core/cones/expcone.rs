@@ -17,6 +17,16 @@ pub struct ExponentialCone<T> {
 
     // holds copy of z at scaling point
     z: [T; 3],
+
+    // Cholesky factor of H_dual, if it exists, and ψ with its
+    // gradient at z.  Computed with the Hessian and reused by
+    // the higher order correction
+    cholH: Option<DenseMatrixSym3<T>>,
+    ψ: T,
+    gψ: [T; 3],
+
+    #[cfg(test)]
+    pub(crate) counters: EvalCounters,
 }
 
 #[allow(clippy::new_without_default)]
@@ -30,6 +40,11 @@ where
             Hs: DenseMatrixSym3::zeros(),
             grad: [T::zero(); 3],
             z: [T::zero(); 3],
+            cholH: None,
+            ψ: T::zero(),
+            gψ: [T::zero(); 3],
+            #[cfg(test)]
+            counters: EvalCounters::default(),
         }
     }
 }
@@ -83,6 +98,10 @@ where
         (z[0], z[1], z[2]) = (s[0], s[1], s[2]);
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        self.is_primal_feasible(s)
+    }
+
     fn set_identity_scaling(&mut self) {
         // We should never use identity scaling because
         // we never want to allow symmetric initialization
@@ -96,18 +115,24 @@ where
         μ: T,
         scaling_strategy: ScalingStrategy,
     ) -> bool {
+        let (s, z) = (load3(s), load3(z));
+
         // update both gradient and Hessian for function f*(z) at the point z
-        self.update_dual_grad_H(z);
+        self.update_dual_grad_H(&z);
 
         // update the scaling matrix Hs
-        self.update_Hs(s, z, μ, scaling_strategy);
+        self.update_Hs(&s, &z, μ, scaling_strategy);
 
         // K.z .= z
-        self.z.copy_from(z);
+        self.z = z;
 
         true
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        false
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         false
     }
@@ -118,7 +143,25 @@ where
     }
 
     fn mul_Hs(&mut self, y: &mut [T], x: &[T], _work: &mut [T]) {
-        self.Hs.mul(y, x);
+        let mut Hx = [T::zero(); 3];
+        self.Hs.mul(&mut Hx, &load3(x));
+        store3(y, &Hx);
+    }
+
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, _x: &mut [T]) {
+        // no NT scaling for nonsymmetric cones
+    }
+
+    fn apply_W_inv(&mut self, _x: &mut [T]) {
+        // no NT scaling for nonsymmetric cones
     }
 
     fn affine_ds(&self, ds: &mut [T], s: &[T]) {
@@ -129,11 +172,13 @@ where
         //3rd order correction requires input variables.z
 
         let mut η = [T::zero(); 3];
-        self.higher_correction(&mut η, step_s, step_z);
+        self.higher_correction(&mut η, &load3(step_s), &load3(step_z));
 
+        let mut out = [T::zero(); 3];
         for i in 0..3 {
-            shift[i] = self.grad[i] * σμ - η[i];
+            out[i] = self.grad[i] * σμ - η[i];
         }
+        store3(shift, &out);
     }
 
     fn Δs_from_Δz_offset(&mut self, out: &mut [T], ds: &[T], _work: &mut [T], _z: &[T]) {
@@ -151,13 +196,13 @@ where
     ) -> (T, T) {
         let step = settings.linesearch_backtrack_step;
         let αmin = settings.min_terminate_step_length;
-        let mut work = [T::zero(); 3];
+        let (dz, ds, z, s) = (load3(dz), load3(ds), load3(z), load3(s));
 
-        let _is_prim_feasible_fcn = |s: &[T]| -> bool { self.is_primal_feasible(s) };
-        let _is_dual_feasible_fcn = |s: &[T]| -> bool { self.is_dual_feasible(s) };
+        let _is_prim_feasible_fcn = |s: &[T; 3]| -> bool { self.is_primal_feasible(s) };
+        let _is_dual_feasible_fcn = |s: &[T; 3]| -> bool { self.is_dual_feasible(s) };
 
-        let αz = backtrack_search(dz, z, αmax, αmin, step, _is_dual_feasible_fcn, &mut work);
-        let αs = backtrack_search(ds, s, αmax, αmin, step, _is_prim_feasible_fcn, &mut work);
+        let αz = backtrack_search3(&dz, &z, αmax, αmin, step, _is_dual_feasible_fcn);
+        let αs = backtrack_search3(&ds, &s, αmax, αmin, step, _is_prim_feasible_fcn);
 
         (αz, αs)
     }
@@ -165,6 +210,7 @@ where
     fn compute_barrier(&mut self, z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T {
         let mut barrier = T::zero();
 
+        let (z, s, dz, ds) = (load3(z), load3(s), load3(dz), load3(ds));
         let cur_z = [z[0] + α * dz[0], z[1] + α * dz[1], z[2] + α * dz[2]];
         let cur_s = [s[0] + α * ds[0], s[1] + α * ds[1], s[2] + α * ds[2]];
 
@@ -191,6 +237,7 @@ where
     where
         T: FloatT,
     {
+        let s = load3(s);
         if s[2] > T::zero() && s[1] > T::zero() {
             //feasible
             let res = s[1] * (s[2] / s[1]).logsafe() - s[0];
@@ -206,6 +253,10 @@ where
     where
         T: FloatT,
     {
+        #[cfg(test)]
+        self.counters.dual_eval();
+
+        let z = load3(z);
         if z[2] > T::zero() && z[0] < T::zero() {
             let res = z[1] - z[0] - z[0] * (-z[2] / z[0]).logsafe();
             if res > T::zero() {
@@ -225,6 +276,7 @@ where
         // where barω = ω(1 - s1/s2 - log(s2) - log(s3))
         // NB: ⟨s,g(s)⟩ = -3 = - ν
 
+        let s = load3(s);
         let ω = _wright_omega(T::one() - s[0] / s[1] - (s[1] / s[2]).logsafe());
 
         let ω = (ω - T::one()) * (ω - T::one()) / ω;
@@ -239,40 +291,38 @@ where
         // Dual barrier:
         // f*(z) = -log(z2 - z1 - z1*log(z3/-z1)) - log(-z1) - log(z3)
         // -----------------------------------------
+        #[cfg(test)]
+        self.counters.dual_eval();
+
+        let z = load3(z);
         let l = (-z[2] / z[0]).logsafe();
         -(-z[2] * z[0]).logsafe() - (z[1] - z[0] - z[0] * l).logsafe()
     }
 
-    fn higher_correction(&mut self, η: &mut [T], ds: &[T], v: &[T])
+    fn higher_correction(&mut self, η_out: &mut [T], ds: &[T], v: &[T])
     where
         T: FloatT,
     {
+        let (ds, v) = (load3(ds), load3(v));
+
         // u for H^{-1}*Δs
-        let H = &self.H_dual;
         let mut u = [T::zero(); 3];
         let z = &self.z;
 
-        //Fine to use symmetric here because the upper
-        //triangle is ignored anyway
-        let mut cholH = DenseMatrixSym3::zeros();
-
         // solve H*u = ds
-        let issuccess = cholH.cholesky_3x3_explicit_factor(H);
-        if issuccess {
-            cholH.cholesky_3x3_explicit_solve(&mut u[..], ds);
+        if let Some(cholH) = &self.cholH {
+            cholH.cholesky_3x3_explicit_solve(&mut u, &ds);
         } else {
-            η.set(T::zero());
+            η_out.set(T::zero());
             return;
         }
 
-        η[1] = T::one();
-        η[2] = -z[0] / z[2]; // gradient of ψ
-        η[0] = η[2].logsafe();
-
-        let ψ = z[0] * η[0] - z[0] + z[1];
+        // gradient of ψ
+        let mut η = self.gψ;
+        let ψ = self.ψ;
 
-        let dotψu = u.dot(η);
-        let dotψv = v.dot(η);
+        let dotψu = dot3(&u, &η);
+        let dotψv = dot3(&v, &η);
 
         let two: T = (2.).as_T();
         let coef =
@@ -280,7 +330,7 @@ where
                 - two * dotψu * dotψv)
                 / (ψ * ψ * ψ);
 
-        η.scale(coef);
+        scale3(&mut η, coef);
 
         let inv_ψ2 = (ψ * ψ).recip();
 
@@ -294,7 +344,8 @@ where
             + dotψu * inv_ψ2 * (z[0] * v[2] / (z[2] * z[2]) - v[0] / z[2])
             + dotψv * inv_ψ2 * (z[0] * u[2] / (z[2] * z[2]) - u[0] / z[2]);
 
-        η[..].scale((0.5).as_T());
+        scale3(&mut η, (0.5).as_T());
+        store3(η_out, &η);
     }
 
     // 3rd-order correction at the point z.  Output is η.
@@ -318,6 +369,7 @@ where
     // gψ is used inside η
 
     fn update_dual_grad_H(&mut self, z: &[T]) {
+        let z = load3(z);
         let grad = &mut self.grad;
         let H = &mut self.H_dual;
 
@@ -340,6 +392,24 @@ where
         H[(0, 2)] = (z[1] - z[0]) / (r * r * z[2]);
         H[(1, 2)] = -z[0] / (r * r * z[2]);
         H[(2, 2)] = (r * r - z[0] * r + z[0] * z[0]) / (r * r * z[2] * z[2]);
+
+        // ψ and its gradient for the higher order correction
+        let gψ = &mut self.gψ;
+        gψ[1] = T::one();
+        gψ[2] = -z[0] / z[2];
+        gψ[0] = gψ[2].logsafe();
+        self.ψ = z[0] * gψ[0] - z[0] + z[1];
+
+        //Fine to use symmetric here because the upper
+        //triangle is ignored anyway
+        let mut cholH = DenseMatrixSym3::zeros();
+        self.cholH = cholH.cholesky_3x3_explicit_factor(H).then_some(cholH);
+
+        #[cfg(test)]
+        {
+            self.counters.dual_eval();
+            self.counters.factorization();
+        }
     }
 }
 
@@ -352,6 +422,7 @@ where
     where
         T: FloatT,
     {
+        let s = load3(s);
         let mut g = [T::zero(); 3];
         let ω = _wright_omega(T::one() - s[0] / s[1] - (s[1] / s[2]).logsafe());
 
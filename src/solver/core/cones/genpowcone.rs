@@ -1,7 +1,7 @@
 use super::*;
 use crate::algebra::*;
+use core::iter::zip;
 use itertools::izip;
-use std::iter::zip;
 
 // -------------------------------------
 // Generalized Power Cone
@@ -41,7 +41,7 @@ where
         let dim1 = α.len();
         let dim = dim1 + dim2;
 
-        //PJG : these checks belong elsewhere
+        // user supplied powers are checked in SupportedConeT::validate
         assert!(α.iter().all(|r| *r > T::zero())); // check all powers are greater than 0
         assert!((T::one() - α.sum()).abs() < (T::epsilon() * α.len().as_T() * (0.5).as_T()));
 
@@ -139,6 +139,10 @@ where
         z.copy_from(s);
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        self.is_primal_feasible(s)
+    }
+
     fn set_identity_scaling(&mut self) {
         // We should never use identity scaling because
         // we never want to allow symmetric initialization
@@ -162,6 +166,10 @@ where
         true
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        false
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         true
     }
@@ -201,6 +209,22 @@ where
         y.scale(data.μ);
     }
 
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, _x: &mut [T]) {
+        // no NT scaling for nonsymmetric cones
+    }
+
+    fn apply_W_inv(&mut self, _x: &mut [T]) {
+        // no NT scaling for nonsymmetric cones
+    }
+
     fn affine_ds(&self, ds: &mut [T], s: &[T]) {
         ds.copy_from(s);
     }
@@ -231,7 +255,7 @@ where
         //simultaneously using "work" and the closures defined
         //below produces a borrow check error, so temporarily
         //move "work" out of self
-        let mut work = std::mem::take(&mut self.data.work);
+        let mut work = core::mem::take(&mut self.data.work);
 
         let is_prim_feasible_fcn = |s: &[T]| -> bool { self.is_primal_feasible(s) };
         let is_dual_feasible_fcn = |s: &[T]| -> bool { self.is_dual_feasible(s) };
@@ -247,7 +271,7 @@ where
 
     fn compute_barrier(&mut self, z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T {
         let mut barrier = T::zero();
-        let mut work = std::mem::take(&mut self.data.work);
+        let mut work = core::mem::take(&mut self.data.work);
 
         work.waxpby(T::one(), s, α, ds);
         barrier += self.barrier_primal(&work);
@@ -318,7 +342,7 @@ where
 
         // can't use "work" here because it was already
         // used to construct the argument s in some cases
-        let mut g = std::mem::take(&mut self.data.work_pb);
+        let mut g = core::mem::take(&mut self.data.work_pb);
 
         self.gradient_primal(&mut g, s);
         g.negate(); //-g(s)
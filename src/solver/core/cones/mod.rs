@@ -32,6 +32,18 @@ mod psdtrianglecone;
 #[cfg(feature = "sdp")]
 pub use psdtrianglecone::*;
 
+// multithreaded composite cone operations
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub use parallel::*;
+
+// random interior points for testing
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rand")]
+pub use random::*;
+
 // marker for primal / dual distinctions
 #[derive(Eq, PartialEq, Clone, Debug, Copy)]
 pub enum PrimalOrDualCone {
@@ -57,6 +69,25 @@ where
     // report false here if only dual scaling is implemented (e.g. GenPowerCone)
     fn allows_primal_dual_scaling(&self) -> bool;
 
+    // true if the scalings computed with strategies a and b differ.
+    // Symmetric cones use NT scaling for both PrimalDual and Dual, and
+    // nonsymmetric cones use dual scaling for all but PrimalDual
+    fn scaling_differs(&self, a: ScalingStrategy, b: ScalingStrategy) -> bool {
+        let effective = |s| {
+            if self.is_symmetric() {
+                match s {
+                    ScalingStrategy::Dual => ScalingStrategy::PrimalDual,
+                    _ => s,
+                }
+            } else if s == ScalingStrategy::PrimalDual && self.allows_primal_dual_scaling() {
+                s
+            } else {
+                ScalingStrategy::Dual
+            }
+        };
+        effective(a) != effective(b)
+    }
+
     // converts an elementwise scaling into
     // a scaling that preserves cone memership
     fn rectify_equilibration(&self, δ: &mut [T], e: &[T]) -> bool;
@@ -76,12 +107,21 @@ where
     fn scaled_unit_shift(&self, z: &mut [T], α: T, pd: PrimalOrDualCone);
     fn unit_initialization(&self, z: &mut [T], s: &mut [T]);
 
+    // true if s is in the interior of the primal cone.  The zero
+    // cone has no interior, and reports true only at the origin
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool;
+
     // Compute scaling points
     fn set_identity_scaling(&mut self);
     fn update_scaling(
         &mut self, s: &[T], z: &[T], μ: T, scaling_strategy: ScalingStrategy
     ) -> bool;
 
+    // true if the scaling cannot depend on the scaling point, as
+    // for the zero cone.   Such cones need only have update_scaling
+    // and get_Hs called once, after which their Hs block is fixed
+    fn scaling_is_constant(&self) -> bool;
+
     // operations on the Hessian of the centrality condition
     // : W^TW for symmmetric cones
     // : μH(s) for nonsymmetric cones
@@ -89,6 +129,19 @@ where
     fn get_Hs(&self, Hsblock: &mut [T]);
     fn mul_Hs(&mut self, y: &mut [T], x: &[T], work: &mut [T]);
 
+    // operations on the scaling matrix W, where W*z = W⁻¹*s
+    // at the scaling point.  Only symmetric cones have an NT
+    // scaling.  For nonsymmetric cones and the zero cone there
+    // is no W and the scaling functions leave x unchanged.
+    //
+    // scaling_factor_W and scaling_factor_W_inv return the
+    // diagonal of W (or W⁻¹) when W is diagonal, and None otherwise
+    fn scaling_factor_W(&self) -> Option<&[T]>;
+    fn scaling_factor_W_inv(&self) -> Option<&[T]>;
+    // x <- Wx and x <- W⁻¹x, in place
+    fn apply_W(&mut self, x: &mut [T]);
+    fn apply_W_inv(&mut self, x: &mut [T]);
+
     // ---------------------------------------------------------
     // Linearized centrality condition functions
     //
@@ -149,6 +202,12 @@ where
         αmax: T,
     ) -> (T, T);
 
+    // index of the cone that limited the most recent step_length,
+    // if one did.   Only composite cones report an index
+    fn binding_cone(&self) -> Option<usize> {
+        None
+    }
+
     // return the barrier function at (z+αdz,s+αds)
     fn compute_barrier(&mut self, z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T;
 }
@@ -1,7 +1,7 @@
 use super::*;
 use crate::algebra::*;
+use core::iter::zip;
 use itertools::izip;
-use std::iter::zip;
 
 // -------------------------------------
 // Nonnegative Cone
@@ -9,8 +9,10 @@ use std::iter::zip;
 
 pub struct NonnegativeCone<T> {
     dim: usize,
-    w: Vec<T>,
-    λ: Vec<T>,
+    // the scaling w, its inverse and the scaled point λ,
+    // stored consecutively in a single allocation
+    data: Vec<T>,
+    scaling_strategy: ScalingStrategy,
 }
 
 impl<T> NonnegativeCone<T>
@@ -20,10 +22,29 @@ where
     pub fn new(dim: usize) -> Self {
         Self {
             dim,
-            w: vec![T::zero(); dim],
-            λ: vec![T::zero(); dim],
+            data: vec![T::zero(); 3 * dim],
+            scaling_strategy: ScalingStrategy::PrimalDual,
         }
     }
+
+    fn w(&self) -> &[T] {
+        &self.data[..self.dim]
+    }
+
+    fn winv(&self) -> &[T] {
+        &self.data[self.dim..2 * self.dim]
+    }
+
+    fn λ(&self) -> &[T] {
+        &self.data[2 * self.dim..]
+    }
+
+    // mutable views of (w, winv, λ)
+    fn state_mut(&mut self) -> (&mut [T], &mut [T], &mut [T]) {
+        let (w, rest) = self.data.split_at_mut(self.dim);
+        let (winv, λ) = rest.split_at_mut(self.dim);
+        (w, winv, λ)
+    }
 }
 
 impl<T> Cone<T> for NonnegativeCone<T>
@@ -70,46 +91,83 @@ where
         s.fill(T::one());
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        s.iter().all(|&si| si > T::zero())
+    }
+
     fn set_identity_scaling(&mut self) {
-        self.w.fill(T::one());
+        let (w, winv, _) = self.state_mut();
+        w.fill(T::one());
+        winv.fill(T::one());
+        self.scaling_strategy = ScalingStrategy::PrimalDual;
     }
 
     fn update_scaling(
         &mut self,
         s: &[T],
         z: &[T],
-        _μ: T,
-        _scaling_strategy: ScalingStrategy,
+        μ: T,
+        scaling_strategy: ScalingStrategy,
     ) -> bool {
-        for (λ, w, s, z) in izip!(&mut self.λ, &mut self.w, s, z) {
+        // λ is always the NT scaled point, and only the
+        // Hessian block Hs = W² depends on the strategy
+        let sqrtμ = T::sqrt(μ);
+        let (w, winv, λ) = self.state_mut();
+        for (λ, w, winv, s, z) in izip!(λ, w, winv, s, z) {
             *λ = T::sqrt((*s) * (*z));
-            *w = T::sqrt((*s) / (*z));
+            *w = match scaling_strategy {
+                ScalingStrategy::PrimalOnly => (*s) / sqrtμ,
+                ScalingStrategy::DualOnly => sqrtμ / (*z),
+                _ => T::sqrt((*s) / (*z)),
+            };
+            *winv = T::recip(*w);
         }
+        self.scaling_strategy = scaling_strategy;
 
         true
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        false
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         true
     }
 
     fn get_Hs(&self, Hsblock: &mut [T]) {
-        assert_eq!(self.w.len(), Hsblock.len());
-        for (blki, &wi) in zip(Hsblock, &self.w) {
+        assert_eq!(self.dim, Hsblock.len());
+        for (blki, &wi) in zip(Hsblock, self.w()) {
             *blki = wi * wi;
         }
     }
 
     fn mul_Hs(&mut self, y: &mut [T], x: &[T], _work: &mut [T]) {
         //NB : seemingly sensitive to order of multiplication
-        for (yi, (&wi, &xi)) in y.iter_mut().zip(self.w.iter().zip(x)) {
+        for (yi, (&wi, &xi)) in y.iter_mut().zip(self.w().iter().zip(x)) {
             *yi = wi * (wi * xi)
         }
     }
 
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        Some(self.w())
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        Some(self.winv())
+    }
+
+    fn apply_W(&mut self, x: &mut [T]) {
+        x.hadamard(self.w());
+    }
+
+    fn apply_W_inv(&mut self, x: &mut [T]) {
+        x.hadamard(self.winv());
+    }
+
     fn affine_ds(&self, ds: &mut [T], _s: &[T]) {
-        assert_eq!(self.λ.len(), ds.len());
-        for (dsi, &λi) in zip(ds, &self.λ) {
+        assert_eq!(self.dim, ds.len());
+        for (dsi, &λi) in zip(ds, self.λ()) {
             *dsi = λi * λi;
         }
     }
@@ -120,8 +178,16 @@ where
     }
 
     fn Δs_from_Δz_offset(&mut self, out: &mut [T], ds: &[T], _work: &mut [T], z: &[T]) {
-        for (outi, (&dsi, &zi)) in zip(out, zip(ds, z)) {
-            *outi = dsi / zi;
+        if self.scaling_strategy == ScalingStrategy::PrimalOnly {
+            // Newton step on the primal centrality condition,
+            // i.e. out = Hs*(ds/s) with s = λ²/z
+            for (outi, &dsi, &zi, &wi, &λi) in izip!(out, ds, z, self.w(), self.λ()) {
+                *outi = (wi * wi) * (dsi * zi) / (λi * λi);
+            }
+        } else {
+            for (outi, (&dsi, &zi)) in zip(out, zip(ds, z)) {
+                *outi = dsi / zi;
+            }
         }
     }
 
@@ -175,22 +241,24 @@ where
     T: FloatT,
 {
     fn λ_inv_circ_op(&mut self, x: &mut [T], z: &[T]) {
-        _inv_circ_op(x, &self.λ, z);
+        _inv_circ_op(x, self.λ(), z);
     }
 
     fn mul_W(&mut self, _is_transpose: MatrixShape, y: &mut [T], x: &[T], α: T, β: T) {
+        let w = self.w();
         assert_eq!(y.len(), x.len());
-        assert_eq!(y.len(), self.w.len());
+        assert_eq!(y.len(), w.len());
         for i in 0..y.len() {
-            y[i] = α * (x[i] * self.w[i]) + β * y[i];
+            y[i] = α * (x[i] * w[i]) + β * y[i];
         }
     }
 
     fn mul_Winv(&mut self, _is_transpose: MatrixShape, y: &mut [T], x: &[T], α: T, β: T) {
+        let w = self.w();
         assert_eq!(y.len(), x.len());
-        assert_eq!(y.len(), self.w.len());
+        assert_eq!(y.len(), w.len());
         for i in 0..y.len() {
-            y[i] = α * (x[i] / self.w[i]) + β * y[i];
+            y[i] = α * (x[i] / w[i]) + β * y[i];
         }
     }
 }
@@ -20,6 +20,27 @@ pub(crate) trait NonsymmetricCone<T: FloatT> {
     fn update_dual_grad_H(&mut self, z: &[T]);
 }
 
+// Counts of the expensive evaluations made by the 3D nonsymmetric
+// cones, so that tests can check which quantities are cached
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct EvalCounters {
+    // evaluations of the dual barrier or its derivatives at a point
+    pub dual_evals: std::cell::Cell<usize>,
+    // Cholesky factorizations of the dual Hessian
+    pub factorizations: std::cell::Cell<usize>,
+}
+
+#[cfg(test)]
+impl EvalCounters {
+    pub fn dual_eval(&self) {
+        self.dual_evals.set(self.dual_evals.get() + 1);
+    }
+    pub fn factorization(&self) {
+        self.factorizations.set(self.factorizations.get() + 1);
+    }
+}
+
 // --------------------------------------
 // Trait and blanket utlity implementations for Exponential and 3D Power Cones
 // -------------------------------------
@@ -52,12 +73,13 @@ where
     C: Nonsymmetric3DCone<T>,
 {
     fn update_Hs(&mut self, s: &[T], z: &[T], μ: T, scaling_strategy: ScalingStrategy) {
-        // Choose the scaling strategy
-        if scaling_strategy == ScalingStrategy::Dual {
+        // Choose the scaling strategy.  There is no separate primal
+        // scaling for nonsymmetric cones, so PrimalOnly also uses Hs = μ*H
+        if scaling_strategy == ScalingStrategy::PrimalDual {
+            self.use_primal_dual_scaling(s, z);
+        } else {
             // Dual scaling: Hs = μ*H
             self.use_dual_scaling(μ);
-        } else {
-            self.use_primal_dual_scaling(s, z);
         }
     }
 
@@ -69,8 +91,9 @@ where
 
     fn use_primal_dual_scaling(&mut self, s: &[T], z: &[T]) {
         let three: T = (3.).as_T();
+        let (s, z) = (load3(s), load3(z));
 
-        let zt: [T; 3] = self.gradient_primal(s);
+        let zt: [T; 3] = self.gradient_primal(&s);
 
         let (H_dual, Hs, grad, _) = self.split_borrow_mut();
 
@@ -80,9 +103,9 @@ where
 
         // compute zt,st,μt locally
         // NB: zt,st have different sign convention wrt Mosek paper
-        let dot_sz = s.dot(z);
+        let dot_sz = dot3(&s, &z);
         let μ = dot_sz / three;
-        let μt = st[..].dot(&zt[..]) / three;
+        let μt = dot3(st, &zt) / three;
 
         // δs = s + μ*st
         // δz = z + μ*zt
@@ -91,7 +114,7 @@ where
             δs[i] = s[i] + μ * st[i];
             δz[i] = z[i] + μ * zt[i];
         }
-        let dot_δsz = δs[..].dot(&δz[..]);
+        let dot_δsz = dot3(&δs, &δz);
 
         let de1 = μ * μt - T::one();
         let de2 = H_dual.quad_form(&zt, &zt) - three * μt * μt;
@@ -120,11 +143,8 @@ where
 
             // generate the remaining axis
             // axis_z = cross(z,zt)
-            let mut axis_z = tmp;
-            axis_z[0] = z[1] * zt[2] - z[2] * zt[1];
-            axis_z[1] = z[2] * zt[0] - z[0] * zt[2];
-            axis_z[2] = z[0] * zt[1] - z[1] * zt[0];
-            axis_z.normalize();
+            let mut axis_z = cross3(&z, &zt);
+            normalize3(&mut axis_z);
 
             // Hs = s*s'/⟨s,z⟩ + δs*δs'/⟨δs,δz⟩ + t*axis_z*axis_z'
             // (only need to write the upper triangle)
@@ -191,6 +211,37 @@ where
     }
     α
 }
+
+// as backtrack_search, for the 3 dimensional cones
+pub(crate) fn backtrack_search3<T>(
+    dq: &[T; 3],
+    q: &[T; 3],
+    α_init: T,
+    α_min: T,
+    step: T,
+    is_in_cone_fcn: impl Fn(&[T; 3]) -> bool,
+) -> T
+where
+    T: FloatT,
+{
+    let mut α = α_init;
+
+    loop {
+        // work = q + α*dq
+        let work = waxpby3(T::one(), q, α, dq);
+
+        if is_in_cone_fcn(&work) {
+            break;
+        }
+        α *= step;
+        if α < α_min {
+            α = T::zero();
+            break;
+        }
+    }
+    α
+}
+
 pub(crate) fn newton_raphson_onesided<T>(x0: T, f0: impl Fn(T) -> T, f1: impl Fn(T) -> T) -> T
 where
     T: FloatT,
@@ -0,0 +1,62 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// ---------------------------------------------------
+// Multithreaded iteration over the cones of a composite
+// cone, for problems with many small cones
+// ---------------------------------------------------
+
+/// Default number of cones above which the scaling updates, step lengths,
+/// step shifts and barrier values of a composite cone are computed on the
+/// rayon thread pool.
+pub const PARALLEL_CONES_THRESHOLD_DEFAULT: usize = 64;
+
+static PARALLEL_CONES_THRESHOLD: AtomicUsize = AtomicUsize::new(PARALLEL_CONES_THRESHOLD_DEFAULT);
+
+/// Revert the parallel cones threshold to its default value.   The default
+/// is [`PARALLEL_CONES_THRESHOLD_DEFAULT`].
+///
+/// See also: [`get_parallel_cones_threshold`], [`set_parallel_cones_threshold`]
+pub fn default_parallel_cones_threshold() {
+    PARALLEL_CONES_THRESHOLD.store(PARALLEL_CONES_THRESHOLD_DEFAULT, Ordering::Relaxed);
+}
+/// Set the number of cones above which the operations of a composite cone
+/// are computed in parallel.   Use `usize::MAX` to always compute them serially.
+///
+/// The scaling updates, step shifts and barrier values are identical to the
+/// serial versions.   In parallel, the step length of every cone is computed
+/// from the same maximum step rather than from the step allowed by the cones
+/// before it, so the steps of nonsymmetric cones, which backtrack from the
+/// maximum step, may differ slightly from the serial ones.
+///
+/// Setting the threshold applies at module level.
+///
+/// See also: [`get_parallel_cones_threshold`], [`default_parallel_cones_threshold`]
+pub fn set_parallel_cones_threshold(ncones: usize) {
+    PARALLEL_CONES_THRESHOLD.store(ncones, Ordering::Relaxed);
+}
+/// Get the current parallel cones threshold.
+///
+/// See also: [`set_parallel_cones_threshold`], [`default_parallel_cones_threshold`]
+pub fn get_parallel_cones_threshold() -> usize {
+    PARALLEL_CONES_THRESHOLD.load(Ordering::Relaxed)
+}
+
+pub(crate) fn _use_parallel_cones(ncones: usize) -> bool {
+    ncones > get_parallel_cones_threshold() && rayon::current_num_threads() > 1
+}
+
+// split v into the subslices given by rngs, which are
+// consecutive and start from zero
+pub(crate) fn _split_at_ranges<'a, T>(
+    mut v: &'a mut [T],
+    rngs: &[Range<usize>],
+) -> Vec<&'a mut [T]> {
+    let mut out = Vec::with_capacity(rngs.len());
+    for rng in rngs {
+        let (head, tail) = v.split_at_mut(rng.len());
+        out.push(head);
+        v = tail;
+    }
+    out
+}
@@ -19,6 +19,16 @@ pub struct PowerCone<T> {
 
     // holds copy of z at scaling point
     z: [T; 3],
+
+    // Cholesky factor of H_dual, if it exists, and ϕ and ψ at z.
+    // Computed with the Hessian and reused by the higher order
+    // correction
+    cholH: Option<DenseMatrixSym3<T>>,
+    phi: T,
+    ψ: T,
+
+    #[cfg(test)]
+    pub(crate) counters: EvalCounters,
 }
 
 impl<T> PowerCone<T>
@@ -32,6 +42,11 @@ where
             Hs: DenseMatrixSym3::zeros(),
             grad: [T::zero(); 3],
             z: [T::zero(); 3],
+            cholH: None,
+            phi: T::zero(),
+            ψ: T::zero(),
+            #[cfg(test)]
+            counters: EvalCounters::default(),
         }
     }
 }
@@ -86,6 +101,10 @@ where
         (z[0], z[1], z[2]) = (s[0], s[1], s[2]);
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        self.is_primal_feasible(s)
+    }
+
     fn set_identity_scaling(&mut self) {
         // We should never use identity scaling because
         // we never want to allow symmetric initialization
@@ -111,6 +130,10 @@ where
         true
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        false
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         false
     }
@@ -124,6 +147,22 @@ where
         self.Hs.mul(y, x);
     }
 
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, _x: &mut [T]) {
+        // no NT scaling for nonsymmetric cones
+    }
+
+    fn apply_W_inv(&mut self, _x: &mut [T]) {
+        // no NT scaling for nonsymmetric cones
+    }
+
     fn affine_ds(&self, ds: &mut [T], s: &[T]) {
         ds.copy_from(s);
     }
@@ -208,6 +247,9 @@ where
     where
         T: FloatT,
     {
+        #[cfg(test)]
+        self.counters.dual_eval();
+
         let α = self.α;
         let two: T = (2.).as_T();
 
@@ -252,6 +294,9 @@ where
     {
         // Dual barrier:
         // f*(z) = -log((z1/α)^{2α} * (z2/(1-α))^{2(1-α)} - z3*z3) - (1-α)*log(z1) - α*log(z2):
+        #[cfg(test)]
+        self.counters.dual_eval();
+
         let α = self.α;
         let two: T = (2.).as_T();
         let arg1 =
@@ -265,17 +310,11 @@ where
         T: FloatT,
     {
         // u for H^{-1}*Δs
-        let H = &self.H_dual;
         let mut u = [T::zero(); 3];
         let z = &self.z;
 
-        //Fine to use symmetric here because the upper
-        //triangle is ignored anyway
-        let mut cholH = DenseMatrixSym3::zeros();
-
         // solve H*u = ds
-        let issuccess = cholH.cholesky_3x3_explicit_factor(H);
-        if issuccess {
+        if let Some(cholH) = &self.cholH {
             cholH.cholesky_3x3_explicit_solve(&mut u[..], ds);
         } else {
             η.set(T::zero());
@@ -286,11 +325,8 @@ where
         let two: T = (2.).as_T();
         let four: T = (4.).as_T();
 
-        let phi = (z[0] / α).powf(two * α) * (z[1] / (T::one() - α)).powf(two - two * α);
-        let ψ = phi - z[2] * z[2];
-
-        // Reuse cholH memory for further computation
-        let Hψ = &mut cholH;
+        let (phi, ψ) = (self.phi, self.ψ);
+        let mut Hψ = DenseMatrixSym3::zeros();
 
         η[0] = two * α * phi / z[0];
         η[1] = two * (T::one() - α) * phi / z[1];
@@ -383,6 +419,20 @@ where
         grad[0] = -two * α * phi / (z[0] * ψ) - (T::one() - α) / z[0];
         grad[1] = -two * (T::one() - α) * phi / (z[1] * ψ) - α / z[1];
         grad[2] = two * z[2] / ψ;
+
+        // ϕ and ψ for the higher order correction
+        (self.phi, self.ψ) = (phi, ψ);
+
+        //Fine to use symmetric here because the upper
+        //triangle is ignored anyway
+        let mut cholH = DenseMatrixSym3::zeros();
+        self.cholH = cholH.cholesky_3x3_explicit_factor(H).then_some(cholH);
+
+        #[cfg(test)]
+        {
+            self.counters.dual_eval();
+            self.counters.factorization();
+        }
     }
 }
 
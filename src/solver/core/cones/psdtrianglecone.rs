@@ -15,6 +15,7 @@ pub struct PSDConeData<T> {
     R: Matrix<T>,
     Rinv: Matrix<T>,
     Hs: Matrix<T>,
+    scaling_strategy: ScalingStrategy,
 
     //workspace for various internal uses
     workmat1: Matrix<T>,
@@ -41,6 +42,7 @@ where
             R: Matrix::zeros((n, n)),
             Rinv: Matrix::zeros((n, n)),
             Hs: Matrix::zeros((Bm, Bm)),
+            scaling_strategy: ScalingStrategy::PrimalDual,
 
             //workspace for various internal uses
             workmat1: Matrix::zeros((n, n)),
@@ -135,18 +137,28 @@ where
         self.scaled_unit_shift(z, T::one(), PrimalOrDualCone::DualCone);
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        // margins takes its argument as mutable workspace
+        let mut work = std::mem::take(&mut self.data.workvec);
+        work.copy_from(s);
+        let (α, _) = self.margins(&mut work, PrimalOrDualCone::PrimalCone);
+        self.data.workvec = work;
+        α > T::zero()
+    }
+
     fn set_identity_scaling(&mut self) {
         self.data.R.set_identity();
         self.data.Rinv.set_identity();
         self.data.Hs.set_identity();
+        self.data.scaling_strategy = ScalingStrategy::PrimalDual;
     }
 
     fn update_scaling(
         &mut self,
         s: &[T],
         z: &[T],
-        _μ: T,
-        _scaling_strategy: ScalingStrategy,
+        μ: T,
+        scaling_strategy: ScalingStrategy,
     ) -> bool {
         if s.is_empty() {
             //bail early on zero length cone
@@ -187,10 +199,29 @@ where
         f.Rinv.mul(&f.SVD.U.t(), &L2.t(), T::one(), T::zero());
         f.Rinv.lscale(&f.Λisqrt);
 
+        // The one-sided strategies replace only the Hessian block with
+        // Hs = (μ∇²f(S))⁻¹ = S⊗S/μ or μ∇²f*(Z) = μZ⁻¹⊗Z⁻¹.  Since
+        // S = RΛRᵀ and Z⁻¹ = RΛ⁻¹Rᵀ, these come from R with scaled columns
+        let Rs = &mut f.workmat3;
+        Rs.data_mut().copy_from(f.R.data());
+        match scaling_strategy {
+            ScalingStrategy::PrimalDual | ScalingStrategy::Dual => {}
+            ScalingStrategy::PrimalOnly => {
+                Rs.rscale(&f.λ);
+                Rs.rscale(&f.Λisqrt);
+                Rs.data_mut().scale(T::sqrt(T::sqrt(μ)).recip());
+            }
+            ScalingStrategy::DualOnly => {
+                Rs.rscale(&f.Λisqrt);
+                Rs.data_mut().scale(T::sqrt(T::sqrt(μ)));
+            }
+        }
+        f.scaling_strategy = scaling_strategy;
+
         // compute R*R^T (upper triangular part only)
         let RRt = &mut f.workmat1;
         RRt.data_mut().set(T::zero());
-        RRt.syrk(&f.R, T::one(), T::zero());
+        RRt.syrk(&*Rs, T::one(), T::zero());
 
         // PJG: it is possibly faster to compute the whole of RRt, and not
         // just the upper triangle using syrk!, because then skron! can be
@@ -203,6 +234,10 @@ where
         true //PJG: Should return result, with "?" operators above
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        false
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         false
     }
@@ -212,11 +247,41 @@ where
     }
 
     fn mul_Hs(&mut self, y: &mut [T], x: &[T], work: &mut [T]) {
+        if matches!(
+            self.data.scaling_strategy,
+            ScalingStrategy::PrimalOnly | ScalingStrategy::DualOnly
+        ) {
+            // one-sided scaling, so Hs is not WᵀW
+            self.data.Hs.sym().symv(x, y, T::one(), T::zero());
+            return;
+        }
         // PJG: Why this way instead of Hs.sym() * x?
         self.mul_W(MatrixShape::N, work, x, T::one(), T::zero()); // work = Wx
         self.mul_W(MatrixShape::T, y, work, T::one(), T::zero()); // y = c Wᵀwork = W^TWx
     }
 
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, x: &mut [T]) {
+        let mut work = std::mem::take(&mut self.data.workvec);
+        work.copy_from(x);
+        self.mul_W(MatrixShape::N, x, &work, T::one(), T::zero());
+        self.data.workvec = work;
+    }
+
+    fn apply_W_inv(&mut self, x: &mut [T]) {
+        let mut work = std::mem::take(&mut self.data.workvec);
+        work.copy_from(x);
+        self.mul_Winv(MatrixShape::N, x, &work, T::one(), T::zero());
+        self.data.workvec = work;
+    }
+
     fn affine_ds(&self, ds: &mut [T], _s: &[T]) {
         ds.set(T::zero());
         for k in 0..self.n {
@@ -229,6 +294,15 @@ where
     }
 
     fn Δs_from_Δz_offset(&mut self, out: &mut [T], ds: &[T], work: &mut [T], _z: &[T]) {
+        if self.data.scaling_strategy == ScalingStrategy::PrimalOnly {
+            // Newton step on the primal centrality condition,
+            // i.e. out = Hs W⁻¹(λ \ ds), since W⁻¹(λ⁻¹) = s⁻¹
+            self.λ_inv_circ_op(work, ds);
+            self.mul_Winv(MatrixShape::N, out, work, T::one(), T::zero());
+            self.data.Hs.sym().symv(out, work, T::one(), T::zero());
+            out.copy_from(work);
+            return;
+        }
         self._Δs_from_Δz_offset_symmetric(out, ds, work);
     }
 
@@ -0,0 +1,88 @@
+use super::*;
+use crate::algebra::*;
+use rand::{Rng, RngCore};
+use std::iter::zip;
+
+// ---------------------------------------------------
+// Random points in the interior of the supported cones,
+// e.g. for randomized testing of cone operations
+// ---------------------------------------------------
+
+// minimum distance from the cone boundary
+const MARGIN: f64 = 0.1;
+
+/// Returns a random point strictly inside the interior of the (primal)
+/// cone described by `cone`.
+///
+/// The zero cone has no interior, and for it the origin is returned.
+pub fn random_cone_interior_point<T: FloatT>(
+    cone: &SupportedConeT<T>,
+    rng: &mut impl RngCore,
+) -> Vec<T> {
+    // uniform on (a, a + 1)
+    let mut unif = |a: f64| -> T { (a + rng.gen::<f64>()).as_T() };
+
+    match cone {
+        SupportedConeT::ZeroConeT(dim) => vec![T::zero(); *dim],
+
+        SupportedConeT::NonnegativeConeT(dim) => (0..*dim).map(|_| unif(MARGIN)).collect(),
+
+        SupportedConeT::SecondOrderConeT(dim) => {
+            let mut s: Vec<T> = (0..*dim).map(|_| unif(-0.5)).collect();
+            if let Some((t, x)) = s.split_first_mut() {
+                *t = x.norm() + unif(MARGIN);
+            }
+            s
+        }
+
+        SupportedConeT::ExponentialConeT() => {
+            // s₂ > s₁exp(s₀/s₁), s₁ > 0, with s₀/s₁ bounded
+            let s1 = unif(0.5);
+            let s0 = unif(-0.5) * s1;
+            let s2 = s1 * T::exp(s0 / s1) + unif(MARGIN);
+            vec![s0, s1, s2]
+        }
+
+        SupportedConeT::PowerConeT(α) => {
+            // s₀^α s₁^(1-α) > |s₂|
+            let (s0, s1) = (unif(MARGIN), unif(MARGIN));
+            let bound = T::powf(s0, *α) * T::powf(s1, T::one() - *α);
+            let s2 = unif(-0.5) * bound;
+            vec![s0, s1, s2]
+        }
+
+        SupportedConeT::GenPowerConeT(α, dim2) => {
+            // Πᵢ s₁ᵢ^αᵢ > ‖s₂‖
+            let mut s: Vec<T> = α.iter().map(|_| unif(MARGIN)).collect();
+            let bound = zip(α, &s).fold(T::one(), |p, (&αi, &si)| p * T::powf(si, αi));
+
+            let mut s2: Vec<T> = (0..*dim2).map(|_| unif(-0.5)).collect();
+            let nrm = s2.norm();
+            if nrm > T::zero() {
+                s2.scale(unif(0.0) * bound / nrm);
+            }
+            s.extend(s2);
+            s
+        }
+
+        #[cfg(feature = "sdp")]
+        SupportedConeT::PSDTriangleConeT(n) => {
+            // S = BBᵀ + margin*I, in scaled triangular form
+            let n = *n;
+            let B: Vec<T> = (0..n * n).map(|_| unif(-0.5)).collect();
+            let mut s = Vec::with_capacity(triangular_number(n));
+            for col in 0..n {
+                for row in 0..=col {
+                    let mut v = (0..n).fold(T::zero(), |v, k| v + B[row + k * n] * B[col + k * n]);
+                    if row == col {
+                        v += MARGIN.as_T();
+                    } else {
+                        v *= T::SQRT_2();
+                    }
+                    s.push(v);
+                }
+            }
+            s
+        }
+    }
+}
@@ -2,6 +2,37 @@ use super::*;
 use crate::algebra::*;
 use itertools::izip;
 
+// vector kernels for the hot loops of this cone, explicitly
+// lane-blocked when the "blocked-kernels" feature is enabled
+cfg_if::cfg_if! {
+    if #[cfg(feature = "blocked-kernels")] {
+        use crate::algebra::blocked::{
+            axpby as _axpby, dot as _dot, dot_shifted as _dot_shifted, sumsq as _sumsq,
+            waxpby as _waxpby,
+        };
+    } else {
+        fn _dot<T: FloatT>(x: &[T], y: &[T]) -> T {
+            x.dot(y)
+        }
+        fn _sumsq<T: FloatT>(x: &[T]) -> T {
+            x.sumsq()
+        }
+        fn _dot_shifted<T: FloatT>(z: &[T], s: &[T], dz: &[T], ds: &[T], α: T) -> T {
+            <[T] as VectorMath<T>>::dot_shifted(z, s, dz, ds, α)
+        }
+        fn _axpby<T: FloatT>(y: &mut [T], a: T, x: &[T], b: T) {
+            y.axpby(a, x, b);
+        }
+        fn _waxpby<T: FloatT>(w: &mut [T], a: T, x: &[T], b: T, y: &[T]) {
+            w.waxpby(a, x, b, y);
+        }
+    }
+}
+
+fn _norm<T: FloatT>(x: &[T]) -> T {
+    T::sqrt(_sumsq(x))
+}
+
 // -------------------------------------
 // Second order Cone
 // -------------------------------------
@@ -36,15 +67,21 @@ pub struct SecondOrderCone<T> {
     pub λ: Vec<T>,
     pub η: T,
     pub sparse_data: Option<SecondOrderConeSparseData<T>>,
+    //scaling point and scalar for Hs when it differs
+    //from the NT scaling, i.e. for one-sided strategies
+    Hs_scaling: Option<(Vec<T>, T)>,
+    scaling_strategy: ScalingStrategy,
 }
 
+// cones larger than this are expanded into a sparse
+// form in the KKT system, with a diagonal block for Hs
+pub(crate) const SOC_NO_EXPANSION_MAX_SIZE: usize = 4;
+
 impl<T> SecondOrderCone<T>
 where
     T: FloatT,
 {
     pub fn new(dim: usize) -> Self {
-        const SOC_NO_EXPANSION_MAX_SIZE: usize = 4;
-
         assert!(dim >= 2);
 
         let w = vec![T::zero(); dim];
@@ -65,6 +102,16 @@ where
             λ,
             η,
             sparse_data,
+            Hs_scaling: None,
+            scaling_strategy: ScalingStrategy::PrimalDual,
+        }
+    }
+
+    // the scaling point w and scalar η with Hs = η²(2wwᵀ - J)
+    pub(crate) fn Hs_factors(&self) -> (&[T], T) {
+        match &self.Hs_scaling {
+            Some((w, η)) => (w, *η),
+            None => (&self.w, self.η),
         }
     }
 }
@@ -102,7 +149,7 @@ where
 
     // functions relating to unit vectors and cone initialization
     fn margins(&mut self, z: &mut [T], _pd: PrimalOrDualCone) -> (T, T) {
-        let α = z[0] - z[1..].norm();
+        let α = z[0] - _norm(&z[1..]);
         let β = T::max(T::zero(), α);
         (α, β)
     }
@@ -118,10 +165,16 @@ where
         self.scaled_unit_shift(z, T::one(), PrimalOrDualCone::DualCone);
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        s[0] - _norm(&s[1..]) > T::zero()
+    }
+
     fn set_identity_scaling(&mut self) {
         self.w.fill(T::zero());
         self.w[0] = T::one();
         self.η = T::one();
+        self.Hs_scaling = None;
+        self.scaling_strategy = ScalingStrategy::PrimalDual;
 
         if let Some(sparse_data) = &mut self.sparse_data {
             sparse_data.d = (0.5).as_T();
@@ -135,8 +188,8 @@ where
         &mut self,
         s: &[T],
         z: &[T],
-        _μ: T,
-        _scaling_strategy: ScalingStrategy,
+        μ: T,
+        scaling_strategy: ScalingStrategy,
     ) -> bool {
         let two: T = (2.0).as_T();
         let half: T = (0.5).as_T();
@@ -158,7 +211,7 @@ where
         w.copy_from(s);
         w.scale(sscale.recip());
         w[0] += z[0] / zscale;
-        w[1..].axpby(-zscale.recip(), &z[1..], T::one());
+        _axpby(&mut w[1..], -zscale.recip(), &z[1..], T::one());
 
         let wscale = _sqrt_soc_residual(w);
         // Fail if w is not an interior point
@@ -168,7 +221,7 @@ where
         w.scale(wscale.recip());
 
         // try to force badly scaled w to come out normalized
-        let w1sq = w[1..].sumsq();
+        let w1sq = _sumsq(&w[1..]);
         w[0] = T::sqrt(T::one() + w1sq);
 
         //---------------------
@@ -177,7 +230,8 @@ where
         //Compute the scaling point λ.   Should satisfy λ = Wz = W^{-T}s
         let γ = half * wscale;
         self.λ[0] = γ;
-        self.λ[1..].waxpby(
+        _waxpby(
+            &mut self.λ[1..],
             (γ + z[0] / zscale) / sscale,
             &s[1..],
             (γ + s[0] / sscale) / zscale,
@@ -186,71 +240,117 @@ where
         self.λ[1..].scale(T::recip(s[0] / sscale + z[0] / zscale + two * γ));
         self.λ.scale(T::sqrt(sscale * zscale));
 
+        // The one-sided strategies replace only the Hessian block, with
+        // Hs = (μ∇²f(s))⁻¹ or μ∇²f*(z).  Both have the same form as the
+        // NT scaling, with w = s/√det(s) or Jz/√det(z) respectively.
+        match scaling_strategy {
+            ScalingStrategy::PrimalDual | ScalingStrategy::Dual => {
+                self.Hs_scaling = None;
+            }
+            ScalingStrategy::PrimalOnly | ScalingStrategy::DualOnly => {
+                let dim = self.dim;
+                let (w, η) = self
+                    .Hs_scaling
+                    .get_or_insert_with(|| (vec![T::zero(); dim], T::zero()));
+                if scaling_strategy == ScalingStrategy::PrimalOnly {
+                    w.copy_from(s);
+                    w.scale(sscale.recip());
+                    *η = sscale / T::sqrt(μ);
+                } else {
+                    w.copy_from(z);
+                    w[1..].negate();
+                    w.scale(zscale.recip());
+                    *η = T::sqrt(μ) / zscale;
+                }
+                w[0] = T::sqrt(T::one() + _sumsq(&w[1..]));
+            }
+        }
+        self.scaling_strategy = scaling_strategy;
+
         if let Some(sparse_data) = &mut self.sparse_data {
-            //various intermediate calcs for u,v,d,η
-            let α = two * w[0];
-
-            //Scalar d is the upper LH corner of the diagonal
-            //term in the rank-2 update form of W^TW
-            let wsq = w[0] * w[0] + w1sq;
-            let wsqinv = wsq.recip();
-            sparse_data.d = half * wsqinv;
-
-            //the vectors for the rank two update
-            //representation of W^TW
-            let u0 = T::sqrt(wsq - sparse_data.d);
-            let u1 = α / u0;
-            let v0 = T::zero();
-            let v1 = T::sqrt(two * (two + wsqinv) / (two * wsq - wsqinv));
-
-            sparse_data.u[0] = u0;
-            sparse_data.u[1..].axpby(u1, &self.w[1..], T::zero());
-            sparse_data.v[0] = v0;
-            sparse_data.v[1..].axpby(v1, &self.w[1..], T::zero());
+            let w = match &self.Hs_scaling {
+                Some((w, _)) => w,
+                None => &self.w,
+            };
+            _update_sparse_data(sparse_data, w);
         }
 
         true
     }
 
+    fn scaling_is_constant(&self) -> bool {
+        false
+    }
+
     fn Hs_is_diagonal(&self) -> bool {
         self.is_sparse_expandable()
     }
 
     fn get_Hs(&self, Hsblock: &mut [T]) {
+        let (w, η) = self.Hs_factors();
         if let Some(sparse_data) = &self.sparse_data {
             // For sparse form, we are returning here the diagonal D block
             // from the sparse representation of W^TW, but not the
             // extra two entries at the bottom right of the block.
-            Hsblock.fill(self.η * self.η);
+            Hsblock.fill(η * η);
             Hsblock[0] *= sparse_data.d;
         } else {
             let two: T = (2.).as_T();
             // for dense form, we return H = \eta^2 (2*ww^T - J), where
             // J = diag(1,-I).  We are packing into dense triu form
-            Hsblock[0] = two * self.w[0] * self.w[0] - T::one();
+            Hsblock[0] = two * w[0] * w[0] - T::one();
             let mut hidx = 1;
 
             for col in 1..self.dim {
-                let wcol = self.w[col];
-                for row in 0..=col {
-                    Hsblock[hidx] = two * self.w[row] * wcol;
+                let wcol = w[col];
+                for &wrow in &w[0..=col] {
+                    Hsblock[hidx] = two * wrow * wcol;
                     hidx += 1
                 }
                 //go back to add the offset term from J
                 Hsblock[hidx - 1] += T::one()
             }
-            Hsblock.scale(self.η * self.η);
+            Hsblock.scale(η * η);
         }
     }
 
     fn mul_Hs(&mut self, y: &mut [T], x: &[T], _work: &mut [T]) {
         //self.mul_W(MatrixShape::N, work, x, T::one(), T::zero()); // work = Wx
         //self.mul_W(MatrixShape::T, y, work, T::one(), T::zero()); // y = c Wᵀwork = W^TWx
-        let c = self.w.dot(x) * (2.).as_T();
+        let (w, η) = self.Hs_factors();
+        let c = _dot(w, x) * (2.).as_T();
         y.copy_from(x);
         y[0] = -x[0];
-        y.axpby(c, &self.w, T::one());
-        y.scale(self.η * self.η);
+        _axpby(y, c, w, T::one());
+        y.scale(η * η);
+    }
+
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, x: &mut [T]) {
+        // in place version of _soc_mul_W_inner
+        let w = &self.w;
+        let ζ = _dot(&w[1..], &x[1..]);
+        let c = x[0] + ζ / (T::one() + w[0]);
+
+        x[0] = self.η * (w[0] * x[0] + ζ);
+        _axpby(&mut x[1..], self.η * c, &w[1..], self.η);
+    }
+
+    fn apply_W_inv(&mut self, x: &mut [T]) {
+        // in place version of _soc_mul_Winv_inner
+        let w = &self.w;
+        let ζ = _dot(&w[1..], &x[1..]);
+        let c = -x[0] + ζ / (T::one() + w[0]);
+
+        x[0] = (w[0] * x[0] - ζ) / self.η;
+        _axpby(&mut x[1..], c / self.η, &w[1..], T::one() / self.η);
     }
 
     fn affine_ds(&self, ds: &mut [T], _s: &[T]) {
@@ -261,14 +361,24 @@ where
         self._combined_ds_shift_symmetric(shift, step_z, step_s, σμ);
     }
 
-    fn Δs_from_Δz_offset(&mut self, out: &mut [T], ds: &[T], _work: &mut [T], z: &[T]) {
+    fn Δs_from_Δz_offset(&mut self, out: &mut [T], ds: &[T], work: &mut [T], z: &[T]) {
+        if self.scaling_strategy == ScalingStrategy::PrimalOnly {
+            // Newton step on the primal centrality condition,
+            // i.e. out = Hs W⁻¹(λ \ ds), since W⁻¹(λ⁻¹) = s⁻¹
+            self.λ_inv_circ_op(work, ds);
+            self.mul_Winv(MatrixShape::N, out, work, T::one(), T::zero());
+            work.copy_from(out);
+            self.mul_Hs(out, work, &mut []);
+            return;
+        }
+
         // out = Wᵀ(λ \ ds).  Below is equivalent,
         // but appears to be a little more stable
 
         let resz = _soc_residual(z);
 
-        let λ1ds1 = self.λ[1..].dot(&ds[1..]);
-        let w1ds1 = self.w[1..].dot(&ds[1..]);
+        let λ1ds1 = _dot(&self.λ[1..], &ds[1..]);
+        let w1ds1 = _dot(&self.w[1..], &ds[1..]);
 
         out.scalarop_from(|zi| -zi, z);
         out[0] = z[0];
@@ -359,9 +469,9 @@ fn _circ_op<T>(x: &mut [T], y: &[T], z: &[T])
 where
     T: FloatT,
 {
-    x[0] = y.dot(z);
+    x[0] = _dot(y, z);
     let (y0, z0) = (y[0], z[0]);
-    x[1..].waxpby(y0, &z[1..], z0, &y[1..]);
+    _waxpby(&mut x[1..], y0, &z[1..], z0, &y[1..]);
 }
 
 fn _inv_circ_op<T>(x: &mut [T], y: &[T], z: &[T])
@@ -370,24 +480,55 @@ where
 {
     let p = _soc_residual(y);
     let pinv = T::recip(p);
-    let v = y[1..].dot(&z[1..]);
+    let v = _dot(&y[1..], &z[1..]);
 
     x[0] = (y[0] * z[0] - v) * pinv;
 
     let c1 = pinv * (v / y[0] - z[0]);
     let c2 = T::recip(y[0]);
-    x[1..].waxpby(c1, &y[1..], c2, &z[1..]);
+    _waxpby(&mut x[1..], c1, &y[1..], c2, &z[1..]);
 }
 
 // ---------------------------------------------
 // internal operations for second order cones
 // ---------------------------------------------
 
+// update the rank-2 representation of Hs = η²(2wwᵀ - J)
+// used for sparse expansion.  Assumes w is normalized.
+fn _update_sparse_data<T>(sparse_data: &mut SecondOrderConeSparseData<T>, w: &[T])
+where
+    T: FloatT,
+{
+    let two: T = (2.0).as_T();
+    let half: T = (0.5).as_T();
+
+    //various intermediate calcs for u,v,d,η
+    let α = two * w[0];
+
+    //Scalar d is the upper LH corner of the diagonal
+    //term in the rank-2 update form of W^TW
+    let wsq = w[0] * w[0] + _sumsq(&w[1..]);
+    let wsqinv = wsq.recip();
+    sparse_data.d = half * wsqinv;
+
+    //the vectors for the rank two update
+    //representation of W^TW
+    let u0 = T::sqrt(wsq - sparse_data.d);
+    let u1 = α / u0;
+    let v0 = T::zero();
+    let v1 = T::sqrt(two * (two + wsqinv) / (two * wsq - wsqinv));
+
+    sparse_data.u[0] = u0;
+    _axpby(&mut sparse_data.u[1..], u1, &w[1..], T::zero());
+    sparse_data.v[0] = v0;
+    _axpby(&mut sparse_data.v[1..], v1, &w[1..], T::zero());
+}
+
 fn _soc_residual<T>(z: &[T]) -> T
 where
     T: FloatT,
 {
-    z[0] * z[0] - z[1..].sumsq()
+    z[0] * z[0] - _sumsq(&z[1..])
 }
 
 fn _sqrt_soc_residual<T>(z: &[T]) -> T
@@ -409,7 +550,7 @@ where
     T: FloatT,
 {
     let x0 = z[0] + α * dz[0];
-    let x1_sq = <[T] as VectorMath<T>>::dot_shifted(&z[1..], &z[1..], &dz[1..], &dz[1..], α);
+    let x1_sq = _dot_shifted(&z[1..], &z[1..], &dz[1..], &dz[1..], α);
 
     x0 * x0 - x1_sq
 }
@@ -427,7 +568,7 @@ where
     let four: T = (4.).as_T();
 
     let a = _soc_residual(y); //NB: could be negative
-    let b = two * (x[0] * y[0] - x[1..].dot(&y[1..]));
+    let b = two * (x[0] * y[0] - _dot(&x[1..], &y[1..]));
     let c = T::max(T::zero(), _soc_residual(x)); //should be ≥0
     let d = b * b - four * a * c;
 
@@ -496,13 +637,13 @@ where
     T: FloatT,
 {
     // use the fast product method from ECOS ECC paper
-    let ζ = w[1..].dot(&x[1..]);
+    let ζ = _dot(&w[1..], &x[1..]);
     let c = x[0] + ζ / (T::one() + w[0]);
 
     y[0] = (α * η) * (w[0] * x[0] + ζ) + β * y[0];
 
-    y[1..].axpby(α * η * c, &w[1..], β);
-    y[1..].axpby(α * η, &x[1..], T::one());
+    _axpby(&mut y[1..], α * η * c, &w[1..], β);
+    _axpby(&mut y[1..], α * η, &x[1..], T::one());
 }
 
 fn _soc_mul_Winv_inner<T>(y: &mut [T], x: &[T], α: T, β: T, w: &[T], η: T)
@@ -510,11 +651,11 @@ where
     T: FloatT,
 {
     // use the fast inverse product method from ECOS ECC paper
-    let ζ = w[1..].dot(&x[1..]);
+    let ζ = _dot(&w[1..], &x[1..]);
     let c = -x[0] + ζ / (T::one() + w[0]);
 
     y[0] = (α / η) * (w[0] * x[0] - ζ) + β * y[0];
 
-    y[1..].axpby(α / η * c, &w[1..], β);
-    y[1..].axpby(α / η, &x[1..], T::one());
+    _axpby(&mut y[1..], α / η * c, &w[1..], β);
+    _axpby(&mut y[1..], α / η, &x[1..], T::one());
 }
@@ -1,7 +1,5 @@
 use super::*;
-
-#[cfg(feature = "sdp")]
-use crate::algebra::triangular_number;
+use crate::algebra::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +11,7 @@ use serde::{Deserialize, Serialize};
 /// API type describing the type of a conic constraint.
 ///
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SupportedConeT<T> {
     /// The zero cone (used for equality constraints).
     ///
@@ -70,11 +68,78 @@ impl<T> SupportedConeT<T> {
     }
 }
 
-impl<T> std::fmt::Display for SupportedConeT<T>
+impl<T> SupportedConeT<T> {
+    // number of slack variables generated by this cone and number
+    // of entries in its packed block of the Hs matrix, or None if
+    // either overflows usize.   Sizes that pass this check at setup
+    // can be computed internally without overflow
+    pub(crate) fn checked_sizes(&self) -> Option<(usize, usize)> {
+        let nvars = match self {
+            SupportedConeT::GenPowerConeT(α, dim2) => α.len().checked_add(*dim2)?,
+            #[cfg(feature = "sdp")]
+            SupportedConeT::PSDTriangleConeT(dim) => checked_triangular_number(*dim)?,
+            _ => self.nvars(),
+        };
+        let nHs = match self {
+            SupportedConeT::ExponentialConeT() | SupportedConeT::PowerConeT(_) => {
+                triangular_number(nvars)
+            }
+            SupportedConeT::SecondOrderConeT(dim) if *dim <= SOC_NO_EXPANSION_MAX_SIZE => {
+                triangular_number(nvars)
+            }
+            #[cfg(feature = "sdp")]
+            SupportedConeT::PSDTriangleConeT(_) => checked_triangular_number(nvars)?,
+            // cones with a diagonal Hs block
+            _ => nvars,
+        };
+        Some((nvars, nHs))
+    }
+}
+
+impl<T> SupportedConeT<T>
+where
+    T: FloatT,
+{
+    // check the cone parameters, returning a description of
+    // the first problem found.   Cones constructed from parameters
+    // that pass these checks do not panic.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        match self {
+            SupportedConeT::SecondOrderConeT(dim) if *dim < 2 => Err(format!(
+                "second order cone has dimension {}, but must have dimension at least 2",
+                dim
+            )),
+            SupportedConeT::PowerConeT(α) if !(*α > T::zero() && *α < T::one()) => Err(format!(
+                "power cone has power {}, but powers must be in (0,1)",
+                α
+            )),
+            SupportedConeT::GenPowerConeT(α, _) => {
+                if let Some(αi) = α.iter().find(|&&αi| αi.is_nan() || αi <= T::zero()) {
+                    return Err(format!(
+                        "generalized power cone has power {}, but powers must be positive",
+                        αi
+                    ));
+                }
+                let tol = T::epsilon() * α.len().as_T() * (0.5).as_T();
+                let err = (T::one() - α.sum()).abs();
+                if err.is_nan() || err >= tol {
+                    return Err(format!(
+                        "generalized power cone has powers summing to {}, but they must sum to 1",
+                        α.sum()
+                    ));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<T> core::fmt::Display for SupportedConeT<T>
 where
     T: FloatT,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{}", &self.as_tag().as_str())
     }
 }
@@ -145,6 +210,11 @@ pub(crate) enum SupportedConeTag {
     PSDTriangleCone,
 }
 
+impl SupportedConeTag {
+    /// Upper bound on the number of tags, for tag-indexed arrays.
+    pub(crate) const COUNT: usize = 7;
+}
+
 pub(crate) trait SupportedConeAsTag {
     fn as_tag(&self) -> SupportedConeTag;
 }
@@ -212,7 +282,7 @@ pub(crate) struct RangeSupportedConesIterator<'a, T> {
 
 #[cfg_attr(not(feature = "sdp"), allow(dead_code))]
 impl<'a, T> Iterator for RangeSupportedConesIterator<'a, T> {
-    type Item = std::ops::Range<usize>;
+    type Item = core::ops::Range<usize>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < self.cones.len() {
@@ -251,9 +321,9 @@ fn test_cone_ranges() {
         SupportedConeT::SecondOrderConeT::<f64>(4),
     ];
 
-    let rngs: Vec<std::ops::Range<usize>> = vec![0..3, 3..3, 3..7];
+    let rngs: Vec<core::ops::Range<usize>> = vec![0..3, 3..3, 3..7];
 
-    for (rng, conerng) in std::iter::zip(rngs.iter(), cones.rng_cones_iter()) {
+    for (rng, conerng) in core::iter::zip(rngs.iter(), cones.rng_cones_iter()) {
         assert_eq!(*rng, conerng);
     }
 }
@@ -8,6 +8,10 @@ use core::marker::PhantomData;
 
 pub struct ZeroCone<T> {
     dim: usize,
+    // number of scaling updates, for checking that
+    // the composite cone skips repeated updates
+    #[cfg(test)]
+    pub(crate) scaling_updates: usize,
     phantom: PhantomData<T>,
 }
 
@@ -18,6 +22,8 @@ where
     pub fn new(dim: usize) -> Self {
         Self {
             dim,
+            #[cfg(test)]
+            scaling_updates: 0,
             phantom: PhantomData,
         }
     }
@@ -47,6 +53,10 @@ where
         true
     }
 
+    fn scaling_differs(&self, _a: ScalingStrategy, _b: ScalingStrategy) -> bool {
+        false
+    }
+
     fn rectify_equilibration(&self, δ: &mut [T], _e: &[T]) -> bool {
         δ.set(T::one());
         false
@@ -73,6 +83,10 @@ where
         z.fill(T::zero());
     }
 
+    fn is_in_cone_interior(&mut self, s: &[T]) -> bool {
+        s.iter().all(|&si| si == T::zero())
+    }
+
     fn set_identity_scaling(&mut self) {
         //nothing to do
     }
@@ -84,6 +98,14 @@ where
         _μ: T,
         _scaling_strategy: ScalingStrategy,
     ) -> bool {
+        #[cfg(test)]
+        {
+            self.scaling_updates += 1;
+        }
+        true
+    }
+
+    fn scaling_is_constant(&self) -> bool {
         true
     }
 
@@ -99,6 +121,22 @@ where
         y.fill(T::zero());
     }
 
+    fn scaling_factor_W(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn scaling_factor_W_inv(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn apply_W(&mut self, _x: &mut [T]) {
+        //nothing to do
+    }
+
+    fn apply_W_inv(&mut self, _x: &mut [T]) {
+        //nothing to do
+    }
+
     fn affine_ds(&self, ds: &mut [T], _s: &[T]) {
         ds.fill(T::zero());
     }
@@ -0,0 +1,94 @@
+use crate::algebra::FloatT;
+
+/// Counts of the main computational operations performed during a solve.
+///
+/// Counters are reset at the start of each call to `solve` and can
+/// be read from the solver info after it completes.
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpCounters {
+    /// number of KKT system solves, excluding iterative refinement
+    pub kkt_solves: u64,
+    /// number of iterative refinement steps applied to KKT solves
+    pub refinement_steps: u64,
+    /// number of numeric factorizations of the KKT matrix
+    pub factorizations: u64,
+    /// number of cone scaling updates
+    pub cone_scaling_updates: u64,
+    /// number of barrier evaluations when backtracking steps for nonsymmetric cones
+    pub backtrack_evaluations: u64,
+    /// number of steps retried without a corrector after a vanishing step length
+    pub uncorrected_step_retries: u64,
+    /// number of steps retried as pure centering steps after a vanishing step length
+    pub centering_step_retries: u64,
+}
+
+impl OpCounters {
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl core::ops::AddAssign for OpCounters {
+    fn add_assign(&mut self, other: Self) {
+        self.kkt_solves += other.kkt_solves;
+        self.refinement_steps += other.refinement_steps;
+        self.factorizations += other.factorizations;
+        self.cone_scaling_updates += other.cone_scaling_updates;
+        self.backtrack_evaluations += other.backtrack_evaluations;
+        self.uncorrected_step_retries += other.uncorrected_step_retries;
+        self.centering_step_retries += other.centering_step_retries;
+    }
+}
+
+/// Iterative refinement statistics for the KKT solves of a solve.
+///
+/// Residuals are the infinity norm of `b - Kx` for the unregularized KKT
+/// matrix `K`, and are only computed when iterative refinement is enabled.
+/// For solves of several right hand sides together, the steps and residual
+/// of a solve are the largest over its right hand sides.
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct RefinementStats<T> {
+    /// refinement steps taken in the most recent KKT solve
+    pub last_steps: u32,
+    /// residual after the most recent KKT solve
+    pub last_residual: T,
+    /// most refinement steps taken in any KKT solve
+    pub max_steps: u32,
+    /// largest residual after any KKT solve
+    pub max_residual: T,
+    /// number of KKT solves that did not reach the refinement tolerance,
+    /// counting each right hand side of a multiple RHS solve
+    pub failures: u64,
+    /// number of refactorizations with increased static regularization
+    /// after a solve did not reach the refinement tolerance
+    pub refactorizations: u64,
+}
+
+impl<T> RefinementStats<T>
+where
+    T: FloatT,
+{
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(crate) fn record(&mut self, steps: u32, residual: T, failures: u64) {
+        self.last_steps = steps;
+        self.last_residual = residual;
+        self.max_steps = u32::max(self.max_steps, steps);
+        self.max_residual = T::max(self.max_residual, residual);
+        self.failures += failures;
+    }
+
+    // combine with the statistics of a later pass of the same solve
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.last_steps = other.last_steps;
+        self.last_residual = other.last_residual;
+        self.max_steps = u32::max(self.max_steps, other.max_steps);
+        self.max_residual = T::max(self.max_residual, other.max_residual);
+        self.failures += other.failures;
+        self.refactorizations += other.refactorizations;
+    }
+}
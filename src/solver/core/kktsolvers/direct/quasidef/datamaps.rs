@@ -2,6 +2,7 @@
 
 use super::*;
 use crate::solver::core::cones::*;
+use core::ops::Deref;
 use enum_dispatch::*;
 
 #[enum_dispatch(SparseExpansionMapTrait)]
@@ -207,7 +208,8 @@ where
         let sparse_data = self.sparse_data.as_ref().unwrap();
 
         let map = self.recover_map(map);
-        let η2 = self.η * self.η;
+        let (_, η) = self.Hs_factors();
+        let η2 = η * η;
 
         // off diagonal columns (or rows)
         updateFcn(ldl, K, &map.u, &sparse_data.u);
@@ -363,8 +365,8 @@ pub(crate) struct LDLDataMap {
 
 impl LDLDataMap {
     pub fn new<T: FloatT>(
-        Pmat: &CscMatrix<T>,
-        Amat: &CscMatrix<T>,
+        Pmat: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        Amat: &CscMatrix<T, impl Deref<Target = [usize]>>,
         cones: &CompositeCone<T>,
     ) -> Self {
         let (m, n) = (Amat.nrows(), Pmat.nrows());
@@ -5,18 +5,31 @@ use super::ldlsolvers::faer_ldl::*;
 
 use super::ldlsolvers::qdldl::*;
 use super::*;
-use crate::solver::core::kktsolvers::KKTSolver;
-use crate::solver::core::{cones::*, CoreSettings};
-use std::iter::zip;
+use crate::solver::core::kktsolvers::{KKTSolution, KKTSolver, KktSnapshot};
+use crate::solver::core::{cones::*, CoreSettings, OpCounters, RefinementStats};
+use crate::solver::RegularizationSchedule;
+use crate::timers::profile_scope;
+use alloc::borrow::Cow;
+use core::iter::zip;
+use core::ops::Deref;
 
 // -------------------------------------
 // KKTSolver using direct LDL factorisation
 // -------------------------------------
 
+// change in the adaptive regularization schedule after each
+// factorization, and its maximum increase over the initial value
+const ADAPTIVE_REG_FACTOR: f64 = 10.;
+const ADAPTIVE_REG_MAX_SCALE: f64 = 1e4;
+
+// increase in the static regularization constant when refactoring
+// after iterative refinement fails to reach its tolerance
+const REFACTOR_REG_FACTOR: f64 = 100.;
+
 // We require Send here to allow pyo3 builds to share
 // solver objects between threads.
 
-pub(crate) type BoxedDirectLDLSolver<T> = Box<dyn DirectLDLSolver<T> + Send>;
+pub(crate) type BoxedDirectLDLSolver<T> = Box<dyn DirectLDLSolver<T> + Send + Sync>;
 
 pub struct DirectLDLKKTSolver<T> {
     // problem dimensions
@@ -24,7 +37,8 @@ pub struct DirectLDLKKTSolver<T> {
     n: usize,
     p: usize,
 
-    // Left and right hand sides for solves
+    // Left and right hand sides for solves, with
+    // one column per RHS for multiple RHS solves
     x: Vec<T>,
     b: Vec<T>,
 
@@ -33,6 +47,9 @@ pub struct DirectLDLKKTSolver<T> {
     work1: Vec<T>,
     work2: Vec<T>,
 
+    // per column IR state for multiple RHS solves
+    refine_state: Vec<RefineState<T>>,
+
     // KKT mapping from problem data to KKT
     map: LDLDataMap,
 
@@ -49,17 +66,67 @@ pub struct DirectLDLKKTSolver<T> {
     // the direct linear LDL solver
     ldlsolver: BoxedDirectLDLSolver<T>,
 
-    // the diagonal regularizer currently applied
+    // the diagonal regularizer currently applied, the constant part
+    // from which it was computed, and whether the KKT matrix has been
+    // refactored with increased regularization since the last update
     diagonal_regularizer: T,
+    reg_constant: T,
+    refactored: bool,
+
+    // current solver iteration, for scheduled regularization
+    iteration: u32,
+
+    // multiplier applied to the adaptive regularization schedule,
+    // and whether iterative refinement has failed to reach its
+    // tolerance since the last factorization
+    adaptive_scale: T,
+    refinement_stalled: bool,
+
+    // whether every solve since the last update
+    // reached the iterative refinement tolerance
+    is_refined: bool,
+
+    // solve, refinement and factorization counts
+    counters: OpCounters,
+
+    // iterative refinement steps and residuals
+    refinement: RefinementStats<T>,
 }
 
 impl<T> DirectLDLKKTSolver<T>
 where
     T: FloatT,
 {
+    /// Bytes allocated by [`new`](Self::new) for the same problem data,
+    /// including the LDL factors.   The KKT matrix is assembled to make
+    /// a symbolic analysis of its factorization, and is then discarded.
+    ///
+    /// The QDLDL ordering and factor sizes are used for every LDL solver,
+    /// so the estimate is approximate for solvers other than QDLDL.
+    pub fn memory_estimate(
+        P: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        A: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        cones: &CompositeCone<T>,
+    ) -> usize {
+        // the lower triangular form has the same number of nonzeros,
+        // so the upper triangle is assembled for the symbolic analysis
+        let (KKT, _) = assemble_kkt_matrix(P, A, cones, MatrixTriangle::Triu);
+        let factors = QDLDLDirectLDLSolver::memory_estimate(&KKT).unwrap();
+
+        let (f, i) = (core::mem::size_of::<T>(), core::mem::size_of::<usize>());
+        let dim = KKT.nrows();
+
+        // KKT and the index maps into it, one entry per structural
+        // nonzero at most, then x, b, work1, work2, dsigns and Hsblocks
+        let kkt = KKT.memory_bytes() + KKT.nnz() * i;
+        let nHs = cones.rng_blocks.last().map_or(0, |rng| rng.end);
+        let vectors = dim * (4 * f + 1) + nHs * f;
+        kkt + vectors + factors
+    }
+
     pub fn new(
-        P: &CscMatrix<T>,
-        A: &CscMatrix<T>,
+        P: &CscMatrix<T, impl Deref<Target = [usize]>>,
+        A: &CscMatrix<T, impl Deref<Target = [usize]>>,
         cones: &CompositeCone<T>,
         m: usize,
         n: usize,
@@ -92,7 +159,10 @@ where
         let diagonal_regularizer = T::zero();
 
         // now make the LDL linear solver engine
-        let ldlsolver = ldl_ctor(&KKT, &dsigns, settings);
+        let ldlsolver = {
+            profile_scope!(Factor);
+            ldl_ctor(&KKT, &dsigns, settings)
+        };
 
         Self {
             m,
@@ -102,12 +172,21 @@ where
             b,
             work1,
             work2,
+            refine_state: Vec::new(),
             map,
             dsigns,
             Hsblocks,
             KKT,
             ldlsolver,
             diagonal_regularizer,
+            reg_constant: T::zero(),
+            refactored: false,
+            iteration: 0,
+            adaptive_scale: T::one(),
+            refinement_stalled: false,
+            is_refined: false,
+            counters: OpCounters::default(),
+            refinement: RefinementStats::default(),
         }
     }
 }
@@ -123,9 +202,26 @@ where
         cones.get_Hs(&mut self.Hsblocks);
 
         let (values, index) = (&mut self.Hsblocks, &map.Hsblocks);
-        // change signs to get -W^TW
-        values.negate();
-        _update_values(&mut self.ldlsolver, &mut self.KKT, index, values);
+        if cones.skips_constant_Hs() {
+            // blocks for cones with constant scaling are already in place
+            for rng in cones.rng_blocks_variable.iter() {
+                let values = &mut values[rng.clone()];
+                values.negate();
+                _update_values(
+                    &mut self.ldlsolver,
+                    &mut self.KKT,
+                    &index[rng.clone()],
+                    values,
+                );
+            }
+        } else {
+            // change signs to get -W^TW
+            values.negate();
+            _update_values(&mut self.ldlsolver, &mut self.KKT, index, values);
+        }
+
+        self.is_refined = true;
+        self.refactored = false;
 
         let mut sparse_map_iter = map.sparse_maps.iter();
         let ldl = &mut self.ldlsolver;
@@ -139,15 +235,12 @@ where
             }
         }
 
-        self.regularize_and_refactor(settings)
+        let constant = self.regularization_constant(settings);
+        self.regularize_and_refactor(constant, settings)
     }
 
     fn setrhs(&mut self, rhsx: &[T], rhsz: &[T]) {
-        let (m, n, p) = (self.m, self.n, self.p);
-
-        self.b[0..n].copy_from(rhsx);
-        self.b[n..(n + m)].copy_from(rhsz);
-        self.b[n + m..(n + m + p)].fill(T::zero());
+        self.setrhs_column(0, rhsx, rhsz);
     }
 
     fn solve(
@@ -156,30 +249,98 @@ where
         lhsz: Option<&mut [T]>,
         settings: &CoreSettings<T>,
     ) -> bool {
-        self.ldlsolver.solve(&self.KKT, &mut self.x, &self.b);
+        let dim = self.dim();
+        self.ldlsolver
+            .solve(&self.KKT, &mut self.x[0..dim], &self.b[0..dim]);
+        self.counters.kkt_solves += 1;
 
-        let is_success = {
-            if settings.iterative_refinement_enable {
-                self.iterative_refinement(settings)
-            } else {
-                self.x.is_finite()
+        let is_success = self.refine(1, settings);
+
+        if is_success {
+            self.getlhs(0, lhsx, lhsz);
+        }
+
+        is_success
+    }
+
+    fn solve_multiple(
+        &mut self,
+        rhs: &[(&[T], &[T])],
+        lhs: &mut [KKTSolution<T>],
+        settings: &CoreSettings<T>,
+    ) -> bool {
+        assert_eq!(rhs.len(), lhs.len());
+        let (dim, nrhs) = (self.dim(), rhs.len());
+
+        // grow the LHS/RHS and IR workspace to nrhs columns
+        if self.x.len() < dim * nrhs {
+            for v in [&mut self.x, &mut self.b, &mut self.work1, &mut self.work2] {
+                v.resize(dim * nrhs, T::zero());
             }
-        };
+        }
+
+        for (col, (rhsx, rhsz)) in rhs.iter().enumerate() {
+            self.setrhs_column(col, rhsx, rhsz);
+        }
+
+        let cols = 0..(dim * nrhs);
+        self.ldlsolver
+            .solve_multiple(&self.KKT, &mut self.x[cols.clone()], &self.b[cols], nrhs);
+        self.counters.kkt_solves += nrhs as u64;
+
+        let is_success = self.refine(nrhs, settings);
 
         if is_success {
-            self.getlhs(lhsx, lhsz);
+            for (col, (lhsx, lhsz)) in lhs.iter_mut().enumerate() {
+                self.getlhs(col, lhsx.as_deref_mut(), lhsz.as_deref_mut());
+            }
         }
 
         is_success
     }
 
-    fn update_P(&mut self, P: &CscMatrix<T>) {
+    fn update_P(&mut self, P: &CscMatrix<T, Cow<'_, [usize]>>) {
         _update_values(&mut self.ldlsolver, &mut self.KKT, &self.map.P, &P.nzval);
     }
 
-    fn update_A(&mut self, A: &CscMatrix<T>) {
+    fn update_A(&mut self, A: &CscMatrix<T, Cow<'_, [usize]>>) {
         _update_values(&mut self.ldlsolver, &mut self.KKT, &self.map.A, &A.nzval);
     }
+
+    fn counters_mut(&mut self) -> &mut OpCounters {
+        &mut self.counters
+    }
+
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<T> {
+        &mut self.refinement
+    }
+
+    fn begin_iteration(&mut self, iter: u32) {
+        if iter == 0 {
+            self.adaptive_scale = T::one();
+            self.refinement_stalled = false;
+        }
+        self.iteration = iter;
+    }
+
+    fn is_refined(&self) -> bool {
+        self.is_refined
+    }
+
+    fn snapshot(&self) -> KktSnapshot<T> {
+        KktSnapshot::new(
+            &self.KKT,
+            self.diagonal_regularizer,
+            &self.dsigns,
+            &self.map.diag_full,
+            self.ldlsolver.permutation(),
+        )
+    }
+
+    fn snapshot_rhs_lhs(&self, col: usize) -> (Vec<T>, Vec<T>) {
+        let rng = self.column(col);
+        (self.b[rng.clone()].to_vec(), self.x[rng].to_vec())
+    }
 }
 
 impl<T> DirectLDLKKTSolver<T>
@@ -187,8 +348,28 @@ where
     T: FloatT,
 {
     // extra helper functions, not required for KKTSolver trait
-    fn getlhs(&self, lhsx: Option<&mut [T]>, lhsz: Option<&mut [T]>) {
-        let x = &self.x;
+    fn dim(&self) -> usize {
+        self.m + self.n + self.p
+    }
+
+    // range of the given column in x, b and the IR workspace
+    fn column(&self, col: usize) -> core::ops::Range<usize> {
+        let dim = self.dim();
+        (col * dim)..((col + 1) * dim)
+    }
+
+    fn setrhs_column(&mut self, col: usize, rhsx: &[T], rhsz: &[T]) {
+        let (m, n, p) = (self.m, self.n, self.p);
+        let rng = self.column(col);
+        let b = &mut self.b[rng];
+
+        b[0..n].copy_from(rhsx);
+        b[n..(n + m)].copy_from(rhsz);
+        b[n + m..(n + m + p)].fill(T::zero());
+    }
+
+    fn getlhs(&self, col: usize, lhsx: Option<&mut [T]>, lhsz: Option<&mut [T]>) {
+        let x = &self.x[self.column(col)];
         let (m, n) = (self.m, self.n);
 
         if let Some(v) = lhsx {
@@ -199,12 +380,15 @@ where
         }
     }
 
-    fn regularize_and_refactor(&mut self, settings: &CoreSettings<T>) -> bool {
+    fn regularize_and_refactor(&mut self, constant: T, settings: &CoreSettings<T>) -> bool {
+        self.reg_constant = constant;
+        let dim = self.dim();
+
         let map = &self.map;
         let KKT = &mut self.KKT;
         let dsigns = &self.dsigns;
-        let diag_kkt = &mut self.work1;
-        let diag_shifted = &mut self.work2;
+        let diag_kkt = &mut self.work1[0..dim];
+        let diag_shifted = &mut self.work2[0..dim];
 
         if settings.static_regularization_enable {
             // hold a copy of the true KKT diagonal
@@ -213,7 +397,7 @@ where
                 *d = KKT.nzval[*idx];
             }
 
-            let eps = _compute_regularizer(diag_kkt, settings);
+            let eps = _compute_regularizer(diag_kkt, constant, settings);
 
             // compute an offset version, accounting for signs
             diag_shifted.copy_from(diag_kkt);
@@ -235,7 +419,11 @@ where
         }
 
         //refactor with new data
-        let is_success = self.ldlsolver.refactor(KKT);
+        let is_success = {
+            profile_scope!(Factor);
+            self.ldlsolver.refactor(KKT)
+        };
+        self.counters.factorizations += 1;
 
         if settings.static_regularization_enable {
             // put our internal copy of the KKT matrix back the way
@@ -248,9 +436,99 @@ where
         is_success
     }
 
-    fn iterative_refinement(&mut self, settings: &CoreSettings<T>) -> bool {
-        let (x, b) = (&mut self.x, &self.b);
-        let (e, dx) = (&mut self.work1, &mut self.work2);
+    // constant part of the static regularizer at the current iteration
+    fn regularization_constant(&mut self, settings: &CoreSettings<T>) -> T {
+        let schedule = match settings.static_regularization_schedule {
+            None => return settings.static_regularization_constant,
+            Some(schedule) => schedule,
+        };
+
+        if let RegularizationSchedule::Adaptive { .. } = schedule {
+            // increase after poorly refined solves, otherwise relax back
+            // towards the initial value
+            if self.refinement_stalled {
+                self.adaptive_scale = T::min(
+                    self.adaptive_scale * ADAPTIVE_REG_FACTOR.as_T(),
+                    ADAPTIVE_REG_MAX_SCALE.as_T(),
+                );
+            } else {
+                self.adaptive_scale =
+                    T::max(self.adaptive_scale / ADAPTIVE_REG_FACTOR.as_T(), T::one());
+            }
+            self.refinement_stalled = false;
+        }
+
+        schedule.value_at_iteration(self.iteration as usize) * self.adaptive_scale
+    }
+
+    // refines the solutions in the first nrhs columns of x, if enabled.
+    // When refinement fails to reach its tolerance, the KKT matrix is
+    // optionally refactored with increased static regularization, at
+    // most once per update, and the solve is repeated
+    fn refine(&mut self, nrhs: usize, settings: &CoreSettings<T>) -> bool {
+        let cols = 0..(self.dim() * nrhs);
+
+        if !settings.iterative_refinement_enable {
+            self.is_refined = false;
+            return self.x[cols].is_finite();
+        }
+
+        let Some(mut is_refined) = self.iterative_refinement(nrhs, settings) else {
+            self.is_refined = false;
+            return false;
+        };
+
+        // record poor refinement for the adaptive regularization schedule
+        self.refinement_stalled |= !is_refined;
+
+        if !is_refined
+            && settings.iterative_refinement_refactor
+            && settings.static_regularization_enable
+            && !self.refactored
+        {
+            self.refactored = true;
+            let constant = self.reg_constant * REFACTOR_REG_FACTOR.as_T();
+            if !self.regularize_and_refactor(constant, settings) {
+                self.is_refined = false;
+                return false;
+            }
+            self.refinement.refactorizations += 1;
+
+            self.ldlsolver.solve_multiple(
+                &self.KKT,
+                &mut self.x[cols.clone()],
+                &self.b[cols],
+                nrhs,
+            );
+            self.counters.kkt_solves += nrhs as u64;
+
+            let Some(retry_is_refined) = self.iterative_refinement(nrhs, settings) else {
+                self.is_refined = false;
+                return false;
+            };
+            is_refined = retry_is_refined;
+        }
+
+        let state = &self.refine_state;
+        let steps = state.iter().map(|st| st.steps).max().unwrap_or(0);
+        let residual = state.iter().fold(T::zero(), |r, st| T::max(r, st.norme));
+        let failures = state.iter().filter(|st| st.norme > st.tol).count();
+        self.refinement.record(steps, residual, failures as u64);
+        self.is_refined &= is_refined;
+
+        true
+    }
+
+    // refines the solutions in the first nrhs columns of x.  Each column
+    // is refined independently, with corrections for all columns solved
+    // together while every column is still being refined.  Returns None
+    // for a non-finite result, and otherwise whether every column
+    // reached the refinement tolerance
+    fn iterative_refinement(&mut self, nrhs: usize, settings: &CoreSettings<T>) -> Option<bool> {
+        let dim = self.dim();
+        let cols = 0..(dim * nrhs);
+        let (x, b) = (&mut self.x[cols.clone()], &self.b[cols.clone()]);
+        let (e, dx) = (&mut self.work1[cols.clone()], &mut self.work2[cols]);
 
         // iterative refinement params
         let reltol = settings.iterative_refinement_reltol;
@@ -259,56 +537,119 @@ where
         let stopratio = settings.iterative_refinement_stop_ratio;
 
         let K = &self.KKT;
-        let normb = b.norm_inf();
+        let state = &mut self.refine_state;
+        state.clear();
 
-        //compute the initial error
-        let mut norme = _get_refine_error(e, b, K, x);
+        //compute the initial errors
+        for col in 0..nrhs {
+            let rng = (col * dim)..((col + 1) * dim);
+            let norme =
+                _get_refine_error(&mut e[rng.clone()], &b[rng.clone()], K, &mut x[rng.clone()]);
 
-        if !norme.is_finite() {
-            return false;
+            if !norme.is_finite() {
+                return None;
+            }
+            let tol = abstol + reltol * b[rng].norm_inf();
+            state.push(RefineState {
+                tol,
+                norme,
+                steps: 0,
+                active: true,
+                in_dx: false,
+            });
         }
 
         for _ in 0..maxiter {
-            if norme <= (abstol + reltol * normb) {
-                //within tolerance.  Exit
+            //columns within tolerance exit
+            for st in state.iter_mut() {
+                st.active &= st.norme > st.tol;
+            }
+            let nactive = state.iter().filter(|st| st.active).count();
+            if nactive == 0 {
                 break;
             }
 
-            let lastnorme = norme;
+            //make a refinement.  While all columns are active, the current
+            //solutions are all in x or all in dx, so the corrections can
+            //be solved together
+            if nactive == nrhs {
+                let dst = if state[0].in_dx { &mut *x } else { &mut *dx };
+                self.ldlsolver.solve_multiple(K, dst, e, nrhs);
+            } else {
+                for (col, st) in state.iter().enumerate().filter(|(_, st)| st.active) {
+                    let rng = (col * dim)..((col + 1) * dim);
+                    let dst = if st.in_dx {
+                        &mut x[rng.clone()]
+                    } else {
+                        &mut dx[rng.clone()]
+                    };
+                    self.ldlsolver.solve(K, dst, &e[rng]);
+                }
+            }
+            self.counters.refinement_steps += nactive as u64;
+
+            for (col, st) in state.iter_mut().enumerate().filter(|(_, st)| st.active) {
+                let rng = (col * dim)..((col + 1) * dim);
+                let (x, dx) = (&mut x[rng.clone()], &mut dx[rng.clone()]);
+                let (x, dx) = if st.in_dx { (dx, x) } else { (x, dx) };
+                let e = &mut e[rng.clone()];
 
-            //make a refinement
-            self.ldlsolver.solve(K, dx, e);
+                let lastnorme = st.norme;
+                st.steps += 1;
 
-            //prospective solution is x + dx.  Use dx space to
-            // hold it for a check before applying to x
-            dx.axpby(T::one(), x, T::one());
+                //prospective solution is x + dx.  Use dx space to
+                // hold it for a check before applying to x
+                dx.axpby(T::one(), x, T::one());
 
-            norme = _get_refine_error(e, b, K, dx);
+                st.norme = _get_refine_error(e, &b[rng], K, dx);
 
-            if !norme.is_finite() {
-                return false;
-            }
+                if !st.norme.is_finite() {
+                    return None;
+                }
 
-            let improved_ratio = lastnorme / norme;
-            if improved_ratio < stopratio {
-                //insufficient improvement.  Exit
-                if improved_ratio > T::one() {
-                    std::mem::swap(x, dx);
+                let improved_ratio = lastnorme / st.norme;
+                if improved_ratio < stopratio {
+                    //insufficient improvement.  Exit
+                    if improved_ratio > T::one() {
+                        st.in_dx = !st.in_dx;
+                    } else {
+                        st.norme = lastnorme;
+                    }
+                    st.active = false;
+                    continue;
                 }
-                break;
+                st.in_dx = !st.in_dx;
             }
-            std::mem::swap(x, dx);
         }
-        //NB: "success" means only that we had a finite valued result
-        true
+
+        for (col, st) in state.iter().enumerate() {
+            // move solutions held in the dx space back to x
+            if st.in_dx {
+                let rng = (col * dim)..((col + 1) * dim);
+                x[rng.clone()].copy_from(&dx[rng]);
+            }
+        }
+
+        Some(state.iter().all(|st| st.norme <= st.tol))
     }
 }
 
-fn _compute_regularizer<T: FloatT>(diag_kkt: &[T], settings: &CoreSettings<T>) -> T {
+// iterative refinement state for one column of a solve
+struct RefineState<T> {
+    // refinement tolerance, current error norm and steps taken
+    tol: T,
+    norme: T,
+    steps: u32,
+    // still refining, and current solution held in the dx space
+    active: bool,
+    in_dx: bool,
+}
+
+fn _compute_regularizer<T: FloatT>(diag_kkt: &[T], constant: T, settings: &CoreSettings<T>) -> T {
     let maxdiag = diag_kkt.norm_inf();
 
     // Compute a new regularizer
-    settings.static_regularization_constant + settings.static_regularization_proportional * maxdiag
+    constant + settings.static_regularization_proportional * maxdiag
 }
 
 //  computes e = b - Kξ, overwriting the first argument
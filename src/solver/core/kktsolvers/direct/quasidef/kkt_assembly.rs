@@ -3,6 +3,7 @@
 use super::datamaps::*;
 use crate::algebra::*;
 use crate::solver::core::cones::*;
+use core::ops::Deref;
 use num_traits::Zero;
 
 pub(crate) fn allocate_kkt_Hsblocks<T, Z>(cones: &CompositeCone<T>) -> Vec<Z>
@@ -18,8 +19,8 @@ where
 }
 
 pub(crate) fn assemble_kkt_matrix<T: FloatT>(
-    P: &CscMatrix<T>,
-    A: &CscMatrix<T>,
+    P: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
     cones: &CompositeCone<T>,
     shape: MatrixTriangle,
 ) -> (CscMatrix<T>, LDLDataMap) {
@@ -50,8 +51,8 @@ pub(crate) fn assemble_kkt_matrix<T: FloatT>(
 }
 fn _kkt_assemble_colcounts<T: FloatT>(
     K: &mut CscMatrix<T>,
-    P: &CscMatrix<T>,
-    A: &CscMatrix<T>,
+    P: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
     cones: &CompositeCone<T>,
     map: &LDLDataMap,
     shape: MatrixTriangle,
@@ -102,8 +103,8 @@ fn _kkt_assemble_colcounts<T: FloatT>(
 
 fn _kkt_assemble_fill<T: FloatT>(
     K: &mut CscMatrix<T>,
-    P: &CscMatrix<T>,
-    A: &CscMatrix<T>,
+    P: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    A: &CscMatrix<T, impl Deref<Target = [usize]>>,
     cones: &CompositeCone<T>,
     map: &mut LDLDataMap,
     shape: MatrixTriangle,
@@ -4,6 +4,9 @@ use crate::qdldl::*;
 use crate::solver::core::kktsolvers::direct::DirectLDLSolver;
 use crate::solver::core::CoreSettings;
 
+// AMD dense row threshold scaling.  See the note in `new`.
+pub(crate) const QDLDL_AMD_DENSE_SCALE: f64 = 1.5;
+
 pub struct QDLDLDirectLDLSolver<T> {
     //KKT matrix and its QDLDL factorization
     factors: QDLDLFactorisation<T>,
@@ -33,7 +36,7 @@ where
             .regularize_enable(true)
             .regularize_eps(settings.dynamic_regularization_eps)
             .regularize_delta(settings.dynamic_regularization_delta)
-            .amd_dense_scale(1.5)
+            .amd_dense_scale(QDLDL_AMD_DENSE_SCALE)
             .build()
             .unwrap();
 
@@ -41,6 +44,21 @@ where
 
         Self { factors }
     }
+
+    /// Bytes allocated by [`new`](Self::new) for the factorization of
+    /// `KKT`, predicted from a symbolic analysis of its sparsity pattern.
+    pub fn memory_estimate(KKT: &CscMatrix<T>) -> Result<usize, QDLDLError> {
+        let (n, nnz) = (KKT.nrows(), KKT.nnz());
+        let Lnz = symbolic_factor_nnz(KKT, QDLDL_AMD_DENSE_SCALE)?;
+        let (f, i) = (core::mem::size_of::<T>(), core::mem::size_of::<usize>());
+
+        // L, and the permuted copy of KKT with its map from KKT
+        let matrices = (Lnz + nnz) * (f + i) + 2 * (n + 1) * i + nnz * i;
+        // D, Dinv and fwork, Dsigns and bwork, then etree,
+        // Lnz, iwork (3n), perm and iperm
+        let vectors = n * (3 * f + 2) + 7 * n * i;
+        Ok(matrices + vectors)
+    }
 }
 
 impl<T> DirectLDLSolver<T> for QDLDLDirectLDLSolver<T>
@@ -67,6 +85,11 @@ where
         self.factors.solve(x);
     }
 
+    fn solve_multiple(&mut self, _kkt: &CscMatrix<T>, x: &mut [T], b: &[T], nrhs: usize) {
+        x.copy_from(b);
+        self.factors.solve_multiple(x, nrhs);
+    }
+
     fn refactor(&mut self, _kkt: &CscMatrix<T>) -> bool {
         //QDLDL has maintained its own version of the permuted
         //KKT matrix through custom update/scale/offset methods,
@@ -75,6 +98,10 @@ where
         self.factors.Dinv.is_finite()
     }
 
+    fn permutation(&self) -> Option<&[usize]> {
+        Some(&self.factors.perm)
+    }
+
     fn required_matrix_shape() -> MatrixTriangle {
         MatrixTriangle::Triu
     }
@@ -17,7 +17,20 @@ pub trait DirectLDLSolver<T: FloatT> {
     #[allow(dead_code)] //PJG: could be removed.
     fn offset_values(&mut self, index: &[usize], offset: T, signs: &[i8]);
     fn solve(&mut self, kkt: &CscMatrix<T>, x: &mut [T], b: &[T]);
+    // solves for the nrhs columns of b, stored contiguously.  Solvers
+    // should override this to make a single pass over their factors
+    fn solve_multiple(&mut self, kkt: &CscMatrix<T>, x: &mut [T], b: &[T], nrhs: usize) {
+        let dim = kkt.nrows();
+        assert_eq!(b.len(), dim * nrhs);
+        for (x, b) in core::iter::zip(x.chunks_exact_mut(dim), b.chunks_exact(dim)) {
+            self.solve(kkt, x, b);
+        }
+    }
     fn refactor(&mut self, kkt: &CscMatrix<T>) -> bool;
+    // the fill reducing permutation of the factorization, if known
+    fn permutation(&self) -> Option<&[usize]> {
+        None
+    }
     fn required_matrix_shape() -> MatrixTriangle
     where
         Self: Sized;
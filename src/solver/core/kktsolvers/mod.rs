@@ -1,9 +1,17 @@
 #![allow(non_snake_case)]
-use super::{cones::CompositeCone, CoreSettings};
+use super::{cones::CompositeCone, CoreSettings, OpCounters, RefinementStats};
 use crate::algebra::*;
+use alloc::borrow::Cow;
 
 pub mod direct;
 
+mod snapshot;
+pub use snapshot::*;
+
+// the (x,z) parts of the solution for one RHS of a
+// multiple RHS solve, with either part optional
+pub type KKTSolution<'a, T> = (Option<&'a mut [T]>, Option<&'a mut [T]>);
+
 pub trait KKTSolver<T: FloatT> {
     fn update(&mut self, cones: &CompositeCone<T>, settings: &CoreSettings<T>) -> bool;
     fn setrhs(&mut self, x: &[T], z: &[T]);
@@ -13,6 +21,31 @@ pub trait KKTSolver<T: FloatT> {
         z: Option<&mut [T]>,
         settings: &CoreSettings<T>,
     ) -> bool;
-    fn update_P(&mut self, P: &CscMatrix<T>);
-    fn update_A(&mut self, A: &CscMatrix<T>);
+    // solves for several right hand sides together, given as (x,z)
+    // pairs, with each solution written to the corresponding pair in lhs
+    fn solve_multiple(
+        &mut self,
+        rhs: &[(&[T], &[T])],
+        lhs: &mut [KKTSolution<T>],
+        settings: &CoreSettings<T>,
+    ) -> bool;
+    fn update_P(&mut self, P: &CscMatrix<T, Cow<'_, [usize]>>);
+    fn update_A(&mut self, A: &CscMatrix<T, Cow<'_, [usize]>>);
+    fn counters_mut(&mut self) -> &mut OpCounters;
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<T>;
+
+    // called at the start of each solver iteration, before
+    // any update of the KKT system at that iteration
+    fn begin_iteration(&mut self, _iter: u32) {}
+
+    // true if every solve since the last update met
+    // the iterative refinement tolerance
+    fn is_refined(&self) -> bool {
+        false
+    }
+
+    // copies of the current KKT matrix, and of the RHS and solution
+    // of the given column of the most recent solve, for diagnostics
+    fn snapshot(&self) -> KktSnapshot<T>;
+    fn snapshot_rhs_lhs(&self, col: usize) -> (Vec<T>, Vec<T>);
 }
@@ -0,0 +1,112 @@
+#![allow(non_snake_case)]
+use crate::algebra::*;
+use crate::qdldl::{permute, permute_symmetric};
+use core::iter::zip;
+
+/// Copy of the KKT system at one iteration of the solver, for
+/// analysis of convergence problems with external tools.
+///
+/// Snapshots are recorded when the
+/// [`kkt_snapshot_iteration`](crate::solver::DefaultSettings::kkt_snapshot_iteration)
+/// setting is nonzero, and are retrieved with
+/// [`DefaultSolver::kkt_snapshot`](crate::solver::DefaultSolver::kkt_snapshot).
+///
+/// The KKT matrix is that of the internal, equilibrated problem data.
+/// It is stored as its upper triangle, without the static regularization
+/// applied before factorization and in its original (unpermuted) ordering.
+/// Use [`matrix`](Self::matrix) for other variants.   Each RHS vector is
+/// paired with the corresponding solution computed in the solver.
+#[derive(Debug, Clone)]
+pub struct KktSnapshot<T = f64> {
+    /// the iteration at which the snapshot was taken
+    pub iteration: u32,
+    /// upper triangle of the KKT matrix, without static regularization
+    pub K: CscMatrix<T>,
+    /// the static regularization applied to the diagonal of `K`
+    pub static_regularization: T,
+    /// signs of the static regularization for each diagonal entry
+    pub dsigns: Vec<i8>,
+    /// fill reducing permutation used in the factorization, if known
+    pub perm: Option<Vec<usize>>,
+    /// RHS and solution of the solve for the constant part of the step
+    pub rhs_constant: Vec<T>,
+    pub lhs_constant: Vec<T>,
+    /// RHS and solution of the predictor (affine) step solve
+    pub rhs_predictor: Vec<T>,
+    pub lhs_predictor: Vec<T>,
+    /// RHS and solution of the corrector (combined) step solve
+    pub rhs_corrector: Vec<T>,
+    pub lhs_corrector: Vec<T>,
+
+    // indices of the diagonal entries of K
+    diag_index: Vec<usize>,
+}
+
+impl<T> KktSnapshot<T>
+where
+    T: FloatT,
+{
+    pub(crate) fn new(
+        K: &CscMatrix<T>,
+        static_regularization: T,
+        dsigns: &[i8],
+        diag_index: &[usize],
+        perm: Option<&[usize]>,
+    ) -> Self {
+        Self {
+            iteration: 0,
+            K: K.clone(),
+            static_regularization,
+            dsigns: dsigns.to_vec(),
+            perm: perm.map(|p| p.to_vec()),
+            rhs_constant: Vec::new(),
+            lhs_constant: Vec::new(),
+            rhs_predictor: Vec::new(),
+            lhs_predictor: Vec::new(),
+            rhs_corrector: Vec::new(),
+            lhs_corrector: Vec::new(),
+            diag_index: diag_index.to_vec(),
+        }
+    }
+
+    /// Upper triangle of the KKT matrix, optionally with the static
+    /// regularization applied and symmetrically permuted as in the
+    /// factorization.   The dynamic regularization applied during
+    /// factorization is not included.
+    ///
+    /// Panics if `permuted` is set and the permutation is not known.
+    pub fn matrix(&self, regularized: bool, permuted: bool) -> CscMatrix<T> {
+        let mut K = self.K.clone();
+
+        if regularized {
+            let eps = self.static_regularization;
+            for (&idx, &sign) in zip(&self.diag_index, &self.dsigns) {
+                if sign == 1 {
+                    K.nzval[idx] += eps;
+                } else {
+                    K.nzval[idx] -= eps;
+                }
+            }
+        }
+
+        if permuted {
+            let iperm = invperm(self.perm.as_ref().expect("permutation not known"));
+            K = permute_symmetric(&K, &iperm).0;
+        }
+        K
+    }
+
+    /// Copy of `v`, permuted as for [`matrix`](Self::matrix) if `permuted`
+    /// is set, i.e. with entries `v[perm[i]]`.
+    pub fn vector(&self, v: &[T], permuted: bool) -> Vec<T> {
+        let mut out = v.to_vec();
+        if permuted {
+            permute(
+                &mut out,
+                v,
+                self.perm.as_ref().expect("permutation not known"),
+            );
+        }
+        out
+    }
+}
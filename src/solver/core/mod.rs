@@ -11,7 +11,9 @@ pub mod kktsolvers;
 pub mod traits;
 
 //partially flatten top level pieces
+mod counters;
 mod settings;
 mod solver;
+pub use counters::*;
 pub use settings::*;
 pub use solver::*;
@@ -2,9 +2,10 @@ use self::internal::*;
 use super::cones::Cone;
 use super::traits::*;
 use crate::algebra::*;
+use crate::solver::utils::panics::{catch_unwind, panic_message, resume_unwind, AssertUnwindSafe};
 use crate::stdio;
+use crate::stdio::Write;
 use crate::timers::*;
-use std::io::Write;
 
 // ---------------------------------
 // Solver status type
@@ -70,9 +71,21 @@ pub enum StepDirection {
 /// linearizing centrality conditions.  
 #[repr(u32)]
 #[derive(PartialEq, Eq, Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScalingStrategy {
+    /// Nesterov-Todd scaling for symmetric cones, and primal-dual
+    /// scaling for nonsymmetric cones.
     PrimalDual,
+    /// Nesterov-Todd scaling for symmetric cones, and dual
+    /// scaling for nonsymmetric cones.
     Dual,
+    /// Scaling `Hs = (μ∇²f(s))⁻¹` computed from the primal
+    /// barrier only, e.g. `W = diag(s)/√μ` for nonnegative cones.
+    /// Nonsymmetric cones use dual scaling.
+    PrimalOnly,
+    /// Scaling `Hs = μ∇²f*(z)` computed from the dual barrier
+    /// only, e.g. `W = √μ·diag(z)⁻¹` for nonnegative cones.
+    DualOnly,
 }
 
 /// An enum for reporting strategy checkpointing
@@ -84,8 +97,16 @@ enum StrategyCheckpoint {
     Fail,                    // Checkpoint found a problem but no more ScalingStrategies to try
 }
 
-impl std::fmt::Display for SolverStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// Stage of the fallback sequence after a vanishing step length
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+enum StepFallback {
+    None,        // no fallback in use
+    NoCorrector, // retrying without the Mehrotra correction, with dual scaling
+    Centering,   // retrying with a pure centering step
+}
+
+impl core::fmt::Display for SolverStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
@@ -120,11 +141,17 @@ pub struct Solver<D, V, R, K, C, I, SO, SE> {
     pub solution: SO,
     pub settings: SE,
     pub timers: Option<Timers>,
+    // user supplied starting point for the next solve, in place
+    // of the default start
+    pub(crate) warm_start: Option<V>,
+    // set when a panic is caught during a solve, after
+    // which the solver state can no longer be trusted
+    pub(crate) poisoned: bool,
 }
 
-fn _print_banner(is_verbose: bool) -> std::io::Result<()> {
+fn _print_banner(is_verbose: bool) -> stdio::Result<()> {
     if !is_verbose {
-        return std::io::Result::Ok(());
+        return stdio::Result::Ok(());
     }
 
     let mut out = stdio::stdout();
@@ -157,7 +184,7 @@ fn _print_banner(is_verbose: bool) -> std::io::Result<()> {
         out,
         "-------------------------------------------------------------"
     )?;
-    std::io::Result::Ok(())
+    stdio::Result::Ok(())
 }
 
 // ---------------------------------
@@ -174,6 +201,11 @@ fn _print_banner(is_verbose: bool) -> std::io::Result<()> {
 pub trait IPSolver<T, D, V, R, K, C, I, SO, SE> {
     /// Run the solver
     fn solve(&mut self);
+
+    /// Returns `true` if a panic was caught during an earlier solve,
+    /// in which case the solver can not be used further.  See the
+    /// `catch_panics` setting of [`DefaultSettings`](crate::solver::DefaultSettings).
+    fn is_poisoned(&self) -> bool;
 }
 
 impl<T, D, V, R, K, C, I, SO, SE> IPSolver<T, D, V, R, K, C, I, SO, SE>
@@ -181,227 +213,45 @@ impl<T, D, V, R, K, C, I, SO, SE> IPSolver<T, D, V, R, K, C, I, SO, SE>
 where
     T: FloatT,
     D: ProblemData<T, V = V>,
-    V: Variables<T, D = D, R = R, C = C, SE = SE>,
-    R: Residuals<T, D = D, V = V>,
-    K: KKTSystem<T, D = D, V = V, C = C, SE = SE>,
+    V: Variables<T, D, R = R, C = C, SE = SE>,
+    R: Residuals<T, D, V = V>,
+    K: KKTSystem<T, D, V = V, C = C, SE = SE>,
     C: Cone<T>,
-    I: Info<T, D = D, V = V, R = R, C = C, SE = SE>,
-    SO: Solution<T, D = D, V = V, I = I, SE = SE>,
+    I: Info<T, D, V = V, R = R, C = C, SE = SE>,
+    SO: Solution<T, D, V = V, I = I, SE = SE>,
     SE: Settings<T>,
 {
     fn solve(&mut self) {
-        // various initializations
-        let mut iter: u32 = 0;
-        let mut σ = T::one();
-        let mut α = T::zero();
-        let mut μ;
-
-        //timers is stored as an option so that
-        //we can swap it out here and avoid
-        //borrow conflicts with other fields.
-        let mut timers = self.timers.take().unwrap();
+        if self.poisoned {
+            self.info
+                .record_panic("solver was poisoned by a panic during an earlier solve");
+            self.solution.abandon(&self.info);
+            return;
+        }
 
         // solver release info, solver config
         // problem dimensions, cone types etc
-        notimeit! {timers; {
-            _print_banner(self.settings.core().verbose).unwrap();
-            self.info.print_configuration(&self.settings, &self.data, &self.cones).unwrap();
-            self.info.print_status_header(&self.settings).unwrap();
-        }}
-
-        self.info.reset(&mut timers);
-
-        timeit! {timers => "solve"; {
-
-        // initialize variables to some reasonable starting point
-        timeit!{timers => "default start"; {
-            self.default_start();
-        }}
-
-        timeit!{timers => "IP iteration"; {
-
-        // ----------
-        // main loop
-        // ----------
-
-        let mut scaling = {
-            if self.cones.allows_primal_dual_scaling() {ScalingStrategy::PrimalDual}
-            else {ScalingStrategy::Dual}
-        };
-
-        loop {
-
-            //update the residuals
-            //--------------
-            self.residuals.update(&self.variables, &self.data);
-
-            //calculate duality gap (scaled)
-            //--------------
-            μ = self.variables.calc_mu(&self.residuals, &self.cones);
-
-            // record scalar values from most recent iteration.
-            // This captures μ at iteration zero.
-            self.info.save_scalars(μ, α, σ, iter);
-
-            // convergence check and printing
-            // --------------
-            self.info.update(
-                &mut self.data,
-                &self.variables,
-                &self.residuals,&timers);
-
-            notimeit!{timers; {
-                self.info.print_status(&self.settings).unwrap();
-            }}
-
-            let isdone = self.info.check_termination(&self.residuals, &self.settings, iter);
-
-            // check for termination due to slow progress and update strategy
-            if isdone{
-                    match self.strategy_checkpoint_insufficient_progress(scaling){
-                        StrategyCheckpoint::NoUpdate | StrategyCheckpoint::Fail => {break}
-                        StrategyCheckpoint::Update(s) => {scaling = s; continue}
-                    }
-            }  // allows continuation if new strategy provided
-
-
-            // update the scalings
-            // --------------
-            let is_scaling_success;
-            timeit!{timers => "scale cones"; {
-                is_scaling_success = self.variables.scale_cones(&mut self.cones,μ,scaling);
-            }}
-            // check whether variables are interior points
-            match self.strategy_checkpoint_is_scaling_success(is_scaling_success,scaling){
-                StrategyCheckpoint::Fail => {break}
-                StrategyCheckpoint::NoUpdate => {} // we only expect NoUpdate or Fail here
-                StrategyCheckpoint::Update(_) => {unreachable!()}
-            }
-
-            //increment counter here because we only count
-            //iterations that produce a KKT update
-            iter += 1;
-
-            // Update the KKT system and the constant parts of its solution.
-            // Keep track of the success of each step that calls KKT
-            // --------------
-            //PJG: This should be a Result in Rust, but needs changes down
-            //into the KKT solvers to do that.
-            let mut is_kkt_solve_success : bool;
-            timeit!{timers => "kkt update"; {
-                is_kkt_solve_success = self.kktsystem.update(&self.data, &self.cones, &self.settings);
-            }} // end "kkt update" timer
-
-            // calculate the affine step
-            // --------------
-            self.step_rhs
-                .affine_step_rhs(&self.residuals, &self.variables, &self.cones);
-
-            timeit!{timers => "kkt solve"; {
-                is_kkt_solve_success = is_kkt_solve_success &&
-                self.kktsystem.solve(
-                    &mut self.step_lhs,
-                    &self.step_rhs,
-                    &self.data,
-                    &self.variables,
-                    &mut self.cones,
-                    StepDirection::Affine,
-                    &self.settings,
-                );
-            }}  //end "kkt solve affine" timer
-
-            // combined step only on affine step success
-            if is_kkt_solve_success {
-
-                //calculate step length and centering parameter
-                // --------------
-                α = self.get_step_length(StepDirection::Affine, scaling);
-                σ = self.centering_parameter(α);
+        _print_banner(self.settings.core().verbose).unwrap();
+        self.info
+            .print_configuration(&self.settings, &self.data, &self.cones)
+            .unwrap();
 
-                // make a reduced Mehrotra correction in the first iteration
-                // to accommodate badly centred starting points
-                let m = if iter > 1 {T::one()} else {α};
-
-                // calculate the combined step and length
-                // --------------
-                self.step_rhs.combined_step_rhs(
-                    &self.residuals,
-                    &self.variables,
-                    &mut self.cones,
-                    &mut self.step_lhs,
-                    σ,
-                    μ,
-                    m
-                );
-
-                timeit!{timers => "kkt solve" ; {
-                    is_kkt_solve_success =
-                    self.kktsystem.solve(
-                        &mut self.step_lhs,
-                        &self.step_rhs,
-                        &self.data,
-                        &self.variables,
-                        &mut self.cones,
-                        StepDirection::Combined,
-                        &self.settings,
-                    );
-                }} //end "kkt solve"
-            }
-
-            // check for numerical failure and update strategy
-            match self.strategy_checkpoint_numerical_error(is_kkt_solve_success,scaling) {
-                StrategyCheckpoint::NoUpdate => {}
-                StrategyCheckpoint::Update(s) => {α = T::zero(); scaling = s; continue}
-                StrategyCheckpoint::Fail => {α = T::zero(); break}
-            }
-
-
-            // compute final step length and update the current iterate
-            // --------------
-            α = self.get_step_length(StepDirection::Combined,scaling);
-
-            // check for undersized step and update strategy
-            match self.strategy_checkpoint_small_step(α, scaling) {
-                StrategyCheckpoint::NoUpdate => {}
-                StrategyCheckpoint::Update(s) => {α = T::zero(); scaling = s; continue}
-                StrategyCheckpoint::Fail => {α = T::zero(); break}
-            }
-
-            // Copy previous iterate in case the next one is a dud
-            self.info.save_prev_iterate(&self.variables,&mut self.prev_vars);
-
-            self.variables.add_step(&self.step_lhs, α);
-
-        } //end loop
-        // ----------
-        // ----------
-
-        }} //end "IP iteration" timer
-
-        }} // end "solve" timer
-
-        // Check we if actually took a final step.  If not, we need
-        // to recapture the scalars and print one last line
-        if α == T::zero() {
-            self.info.save_scalars(μ, α, σ, iter);
-            notimeit! {timers; {self.info.print_status(&self.settings).unwrap();}}
+        if !self.settings.core().catch_panics {
+            return self.solve_with_recovery();
         }
 
-        timeit! {timers => "post-process"; {
-            //check for "almost" convergence case and then extract solution
-            self.info.post_process(&self.residuals, &self.settings);
-            self.solution
-                .post_process(&self.data, &mut self.variables, &self.info, &self.settings);
-        }}
-
-        //halt timers
-        self.info.finalize(&mut timers);
-        self.solution.finalize(&self.info);
-
-        self.info.print_footer(&self.settings).unwrap();
+        // the solver is poisoned after a panic, so is not used
+        // again in whatever state the panic left it
+        let result = catch_unwind(AssertUnwindSafe(|| self.solve_with_recovery()));
+        if let Err(payload) = result {
+            self.poisoned = true;
+            self.info.record_panic(&panic_message(payload.as_ref()));
+            self.solution.abandon(&self.info);
+        }
+    }
 
-        //stow the timers back into Option in the solver struct
-        self.timers.replace(timers);
+    fn is_poisoned(&self) -> bool {
+        self.poisoned
     }
 }
 
@@ -412,7 +262,16 @@ mod internal {
     use super::*;
 
     pub(super) trait IPSolverInternals<T, D, V, R, K, C, I, SO, SE> {
-        /// Find an initial condition
+        /// Solve from a default start, re-solving with more conservative
+        /// settings on numerical failure if automatic recovery is enabled
+        fn solve_with_recovery(&mut self);
+
+        /// Run a single pass of the IP method from a default start,
+        /// using the given initial scaling strategy
+        fn solve_attempt(&mut self, initial_scaling: ScalingStrategy);
+
+        /// Find an initial condition, or start from the
+        /// point supplied for a warm start if there is one
         fn default_start(&mut self);
 
         /// Compute a centering parameter
@@ -441,6 +300,8 @@ mod internal {
             &mut self,
             α: T,
             scaling: ScalingStrategy,
+            fallback: &mut StepFallback,
+            iter: u32,
         ) -> StrategyCheckpoint;
 
         fn strategy_checkpoint_is_scaling_success(
@@ -455,16 +316,327 @@ mod internal {
     where
         T: FloatT,
         D: ProblemData<T, V = V>,
-        V: Variables<T, D = D, R = R, C = C, SE = SE>,
-        R: Residuals<T, D = D, V = V>,
-        K: KKTSystem<T, D = D, V = V, C = C, SE = SE>,
+        V: Variables<T, D, R = R, C = C, SE = SE>,
+        R: Residuals<T, D, V = V>,
+        K: KKTSystem<T, D, V = V, C = C, SE = SE>,
         C: Cone<T>,
-        I: Info<T, D = D, V = V, R = R, C = C, SE = SE>,
-        SO: Solution<T, D = D, V = V, I = I>,
+        I: Info<T, D, V = V, R = R, C = C, SE = SE>,
+        SO: Solution<T, D, V = V, I = I, SE = SE>,
         SE: Settings<T>,
     {
+        fn solve_with_recovery(&mut self) {
+            self.info.counters_mut().reset();
+            self.kktsystem.counters_mut().reset();
+            self.info.refinement_stats_mut().reset();
+            self.kktsystem.refinement_stats_mut().reset();
+            if let Some(timers) = self.timers.as_mut() {
+                timers.start_timings(self.settings.core().timing);
+            }
+            let scaling = if self.cones.allows_primal_dual_scaling() {
+                ScalingStrategy::PrimalDual
+            } else {
+                ScalingStrategy::Dual
+            };
+            self.info.save_recovery(0, &self.settings, scaling);
+            self.solve_attempt(scaling);
+
+            if !self.settings.core().auto_recovery {
+                return;
+            }
+
+            // on numerical failure, re-solve from scratch with progressively
+            // more conservative settings, restoring the user's settings after.
+            // A panic during an attempt is re-raised once they are restored
+            let user_settings = self.settings.core().clone();
+
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                for attempt in 1..=user_settings.max_recovery_attempts {
+                    if self.info.get_status() != SolverStatus::NumericalError {
+                        break;
+                    }
+                    *self.settings.core_mut() = user_settings.recovery_profile(attempt);
+                    let scaling = ScalingStrategy::Dual;
+                    self.info.save_recovery(attempt, &self.settings, scaling);
+                    self.solve_attempt(scaling);
+                }
+            }));
+
+            *self.settings.core_mut() = user_settings;
+            if let Err(payload) = result {
+                resume_unwind(payload);
+            }
+        }
+
+        fn solve_attempt(&mut self, initial_scaling: ScalingStrategy) {
+            // various initializations
+            let mut iter: u32 = 0;
+            let mut σ = T::one();
+            let mut α = T::zero();
+            let mut μ;
+
+            //timers is stored as an option so that
+            //we can swap it out here and avoid
+            //borrow conflicts with other fields.
+            let mut timers = self.timers.take().unwrap();
+
+            // a table header for each attempt.  The banner and
+            // configuration are printed once per solve
+            notimeit! {timers; {
+                self.info.print_status_header(&self.settings).unwrap();
+            }}
+
+            self.info.reset(&mut timers);
+            self.kktsystem.begin_iteration(iter);
+            #[cfg(feature = "profiling")]
+            timers.start_profile();
+
+            timeit! {timers => "solve"; {
+
+            // initialize variables to some reasonable starting point
+            timeit!{timers => "default start"; {
+                profile_scope!(Cones);
+                self.default_start();
+            }}
+
+            timeit!{timers => "IP iteration"; {
+
+            // ----------
+            // main loop
+            // ----------
+
+            let mut scaling = initial_scaling;
+            let mut fallback = StepFallback::None;
+
+            loop {
+
+                {
+                profile_scope!(Termination);
+
+                //update the residuals
+                //--------------
+                self.residuals.update(&self.variables, &self.data);
+
+                //calculate duality gap (scaled)
+                //--------------
+                μ = self.variables.calc_mu(&self.residuals, &self.cones);
+
+                // record scalar values from most recent iteration.
+                // This captures μ at iteration zero.
+                self.info.save_scalars(μ, α, σ, iter);
+
+                // convergence check and printing
+                // --------------
+                self.info.update(
+                    &mut self.data,
+                    &self.variables,
+                    &self.residuals,&timers);
+                }
+
+                notimeit!{timers; {
+                    self.info.print_status(&self.settings).unwrap();
+                }}
+
+                let isdone;
+                let conservative;
+                {
+                profile_scope!(Termination);
+                isdone = self.info.check_termination(&self.residuals, &self.settings, iter);
+
+                // cap step lengths and drop the Mehrotra correction
+                // once close to convergence
+                conservative = self.info.check_conservative_steps(&self.settings);
+                }
+
+                // check for termination due to slow progress and update strategy
+                if isdone{
+                        match self.strategy_checkpoint_insufficient_progress(scaling){
+                            StrategyCheckpoint::NoUpdate | StrategyCheckpoint::Fail => {break}
+                            StrategyCheckpoint::Update(s) => {scaling = s; continue}
+                        }
+                }  // allows continuation if new strategy provided
+
+                // update the scalings
+                // --------------
+                let is_scaling_success;
+                timeit!{timers => "scale cones", cone_scaling; {
+                    profile_scope!(Cones);
+                    is_scaling_success = self.variables.scale_cones(&mut self.cones,μ,scaling);
+                }}
+                self.info.counters_mut().cone_scaling_updates += 1;
+                // check whether variables are interior points
+                match self.strategy_checkpoint_is_scaling_success(is_scaling_success,scaling){
+                    StrategyCheckpoint::NoUpdate => {}
+                    StrategyCheckpoint::Update(s) => {α = T::zero(); scaling = s; continue}
+                    StrategyCheckpoint::Fail => {break}
+                }
+
+                //increment counter here because we only count
+                //iterations that produce a KKT update
+                iter += 1;
+                self.kktsystem.begin_iteration(iter);
+
+                // Update the KKT system and the constant parts of its solution.
+                // Keep track of the success of each step that calls KKT
+                // --------------
+                //PJG: This should be a Result in Rust, but needs changes down
+                //into the KKT solvers to do that.
+                let mut is_kkt_solve_success : bool;
+                timeit!{timers => "kkt update", factorization; {
+                    is_kkt_solve_success = self.kktsystem.update(&self.data, &self.cones, &self.settings);
+                }} // end "kkt update" timer
+
+                // calculate the affine step
+                // --------------
+                {
+                profile_scope!(Cones);
+                self.step_rhs
+                    .affine_step_rhs(&self.residuals, &self.variables, &self.cones);
+                }
+
+                timeit!{timers => "kkt solve", affine_solve; {
+                    is_kkt_solve_success = is_kkt_solve_success &&
+                    self.kktsystem.solve(
+                        &mut self.step_lhs,
+                        &self.step_rhs,
+                        &self.data,
+                        &self.variables,
+                        &mut self.cones,
+                        StepDirection::Affine,
+                        &self.settings,
+                    );
+                }}  //end "kkt solve affine" timer
+
+                // combined step only on affine step success
+                if is_kkt_solve_success {
+
+                    //calculate step length and centering parameter
+                    // --------------
+                    α = self.get_step_length(StepDirection::Affine, scaling);
+                    σ = self.centering_parameter(α);
+                    if fallback == StepFallback::Centering {
+                        σ = T::one();
+                    }
+
+                    // make a reduced Mehrotra correction in the first iteration
+                    // to accommodate badly centred starting points
+                    let m = if conservative || fallback != StepFallback::None {T::zero()}
+                            else if iter > 1 {T::one()} else {α};
+
+                    // calculate the combined step and length
+                    // --------------
+                    {
+                    profile_scope!(Cones);
+                    self.step_rhs.combined_step_rhs(
+                        &self.residuals,
+                        &self.variables,
+                        &mut self.cones,
+                        &mut self.step_lhs,
+                        σ,
+                        μ,
+                        m
+                    );
+                    }
+
+                    timeit!{timers => "kkt solve", combined_solve; {
+                        is_kkt_solve_success =
+                        self.kktsystem.solve(
+                            &mut self.step_lhs,
+                            &self.step_rhs,
+                            &self.data,
+                            &self.variables,
+                            &mut self.cones,
+                            StepDirection::Combined,
+                            &self.settings,
+                        );
+                    }} //end "kkt solve"
+                }
+
+                // check for numerical failure and update strategy
+                match self.strategy_checkpoint_numerical_error(is_kkt_solve_success,scaling) {
+                    StrategyCheckpoint::NoUpdate => {}
+                    StrategyCheckpoint::Update(s) => {α = T::zero(); scaling = s; continue}
+                    StrategyCheckpoint::Fail => {α = T::zero(); break}
+                }
+
+
+                // compute final step length and update the current iterate
+                // --------------
+                α = self.get_step_length(StepDirection::Combined,scaling);
+
+                if conservative {
+                    let fraction = self.settings.core().max_step_fraction;
+                    let cap = self.settings.core().conservative_step_fraction;
+                    if cap < fraction {
+                        α *= cap / fraction;
+                    }
+                }
+
+                // check for undersized step and update strategy
+                match self.strategy_checkpoint_small_step(α, scaling, &mut fallback, iter) {
+                    StrategyCheckpoint::NoUpdate => {}
+                    StrategyCheckpoint::Update(s) => {α = T::zero(); scaling = s; continue}
+                    StrategyCheckpoint::Fail => {α = T::zero(); break}
+                }
+
+                {
+                profile_scope!(LineSearch);
+
+                // Copy previous iterate in case the next one is a dud
+                self.info.save_prev_iterate(&self.variables,&mut self.prev_vars);
+
+                self.variables.add_step(&self.step_lhs, α);
+                if self.settings.core().incremental_residuals && self.kktsystem.is_step_accurate() {
+                    self.residuals.apply_step(&self.step_lhs, &self.step_rhs, α, &self.data);
+                }
+                }
+                fallback = StepFallback::None;
+
+            } //end loop
+            // ----------
+            // ----------
+
+            }} //end "IP iteration" timer
+
+            }} // end "solve" timer
+
+            // Check we if actually took a final step.  If not, we need
+            // to recapture the scalars and print one last line
+            if α == T::zero() {
+                self.info.save_scalars(μ, α, σ, iter);
+                notimeit! {timers; {self.info.print_status(&self.settings).unwrap();}}
+            }
+
+            timeit! {timers => "post-process"; {
+                profile_scope!(Termination);
+                //check for "almost" convergence case and then extract solution
+                self.info.post_process(&self.residuals, &self.settings);
+                self.solution
+                    .post_process(&self.data, &mut self.variables, &self.info, &self.settings);
+            }}
+
+            // collect KKT operation counts from this pass
+            let kktcounts = core::mem::take(self.kktsystem.counters_mut());
+            *self.info.counters_mut() += kktcounts;
+            let kktstats = core::mem::take(self.kktsystem.refinement_stats_mut());
+            self.info.refinement_stats_mut().merge(kktstats);
+
+            //halt timers
+            #[cfg(feature = "profiling")]
+            timers.stop_profile();
+            self.info.finalize(&mut timers);
+            self.solution.finalize(&self.info);
+
+            self.info.print_footer(&self.settings).unwrap();
+
+            //stow the timers back into Option in the solver struct
+            self.timers.replace(timers);
+        }
+
         fn default_start(&mut self) {
-            if self.cones.is_symmetric() {
+            if let Some(start) = self.warm_start.take() {
+                self.variables.copy_from(&start);
+                self.variables.warm_initialization(&mut self.cones);
+            } else if self.cones.is_symmetric() {
                 // set all scalings to identity (or zero for the zero cone)
                 self.cones.set_identity_scaling();
                 // Refactor
@@ -490,6 +662,8 @@ mod internal {
             step_direction: StepDirection,
             scaling: ScalingStrategy,
         ) -> T {
+            profile_scope!(LineSearch);
+
             //step length to stay within the cones
             let mut α = self.variables.calc_step_length(
                 &self.step_lhs,
@@ -501,7 +675,7 @@ mod internal {
             // additional barrier function limits for asymmetric cones
             if !self.cones.is_symmetric()
                 && step_direction == StepDirection::Combined
-                && scaling == ScalingStrategy::Dual
+                && scaling != ScalingStrategy::PrimalDual
             {
                 let αinit = α;
                 α = self.backtrack_step_to_barrier(αinit);
@@ -515,6 +689,7 @@ mod internal {
 
             for _ in 0..50 {
                 let barrier = self.variables.barrier(&self.step_lhs, α, &mut self.cones);
+                self.info.counters_mut().backtrack_evaluations += 1;
                 if barrier < T::one() {
                     return α;
                 } else {
@@ -562,6 +737,14 @@ mod internal {
             // If problem is asymmetric, we can try to continue with the dual-only strategy
             else if !self.cones.is_symmetric() && (scaling == ScalingStrategy::PrimalDual) {
                 output = StrategyCheckpoint::Update(ScalingStrategy::Dual);
+            }
+            // otherwise try the user's fallback strategy, if it would
+            // change the scaling of any of the cones
+            else if self
+                .cones
+                .scaling_differs(scaling, self.settings.core().scaling_fallback)
+            {
+                output = StrategyCheckpoint::Update(self.settings.core().scaling_fallback);
             } else {
                 // out of tricks.  Bail out with an error
                 self.info.set_status(SolverStatus::NumericalError);
@@ -574,6 +757,8 @@ mod internal {
             &mut self,
             α: T,
             scaling: ScalingStrategy,
+            fallback: &mut StepFallback,
+            iter: u32,
         ) -> StrategyCheckpoint {
             let output;
 
@@ -583,8 +768,30 @@ mod internal {
             {
                 output = StrategyCheckpoint::Update(ScalingStrategy::Dual);
             } else if α <= T::max(T::zero(), self.settings.core().min_terminate_step_length) {
-                self.info.set_status(SolverStatus::InsufficientProgress);
-                output = StrategyCheckpoint::Fail;
+                // retry without the corrector, then as a pure centering step.
+                // The retries start from the current iterate, so it is saved
+                // as the previous one for the progress checks
+                if *fallback != StepFallback::Centering {
+                    self.info
+                        .save_prev_iterate(&self.variables, &mut self.prev_vars);
+                }
+                match *fallback {
+                    StepFallback::None => {
+                        *fallback = StepFallback::NoCorrector;
+                        self.info.counters_mut().uncorrected_step_retries += 1;
+                        output = StrategyCheckpoint::Update(ScalingStrategy::Dual);
+                    }
+                    StepFallback::NoCorrector => {
+                        *fallback = StepFallback::Centering;
+                        self.info.counters_mut().centering_step_retries += 1;
+                        output = StrategyCheckpoint::Update(scaling);
+                    }
+                    StepFallback::Centering => {
+                        let cone = self.cones.binding_cone();
+                        self.info.record_vanishing_step(iter, α, cone);
+                        output = StrategyCheckpoint::Fail;
+                    }
+                }
             } else {
                 output = StrategyCheckpoint::NoUpdate;
             }
@@ -595,10 +802,13 @@ mod internal {
         fn strategy_checkpoint_is_scaling_success(
             &mut self,
             is_scaling_success: bool,
-            _scaling: ScalingStrategy,
+            scaling: ScalingStrategy,
         ) -> StrategyCheckpoint {
+            let fallback = self.settings.core().scaling_fallback;
             if is_scaling_success {
                 StrategyCheckpoint::NoUpdate
+            } else if self.cones.scaling_differs(scaling, fallback) {
+                StrategyCheckpoint::Update(fallback)
             } else {
                 self.info.set_status(SolverStatus::NumericalError);
                 StrategyCheckpoint::Fail
@@ -606,3 +816,54 @@ mod internal {
         }
     } // end trait impl
 } //end internals module
+
+#[test]
+#[allow(non_snake_case)]
+fn test_solve_with_scaling_strategies() {
+    use crate::solver::{DefaultSettingsBuilder, DefaultSolver, SupportedConeT::*};
+
+    // min x₁ + 2x₂ + 1.5x₃  s.t.  x₁ + x₂ + x₃ = 1, x >= 0, x <= 1,
+    // and optionally ‖(x₁,x₂)‖ <= 2.  Optimum 1 at x = (1,0,0)
+    let P = CscMatrix::<f64>::zeros((3, 3));
+    let q = vec![1., 2., 1.5];
+    let A = CscMatrix::from(&[
+        [1., 1., 1.],  //
+        [-1., 0., 0.], //
+        [0., -1., 0.], //
+        [0., 0., -1.], //
+        [1., 0., 0.],  //
+        [0., 1., 0.],  //
+        [0., 0., 1.],  //
+        [0., 0., 0.],  //
+        [-1., 0., 0.], //
+        [0., -1., 0.], //
+    ]);
+    let b = vec![1., 0., 0., 0., 1., 1., 1., 2., 0., 0.];
+
+    let lp = [ZeroConeT(1), NonnegativeConeT(6)];
+    let socp = [ZeroConeT(1), NonnegativeConeT(6), SecondOrderConeT(3)];
+
+    for scaling in [
+        ScalingStrategy::PrimalDual,
+        ScalingStrategy::Dual,
+        ScalingStrategy::PrimalOnly,
+        ScalingStrategy::DualOnly,
+    ] {
+        for cones in [&lp[..], &socp[..]] {
+            let m = cones.iter().map(|c| c.nvars()).sum();
+            let A = A.select_rows(&(0..A.m).map(|i| i < m).collect());
+
+            let settings = DefaultSettingsBuilder::default()
+                .verbose(false)
+                .build()
+                .unwrap();
+            let mut solver = DefaultSolver::new(&P, &q, &A, &b[0..m], cones, settings).unwrap();
+            solver.solve_attempt(scaling);
+
+            let solution = &solver.solution;
+            assert_eq!(solution.status, SolverStatus::Solved, "{:?}", scaling);
+            assert!((solution.obj_val - 1.).abs() < 1e-6);
+            assert!((solution.x[0] - 1.).abs() < 1e-6);
+        }
+    }
+}
@@ -10,15 +10,16 @@
 //!  which collectively implement support for the problem format described in the top
 //! level crate documentation.
 
-use super::{cones::Cone, CoreSettings, ScalingStrategy};
+use super::{cones::Cone, CoreSettings, OpCounters, RefinementStats, ScalingStrategy};
 use super::{SolverStatus, StepDirection};
 use crate::algebra::*;
+use crate::stdio;
 use crate::timers::*;
 
 /// Data for a conic optimization problem.
 
-pub trait ProblemData<T: FloatT> {
-    type V: Variables<T>;
+pub trait ProblemData<T: FloatT>: Sized {
+    type V: Variables<T, Self>;
     type C: Cone<T>;
     type SE: Settings<T>;
 
@@ -26,11 +27,10 @@ pub trait ProblemData<T: FloatT> {
     fn equilibrate(&mut self, cones: &Self::C, settings: &Self::SE);
 }
 
-/// Variables for a conic optimization problem.
+/// Variables for a conic optimization problem with data `D`.
 
-pub trait Variables<T: FloatT> {
-    type D: ProblemData<T>;
-    type R: Residuals<T>;
+pub trait Variables<T: FloatT, D: ProblemData<T>> {
+    type R: Residuals<T, D>;
     type C: Cone<T>;
     type SE: Settings<T>;
 
@@ -76,6 +76,10 @@ pub trait Variables<T: FloatT> {
     /// Initialize all conic variables to unit values.
     fn unit_initialization(&mut self, cones: &Self::C);
 
+    /// Bring user supplied variables into the interior of the cone
+    /// constraints, moving them as little as possible.
+    fn warm_initialization(&mut self, cones: &mut Self::C);
+
     /// Overwrite values with those from another object
     fn copy_from(&mut self, src: &Self);
 
@@ -93,29 +97,33 @@ pub trait Variables<T: FloatT> {
     fn rescale(&mut self);
 }
 
-/// Residuals for a conic optimization problem.
+/// Residuals for a conic optimization problem with data `D`.
 
-pub trait Residuals<T: FloatT> {
-    type D: ProblemData<T>;
-    type V: Variables<T>;
+pub trait Residuals<T: FloatT, D: ProblemData<T>> {
+    type V: Variables<T, D>;
 
     /// Compute residuals for the given variables.
     ///
-    fn update(&mut self, variables: &Self::V, data: &Self::D);
+    fn update(&mut self, variables: &Self::V, data: &D);
+
+    /// Update any cached products of the problem data with the variables
+    /// for a step `variables += α*step`, where `step` solves the KKT system
+    /// for `step_rhs`, so that the next call to [`update`](Self::update)
+    /// can use them.   Does nothing by default.
+    fn apply_step(&mut self, _step: &Self::V, _step_rhs: &Self::V, _α: T, _data: &D) {}
 }
 
-/// KKT linear solver object.
+/// KKT linear solver object for a problem with data `D`.
 
-pub trait KKTSystem<T: FloatT> {
-    type D: ProblemData<T>;
-    type V: Variables<T>;
+pub trait KKTSystem<T: FloatT, D: ProblemData<T>> {
+    type V: Variables<T, D>;
     type C: Cone<T>;
     type SE: Settings<T>;
 
     /// Update the KKT system.   In particular, update KKT
     /// matrix entries with new variable and refactor.
 
-    fn update(&mut self, data: &Self::D, cones: &Self::C, settings: &Self::SE) -> bool;
+    fn update(&mut self, data: &D, cones: &Self::C, settings: &Self::SE) -> bool;
 
     /// Solve the KKT system for the given RHS.
 
@@ -124,7 +132,7 @@ pub trait KKTSystem<T: FloatT> {
         &mut self,
         step_lhs: &mut Self::V,
         step_rhs: &Self::V,
-        data: &Self::D,
+        data: &D,
         variables: &Self::V,
         cones: &mut Self::C,
         step_direction: StepDirection,
@@ -136,18 +144,35 @@ pub trait KKTSystem<T: FloatT> {
     fn solve_initial_point(
         &mut self,
         variables: &mut Self::V,
-        data: &Self::D,
+        data: &D,
         settings: &Self::SE,
     ) -> bool;
+
+    /// Operation counts for KKT solves and factorizations.
+    fn counters_mut(&mut self) -> &mut OpCounters;
+
+    /// Iterative refinement statistics for KKT solves.
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<T>;
+
+    /// Notify the KKT system of the start of iteration `iter`, with
+    /// `iter = 0` during initialization.  Does nothing by default.
+    fn begin_iteration(&mut self, _iter: u32) {}
+
+    /// Return `true` if the step directions computed since the last
+    /// update satisfy the linearized KKT equations, with the cone scaling
+    /// used for the step, to within the iterative refinement tolerance.
+    fn is_step_accurate(&self) -> bool {
+        false
+    }
 }
 
 /// Printing functions for the solver's Info
 
-pub trait InfoPrint<T>
+pub trait InfoPrint<T, D>
 where
     T: FloatT,
+    D: ProblemData<T>,
 {
-    type D: ProblemData<T>;
     type C: Cone<T>;
     type SE: Settings<T>;
 
@@ -156,29 +181,30 @@ where
     fn print_configuration(
         &self,
         settings: &Self::SE,
-        data: &Self::D,
+        data: &D,
         cones: &Self::C,
-    ) -> std::io::Result<()>;
+    ) -> stdio::Result<()>;
 
     /// Print a header to appear at the top of progress information.
-    fn print_status_header(&self, settings: &Self::SE) -> std::io::Result<()>;
+    fn print_status_header(&self, settings: &Self::SE) -> stdio::Result<()>;
 
     /// Print solver progress information.   Called once per iteration.
-    fn print_status(&self, settings: &Self::SE) -> std::io::Result<()>;
+    fn print_status(&mut self, settings: &Self::SE) -> stdio::Result<()>;
 
     /// Print solver final status and other exit information.   Called at
     /// solver termination.
-    fn print_footer(&self, settings: &Self::SE) -> std::io::Result<()>;
+    fn print_footer(&mut self, settings: &Self::SE) -> stdio::Result<()>;
 }
 
 /// Internal information for the solver to monitor progress and check for termination.
 
-pub trait Info<T>: InfoPrint<T>
+pub trait Info<T, D>: InfoPrint<T, D>
 where
     T: FloatT,
+    D: ProblemData<T>,
 {
-    type V: Variables<T>;
-    type R: Residuals<T>;
+    type V: Variables<T, D>;
+    type R: Residuals<T, D>;
 
     /// Reset internal data, particularly solve timers.
     fn reset(&mut self, timers: &mut Timers);
@@ -190,13 +216,7 @@ where
     fn finalize(&mut self, timers: &mut Timers);
 
     /// Update solver progress information
-    fn update(
-        &mut self,
-        data: &mut Self::D,
-        variables: &Self::V,
-        residuals: &Self::R,
-        timers: &Timers,
-    );
+    fn update(&mut self, data: &mut D, variables: &Self::V, residuals: &Self::R, timers: &Timers);
 
     /// Return `true` if termination conditions have been reached.
     fn check_termination(&mut self, residuals: &Self::R, settings: &Self::SE, iter: u32) -> bool;
@@ -210,23 +230,46 @@ where
     /// `σ = ` multiplier for the updated centering parameter.
     fn save_scalars(&mut self, μ: T, α: T, σ: T, iter: u32);
 
+    /// Record the automatic recovery attempt in use, and the settings
+    /// and initial scaling strategy applied for it.  An attempt of zero
+    /// means the user's settings.
+    fn save_recovery(&mut self, attempt: u32, settings: &Self::SE, scaling: ScalingStrategy);
+
+    /// Operation counts for the current solve.
+    fn counters_mut(&mut self) -> &mut OpCounters;
+
+    /// Iterative refinement statistics for the current solve.
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<T>;
+
+    /// Return `true` if the solver should take conservative steps
+    /// from this iteration on, i.e. close to convergence.
+    fn check_conservative_steps(&mut self, settings: &Self::SE) -> bool;
+
     /// Report or update termination status
     fn get_status(&self) -> SolverStatus;
     fn set_status(&mut self, status: SolverStatus);
+
+    /// Record a panic caught during the solve, with its message,
+    /// and set an error status.
+    fn record_panic(&mut self, message: &str);
+
+    /// Record termination after a vanishing step length at
+    /// iteration `iter`, with the cone that limited the step
+    /// if known, and set an error status.
+    fn record_vanishing_step(&mut self, iter: u32, α: T, cone: Option<usize>);
 }
 
-/// Solution for a conic optimization problem.
+/// Solution for a conic optimization problem with data `D`.
 
-pub trait Solution<T: FloatT> {
-    type D: ProblemData<T>;
-    type V: Variables<T>;
-    type I: Info<T>;
+pub trait Solution<T: FloatT, D: ProblemData<T>> {
+    type V: Variables<T, D>;
+    type I: Info<T, D>;
     type SE: Settings<T>;
 
     /// Compute solution from the Variables at solver termination
     fn post_process(
         &mut self,
-        data: &Self::D,
+        data: &D,
         variables: &mut Self::V,
         info: &Self::I,
         settings: &Self::SE,
@@ -234,6 +277,10 @@ pub trait Solution<T: FloatT> {
 
     /// finalize the solution, e.g. extract final timing from info
     fn finalize(&mut self, info: &Self::I);
+
+    /// Report the status from info without a solution, e.g. after
+    /// a panic has left the Variables in an inconsistent state
+    fn abandon(&mut self, info: &Self::I);
 }
 
 /// Settings for a conic optimization problem.
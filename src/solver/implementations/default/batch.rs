@@ -0,0 +1,266 @@
+use super::*;
+use crate::algebra::*;
+use crate::solver::core::{cones::SupportedConeT, IPSolver, SolverStatus};
+use crate::solver::utils::panics::panic_message;
+use rayon::prelude::*;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::Instant;
+use thiserror::Error;
+
+// ---------------------------------------------------
+// Solving batches of independent problems on a rayon
+// thread pool
+// ---------------------------------------------------
+
+/// Data `(P,q,A,b,cones)` for one problem of a batch, as solved
+/// by [`DefaultSolver::solve_batch`].
+#[derive(Debug, Clone)]
+pub struct BatchProblem<T: FloatT = f64> {
+    pub P: CscMatrix<T>,
+    pub q: Vec<T>,
+    pub A: CscMatrix<T>,
+    pub b: Vec<T>,
+    pub cones: Vec<SupportedConeT<T>>,
+}
+
+impl<T> BatchProblem<T>
+where
+    T: FloatT,
+{
+    pub fn new(
+        P: CscMatrix<T>,
+        q: Vec<T>,
+        A: CscMatrix<T>,
+        b: Vec<T>,
+        cones: Vec<SupportedConeT<T>>,
+    ) -> Self {
+        Self { P, q, A, b, cones }
+    }
+
+    fn check_dimensions(&self) -> Result<(), BatchSolveError> {
+        check_dimensions(&self.P, &self.q, &self.A, &self.b, &self.cones)
+            .map_err(BatchSolveError::BadProblem)
+    }
+
+    fn solver(&self, settings: &DefaultSettings<T>) -> Result<DefaultSolver<T>, BatchSolveError> {
+        DefaultSolver::new(
+            &self.P,
+            &self.q,
+            &self.A,
+            &self.b,
+            &self.cones,
+            settings.clone(),
+        )
+        .map_err(BatchSolveError::BadProblem)
+    }
+}
+
+/// Threads used to solve a batch of problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchParallelism {
+    /// Use the global rayon thread pool.
+    #[default]
+    Global,
+    /// Use a dedicated thread pool with the given number of threads,
+    /// or the rayon default if zero.
+    Threads(usize),
+}
+
+/// Error type for a problem of a batch that could not be solved.
+#[derive(Error, Debug, Clone)]
+pub enum BatchSolveError {
+    #[error("Bad problem data: {0}")]
+    BadProblem(ClarabelError),
+    #[error("Solver panicked: {0}")]
+    Panicked(String),
+}
+
+/// Result of solving one problem of a batch.
+pub type BatchSolveResult<T> = Result<DefaultSolution<T>, BatchSolveError>;
+
+/// Aggregate statistics for a batch of problems.
+#[derive(Debug, Clone, Default)]
+pub struct BatchStatistics {
+    pub problems: usize,
+    /// problems with status `Solved` or `AlmostSolved`
+    pub solved: usize,
+    /// problems that were solved with any other status
+    pub unsolved: usize,
+    /// problems for which the solver could not be created or panicked
+    pub failed: usize,
+    /// total iterations over all problems
+    pub iterations: u64,
+    /// total of the solve times of all problems (seconds)
+    pub solve_time: f64,
+    /// elapsed time for the whole batch (seconds)
+    pub wall_time: f64,
+    /// number of solvers created.   This is the number of problems
+    /// unless solvers were reused for problems with shared patterns
+    pub setups: usize,
+}
+
+/// Results for a batch of problems, in the order the problems were given.
+#[derive(Debug)]
+pub struct BatchSolution<T> {
+    pub results: Vec<BatchSolveResult<T>>,
+    pub stats: BatchStatistics,
+}
+
+impl<T> DefaultSolver<T>
+where
+    T: FloatT,
+{
+    /// Solve a batch of independent problems in parallel, with the same
+    /// settings for each.   Requires the "parallel" feature.
+    ///
+    /// A problem for which the solver cannot be created, e.g. because of
+    /// inconsistent dimensions, or for which the solver panics is reported
+    /// as an error and does not affect the others.
+    /// Solver output should normally be disabled with `verbose = false`,
+    /// since output from different problems would be interleaved.
+    pub fn solve_batch(
+        problems: Vec<BatchProblem<T>>,
+        settings: &DefaultSettings<T>,
+        parallelism: BatchParallelism,
+    ) -> BatchSolution<T> {
+        _run_batch(
+            problems,
+            parallelism,
+            || (),
+            |_, problem| {
+                let result = catch_unwind(AssertUnwindSafe(|| {
+                    let mut solver = problem.solver(settings)?;
+                    solver.solve();
+                    Ok(solver.solution)
+                }))
+                .unwrap_or_else(|e| Err(_panicked(e)));
+                (result, true)
+            },
+        )
+    }
+
+    /// Solve a batch of problems whose matrices `P` and `A` share a
+    /// sparsity pattern and whose cones are the same.   Requires the
+    /// "parallel" feature.
+    ///
+    /// Each thread creates a solver for the first problem it is given, and
+    /// for later problems overwrites its data in place as with
+    /// [`update_data`](DefaultSolver::update_data), so that the symbolic
+    /// factorization of the KKT system is computed once per thread.  The
+    /// equilibration of the first problem is reused for the others.
+    /// `P` should be given in upper triangular form.   Problems that do
+    /// not match the pattern are solved with a new solver instead.
+    ///
+    /// Presolve is disabled since it prevents data updates.   Errors
+    /// are reported as for [`solve_batch`](DefaultSolver::solve_batch).
+    pub fn solve_batch_shared_pattern(
+        problems: Vec<BatchProblem<T>>,
+        settings: &DefaultSettings<T>,
+        parallelism: BatchParallelism,
+    ) -> BatchSolution<T> {
+        let mut settings = settings.clone();
+        settings.presolve_enable = false;
+        settings.presolve_reductions_enable = false;
+        let settings = &settings;
+
+        _run_batch(
+            problems,
+            parallelism,
+            || None,
+            |slot: &mut Option<(DefaultSolver<T>, Vec<SupportedConeT<T>>)>, problem| {
+                if let Err(e) = problem.check_dimensions() {
+                    return (Err(e), false);
+                }
+                let solved = catch_unwind(AssertUnwindSafe(|| {
+                    let reused = match slot {
+                        Some((solver, cones)) if *cones == problem.cones => solver
+                            .update_data(&problem.P, &problem.q, &problem.A, &problem.b)
+                            .is_ok(),
+                        _ => false,
+                    };
+                    if !reused {
+                        *slot = Some((problem.solver(settings)?, problem.cones.clone()));
+                    }
+                    let solver = &mut slot.as_mut().unwrap().0;
+                    solver.solve();
+                    let empty = DefaultSolution::new(problem.q.len(), problem.b.len());
+                    Ok((std::mem::replace(&mut solver.solution, empty), !reused))
+                }));
+                match solved {
+                    Ok(Ok((solution, setup))) => (Ok(solution), setup),
+                    Ok(Err(e)) => (Err(e), true),
+                    Err(e) => {
+                        // the solver may have been left in any state
+                        *slot = None;
+                        (Err(_panicked(e)), true)
+                    }
+                }
+            },
+        )
+    }
+}
+
+// ---------------------------------------------------
+// internal helpers
+// ---------------------------------------------------
+
+// solve each problem with `solve`, which also reports whether a new
+// solver was created.   `init` creates per-task state for reuse
+// across problems solved on the same thread
+fn _run_batch<T, S, I, F>(
+    problems: Vec<BatchProblem<T>>,
+    parallelism: BatchParallelism,
+    init: I,
+    solve: F,
+) -> BatchSolution<T>
+where
+    T: FloatT,
+    I: Fn() -> S + Sync + Send,
+    F: Fn(&mut S, BatchProblem<T>) -> (BatchSolveResult<T>, bool) + Sync + Send,
+{
+    let start = Instant::now();
+    let run = move || -> Vec<(BatchSolveResult<T>, bool)> {
+        problems.into_par_iter().map_init(init, solve).collect()
+    };
+
+    let outcomes = match parallelism {
+        BatchParallelism::Global => run(),
+        BatchParallelism::Threads(n) => {
+            match rayon::ThreadPoolBuilder::new().num_threads(n).build() {
+                Ok(pool) => pool.install(run),
+                Err(_) => run(),
+            }
+        }
+    };
+
+    let mut stats = BatchStatistics {
+        problems: outcomes.len(),
+        ..BatchStatistics::default()
+    };
+    let results = outcomes
+        .into_iter()
+        .map(|(result, setup)| {
+            stats.setups += setup as usize;
+            match &result {
+                Ok(solution) => {
+                    match solution.status {
+                        SolverStatus::Solved | SolverStatus::AlmostSolved => stats.solved += 1,
+                        _ => stats.unsolved += 1,
+                    }
+                    stats.iterations += u64::from(solution.iterations);
+                    stats.solve_time += solution.solve_time;
+                }
+                Err(_) => stats.failed += 1,
+            }
+            result
+        })
+        .collect();
+    stats.wall_time = start.elapsed().as_secs_f64();
+
+    BatchSolution { results, stats }
+}
+
+fn _panicked(e: Box<dyn Any + Send>) -> BatchSolveError {
+    BatchSolveError::Panicked(panic_message(e.as_ref()))
+}
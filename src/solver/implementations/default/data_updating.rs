@@ -1,7 +1,8 @@
 #![allow(non_snake_case)]
-use super::DefaultSolver;
+use super::BorrowedSolver;
 use crate::algebra::*;
 use core::iter::Zip;
+use core::ops::Deref;
 use core::slice::Iter;
 use thiserror::Error;
 
@@ -10,7 +11,11 @@ use thiserror::Error;
 pub enum DataUpdateError {
     #[error("Data updates are not allowed when presolve is enabled")]
     PresolveEnabled,
-    #[error("Data formatting error")]
+    #[error("Data updates are not allowed when the problem has been dualized")]
+    Dualized,
+    #[error("Data updates are not allowed after a panic during a solve has poisoned the solver")]
+    Poisoned,
+    #[error("Data formatting error: {0}")]
     BadFormat(#[from] SparseFormatError),
 }
 
@@ -18,7 +23,7 @@ pub enum DataUpdateError {
 pub trait MatrixProblemDataUpdate<T: FloatT> {
     fn update_matrix(
         &self,
-        M: &mut CscMatrix<T>,
+        M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
         lscale: &[T],
         rscale: &[T],
     ) -> Result<(), SparseFormatError>;
@@ -29,7 +34,7 @@ pub trait VectorProblemDataUpdate<T: FloatT> {
     fn update_vector(&self, v: &mut [T], scale: &[T]) -> Result<(), SparseFormatError>;
 }
 
-impl<T> DefaultSolver<T>
+impl<'a, T> BorrowedSolver<'a, T>
 where
     T: FloatT,
 {
@@ -70,9 +75,11 @@ where
         &mut self,
         data: &Data,
     ) -> Result<(), DataUpdateError> {
-        self.check_presolve_disabled()?;
-        let d = &self.data.equilibration.d;
-        data.update_matrix(&mut self.data.P, d, d)?;
+        self.check_update_allowed()?;
+        // P is scaled by the cost scaling c as well as by D
+        let equil = &self.data.equilibration;
+        data.update_matrix(&mut self.data.P, &equil.d, &equil.cd)?;
+        self.data.update_P_diag();
         // overwrite KKT data
         self.kktsystem.update_P(&self.data.P);
         Ok(())
@@ -92,10 +99,11 @@ where
         &mut self,
         data: &Data,
     ) -> Result<(), DataUpdateError> {
-        self.check_presolve_disabled()?;
+        self.check_update_allowed()?;
         let d = &self.data.equilibration.d;
         let e = &self.data.equilibration.e;
         data.update_matrix(&mut self.data.A, e, d)?;
+        self.data.update_A_transpose();
         // overwrite KKT data
         self.kktsystem.update_A(&self.data.A);
         Ok(())
@@ -106,9 +114,17 @@ where
         &mut self,
         data: &Data,
     ) -> Result<(), DataUpdateError> {
-        self.check_presolve_disabled()?;
-        let d = &self.data.equilibration.d;
-        data.update_vector(&mut self.data.q, d)?;
+        self.check_update_allowed()?;
+        // updates are given with the user's objective sense
+        let negate = self.data.maximization_flag;
+        if negate {
+            self.data.q.negate();
+        }
+        let result = data.update_vector(&mut self.data.q, &self.data.equilibration.cd);
+        if negate {
+            self.data.q.negate();
+        }
+        result?;
 
         // flush unscaled norm. Will be recalculated during solve
         self.data.clear_normq();
@@ -121,7 +137,7 @@ where
         &mut self,
         data: &Data,
     ) -> Result<(), DataUpdateError> {
-        self.check_presolve_disabled()?;
+        self.check_update_allowed()?;
         let e = &self.data.equilibration.e;
         data.update_vector(&mut self.data.b, e)?;
 
@@ -131,9 +147,13 @@ where
         Ok(())
     }
 
-    fn check_presolve_disabled(&self) -> Result<(), DataUpdateError> {
-        if self.settings.presolve_enable {
+    fn check_update_allowed(&self) -> Result<(), DataUpdateError> {
+        if self.poisoned {
+            Err(DataUpdateError::Poisoned)
+        } else if self.settings.presolve_enable || self.settings.presolve_reductions_enable {
             Err(DataUpdateError::PresolveEnabled)
+        } else if self.data.dualization.is_some() {
+            Err(DataUpdateError::Dualized)
         } else {
             Ok(())
         }
@@ -146,7 +166,7 @@ where
 {
     fn update_matrix(
         &self,
-        M: &mut CscMatrix<T>,
+        M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
         lscale: &[T],
         rscale: &[T],
     ) -> Result<(), SparseFormatError> {
@@ -162,7 +182,7 @@ where
 {
     fn update_matrix(
         &self,
-        M: &mut CscMatrix<T>,
+        M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
         lscale: &[T],
         rscale: &[T],
     ) -> Result<(), SparseFormatError> {
@@ -187,7 +207,7 @@ where
 impl<T: FloatT> MatrixProblemDataUpdate<T> for Vec<T> {
     fn update_matrix(
         &self,
-        M: &mut CscMatrix<T>,
+        M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
         lscale: &[T],
         rscale: &[T],
     ) -> Result<(), SparseFormatError> {
@@ -198,7 +218,7 @@ impl<T: FloatT> MatrixProblemDataUpdate<T> for Vec<T> {
 impl<T: FloatT> MatrixProblemDataUpdate<T> for [T; 0] {
     fn update_matrix(
         &self,
-        _M: &mut CscMatrix<T>,
+        _M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
         _lscale: &[T],
         _rscale: &[T],
     ) -> Result<(), SparseFormatError> {
@@ -216,7 +236,7 @@ where
 {
     fn update_matrix(
         &self,
-        M: &mut CscMatrix<T>,
+        M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
         lscale: &[T],
         rscale: &[T],
     ) -> Result<(), SparseFormatError> {
@@ -231,6 +251,25 @@ where
     }
 }
 
+// Updates from ndarray vectors, copying the data first if the
+// vector is not stored contiguously
+
+#[cfg(feature = "ndarray")]
+impl<T, S> MatrixProblemDataUpdate<T> for ndarray::ArrayBase<S, ndarray::Ix1>
+where
+    T: FloatT,
+    S: ndarray::Data<Elem = T>,
+{
+    fn update_matrix(
+        &self,
+        M: &mut CscMatrix<T, impl Deref<Target = [usize]>>,
+        lscale: &[T],
+        rscale: &[T],
+    ) -> Result<(), SparseFormatError> {
+        self.as_vector_slice().update_matrix(M, lscale, rscale)
+    }
+}
+
 impl<T> VectorProblemDataUpdate<T> for [T]
 where
     T: FloatT,
@@ -284,3 +323,14 @@ where
         Ok(())
     }
 }
+
+#[cfg(feature = "ndarray")]
+impl<T, S> VectorProblemDataUpdate<T> for ndarray::ArrayBase<S, ndarray::Ix1>
+where
+    T: FloatT,
+    S: ndarray::Data<Elem = T>,
+{
+    fn update_vector(&self, v: &mut [T], scale: &[T]) -> Result<(), SparseFormatError> {
+        self.as_vector_slice().update_vector(v, scale)
+    }
+}
@@ -0,0 +1,242 @@
+#![allow(non_snake_case)]
+use super::*;
+use crate::algebra::*;
+use crate::solver::{core::SolverStatus, SupportedConeT};
+use thiserror::Error;
+
+// ---------------
+// Dualization of the standard problem format.
+//
+// The primal problem
+//
+//   min ½xᵀPx + qᵀx  s.t.  Ax + s = b,  s ∈ K
+//
+// has the Lagrangian dual
+//
+//   max -½wᵀPw - bᵀz  s.t.  Pw + Aᵀz + q = 0,  z ∈ K*
+//
+// which is solved in the standard form
+//
+//   min ½wᵀPw + bᵀz  s.t.  [P  Aᵀ](w,z) = -q,  z_K ∈ K*
+//
+// where z_K are the dual variables of the rows not in a zero cone, whose
+// duals are free.   The variable w is omitted when P = 0.   Since
+// the primal variables x are the negated multipliers of the equality
+// constraints of the dual, a primal solution or infeasibility certificate
+// is recovered from the dual without further computation.
+// ---------------
+
+/// Error type returned when a problem can not be dualized.
+#[derive(Error, Debug)]
+pub enum DualizeError {
+    #[error("Cone type {0} is not supported for dualization")]
+    UnsupportedCone(String),
+    #[error("Infinite bounds are not supported for dualization")]
+    InfiniteBound,
+}
+
+/// The Lagrangian dual of a problem, and the data required to recover
+/// a solution to the original problem from a solution to the dual.
+///
+/// Dualization is supported for problems with zero, nonnegative, second
+/// order and positive semidefinite cones, since these cones are self dual.
+/// Solving the dual can be much faster for problems with many more
+/// constraints than variables.
+///
+/// Use [`solver`](DualTransform::solver) to solve the dual problem and
+/// [`recover_primal`](DualTransform::recover_primal) to recover a solution
+/// to the original problem.   Problems are dualized automatically by the
+/// solver when `auto_dualize` is set in the solver settings.
+#[derive(Debug, Clone)]
+pub struct DualTransform<T> {
+    /// quadratic cost of the dual problem, upper triangular
+    pub P: CscMatrix<T>,
+    /// linear cost of the dual problem
+    pub q: Vec<T>,
+    /// constraint matrix of the dual problem
+    pub A: CscMatrix<T>,
+    /// constraint right hand side of the dual problem
+    pub b: Vec<T>,
+    /// cones of the dual problem
+    pub cones: Vec<SupportedConeT<T>>,
+
+    // dimensions of the original problem
+    n: usize,
+    m: usize,
+
+    // number of variables in w, either n or zero
+    nw: usize,
+
+    // original rows not in a zero cone, in order
+    conic_rows: Vec<usize>,
+}
+
+impl<T> DualTransform<T>
+where
+    T: FloatT,
+{
+    /// Construct the dual of the problem with data `(P,q,A,b)`.
+    pub fn new(
+        P: &CscMatrix<T>,
+        q: &[T],
+        A: &CscMatrix<T>,
+        b: &[T],
+        cones: &[SupportedConeT<T>],
+    ) -> Result<Self, DualizeError> {
+        let (m, n) = A.size();
+        let infbound: T = crate::solver::get_infinity().as_T();
+
+        if b.iter().any(|&v| v.abs() >= infbound) {
+            return Err(DualizeError::InfiniteBound);
+        }
+
+        // rows not in a zero cone, and the cones of the dual
+        let mut conic_rows = Vec::with_capacity(m);
+        let mut dual_cones = vec![SupportedConeT::ZeroConeT(n)];
+        let mut row = 0;
+        for cone in cones {
+            let dim = cone.nvars();
+            match cone {
+                SupportedConeT::ZeroConeT(_) => {}
+                SupportedConeT::NonnegativeConeT(_) | SupportedConeT::SecondOrderConeT(_) => {
+                    dual_cones.push(cone.clone());
+                    conic_rows.extend(row..row + dim);
+                }
+                #[cfg(feature = "sdp")]
+                SupportedConeT::PSDTriangleConeT(_) => {
+                    dual_cones.push(cone.clone());
+                    conic_rows.extend(row..row + dim);
+                }
+                _ => return Err(DualizeError::UnsupportedCone(cone.to_string())),
+            }
+            row += dim;
+        }
+
+        let P = if P.is_triu() { P.clone() } else { P.to_triu() };
+        let nw = if P.nnz() > 0 { n } else { 0 };
+        let (nd, md) = (nw + m, n + conic_rows.len());
+
+        // cost terms ½wᵀPw + bᵀz
+        let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+        for (i, j, v) in _entries(&P) {
+            I.push(i);
+            J.push(j);
+            V.push(v);
+        }
+        let Pd = CscMatrix::new_from_triplets(nd, nd, I, J, V);
+
+        let mut qd = vec![T::zero(); nw];
+        qd.extend_from_slice(b);
+
+        // constraints Pw + Aᵀz = -q, with P in full symmetric form
+        let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+        if nw > 0 {
+            for (i, j, v) in _entries(&P) {
+                I.push(i);
+                J.push(j);
+                V.push(v);
+                if i != j {
+                    I.push(j);
+                    J.push(i);
+                    V.push(v);
+                }
+            }
+        }
+        for (i, j, v) in _entries(A) {
+            I.push(j);
+            J.push(nw + i);
+            V.push(v);
+        }
+
+        // and -z_K + s = 0, s ∈ K*
+        for (k, &i) in conic_rows.iter().enumerate() {
+            I.push(n + k);
+            J.push(nw + i);
+            V.push(-T::one());
+        }
+        let Ad = CscMatrix::new_from_triplets(md, nd, I, J, V);
+
+        let mut bd = q.to_vec();
+        bd.negate();
+        bd.resize(md, T::zero());
+
+        Ok(Self {
+            P: Pd,
+            q: qd,
+            A: Ad,
+            b: bd,
+            cones: dual_cones,
+            n,
+            m,
+            nw,
+            conic_rows,
+        })
+    }
+
+    /// Create a solver for the dual problem
+    pub fn solver(&self, settings: DefaultSettings<T>) -> Result<DefaultSolver<T>, ClarabelError> {
+        DefaultSolver::new(&self.P, &self.q, &self.A, &self.b, &self.cones, settings)
+    }
+
+    /// Recover a solution to the original problem from a solution
+    /// to the dual problem.
+    ///
+    /// Primal and dual objective values are exchanged and negated, and
+    /// primal and dual infeasibility are exchanged in the solution status.
+    /// An infeasibility certificate for the dual is recovered as a
+    /// certificate for the original problem.
+    pub fn recover_primal(&self, dual: &DefaultSolution<T>) -> DefaultSolution<T> {
+        let mut primal = DefaultSolution::new(self.n, self.m);
+
+        primal.status = _primal_status(dual.status);
+        primal.obj_val = -dual.obj_val_dual;
+        primal.obj_val_dual = -dual.obj_val;
+        primal.solve_time = dual.solve_time;
+        primal.iterations = dual.iterations;
+        primal.r_prim = dual.r_dual;
+        primal.r_dual = dual.r_prim;
+        primal.recovery_attempt = dual.recovery_attempt;
+
+        self.recover_into(&mut primal, &dual.x, &dual.z);
+        primal
+    }
+
+    // index of the dual variable of an original row
+    pub(crate) fn dual_variable(&self, row: usize) -> usize {
+        self.nw + row
+    }
+
+    // x is the negated multiplier of the equality constraints of the
+    // dual, z its variables, and s the multipliers of the conic rows
+    pub(crate) fn recover_into(&self, primal: &mut DefaultSolution<T>, x_dual: &[T], z_dual: &[T]) {
+        let n = self.n;
+
+        primal.x.copy_from(&z_dual[0..n]);
+        primal.x.negate();
+
+        primal.z.copy_from(&x_dual[self.nw..]);
+
+        primal.s.set(T::zero());
+        for (&i, &v) in self.conic_rows.iter().zip(&z_dual[n..]) {
+            primal.s[i] = v;
+        }
+    }
+}
+
+// solution status of the original problem given the status of its dual
+pub(crate) fn _primal_status(status: SolverStatus) -> SolverStatus {
+    match status {
+        SolverStatus::PrimalInfeasible => SolverStatus::DualInfeasible,
+        SolverStatus::DualInfeasible => SolverStatus::PrimalInfeasible,
+        SolverStatus::AlmostPrimalInfeasible => SolverStatus::AlmostDualInfeasible,
+        SolverStatus::AlmostDualInfeasible => SolverStatus::AlmostPrimalInfeasible,
+        _ => status,
+    }
+}
+
+// (row, col, value) of each stored entry
+fn _entries<T: FloatT>(M: &CscMatrix<T>) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+    (0..M.n).flat_map(move |j| {
+        (M.colptr[j]..M.colptr[j + 1]).map(move |k| (M.rowval[k], j, M.nzval[k]))
+    })
+}
@@ -18,6 +18,10 @@ pub struct DefaultEquilibrationData<T> {
 
     // overall scaling for objective function
     pub c: T,
+
+    // d scaled by c, i.e. the scaling applied to the objective
+    // terms, for updates of P and q made without allocating
+    pub(crate) cd: Vec<T>,
 }
 
 impl<T> DefaultEquilibrationData<T>
@@ -32,6 +36,7 @@ where
         let einv = vec![T::one(); m];
 
         let c = T::one();
+        let cd = vec![T::one(); n];
 
         Self {
             d,
@@ -39,6 +44,7 @@ where
             e,
             einv,
             c,
+            cd,
         }
     }
 }
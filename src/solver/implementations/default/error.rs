@@ -0,0 +1,53 @@
+use crate::algebra::SparseFormatError;
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use thiserror::Error;
+
+/// Error type returned when a [`DefaultSolver`](super::DefaultSolver)
+/// cannot be constructed.
+#[derive(Error, Debug, Clone)]
+pub enum ClarabelError {
+    #[error("{what} has dimension {got}, but {expected} was expected")]
+    DimensionMismatch {
+        what: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    #[error("The {0} overflows usize")]
+    DimensionOverflow(&'static str),
+    #[error("Bad specification for cone {index}: {reason}")]
+    BadConeSpecification { index: usize, reason: String },
+    #[error("Matrix {0} is badly formatted: {1}")]
+    BadMatrixFormat(&'static str, #[source] SparseFormatError),
+    #[error("Bad settings: {0}")]
+    BadSettings(String),
+    #[error("Estimated solver memory of {estimate} bytes exceeds the budget of {budget} bytes")]
+    MemoryLimitExceeded { estimate: usize, budget: usize },
+    #[error("P has an entry below the diagonal at ({row}, {col}). Supply only its upper triangle, or enable p_auto_triu")]
+    PNotUpperTriangular { row: usize, col: usize },
+    #[error("P is not symmetric, since entry ({row}, {col}) differs from entry ({col}, {row})")]
+    PNotSymmetric { row: usize, col: usize },
+    #[error("Entry {index} of {what} is infinite or NaN, which is allowed only for entries of b in nonnegative cone rows")]
+    NonFiniteEntry { what: &'static str, index: usize },
+    #[error("P is not positive semidefinite, with negative curvature found at variable {index}")]
+    NonConvexObjective { index: usize },
+}
+
+/// Error type returned when a starting point supplied with
+/// [`warm_start`](super::DefaultSolver::warm_start) cannot be used.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WarmStartError {
+    #[error("{what} has dimension {got}, but {expected} was expected")]
+    DimensionMismatch {
+        what: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    #[error("Entry {index} of {what} is infinite or NaN")]
+    NonFiniteEntry { what: &'static str, index: usize },
+    #[error("Warm starts are not supported for problems solved in dual form")]
+    Dualized,
+    #[cfg(feature = "sdp")]
+    #[error("Warm starts are not supported for problems with decomposed PSD cones")]
+    Decomposed,
+}
@@ -1,8 +1,74 @@
 use super::*;
 use crate::algebra::*;
-use crate::solver::core::{traits::Info, SolverStatus};
+use crate::solver::core::{
+    traits::Info, OpCounters, RefinementStats, ScalingStrategy, SolverStatus,
+};
 use crate::solver::traits::Variables;
 use crate::timers::*;
+use alloc::collections::VecDeque;
+
+/// Summary of the solver's progress at a single iteration.
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct IterationRecord<T> {
+    pub iteration: u32,
+    pub cost_primal: T,
+    pub cost_dual: T,
+    pub res_primal: T,
+    pub res_dual: T,
+    pub gap_abs: T,
+    pub gap_rel: T,
+    pub ktratio: T,
+    pub μ: T,
+    pub step_length: T,
+    /// true if conservative steps were in use from this iteration
+    pub conservative: bool,
+}
+
+/// Settings changes made by automatic recovery after a numerical error.
+/// See [`auto_recovery`](crate::solver::DefaultSettings::auto_recovery).
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct RecoveryInfo<T> {
+    /// recovery attempt number, starting from 1
+    pub attempt: u32,
+    /// static regularization constant used for this attempt
+    pub static_regularization_constant: T,
+    /// maximum step fraction used for this attempt
+    pub max_step_fraction: T,
+    /// true if the attempt started from dual-only scaling
+    pub dual_scaling: bool,
+}
+
+/// Termination tolerances used by the solver, after any were raised at
+/// setup under the [`tolerance_policy`](crate::solver::DefaultSettings::tolerance_policy)
+/// setting.
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct EffectiveTolerances<T> {
+    pub tol_gap_abs: T,
+    pub tol_gap_rel: T,
+    pub tol_feas: T,
+    pub tol_infeas_abs: T,
+    pub tol_infeas_rel: T,
+    pub tol_ktratio: T,
+    /// names of the tolerance settings that were raised
+    pub clamped: Vec<&'static str>,
+}
+
+/// Details of a solve terminated because the step length vanished,
+/// even after retrying with an uncorrected step and a centering step.
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct VanishingStep<T> {
+    /// iteration at which the final step was attempted
+    pub iteration: u32,
+    /// length of the final step
+    pub step_length: T,
+    /// index of the cone that limited the step, if any.  Cones
+    /// are numbered as in the problem solved, i.e. after presolve
+    pub cone: Option<usize>,
+}
 
 /// Standard-form solver type implementing the [`Info`](crate::solver::core::traits::Info) and [`InfoPrint`](crate::solver::core::traits::InfoPrint) traits
 
@@ -24,15 +90,48 @@ pub struct DefaultInfo<T> {
     pub ktratio: T,
 
     // previous iterate
-    prev_cost_primal: T,
-    prev_cost_dual: T,
-    prev_res_primal: T,
-    prev_res_dual: T,
-    prev_gap_abs: T,
-    prev_gap_rel: T,
+    pub(crate) prev_cost_primal: T,
+    pub(crate) prev_cost_dual: T,
+    pub(crate) prev_res_primal: T,
+    pub(crate) prev_res_dual: T,
+    pub(crate) prev_gap_abs: T,
+    pub(crate) prev_gap_rel: T,
 
     pub solve_time: f64,
     pub status: SolverStatus,
+
+    // settings changes made by automatic recovery, if any
+    pub recovery: Option<RecoveryInfo<T>>,
+
+    // description of an abnormal termination of the most recent
+    // solve, e.g. the message of a panic caught during the solve
+    pub termination_detail: Option<String>,
+
+    // the final step of a solve ended by a vanishing step length
+    pub vanishing_step: Option<VanishingStep<T>>,
+
+    // termination tolerances in use, as checked at setup
+    pub tolerances: EffectiveTolerances<T>,
+
+    // operation counts for the most recent solve
+    counters: OpCounters,
+
+    // iterative refinement statistics for the most recent solve
+    refinement: RefinementStats<T>,
+
+    // true once conservative steps are engaged near convergence
+    conservative_steps: bool,
+
+    // records of the most recent iterations, retained for
+    // post-mortem reporting.  Length is at most `postmortem_window`
+    recent: VecDeque<IterationRecord<T>>,
+
+    // buffer for formatting the progress line printed at each iteration
+    pub(crate) line: String,
+
+    // time spent in each solver phase during setup and the most recent solve
+    #[cfg(feature = "profiling")]
+    profile: PhaseTimes,
 }
 
 impl<T> DefaultInfo<T>
@@ -42,9 +141,83 @@ where
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Records of the most recent solver iterations, oldest first.  The
+    /// number retained is set by [`postmortem_window`](crate::solver::DefaultSettings::postmortem_window).
+    pub fn recent_history(&self) -> Vec<IterationRecord<T>> {
+        self.recent.iter().copied().collect()
+    }
+
+    /// Counts of KKT solves, factorizations and other operations
+    /// performed during the most recent solve.
+    pub fn counters(&self) -> OpCounters {
+        self.counters
+    }
+
+    /// Iterative refinement statistics for the KKT solves of the most
+    /// recent solve.   See [`RefinementStats`](crate::solver::RefinementStats).
+    pub fn refinement(&self) -> RefinementStats<T> {
+        self.refinement
+    }
+
+    /// Time spent in each phase of the solver setup and most recent
+    /// solve.  Requires the `profiling` feature.
+    #[cfg(feature = "profiling")]
+    pub fn profile(&self) -> &PhaseTimes {
+        &self.profile
+    }
+
+    pub(crate) fn record_tolerances(
+        &mut self,
+        settings: &DefaultSettings<T>,
+        clamped: &[&'static str],
+    ) {
+        self.tolerances = EffectiveTolerances {
+            tol_gap_abs: settings.tol_gap_abs,
+            tol_gap_rel: settings.tol_gap_rel,
+            tol_feas: settings.tol_feas,
+            tol_infeas_abs: settings.tol_infeas_abs,
+            tol_infeas_rel: settings.tol_infeas_rel,
+            tol_ktratio: settings.tol_ktratio,
+            clamped: clamped.to_vec(),
+        };
+    }
+
+    pub(crate) fn recent_history_iter(&self) -> impl Iterator<Item = &IterationRecord<T>> {
+        self.recent.iter()
+    }
+
+    fn record_iteration(&mut self, window: usize, max_iter: u32) {
+        if window == 0 {
+            self.recent.clear();
+            return;
+        }
+        while self.recent.len() >= window {
+            self.recent.pop_front();
+        }
+        // size the window once, rather than growing it over the
+        // first iterations.  A solve records at most max_iter + 1
+        let capacity = usize::min(window, max_iter as usize + 1);
+        if self.recent.capacity() < capacity {
+            self.recent.reserve_exact(capacity - self.recent.len());
+        }
+        self.recent.push_back(IterationRecord {
+            iteration: self.iterations,
+            cost_primal: self.cost_primal,
+            cost_dual: self.cost_dual,
+            res_primal: self.res_primal,
+            res_dual: self.res_dual,
+            gap_abs: self.gap_abs,
+            gap_rel: self.gap_rel,
+            ktratio: self.ktratio,
+            μ: self.μ,
+            step_length: self.step_length,
+            conservative: false,
+        });
+    }
 }
 
-impl<T> Info<T> for DefaultInfo<T>
+impl<'a, T> Info<T, DefaultProblemData<'a, T>> for DefaultInfo<T>
 where
     T: FloatT,
 {
@@ -53,8 +226,12 @@ where
 
     fn reset(&mut self, timers: &mut Timers) {
         self.status = SolverStatus::Unsolved;
+        self.termination_detail = None;
+        self.vanishing_step = None;
         self.iterations = 0;
         self.solve_time = 0f64;
+        self.conservative_steps = false;
+        self.recent.clear();
 
         timers.reset_timer("solve");
     }
@@ -74,11 +251,15 @@ where
     fn finalize(&mut self, timers: &mut Timers) {
         //final check of timers
         self.solve_time = timers.total_time().as_secs_f64();
+        #[cfg(feature = "profiling")]
+        {
+            self.profile = timers.profile.clone();
+        }
     }
 
     fn update(
         &mut self,
-        data: &mut DefaultProblemData<T>,
+        data: &mut DefaultProblemData<'_, T>,
         variables: &DefaultVariables<T>,
         residuals: &DefaultResiduals<T>,
         timers: &Timers,
@@ -150,6 +331,9 @@ where
         settings: &DefaultSettings<T>,
         iter: u32,
     ) -> bool {
+        // retain this iteration for post-mortem reporting
+        self.record_iteration(settings.postmortem_window, settings.max_iter);
+
         //  optimality or infeasibility
         // ---------------------
         self.check_convergence_full(residuals, settings);
@@ -221,6 +405,40 @@ where
         self.iterations = iter;
     }
 
+    fn save_recovery(
+        &mut self,
+        attempt: u32,
+        settings: &DefaultSettings<T>,
+        scaling: ScalingStrategy,
+    ) {
+        self.recovery = (attempt > 0).then_some(RecoveryInfo {
+            attempt,
+            static_regularization_constant: settings.static_regularization_constant,
+            max_step_fraction: settings.max_step_fraction,
+            dual_scaling: scaling == ScalingStrategy::Dual,
+        });
+    }
+
+    fn counters_mut(&mut self) -> &mut OpCounters {
+        &mut self.counters
+    }
+
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<T> {
+        &mut self.refinement
+    }
+
+    fn check_conservative_steps(&mut self, settings: &DefaultSettings<T>) -> bool {
+        // once engaged, stays engaged for the rest of the solve
+        if settings.conservative_steps_enable && self.gap_rel < settings.conservative_steps_gap_rel
+        {
+            self.conservative_steps = true;
+        }
+        if let Some(rec) = self.recent.back_mut() {
+            rec.conservative = self.conservative_steps;
+        }
+        self.conservative_steps
+    }
+
     fn get_status(&self) -> SolverStatus {
         self.status
     }
@@ -228,6 +446,28 @@ where
     fn set_status(&mut self, status: SolverStatus) {
         self.status = status;
     }
+
+    fn record_panic(&mut self, message: &str) {
+        self.status = SolverStatus::NumericalError;
+        self.termination_detail = Some(message.to_string());
+    }
+
+    fn record_vanishing_step(&mut self, iter: u32, α: T, cone: Option<usize>) {
+        self.status = SolverStatus::InsufficientProgress;
+        let limit = match cone {
+            Some(i) => format!("limited by cone {}", i),
+            None => "not limited by a cone".to_string(),
+        };
+        self.termination_detail = Some(format!(
+            "step length {:e} at iteration {} {}",
+            α, iter, limit
+        ));
+        self.vanishing_step = Some(VanishingStep {
+            iteration: iter,
+            step_length: α,
+            cone,
+        });
+    }
 }
 
 // Utility functions for convergence checkiing
@@ -2,49 +2,72 @@
 use crate::solver::chordal::ChordalInfo;
 
 use crate::stdio;
+use crate::stdio::Write;
 use crate::{
     algebra::*,
     solver::core::cones::{SupportedConeAsTag, SupportedConeTag},
 };
-use std::io::Write;
+use core::fmt::Write as _;
 
 use super::*;
 use crate::solver::core::{
     cones::{CompositeCone, Cone},
     traits::InfoPrint,
+    SolverStatus,
 };
-use std::time::Duration;
-
-macro_rules! expformat {
-    ($fmt:expr,$val:expr) => {
+use core::time::Duration;
+
+// append a value in exponential format to a String, with
+// the exponent padded to two digits and always signed
+macro_rules! expwrite {
+    ($buf:expr, $fmt:expr, $val:expr) => {{
+        let buf: &mut String = $buf;
+        let start = buf.len();
+        // writing to a String can't fail
+        write!(buf, $fmt, $val).unwrap();
         if $val.is_finite() {
-            _exp_str_reformat(format!($fmt, $val))
-        } else {
-            format!($fmt, $val)
+            _exp_str_reformat(buf, start);
         }
-    };
+    }};
 }
 
-impl<T> InfoPrint<T> for DefaultInfo<T>
+impl<'a, T> InfoPrint<T, DefaultProblemData<'a, T>> for DefaultInfo<T>
 where
     T: FloatT,
 {
-    type D = DefaultProblemData<T>;
     type C = CompositeCone<T>;
     type SE = DefaultSettings<T>;
 
     fn print_configuration(
         &self,
         settings: &DefaultSettings<T>,
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         cones: &CompositeCone<T>,
-    ) -> std::io::Result<()> {
+    ) -> stdio::Result<()> {
         if !settings.verbose {
-            return std::io::Result::Ok(());
+            return stdio::Result::Ok(());
         }
 
         let mut out = stdio::stdout();
 
+        if let Some(ref reductions) = data.reductions {
+            writeln!(
+                out,
+                "\npresolve: removed {} variables and {} constraints",
+                reductions.removed_variables.len(),
+                reductions.removed_constraints.len()
+            )?;
+        }
+
+        if let Some(ref dualization) = data.dualization {
+            writeln!(
+                out,
+                "\ndualized: solving the dual problem with {} variables and {} constraints",
+                dualization.q.len(),
+                dualization.b.len()
+            )?;
+        }
+
         if let Some(ref presolver) = data.presolver {
             writeln!(
                 out,
@@ -61,8 +84,33 @@ where
         writeln!(out, "\nproblem:")?;
         writeln!(out, "  variables     = {}", data.n)?;
         writeln!(out, "  constraints   = {}", data.m)?;
-        writeln!(out, "  nnz(P)        = {}", data.P.nnz())?;
+        writeln!(
+            out,
+            "  nnz(P)        = {} ({})",
+            data.P.nnz(),
+            data.P_structure().as_str()
+        )?;
+        if data.P_converted_to_triu {
+            writeln!(
+                out,
+                "  note: P supplied as full symmetric, using upper triangle"
+            )?;
+        }
         writeln!(out, "  nnz(A)        = {}", data.A.nnz())?;
+        if let Some(merged) = data.input_merged_entries {
+            writeln!(
+                out,
+                "  note: sorted the entries of P and A, summing {} repeated entries",
+                merged
+            )?;
+        }
+        if data.huge_entries > 0 {
+            writeln!(
+                out,
+                "  warning: {} entries of q and b exceed {:.1e} in magnitude, which may cause poor scaling",
+                data.huge_entries, settings.infinite_bound_threshold
+            )?;
+        }
         writeln!(out, "  cones (total) = {}", cones.len())?;
 
         //All dims here are dummies since we just care about the cone type
@@ -76,15 +124,15 @@ where
         _print_conedims_by_type(cones, SupportedConeTag::PSDTriangleCone)?;
 
         writeln!(out,)?;
-        _print_settings(settings)?;
+        _print_settings(settings, &self.tolerances.clamped)?;
         writeln!(out,)?;
 
-        std::io::Result::Ok(())
+        stdio::Result::Ok(())
     }
 
-    fn print_status_header(&self, settings: &DefaultSettings<T>) -> std::io::Result<()> {
+    fn print_status_header(&self, settings: &DefaultSettings<T>) -> stdio::Result<()> {
         if !settings.verbose {
-            return std::io::Result::Ok(());
+            return stdio::Result::Ok(());
         }
 
         let mut out = stdio::stdout();
@@ -104,40 +152,28 @@ where
             "---------------------------------------------------------------------------------------------"
         )?;
         stdio::stdout().flush()?;
-        std::io::Result::Ok(())
+        stdio::Result::Ok(())
     }
 
-    fn print_status(&self, settings: &DefaultSettings<T>) -> std::io::Result<()> {
+    fn print_status(&mut self, settings: &DefaultSettings<T>) -> stdio::Result<()> {
         if !settings.verbose {
-            return std::io::Result::Ok(());
-        }
-
-        let mut out = stdio::stdout();
-
-        write!(out, "{:>3}  ", self.iterations)?;
-        write!(out, "{}  ", expformat!("{:+8.4e}", self.cost_primal))?;
-        write!(out, "{}  ", expformat!("{:+8.4e}", self.cost_dual))?;
-        let gapprint = T::min(self.gap_abs, self.gap_rel);
-        write!(out, "{}  ", expformat!("{:6.2e}", gapprint))?;
-        write!(out, "{}  ", expformat!("{:6.2e}", self.res_primal))?;
-        write!(out, "{}  ", expformat!("{:6.2e}", self.res_dual))?;
-        write!(out, "{}  ", expformat!("{:6.2e}", self.ktratio))?;
-        write!(out, "{}  ", expformat!("{:6.2e}", self.μ))?;
-
-        if self.iterations > 0 {
-            write!(out, "{}  ", expformat!("{:>.2e}", self.step_length))?;
-        } else {
-            write!(out, " ------   ")?; //info.step_length
+            return stdio::Result::Ok(());
         }
 
-        writeln!(out,)?;
+        // format the line into a buffer reused across iterations
+        // to avoid allocating on every call
+        let mut line = core::mem::take(&mut self.line);
+        line.clear();
+        _write_status_line(&mut line, self);
+        let result = stdio::stdout().write_all(line.as_bytes());
+        self.line = line;
 
-        std::io::Result::Ok(())
+        result
     }
 
-    fn print_footer(&self, settings: &DefaultSettings<T>) -> std::io::Result<()> {
+    fn print_footer(&mut self, settings: &DefaultSettings<T>) -> stdio::Result<()> {
         if !settings.verbose {
-            return std::io::Result::Ok(());
+            return stdio::Result::Ok(());
         }
 
         let mut out = stdio::stdout();
@@ -148,16 +184,103 @@ where
 
         writeln!(out, "Terminated with status = {}", self.status)?;
 
+        if _is_failure_status(&self.status) {
+            let mut line = core::mem::take(&mut self.line);
+            let result = _print_recent_history(&mut line, self);
+            self.line = line;
+            result?;
+        }
+
+        if let Some(r) = self.recovery {
+            write!(
+                out,
+                "auto recovery attempt {}: static reg ϵ1 = {:.1e}, max step = {:.3}",
+                r.attempt, r.static_regularization_constant, r.max_step_fraction
+            )?;
+            writeln!(
+                out,
+                "{}",
+                if r.dual_scaling { ", dual scaling" } else { "" }
+            )?;
+        }
+
         writeln!(
             out,
             "solve time = {:?}",
             Duration::from_secs_f64(self.solve_time)
         )?;
 
-        std::io::Result::Ok(())
+        #[cfg(feature = "profiling")]
+        write!(out, "{}", self.profile())?;
+
+        stdio::Result::Ok(())
     }
 }
 
+fn _is_failure_status(status: &SolverStatus) -> bool {
+    status.is_errored() || matches!(status, SolverStatus::MaxIterations | SolverStatus::MaxTime)
+}
+
+fn _write_status_line<T: FloatT>(line: &mut String, info: &DefaultInfo<T>) {
+    // writing to a String can't fail
+    write!(line, "{:>3}  ", info.iterations).unwrap();
+    expwrite!(line, "{:+8.4e}", info.cost_primal);
+    line.push_str("  ");
+    expwrite!(line, "{:+8.4e}", info.cost_dual);
+    line.push_str("  ");
+    let gapprint = T::min(info.gap_abs, info.gap_rel);
+    for val in [
+        gapprint,
+        info.res_primal,
+        info.res_dual,
+        info.ktratio,
+        info.μ,
+    ] {
+        expwrite!(line, "{:6.2e}", val);
+        line.push_str("  ");
+    }
+
+    if info.iterations > 0 {
+        expwrite!(line, "{:>.2e}", info.step_length);
+        line.push_str("  ");
+    } else {
+        line.push_str(" ------   "); //info.step_length
+    }
+    line.push('\n');
+}
+
+fn _print_recent_history<T: FloatT>(line: &mut String, info: &DefaultInfo<T>) -> stdio::Result<()> {
+    let mut history = info.recent_history_iter().peekable();
+    if history.peek().is_none() {
+        return stdio::Result::Ok(());
+    }
+
+    let mut out = stdio::stdout();
+
+    writeln!(out, "recent iterations:")?;
+    writeln!(
+        out,
+        "iter    pcost        dcost       pres      dres      k/t        μ       step"
+    )?;
+    for rec in history {
+        line.clear();
+        write!(line, "{:>3}  ", rec.iteration).unwrap();
+        expwrite!(line, "{:+8.4e}", rec.cost_primal);
+        line.push_str("  ");
+        expwrite!(line, "{:+8.4e}", rec.cost_dual);
+        line.push_str("  ");
+        for val in [rec.res_primal, rec.res_dual, rec.ktratio, rec.μ] {
+            expwrite!(line, "{:6.2e}", val);
+            line.push_str("  ");
+        }
+        expwrite!(line, "{:>.2e}", rec.step_length);
+        writeln!(line, "{}", if rec.conservative { " *" } else { "" }).unwrap();
+        out.write_all(line.as_bytes())?;
+    }
+
+    stdio::Result::Ok(())
+}
+
 fn _bool_on_off(v: bool) -> &'static str {
     match v {
         true => "on",
@@ -165,7 +288,10 @@ fn _bool_on_off(v: bool) -> &'static str {
     }
 }
 
-fn _print_settings<T: FloatT>(settings: &DefaultSettings<T>) -> std::io::Result<()> {
+fn _print_settings<T: FloatT>(
+    settings: &DefaultSettings<T>,
+    clamped_tolerances: &[&str],
+) -> stdio::Result<()> {
     let set = settings;
     let mut out = stdio::stdout();
 
@@ -198,6 +324,14 @@ fn _print_settings<T: FloatT>(settings: &DefaultSettings<T>) -> std::io::Result<
         "  tol_feas = {:.1e}, tol_gap_abs = {:.1e}, tol_gap_rel = {:.1e},",
         set.tol_feas, set.tol_gap_abs, set.tol_gap_rel
     )?;
+    for name in clamped_tolerances {
+        writeln!(
+            out,
+            "  warning: {} raised to the smallest value attainable at {} bit precision",
+            name,
+            _get_precision_string::<T>()
+        )?;
+    }
 
     writeln!(
         out,
@@ -225,8 +359,10 @@ fn _print_settings<T: FloatT>(settings: &DefaultSettings<T>) -> std::io::Result<
 
     writeln!(
         out,
-        "               max iter = {}, stop ratio = {:.1}",
-        set.iterative_refinement_max_iter, set.iterative_refinement_stop_ratio
+        "               max iter = {}, stop ratio = {:.1}, refactor = {}",
+        set.iterative_refinement_max_iter,
+        set.iterative_refinement_stop_ratio,
+        _bool_on_off(set.iterative_refinement_refactor)
     )?;
 
     writeln!(
@@ -243,14 +379,14 @@ fn _print_settings<T: FloatT>(settings: &DefaultSettings<T>) -> std::io::Result<
         set.equilibrate_max_iter,
     )?;
 
-    std::io::Result::Ok(())
+    stdio::Result::Ok(())
 }
 
 #[cfg(feature = "sdp")]
 fn print_chordal_decomposition<T: FloatT>(
     chordal_info: &ChordalInfo<T>,
     settings: &DefaultSettings<T>,
-) -> std::io::Result<()> {
+) -> stdio::Result<()> {
     let mut out = stdio::stdout();
 
     writeln!(out, "\nchordal decomposition:")?;
@@ -291,24 +427,24 @@ fn print_chordal_decomposition<T: FloatT>(
         chordal_info.final_psd_cone_count()
     )?;
 
-    std::io::Result::Ok(())
+    stdio::Result::Ok(())
 }
 
 fn _get_precision_string<T: FloatT>() -> String {
-    (::std::mem::size_of::<T>() * 8).to_string()
+    (core::mem::size_of::<T>() * 8).to_string()
 }
 
 fn _print_conedims_by_type<T: FloatT>(
     cones: &CompositeCone<T>,
     conetag: SupportedConeTag,
-) -> std::io::Result<()> {
+) -> stdio::Result<()> {
     let maxlistlen = 5;
 
     let count = cones.get_type_count(conetag);
 
     //skip if there are none of this type
     if count == 0 {
-        return std::io::Result::Ok(());
+        return stdio::Result::Ok(());
     }
 
     let mut out = stdio::stdout();
@@ -348,7 +484,7 @@ fn _print_conedims_by_type<T: FloatT>(
 
     writeln!(out,)?;
 
-    std::io::Result::Ok(())
+    stdio::Result::Ok(())
 }
 
 // convert a string in LowerExp display format into one that
@@ -356,9 +492,11 @@ fn _print_conedims_by_type<T: FloatT>(
 // 2) has at least two digits in the exponent.
 // This matches the Julia output formatting.
 
-fn _exp_str_reformat(mut thestr: String) -> String {
+// reformat the exponent of the number starting at `start`,
+// which must be the last thing in the string
+fn _exp_str_reformat(thestr: &mut String, start: usize) {
     // Safe to `unwrap` as `num` is guaranteed to contain `'e'`
-    let eidx = thestr.find('e').unwrap();
+    let eidx = start + thestr[start..].find('e').unwrap();
     let has_sign = thestr.chars().nth(eidx + 1).unwrap() == '-';
 
     let has_short_exp = {
@@ -384,5 +522,4 @@ fn _exp_str_reformat(mut thestr: String) -> String {
 
     let shift = if has_sign { 2 } else { 1 };
     thestr.insert_str(eidx + shift, chars);
-    thestr
 }
@@ -1,11 +1,17 @@
 use crate::{
     algebra::*,
-    solver::{core::SolverJSONReadWrite, DefaultSettings, DefaultSolver, SupportedConeT},
+    solver::{
+        core::{IPSolver, SolverJSONReadWrite},
+        DefaultSettings, DefaultSolver, OptimizationSense, SupportedConeT,
+    },
 };
 
+use super::{check_dimensions, ClarabelError};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::io::Write;
+use std::io::{BufReader, Write};
+use std::path::Path;
 use std::{fs::File, io, io::Read};
+use thiserror::Error;
 
 // A struct very similar to the problem data, but containing only
 // the data types provided by the user (i.e. no internal types).
@@ -27,9 +33,9 @@ where
 {
     fn write_to_file(&self, file: &mut File) -> Result<(), io::Error> {
         let mut json_data = JsonProblemData {
-            P: self.data.P.clone(),
+            P: self.data.P.clone().into(),
             q: self.data.q.clone(),
-            A: self.data.A.clone(),
+            A: self.data.A.clone().into(),
             b: self.data.b.clone(),
             cones: self.data.cones.clone(),
             settings: self.settings.clone(),
@@ -48,6 +54,14 @@ where
         json_data.A.lrscale(einv, dinv);
         json_data.b.hadamard(einv);
 
+        // write q with the user's objective sense
+        if self.data.maximization_flag {
+            json_data.q.negate();
+            json_data.settings.sense = OptimizationSense::Maximize;
+        } else {
+            json_data.settings.sense = OptimizationSense::Minimize;
+        }
+
         // sanitize settings to remove values that
         // can't be serialized, i.e. infs
         sanitize_settings(&mut json_data.settings);
@@ -75,9 +89,8 @@ where
         let b = json_data.b;
         let cones = json_data.cones;
         let settings = json_data.settings;
-        let solver = Self::new(&P, &q, &A, &b, &cones, settings);
-
-        Ok(solver)
+        Self::new(&P, &q, &A, &b, &cones, settings)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 }
 
@@ -93,6 +106,187 @@ fn desanitize_settings<T: FloatT>(settings: &mut DefaultSettings<T>) {
     }
 }
 
+// ---------------------------------------------------
+// Versioned interchange format for reproducing a solve, e.g. from a
+// bug report.   Matrices are stored as triplets so that files are easy
+// to inspect and edit by hand.   Unknown top level fields are ignored,
+// so files written by later versions can be read if the version
+// number is unchanged.
+// ---------------------------------------------------
+
+/// Version of the JSON problem format written by [`write_problem_json`].
+pub const PROBLEM_JSON_VERSION: u32 = 1;
+
+/// Error type returned when reading or writing JSON problem files.
+#[derive(Error, Debug)]
+pub enum JsonProblemError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported problem file version {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid problem data: {0}")]
+    InvalidData(String),
+    #[error("solver setup failed: {0}")]
+    Setup(#[from] ClarabelError),
+}
+
+/// Problem data and settings for a single solve, as stored by
+/// [`write_problem_json`] and [`read_problem_json`].
+#[derive(Debug, Clone)]
+pub struct JsonProblem<T: FloatT = f64> {
+    pub P: CscMatrix<T>,
+    pub q: Vec<T>,
+    pub A: CscMatrix<T>,
+    pub b: Vec<T>,
+    pub cones: Vec<SupportedConeT<T>>,
+    pub settings: DefaultSettings<T>,
+}
+
+impl<T> JsonProblem<T>
+where
+    T: FloatT,
+{
+    /// Create a solver for this problem with the stored settings.
+    pub fn solver(&self) -> Result<DefaultSolver<T>, ClarabelError> {
+        DefaultSolver::new(
+            &self.P,
+            &self.q,
+            &self.A,
+            &self.b,
+            &self.cones,
+            self.settings.clone(),
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+struct JsonTriplets<T> {
+    m: usize,
+    n: usize,
+    i: Vec<usize>,
+    j: Vec<usize>,
+    v: Vec<T>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+struct JsonProblemFile<T: FloatT> {
+    version: u32,
+    P: JsonTriplets<T>,
+    q: Vec<T>,
+    A: JsonTriplets<T>,
+    b: Vec<T>,
+    cones: Vec<SupportedConeT<T>>,
+    #[serde(default)]
+    settings: DefaultSettings<T>,
+}
+
+impl<T: FloatT> JsonTriplets<T> {
+    fn from_csc(M: &CscMatrix<T>) -> Self {
+        let (i, j, v) = M.findnz();
+        Self {
+            m: M.m,
+            n: M.n,
+            i,
+            j,
+            v,
+        }
+    }
+
+    fn to_csc(&self, name: &str) -> Result<CscMatrix<T>, JsonProblemError> {
+        let invalid = |msg: &str| JsonProblemError::InvalidData(format!("{}: {}", name, msg));
+        if self.i.len() != self.v.len() || self.j.len() != self.v.len() {
+            return Err(invalid("triplet lengths differ"));
+        }
+        if self.i.iter().any(|&i| i >= self.m) || self.j.iter().any(|&j| j >= self.n) {
+            return Err(invalid("index out of range"));
+        }
+        Ok(CscMatrix::new_from_triplets(
+            self.m,
+            self.n,
+            self.i.clone(),
+            self.j.clone(),
+            self.v.clone(),
+        ))
+    }
+}
+
+/// Write a problem and its settings in the versioned JSON interchange
+/// format.   The output is pretty printed for easy inspection.
+pub fn write_problem_json<T, W>(problem: &JsonProblem<T>, writer: W) -> Result<(), JsonProblemError>
+where
+    T: FloatT + Serialize + DeserializeOwned,
+    W: Write,
+{
+    let mut settings = problem.settings.clone();
+    sanitize_settings(&mut settings);
+
+    let json_data = JsonProblemFile {
+        version: PROBLEM_JSON_VERSION,
+        P: JsonTriplets::from_csc(&problem.P),
+        q: problem.q.clone(),
+        A: JsonTriplets::from_csc(&problem.A),
+        b: problem.b.clone(),
+        cones: problem.cones.clone(),
+        settings,
+    };
+
+    serde_json::to_writer_pretty(writer, &json_data)?;
+    Ok(())
+}
+
+/// Read a problem written by [`write_problem_json`].
+///
+/// Problem dimensions and cones are validated with the same checks as
+/// [`DefaultSolver::new`], and inconsistencies are returned as a
+/// [`JsonProblemError::InvalidData`] error.
+pub fn read_problem_json<T, R>(reader: R) -> Result<JsonProblem<T>, JsonProblemError>
+where
+    T: FloatT + Serialize + DeserializeOwned,
+    R: Read,
+{
+    let json_data: JsonProblemFile<T> = serde_json::from_reader(reader)?;
+
+    if json_data.version > PROBLEM_JSON_VERSION {
+        return Err(JsonProblemError::UnsupportedVersion(json_data.version));
+    }
+
+    let P = json_data.P.to_csc("P")?;
+    let A = json_data.A.to_csc("A")?;
+    let (q, b, cones) = (json_data.q, json_data.b, json_data.cones);
+
+    check_dimensions(&P, &q, &A, &b, &cones)
+        .map_err(|e| JsonProblemError::InvalidData(e.to_string()))?;
+
+    let mut settings = json_data.settings;
+    desanitize_settings(&mut settings);
+
+    Ok(JsonProblem {
+        P,
+        q,
+        A,
+        b,
+        cones,
+        settings,
+    })
+}
+
+/// Read a problem from a JSON file written by [`write_problem_json`],
+/// then solve it and print the solver output.   Verbose printing is
+/// always enabled.   Intended for triaging problems from bug reports.
+pub fn solve_json_file(path: impl AsRef<Path>) -> Result<DefaultSolver<f64>, JsonProblemError> {
+    let file = File::open(path)?;
+    let mut problem = read_problem_json::<f64, _>(BufReader::new(file))?;
+    problem.settings.verbose = true;
+
+    let mut solver = problem.solver()?;
+    solver.solve();
+    Ok(solver)
+}
+
 #[test]
 fn test_json_io() {
     use crate::solver::IPSolver;
@@ -120,7 +314,8 @@ fn test_json_io() {
         .build()
         .unwrap();
 
-    let mut solver = crate::solver::DefaultSolver::<f64>::new(&P, &q, &A, &b, &cones, settings);
+    let mut solver =
+        crate::solver::DefaultSolver::<f64>::new(&P, &q, &A, &b, &cones, settings).unwrap();
     solver.solve();
 
     // write the problem to a file
@@ -133,3 +328,117 @@ fn test_json_io() {
     solver2.solve();
     assert_eq!(solver.solution.x, solver2.solution.x);
 }
+
+#[cfg(test)]
+fn test_json_problem() -> JsonProblem<f64> {
+    use crate::solver::SupportedConeT::*;
+
+    // min ½(x₁² + x₂²) + x₁ s.t. x₁ + x₂ = 1, x ≥ 0
+    let P = CscMatrix::identity(2);
+    let A = CscMatrix::from(&[
+        [1., 1.],  //
+        [-1., 0.], //
+        [0., -1.], //
+    ]);
+    let settings = crate::solver::DefaultSettingsBuilder::default()
+        .verbose(false)
+        .max_iter(50)
+        .build()
+        .unwrap();
+
+    JsonProblem {
+        P,
+        q: vec![1., 0.],
+        A,
+        b: vec![1., 0., 0.],
+        cones: vec![ZeroConeT(1), NonnegativeConeT(2)],
+        settings,
+    }
+}
+
+#[test]
+fn test_problem_json_roundtrip() {
+    let problem = test_json_problem();
+
+    let mut buffer = Vec::new();
+    write_problem_json(&problem, &mut buffer).unwrap();
+    let problem2: JsonProblem<f64> = read_problem_json(buffer.as_slice()).unwrap();
+
+    assert_eq!(problem.P, problem2.P);
+    assert_eq!(problem.q, problem2.q);
+    assert_eq!(problem.A, problem2.A);
+    assert_eq!(problem.b, problem2.b);
+    assert_eq!(
+        format!("{:?}", problem.cones),
+        format!("{:?}", problem2.cones)
+    );
+    assert_eq!(problem2.settings.max_iter, 50);
+    assert_eq!(problem2.settings.time_limit, f64::INFINITY);
+
+    // the file is readable and versioned
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.contains("\"version\": 1"));
+
+    // both problems should produce the same solution
+    let mut solver1 = problem.solver().unwrap();
+    solver1.solve();
+    let mut solver2 = problem2.solver().unwrap();
+    solver2.solve();
+    assert_eq!(solver2.solution.status, crate::solver::SolverStatus::Solved);
+    assert_eq!(solver1.solution.x, solver2.solution.x);
+    assert!(solver2.solution.x.dist(&[0., 1.]) <= 1e-4);
+
+    // solve directly from a file
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    write_problem_json(&problem, file.as_file_mut()).unwrap();
+    let solver = solve_json_file(file.path()).unwrap();
+    assert_eq!(solver.solution.status, crate::solver::SolverStatus::Solved);
+}
+
+#[test]
+fn test_problem_json_forward_compatible() {
+    let problem = test_json_problem();
+
+    let mut buffer = Vec::new();
+    write_problem_json(&problem, &mut buffer).unwrap();
+
+    // unknown top level fields are ignored
+    let mut value: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+    value["comment"] = serde_json::json!("attached to issue");
+    value["solution"] = serde_json::json!({"x": [0.0, 1.0]});
+    let text = serde_json::to_string(&value).unwrap();
+    let problem2: JsonProblem<f64> = read_problem_json(text.as_bytes()).unwrap();
+    assert_eq!(problem.A, problem2.A);
+
+    // but later versions are rejected
+    value["version"] = serde_json::json!(PROBLEM_JSON_VERSION + 1);
+    let text = serde_json::to_string(&value).unwrap();
+    assert!(matches!(
+        read_problem_json::<f64, _>(text.as_bytes()),
+        Err(JsonProblemError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn test_problem_json_validation() {
+    let mut problem = test_json_problem();
+    problem.b.push(0.);
+    let mut buffer = Vec::new();
+    write_problem_json(&problem, &mut buffer).unwrap();
+    assert!(matches!(
+        read_problem_json::<f64, _>(buffer.as_slice()),
+        Err(JsonProblemError::InvalidData(_))
+    ));
+
+    // triplets out of range
+    let problem = test_json_problem();
+    let mut buffer = Vec::new();
+    write_problem_json(&problem, &mut buffer).unwrap();
+    let mut value: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+    value["A"]["i"][0] = serde_json::json!(7);
+    let text = serde_json::to_string(&value).unwrap();
+    assert!(matches!(
+        read_problem_json::<f64, _>(text.as_bytes()),
+        Err(JsonProblemError::InvalidData(_))
+    ));
+}
@@ -3,33 +3,38 @@ use crate::solver::core::{
     cones::{CompositeCone, Cone},
     kktsolvers::{direct::*, *},
     traits::{KKTSystem, Settings},
-    StepDirection,
+    OpCounters, RefinementStats, StepDirection,
 };
 
 use crate::algebra::*;
+use crate::timers::profile_scope;
+use alloc::borrow::Cow;
 
 // We require Send here to allow pyo3 builds to share
 // solver objects between threads.
 
-type BoxedKKTSolver<T> = Box<dyn KKTSolver<T> + Send>;
+type BoxedKKTSolver<T> = Box<dyn KKTSolver<T> + Send + Sync>;
 
 /// Standard-form solver type implementing the [`KKTSystem`](crate::solver::core::traits::KKTSystem) trait
 
 pub struct DefaultKKTSystem<T> {
-    kktsolver: BoxedKKTSolver<T>,
+    pub(crate) kktsolver: BoxedKKTSolver<T>,
 
-    // solution vector for constant part of KKT solves
-    x1: Vec<T>,
-    z1: Vec<T>,
+    // solution and work vectors for the step computations
+    work: Workspace<T>,
 
-    // solution vector for general KKT solves
-    x2: Vec<T>,
-    z2: Vec<T>,
+    // the constant part has not been solved since the last
+    // update, and will be solved together with the next step
+    constant_rhs_pending: bool,
 
-    // work vectors for assembling/dissambling vectors
-    workx: Vec<T>,
-    workz: Vec<T>,
-    work_conic: Vec<T>,
+    // the KKT matrix uses user supplied Hs blocks
+    // in place of the cones' own scaling
+    initial_Hs: bool,
+
+    // current iteration, and the KKT snapshot
+    // recorded at `kkt_snapshot_iteration`
+    iteration: u32,
+    pub(crate) snapshot: Option<KktSnapshot<T>>,
 }
 
 impl<T> DefaultKKTSystem<T>
@@ -37,7 +42,7 @@ where
     T: FloatT,
 {
     pub fn new(
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         cones: &CompositeCone<T>,
         settings: &DefaultSettings<T>,
     ) -> Self {
@@ -59,58 +64,79 @@ where
             panic!("Indirect and other solve strategies not yet supported.");
         };
 
-        //the LHS constant part of the reduced solve
-        let x1 = vec![T::zero(); n];
-        let z1 = vec![T::zero(); m];
-
-        //the LHS for other solves
-        let x2 = vec![T::zero(); n];
-        let z2 = vec![T::zero(); m];
-
-        //workspace compatible with (x,z)
-        let workx = vec![T::zero(); n];
-        let workz = vec![T::zero(); m];
-
-        //additional conic workspace vector compatible with s and z
-        let work_conic = vec![T::zero(); m];
-
         Self {
             kktsolver,
-            x1,
-            z1,
-            x2,
-            z2,
-            workx,
-            workz,
-            work_conic,
+            work: Workspace::new(n, m),
+            constant_rhs_pending: false,
+            initial_Hs: false,
+            iteration: 0,
+            snapshot: None,
         }
     }
+
+    // bytes allocated by `new` for the same problem data
+    pub(crate) fn memory_estimate(
+        data: &DefaultProblemData<'_, T>,
+        cones: &CompositeCone<T>,
+        settings: &DefaultSettings<T>,
+    ) -> usize {
+        let (m, n) = (data.m, data.n);
+        let kktsolver = if settings.direct_kkt_solver {
+            DirectLDLKKTSolver::<T>::memory_estimate(&data.P, &data.A, cones)
+        } else {
+            0
+        };
+        kktsolver + Workspace::<T>::memory_estimate(n, m)
+    }
 }
 
-impl<T> KKTSystem<T> for DefaultKKTSystem<T>
+impl<'a, T> KKTSystem<T, DefaultProblemData<'a, T>> for DefaultKKTSystem<T>
 where
     T: FloatT,
 {
-    type D = DefaultProblemData<T>;
     type V = DefaultVariables<T>;
     type C = CompositeCone<T>;
     type SE = DefaultSettings<T>;
 
     fn update(
         &mut self,
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         cones: &CompositeCone<T>,
         settings: &DefaultSettings<T>,
     ) -> bool {
+        profile_scope!(KKTUpdate);
+
         // update the linear solver with new cones
+        self.constant_rhs_pending = false;
+        self.initial_Hs = cones.uses_initial_Hs();
         let is_success = self.kktsolver.update(cones, settings.core());
 
         if !is_success {
             return is_success;
         }
 
-        // calculate KKT solution for constant terms
-        return self.solve_constant_rhs(data, settings.core());
+        if _is_snapshot_iteration(self.iteration, settings) {
+            let mut snapshot = self.kktsolver.snapshot();
+            snapshot.iteration = self.iteration;
+            self.snapshot = Some(snapshot);
+        }
+
+        // calculate KKT solution for constant terms, or defer it
+        // to be solved together with the affine step
+        if settings.batched_kkt_solves {
+            self.constant_rhs_pending = true;
+            return true;
+        }
+        let is_success = self.solve_constant_rhs(data, settings.core());
+
+        if _is_snapshot_iteration(self.iteration, settings) {
+            let (rhs, lhs) = self.kktsolver.snapshot_rhs_lhs(0);
+            if let Some(snapshot) = self.snapshot.as_mut() {
+                (snapshot.rhs_constant, snapshot.lhs_constant) = (rhs, lhs);
+            }
+        }
+
+        return is_success;
 
         //PJG is_success should be a Result in rust
     }
@@ -119,61 +145,95 @@ where
         &mut self,
         lhs: &mut DefaultVariables<T>,
         rhs: &DefaultVariables<T>,
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         variables: &DefaultVariables<T>,
         cones: &mut CompositeCone<T>,
         step_direction: StepDirection,
         settings: &DefaultSettings<T>,
     ) -> bool {
-        let (x1, z1) = (&mut self.x1, &mut self.z1);
-        let (x2, z2) = (&self.x2, &self.z2); //from constant solve, so not mut
-        let (workx, workz) = (&mut self.workx, &mut self.workz);
+        profile_scope!(Solve);
 
         // solve for (x1,z1)
         // -----------
-        workx.copy_from(&rhs.x);
-
         // compute the vector c in the step equation HₛΔz + Δs = -c,
         // with shortcut in affine case
-        let Δs_const_term = &mut self.work_conic;
+        let work = &mut self.work;
+        let Δs_const_term = &mut work.Δs_const;
 
         match step_direction {
             StepDirection::Affine => {
                 Δs_const_term.copy_from(&variables.s);
             }
             StepDirection::Combined => {
-                cones.Δs_from_Δz_offset(Δs_const_term, &rhs.s, &mut lhs.z, &variables.z);
+                cones.Δs_from_Δz_offset(Δs_const_term, &rhs.s, &mut work.conic, &variables.z);
             }
         }
 
-        workz.waxpby(T::one(), Δs_const_term, -T::one(), &rhs.z);
+        work.z.waxpby(T::one(), Δs_const_term, -T::one(), &rhs.z);
 
         // ---------------------------------------------------
-        // this solves the variable part of reduced KKT system
-        self.kktsolver.setrhs(workx, workz);
-        let is_success = self.kktsolver.solve(Some(x1), Some(z1), settings.core());
+        // this solves the variable part of reduced KKT system,
+        // and also the constant part if deferred from update
+        let batched = self.constant_rhs_pending;
+        let is_success = if batched {
+            self.constant_rhs_pending = false;
+            work.x.axpby(-T::one(), &data.q, T::zero()); //work.x .= -q
+            self.kktsolver.solve_multiple(
+                &[(&work.x, &data.b), (&rhs.x, &work.z)],
+                &mut [
+                    (Some(&mut work.x2), Some(&mut work.z2)),
+                    (Some(&mut work.x1), Some(&mut work.z1)),
+                ],
+                settings.core(),
+            )
+        } else {
+            self.kktsolver.setrhs(&rhs.x, &work.z);
+            self.kktsolver
+                .solve(Some(&mut work.x1), Some(&mut work.z1), settings.core())
+        };
         if !is_success {
             return false;
         }
 
+        if _is_snapshot_iteration(self.iteration, settings) {
+            let (rhs, lhs) = self.kktsolver.snapshot_rhs_lhs(batched as usize);
+            if let Some(snapshot) = self.snapshot.as_mut() {
+                if batched {
+                    (snapshot.rhs_constant, snapshot.lhs_constant) =
+                        self.kktsolver.snapshot_rhs_lhs(0);
+                }
+                match step_direction {
+                    StepDirection::Affine => {
+                        (snapshot.rhs_predictor, snapshot.lhs_predictor) = (rhs, lhs);
+                    }
+                    StepDirection::Combined => {
+                        (snapshot.rhs_corrector, snapshot.lhs_corrector) = (rhs, lhs);
+                    }
+                }
+            }
+        }
+
+        let (x1, z1) = (&work.x1, &work.z1);
+        let (x2, z2) = (&work.x2, &work.z2); //from constant solve, so not mut
+
         // solve for Δτ.
         // -----------
         // Numerator first
-        let ξ = workx;
+        let ξ = &mut work.x;
         ξ.axpby(T::recip(variables.τ), &variables.x, T::zero());
 
         let two: T = (2.).as_T();
         let tau_num = rhs.τ - rhs.κ / variables.τ
             + data.q.dot(x1)
             + data.b.dot(z1)
-            + two * data.P.quad_form(ξ, x1);
+            + two * data.quad_form_P(ξ, x1);
 
         // offset ξ for the quadratic form in the denominator
-        let ξ_minus_x2 = ξ; //alias to ξ, same as workx
+        let ξ_minus_x2 = ξ; //alias to ξ, same as work.x
         ξ_minus_x2.axpby(-T::one(), x2, T::one());
 
         let mut tau_den = variables.κ / variables.τ - data.q.dot(x2) - data.b.dot(z2);
-        tau_den += data.P.quad_form(ξ_minus_x2, ξ_minus_x2) - data.P.quad_form(x2, x2);
+        tau_den += data.quad_form_P(ξ_minus_x2, ξ_minus_x2) - data.quad_form_P(x2, x2);
 
         // solve for (Δx,Δz)
         // -----------
@@ -185,7 +245,7 @@ where
         // -------------
         //  compute the linear term HₛΔz, where Hs = WᵀW for symmetric
         //  cones and Hs = μH(z) for asymmetric cones
-        cones.mul_Hs(&mut lhs.s, &lhs.z, workz);
+        cones.mul_Hs(&mut lhs.s, &lhs.z, &mut work.conic);
         lhs.s.axpby(-T::one(), Δs_const_term, -T::one()); // lhs.s = -(lhs.s+Δs_const_term);
 
         // solve for Δκ
@@ -201,18 +261,39 @@ where
     fn solve_initial_point(
         &mut self,
         variables: &mut DefaultVariables<T>,
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         settings: &DefaultSettings<T>,
     ) -> bool {
+        profile_scope!(Solve);
+
         let mut is_success;
 
-        if data.P.nnz() == 0 {
+        if data.P_structure() == QuadraticStructure::Zero {
             // LP initialization
             // solve with [0;b] as a RHS to get (x,-s) initializers
+            // and with [-q;0] as a RHS to get z initializer.
             // zero out any sparse cone variables at end
-            self.workx.fill(T::zero());
-            self.workz.copy_from(&data.b);
-            self.kktsolver.setrhs(&self.workx, &self.workz);
+            if settings.batched_kkt_solves {
+                // x1 is free for use as workspace until the first step
+                let work = &mut self.work;
+                work.x.fill(T::zero());
+                work.z.fill(T::zero());
+                work.x1.axpby(-T::one(), &data.q, T::zero());
+                is_success = self.kktsolver.solve_multiple(
+                    &[(&work.x, &data.b), (&work.x1, &work.z)],
+                    &mut [
+                        (Some(&mut variables.x), Some(&mut variables.s)),
+                        (None, Some(&mut variables.z)),
+                    ],
+                    settings.core(),
+                );
+                variables.s.negate();
+                return is_success;
+            }
+
+            self.work.x.fill(T::zero());
+            self.work.z.copy_from(&data.b);
+            self.kktsolver.setrhs(&self.work.x, &self.work.z);
             is_success = self.kktsolver.solve(
                 Some(&mut variables.x),
                 Some(&mut variables.s),
@@ -224,20 +305,18 @@ where
                 return is_success;
             }
 
-            // solve with [-q;0] as a RHS to get z initializer
-            // zero out any sparse cone variables at end
-            self.workx.axpby(-T::one(), &data.q, T::zero());
-            self.workz.fill(T::zero());
+            self.work.x.axpby(-T::one(), &data.q, T::zero());
+            self.work.z.fill(T::zero());
 
-            self.kktsolver.setrhs(&self.workx, &self.workz);
+            self.kktsolver.setrhs(&self.work.x, &self.work.z);
             is_success = self
                 .kktsolver
                 .solve(None, Some(&mut variables.z), settings.core());
         } else {
             //QP initialization
-            self.workx.scalarop_from(|q| -q, &data.q);
-            self.workz.copy_from(&data.b);
-            self.kktsolver.setrhs(&self.workx, &self.workz);
+            self.work.x.scalarop_from(|q| -q, &data.q);
+            self.work.z.copy_from(&data.b);
+            self.kktsolver.setrhs(&self.work.x, &self.work.z);
             is_success = self.kktsolver.solve(
                 Some(&mut variables.x),
                 Some(&mut variables.z),
@@ -247,6 +326,27 @@ where
         }
         is_success
     }
+
+    fn counters_mut(&mut self) -> &mut OpCounters {
+        self.kktsolver.counters_mut()
+    }
+
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<T> {
+        self.kktsolver.refinement_stats_mut()
+    }
+
+    fn begin_iteration(&mut self, iter: u32) {
+        // discard snapshots from earlier solves
+        if iter == 0 {
+            self.snapshot = None;
+        }
+        self.iteration = iter;
+        self.kktsolver.begin_iteration(iter);
+    }
+
+    fn is_step_accurate(&self) -> bool {
+        self.kktsolver.is_refined() && !self.initial_Hs
+    }
 }
 
 impl<T> DefaultKKTSystem<T>
@@ -255,23 +355,31 @@ where
 {
     fn solve_constant_rhs(
         &mut self,
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         settings: &DefaultSettings<T>,
     ) -> bool {
-        self.workx.axpby(-T::one(), &data.q, T::zero()); //workx .= -q
-        self.kktsolver.setrhs(&self.workx, &data.b);
-        let is_success =
-            self.kktsolver
-                .solve(Some(&mut self.x2), Some(&mut self.z2), settings.core());
+        profile_scope!(Solve);
+
+        self.work.x.axpby(-T::one(), &data.q, T::zero()); //work.x .= -q
+        self.kktsolver.setrhs(&self.work.x, &data.b);
+        let is_success = self.kktsolver.solve(
+            Some(&mut self.work.x2),
+            Some(&mut self.work.z2),
+            settings.core(),
+        );
 
         is_success
     }
 
-    pub(crate) fn update_P(&mut self, P: &CscMatrix<T>) {
+    pub(crate) fn update_P(&mut self, P: &CscMatrix<T, Cow<'_, [usize]>>) {
         self.kktsolver.update_P(P);
     }
 
-    pub(crate) fn update_A(&mut self, A: &CscMatrix<T>) {
+    pub(crate) fn update_A(&mut self, A: &CscMatrix<T, Cow<'_, [usize]>>) {
         self.kktsolver.update_A(A);
     }
 }
+
+fn _is_snapshot_iteration<T: FloatT>(iteration: u32, settings: &DefaultSettings<T>) -> bool {
+    iteration != 0 && iteration == settings.kkt_snapshot_iteration
+}
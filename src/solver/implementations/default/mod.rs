@@ -4,30 +4,47 @@
 #![allow(non_snake_case)]
 
 mod data_updating;
+mod dualization;
 mod equilibration;
+mod error;
 mod info;
 mod info_print;
 mod kktsystem;
 mod presolver;
 mod problemdata;
+mod reductions;
 mod residuals;
+mod rowmapping;
 mod settings;
 mod solution;
 mod solver;
 mod variables;
+mod workspace;
 
 // export flattened
 pub use data_updating::*;
+pub use dualization::*;
 pub use equilibration::*;
+pub use error::*;
 pub use info::*;
 pub use kktsystem::*;
 pub use presolver::*;
 pub use problemdata::*;
+pub use reductions::*;
 pub use residuals::*;
+pub use rowmapping::*;
 pub use settings::*;
 pub use solution::*;
 pub use solver::*;
 pub use variables::*;
+pub(crate) use workspace::*;
+
+#[cfg(feature = "parallel")]
+mod batch;
+#[cfg(feature = "parallel")]
+pub use batch::*;
 
 #[cfg(feature = "serde")]
 mod json;
+#[cfg(feature = "serde")]
+pub use json::*;
@@ -45,7 +45,7 @@ where
         _A: &CscMatrix<T>,
         b: &[T],
         cones: &[SupportedConeT<T>],
-        _settings: &DefaultSettings<T>,
+        settings: &DefaultSettings<T>,
     ) -> Self {
         let infbound = crate::solver::get_infinity();
 
@@ -53,7 +53,8 @@ where
         let init_cones = cones.to_vec();
         let mfull = b.len();
 
-        let (reduce_map, mreduced) = make_reduction_map(cones, b, infbound.as_T());
+        let (reduce_map, mreduced) =
+            make_reduction_map(cones, b, settings.infinite_bound_threshold);
 
         Self {
             _init_cones: init_cones,
@@ -71,6 +72,23 @@ where
         self.mfull - self.mreduced
     }
 
+    // fate of each row of the problem passed to the presolver
+    pub(crate) fn row_fates(&self) -> Vec<RowFate> {
+        let map = self.reduce_map.as_ref().unwrap();
+        let mut ctr = 0;
+        map.keep_logical
+            .iter()
+            .map(|&keep| {
+                if keep {
+                    ctr += 1;
+                    RowFate::Row(ctr - 1)
+                } else {
+                    RowFate::Removed(RowRemoval::InfiniteBound)
+                }
+            })
+            .collect()
+    }
+
     pub(crate) fn presolve(
         &self,
         A: &CscMatrix<T>,
@@ -105,11 +123,12 @@ where
         let mut keep_iter = map.keep_logical.iter();
 
         for cone in cones {
+            // the markers of every cone are consumed, so
+            // that those of the next cone stay aligned
             let numel_cone = cone.nvars();
-            let markers = keep_iter.by_ref().take(numel_cone);
+            let nkeep = keep_iter.by_ref().take(numel_cone).filter(|&b| *b).count();
 
             if matches!(cone, SupportedConeT::NonnegativeConeT(_)) {
-                let nkeep = markers.filter(|&b| *b).count();
                 if nkeep > 0 {
                     cones_new.push(SupportedConeT::NonnegativeConeT(nkeep));
                 }
@@ -144,10 +163,17 @@ where
     }
 }
 
+// True if an entry of b in a nonnegative cone row is to be treated
+// as an absent bound.   Makes a slight contraction of the threshold,
+// so that entries equal to it are firmly "greater than" here
+pub(crate) fn is_absent_bound<T: FloatT>(v: T, threshold: T) -> bool {
+    v > (T::one() - T::epsilon() * (10.).as_T()) * threshold
+}
+
 fn make_reduction_map<T>(
     cones: &[SupportedConeT<T>],
     b: &[T],
-    infbound: T,
+    threshold: T,
 ) -> (Option<PresolverRowReductionIndex>, usize)
 where
     T: FloatT,
@@ -156,12 +182,8 @@ where
     let mut keep_logical = vec![true; b.len()];
     let mut mreduced = b.len();
 
-    // only try to reduce nn cones.  Make a slight contraction
-    // so that we are firmly "less than" here
-    let infbound = (T::one() - T::epsilon() * (10.).as_T()) * infbound;
-
     // we loop through b and remove any entries that are both infinite
-    // and in a nonnegative cone
+    // and in a nonnegative cone.  Only nn cones are reduced
 
     let mut idx = 0; // index into the b vector
 
@@ -170,7 +192,7 @@ where
 
         if matches!(cone, SupportedConeT::NonnegativeConeT(_)) {
             for _ in 0..numel_cone {
-                if b[idx] > infbound {
+                if is_absent_bound(b[idx], threshold) {
                     keep_logical[idx] = false;
                     mreduced -= 1;
                 }
@@ -1,4 +1,7 @@
 #![allow(non_snake_case)]
+use alloc::borrow::Cow;
+use core::iter::zip;
+use core::ops::Deref;
 use itertools::izip;
 
 use super::*;
@@ -7,7 +10,7 @@ use crate::solver::core::{
     cones::{CompositeCone, Cone},
     traits::ProblemData,
 };
-use crate::solver::SupportedConeT;
+use crate::solver::{OptimizationSense, SupportedConeT};
 
 #[cfg(feature = "sdp")]
 use crate::solver::chordal::ChordalInfo;
@@ -17,12 +20,17 @@ use crate::solver::chordal::ChordalInfo;
 // ---------------
 
 /// Standard-form solver type implementing the [`ProblemData`](crate::solver::core::traits::ProblemData) trait
+///
+/// The sparsity patterns of `P` and `A` are borrowed for the lifetime
+/// `'a` from the user's matrices when the data is created by
+/// [`new_borrowed`](crate::solver::DefaultSolver::new_borrowed), and
+/// are owned otherwise.
 
-pub struct DefaultProblemData<T> {
+pub struct DefaultProblemData<'a, T> {
     // the main KKT residuals
-    pub P: CscMatrix<T>,
+    pub P: CscMatrix<T, Cow<'a, [usize]>>,
     pub q: Vec<T>,
-    pub A: CscMatrix<T>,
+    pub A: CscMatrix<T, Cow<'a, [usize]>>,
     pub b: Vec<T>,
     pub cones: Vec<SupportedConeT<T>>,
     pub n: usize,
@@ -35,13 +43,91 @@ pub struct DefaultProblemData<T> {
     normq: Option<T>,
     normb: Option<T>,
 
+    // true if q has been negated to turn a maximization
+    // into a minimization.  See `negate_objective`
+    pub(crate) maximization_flag: bool,
+
     pub(crate) presolver: Option<Presolver<T>>,
 
+    pub(crate) reductions: Option<PresolveResult<T>>,
+
+    pub(crate) dualization: Option<DualTransform<T>>,
+
+    pub(crate) row_mapping: RowMapping,
+
     #[cfg(feature = "sdp")]
     pub(crate) chordal_info: Option<ChordalInfo<T>>,
+
+    // structure of P, and its diagonal as a dense
+    // vector if P is diagonal.  Empty otherwise
+    P_structure: QuadraticStructure,
+    P_diag: Vec<T>,
+
+    // explicit transpose of A, if enabled
+    At: Option<ExplicitTranspose<T>>,
+
+    // the data was already well scaled at setup, so
+    // it is used as is with identity equilibration
+    equilibration_skipped: bool,
+
+    // P was supplied as a full symmetric matrix and
+    // has been converted to its upper triangle
+    pub(crate) P_converted_to_triu: bool,
+
+    // P or A had unsorted or repeated entries in a column and
+    // was sorted at setup, with the number of entries merged
+    pub(crate) input_merged_entries: Option<usize>,
+
+    // finite entries of q and b beyond the infinite bound
+    // threshold in magnitude, likely to cause poor scaling
+    pub(crate) huge_entries: usize,
+}
+
+// a copy of A' with the plan that copies the values of A into
+// it, so that values can be updated in lockstep with A
+struct ExplicitTranspose<T> {
+    At: CscMatrix<T>,
+    plan: TransposePlan,
+}
+
+/// Structure of the quadratic cost matrix `P`, as detected from its
+/// sparsity pattern during setup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuadraticStructure {
+    /// `P` has no structural nonzeros, i.e. the problem is an LP
+    Zero,
+    /// `P` has at most one entry per column, on the diagonal
+    Diagonal,
+    /// any other sparsity pattern
+    General,
+}
+
+impl QuadraticStructure {
+    fn detect<T: FloatT>(P: &CscMatrix<T, impl Deref<Target = [usize]>>) -> Self {
+        if P.nnz() == 0 {
+            return Self::Zero;
+        }
+        let is_diagonal = (0..P.n).all(|col| {
+            let rows = &P.rowval[P.colptr[col]..P.colptr[col + 1]];
+            rows.is_empty() || rows == [col]
+        });
+        if is_diagonal {
+            Self::Diagonal
+        } else {
+            Self::General
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::Diagonal => "diagonal",
+            Self::General => "general",
+        }
+    }
 }
 
-impl<T> DefaultProblemData<T>
+impl<'a, T> DefaultProblemData<'a, T>
 where
     T: FloatT,
 {
@@ -53,6 +139,39 @@ where
         cones: &[SupportedConeT<T>],
         settings: &DefaultSettings<T>,
     ) -> Self {
+        let (P, A) = (MatrixSource::Copied(P), MatrixSource::Copied(A));
+        Self::_new(P, q, A, b, cones, settings)
+    }
+
+    // As `new`, but borrowing the sparsity patterns of `P` and `A`
+    // when they are used without modification, so that only their
+    // values are copied.
+    pub(crate) fn new_borrowed(
+        P: &'a CscMatrix<T>,
+        q: &[T],
+        A: &'a CscMatrix<T>,
+        b: &[T],
+        cones: &[SupportedConeT<T>],
+        settings: &DefaultSettings<T>,
+    ) -> Self {
+        let (P, A) = (MatrixSource::Borrowed(P), MatrixSource::Borrowed(A));
+        Self::_new(P, q, A, b, cones, settings)
+    }
+
+    fn _new(
+        Psrc: MatrixSource<'_, 'a, T>,
+        q: &[T],
+        Asrc: MatrixSource<'_, 'a, T>,
+        b: &[T],
+        cones: &[SupportedConeT<T>],
+        settings: &DefaultSettings<T>,
+    ) -> Self {
+        let (P, A) = (Psrc.get(), Asrc.get());
+        let m_original = b.len();
+
+        // a full symmetric P is used by its upper triangle
+        let P_converted_to_triu = !P.is_triu();
+
         // some caution is required to ensure we take a minimal,
         // but nonzero, number of data copies during presolve steps
 
@@ -63,6 +182,38 @@ where
         let mut b_new: Option<Vec<T>> = None;
         let mut cones_new: Option<Vec<SupportedConeT<T>>> = None;
 
+        // presolve reductions : return nothing if disabled or no
+        // reduction.  Applied first, so that they are reversed last
+        // --------------------------------------
+        let reductions = try_reductions(P, q, A, b, cones, settings);
+
+        let (P, q, A, b, cones) = match reductions {
+            Some(ref r) => (
+                &r.P,
+                r.q.as_slice(),
+                &r.A,
+                r.b.as_slice(),
+                r.cones.as_slice(),
+            ),
+            None => (P, q, A, b, cones),
+        };
+
+        // dualization : return nothing if disabled or the
+        // problem is not suitable for dualization
+        // --------------------------------------
+        let dualization = try_dualization(P, q, A, b, cones, settings);
+
+        let (P, q, A, b, cones) = match dualization {
+            Some(ref d) => (
+                &d.P,
+                d.q.as_slice(),
+                &d.A,
+                d.b.as_slice(),
+                d.cones.as_slice(),
+            ),
+            None => (P, q, A, b, cones),
+        };
+
         if !P.is_triu() {
             P_new = Some(P.to_triu());
         }
@@ -76,6 +227,14 @@ where
             (A_new, b_new, cones_new) = (Some(_A_new), Some(_b_new), Some(_cones_new));
         }
 
+        let row_mapping = RowMapping::new(
+            m_original,
+            reductions.as_ref(),
+            dualization.as_ref(),
+            presolver.as_ref(),
+            b_new.as_ref().map_or(b.len(), |b| b.len()),
+        );
+
         // chordal decomposition : return nothing if disabled or no decomp
         // --------------------------------------
         #[cfg(feature = "sdp")]
@@ -98,13 +257,36 @@ where
             );
         }
 
+        // this ensures m is the *reduced* size m
+        let (m, n) = A_new.as_ref().unwrap_or(A).size();
+
+        let mut equilibration = DefaultEquilibrationData::<T>::new(n, m);
+
+        // decide now whether equilibration will scale the data
+        let equilibration_skipped = settings.equilibrate_enable
+            && is_well_scaled(
+                P_new.as_ref().unwrap_or(P),
+                q_new.as_deref().unwrap_or(q),
+                A_new.as_ref().unwrap_or(A),
+                &mut equilibration,
+                settings,
+            );
+
         // now make sure we have a clean copy of everything if we
         // haven't made one already.   Necessary since we will scale
         // the internal copy and don't want to step on the user
 
-        let P_new = P_new.unwrap_or_else(|| P.clone());
+        let P_new = match P_new {
+            Some(P_new) => P_new.into(),
+            None if core::ptr::eq(P, Psrc.get()) => Psrc.into_matrix(),
+            None => P.clone().into(),
+        };
+        let A_new = match A_new {
+            Some(A_new) => A_new.into(),
+            None if core::ptr::eq(A, Asrc.get()) => Asrc.into_matrix(),
+            None => A.clone().into(),
+        };
         let q_new = q_new.unwrap_or_else(|| q.to_vec());
-        let A_new = A_new.unwrap_or_else(|| A.clone());
         let mut b_new = b_new.unwrap_or_else(|| b.to_vec());
         let cones_new = cones_new.unwrap_or_else(|| cones.to_vec());
 
@@ -115,15 +297,12 @@ where
         let infbound = crate::solver::get_infinity().as_T();
         b_new.scalarop(|x| T::min(x, infbound));
 
-        // this ensures m is the *reduced* size m
-        let (m, n) = A_new.size();
-
-        let equilibration = DefaultEquilibrationData::<T>::new(n, m);
-
         let normq = Some(q_new.norm_inf());
         let normb = Some(b_new.norm_inf());
 
-        Self {
+        let P_structure = QuadraticStructure::detect(&P_new);
+
+        let mut data = Self {
             P: P_new,
             q: q_new,
             A: A_new,
@@ -134,9 +313,178 @@ where
             equilibration,
             normq,
             normb,
+            maximization_flag: false,
             presolver,
+            reductions,
+            dualization,
+            row_mapping,
             #[cfg(feature = "sdp")]
             chordal_info,
+            P_structure,
+            P_diag: Vec::new(),
+            At: None,
+            equilibration_skipped,
+            P_converted_to_triu,
+            input_merged_entries: None,
+            huge_entries: 0,
+        };
+        data.update_P_diag();
+        data
+    }
+
+    /// Negate the linear cost term `q`, converting a maximization
+    /// problem into a minimization problem or vice versa.   Reported
+    /// objective values are negated in the solution while the
+    /// problem is marked as a maximization.
+    pub fn negate_objective(&mut self) {
+        self.q.negate();
+        self.maximization_flag = !self.maximization_flag;
+    }
+
+    /// `true` if the problem has been converted from a maximization.
+    pub fn is_maximization(&self) -> bool {
+        self.maximization_flag
+    }
+
+    /// `true` if equilibration was skipped because the data was already
+    /// well scaled.   See the `equilibrate_skip_threshold` setting.
+    pub fn is_equilibration_skipped(&self) -> bool {
+        self.equilibration_skipped
+    }
+
+    /// Number of finite entries of `q` and `b` kept in the problem that
+    /// exceed the `infinite_bound_threshold` setting in magnitude.
+    pub fn count_huge_entries(&self) -> usize {
+        self.huge_entries
+    }
+
+    /// Structure of `P` detected during setup.   Products with `P` are
+    /// skipped entirely when it is zero, and computed from a dense
+    /// diagonal when it is diagonal.
+    pub fn P_structure(&self) -> QuadraticStructure {
+        self.P_structure
+    }
+
+    // refresh the dense diagonal of P after its values change
+    pub(crate) fn update_P_diag(&mut self) {
+        if self.P_structure != QuadraticStructure::Diagonal {
+            return;
+        }
+        self.P_diag.resize(self.n, T::zero());
+        self.P_diag.fill(T::zero());
+        for (&row, &v) in zip(self.P.rowval.iter(), &self.P.nzval) {
+            self.P_diag[row] = v;
+        }
+    }
+
+    // y = Px, with P treated as symmetric
+    pub(crate) fn mul_P(&self, y: &mut [T], x: &[T]) {
+        match self.P_structure {
+            QuadraticStructure::Zero => y.fill(T::zero()),
+            QuadraticStructure::Diagonal => {
+                for (yi, &di, &xi) in izip!(y, &self.P_diag, x) {
+                    *yi = di * xi;
+                }
+            }
+            QuadraticStructure::General => {
+                self.P.sym().symv(y, x, T::one(), T::zero());
+            }
+        }
+    }
+
+    // yᵀPx, with P treated as symmetric
+    pub(crate) fn quad_form_P(&self, y: &[T], x: &[T]) -> T {
+        match self.P_structure {
+            QuadraticStructure::Zero => T::zero(),
+            QuadraticStructure::Diagonal => {
+                izip!(&self.P_diag, x, y).fold(T::zero(), |out, (&di, &xi, &yi)| out + di * xi * yi)
+            }
+            QuadraticStructure::General => self.P.quad_form(y, x),
+        }
+    }
+
+    /// The explicit transpose of `A` used for products with `A'`, if one
+    /// is stored.   See the `explicit_transpose_*` settings.
+    pub fn A_transpose(&self) -> Option<&CscMatrix<T>> {
+        self.At.as_ref().map(|t| &t.At)
+    }
+
+    // store an explicit transpose of A if it has enough nonzeros, or if
+    // products with A are computed in parallel, and it fits within the
+    // memory budget.   The transpose is only used when each column of A
+    // has sorted and unique row indices, so that products with it sum
+    // terms in the same order as products with A, and give identical results
+    pub(crate) fn setup_A_transpose(&mut self, settings: &DefaultSettings<T>) {
+        let nnz = self.A.nnz();
+        let bytes = nnz * (core::mem::size_of::<T>() + 2 * core::mem::size_of::<usize>())
+            + (self.m + 1) * core::mem::size_of::<usize>();
+
+        #[cfg(feature = "parallel")]
+        let min_nnz = match _use_parallel_gemv(&self.A) {
+            true => 0,
+            false => settings.explicit_transpose_min_nnz,
+        };
+        #[cfg(not(feature = "parallel"))]
+        let min_nnz = settings.explicit_transpose_min_nnz;
+
+        self.At = None;
+        if nnz < min_nnz
+            || bytes > settings.explicit_transpose_max_bytes
+            || self.A.check_format().is_err()
+        {
+            return;
+        }
+        let (plan, At) = self.A.transpose_symbolic();
+        self.At = Some(ExplicitTranspose { At, plan });
+    }
+
+    // bytes held by the matrices and vectors of the problem data
+    // and its equilibration, including any explicit transpose of A
+    pub(crate) fn memory_bytes(&self) -> usize {
+        let At = self.At.as_ref().map_or(0, |t| {
+            // the transpose plan maps each entry of A
+            t.At.memory_bytes() + t.At.nnz() * core::mem::size_of::<usize>()
+        });
+        // the indices of a borrowed pattern are not held by the solver
+        let matrix = |M: &CscMatrix<T, Cow<'a, [usize]>>| match M.colptr {
+            Cow::Borrowed(_) => M.nnz() * core::mem::size_of::<T>(),
+            Cow::Owned(_) => M.memory_bytes(),
+        };
+        let vectors = self.q.len() + self.b.len() + self.P_diag.len() + 2 * (self.n + self.m);
+        matrix(&self.P) + matrix(&self.A) + At + vectors * core::mem::size_of::<T>()
+    }
+
+    // copy the values of A into its explicit transpose
+    pub(crate) fn update_A_transpose(&mut self) {
+        if let Some(t) = &mut self.At {
+            // A is only updated in place, so the plan is always valid
+            self.A.transpose_numeric(&t.plan, &mut t.At).unwrap();
+        }
+    }
+
+    // y = a*Ax + b*y.   In parallel, each output is computed from a
+    // column of the explicit transpose, which needs no workspace.
+    // Without a transpose the product is computed serially
+    pub(crate) fn gemv_A(&self, y: &mut [T], x: &[T], a: T, b: T) {
+        #[cfg(feature = "parallel")]
+        if let Some(t) = &self.At {
+            if _use_parallel_gemv(&self.A) {
+                return t.At.t().gemv(y, x, a, b);
+            }
+        }
+        self.A.gemv(y, x, a, b);
+    }
+
+    // y = a*A'x + b*y, using the explicit transpose if there is
+    // one and the product would not be computed in parallel
+    pub(crate) fn gemv_At(&self, y: &mut [T], x: &[T], a: T, b: T) {
+        #[cfg(feature = "parallel")]
+        if _use_parallel_gemv(&self.A) {
+            return self.A.t().gemv(y, x, a, b);
+        }
+        match &self.At {
+            Some(t) => t.At.gemv(y, x, a, b),
+            None => self.A.t().gemv(y, x, a, b),
         }
     }
 
@@ -171,7 +519,41 @@ where
     }
 }
 
-impl<T> ProblemData<T> for DefaultProblemData<T>
+// user supplied P or A, to be copied into the problem data or,
+// if borrowed for the lifetime `'a` of the data, to have its
+// sparsity pattern borrowed by it
+enum MatrixSource<'b, 'a, T> {
+    Copied(&'b CscMatrix<T>),
+    Borrowed(&'a CscMatrix<T>),
+}
+
+impl<'b, 'a, T> MatrixSource<'b, 'a, T>
+where
+    T: FloatT,
+{
+    fn get(&self) -> &CscMatrix<T> {
+        match self {
+            MatrixSource::Copied(M) => M,
+            MatrixSource::Borrowed(M) => M,
+        }
+    }
+
+    // the values are always copied, since they are scaled
+    fn into_matrix(self) -> CscMatrix<T, Cow<'a, [usize]>> {
+        match self {
+            MatrixSource::Copied(M) => M.clone().into(),
+            MatrixSource::Borrowed(M) => CscMatrix {
+                m: M.m,
+                n: M.n,
+                colptr: Cow::Borrowed(&M.colptr),
+                rowval: Cow::Borrowed(&M.rowval),
+                nzval: M.nzval.clone(),
+            },
+        }
+    }
+}
+
+impl<'a, T> ProblemData<T> for DefaultProblemData<'a, T>
 where
     T: FloatT,
 {
@@ -183,10 +565,11 @@ where
         let data = self;
         let equil = &mut data.equilibration;
 
-        // if equilibration is disabled, just return.  Note that
-        // the default equilibration structure initializes with
-        // identity scaling already.
-        if !settings.equilibrate_enable {
+        // if equilibration is disabled or the data is already
+        // well scaled, just return.  Note that the default
+        // equilibration structure initializes with identity
+        // scaling already.
+        if !settings.equilibrate_enable || data.equilibration_skipped {
             return;
         }
 
@@ -204,9 +587,19 @@ where
         let scale_min = settings.equilibrate_min_scaling;
         let scale_max = settings.equilibrate_max_scaling;
 
+        // inf norms of the columns and rows of A.  These are
+        // recomputed in the same pass that scales A, so that each
+        // iteration makes a single pass over A
+        let mut A_col_norms = vec![T::zero(); A.n];
+        let mut A_row_norms = vec![T::zero(); A.m];
+        if settings.equilibrate_max_iter > 0 {
+            A.col_norms(&mut A_col_norms);
+            A.row_norms(&mut A_row_norms);
+        }
+
         // perform scaling operations for a fixed number of steps
         for _ in 0..settings.equilibrate_max_iter {
-            kkt_col_norms(P, A, dwork, ework);
+            kkt_col_norms(P, &A_col_norms, &A_row_norms, dwork, ework);
 
             //zero rows or columns should not get scaled
             dwork.scalarop(|x| if x == T::zero() { T::one() } else { x });
@@ -225,7 +618,10 @@ where
 
             // Scale the problem data and update the
             // equilibration matrices
-            scale_data(P, A, q, b, Some(dwork), ework);
+            P.lrscale(dwork, dwork);
+            A.lrscale_with_norms(ework, dwork, &mut A_col_norms, &mut A_row_norms);
+            q.hadamard(dwork);
+            b.hadamard(ework);
             d.hadamard(dwork);
             e.hadamard(ework);
 
@@ -255,13 +651,17 @@ where
         //bounds on the scalings here
         if cones.rectify_equilibration(ework, e) {
             // only rescale again if some cones were rectified
-            scale_data(P, A, q, b, None, ework);
+            A.lscale(ework); // A[:,:] = Es*A
+            b.hadamard(ework);
             e.hadamard(ework);
         }
 
         // update the inverse scaling data
         equil.dinv.scalarop_from(T::recip, d);
         equil.einv.scalarop_from(T::recip, e);
+        equil.cd.copy_from(d).scale(equil.c);
+
+        data.update_P_diag();
     }
 }
 
@@ -269,36 +669,121 @@ where
 // utilities
 // ---------------
 
+// KKT column norms, given the column and row norms of A
 fn kkt_col_norms<T: FloatT>(
-    P: &CscMatrix<T>,
-    A: &CscMatrix<T>,
+    P: &CscMatrix<T, impl Deref<Target = [usize]>>,
+    A_col_norms: &[T],
+    A_row_norms: &[T],
     norm_LHS: &mut [T],
     norm_RHS: &mut [T],
 ) {
     P.col_norms_sym(norm_LHS); // P can be triu
-    A.col_norms_no_reset(norm_LHS); // incrementally from P norms
-    A.row_norms(norm_RHS); // same as column norms of A'
+    for (norm, &Anorm) in zip(norm_LHS.iter_mut(), A_col_norms) {
+        *norm = T::max(*norm, Anorm); // incrementally from P norms
+    }
+    norm_RHS.copy_from(A_row_norms); // same as column norms of A'
 }
 
-fn scale_data<T: FloatT>(
-    P: &mut CscMatrix<T>,
-    A: &mut CscMatrix<T>,
-    q: &mut [T],
-    b: &mut [T],
-    d: Option<&[T]>,
-    e: &[T],
-) {
-    match d {
-        Some(d) => {
-            P.lrscale(d, d); // P[:,:] = Ds*P*Ds
-            A.lrscale(e, d);
-            q.hadamard(d);
+// true if every scaling computed by `equilibrate` for the data, and
+// the cost scaling, lies within [1/τ, τ] for the skip threshold τ.
+// The Ruiz iterations are repeated here on the norms of the scaled
+// data without scaling the data itself, stopping as soon as any
+// scaling leaves the interval.   P must be triu.
+//
+// The identity scalings in `equil` are used as workspace, and are
+// restored on return.
+fn is_well_scaled<T: FloatT>(
+    P: &CscMatrix<T>,
+    q: &[T],
+    A: &CscMatrix<T>,
+    equil: &mut DefaultEquilibrationData<T>,
+    settings: &DefaultSettings<T>,
+) -> bool {
+    let τ = settings.equilibrate_skip_threshold;
+    if τ < T::one() {
+        return false;
+    }
+    let is_well_scaled = _ruiz_in_range(P, q, A, equil, settings);
+
+    for v in [&mut equil.d, &mut equil.dinv, &mut equil.e, &mut equil.einv] {
+        v.fill(T::one());
+    }
+    equil.c = T::one();
+    is_well_scaled
+}
+
+fn _ruiz_in_range<T: FloatT>(
+    P: &CscMatrix<T>,
+    q: &[T],
+    A: &CscMatrix<T>,
+    equil: &mut DefaultEquilibrationData<T>,
+    settings: &DefaultSettings<T>,
+) -> bool {
+    let τ = settings.equilibrate_skip_threshold;
+    let in_range = |s: &T| *s >= τ.recip() && *s <= τ;
+
+    let n = A.n;
+    let (d, e, c) = (&mut equil.d, &mut equil.e, &mut equil.c);
+    let (dwork, ework) = (&mut equil.dinv, &mut equil.einv);
+    let scale_min = settings.equilibrate_min_scaling;
+    let scale_max = settings.equilibrate_max_scaling;
+
+    for _ in 0..settings.equilibrate_max_iter {
+        // KKT column norms of the scaled data
+        dwork.fill(T::zero());
+        ework.fill(T::zero());
+        for col in 0..n {
+            for k in P.colptr[col]..P.colptr[col + 1] {
+                let row = P.rowval[k];
+                let v = *c * d[row] * d[col] * P.nzval[k].abs();
+                dwork[col] = T::max(dwork[col], v);
+                dwork[row] = T::max(dwork[row], v);
+            }
+            for k in A.colptr[col]..A.colptr[col + 1] {
+                let row = A.rowval[k];
+                let v = e[row] * d[col] * A.nzval[k].abs();
+                dwork[col] = T::max(dwork[col], v);
+                ework[row] = T::max(ework[row], v);
+            }
         }
-        None => {
-            A.lscale(e); // A[:,:] = Es*A
+
+        // scalings as computed in `equilibrate`
+        for (w, &s) in zip(
+            dwork.iter_mut().chain(ework.iter_mut()),
+            d.iter().chain(e.iter()),
+        ) {
+            let x = if *w == T::zero() { T::one() } else { *w };
+            *w = T::clip(&x.sqrt().recip(), scale_min / s, scale_max / s);
+        }
+        d.hadamard(dwork);
+        e.hadamard(ework);
+        if !d.iter().chain(e.iter()).all(in_range) {
+            return false;
+        }
+
+        // cost scaling from the mean column norm of the scaled
+        // P, using dwork to hold its column norms
+        for (col, norm) in dwork.iter_mut().enumerate() {
+            let rng = P.colptr[col]..P.colptr[col + 1];
+            *norm = zip(&P.rowval[rng.clone()], &P.nzval[rng])
+                .fold(T::zero(), |acc, (&row, &v)| {
+                    T::max(acc, *c * d[row] * d[col] * v.abs())
+                });
+        }
+        let mean_col_norm_P = dwork.mean();
+        let inf_norm_q =
+            zip(q, d.iter()).fold(T::zero(), |acc, (&qi, &di)| T::max(acc, *c * di * qi.abs()));
+
+        if mean_col_norm_P != T::zero() && inf_norm_q != T::zero() {
+            let scale_cost = T::max(inf_norm_q, mean_col_norm_P);
+            let ctmp = T::clip(&scale_cost.recip(), scale_min / *c, scale_max / *c);
+            *c *= ctmp;
+            if !in_range(c) {
+                return false;
+            }
         }
     }
-    b.hadamard(e);
+    true
 }
 
 #[cfg(feature = "sdp")]
@@ -333,6 +818,54 @@ where
     Some(chordal_info)
 }
 
+fn try_reductions<T>(
+    P: &CscMatrix<T>,
+    q: &[T],
+    A: &CscMatrix<T>,
+    b: &[T],
+    cones: &[SupportedConeT<T>],
+    settings: &DefaultSettings<T>,
+) -> Option<PresolveResult<T>>
+where
+    T: FloatT,
+{
+    if !settings.presolve_reductions_enable {
+        return None;
+    }
+
+    let reductions = PresolveResult::new(P, q, A, b, cones);
+
+    if !reductions.is_reduced() {
+        return None;
+    }
+
+    Some(reductions)
+}
+
+fn try_dualization<T>(
+    P: &CscMatrix<T>,
+    q: &[T],
+    A: &CscMatrix<T>,
+    b: &[T],
+    cones: &[SupportedConeT<T>],
+    settings: &DefaultSettings<T>,
+) -> Option<DualTransform<T>>
+where
+    T: FloatT,
+{
+    // maximization problems are solved by negating q after
+    // setup, so are not dualized
+    if !settings.auto_dualize || settings.sense == OptimizationSense::Maximize {
+        return None;
+    }
+
+    if A.nrows() <= 10 * A.ncols() {
+        return None;
+    }
+
+    DualTransform::new(P, q, A, b, cones).ok()
+}
+
 fn try_presolver<T>(
     A: &CscMatrix<T>,
     b: &[T],
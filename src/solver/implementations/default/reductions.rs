@@ -0,0 +1,562 @@
+#![allow(non_snake_case)]
+use super::*;
+use crate::algebra::*;
+use crate::solver::SupportedConeT;
+use core::iter::zip;
+use itertools::izip;
+
+// ---------------
+// Presolve reductions for the standard problem format.
+//
+// Reductions are applied only to rows in zero and nonnegative cones,
+// since rows of other cones can't be removed independently.   Variables
+// can be fixed regardless of the cones in which they appear, with their
+// values substituted into q and b.
+// ---------------
+
+// relative tolerance for equal bounds and consistency of empty rows
+const REDUCTION_TOL: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowType {
+    Equality,
+    Inequality,
+    Other,
+}
+
+/// Reductions applied to a problem before solving, and the data required
+/// to recover a solution to the original problem.
+///
+/// Presolve applies, until no further reduction is possible:
+/// - fixed variable detection, i.e. equality constraints with a single
+///   nonzero or a pair of inequality constraints with equal bounds
+/// - removal of empty constraints and of variables that appear only in
+///   the objective with no coupling to other variables
+/// - singleton constraint processing, i.e. inequality constraints with a
+///   single nonzero are treated as variable bounds
+/// - redundant bound elimination, keeping only the tightest upper and
+///   lower bound for each variable
+///
+/// Use [`solver`](PresolveResult::solver) to solve the reduced problem and
+/// [`postsolve`](PresolveResult::postsolve) to recover a solution to the
+/// original problem.   Reductions are applied automatically by the solver
+/// when `presolve_reductions_enable` is set in the solver settings.
+#[derive(Debug, Clone)]
+pub struct PresolveResult<T> {
+    /// variables removed from the problem, in the order they were eliminated
+    pub removed_variables: Vec<usize>,
+    /// values assigned to each of the removed variables
+    pub fixed_values: Vec<T>,
+    /// constraints removed from the problem, in increasing order
+    pub removed_constraints: Vec<usize>,
+    /// reason for the removal of each of the removed constraints
+    pub removal_reasons: Vec<RowRemoval>,
+
+    /// reduced quadratic cost, upper triangular
+    pub P: CscMatrix<T>,
+    /// reduced linear cost
+    pub q: Vec<T>,
+    /// reduced constraint matrix
+    pub A: CscMatrix<T>,
+    /// reduced constraint right hand side
+    pub b: Vec<T>,
+    /// reduced cones
+    pub cones: Vec<SupportedConeT<T>>,
+
+    // original problem data, with P in full symmetric form
+    Pfull: CsrMatrix<T>,
+    qfull: Vec<T>,
+    Afull: CscMatrix<T>,
+    Atfull: CscMatrix<T>,
+    bfull: Vec<T>,
+
+    // index of each variable / row in the reduced problem
+    var_map: Vec<Option<usize>>,
+    row_map: Vec<Option<usize>>,
+
+    // rows that fixed each removed variable.  Their duals are
+    // recovered during postsolve from the dual residual
+    fixing_rows: Vec<Vec<usize>>,
+}
+
+impl<T> PresolveResult<T>
+where
+    T: FloatT,
+{
+    /// Apply presolve reductions to the problem with data `(P,q,A,b)`.
+    pub fn new(
+        P: &CscMatrix<T>,
+        q: &[T],
+        A: &CscMatrix<T>,
+        b: &[T],
+        cones: &[SupportedConeT<T>],
+    ) -> Self {
+        let (m, n) = A.size();
+        let P = if P.is_triu() { P.clone() } else { P.to_triu() };
+        let Pfull = P.to_symmetric_csr();
+        let At: CscMatrix<T> = A.t().into();
+
+        let mut rowtype = Vec::with_capacity(m);
+        for cone in cones {
+            let t = match cone {
+                SupportedConeT::ZeroConeT(_) => RowType::Equality,
+                SupportedConeT::NonnegativeConeT(_) => RowType::Inequality,
+                _ => RowType::Other,
+            };
+            rowtype.extend(core::iter::repeat(t).take(cone.nvars()));
+        }
+
+        let mut state = ReductionState {
+            Pfull: &Pfull,
+            A,
+            At: &At,
+            rowtype,
+            keep_vars: vec![true; n],
+            keep_rows: vec![true; m],
+            row_removals: vec![None; m],
+            q: q.to_vec(),
+            b: b.to_vec(),
+            removed_variables: vec![],
+            fixed_values: vec![],
+            fixing_rows: vec![],
+        };
+
+        while state.reduce_rows() | state.reduce_columns() {
+            // repeat until no further reduction
+        }
+
+        let ReductionState {
+            keep_vars,
+            keep_rows,
+            row_removals,
+            q: q_cur,
+            b: b_cur,
+            removed_variables,
+            fixed_values,
+            fixing_rows,
+            ..
+        } = state;
+
+        // maps from the original to the reduced problem
+        let var_map = _make_index_map(&keep_vars);
+        let row_map = _make_index_map(&keep_rows);
+
+        let removed_constraints: Vec<usize> = (0..m).filter(|&i| !keep_rows[i]).collect();
+        let removal_reasons = removed_constraints
+            .iter()
+            .map(|&i| row_removals[i].unwrap())
+            .collect();
+
+        Self {
+            removed_variables,
+            fixed_values,
+            removed_constraints,
+            removal_reasons,
+            P: _select_entries(&P, &var_map, &var_map),
+            q: q_cur.select(&keep_vars),
+            A: _select_entries(A, &row_map, &var_map),
+            b: b_cur.select(&keep_rows),
+            cones: _reduce_cones(cones, &keep_rows),
+            Pfull,
+            qfull: q.to_vec(),
+            Afull: A.clone(),
+            Atfull: At,
+            bfull: b.to_vec(),
+            var_map,
+            row_map,
+            fixing_rows,
+        }
+    }
+
+    // fate of each original row in the reduced problem
+    pub(crate) fn row_fates(&self) -> Vec<RowFate> {
+        let mut reasons = zip(&self.removed_constraints, &self.removal_reasons);
+        self.row_map
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| match k {
+                Some(k) => RowFate::Row(k),
+                None => {
+                    let (_, &reason) = reasons.find(|(&r, _)| r == i).unwrap();
+                    RowFate::Removed(reason)
+                }
+            })
+            .collect()
+    }
+
+    /// True if any variables or constraints were removed
+    pub fn is_reduced(&self) -> bool {
+        !self.removed_variables.is_empty() || !self.removed_constraints.is_empty()
+    }
+
+    /// Create a solver for the reduced problem
+    pub fn solver(&self, settings: DefaultSettings<T>) -> Result<DefaultSolver<T>, ClarabelError> {
+        DefaultSolver::new(&self.P, &self.q, &self.A, &self.b, &self.cones, settings)
+    }
+
+    /// Recover a solution to the original problem from a solution
+    /// to the reduced problem.
+    ///
+    /// Removed variables take their fixed values, and the slacks of
+    /// removed constraints are computed from the full solution.   The
+    /// duals of constraints that fixed a variable are recovered from the
+    /// dual residual, and are otherwise zero.   Infeasibility certificates
+    /// are extended with zeros.
+    pub fn postsolve(&self, reduced: &DefaultSolution<T>) -> DefaultSolution<T> {
+        let mut full = DefaultSolution::new(self.var_map.len(), self.row_map.len());
+
+        full.status = reduced.status;
+        full.obj_val = reduced.obj_val;
+        full.obj_val_dual = reduced.obj_val_dual;
+        full.solve_time = reduced.solve_time;
+        full.iterations = reduced.iterations;
+        full.r_prim = reduced.r_prim;
+        full.r_dual = reduced.r_dual;
+        full.recovery_attempt = reduced.recovery_attempt;
+
+        self.postsolve_into(&mut full, &reduced.x, &reduced.z, &reduced.s);
+        full
+    }
+
+    // expand a reduced solution into the full one, updating
+    // the reported objective values to include any constant
+    // terms from the fixed variables
+    pub(crate) fn postsolve_into(
+        &self,
+        full: &mut DefaultSolution<T>,
+        x_red: &[T],
+        z_red: &[T],
+        s_red: &[T],
+    ) {
+        _expand(&mut full.z, z_red, &self.row_map);
+        _expand(&mut full.s, s_red, &self.row_map);
+        self.postsolve_expanded(full, x_red);
+    }
+
+    // restrict the original variables to those of the reduced problem
+    pub(crate) fn reduce_variables(&self, x_red: &mut [T], x: &[T]) {
+        for (&xj, &k) in x.iter().zip(&self.var_map) {
+            if let Some(k) = k {
+                x_red[k] = xj;
+            }
+        }
+    }
+
+    // as postsolve_into, for a solution whose slacks and duals have
+    // already been expanded, with zeros in the removed rows
+    pub(crate) fn postsolve_expanded(&self, full: &mut DefaultSolution<T>, x_red: &[T]) {
+        let (x, z, s) = (&mut full.x, &mut full.z, &mut full.s);
+
+        // expand from the reduced variables, with fixed values
+        // or zeros for certificates
+        _expand(x, x_red, &self.var_map);
+
+        if full.status.is_infeasible() {
+            return;
+        }
+
+        for (&j, &v) in self.removed_variables.iter().zip(&self.fixed_values) {
+            x[j] = v;
+        }
+
+        // slacks of the removed rows
+        for &i in &self.removed_constraints {
+            let rng = self.Atfull.colptr[i]..self.Atfull.colptr[i + 1];
+            let ax = x.sparse_dot(&self.Atfull.rowval[rng.clone()], &self.Atfull.nzval[rng]);
+            s[i] = self.bfull[i] - ax;
+        }
+
+        // dual residual r = Px + q + A'z, then recover the duals of the fixing
+        // rows in the reverse of the order in which the variables were fixed.
+        let mut r = self.qfull.clone();
+        self.Pfull.gemv_sym(&mut r, x, T::one(), T::one());
+        self.Afull.t().gemv(&mut r, z, T::one(), T::one());
+
+        for (&j, rows) in self.removed_variables.iter().zip(&self.fixing_rows).rev() {
+            // a single fixing row is an equality constraint.  Otherwise
+            // choose the bound whose dual has the correct sign
+            let choice = rows.iter().find_map(|&i| {
+                let aij = self.Afull.get_entry((i, j)).unwrap_or(T::zero());
+                let zi = -r[j] / aij;
+                (aij != T::zero() && (rows.len() == 1 || zi >= T::zero())).then_some((i, zi))
+            });
+
+            if let Some((i, zi)) = choice {
+                z[i] = zi;
+                let rng = self.Atfull.colptr[i]..self.Atfull.colptr[i + 1];
+                for (&col, &aij) in zip(&self.Atfull.rowval[rng.clone()], &self.Atfull.nzval[rng]) {
+                    r[col] += aij * zi;
+                }
+            }
+        }
+
+        // objective including the fixed variables
+        let mut Px = vec![T::zero(); x.len()];
+        self.Pfull.gemv_sym(&mut Px, x, T::one(), T::zero());
+        let half: T = (0.5).as_T();
+        let obj = half * x.dot(&Px) + self.qfull.dot(x);
+        let shift = obj - full.obj_val;
+        full.obj_val = obj;
+        full.obj_val_dual += shift;
+    }
+}
+
+// working data for the reductions
+struct ReductionState<'a, T> {
+    Pfull: &'a CsrMatrix<T>,
+    A: &'a CscMatrix<T>,
+    At: &'a CscMatrix<T>,
+    rowtype: Vec<RowType>,
+    keep_vars: Vec<bool>,
+    keep_rows: Vec<bool>,
+    row_removals: Vec<Option<RowRemoval>>,
+    q: Vec<T>,
+    b: Vec<T>,
+    removed_variables: Vec<usize>,
+    fixed_values: Vec<T>,
+    fixing_rows: Vec<Vec<usize>>,
+}
+
+impl<T> ReductionState<'_, T>
+where
+    T: FloatT,
+{
+    // remove empty rows, fix variables from singleton equalities
+    // and from equal bounds, and remove redundant bounds.  Returns
+    // true if any reduction was made.
+    fn reduce_rows(&mut self) -> bool {
+        let At = self.At;
+        let tol: T = T::max(REDUCTION_TOL.as_T(), T::epsilon() * (4e3).as_T());
+        let mut changed = false;
+
+        // singleton inequalities as (col, row, bound, is_upper)
+        let mut bounds = Vec::new();
+
+        for i in 0..self.rowtype.len() {
+            let rtype = self.rowtype[i];
+            if !self.keep_rows[i] || rtype == RowType::Other {
+                continue;
+            }
+
+            let rng = At.colptr[i]..At.colptr[i + 1];
+            let mut active = zip(&At.rowval[rng.clone()], &At.nzval[rng])
+                .filter(|(&j, &a)| self.keep_vars[j] && a != T::zero());
+
+            let bi = self.b[i];
+            match (active.next(), active.next()) {
+                (None, _) => {
+                    // empty rows are removed if consistent, otherwise
+                    // they are left for the solver to detect infeasibility
+                    let consistent = match rtype {
+                        RowType::Equality => T::abs(bi) <= tol,
+                        _ => bi >= -tol,
+                    };
+                    if consistent {
+                        self.remove_row(i, RowRemoval::EmptyRow);
+                        changed = true;
+                    }
+                }
+                (Some((&j, &a)), None) => {
+                    if rtype == RowType::Equality {
+                        self.remove_row(i, RowRemoval::FixedVariable(j));
+                        self.fix_variable(j, bi / a, vec![i]);
+                        changed = true;
+                    } else {
+                        bounds.push((j, i, bi / a, a > T::zero()));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // group bounds by variable
+        bounds.sort_by_key(|&(j, i, _, _)| (j, i));
+
+        let mut start = 0;
+        while start < bounds.len() {
+            let j = bounds[start].0;
+            let len = bounds[start..].iter().take_while(|x| x.0 == j).count();
+            let group = &bounds[start..start + len];
+            start += len;
+
+            if !self.keep_vars[j] {
+                // fixed by an equality in this pass
+                continue;
+            }
+
+            let upper = group
+                .iter()
+                .filter(|x| x.3)
+                .min_by(|x, y| x.2.partial_cmp(&y.2).unwrap());
+            let lower = group
+                .iter()
+                .filter(|x| !x.3)
+                .max_by(|x, y| x.2.partial_cmp(&y.2).unwrap());
+
+            // keep only the tightest bounds
+            for &(_, i, _, _) in group {
+                if Some(i) != upper.map(|x| x.1) && Some(i) != lower.map(|x| x.1) {
+                    self.remove_row(i, RowRemoval::RedundantBound(j));
+                    changed = true;
+                }
+            }
+
+            if let (Some(&(_, iu, u, _)), Some(&(_, il, l, _))) = (upper, lower) {
+                if T::abs(u - l) <= tol * T::max(T::one(), T::abs(u)) {
+                    self.remove_row(iu, RowRemoval::FixedVariable(j));
+                    self.remove_row(il, RowRemoval::FixedVariable(j));
+                    self.fix_variable(j, u, vec![iu, il]);
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    // fix variables that appear in no remaining constraint and have
+    // no coupling to other variables in the objective.  Returns true
+    // if any reduction was made.
+    fn reduce_columns(&mut self) -> bool {
+        let (Pfull, A) = (self.Pfull, self.A);
+        let mut changed = false;
+
+        for j in 0..self.keep_vars.len() {
+            if !self.keep_vars[j] {
+                continue;
+            }
+
+            let rng = A.colptr[j]..A.colptr[j + 1];
+            let in_rows = zip(&A.rowval[rng.clone()], &A.nzval[rng])
+                .any(|(&i, &a)| self.keep_rows[i] && a != T::zero());
+            if in_rows {
+                continue;
+            }
+
+            let rng = Pfull.rowptr[j]..Pfull.rowptr[j + 1];
+            let mut Pjj = T::zero();
+            let mut coupled = false;
+            for (&c, &v) in zip(&Pfull.colval[rng.clone()], &Pfull.nzval[rng]) {
+                if c == j {
+                    Pjj = v;
+                } else if self.keep_vars[c] && v != T::zero() {
+                    coupled = true;
+                }
+            }
+            if coupled {
+                continue;
+            }
+
+            // minimizer of the separable objective term.  Variables
+            // with only a nonzero linear term are unbounded, and are left
+            // for the solver to detect dual infeasibility
+            let v = if Pjj != T::zero() {
+                -self.q[j] / Pjj
+            } else if self.q[j] == T::zero() {
+                T::zero()
+            } else {
+                continue;
+            };
+
+            self.fix_variable(j, v, vec![]);
+            changed = true;
+        }
+
+        changed
+    }
+
+    fn remove_row(&mut self, i: usize, reason: RowRemoval) {
+        self.keep_rows[i] = false;
+        self.row_removals[i] = Some(reason);
+    }
+
+    fn fix_variable(&mut self, j: usize, v: T, rows: Vec<usize>) {
+        self.keep_vars[j] = false;
+        self.removed_variables.push(j);
+        self.fixed_values.push(v);
+        self.fixing_rows.push(rows);
+
+        // b -= A[:,j]*v
+        let A = self.A;
+        let rng = A.colptr[j]..A.colptr[j + 1];
+        for (&i, &a) in zip(&A.rowval[rng.clone()], &A.nzval[rng]) {
+            self.b[i] -= a * v;
+        }
+
+        // q += P[:,j]*v
+        let P = self.Pfull;
+        let rng = P.rowptr[j]..P.rowptr[j + 1];
+        for (&c, &Pcj) in zip(&P.colval[rng.clone()], &P.nzval[rng]) {
+            self.q[c] += Pcj * v;
+        }
+    }
+}
+
+fn _make_index_map(keep: &[bool]) -> Vec<Option<usize>> {
+    let mut next = 0;
+    keep.iter()
+        .map(|&k| {
+            k.then(|| {
+                next += 1;
+                next - 1
+            })
+        })
+        .collect()
+}
+
+fn _select_entries<T: FloatT>(
+    M: &CscMatrix<T>,
+    row_map: &[Option<usize>],
+    col_map: &[Option<usize>],
+) -> CscMatrix<T> {
+    let m = row_map.iter().flatten().count();
+    let n = col_map.iter().flatten().count();
+    let (I, J, V) = M.findnz();
+
+    let (mut Inew, mut Jnew, mut Vnew) = (vec![], vec![], vec![]);
+    for (i, j, v) in izip!(I, J, V) {
+        if let (Some(inew), Some(jnew)) = (row_map[i], col_map[j]) {
+            Inew.push(inew);
+            Jnew.push(jnew);
+            Vnew.push(v);
+        }
+    }
+    CscMatrix::new_from_triplets(m, n, Inew, Jnew, Vnew)
+}
+
+fn _reduce_cones<T: FloatT>(
+    cones: &[SupportedConeT<T>],
+    keep_rows: &[bool],
+) -> Vec<SupportedConeT<T>> {
+    let mut cones_new = Vec::with_capacity(cones.len());
+    let mut keep_iter = keep_rows.iter();
+
+    for cone in cones {
+        let nkeep = keep_iter
+            .by_ref()
+            .take(cone.nvars())
+            .filter(|&&k| k)
+            .count();
+
+        match cone {
+            SupportedConeT::ZeroConeT(_) if nkeep > 0 => {
+                cones_new.push(SupportedConeT::ZeroConeT(nkeep));
+            }
+            SupportedConeT::NonnegativeConeT(_) if nkeep > 0 => {
+                cones_new.push(SupportedConeT::NonnegativeConeT(nkeep));
+            }
+            SupportedConeT::ZeroConeT(_) | SupportedConeT::NonnegativeConeT(_) => {}
+            _ => {
+                debug_assert_eq!(nkeep, cone.nvars());
+                cones_new.push(cone.clone());
+            }
+        }
+    }
+    cones_new
+}
+
+fn _expand<T: FloatT>(full: &mut [T], reduced: &[T], map: &[Option<usize>]) {
+    for (v, idx) in zip(full.iter_mut(), map) {
+        *v = idx.map_or(T::zero(), |k| reduced[k]);
+    }
+}
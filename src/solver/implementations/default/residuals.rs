@@ -28,6 +28,14 @@ pub struct DefaultResiduals<T> {
 
     // the product Px by itself. Required for infeasibilty checks
     pub Px: Vec<T>,
+
+    // Px, rx_inf and rz_inf have been updated for the most recent
+    // step, and need not be recomputed on the next update
+    products_updated: bool,
+
+    // the residuals are large enough relative to the products
+    // they are formed from for a step to be applied incrementally
+    step_update_ok: bool,
 }
 
 impl<T> DefaultResiduals<T>
@@ -51,6 +59,8 @@ where
             rx_inf,
             rz_inf,
             Px,
+            products_updated: false,
+            step_update_ok: false,
             dot_qx: T::zero(),
             dot_bz: T::zero(),
             dot_sz: T::zero(),
@@ -59,38 +69,27 @@ where
     }
 }
 
-impl<T> Residuals<T> for DefaultResiduals<T>
+impl<'a, T> Residuals<T, DefaultProblemData<'a, T>> for DefaultResiduals<T>
 where
     T: FloatT,
 {
-    type D = DefaultProblemData<T>;
     type V = DefaultVariables<T>;
 
-    fn update(&mut self, variables: &DefaultVariables<T>, data: &DefaultProblemData<T>) {
+    fn update(&mut self, variables: &DefaultVariables<T>, data: &DefaultProblemData<'_, T>) {
         // various products used multiple times
         let qx = data.q.dot(&variables.x);
         let bz = data.b.dot(&variables.z);
         let sz = variables.s.dot(&variables.z);
 
-        //Px = P*x, P treated as symmetric
-        let symP = data.P.sym();
-        symP.symv(&mut self.Px, &variables.x, T::one(), T::zero());
+        if core::mem::take(&mut self.products_updated) {
+            #[cfg(feature = "residual-check")]
+            self.check_products(variables, data);
+        } else {
+            self.update_products(variables, data);
+        }
 
         let xPx = variables.x.dot(&self.Px);
 
-        //partial residual calc so we can check primal/dual
-        //infeasibility conditions
-
-        //Same as:
-        //rx_inf .= -data.A'* variables.z
-        let At = data.A.t();
-        At.gemv(&mut self.rx_inf, &variables.z, -T::one(), T::zero());
-
-        //Same as:  residuals.rz_inf .=  data.A * variables.x + variables.s
-        self.rz_inf.copy_from(&variables.s);
-        let A = &data.A;
-        A.gemv(&mut self.rz_inf, &variables.x, T::one(), T::one());
-
         //complete the residuals
         //rx = rx_inf - Px - qτ
         self.rx.waxpby(-T::one(), &self.Px, -variables.τ, &data.q);
@@ -103,10 +102,115 @@ where
         // τ = qz + bz + κ + xPx/τ;
         self.rτ = qx + bz + variables.κ + xPx / variables.τ;
 
+        // Incremental updates are only as accurate as the KKT solves,
+        // and their error persists until the next full update.  Allow
+        // them only while one of the residuals is well above that error,
+        // so that residuals near convergence are always recomputed
+        let δ = Self::step_update_tol();
+        let τ = variables.τ;
+        let scale_x = T::max(self.Px.norm_inf(), self.rx_inf.norm_inf());
+        let scale_x = T::max(scale_x, τ * data.q.norm_inf());
+        let scale_z = T::max(self.rz_inf.norm_inf(), τ * data.b.norm_inf());
+        self.step_update_ok =
+            self.rx.norm_inf() >= δ * scale_x || self.rz.norm_inf() >= δ * scale_z;
+
         //save local versions
         self.dot_qx = qx;
         self.dot_bz = bz;
         self.dot_sz = sz;
         self.dot_xPx = xPx;
     }
+
+    fn apply_step(
+        &mut self,
+        step: &DefaultVariables<T>,
+        step_rhs: &DefaultVariables<T>,
+        α: T,
+        data: &DefaultProblemData<'_, T>,
+    ) {
+        if !self.step_update_ok {
+            return;
+        }
+
+        // The step satisfies the KKT equations
+        //
+        //   PΔx + A'Δz = rhs.x - qΔτ
+        //   AΔx + Δs   = bΔτ - rhs.z
+        //
+        // so only PΔx needs a product with the problem data.  It is
+        // held in rx, which is recomputed on the next update
+        let PΔx = &mut self.rx;
+        data.mul_P(PΔx, &step.x);
+        self.Px.axpby(α, PΔx, T::one());
+
+        // rx_inf = -A'z
+        PΔx.axpby(step.τ, &data.q, T::one());
+        PΔx.axpby(-T::one(), &step_rhs.x, T::one());
+        self.rx_inf.axpby(α, PΔx, T::one());
+
+        // rz_inf = Ax + s
+        self.rz_inf.axpby(α * step.τ, &data.b, T::one());
+        self.rz_inf.axpby(-α, &step_rhs.z, T::one());
+
+        self.products_updated = true;
+    }
+}
+
+impl<T> DefaultResiduals<T>
+where
+    T: FloatT,
+{
+    // relative size of residuals below which steps are no longer
+    // applied incrementally, and the tolerance for their checks
+    fn step_update_tol() -> T {
+        T::epsilon().sqrt().sqrt()
+    }
+
+    fn update_products(
+        &mut self,
+        variables: &DefaultVariables<T>,
+        data: &DefaultProblemData<'_, T>,
+    ) {
+        //Px = P*x, P treated as symmetric
+        data.mul_P(&mut self.Px, &variables.x);
+
+        //partial residual calc so we can check primal/dual
+        //infeasibility conditions
+
+        //Same as:
+        //rx_inf .= -data.A'* variables.z
+        data.gemv_At(&mut self.rx_inf, &variables.z, -T::one(), T::zero());
+
+        //Same as:  residuals.rz_inf .=  data.A * variables.x + variables.s
+        self.rz_inf.copy_from(&variables.s);
+        data.gemv_A(&mut self.rz_inf, &variables.x, T::one(), T::one());
+    }
+
+    // compare updated products against a full recomputation,
+    // using rx and rz as workspace since they are not yet updated
+    #[cfg(feature = "residual-check")]
+    fn check_products(
+        &mut self,
+        variables: &DefaultVariables<T>,
+        data: &DefaultProblemData<'_, T>,
+    ) {
+        let check = |name, updated: &[T], computed: &[T]| {
+            let tol = Self::step_update_tol() * T::max(T::one(), computed.norm());
+            let err = updated.dist(computed);
+            assert!(
+                err <= tol,
+                "updated {name} differs from its recomputed value by {err}"
+            );
+        };
+
+        data.mul_P(&mut self.rx, &variables.x);
+        check("Px", &self.Px, &self.rx);
+
+        data.gemv_At(&mut self.rx, &variables.z, -T::one(), T::zero());
+        check("rx_inf", &self.rx_inf, &self.rx);
+
+        self.rz.copy_from(&variables.s);
+        data.gemv_A(&mut self.rz, &variables.x, T::one(), T::one());
+        check("rz_inf", &self.rz_inf, &self.rz);
+    }
 }
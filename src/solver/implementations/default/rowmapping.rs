@@ -0,0 +1,152 @@
+use super::*;
+use crate::algebra::*;
+
+// ---------------
+// Provenance of the constraint rows of the problem passed to the
+// solver through the transformations applied at setup.
+//
+// Rows are removed by presolve reductions and by the presolver for
+// absent bounds, with the remaining rows keeping their relative order.
+// When the problem is dualized, each remaining row becomes a variable
+// of the dual problem, and the rows of the dual problem have no
+// counterpart in the original.   Internal indices refer to the problem
+// before any chordal decomposition of PSD cones.
+// ---------------
+
+/// Reason for which a constraint row was removed at setup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowRemoval {
+    /// an empty row that is satisfied by any `x`
+    EmptyRow,
+    /// a row that fixed the value of the variable with the given index
+    FixedVariable(usize),
+    /// a bound on the variable with the given index that is implied
+    /// by a tighter one
+    RedundantBound(usize),
+    /// a nonnegative cone row with an infinite right hand side
+    InfiniteBound,
+}
+
+/// Fate of a constraint row of the original problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowFate {
+    /// the row has the given index in the internal problem
+    Row(usize),
+    /// the row was removed from the problem
+    Removed(RowRemoval),
+    /// the problem was dualized, and the dual of the row is the
+    /// variable with the given index in the internal problem
+    DualVariable(usize),
+}
+
+/// Map between the constraint rows of the original problem and those
+/// of the problem solved internally, as built at setup from the presolve
+/// reductions, dualization and presolver applied to the problem.
+///
+/// Slacks and duals of rows removed for an infinite bound are reported
+/// as `s = ∞` and `z = 0`.   Those of rows removed by presolve reductions
+/// are recovered from the solution, as described in
+/// [`PresolveResult::postsolve`].
+#[derive(Debug, Clone)]
+pub struct RowMapping {
+    fates: Vec<RowFate>,
+    // the original row of each internal row, if any
+    origins: Vec<Option<usize>>,
+    infbound: f64,
+}
+
+impl RowMapping {
+    pub(crate) fn new<T: FloatT>(
+        m: usize,
+        reductions: Option<&PresolveResult<T>>,
+        dualization: Option<&DualTransform<T>>,
+        presolver: Option<&Presolver<T>>,
+        minternal: usize,
+    ) -> Self {
+        let mut fates = match reductions {
+            Some(r) => r.row_fates(),
+            None => (0..m).map(RowFate::Row).collect(),
+        };
+
+        if let Some(d) = dualization {
+            for fate in fates.iter_mut() {
+                if let RowFate::Row(k) = *fate {
+                    *fate = RowFate::DualVariable(d.dual_variable(k));
+                }
+            }
+        }
+
+        if let Some(p) = presolver {
+            let next = p.row_fates();
+            for fate in fates.iter_mut() {
+                if let RowFate::Row(k) = *fate {
+                    *fate = next[k];
+                }
+            }
+        }
+
+        let mut origins = vec![None; minternal];
+        for (i, fate) in fates.iter().enumerate() {
+            if let RowFate::Row(k) = *fate {
+                origins[k] = Some(i);
+            }
+        }
+
+        Self {
+            fates,
+            origins,
+            infbound: presolver.map_or_else(crate::solver::get_infinity, |p| p.infbound),
+        }
+    }
+
+    /// Number of constraint rows in the original problem.
+    pub fn original_rows(&self) -> usize {
+        self.fates.len()
+    }
+
+    /// Number of constraint rows in the internal problem.
+    pub fn internal_rows(&self) -> usize {
+        self.origins.len()
+    }
+
+    /// Fate of row `i` of the original problem.
+    pub fn original_to_internal(&self, i: usize) -> RowFate {
+        self.fates[i]
+    }
+
+    /// The original row that became row `k` of the internal problem,
+    /// or `None` if the row has no counterpart in the original problem.
+    pub fn internal_to_original(&self, k: usize) -> Option<usize> {
+        self.origins[k]
+    }
+
+    // restrict a vector over the original rows to the internal rows.
+    // Every internal row has an original row unless dualized
+    pub(crate) fn restrict<T: FloatT>(&self, v_int: &mut [T], v: &[T]) {
+        for (vk, &i) in v_int.iter_mut().zip(&self.origins) {
+            *vk = v[i.unwrap()];
+        }
+    }
+
+    // expand the internal slacks and duals to the original rows.  Rows
+    // removed for an infinite bound take s = ∞ and z = 0, and any other
+    // removed rows are zero for presolve reductions to complete
+    pub(crate) fn expand<T: FloatT>(&self, s: &mut [T], z: &mut [T], s_int: &[T], z_int: &[T]) {
+        for (i, &fate) in self.fates.iter().enumerate() {
+            (s[i], z[i]) = match fate {
+                RowFate::Row(k) => (s_int[k], z_int[k]),
+                RowFate::Removed(RowRemoval::InfiniteBound) => (self.infbound.as_T(), T::zero()),
+                _ => (T::zero(), T::zero()),
+            };
+        }
+    }
+
+    // set the slacks of the rows removed for an infinite bound to ∞
+    pub(crate) fn reset_infinite_bounds<T: FloatT>(&self, s: &mut [T]) {
+        for (si, &fate) in s.iter_mut().zip(&self.fates) {
+            if fate == RowFate::Removed(RowRemoval::InfiniteBound) {
+                *si = self.infbound.as_T();
+            }
+        }
+    }
+}
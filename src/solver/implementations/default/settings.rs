@@ -1,16 +1,104 @@
 use crate::algebra::*;
-use crate::solver::core::traits::Settings;
+use crate::solver::core::{traits::Settings, ScalingStrategy};
 use derive_builder::Builder;
 
 #[cfg(feature = "serde")]
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+/// Direction of optimization for the objective.   Problems marked
+/// [`Maximize`](OptimizationSense::Maximize) maximize `qᵀx - ½xᵀPx`,
+/// and are solved internally by minimizing its negation.
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OptimizationSense {
+    #[default]
+    Minimize,
+    Maximize,
+}
+
+/// Handling of termination tolerances that are too small to be met in
+/// the scalar type of the solver.  See
+/// [`tolerance_policy`](DefaultSettings::tolerance_policy).
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TolerancePolicy {
+    /// raise the tolerance to the smallest attainable value,
+    /// with a warning in the verbose output
+    #[default]
+    Clamp,
+    /// fail the setup with a settings error
+    Strict,
+}
+
+/// Iteration dependent schedule for the constant part of the static
+/// KKT regularization.  When set in
+/// [`static_regularization_schedule`](DefaultSettings::static_regularization_schedule)
+/// it replaces [`static_regularization_constant`](DefaultSettings::static_regularization_constant).
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RegularizationSchedule<T> {
+    /// the same value at every iteration
+    Constant(T),
+    /// `initial * decay^k` at iteration `k`, i.e. large regularization
+    /// early in the solve and small regularization near convergence
+    Decreasing { initial: T, decay: T },
+    /// starts from `initial`, and is increased by the solver whenever
+    /// iterative refinement fails to reduce the KKT residual to within
+    /// tolerance, then relaxed back towards `initial` once it does.
+    /// Requires `iterative_refinement_enable`.
+    Adaptive { initial: T },
+}
+
+impl<T> RegularizationSchedule<T>
+where
+    T: FloatT,
+{
+    /// Scheduled regularization at iteration `k`.  For the
+    /// [`Adaptive`](RegularizationSchedule::Adaptive) schedule this is the
+    /// initial value, before any increase applied by the solver.
+    pub fn value_at_iteration(&self, k: usize) -> T {
+        match *self {
+            RegularizationSchedule::Constant(value) => value,
+            RegularizationSchedule::Decreasing { initial, decay } => {
+                let k = i32::try_from(k).unwrap_or(i32::MAX);
+                initial * T::powi(decay, k)
+            }
+            RegularizationSchedule::Adaptive { initial } => initial,
+        }
+    }
+
+    // the same schedule with all values multiplied by `factor`
+    pub(crate) fn scaled(&self, factor: T) -> Self {
+        match *self {
+            RegularizationSchedule::Constant(value) => {
+                RegularizationSchedule::Constant(value * factor)
+            }
+            RegularizationSchedule::Decreasing { initial, decay } => {
+                RegularizationSchedule::Decreasing {
+                    initial: initial * factor,
+                    decay,
+                }
+            }
+            RegularizationSchedule::Adaptive { initial } => RegularizationSchedule::Adaptive {
+                initial: initial * factor,
+            },
+        }
+    }
+}
+
 /// Standard-form solver type implementing the [`Settings`](crate::solver::core::traits::Settings) trait
 
 #[derive(Builder, Debug, Clone)]
 #[builder(build_fn(validate = "Self::validate"))]
+#[cfg_attr(not(feature = "std"), builder(no_std))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[serde(bound = "T: Serialize + DeserializeOwned")]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "T: Serialize + DeserializeOwned", default)
+)]
 pub struct DefaultSettings<T: FloatT> {
     ///maximum number of iterations
     #[builder(default = "200")]
@@ -20,10 +108,19 @@ pub struct DefaultSettings<T: FloatT> {
     #[builder(default = "f64::INFINITY")]
     pub time_limit: f64,
 
+    ///record the time spent in each phase of setup and solve.
+    ///See [`DefaultSolver::timings`](crate::solver::DefaultSolver::timings).
+    #[builder(default = "false")]
+    pub timing: bool,
+
     ///verbose printing
     #[builder(default = "true")]
     pub verbose: bool,
 
+    ///minimize or maximize the objective
+    #[builder(default = "OptimizationSense::Minimize")]
+    pub sense: OptimizationSense,
+
     ///maximum interior point step length
     #[builder(default = "(0.99).as_T()")]
     pub max_step_fraction: T,
@@ -76,6 +173,32 @@ pub struct DefaultSettings<T: FloatT> {
     #[builder(default = "(1e-4).as_T()")]
     pub reduced_tol_ktratio: T,
 
+    ///handling of termination tolerances `tol_*` below the smallest value
+    ///attainable in the scalar type, i.e. `10ε` for relative tolerances
+    ///and `10ε·max(1,‖q‖∞,‖b‖∞)` for absolute ones.  The tolerances in use
+    ///are recorded in the solver info
+    #[builder(default = "TolerancePolicy::Clamp")]
+    pub tolerance_policy: TolerancePolicy,
+
+    ///accept `P` and `A` with unsorted or repeated entries in a column,
+    ///using internal copies with sorted rows and repeated entries summed.
+    ///If disabled, setup fails for such matrices.  Data updates with vectors
+    ///of values then refer to the sorted entries.
+    #[builder(default = "true")]
+    pub input_sparse_canonicalize: bool,
+
+    ///accept `P` supplied as a full symmetric matrix, using its upper triangle.
+    ///If disabled, or if `P` is not symmetric, setup fails for a `P` with
+    ///entries below the diagonal.  See [`DefaultSolver::new`](crate::solver::DefaultSolver::new).
+    #[builder(default = "true")]
+    pub p_auto_triu: bool,
+
+    ///check that `P` is positive semidefinite during setup by factoring it,
+    ///failing setup if it is not.  Negative diagonal entries of `P` are
+    ///always rejected.  See [`DefaultSolver::new`](crate::solver::DefaultSolver::new).
+    #[builder(default = "false")]
+    pub check_convexity: bool,
+
     ///enable data equilibration pre-scaling
     #[builder(default = "true")]
     pub equilibrate_enable: bool,
@@ -92,6 +215,36 @@ pub struct DefaultSettings<T: FloatT> {
     #[builder(default = "(1e+4).as_T()")]
     pub equilibrate_max_scaling: T,
 
+    ///skip equilibration if the data is already well scaled, i.e. if every
+    ///equilibration scaling lies within `[1/τ, τ]` for this threshold `τ`,
+    ///e.g. `τ = 1.5`.  The solver then uses the unscaled data, and so takes
+    ///different iterates than with equilibration.  Values below 1,
+    ///including the default, always equilibrate
+    #[builder(default = "(0.0).as_T()")]
+    pub equilibrate_skip_threshold: T,
+
+    ///back off step lengths by distance from the cone boundary (nonsymmetric cones).
+    ///The back-off never exceeds `max_step_fraction`
+    #[builder(default = "false")]
+    pub adaptive_step_backoff: bool,
+
+    ///fixed step length back-off when `adaptive_step_backoff` is disabled (nonsymmetric cones).
+    ///The smaller of this and `max_step_fraction` is used
+    #[builder(default = "(0.99).as_T()")]
+    pub step_backoff_factor: T,
+
+    ///switch to conservative steps near convergence
+    #[builder(default = "false")]
+    pub conservative_steps_enable: bool,
+
+    ///relative duality gap below which conservative steps are used
+    #[builder(default = "(1e-5).as_T()")]
+    pub conservative_steps_gap_rel: T,
+
+    ///maximum interior point step length for conservative steps
+    #[builder(default = "(0.9).as_T()")]
+    pub conservative_step_fraction: T,
+
     ///linesearch backtracking
     #[builder(default = "(0.8).as_T()")]
     pub linesearch_backtrack_step: T,
@@ -100,10 +253,18 @@ pub struct DefaultSettings<T: FloatT> {
     #[builder(default = "(1e-1).as_T()")]
     pub min_switch_step_length: T,
 
-    ///minimum step size allowed for symmetric cones & asymmetric cones with Dual scaling
+    ///minimum step size allowed for symmetric cones & asymmetric cones with Dual scaling.
+    ///Smaller steps are retried without a corrector and then as a centering step before
+    ///the solver terminates with `InsufficientProgress`
     #[builder(default = "(1e-4).as_T()")]
     pub min_terminate_step_length: T,
 
+    ///scaling strategy to switch to when the primary strategy fails.  The switch is
+    ///skipped if it would not change the scaling of any cone, e.g. from `PrimalDual`
+    ///to `Dual` for problems with only symmetric cones
+    #[builder(default = "ScalingStrategy::Dual")]
+    pub scaling_fallback: ScalingStrategy,
+
     ///use a direct linear solver method (required true)
     #[builder(default = "true")]
     pub direct_kkt_solver: bool,
@@ -124,6 +285,11 @@ pub struct DefaultSettings<T: FloatT> {
     #[builder(default = "T::epsilon()*T::epsilon()")]
     pub static_regularization_proportional: T,
 
+    ///iteration dependent schedule for the KKT static regularization
+    ///parameter, replacing `static_regularization_constant` when set
+    #[builder(default = "None")]
+    pub static_regularization_schedule: Option<RegularizationSchedule<T>>,
+
     ///enable KKT dynamic regularization
     #[builder(default = "true")]
     pub dynamic_regularization_enable: bool,
@@ -156,10 +322,94 @@ pub struct DefaultSettings<T: FloatT> {
     #[builder(default = "(5.0).as_T()")]
     pub iterative_refinement_stop_ratio: T,
 
+    ///refactor the KKT matrix with increased static regularization and
+    ///solve again when iterative refinement fails to reach its tolerance.
+    ///Applied at most once per solver iteration
+    #[builder(default = "false")]
+    pub iterative_refinement_refactor: bool,
+
+    ///solve independent KKT right hand sides together, in a single
+    ///pass over the factors.   Solutions are identical either way
+    #[builder(default = "true")]
+    pub batched_kkt_solves: bool,
+
+    ///update the products of the problem data with the variables from
+    ///each step, rather than recomputing them for the residuals, when
+    ///the KKT solves for the step met the iterative refinement tolerance
+    #[builder(default = "false")]
+    pub incremental_residuals: bool,
+
     ///enable presolve constraint reduction
     #[builder(default = "true")]
     pub presolve_enable: bool,
 
+    ///threshold beyond which entries of `b` and `q` are treated as infinite.
+    ///
+    ///- Entries of `b` in nonnegative cone rows at or above the threshold,
+    ///  including `+∞`, are absent bounds.  Their rows are removed if
+    ///  `presolve_enable` is set, and are otherwise kept with the entry capped
+    ///  at the bound given by [`set_infinity`](crate::solver::set_infinity).
+    ///- Any other entry of `b` or `q` that is infinite or NaN, including
+    ///  those in zero cone rows, fails setup.
+    ///- Finite entries beyond the threshold in magnitude that remain in the
+    ///  problem are reported with a warning in the verbose output, since they
+    ///  are likely to cause poor scaling.
+    #[builder(default = "(1e20).as_T()")]
+    pub infinite_bound_threshold: T,
+
+    ///enable presolve reductions, i.e. removal of fixed variables,
+    ///empty and singleton constraints and redundant bounds
+    #[builder(default = "false")]
+    pub presolve_reductions_enable: bool,
+
+    ///solve the dual problem instead when there are more than ten
+    ///times as many constraints as variables
+    #[builder(default = "false")]
+    pub auto_dualize: bool,
+
+    ///store an explicit transpose of A for products with A' when A
+    ///has at least this many nonzeros.   Use `usize::MAX` to disable.
+    ///With the `parallel` feature, the transpose is also stored when
+    ///products with A are computed in parallel, since they use it
+    #[builder(default = "1_000_000")]
+    pub explicit_transpose_min_nnz: usize,
+
+    ///maximum memory in bytes used by an explicit transpose of A
+    #[builder(default = "1 << 30")]
+    pub explicit_transpose_max_bytes: usize,
+
+    ///number of most recent iterations retained for post-mortem reporting
+    #[builder(default = "10")]
+    pub postmortem_window: usize,
+
+    ///record a copy of the KKT system at this iteration, or 0 to disable.
+    ///See [`DefaultSolver::kkt_snapshot`](crate::solver::DefaultSolver::kkt_snapshot).
+    #[builder(default = "0")]
+    pub kkt_snapshot_iteration: u32,
+
+    ///re-solve with more conservative settings on numerical error
+    #[builder(default = "false")]
+    pub auto_recovery: bool,
+
+    ///maximum number of automatic recovery re-solves
+    #[builder(default = "3")]
+    pub max_recovery_attempts: u32,
+
+    ///catch panics raised during a solve and report them with a
+    ///[`NumericalError`](crate::solver::SolverStatus::NumericalError) status.
+    ///The panic message is recorded in the solver info, and the solver is
+    ///poisoned so that later solves and data updates fail.   Disabled by
+    ///default in debug builds, where panics are re-raised.   Has no effect
+    ///without the `std` feature, where panics can't be caught
+    #[builder(default = "!cfg!(debug_assertions)")]
+    pub catch_panics: bool,
+
+    ///memory budget in bytes for the solver.   Setup fails with
+    ///[`MemoryLimitExceeded`](crate::solver::ClarabelError::MemoryLimitExceeded)
+    ///if the estimated memory use exceeds it.  See [`DefaultSolver::new`](crate::solver::DefaultSolver::new).
+    #[builder(default = "None")]
+    pub max_memory_bytes: Option<usize>,
+
     /// enable chordal decomposition.
     /// [requires "sdp" feature.]
     #[cfg(feature = "sdp")]
@@ -207,6 +457,109 @@ where
     }
 }
 
+impl<T> DefaultSettings<T>
+where
+    T: FloatT,
+{
+    /// Default settings with tolerances and regularization calibrated
+    /// to the machine precision of `T`.
+    ///
+    /// The standard defaults are chosen for `f64`, and are too tight for
+    /// lower precision types.  Here they are scaled by `r = ε/ε_f64`, where
+    /// `ε` is the machine epsilon of `T`.  Termination tolerances and
+    /// regularization terms that are on the order of `√ε` are scaled by
+    /// `√r`, and terms on the order of `ε` are scaled by `r`.   For `f64`
+    /// this is the same as [`default`](DefaultSettings::default), and for
+    /// higher precision types (e.g. `DoubleDouble`, with the
+    /// `higher-precision` feature) the tolerances are tightened accordingly.
+    ///
+    /// In `f32`, small and well scaled LPs, QPs and SOCPs typically solve
+    /// to the resulting tolerances of about `1e-4`.   Problems with
+    /// exponential, power or PSD cones, badly scaled data or near
+    /// infeasibility are unlikely to solve reliably in `f32`.
+    pub fn default_for() -> Self {
+        let mut settings = Self::default();
+
+        let r = T::epsilon() / f64::EPSILON.as_T();
+        if r == T::one() {
+            return settings;
+        }
+        let sqrt_r = r.sqrt();
+
+        settings.tol_gap_abs *= sqrt_r;
+        settings.tol_gap_rel *= sqrt_r;
+        settings.tol_feas *= sqrt_r;
+        settings.tol_infeas_abs *= sqrt_r;
+        settings.tol_infeas_rel *= sqrt_r;
+        settings.tol_ktratio *= sqrt_r.sqrt();
+
+        // reduced tolerances should remain looser than the full ones,
+        // and are only tightened for higher precision types
+        let ten: T = (10.).as_T();
+        let reduced_r = T::min(sqrt_r, T::one());
+        settings.reduced_tol_gap_abs *= reduced_r;
+        settings.reduced_tol_gap_rel *= reduced_r;
+        settings.reduced_tol_feas *= reduced_r;
+        settings.reduced_tol_infeas_abs *= reduced_r;
+        settings.reduced_tol_infeas_rel *= reduced_r;
+        settings.reduced_tol_ktratio *= reduced_r.sqrt();
+        settings.reduced_tol_gap_abs =
+            T::max(settings.reduced_tol_gap_abs, settings.tol_gap_abs * ten);
+        settings.reduced_tol_gap_rel =
+            T::max(settings.reduced_tol_gap_rel, settings.tol_gap_rel * ten);
+        settings.reduced_tol_feas = T::max(settings.reduced_tol_feas, settings.tol_feas * ten);
+        settings.reduced_tol_infeas_abs = T::max(
+            settings.reduced_tol_infeas_abs,
+            settings.tol_infeas_abs * ten,
+        );
+        settings.reduced_tol_infeas_rel = T::max(
+            settings.reduced_tol_infeas_rel,
+            settings.tol_infeas_rel * ten,
+        );
+        settings.reduced_tol_ktratio =
+            T::max(settings.reduced_tol_ktratio, settings.tol_ktratio * ten);
+
+        settings.static_regularization_constant *= sqrt_r;
+        settings.dynamic_regularization_eps *= r;
+        settings.dynamic_regularization_delta *= sqrt_r;
+        settings.iterative_refinement_reltol *= r;
+        settings.iterative_refinement_abstol *= r;
+
+        settings
+    }
+
+    // the termination tolerances, with true for those
+    // measured in absolute rather than relative terms
+    pub(crate) fn tolerances_mut(&mut self) -> [(&'static str, &mut T, bool); 6] {
+        [
+            ("tol_gap_abs", &mut self.tol_gap_abs, true),
+            ("tol_gap_rel", &mut self.tol_gap_rel, false),
+            ("tol_feas", &mut self.tol_feas, false),
+            ("tol_infeas_abs", &mut self.tol_infeas_abs, true),
+            ("tol_infeas_rel", &mut self.tol_infeas_rel, false),
+            ("tol_ktratio", &mut self.tol_ktratio, false),
+        ]
+    }
+
+    // settings used for the n^th automatic recovery attempt after a
+    // numerical error.   Each attempt increases the static regularization
+    // by a factor of 10 and shortens the maximum step by a factor of 0.9
+    pub(crate) fn recovery_profile(&self, attempt: u32) -> Self {
+        let mut settings = self.clone();
+        let attempt = attempt as i32;
+
+        let factor = T::powi((10.).as_T(), attempt);
+        settings.static_regularization_enable = true;
+        settings.static_regularization_constant *= factor;
+        settings.static_regularization_schedule = settings
+            .static_regularization_schedule
+            .map(|s| s.scaled(factor));
+        settings.max_step_fraction *= T::powi((0.9).as_T(), attempt);
+
+        settings
+    }
+}
+
 // pre build checker (for auto-validation when using the builder)
 
 /// Automatic pre-build settings validation
@@ -7,10 +7,20 @@ use crate::{
 };
 
 /// Standard-form solver type implementing the [`Solution`](crate::solver::core::traits::Solution) trait
+///
+/// When the problem is found to be infeasible, the solution holds a
+/// certificate of infeasibility.   For primal infeasibility, `z` satisfies
+/// `A'z ≈ 0`, `b'z < 0` and `z ∈ K*`, and is scaled to unit infinity norm.
+/// For dual infeasibility, `x` satisfies `Px ≈ 0`, `q'x < 0` and `-Ax ∈ K`,
+/// with `s = -Ax`, and is scaled to unit infinity norm.
 #[derive(Debug)]
 pub struct DefaultSolution<T> {
+    /// primal variables of the problem as supplied
     pub x: Vec<T>,
+    /// dual variables, one per constraint row of the problem as
+    /// supplied, so in the order of its cones
     pub z: Vec<T>,
+    /// slack variables, in the same layout as `z`
     pub s: Vec<T>,
     pub status: SolverStatus,
     pub obj_val: T,
@@ -19,6 +29,9 @@ pub struct DefaultSolution<T> {
     pub iterations: u32,
     pub r_prim: T,
     pub r_dual: T,
+    /// automatic recovery attempt that produced this solution,
+    /// or zero if the user's settings were used throughout
+    pub recovery_attempt: u32,
 }
 
 impl<T> DefaultSolution<T>
@@ -41,22 +54,43 @@ where
             iterations: 0,
             r_prim: T::nan(),
             r_dual: T::nan(),
+            recovery_attempt: 0,
         }
     }
 }
 
-impl<T> Solution<T> for DefaultSolution<T>
+#[cfg(feature = "ndarray")]
+impl<T> DefaultSolution<T>
+where
+    T: FloatT,
+{
+    /// Copy of the primal solution `x` as an ndarray vector.
+    pub fn x_array(&self) -> ndarray::Array1<T> {
+        ndarray::Array1::from(self.x.clone())
+    }
+
+    /// Copy of the dual solution `z` as an ndarray vector.
+    pub fn z_array(&self) -> ndarray::Array1<T> {
+        ndarray::Array1::from(self.z.clone())
+    }
+
+    /// Copy of the slack variables `s` as an ndarray vector.
+    pub fn s_array(&self) -> ndarray::Array1<T> {
+        ndarray::Array1::from(self.s.clone())
+    }
+}
+
+impl<'a, T> Solution<T, DefaultProblemData<'a, T>> for DefaultSolution<T>
 where
     T: FloatT,
 {
-    type D = DefaultProblemData<T>;
     type V = DefaultVariables<T>;
     type I = DefaultInfo<T>;
     type SE = DefaultSettings<T>;
 
     fn post_process(
         &mut self,
-        data: &DefaultProblemData<T>,
+        data: &DefaultProblemData<'_, T>,
         variables: &mut DefaultVariables<T>,
         info: &DefaultInfo<T>,
         settings: &DefaultSettings<T>,
@@ -72,10 +106,24 @@ where
             self.obj_val_dual = info.cost_dual;
         }
 
+        // report objectives in terms of the user's maximization
+        if data.maximization_flag {
+            self.obj_val = -self.obj_val;
+            self.obj_val_dual = -self.obj_val_dual;
+        }
+
         self.iterations = info.iterations;
         self.r_prim = info.res_primal;
         self.r_dual = info.res_dual;
 
+        // report status, objectives and residuals for the
+        // original problem when the dual problem was solved
+        if data.dualization.is_some() {
+            self.status = _primal_status(self.status);
+            (self.obj_val, self.obj_val_dual) = (-self.obj_val_dual, -self.obj_val);
+            (self.r_prim, self.r_dual) = (self.r_dual, self.r_prim);
+        }
+
         // unscale the variables to get a solution
         // to the internal problem as we solved it
         variables.unscale(data, is_infeasible);
@@ -90,16 +138,102 @@ where
         #[cfg(feature = "sdp")]
         let variables = tmp.as_ref().unwrap_or(variables);
 
-        if let Some(ref presolver) = data.presolver {
-            presolver.reverse_presolve(self, variables);
+        // without dualization, rows map directly to those of the original
+        // problem, and presolve reductions are reversed last
+        if let Some(ref dualization) = data.dualization {
+            _recover_from_dual(self, data, variables, dualization);
         } else {
-            self.x.copy_from(&variables.x);
-            self.z.copy_from(&variables.z);
-            self.s.copy_from(&variables.s);
+            data.row_mapping
+                .expand(&mut self.s, &mut self.z, &variables.s, &variables.z);
+            if let Some(ref reductions) = data.reductions {
+                reductions.postsolve_expanded(self, &variables.x);
+            } else {
+                self.x.copy_from(&variables.x);
+            }
+        }
+
+        if is_infeasible {
+            _normalize_certificate(self);
+            data.row_mapping.reset_infinite_bounds(&mut self.s);
         }
     }
 
     fn finalize(&mut self, info: &DefaultInfo<T>) {
         self.solve_time = info.solve_time;
+        self.recovery_attempt = info.recovery.map_or(0, |r| r.attempt);
+    }
+
+    fn abandon(&mut self, info: &DefaultInfo<T>) {
+        self.status = info.status;
+        self.x.fill(T::nan());
+        self.z.fill(T::nan());
+        self.s.fill(T::nan());
+        self.obj_val = T::nan();
+        self.obj_val_dual = T::nan();
+        self.r_prim = T::nan();
+        self.r_dual = T::nan();
+        self.iterations = info.iterations;
+        self.finalize(info);
+    }
+}
+
+fn _reverse_presolve<T: FloatT>(
+    solution: &mut DefaultSolution<T>,
+    data: &DefaultProblemData<'_, T>,
+    variables: &DefaultVariables<T>,
+) {
+    if let Some(ref presolver) = data.presolver {
+        presolver.reverse_presolve(solution, variables);
+    } else {
+        solution.x.copy_from(&variables.x);
+        solution.z.copy_from(&variables.z);
+        solution.s.copy_from(&variables.s);
+    }
+}
+
+// dualization and then presolve reductions are reversed, starting
+// from the solution to the problem as passed to the presolver
+fn _recover_from_dual<T: FloatT>(
+    solution: &mut DefaultSolution<T>,
+    data: &DefaultProblemData<'_, T>,
+    variables: &DefaultVariables<T>,
+    dualization: &DualTransform<T>,
+) {
+    let mut inner = DefaultSolution::new(dualization.q.len(), dualization.b.len());
+    _reverse_presolve(&mut inner, data, variables);
+
+    let (n, m) = data
+        .reductions
+        .as_ref()
+        .map_or((solution.x.len(), solution.z.len()), |r| {
+            (r.q.len(), r.b.len())
+        });
+    let mut primal = DefaultSolution::new(n, m);
+    dualization.recover_into(&mut primal, &inner.x, &inner.z);
+
+    if let Some(ref reductions) = data.reductions {
+        reductions.postsolve_into(solution, &primal.x, &primal.z, &primal.s);
+    } else {
+        solution.x.copy_from(&primal.x);
+        solution.z.copy_from(&primal.z);
+        solution.s.copy_from(&primal.s);
+    }
+}
+
+// scale an infeasibility certificate so that z, for primal infeasibility,
+// or x, for dual infeasibility, has unit infinity norm
+fn _normalize_certificate<T: FloatT>(solution: &mut DefaultSolution<T>) {
+    let norm = match solution.status {
+        SolverStatus::PrimalInfeasible | SolverStatus::AlmostPrimalInfeasible => {
+            solution.z.norm_inf()
+        }
+        _ => solution.x.norm_inf(),
+    };
+    if norm == T::zero() || !norm.is_finite() {
+        return;
     }
+    let scale = norm.recip();
+    solution.x.scale(scale);
+    solution.z.scale(scale);
+    solution.s.scale(scale);
 }
@@ -1,17 +1,41 @@
 use super::*;
 use crate::solver::core::{
-    cones::{CompositeCone, SupportedConeT},
+    cones::{CompositeCone, ScalingError, SupportedConeT},
+    kktsolvers::KktSnapshot,
     traits::ProblemData,
     Solver,
 };
 
 use crate::algebra::*;
+use crate::qdldl::{QDLDLFactorisation, QDLDLSettingsBuilder};
 use crate::timers::*;
+use core::iter::zip;
 
 /// Solver for problems in standard conic program form
+///
+/// # Thread safety
+///
+/// Solvers are `Send` and `Sync`, so a solver can be created on one
+/// thread and solved on another, and independent solvers can be solved
+/// in parallel on separate threads.   A single solver cannot be used
+/// for concurrent solves, since solving and updating the problem data
+/// require exclusive (`&mut`) access.
+///
+/// The infinity bound set with [`set_infinity`](crate::solver::set_infinity)
+/// is shared by all solvers.   It is read when a solver is created,
+/// so it should not be changed while solvers are being created on
+/// other threads.
 
-pub type DefaultSolver<T = f64> = Solver<
-    DefaultProblemData<T>,
+pub type DefaultSolver<T = f64> = BorrowedSolver<'static, T>;
+
+/// A [`DefaultSolver`] borrowing the sparsity patterns of its problem
+/// data from the caller for the lifetime `'a`, as returned by
+/// [`DefaultSolver::new_borrowed`].
+///
+/// The methods of [`DefaultSolver`] are available for this type too,
+/// except for those creating a solver.
+pub type BorrowedSolver<'a, T = f64> = Solver<
+    DefaultProblemData<'a, T>,
     DefaultVariables<T>,
     DefaultResiduals<T>,
     DefaultKKTSystem<T>,
@@ -25,33 +49,144 @@ impl<T> DefaultSolver<T>
 where
     T: FloatT,
 {
+    /// Create a new solver for the conic program with data `(P,q,A,b)`.
+    ///
+    /// The vectors `q` and `b` can be any type implementing
+    /// [`AsVectorSlice`], e.g. slices, `Vec<T>` or (with the `nalgebra`
+    /// or `ndarray` features) `DVector<T>` and `ArrayView1<T>`.
+    ///
+    /// The problem data, cones and settings are checked before any
+    /// other work is done, and an error describing the first problem
+    /// found is returned if they are inconsistent.   An error is also
+    /// returned if the solver would exceed the
+    /// [`max_memory_bytes`](DefaultSettings::max_memory_bytes) budget.
+    ///
+    /// `P` should be upper triangular.   A `P` supplied as a full symmetric
+    /// matrix is accepted if the [`p_auto_triu`](DefaultSettings::p_auto_triu)
+    /// setting is enabled, and its upper triangle is used.   Otherwise, or if
+    /// `P` is not symmetric, an error naming an entry below the diagonal is
+    /// returned, since using only the upper triangle would silently solve a
+    /// different problem.
+    ///
+    /// A `P` with a negative diagonal entry makes the objective nonconvex
+    /// and is always rejected.   Other indefinite `P` are rejected only if
+    /// the [`check_convexity`](DefaultSettings::check_convexity) setting is
+    /// enabled, since the check requires a factorization of `P`.
+    ///
+    /// `P` and `A` with unsorted or repeated entries in a column are
+    /// replaced by sorted internal copies, with repeated entries summed,
+    /// if the [`input_sparse_canonicalize`](DefaultSettings::input_sparse_canonicalize)
+    /// setting is enabled, and are otherwise rejected.
+    ///
+    /// Entries of `b` and `q` that are infinite or very large are handled
+    /// as described for the
+    /// [`infinite_bound_threshold`](DefaultSettings::infinite_bound_threshold)
+    /// setting.
+    ///
+    /// Termination tolerances too small to be met in the scalar type `T`
+    /// are raised or rejected according to the
+    /// [`tolerance_policy`](DefaultSettings::tolerance_policy) setting.
     pub fn new(
         P: &CscMatrix<T>,
-        q: &[T],
+        q: &(impl AsVectorSlice<T> + ?Sized),
         A: &CscMatrix<T>,
-        b: &[T],
+        b: &(impl AsVectorSlice<T> + ?Sized),
         cones: &[SupportedConeT<T>],
-        settings: DefaultSettings<T>,
-    ) -> Self {
-        //sanity check problem dimensions
-        _check_dimensions(P, q, A, b, cones);
+        mut settings: DefaultSettings<T>,
+    ) -> Result<Self, ClarabelError> {
+        let (q, b) = (q.as_vector_slice(), b.as_vector_slice());
+        let (q, b) = (&q[..], &b[..]);
+
+        let checked = check_problem(P, q, A, b, cones, &mut settings)?;
+        let (P, A) = match checked.sorted {
+            Some((ref P, ref A, _)) => (P, A),
+            None => (P, A),
+        };
+
+        let mut solver = Self::_new(A.size(), settings, |settings| {
+            let mut data = DefaultProblemData::<T>::new(P, q, A, b, cones, settings);
+            checked.record(&mut data);
+            data
+        })?;
+        checked.record_info(&mut solver);
+        Ok(solver)
+    }
+
+    /// Create a new solver for the conic program with data `(P,q,A,b)`,
+    /// borrowing the sparsity patterns of `P` and `A` from the caller.
+    ///
+    /// The solver takes internal copies only of the values of `P` and
+    /// `A` and of the vectors `q` and `b`, which it scales during setup.
+    /// The `colptr` and `rowval` vectors of `P` and `A` are borrowed
+    /// for the lifetime of the returned [`BorrowedSolver`], and are
+    /// never modified.
+    ///
+    /// Patterns are copied as usual if presolve reductions, dualization,
+    /// sorting of unsorted entries or a conversion of `P` to upper
+    /// triangular form modify a matrix.
+    ///
+    /// Errors are returned as for [`new`](Self::new).
+    pub fn new_borrowed<'a>(
+        P: &'a CscMatrix<T>,
+        q: &(impl AsVectorSlice<T> + ?Sized),
+        A: &'a CscMatrix<T>,
+        b: &(impl AsVectorSlice<T> + ?Sized),
+        cones: &[SupportedConeT<T>],
+        mut settings: DefaultSettings<T>,
+    ) -> Result<BorrowedSolver<'a, T>, ClarabelError> {
+        let (q, b) = (q.as_vector_slice(), b.as_vector_slice());
+        let (q, b) = (&q[..], &b[..]);
 
+        let checked = check_problem(P, q, A, b, cones, &mut settings)?;
+
+        let mut solver = BorrowedSolver::_new(A.size(), settings, |settings| {
+            let mut data = match checked.sorted {
+                // sorted copies are used in place of the caller's patterns
+                Some((ref P, ref A, _)) => DefaultProblemData::new(P, q, A, b, cones, settings),
+                None => DefaultProblemData::new_borrowed(P, q, A, b, cones, settings),
+            };
+            checked.record(&mut data);
+            data
+        })?;
+        checked.record_info(&mut solver);
+        Ok(solver)
+    }
+}
+
+impl<'a, T> BorrowedSolver<'a, T>
+where
+    T: FloatT,
+{
+    fn _new(
+        (m, n): (usize, usize),
+        settings: DefaultSettings<T>,
+        make_data: impl FnOnce(&DefaultSettings<T>) -> DefaultProblemData<'a, T>,
+    ) -> Result<Self, ClarabelError> {
         let mut timers = Timers::default();
-        let mut output;
+        let result;
         let info = DefaultInfo::<T>::new();
 
-        timeit! {timers => "setup"; {
+        #[cfg(feature = "profiling")]
+        timers.start_setup_profile();
+        timers.start_setup_timings(settings.timing);
+
+        timeit! {timers => "setup", setup; 'setup: {
+        profile_scope!(Setup);
 
         // user facing results go here.
-        let solution = DefaultSolution::<T>::new(A.n, A.m);
+        let solution = DefaultSolution::<T>::new(n, m);
 
         // presolve / chordal decomposition if needed,
         // then take an internal copy of the problem data
         let mut data;
         timeit!{timers => "presolve"; {
-            data = DefaultProblemData::<T>::new(P,q,A,b,cones,&settings);
+            data = make_data(&settings);
         }}
 
+        if settings.sense == OptimizationSense::Maximize {
+            data.negate_objective();
+        }
+
         let cones = CompositeCone::<T>::new(&data.cones);
         assert_eq!(cones.numel, data.m);
         let variables = DefaultVariables::<T>::new(data.n,data.m);
@@ -60,9 +195,22 @@ where
         // equilibrate problem data immediately on setup.
         // this prevents multiple equlibrations if solve!
         // is called more than once.
-        timeit!{timers => "equilibration"; {
+        timeit!{timers => "equilibration", equilibration; {
+            profile_scope!(Equilibration);
             data.equilibrate(&cones,&settings);
         }}
+        data.setup_A_transpose(&settings);
+
+        // check the memory budget before the KKT system and its
+        // factors, usually the largest allocations, are made
+        if let Some(budget) = settings.max_memory_bytes {
+            let estimate = Self::_memory_estimate(&data, &cones, &settings);
+            if estimate > budget {
+                let e = ClarabelError::MemoryLimitExceeded { estimate, budget };
+                result = Err(e);
+                break 'setup;
+            }
+        }
 
         let kktsystem;
         timeit!{timers => "kktinit"; {
@@ -74,36 +222,481 @@ where
         let step_lhs  = DefaultVariables::<T>::new(data.n,data.m);
         let prev_vars = DefaultVariables::<T>::new(data.n,data.m);
 
-        output = Self{data,variables,residuals,kktsystem,step_lhs,
-             step_rhs,prev_vars,info,solution,cones,settings,timers: None};
+        result = Ok(Self{data,variables,residuals,kktsystem,step_lhs,
+             step_rhs,prev_vars,info,solution,cones,settings,timers: None,
+             warm_start: None, poisoned: false});
 
         }} //end "setup" timer.
 
+        #[cfg(feature = "profiling")]
+        timers.stop_setup_profile();
+        timers.stop_setup_timings();
+
         //now that the timer is finished we can swap our
         //timer object into the solver structure
+        let mut output = result?;
         output.timers.replace(timers);
 
-        output
+        Ok(output)
+    }
+
+    // bytes used by a solver for the problem data, excluding the
+    // internal storage of the cones
+    fn _memory_estimate(
+        data: &DefaultProblemData<'_, T>,
+        cones: &CompositeCone<T>,
+        settings: &DefaultSettings<T>,
+    ) -> usize {
+        let (m, n) = (data.m, data.n);
+
+        // the variables, step directions and previous iterate, then
+        // the residuals and the user facing solution
+        let vectors = 4 * (n + 2 * m) + (3 * n + 2 * m) + (n + 2 * m);
+
+        data.memory_bytes()
+            + vectors * core::mem::size_of::<T>()
+            + DefaultKKTSystem::<T>::memory_estimate(data, cones, settings)
+    }
+
+    /// Time spent in the main phases of setup and of the most recent
+    /// solve, recorded when the [`timing`](DefaultSettings::timing)
+    /// setting is enabled.
+    pub fn timings(&self) -> SolveTimings {
+        self.timers
+            .as_ref()
+            .map_or_else(SolveTimings::default, |t| t.timings)
+    }
+
+    /// Reductions applied to the problem data at setup when
+    /// `presolve_reductions_enable` is set, or `None` if the problem
+    /// could not be reduced.
+    pub fn presolve_result(&self) -> Option<&PresolveResult<T>> {
+        self.data.reductions.as_ref()
+    }
+
+    /// Map between the constraint rows of the problem as supplied and
+    /// those of the problem solved internally, recording the rows removed
+    /// or dualized at setup.
+    pub fn row_mapping(&self) -> &RowMapping {
+        &self.data.row_mapping
+    }
+
+    /// Copy of the KKT system recorded at the iteration given by the
+    /// [`kkt_snapshot_iteration`](DefaultSettings::kkt_snapshot_iteration)
+    /// setting during the most recent solve, or `None` if disabled or
+    /// the solver terminated earlier.
+    pub fn kkt_snapshot(&self) -> Option<KktSnapshot<T>> {
+        self.kktsystem.snapshot.clone()
+    }
+
+    /// Supply the Hessian scaling block of cone `cone_index` used at the
+    /// first iteration of the next solve, e.g. as saved with
+    /// [`scaling_data`](Self::scaling_data) from a previous solve.  The
+    /// cone computes its own scaling from the second iteration onward.
+    ///
+    /// Cone indices and scaling data refer to the internal problem, i.e.
+    /// after any presolve reductions and equilibration.   The data is
+    /// the diagonal of the block for cones with diagonal scalings, and
+    /// otherwise the packed upper triangle of the block by columns.
+    /// Only the KKT matrix uses the supplied block; the cone's own
+    /// scaling is still updated and used elsewhere in the first iteration.
+    pub fn set_initial_scaling(
+        &mut self,
+        cone_index: usize,
+        scaling_data: &[T],
+    ) -> Result<(), ScalingError> {
+        self.cones.set_initial_Hs_block(cone_index, scaling_data)
+    }
+
+    /// The Hessian scaling block of cone `cone_index` at the final
+    /// iterate of the most recent solve, in the layout expected by
+    /// [`set_initial_scaling`](Self::set_initial_scaling).
+    pub fn scaling_data(&self, cone_index: usize) -> Result<Vec<T>, ScalingError> {
+        self.cones.get_Hs_block(cone_index)
+    }
+
+    /// Start the next solve from the point `(x,s,z)` rather than from the
+    /// default starting point, e.g. from the solution of a closely related
+    /// problem.   The vectors are in the layout of the [`solution`](Self::solution),
+    /// which follows the variables and constraint rows of the problem as
+    /// supplied.   A missing `s` is taken as `b - Ax`, and a missing `z`
+    /// as zero.
+    ///
+    /// Slacks and duals that are not strictly inside their cones are shifted
+    /// into the cone interiors when the solve starts.   Nonsymmetric cones
+    /// can not be shifted, so only `x` is used if the problem has any.  The
+    /// starting point is used by the next solve only, and not by any
+    /// re-solves made for the [`auto_recovery`](DefaultSettings::auto_recovery)
+    /// setting.
+    pub fn warm_start(
+        &mut self,
+        x: &[T],
+        s: Option<&[T]>,
+        z: Option<&[T]>,
+    ) -> Result<(), WarmStartError> {
+        let data = &self.data;
+        if data.dualization.is_some() {
+            return Err(WarmStartError::Dualized);
+        }
+        #[cfg(feature = "sdp")]
+        if data.chordal_info.is_some() {
+            return Err(WarmStartError::Decomposed);
+        }
+
+        let (n, m) = (self.solution.x.len(), self.solution.z.len());
+        for (what, v, expected) in [("x", Some(x), n), ("s", s, m), ("z", z, m)] {
+            let Some(v) = v else { continue };
+            if v.len() != expected {
+                return Err(WarmStartError::DimensionMismatch {
+                    what,
+                    expected,
+                    got: v.len(),
+                });
+            }
+            if let Some(index) = v.iter().position(|v| !v.is_finite()) {
+                return Err(WarmStartError::NonFiniteEntry { what, index });
+            }
+        }
+
+        let mut start = DefaultVariables::<T>::new(data.n, data.m);
+
+        // restrict to the internal problem, then apply the equilibration
+        match &data.reductions {
+            Some(reductions) => reductions.reduce_variables(&mut start.x, x),
+            None => {
+                start.x.copy_from(x);
+            }
+        }
+        start.x.hadamard(&data.equilibration.dinv);
+
+        match s {
+            Some(s) => {
+                data.row_mapping.restrict(&mut start.s, s);
+                start.s.hadamard(&data.equilibration.e);
+            }
+            None => {
+                start.s.copy_from(&data.b);
+                data.gemv_A(&mut start.s, &start.x, -T::one(), T::one());
+            }
+        }
+
+        if let Some(z) = z {
+            data.row_mapping.restrict(&mut start.z, z);
+            start.z.hadamard(&data.equilibration.einv);
+            start.z.scale(data.equilibration.c);
+        }
+
+        self.warm_start = Some(start);
+        Ok(())
+    }
+}
+
+// sorted copies of P and A, with the number of entries merged
+type SortedMatrices<T> = (CscMatrix<T>, CscMatrix<T>, usize);
+
+// findings of the setup checks that are used in the setup
+// or reported in the verbose output
+struct CheckedProblem<T> {
+    // sorted copies of P and A to be used in their place
+    sorted: Option<SortedMatrices<T>>,
+    // finite entries of q and b beyond the infinite bound threshold
+    huge_entries: usize,
+    // termination tolerances raised to the attainable floor
+    clamped_tolerances: Vec<&'static str>,
+}
+
+impl<T: FloatT> CheckedProblem<T> {
+    fn record(&self, data: &mut DefaultProblemData<'_, T>) {
+        data.input_merged_entries = self.sorted.as_ref().map(|(_, _, merged)| *merged);
+        data.huge_entries = self.huge_entries;
+    }
+
+    fn record_info(&self, solver: &mut BorrowedSolver<'_, T>) {
+        solver
+            .info
+            .record_tolerances(&solver.settings, &self.clamped_tolerances);
+    }
+}
+
+// check the problem data, cones and settings before any setup,
+// returning an error for the first inconsistency found
+fn check_problem<T: FloatT>(
+    P: &CscMatrix<T>,
+    q: &[T],
+    A: &CscMatrix<T>,
+    b: &[T],
+    cones: &[SupportedConeT<T>],
+    settings: &mut DefaultSettings<T>,
+) -> Result<CheckedProblem<T>, ClarabelError> {
+    check_dimensions(P, q, A, b, cones)?;
+    settings.validate().map_err(ClarabelError::BadSettings)?;
+    let huge_entries = check_infinite_entries(q, b, cones, settings)?;
+    let clamped_tolerances = check_tolerances(q, b, settings)?;
+    let sorted = sort_matrices(P, A, settings)?;
+    let P = sorted.as_ref().map_or(P, |(P, _, _)| P);
+    check_P_triangle(P, settings)?;
+    check_P_convexity(P, settings)?;
+    Ok(CheckedProblem {
+        sorted,
+        huge_entries,
+        clamped_tolerances,
+    })
+}
+
+// raise termination tolerances that cannot be met in the scalar type
+// to the smallest attainable value, returning their names, or return
+// an error for the first such tolerance under the strict policy.
+// Absolute tolerances scale with the largest entry of q and b,
+// ignoring absent bounds and other entries beyond the infinite
+// bound threshold
+fn check_tolerances<T: FloatT>(
+    q: &[T],
+    b: &[T],
+    settings: &mut DefaultSettings<T>,
+) -> Result<Vec<&'static str>, ClarabelError> {
+    let threshold = settings.infinite_bound_threshold;
+    let scale = q
+        .iter()
+        .chain(b)
+        .map(|v| v.abs())
+        .filter(|v| *v <= threshold)
+        .fold(T::one(), T::max);
+
+    let floor_rel = T::epsilon() * (10.).as_T();
+    let floor_abs = floor_rel * scale;
+    let policy = settings.tolerance_policy;
+
+    let mut clamped = vec![];
+    for (name, tol, absolute) in settings.tolerances_mut() {
+        let floor = if absolute { floor_abs } else { floor_rel };
+        if *tol >= floor {
+            continue;
+        }
+        if policy == TolerancePolicy::Strict {
+            return Err(ClarabelError::BadSettings(format!(
+                "{} = {:e} is below the smallest attainable value {:e}",
+                name, *tol, floor
+            )));
+        }
+        *tol = floor;
+        clamped.push(name);
+    }
+    Ok(clamped)
+}
+
+// reject infinite or NaN entries of q and b, other than absent bounds
+// in nonnegative cone rows, returning the number of other entries of
+// q and b that are beyond the infinite bound threshold in magnitude
+// and are kept in the problem
+fn check_infinite_entries<T: FloatT>(
+    q: &[T],
+    b: &[T],
+    cones: &[SupportedConeT<T>],
+    settings: &DefaultSettings<T>,
+) -> Result<usize, ClarabelError> {
+    let threshold = settings.infinite_bound_threshold;
+    let mut huge = 0;
+
+    if let Some(index) = q.iter().position(|v| !v.is_finite()) {
+        return Err(ClarabelError::NonFiniteEntry { what: "q", index });
+    }
+    huge += q.iter().filter(|v| v.abs() > threshold).count();
+
+    let mut index = 0;
+    for cone in cones {
+        let nonnegative = matches!(cone, SupportedConeT::NonnegativeConeT(_));
+        for &v in &b[index..index + cone.nvars()] {
+            let absent = nonnegative && is_absent_bound(v, threshold);
+            if absent && settings.presolve_enable {
+                // the row is removed
+            } else if !absent && !v.is_finite() {
+                return Err(ClarabelError::NonFiniteEntry { what: "b", index });
+            } else if v.abs() > threshold {
+                huge += 1;
+            }
+            index += 1;
+        }
+    }
+    Ok(huge)
+}
+
+// copies of P and A with sorted columns and repeated entries summed,
+// if either has unsorted or repeated entries, or an error if these
+// are not to be repaired.   P and A must be correctly formatted otherwise
+#[allow(non_snake_case)]
+fn sort_matrices<T: FloatT>(
+    P: &CscMatrix<T>,
+    A: &CscMatrix<T>,
+    settings: &DefaultSettings<T>,
+) -> Result<Option<SortedMatrices<T>>, ClarabelError> {
+    let P_check = P.check_canonical();
+    let A_check = A.check_canonical();
+    if P_check.is_ok() && A_check.is_ok() {
+        return Ok(None);
+    }
+    if !settings.input_sparse_canonicalize {
+        P_check.map_err(|e| ClarabelError::BadMatrixFormat("P", e))?;
+        A_check.map_err(|e| ClarabelError::BadMatrixFormat("A", e))?;
     }
+    let (mut P, mut A) = (P.clone(), A.clone());
+    let merged = P.canonicalize() + A.canonicalize();
+    Ok(Some((P, A, merged)))
 }
 
-fn _check_dimensions<T: FloatT>(
+// sanity check the format and dimensions of the problem data
+// and the cone parameters, independently of the settings
+pub(crate) fn check_dimensions<T: FloatT>(
     P: &CscMatrix<T>,
     q: &[T],
     A: &CscMatrix<T>,
     b: &[T],
-    cone_types: &[SupportedConeT<T>],
-) {
-    let m = b.len();
-    let n = q.len();
-    let p = cone_types.iter().fold(0, |acc, cone| acc + cone.nvars());
-
-    assert!(m == A.nrows(), "A and b incompatible dimensions.");
-    assert!(
-        p == m,
-        "Constraint dimensions inconsistent with size of cones."
-    );
-    assert!(n == A.ncols(), "A and q incompatible dimensions.");
-    assert!(n == P.ncols(), "P and q incompatible dimensions.");
-    assert!(P.is_square(), "P not square.");
+    cones: &[SupportedConeT<T>],
+) -> Result<(), ClarabelError> {
+    P.check_structure()
+        .map_err(|e| ClarabelError::BadMatrixFormat("P", e))?;
+    A.check_structure()
+        .map_err(|e| ClarabelError::BadMatrixFormat("A", e))?;
+
+    let (m, n) = (b.len(), q.len());
+    let p = check_cone_sizes(cones)?;
+    let mismatch = |what, expected, got| ClarabelError::DimensionMismatch {
+        what,
+        expected,
+        got,
+    };
+
+    if P.ncols() != n {
+        return Err(mismatch("columns of P", n, P.ncols()));
+    }
+    if P.nrows() != n {
+        return Err(mismatch("rows of P", n, P.nrows()));
+    }
+    if A.ncols() != n {
+        return Err(mismatch("columns of A", n, A.ncols()));
+    }
+    if A.nrows() != m {
+        return Err(mismatch("rows of A", m, A.nrows()));
+    }
+    if p != m {
+        return Err(mismatch("sum of cone dimensions", m, p));
+    }
+
+    for (index, cone) in cones.iter().enumerate() {
+        cone.validate()
+            .map_err(|reason| ClarabelError::BadConeSpecification { index, reason })?;
+    }
+    Ok(())
+}
+
+// the sum of the cone dimensions, checking that neither it nor
+// any of the other cone sizes computed during setup overflows
+fn check_cone_sizes<T>(cones: &[SupportedConeT<T>]) -> Result<usize, ClarabelError> {
+    let sizes = cones
+        .iter()
+        .enumerate()
+        .map(|(index, cone)| {
+            cone.checked_sizes()
+                .ok_or_else(|| ClarabelError::BadConeSpecification {
+                    index,
+                    reason: "cone dimension overflows usize".to_string(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let p = checked_sum(sizes.iter().map(|&(nvars, _)| nvars))
+        .ok_or(ClarabelError::DimensionOverflow("sum of cone dimensions"))?;
+    checked_sum(sizes.iter().map(|&(_, nHs)| nHs)).ok_or(ClarabelError::DimensionOverflow(
+        "total size of the cone Hs blocks",
+    ))?;
+    Ok(p)
+}
+
+// relative tolerance for a full P to be accepted as symmetric
+fn _P_symmetry_tol<T: FloatT>() -> T {
+    T::epsilon().sqrt()
+}
+
+// P with entries below the diagonal must be symmetric and is converted
+// to its upper triangle during setup, or is rejected if conversion
+// is disabled.   P must be square and correctly formatted
+#[allow(non_snake_case)]
+fn check_P_triangle<T: FloatT>(
+    P: &CscMatrix<T>,
+    settings: &DefaultSettings<T>,
+) -> Result<(), ClarabelError> {
+    if P.is_triu() {
+        return Ok(());
+    }
+    if settings.p_auto_triu {
+        return match P.first_asymmetric_entry(_P_symmetry_tol()) {
+            Some((row, col)) => Err(ClarabelError::PNotSymmetric { row, col }),
+            None => Ok(()),
+        };
+    }
+    let (row, col) = (0..P.n)
+        .find_map(|col| {
+            let rows = &P.rowval[P.colptr[col]..P.colptr[col + 1]];
+            rows.iter()
+                .filter(|&&row| row > col)
+                .min()
+                .map(|&row| (row, col))
+        })
+        .unwrap();
+    Err(ClarabelError::PNotUpperTriangular { row, col })
+}
+
+// absolute tolerance for negative curvature of P, relative
+// to the size of its largest entry
+fn _P_convexity_tol<T: FloatT>(P: &CscMatrix<T>) -> T {
+    T::epsilon().sqrt() * P.nzval.norm_inf()
+}
+
+// reject P with negative diagonal entries, then optionally check that
+// the upper triangle of P is positive semidefinite.   P must be square
+// and correctly formatted
+#[allow(non_snake_case)]
+fn check_P_convexity<T: FloatT>(
+    P: &CscMatrix<T>,
+    settings: &DefaultSettings<T>,
+) -> Result<(), ClarabelError> {
+    let tol = _P_convexity_tol(P);
+
+    for col in 0..P.n {
+        let rng = P.colptr[col]..P.colptr[col + 1];
+        let diag = zip(&P.rowval[rng.clone()], &P.nzval[rng]).find(|(&row, _)| row == col);
+        if let Some((_, &v)) = diag {
+            if v < -tol {
+                return Err(ClarabelError::NonConvexObjective { index: col });
+            }
+        }
+    }
+
+    if !settings.check_convexity || tol == T::zero() {
+        return Ok(());
+    }
+
+    // factor P + tol*I without regularization.   Every pivot is at
+    // least tol if P is positive semidefinite, so a negative pivot
+    // shows negative curvature beyond the tolerance
+    let triu = if P.is_triu() { P.clone() } else { P.to_triu() };
+    let I = CscMatrix::identity(P.n);
+    let (plan, mut M) = CscMatrix::add_scaled_symbolic(&triu, &I).unwrap();
+    CscMatrix::add_scaled_numeric(&plan, &mut M, T::one(), &triu, tol, &I).unwrap();
+
+    let opts = QDLDLSettingsBuilder::default()
+        .regularize_enable(false)
+        .build()
+        .unwrap();
+
+    // an exactly zero pivot stops the factorization, and can only
+    // occur at the boundary of the tolerance, so is accepted
+    let Ok(factors) = QDLDLFactorisation::new(&M, Some(opts)) else {
+        return Ok(());
+    };
+    match factors.D.iter().position(|&d| d < T::zero()) {
+        Some(k) => Err(ClarabelError::NonConvexObjective {
+            index: factors.perm[k],
+        }),
+        None => Ok(()),
+    }
 }
@@ -24,8 +24,8 @@ pub struct DefaultVariables<T> {
     pub κ: T,
 }
 
-impl<T: std::fmt::Display + std::fmt::Debug> std::fmt::Debug for DefaultVariables<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T: core::fmt::Display + core::fmt::Debug> core::fmt::Debug for DefaultVariables<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "x: {:?}\ns: {:?}\nz: {:?}\nτ: {:?}\nκ: {:?}\n",
@@ -49,11 +49,10 @@ where
     }
 }
 
-impl<T> Variables<T> for DefaultVariables<T>
+impl<'a, T> Variables<T, DefaultProblemData<'a, T>> for DefaultVariables<T>
 where
     T: FloatT,
 {
-    type D = DefaultProblemData<T>;
     type R = DefaultResiduals<T>;
     type C = CompositeCone<T>;
     type SE = DefaultSettings<T>;
@@ -176,6 +175,20 @@ where
         self.κ = T::one();
     }
 
+    fn warm_initialization(&mut self, cones: &mut CompositeCone<T>) {
+        // nonsymmetric cones can not be shifted into their interior,
+        // so only the primal variables are kept for those problems
+        if cones.is_symmetric() {
+            _shift_to_warm_interior(&mut self.s, cones, PrimalOrDualCone::PrimalCone);
+            _shift_to_warm_interior(&mut self.z, cones, PrimalOrDualCone::DualCone);
+        } else {
+            cones.unit_initialization(&mut self.z, &mut self.s);
+        }
+
+        self.τ = T::one();
+        self.κ = T::one();
+    }
+
     fn copy_from(&mut self, src: &Self) {
         self.x.copy_from(&src.x);
         self.s.copy_from(&src.s);
@@ -254,11 +267,35 @@ where
     }
 }
 
+// as _shift_to_cone_interior, but with a much smaller target margin
+// so that a warm start close to the cone boundary is not lost
+fn _shift_to_warm_interior<T>(z: &mut [T], cones: &mut CompositeCone<T>, pd: PrimalOrDualCone)
+where
+    T: FloatT,
+{
+    let (min_margin, pos_margin) = cones.margins(z, pd);
+    let target = T::max(
+        (1e-2).as_T(),
+        (pos_margin * (0.1).as_T()) / cones.degree().as_T(),
+    );
+
+    if min_margin <= T::zero() {
+        // two stages, as for the default start
+        cones.scaled_unit_shift(z, -min_margin, pd);
+        cones.scaled_unit_shift(z, target, pd);
+    } else if min_margin < target {
+        cones.scaled_unit_shift(z, target - min_margin, pd);
+    } else {
+        // force any elements in the zero cone to zero
+        cones.scaled_unit_shift(z, T::zero(), pd);
+    }
+}
+
 impl<T> DefaultVariables<T>
 where
     T: FloatT,
 {
-    pub(crate) fn unscale(&mut self, data: &DefaultProblemData<T>, is_infeasible: bool) {
+    pub(crate) fn unscale(&mut self, data: &DefaultProblemData<'_, T>, is_infeasible: bool) {
         // if we have an infeasible problem, normalize
         // using κ to get an infeasibility certificate.
         // Otherwise use τ to get an unscaled solution.
@@ -0,0 +1,53 @@
+use crate::algebra::*;
+
+// ---------------
+// Work vectors for the step computations of the default solver
+// ---------------
+
+// Named buffers for the KKT solves and the cone operations of each
+// step, sized once at setup so that the iterations of the main loop
+// make no allocations.
+
+pub(crate) struct Workspace<T> {
+    // solution of the variable part of the reduced KKT system
+    pub(crate) x1: Vec<T>,
+    pub(crate) z1: Vec<T>,
+
+    // solution of the constant part of the reduced KKT system
+    pub(crate) x2: Vec<T>,
+    pub(crate) z2: Vec<T>,
+
+    // right hand sides compatible with (x,z), used for
+    // assembling KKT solves and as general workspace
+    pub(crate) x: Vec<T>,
+    pub(crate) z: Vec<T>,
+
+    // the constant term c in the step equation HₛΔz + Δs = -c
+    pub(crate) Δs_const: Vec<T>,
+
+    // scratch for the cones' Hₛ products and Δs offsets
+    pub(crate) conic: Vec<T>,
+}
+
+impl<T> Workspace<T>
+where
+    T: FloatT,
+{
+    pub(crate) fn new(n: usize, m: usize) -> Self {
+        Self {
+            x1: vec![T::zero(); n],
+            z1: vec![T::zero(); m],
+            x2: vec![T::zero(); n],
+            z2: vec![T::zero(); m],
+            x: vec![T::zero(); n],
+            z: vec![T::zero(); m],
+            Δs_const: vec![T::zero(); m],
+            conic: vec![T::zero(); m],
+        }
+    }
+
+    // bytes allocated by `new` for the same dimensions
+    pub(crate) fn memory_estimate(n: usize, m: usize) -> usize {
+        (3 * n + 5 * m) * core::mem::size_of::<T>()
+    }
+}
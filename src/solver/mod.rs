@@ -31,8 +31,21 @@ pub use crate::solver::utils::infbounds::*;
 //allows declaration of cone constraints
 pub use crate::solver::core::cones::{SupportedConeT, SupportedConeT::*};
 
+//random interior points of cones, for testing
+#[cfg(feature = "rand")]
+pub use crate::solver::core::cones::random_cone_interior_point;
+
+//thresholds for multithreaded cone operations
+#[cfg(feature = "parallel")]
+pub use crate::solver::core::cones::{
+    default_parallel_cones_threshold, get_parallel_cones_threshold, set_parallel_cones_threshold,
+    PARALLEL_CONES_THRESHOLD_DEFAULT,
+};
+
 //user facing traits required to interact with solver
-pub use crate::solver::core::{IPSolver, SolverStatus};
+pub use crate::solver::core::{IPSolver, OpCounters, RefinementStats, ScalingStrategy, SolverStatus};
+pub use crate::solver::core::kktsolvers::KktSnapshot;
+pub use crate::solver::core::cones::ScalingError;
 
 //user facing traits required to define new implementatiions
 pub use crate::solver::core::traits;
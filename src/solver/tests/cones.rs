@@ -1,3 +1,5 @@
+#![allow(non_snake_case)]
+
 use crate::solver::core::cones::*;
 
 #[test]
@@ -26,3 +28,728 @@ fn numel_degree() {
         assert_eq!(sdpcone.degree(), 5);
     }
 }
+
+#[cfg(test)]
+fn check_scaling_roundtrip<C: Cone<f64>>(cone: &mut C, x: &[f64]) {
+    let mut y = x.to_vec();
+    cone.apply_W_inv(&mut y);
+    cone.apply_W(&mut y);
+    assert!(y.iter().zip(x).all(|(a, b)| (a - b).abs() < 1e-12));
+
+    let mut y = x.to_vec();
+    cone.apply_W(&mut y);
+    cone.apply_W_inv(&mut y);
+    assert!(y.iter().zip(x).all(|(a, b)| (a - b).abs() < 1e-12));
+}
+
+#[cfg(test)]
+fn check_scaling_point<C: Cone<f64>>(cone: &mut C, s: &[f64], z: &[f64]) {
+    use crate::solver::core::ScalingStrategy;
+
+    assert!(cone.update_scaling(s, z, 1.0, ScalingStrategy::PrimalDual));
+
+    // W*z = W⁻¹*s at the scaling point
+    let mut Wz = z.to_vec();
+    let mut Winvs = s.to_vec();
+    cone.apply_W(&mut Wz);
+    cone.apply_W_inv(&mut Winvs);
+    assert!(Wz.iter().zip(&Winvs).all(|(a, b)| (a - b).abs() < 1e-12));
+
+    check_scaling_roundtrip(cone, z);
+    let x: Vec<f64> = (0..s.len()).map(|i| 0.5 - i as f64).collect();
+    check_scaling_roundtrip(cone, &x);
+}
+
+#[test]
+fn scaling_W_nonnegative() {
+    let mut cone = NonnegativeCone::<f64>::new(3);
+    let s = [1.0, 2.0, 3.0];
+    let z = [3.0, 1.0, 0.5];
+    check_scaling_point(&mut cone, &s, &z);
+
+    let w = cone.scaling_factor_W().unwrap().to_vec();
+    let winv = cone.scaling_factor_W_inv().unwrap().to_vec();
+    for i in 0..3 {
+        assert!((w[i] - (s[i] / z[i]).sqrt()).abs() < 1e-12);
+        assert!((w[i] * winv[i] - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn scaling_W_secondorder() {
+    let mut cone = SecondOrderCone::<f64>::new(4);
+    let s = [3.0, 1.0, 0.5, -1.0];
+    let z = [2.0, 0.3, -0.2, 0.5];
+    check_scaling_point(&mut cone, &s, &z);
+    assert!(cone.scaling_factor_W().is_none());
+    assert!(cone.scaling_factor_W_inv().is_none());
+}
+
+#[cfg(feature = "sdp")]
+#[test]
+fn scaling_W_psdtriangle() {
+    let mut cone = PSDTriangleCone::<f64>::new(2);
+    let s = [2.0, 0.5, 1.0];
+    let z = [1.0, -0.3, 3.0];
+    check_scaling_point(&mut cone, &s, &z);
+    assert!(cone.scaling_factor_W().is_none());
+}
+
+#[test]
+fn scaling_W_without_nt_scaling() {
+    // no scaling for the zero cone or nonsymmetric cones
+    let x = [0.5, -1.0, 2.0];
+
+    let mut zcone = ZeroCone::<f64>::new(3);
+    check_scaling_roundtrip(&mut zcone, &x);
+    assert!(zcone.scaling_factor_W().is_none());
+
+    let mut expcone = ExponentialCone::<f64>::new();
+    check_scaling_roundtrip(&mut expcone, &x);
+    assert!(expcone.scaling_factor_W().is_none());
+
+    let mut powcone = PowerCone::<f64>::new(0.5);
+    check_scaling_roundtrip(&mut powcone, &x);
+    assert!(powcone.scaling_factor_W().is_none());
+
+    let mut genpowcone = GenPowerCone::<f64>::new(vec![0.5, 0.5], 1);
+    check_scaling_roundtrip(&mut genpowcone, &x);
+    assert!(genpowcone.scaling_factor_W_inv().is_none());
+}
+
+#[test]
+fn scaling_W_composite() {
+    use crate::solver::SupportedConeT::*;
+
+    let mut cone =
+        CompositeCone::<f64>::new(&[ZeroConeT(1), NonnegativeConeT(2), SecondOrderConeT(3)]);
+    let s = [0.0, 1.0, 2.0, 2.0, 0.5, -1.0];
+    let z = [0.0, 3.0, 0.5, 3.0, 0.3, 0.2];
+    check_scaling_point(&mut cone, &s, &z);
+    assert!(cone.scaling_factor_W().is_none());
+}
+
+#[test]
+fn composite_cone_ranges() {
+    use crate::solver::SupportedConeT::*;
+
+    let cone = CompositeCone::<f64>::new(&[NonnegativeConeT(2), ZeroConeT(3), SecondOrderConeT(4)]);
+    let starts: Vec<usize> = cone.rng_cones.iter().map(|r| r.start).collect();
+    assert_eq!(starts, [0, 2, 5]);
+    assert_eq!(cone.rng_cones, [0..2, 2..5, 5..9]);
+    assert_eq!(cone.numel(), 9);
+
+    // diagonal blocks for the first two cones, and
+    // a packed triangle for the second order cone
+    assert_eq!(cone.rng_blocks, [0..2, 2..5, 5..15]);
+}
+
+#[test]
+fn composite_cone_type_summary() {
+    use crate::solver::SupportedConeT::*;
+
+    let cone = CompositeCone::<f64>::new(&[
+        ZeroConeT(5),
+        SecondOrderConeT(10),
+        ExponentialConeT(),
+        NonnegativeConeT(4),
+        ZeroConeT(5),
+        SecondOrderConeT(3),
+        PowerConeT(0.3),
+        ZeroConeT(5),
+        SecondOrderConeT(10),
+    ]);
+
+    assert_eq!(
+        cone.type_count_sorted(),
+        vec![
+            ("ExponentialCone", 1),
+            ("NonnegativeCone", 1),
+            ("PowerCone", 1),
+            ("SecondOrderCone", 3),
+            ("ZeroCone", 3),
+        ]
+    );
+    assert_eq!(
+        cone.cone_type_summary(),
+        "1×ExponentialCone, 1×NonnegativeCone(dim=4), 1×PowerCone, \
+         1×SecondOrderCone(dim=3), 2×SecondOrderCone(dim=10), 3×ZeroCone(dim=5)"
+    );
+
+    let empty = CompositeCone::<f64>::new(&[]);
+    assert!(empty.type_count_sorted().is_empty());
+    assert_eq!(empty.cone_type_summary(), "");
+}
+
+#[test]
+fn scaling_one_sided_nonnegative() {
+    use crate::solver::core::ScalingStrategy;
+
+    let mut cone = NonnegativeCone::<f64>::new(3);
+    let s = [1.0, 2.0, 3.0];
+    let z = [3.0, 1.0, 0.5];
+    let μ = 2.0;
+    let mut Hs = [0.0; 3];
+
+    // Hs = diag(s)²/μ
+    assert!(cone.update_scaling(&s, &z, μ, ScalingStrategy::PrimalOnly));
+    cone.get_Hs(&mut Hs);
+    for i in 0..3 {
+        assert!((Hs[i] - s[i] * s[i] / μ).abs() < 1e-12);
+    }
+
+    // Hs = μ*diag(z)⁻²
+    assert!(cone.update_scaling(&s, &z, μ, ScalingStrategy::DualOnly));
+    cone.get_Hs(&mut Hs);
+    for i in 0..3 {
+        assert!((Hs[i] - μ / (z[i] * z[i])).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn scaling_one_sided_secondorder() {
+    use crate::solver::core::ScalingStrategy;
+
+    // dense and sparse expanded forms
+    for dim in [4, 6] {
+        let mut cone = SecondOrderCone::<f64>::new(dim);
+        let s: Vec<f64> = (0..dim)
+            .map(|i| if i == 0 { 4.0 } else { 0.5 - i as f64 * 0.3 })
+            .collect();
+        let z: Vec<f64> = (0..dim)
+            .map(|i| if i == 0 { 3.0 } else { 0.2 * i as f64 - 0.4 })
+            .collect();
+        let μ = 0.5;
+        let mut y = vec![0.0; dim];
+        let mut work = vec![0.0; dim];
+
+        // with gradient g = -Jx/(xᵀJx) of the barrier -½log(xᵀJx),
+        // we expect ∇²f(x)x = -g, so that Hs*(Js/sᵀJs) = s/μ and
+        // Hs*z = μJz/(zᵀJz) for primal and dual scaling respectively
+        let Jscale = |x: &[f64]| -> Vec<f64> {
+            let res = x[0] * x[0] - x[1..].iter().map(|v| v * v).sum::<f64>();
+            x.iter()
+                .enumerate()
+                .map(|(i, &v)| if i == 0 { v / res } else { -v / res })
+                .collect()
+        };
+
+        assert!(cone.update_scaling(&s, &z, μ, ScalingStrategy::PrimalOnly));
+        cone.mul_Hs(&mut y, &Jscale(&s), &mut work);
+        assert!(y.iter().zip(&s).all(|(a, b)| (a - b / μ).abs() < 1e-10));
+
+        assert!(cone.update_scaling(&s, &z, μ, ScalingStrategy::DualOnly));
+        cone.mul_Hs(&mut y, &z, &mut work);
+        let g = Jscale(&z);
+        assert!(y.iter().zip(&g).all(|(a, b)| (a - μ * b).abs() < 1e-10));
+
+        // W is still the NT scaling
+        let mut Wz = z.clone();
+        let mut Winvs = s.clone();
+        cone.apply_W(&mut Wz);
+        cone.apply_W_inv(&mut Winvs);
+        assert!(Wz.iter().zip(&Winvs).all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+}
+
+// deterministic pseudo-random values in [0,1) for test data
+#[cfg(test)]
+fn test_rand(state: &mut u64) -> f64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    (*state >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[test]
+fn verify_scaling_consistency() {
+    use crate::solver::SupportedConeT::*;
+
+    // includes dense and sparse expanded second order cones
+    let types = [
+        ZeroConeT(2),
+        NonnegativeConeT(3),
+        SecondOrderConeT(3),
+        SecondOrderConeT(7),
+        ExponentialConeT(),
+        PowerConeT(0.3),
+        GenPowerConeT(vec![0.2, 0.3, 0.5], 2),
+    ];
+    let mut state = 1;
+
+    for _ in 0..10 {
+        let mut cone = CompositeCone::<f64>::new(&types);
+        let n = cone.numel();
+
+        // random perturbations of the central point
+        let (mut s, mut z) = (vec![0.0; n], vec![0.0; n]);
+        cone.unit_initialization(&mut z, &mut s);
+        for v in s.iter_mut().chain(z.iter_mut()) {
+            *v += 0.2 * (test_rand(&mut state) - 0.5);
+        }
+
+        assert!(cone.verify_scaling_consistency(&s, &z, 1e-10).is_ok());
+    }
+
+    // points outside the cone are reported by index
+    let mut cone = CompositeCone::<f64>::new(&types);
+    let n = cone.numel();
+    let (mut s, mut z) = (vec![0.0; n], vec![0.0; n]);
+    cone.unit_initialization(&mut z, &mut s);
+    s[5] = 0.0;
+    assert!(matches!(
+        cone.verify_scaling_consistency(&s, &z, 1e-10),
+        Err(ScalingError::UpdateFailed { index: 2, .. })
+    ));
+}
+
+#[test]
+fn is_in_cone_interior() {
+    use crate::solver::SupportedConeT::*;
+
+    let types = [
+        ZeroConeT(2),
+        NonnegativeConeT(3),
+        SecondOrderConeT(3),
+        ExponentialConeT(),
+        PowerConeT(0.3),
+        GenPowerConeT(vec![0.2, 0.3, 0.5], 2),
+    ];
+    let mut cone = CompositeCone::<f64>::new(&types);
+    let n = cone.numel();
+
+    // the unit initialization is interior, except for the zero cone
+    let (mut s, mut z) = (vec![0.0; n], vec![0.0; n]);
+    cone.unit_initialization(&mut z, &mut s);
+    assert!(cone.is_in_cone_interior(&s));
+
+    s[0] = 1.0;
+    assert!(!cone.is_in_cone_interior(&s));
+    s[0] = 0.0;
+    s[5] = 0.0;
+    assert!(!cone.is_in_cone_interior(&s));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_interior_points() {
+    use crate::solver::SupportedConeT::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let types = vec![
+        ZeroConeT(2),
+        NonnegativeConeT(3),
+        SecondOrderConeT(2),
+        SecondOrderConeT(5),
+        ExponentialConeT(),
+        PowerConeT(0.3),
+        PowerConeT(0.9),
+        GenPowerConeT(vec![0.2, 0.3, 0.5], 3),
+    ];
+    #[cfg(feature = "sdp")]
+    let types = [types, vec![PSDTriangleConeT(4)]].concat();
+
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    for t in types.iter() {
+        let mut cone = make_cone(t);
+        for _ in 0..100 {
+            let s = random_cone_interior_point(t, &mut rng);
+            assert_eq!(s.len(), cone.numel());
+            assert!(cone.is_in_cone_interior(&s));
+        }
+    }
+
+    // and for the composite cone
+    let mut cone = CompositeCone::<f64>::new(&types);
+    let s: Vec<f64> = types
+        .iter()
+        .flat_map(|t| random_cone_interior_point(t, &mut rng))
+        .collect();
+    assert!(cone.is_in_cone_interior(&s));
+}
+
+// second order cone operations at random interior points, which
+// with the "blocked-kernels" feature exercise the lane-blocked kernels across
+// dimensions with and without remainders after full blocks
+#[cfg(feature = "rand")]
+#[test]
+fn random_secondorder_operations() {
+    use crate::algebra::*;
+    use crate::solver::{core::ScalingStrategy, CoreSettings, SupportedConeT::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(4321);
+    let settings = CoreSettings::<f64>::default();
+
+    for dim in [2, 3, 8, 9, 17, 64, 250] {
+        let t = SecondOrderConeT(dim);
+        for _ in 0..20 {
+            let s = random_cone_interior_point(&t, &mut rng);
+            let z = random_cone_interior_point(&t, &mut rng);
+
+            // Hs products agree with the dense Hs
+            let mut cone = CompositeCone::<f64>::new(std::slice::from_ref(&t));
+            assert!(cone.verify_scaling_consistency(&s, &z, 1e-10).is_ok());
+
+            // W*z = W⁻¹*s at the scaling point
+            let mut cone = SecondOrderCone::<f64>::new(dim);
+            assert!(cone.update_scaling(&s, &z, 1.0, ScalingStrategy::PrimalDual));
+            let (mut Wz, mut Winvs) = (z.clone(), s.clone());
+            cone.apply_W(&mut Wz);
+            cone.apply_W_inv(&mut Winvs);
+            let scale = Wz.norm_inf();
+            assert!(Wz.norm_inf_diff(&Winvs) <= 1e-12 * scale);
+
+            // a step toward a point outside the cone stops at the boundary
+            let dz: Vec<f64> = z
+                .iter()
+                .enumerate()
+                .map(|(i, &zi)| if i == 0 { -2. * zi } else { zi })
+                .collect();
+            let (αz, _) = cone.step_length(&dz, &dz, &z, &s, &settings, 1e3);
+            let mut x = z.clone();
+            x.axpby(αz, &dz, 1.);
+            let res = x[0] * x[0] - x[1..].sumsq();
+            assert!(f64::abs(res) <= 1e-10 * z.sumsq());
+        }
+    }
+}
+
+// Timing of the second order cone operations in each interior point
+// iteration for a single large cone.   Compare runs with and without
+// the "blocked-kernels" feature, e.g.
+//
+//   cargo test --release --features rand --lib bench_secondorder -- --ignored --nocapture
+//   cargo test --release --features rand,blocked-kernels --lib bench_secondorder -- --ignored --nocapture
+#[cfg(feature = "rand")]
+#[test]
+#[ignore]
+fn bench_secondorder_operations() {
+    use crate::solver::{core::ScalingStrategy, CoreSettings, SupportedConeT::*};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::time::Instant;
+
+    let dim = 10_000;
+    let repeats = 2000;
+    let t = SecondOrderConeT(dim);
+    let mut rng = StdRng::seed_from_u64(1);
+    let s = random_cone_interior_point(&t, &mut rng);
+    let z = random_cone_interior_point(&t, &mut rng);
+    let ds = random_cone_interior_point(&t, &mut rng);
+    let dz = random_cone_interior_point(&t, &mut rng);
+    let settings = CoreSettings::<f64>::default();
+
+    let mut cone = SecondOrderCone::<f64>::new(dim);
+    let (mut y, mut work) = (vec![0.0; dim], vec![0.0; dim]);
+
+    let start = Instant::now();
+    for _ in 0..repeats {
+        cone.update_scaling(&s, &z, 1.0, ScalingStrategy::PrimalDual);
+        cone.mul_Hs(&mut y, &dz, &mut work);
+        cone.Δs_from_Δz_offset(&mut y, &ds, &mut work, &z);
+        cone.step_length(&dz, &ds, &z, &s, &settings, 1.0);
+        cone.compute_barrier(&z, &s, &dz, &ds, 0.5);
+    }
+    let time = start.elapsed().as_secs_f64() / repeats as f64;
+    println!(
+        "second order cone of dimension {}: {:.3} ms per iteration",
+        dim,
+        time * 1e3
+    );
+}
+
+// Timing of the cone operations in each interior point iteration
+// for many exponential or power cones, e.g.
+//
+//   cargo test --release --lib bench_nonsymmetric -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_nonsymmetric_operations() {
+    use crate::solver::{core::ScalingStrategy, CoreSettings, SupportedConeT::*};
+    use std::time::Instant;
+
+    let ncones = 50_000;
+    let repeats = 100;
+    for (name, t) in [
+        ("exponential", ExponentialConeT()),
+        ("power", PowerConeT(0.3)),
+    ] {
+        let types = vec![t; ncones];
+        let mut cone = CompositeCone::<f64>::new(&types);
+        let n = cone.numel();
+        let settings = CoreSettings::<f64>::default();
+
+        // random perturbations of the central point and directions
+        let mut state = 1;
+        let (mut s, mut z) = (vec![0.0; n], vec![0.0; n]);
+        cone.unit_initialization(&mut z, &mut s);
+        for v in s.iter_mut().chain(z.iter_mut()) {
+            *v += 0.2 * (test_rand(&mut state) - 0.5);
+        }
+        let mut ds: Vec<f64> = (0..n).map(|_| test_rand(&mut state) - 0.5).collect();
+        let mut dz: Vec<f64> = (0..n).map(|_| test_rand(&mut state) - 0.5).collect();
+        let (mut y, mut work) = (vec![0.0; n], vec![0.0; n]);
+
+        let start = Instant::now();
+        for _ in 0..repeats {
+            cone.update_scaling(&s, &z, 1.0, ScalingStrategy::PrimalDual);
+            cone.mul_Hs(&mut y, &dz, &mut work);
+            cone.combined_ds_shift(&mut y, &mut dz, &mut ds, 0.1);
+            cone.step_length(&dz, &ds, &z, &s, &settings, 1.0);
+            cone.compute_barrier(&z, &s, &dz, &ds, 0.5);
+        }
+        let time = start.elapsed().as_secs_f64() / repeats as f64;
+        println!(
+            "{} {} cones: {:.3} ms per iteration",
+            ncones,
+            name,
+            time * 1e3
+        );
+    }
+}
+
+// The dual gradient, Hessian and Hessian factorization of the 3D
+// nonsymmetric cones are computed once per iteration in update_scaling
+// and reused by combined_ds_shift, with a single further dual
+// evaluation for each backtracking trial and barrier evaluation
+#[cfg(test)]
+fn check_cached_evaluations<C: Cone<f64>>(mut cone: C, counts: impl Fn(&C) -> (usize, usize)) {
+    use crate::solver::{core::ScalingStrategy, CoreSettings};
+    let settings = CoreSettings::<f64>::default();
+
+    let (mut s, mut z) = ([0.0; 3], [0.0; 3]);
+    cone.unit_initialization(&mut z, &mut s);
+    cone.update_scaling(&s, &z, 1.0, ScalingStrategy::PrimalDual);
+    assert_eq!(counts(&cone), (1, 1));
+
+    let (mut dz, mut ds, mut shift) = ([0.1, 0.2, -0.1], [0.2, -0.1, 0.1], [0.0; 3]);
+    cone.combined_ds_shift(&mut shift, &mut dz, &mut ds, 0.1);
+    assert_eq!(counts(&cone), (1, 1));
+
+    // z - 2αz is dual feasible only once α < 1/2
+    let dz = z.map(|zi| -2.0 * zi);
+    let step = settings.linesearch_backtrack_step;
+    let trials = std::iter::successors(Some(1.0), |α| Some(α * step))
+        .take_while(|α| *α >= 0.5)
+        .count()
+        + 1;
+    cone.step_length(&dz, &[0.0; 3], &z, &s, &settings, 1.0);
+    assert_eq!(counts(&cone), (1 + trials, 1));
+
+    cone.compute_barrier(&z, &s, &dz, &[0.0; 3], 0.25);
+    assert_eq!(counts(&cone), (2 + trials, 1));
+
+    // the next scaling update evaluates everything again
+    cone.update_scaling(&s, &z, 1.0, ScalingStrategy::PrimalDual);
+    assert_eq!(counts(&cone), (3 + trials, 2));
+}
+
+#[test]
+fn nonsymmetric_cached_evaluations() {
+    check_cached_evaluations(ExponentialCone::<f64>::new(), |c| {
+        let counters = &c.counters;
+        (counters.dual_evals.get(), counters.factorizations.get())
+    });
+    check_cached_evaluations(PowerCone::<f64>::new(0.3), |c| {
+        let counters = &c.counters;
+        (counters.dual_evals.get(), counters.factorizations.get())
+    });
+}
+
+// min ½‖x‖² + qᵀx s.t. x[i] = 1 for i < m and x ≥ 0, with
+// many equality constraints in a single zero cone
+#[cfg(test)]
+fn equality_heavy_solver(n: usize, m: usize) -> crate::solver::DefaultSolver<f64> {
+    use crate::algebra::*;
+    use crate::solver::{DefaultSettingsBuilder, DefaultSolver, SupportedConeT::*};
+
+    let P = CscMatrix::identity(n);
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+    let rows: Vec<bool> = (0..n).map(|i| i < m).collect();
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&P.select_rows(&rows), &I);
+    let b = [vec![1.; m], vec![0.; n]].concat();
+    let cones = [ZeroConeT(m), NonnegativeConeT(n)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap()
+}
+
+#[test]
+fn constant_scaling_updated_once() {
+    use crate::solver::{IPSolver, SolverStatus};
+
+    let zero_cone_updates = |solver: &crate::solver::DefaultSolver<f64>| {
+        solver
+            .cones
+            .iter()
+            .map(|cone| match cone {
+                SupportedCone::ZeroCone(c) => c.scaling_updates,
+                _ => 0,
+            })
+            .sum::<usize>()
+    };
+
+    let mut solver = equality_heavy_solver(50, 30);
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.iterations > 1);
+    assert_eq!(zero_cone_updates(&solver), 1);
+
+    // once more for each new solve
+    let x = solver.solution.x.clone();
+    solver.solve();
+    assert_eq!(zero_cone_updates(&solver), 2);
+    assert_eq!(solver.solution.x, x);
+}
+
+// Timing of a solve with many equality constraints, e.g.
+//
+//   cargo test --release --lib bench_equality_heavy -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_equality_heavy() {
+    use crate::solver::IPSolver;
+
+    let mut solver = equality_heavy_solver(400_000, 300_000);
+    solver.solve();
+    let solution = &solver.solution;
+    println!(
+        "{} iterations, {:.3} s, {:.3} ms per iteration",
+        solution.iterations,
+        solution.solve_time,
+        solution.solve_time * 1e3 / solution.iterations as f64
+    );
+}
+
+#[test]
+fn nonnegative_cone_split_matches_single() {
+    // elementwise operations give identical results whether an
+    // orthant is given as one cone or as many one dimensional cones
+    use crate::solver::{core::ScalingStrategy, SupportedConeT::*};
+
+    let n = 50;
+    let single_types = vec![NonnegativeConeT(n)];
+    let split_types = vec![NonnegativeConeT(1); n];
+    let mut single = CompositeCone::<f64>::new(&single_types);
+    let mut split = CompositeCone::<f64>::new(&split_types);
+
+    let mut state = 3;
+    let s: Vec<f64> = (0..n).map(|_| 0.5 + test_rand(&mut state)).collect();
+    let z: Vec<f64> = (0..n).map(|_| 0.5 + test_rand(&mut state)).collect();
+    let x: Vec<f64> = (0..n).map(|_| test_rand(&mut state) - 0.5).collect();
+
+    let mut outputs = Vec::new();
+    for cone in [&mut single, &mut split] {
+        assert!(cone.update_scaling(&s, &z, 0.7, ScalingStrategy::PrimalDual));
+        let (mut Hs, mut y, mut ds, mut work) =
+            (vec![0.; n], vec![0.; n], vec![0.; n], vec![0.; n]);
+        cone.get_Hs(&mut Hs);
+        cone.mul_Hs(&mut y, &x, &mut work);
+        cone.affine_ds(&mut ds, &s);
+        outputs.push((Hs, y, ds));
+    }
+    assert_eq!(outputs[0], outputs[1]);
+}
+
+#[test]
+fn test_composite_binding_cone() {
+    use crate::solver::{CoreSettings, SupportedConeT::*};
+
+    let types = [ZeroConeT(1), NonnegativeConeT(2), SecondOrderConeT(3)];
+    let mut cone = CompositeCone::<f64>::new(&types);
+    let settings = CoreSettings::<f64>::default();
+    assert_eq!(cone.binding_cone(), None);
+
+    let z = [0., 1., 1., 2., 0., 0.];
+    let s = [0., 1., 1., 2., 0., 0.];
+    let zero = [0.; 6];
+
+    // the second order cone reaches its boundary first
+    let dz = [1., -2., 0., -8., 0., 0.];
+    let (αz, αs) = cone.step_length(&dz, &zero, &z, &s, &settings, 1.0);
+    assert_eq!((αz, αs), (0.25, 0.25));
+    assert_eq!(cone.binding_cone(), Some(2));
+
+    // then the nonnegative cone
+    let dz = [1., -4., 0., -4., 0., 0.];
+    let (αz, _) = cone.step_length(&dz, &zero, &z, &s, &settings, 1.0);
+    assert_eq!(αz, 0.25);
+    assert_eq!(cone.binding_cone(), Some(1));
+
+    // no cone limits the step below its maximum
+    cone.step_length(&dz, &zero, &z, &s, &settings, 0.1);
+    assert_eq!(cone.binding_cone(), None);
+}
+
+// Setup and per-iteration cone operation times for a problem with
+// 100k one dimensional nonnegative cones.   Run with
+//
+//   cargo test --release --lib bench_many_cones -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_many_cones() {
+    use crate::solver::{core::ScalingStrategy, CoreSettings, SupportedConeT::*};
+    use std::time::Instant;
+
+    let types = vec![NonnegativeConeT(1); 100_000];
+    let start = Instant::now();
+    let mut cone = CompositeCone::<f64>::new(&types);
+    println!("setup: {:8.3} ms", start.elapsed().as_secs_f64() * 1e3);
+
+    let n = cone.numel();
+    let settings = CoreSettings::<f64>::default();
+    let mut state = 1;
+    let s: Vec<f64> = (0..n).map(|_| 0.5 + test_rand(&mut state)).collect();
+    let z: Vec<f64> = (0..n).map(|_| 0.5 + test_rand(&mut state)).collect();
+    let ds: Vec<f64> = (0..n).map(|_| test_rand(&mut state) - 0.5).collect();
+    let dz: Vec<f64> = (0..n).map(|_| test_rand(&mut state) - 0.5).collect();
+    let (mut Hs, mut y, mut work) = (vec![0.; n], vec![0.; n], vec![0.; n]);
+
+    let reps = 100;
+    let start = Instant::now();
+    for _ in 0..reps {
+        cone.update_scaling(&s, &z, 1.0, ScalingStrategy::PrimalDual);
+        cone.get_Hs(&mut Hs);
+        cone.mul_Hs(&mut y, &dz, &mut work);
+        cone.affine_ds(&mut y, &s);
+        cone.step_length(&dz, &ds, &z, &s, &settings, 1.0);
+        cone.compute_barrier(&z, &s, &dz, &ds, 0.5);
+    }
+    println!(
+        "iteration: {:8.3} ms",
+        start.elapsed().as_secs_f64() * 1e3 / reps as f64
+    );
+
+    let start = Instant::now();
+    drop(cone);
+    println!("drop: {:8.3} ms", start.elapsed().as_secs_f64() * 1e3);
+}
+
+#[test]
+fn scaling_fallback_differs() {
+    use crate::solver::core::ScalingStrategy::*;
+    use crate::solver::SupportedConeT::*;
+
+    // symmetric cones use NT scaling for both PrimalDual and Dual
+    let cone = CompositeCone::<f64>::new(&[ZeroConeT(2), NonnegativeConeT(3)]);
+    assert!(!cone.scaling_differs(PrimalDual, Dual));
+    assert!(cone.scaling_differs(Dual, PrimalOnly));
+
+    // the zero cone has no scaling
+    let cone = CompositeCone::<f64>::new(&[ZeroConeT(2)]);
+    assert!(!cone.scaling_differs(PrimalDual, DualOnly));
+
+    // nonsymmetric cones use dual scaling for the one-sided strategies
+    let cone = CompositeCone::<f64>::new(&[ExponentialConeT()]);
+    assert!(cone.scaling_differs(PrimalDual, Dual));
+    assert!(!cone.scaling_differs(Dual, PrimalOnly));
+
+    // unless combined with a symmetric cone that uses them
+    let cone = CompositeCone::<f64>::new(&[ExponentialConeT(), NonnegativeConeT(2)]);
+    assert!(cone.scaling_differs(Dual, PrimalOnly));
+}
@@ -1 +1,2 @@
 mod cones;
+mod panics;
@@ -0,0 +1,139 @@
+#![allow(non_snake_case)]
+
+use crate::algebra::*;
+use crate::solver::core::{
+    cones::CompositeCone,
+    kktsolvers::{KKTSolution, KKTSolver, KktSnapshot},
+    traits::Settings,
+    CoreSettings, IPSolver, OpCounters, RefinementStats, SolverStatus,
+};
+use crate::solver::{DefaultSettingsBuilder, DefaultSolver, SupportedConeT::*};
+use std::borrow::Cow;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// A KKT solver that wraps the solver's own, failing every solve
+// in the first solve attempt and panicking in the second, i.e.
+// in the first automatic recovery attempt
+struct FailingKKTSolver {
+    inner: Box<dyn KKTSolver<f64> + Send + Sync>,
+    attempt: u32,
+}
+
+impl FailingKKTSolver {
+    fn fail(&self) -> bool {
+        if self.attempt >= 2 {
+            panic!("injected KKT panic");
+        }
+        false
+    }
+}
+
+impl KKTSolver<f64> for FailingKKTSolver {
+    fn update(&mut self, cones: &CompositeCone<f64>, settings: &CoreSettings<f64>) -> bool {
+        self.inner.update(cones, settings)
+    }
+    fn setrhs(&mut self, x: &[f64], z: &[f64]) {
+        self.inner.setrhs(x, z)
+    }
+    fn solve(
+        &mut self,
+        x: Option<&mut [f64]>,
+        z: Option<&mut [f64]>,
+        settings: &CoreSettings<f64>,
+    ) -> bool {
+        self.inner.solve(x, z, settings) && self.fail()
+    }
+    fn solve_multiple(
+        &mut self,
+        rhs: &[(&[f64], &[f64])],
+        lhs: &mut [KKTSolution<f64>],
+        settings: &CoreSettings<f64>,
+    ) -> bool {
+        self.inner.solve_multiple(rhs, lhs, settings) && self.fail()
+    }
+    fn update_P(&mut self, P: &CscMatrix<f64, Cow<'_, [usize]>>) {
+        self.inner.update_P(P)
+    }
+    fn update_A(&mut self, A: &CscMatrix<f64, Cow<'_, [usize]>>) {
+        self.inner.update_A(A)
+    }
+    fn counters_mut(&mut self) -> &mut OpCounters {
+        self.inner.counters_mut()
+    }
+    fn refinement_stats_mut(&mut self) -> &mut RefinementStats<f64> {
+        self.inner.refinement_stats_mut()
+    }
+    fn begin_iteration(&mut self, iter: u32) {
+        if iter == 0 {
+            self.attempt += 1;
+        }
+        self.inner.begin_iteration(iter)
+    }
+    fn snapshot(&self) -> KktSnapshot<f64> {
+        self.inner.snapshot()
+    }
+    fn snapshot_rhs_lhs(&self, col: usize) -> (Vec<f64>, Vec<f64>) {
+        self.inner.snapshot_rhs_lhs(col)
+    }
+}
+
+// min ½‖x‖² - x₀ s.t. x ≤ 2
+fn new_solver(catch_panics: bool) -> DefaultSolver<f64> {
+    let P = CscMatrix::identity(2);
+    let q = [-1., 0.];
+    let A = CscMatrix::identity(2);
+    let b = [2., 2.];
+    let cones = [NonnegativeConeT(2)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(false)
+        .auto_recovery(true)
+        .catch_panics(catch_panics)
+        .build()
+        .unwrap();
+    DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap()
+}
+
+// a solver whose KKT solves fail and then panic during automatic
+// recovery, wrapping the KKT solver of a second identical solver
+fn failing_solver(catch_panics: bool) -> DefaultSolver<f64> {
+    let mut solver = new_solver(catch_panics);
+    let inner = new_solver(catch_panics).kktsystem.kktsolver;
+    solver.kktsystem.kktsolver = Box::new(FailingKKTSolver { inner, attempt: 0 });
+    solver
+}
+
+// the settings changed by the recovery profiles
+fn recovery_settings(solver: &DefaultSolver<f64>) -> (bool, f64, f64) {
+    let settings = solver.settings.core();
+    (
+        settings.static_regularization_enable,
+        settings.static_regularization_constant,
+        settings.max_step_fraction,
+    )
+}
+
+#[test]
+fn test_recovery_panic_restores_settings() {
+    let mut solver = failing_solver(true);
+    let user_settings = recovery_settings(&solver);
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::NumericalError);
+    assert!(solver.is_poisoned());
+    let detail = solver.info.termination_detail.as_ref().unwrap();
+    assert!(detail.contains("injected KKT panic"), "{}", detail);
+
+    // the recovery profile was in use when the panic occurred
+    assert_eq!(recovery_settings(&solver), user_settings);
+}
+
+#[test]
+fn test_recovery_panic_reraised_restores_settings() {
+    let mut solver = failing_solver(false);
+    let user_settings = recovery_settings(&solver);
+
+    let result = catch_unwind(AssertUnwindSafe(|| solver.solve()));
+    assert!(result.is_err());
+    assert_eq!(recovery_settings(&solver), user_settings);
+}
@@ -3,15 +3,17 @@
 //
 // See: https://github.com/rust-lang/rust/issues/72353
 
-use std::sync::atomic::AtomicU64;
-pub use std::sync::atomic::Ordering;
+use core::sync::atomic::AtomicU64;
+pub use core::sync::atomic::Ordering;
 
 pub struct AtomicF64 {
     storage: AtomicU64,
 }
 impl AtomicF64 {
-    pub fn new(value: f64) -> Self {
-        let as_u64 = value.to_bits();
+    // f64::to_bits is not const for the supported rust versions
+    #[allow(unknown_lints, unnecessary_transmutes)]
+    pub const fn new(value: f64) -> Self {
+        let as_u64 = unsafe { core::mem::transmute::<f64, u64>(value) };
         Self {
             storage: AtomicU64::new(as_u64),
         }
@@ -1,2 +1,3 @@
 pub(crate) mod atomic;
 pub(crate) mod infbounds;
+pub(crate) mod panics;
@@ -0,0 +1,38 @@
+// handling of panics caught during solves
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::any::Any;
+
+#[cfg(feature = "std")]
+pub(crate) use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+// without std panics can't be caught, and go straight to the
+// panic handler as they do with catch_panics disabled
+
+#[cfg(not(feature = "std"))]
+pub(crate) struct AssertUnwindSafe<F>(pub F);
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn catch_unwind<R>(
+    f: AssertUnwindSafe<impl FnOnce() -> R>,
+) -> Result<R, Box<dyn Any + Send>> {
+    Ok((f.0)())
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn resume_unwind(_payload: Box<dyn Any + Send>) -> ! {
+    unreachable!("catch_unwind never fails without std")
+}
+
+// the message carried by a panic payload, which is a &str or a
+// String for panics raised with a message, as nearly all are
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
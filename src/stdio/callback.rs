@@ -0,0 +1,60 @@
+use core::fmt;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+// printing without std only fails if the output is not valid utf8
+pub(crate) type Result<T> = core::result::Result<T, fmt::Error>;
+
+// the subset of std::io::Write used for printing solver output
+pub(crate) trait Write {
+    fn write_all(&mut self, buf: &[u8]) -> Result<()>;
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<()> {
+        // forwards formatted output to write_all
+        struct Adapter<'a, W: ?Sized>(&'a mut W);
+        impl<W: Write + ?Sized> fmt::Write for Adapter<'_, W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0.write_all(s.as_bytes())
+            }
+        }
+        fmt::write(&mut Adapter(self), args)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+static PRINT_CALLBACK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Sets the function that receives the solver's printed output
+/// when compiled without the `std` feature.
+///
+/// Output is discarded until a callback is set.
+pub fn set_print_callback(callback: fn(&str)) {
+    PRINT_CALLBACK.store(callback as *mut (), Ordering::Release);
+}
+
+fn print_callback() -> Option<fn(&str)> {
+    let ptr = PRINT_CALLBACK.load(Ordering::Acquire);
+    if ptr.is_null() {
+        None
+    } else {
+        // only ever set from a fn(&str) in set_print_callback
+        Some(unsafe { core::mem::transmute::<*mut (), fn(&str)>(ptr) })
+    }
+}
+
+pub(crate) struct Stdout;
+
+pub(crate) fn stdout() -> Stdout {
+    Stdout
+}
+
+impl Write for Stdout {
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        if let Some(callback) = print_callback() {
+            callback(core::str::from_utf8(buf).map_err(|_| fmt::Error)?);
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-#[cfg(not(feature = "python"))]
+#[cfg(all(feature = "std", not(feature = "python")))]
 #[allow(unused_imports)]
 pub(crate) use std::io::{stderr, stdout};
 
@@ -8,3 +8,13 @@ pub(crate) use std::io::{stderr, stdout};
 #[cfg(feature = "python")]
 #[allow(unused_imports)]
 pub(crate) use crate::python::io::{stderr, stdout};
+
+#[cfg(feature = "std")]
+pub(crate) use std::io::{Result, Write};
+
+// without std there is no stdout, and printed output
+// goes to a user supplied callback instead
+#[cfg(not(feature = "std"))]
+mod callback;
+#[cfg(not(feature = "std"))]
+pub use callback::*;
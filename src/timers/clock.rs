@@ -0,0 +1,47 @@
+use crate::alloc_prelude::*;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::time::Duration;
+
+/// A monotonic time source for the solver's timers and time limit
+/// when compiled without the `std` feature.
+pub trait Clock: Sync {
+    /// Time elapsed since some fixed starting point.
+    fn now(&self) -> Duration;
+}
+
+static CLOCK: AtomicPtr<&'static dyn Clock> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Sets the time source used by the solver when compiled without the
+/// `std` feature.
+///
+/// Until a clock is set all timings read as zero, so `time_limit`
+/// never triggers.   Each call allocates a small handle that is never
+/// freed, so this should be called once at startup.
+pub fn set_clock(clock: &'static dyn Clock) {
+    let handle = Box::leak(Box::new(clock));
+    CLOCK.store(handle, Ordering::Release);
+}
+
+fn now() -> Duration {
+    let handle = CLOCK.load(Ordering::Acquire);
+    if handle.is_null() {
+        Duration::ZERO
+    } else {
+        // only ever set from a leaked Box in set_clock
+        unsafe { (*handle).now() }
+    }
+}
+
+// the parts of std::time::Instant used by the timers
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Instant(Duration);
+
+impl Instant {
+    pub(crate) fn now() -> Self {
+        Instant(now())
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        now().saturating_sub(self.0)
+    }
+}
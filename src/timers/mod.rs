@@ -3,3 +3,17 @@
 #[allow(clippy::module_inception)]
 mod timers;
 pub use timers::*;
+
+mod timings;
+pub use timings::*;
+
+// user supplied time source when compiled without std
+#[cfg(not(feature = "std"))]
+mod clock;
+#[cfg(not(feature = "std"))]
+pub use clock::*;
+
+#[cfg(feature = "profiling")]
+mod profiling;
+#[cfg(feature = "profiling")]
+pub use profiling::*;
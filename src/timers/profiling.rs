@@ -0,0 +1,206 @@
+use std::cell::RefCell;
+use std::fmt;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature="wasm")] {
+        use web_time::{Duration, Instant};
+    }
+    else {
+        use std::time::{Duration, Instant};
+    }
+}
+
+// ---------------------------------------------------
+// Per-phase wall time accounting, enabled by the
+// `profiling` feature.
+//
+// Scopes created by `profile_scope!` charge the time
+// spent in them to a solver phase.   Scopes may nest, in
+// which case the time spent in the inner scope is charged
+// only to the inner phase, so that the phase times are
+// disjoint and sum to the time spent in the outermost scopes.
+//
+// The scopes in the linear solvers have no access to the
+// solver that called them, so times are accumulated in a
+// thread local recorder installed by the solver for the
+// duration of its setup and of each solve.  Scopes reached
+// without a recorder installed are ignored.
+// ---------------------------------------------------
+
+/// Solver phases timed when the `profiling` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// problem data setup and presolve, excluding the other phases
+    Setup,
+    /// equilibration of the problem data
+    Equilibration,
+    /// updates to the KKT system, excluding its factorization
+    KKTUpdate,
+    /// numeric factorization of the KKT system
+    Factor,
+    /// KKT solves, including iterative refinement
+    Solve,
+    /// cone scaling updates, step right hand sides and the initial point
+    Cones,
+    /// step length calculation and iterate updates
+    LineSearch,
+    /// residual updates, termination checks and post-processing
+    Termination,
+}
+
+impl Phase {
+    /// All phases, in the order they are reported.
+    pub const ALL: [Phase; 8] = [
+        Phase::Setup,
+        Phase::Equilibration,
+        Phase::KKTUpdate,
+        Phase::Factor,
+        Phase::Solve,
+        Phase::Cones,
+        Phase::LineSearch,
+        Phase::Termination,
+    ];
+
+    /// Name of the phase as printed by [`PhaseTimes`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Setup => "setup",
+            Phase::Equilibration => "equilibration",
+            Phase::KKTUpdate => "kkt update",
+            Phase::Factor => "factor",
+            Phase::Solve => "solve",
+            Phase::Cones => "cones",
+            Phase::LineSearch => "linesearch",
+            Phase::Termination => "termination",
+        }
+    }
+}
+
+/// Wall time accumulated in each solver [`Phase`].
+///
+/// Times include the setup of the solver and its most recent solve.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhaseTimes {
+    times: [Duration; 8],
+}
+
+impl PhaseTimes {
+    /// Time spent in a phase.
+    pub fn get(&self, phase: Phase) -> Duration {
+        self.times[phase as usize]
+    }
+
+    /// Time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.times.iter().sum()
+    }
+
+    fn add(&mut self, phase: Phase, elapsed: Duration) {
+        self.times[phase as usize] += elapsed;
+    }
+}
+
+impl fmt::Display for PhaseTimes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        let percent = |t: Duration| {
+            if total.is_zero() {
+                0.
+            } else {
+                100. * t.as_secs_f64() / total.as_secs_f64()
+            }
+        };
+        writeln!(f, "{:<15}{:>12}{:>8}", "phase", "time (ms)", "%")?;
+        for phase in Phase::ALL {
+            let t = self.get(phase);
+            writeln!(
+                f,
+                "{:<15}{:>12.3}{:>8.1}",
+                phase.name(),
+                t.as_secs_f64() * 1e3,
+                percent(t)
+            )?;
+        }
+        writeln!(
+            f,
+            "{:<15}{:>12.3}{:>8.1}",
+            "total",
+            total.as_secs_f64() * 1e3,
+            percent(total)
+        )
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    times: PhaseTimes,
+    // open scopes, with the time each was last resumed
+    stack: Vec<(Phase, Instant)>,
+}
+
+thread_local! {
+    // one recorder per solver, so that a solver created
+    // and solved within another's scope is counted separately
+    static RECORDERS: RefCell<Vec<Recorder>> = const { RefCell::new(Vec::new()) };
+}
+
+// Start accumulating phase times on this thread, from initial values
+pub(crate) fn start_recording(times: PhaseTimes) {
+    RECORDERS.with(|r| {
+        r.borrow_mut().push(Recorder {
+            times,
+            stack: Vec::new(),
+        })
+    });
+}
+
+// Stop the most recently started recorder and return its times
+pub(crate) fn stop_recording() -> PhaseTimes {
+    RECORDERS.with(|r| r.borrow_mut().pop().unwrap_or_default().times)
+}
+
+// Guard created by `profile_scope!`, which charges the time until
+// it is dropped to its phase.   The enclosing scope, if any, is
+// paused for the duration.
+pub(crate) struct ProfileScope {
+    active: bool,
+}
+
+impl ProfileScope {
+    pub(crate) fn enter(phase: Phase) -> Self {
+        let active = RECORDERS.with(|r| {
+            let mut r = r.borrow_mut();
+            let Some(rec) = r.last_mut() else {
+                return false;
+            };
+            let now = Instant::now();
+            if let Some(&(outer, since)) = rec.stack.last() {
+                rec.times.add(outer, now - since);
+            }
+            rec.stack.push((phase, now));
+            true
+        });
+        Self { active }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        if !self.active {
+            return;
+        }
+        RECORDERS.with(|r| {
+            let mut r = r.borrow_mut();
+            let Some(rec) = r.last_mut() else {
+                return;
+            };
+            let now = Instant::now();
+            if let Some((phase, since)) = rec.stack.pop() {
+                rec.times.add(phase, now - since);
+            }
+            if let Some(outer) = rec.stack.last_mut() {
+                outer.1 = now;
+            }
+        });
+    }
+}
@@ -1,15 +1,24 @@
-use std::collections::HashMap;
-use std::ops::{Deref, DerefMut};
+use super::SolveTimings;
+#[cfg(feature = "profiling")]
+use super::{start_recording, stop_recording, PhaseTimes};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::stdio::{self, Write};
+use alloc::collections::BTreeMap;
+use core::ops::{Deref, DerefMut};
 
 cfg_if::cfg_if! {
     if #[cfg(feature="wasm")] {
         use web_time::{Duration, Instant};
     }
-    else {
+    else if #[cfg(feature="std")] {
         use std::time::{Duration, Instant};
     }
+    else {
+        use super::Instant;
+        use core::time::Duration;
+    }
 }
-        
 
 #[derive(Debug, Default)]
 struct InnerTimer {
@@ -58,10 +67,10 @@ impl InnerTimer {
 }
 
 #[derive(Debug, Default)]
-struct SubTimersMap(HashMap<&'static str, InnerTimer>);
+struct SubTimersMap(BTreeMap<&'static str, InnerTimer>);
 
 impl Deref for SubTimersMap {
-    type Target = HashMap<&'static str, InnerTimer>;
+    type Target = BTreeMap<&'static str, InnerTimer>;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
@@ -112,7 +121,8 @@ impl SubTimersMap {
     pub fn print(&self, depth: u8) {
         for (key, val) in self.iter() {
             let tabs = format!("{: <1$}", "", 4 * depth as usize);
-            println!("{}{:} : {:?}", tabs, *key, val.elapsed);
+            // printing is best effort, as with println!
+            let _ = writeln!(stdio::stdout(), "{}{:} : {:?}", tabs, *key, val.elapsed);
             val.subtimers.print(depth + 1);
         }
     }
@@ -122,6 +132,18 @@ impl SubTimersMap {
 pub struct Timers {
     stack: Vec<&'static str>,
     subtimers: SubTimersMap,
+
+    // phase times recorded when enabled by the `timing` setting,
+    // from setup, and from setup and the most recent solve
+    timing: bool,
+    setup_timings: SolveTimings,
+    pub(crate) timings: SolveTimings,
+
+    // phase times from setup, and from setup and the most recent solve
+    #[cfg(feature = "profiling")]
+    setup_profile: PhaseTimes,
+    #[cfg(feature = "profiling")]
+    pub(crate) profile: PhaseTimes,
 }
 
 impl Timers {
@@ -191,6 +213,56 @@ impl Timers {
     }
 }
 
+impl Timers {
+    // Start recording phase times for setup if `enable` is set
+    pub(crate) fn start_setup_timings(&mut self, enable: bool) {
+        self.timing = enable;
+        self.timings = SolveTimings::default();
+    }
+
+    pub(crate) fn stop_setup_timings(&mut self) {
+        self.setup_timings = self.timings;
+    }
+
+    // Start recording phase times for a solve if `enable` is set,
+    // on top of those from setup
+    pub(crate) fn start_timings(&mut self, enable: bool) {
+        self.timing = enable;
+        self.timings = self.setup_timings;
+    }
+
+    // Start time of a phase, or `None` if phases are not timed
+    pub(crate) fn start_phase(&self) -> Option<Instant> {
+        self.timing.then(Instant::now)
+    }
+
+    pub(crate) fn phase_elapsed(start: Option<Instant>) -> Duration {
+        start.map_or(Duration::ZERO, |t| t.elapsed())
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl Timers {
+    // Start recording phase times for setup
+    pub(crate) fn start_setup_profile(&mut self) {
+        start_recording(PhaseTimes::default());
+    }
+
+    pub(crate) fn stop_setup_profile(&mut self) {
+        self.setup_profile = stop_recording();
+        self.profile = self.setup_profile.clone();
+    }
+
+    // Start recording phase times for a solve, on top of those from setup
+    pub(crate) fn start_profile(&mut self) {
+        start_recording(self.setup_profile.clone());
+    }
+
+    pub(crate) fn stop_profile(&mut self) {
+        self.profile = stop_recording();
+    }
+}
+
 macro_rules! timeit {
     ($timer:ident => $key:literal; $($tt:tt)+) => {
 
@@ -199,7 +271,16 @@ macro_rules! timeit {
             $tt
         )+
         $timer.stop_current();
-    }
+    };
+
+    // also adds the time to a field of the solver's `SolveTimings`
+    // when the `timing` setting is enabled
+    ($timer:ident => $key:literal, $phase:ident; $($tt:tt)+) => {
+
+        let _phase_start = $timer.start_phase();
+        $crate::timers::timeit!{$timer => $key; $($tt)+}
+        $timer.timings.$phase += $crate::timers::Timers::phase_elapsed(_phase_start);
+    };
 }
 pub(crate) use timeit;
 
@@ -214,3 +295,18 @@ macro_rules! notimeit {
     }
 }
 pub(crate) use notimeit;
+
+// Charge the time until the end of the enclosing block to a solver
+// phase.   Expands to nothing unless the `profiling` feature is enabled.
+#[cfg(feature = "profiling")]
+macro_rules! profile_scope {
+    ($phase:ident) => {
+        let _profile_scope = $crate::timers::ProfileScope::enter($crate::timers::Phase::$phase);
+    };
+}
+
+#[cfg(not(feature = "profiling"))]
+macro_rules! profile_scope {
+    ($phase:ident) => {};
+}
+pub(crate) use profile_scope;
@@ -0,0 +1,34 @@
+use core::time::Duration;
+
+// ---------------------------------------------------
+// Per-phase wall time accounting, enabled at run time
+// by the `timing` setting.
+//
+// Unlike the phase times of the `profiling` feature, these
+// are recorded only at the solver's own call sites, so they
+// are available in any build but do not cover every phase.
+// Phases not listed here are not timed.
+// ---------------------------------------------------
+
+/// Wall time spent in the main phases of a solver's setup and of its
+/// most recent solve, recorded when the
+/// [`timing`](crate::solver::DefaultSettings::timing) setting is enabled.
+///
+/// Times for a solve include every recovery attempt made by it.
+/// All times are zero if `timing` is disabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveTimings {
+    /// creation of the solver, including its equilibration
+    pub setup: Duration,
+    /// equilibration of the problem data
+    pub equilibration: Duration,
+    /// updates and numeric factorizations of the KKT system
+    /// in the main loop
+    pub factorization: Duration,
+    /// KKT solves for the affine step
+    pub affine_solve: Duration,
+    /// KKT solves for the combined step
+    pub combined_solve: Duration,
+    /// cone scaling updates
+    pub cone_scaling: Duration,
+}
@@ -0,0 +1,122 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn adaptive_backoff_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // max  x
+    // s.t. y * exp(x / y) <= z
+    //      y == 1, z == exp(5)
+    //
+    // plus badly scaled bounds on x and z, so that
+    // the unit initialization is poorly centered
+
+    let P = CscMatrix::<f64>::zeros((3, 3));
+    let c = vec![-1., 0., 0.];
+
+    let mut A1 = CscMatrix::<f64>::identity(3);
+    A1.negate();
+    let b1 = vec![0.; 3];
+
+    let A2 = CscMatrix::new(
+        2,                // m
+        3,                // n
+        vec![0, 0, 1, 2], //colptr
+        vec![0, 1],       //rowval
+        vec![1., 1.],     //nzval
+    );
+    let b2 = vec![1., f64::exp(5.)];
+
+    let A3 = CscMatrix::from(&[
+        [1., 0., 0.], //
+        [1., 0., 0.], //
+        [0., 0., 1.], //
+        [1., 1., 0.],
+    ]);
+    let b3 = vec![1e4, 4., 1e6, 6.];
+
+    let A = CscMatrix::vcat(&CscMatrix::vcat(&A1, &A2), &A3);
+    let b = [b1, b2, b3].concat();
+
+    let cones = vec![ExponentialConeT(), ZeroConeT(2), NonnegativeConeT(4)];
+
+    (P, c, A, b, cones)
+}
+
+fn solve_with_backoff(adaptive: Option<bool>) -> DefaultSolver {
+    let (P, c, A, b, cones) = adaptive_backoff_data();
+
+    let mut builder = DefaultSettingsBuilder::default();
+    if let Some(adaptive) = adaptive {
+        builder.adaptive_step_backoff(adaptive);
+    }
+    let settings = builder.build().unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+#[test]
+fn test_adaptive_backoff() {
+    let adaptive = solve_with_backoff(Some(true));
+    let fixed = solve_with_backoff(Some(false));
+
+    assert_eq!(adaptive.solution.status, SolverStatus::Solved);
+    assert_eq!(fixed.solution.status, SolverStatus::Solved);
+
+    let refsol = vec![4.0, 1.0, f64::exp(5.0)];
+    assert!(adaptive.solution.x.dist(&refsol) <= 1e-6);
+    assert!(fixed.solution.x.dist(&refsol) <= 1e-6);
+}
+
+#[test]
+fn test_adaptive_backoff_is_opt_in() {
+    // the default settings use the fixed back-off
+    let default = solve_with_backoff(None);
+    let fixed = solve_with_backoff(Some(false));
+
+    assert_eq!(default.info.iterations, fixed.info.iterations);
+    assert_eq!(default.solution.x, fixed.solution.x);
+}
+
+#[test]
+fn test_fixed_backoff_factor() {
+    // the fixed back-off is configurable
+    let (P, c, A, b, cones) = adaptive_backoff_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .adaptive_step_backoff(false)
+        .step_backoff_factor(0.95)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.info.step_length <= 0.95);
+}
+
+#[test]
+fn test_adaptive_backoff_respects_max_step_fraction() {
+    let (P, c, A, b, cones) = adaptive_backoff_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .adaptive_step_backoff(true)
+        .max_step_fraction(0.9)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.info.step_length <= 0.9);
+}
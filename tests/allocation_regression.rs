@@ -0,0 +1,243 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// allocator counting the allocations made by the current thread,
+// so that tests running in parallel don't disturb each other
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count(bytes: usize) {
+    // try_with, since the counters are unavailable during thread teardown
+    let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+    let _ = ALLOCATED_BYTES.try_with(|c| c.set(c.get() + bytes));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|c| c.get())
+}
+
+fn allocated_bytes() -> usize {
+    ALLOCATED_BYTES.with(|c| c.get())
+}
+
+// number of allocations made by a solve limited to max_iter iterations
+fn solve_allocations(
+    P: &CscMatrix<f64>,
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    cones: &[SupportedConeT<f64>],
+    max_iter: u32,
+    verbose: bool,
+) -> (usize, u32) {
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(verbose)
+        .max_iter(max_iter)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(P, q, A, b, cones, settings).unwrap();
+
+    let start = allocations();
+    solver.solve();
+    (allocations() - start, solver.solution.iterations)
+}
+
+// the number of allocations made by a solve should not
+// depend on the number of iterations performed
+fn assert_constant_allocations(
+    P: &CscMatrix<f64>,
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    cones: &[SupportedConeT<f64>],
+    verbose: bool,
+) {
+    let (first, _) = solve_allocations(P, q, A, b, cones, 1, verbose);
+    let mut iter = 1;
+    for max_iter in 2..50 {
+        let (count, iterations) = solve_allocations(P, q, A, b, cones, max_iter, verbose);
+        assert_eq!(count, first, "allocations after {} iterations", iterations);
+        iter = iterations;
+        if iterations < max_iter {
+            break;
+        }
+    }
+    // make sure the solve wasn't trivial
+    assert!(iter > 5);
+}
+
+#[test]
+fn test_no_iteration_allocations_qp() {
+    // box constrained QP with a tridiagonal P
+    let n = 50;
+    let P = CscMatrix::from_banded(n, &[(0, vec![4.; n]), (1, vec![-1.; n - 1])]).to_triu();
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &{
+        let mut I = CscMatrix::identity(n);
+        I.negate();
+        I
+    });
+    let b = vec![0.1; 2 * n];
+    let cones = [NonnegativeConeT(2 * n)];
+
+    assert_constant_allocations(&P, &q, &A, &b, &cones, false);
+}
+
+#[test]
+fn test_no_iteration_allocations_verbose() {
+    // as above, with the progress of each iteration printed and
+    // recorded for post-mortem reporting
+    let n = 50;
+    let P = CscMatrix::identity(n);
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &{
+        let mut I = CscMatrix::identity(n);
+        I.negate();
+        I
+    });
+    let b = vec![0.1; 2 * n];
+    let cones = [NonnegativeConeT(2 * n)];
+
+    // warm up, since the first write to stdout allocates its buffer
+    solve_allocations(&P, &q, &A, &b, &cones, 1, true);
+    assert_constant_allocations(&P, &q, &A, &b, &cones, true);
+}
+
+#[test]
+fn test_no_iteration_allocations_mixed_cones() {
+    // min x₁ + x₂ + x₃ with -(x₁,x₂,x₃) in each of the second
+    // order, exponential, power and generalized power cones, and x ≤ 1
+    let n = 3;
+    let P = CscMatrix::zeros((n, n));
+    let q = vec![1., 1., 1.];
+
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&CscMatrix::vcat(&I, &I), &CscMatrix::vcat(&I, &I));
+    let A = CscMatrix::vcat(&A, &CscMatrix::identity(n));
+    let b = [vec![0.; 4 * n], vec![1.; n]].concat();
+    let cones = [
+        SecondOrderConeT(n),
+        ExponentialConeT(),
+        PowerConeT(0.3),
+        GenPowerConeT(vec![0.4, 0.6], 1),
+        NonnegativeConeT(n),
+    ];
+
+    assert_constant_allocations(&P, &q, &A, &b, &cones, false);
+}
+
+#[test]
+fn test_borrowed_pattern_copies() {
+    // a borrowed solver takes no copy of the sparsity patterns of
+    // P and A, and so allocates less than an owned solver
+    let n = 1000;
+    let P = CscMatrix::identity(n);
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &{
+        let mut I = CscMatrix::identity(n);
+        I.negate();
+        I
+    });
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+    let b = vec![0.5; 2 * n];
+    let cones = [NonnegativeConeT(2 * n)];
+    let settings = || {
+        DefaultSettingsBuilder::default()
+            .verbose(false)
+            .build()
+            .unwrap()
+    };
+
+    let start = allocated_bytes();
+    let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings()).unwrap();
+    let owned = allocated_bytes() - start;
+    drop(solver);
+
+    let start = allocated_bytes();
+    let solver = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings()).unwrap();
+    let borrowed = allocated_bytes() - start;
+    drop(solver);
+
+    let patterns = P.colptr.len() + P.rowval.len() + A.colptr.len() + A.rowval.len();
+    assert!(owned - borrowed >= patterns * std::mem::size_of::<usize>());
+}
+
+#[test]
+fn test_no_update_allocations() {
+    // box constrained QP with a tridiagonal P, solved
+    // again after updating all of the problem data
+    let n = 50;
+    let P = CscMatrix::from_banded(n, &[(0, vec![4.; n]), (1, vec![-1.; n - 1])]).to_triu();
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &{
+        let mut I = CscMatrix::identity(n);
+        I.negate();
+        I
+    });
+    let b = vec![0.1; 2 * n];
+    let cones = [NonnegativeConeT(2 * n)];
+    let settings = || {
+        DefaultSettingsBuilder::default()
+            .presolve_enable(false)
+            .verbose(false)
+            .build()
+            .unwrap()
+    };
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings()).unwrap();
+    solver.solve();
+
+    let mut P2 = P.clone();
+    P2.nzval.iter_mut().for_each(|v| *v *= 1.5);
+    let q2: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+    let mut A2 = A.clone();
+    A2.nzval.iter_mut().for_each(|v| *v *= 2.);
+    let b2: Vec<f64> = (0..2 * n).map(|i| 0.1 + 0.01 * (i % 7) as f64).collect();
+
+    let start = allocations();
+    solver.update_P(&P2.nzval).unwrap();
+    solver.update_q(&q2).unwrap();
+    solver.update_A(&A2).unwrap();
+    solver.update_b(&b2).unwrap();
+    assert_eq!(allocations() - start, 0);
+
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    // matches a solver built for the updated problem
+    let mut fresh = DefaultSolver::new(&P2, &q2, &A2, &b2, &cones, settings()).unwrap();
+    fresh.solve();
+    assert!(solver.solution.x.dist(&fresh.solution.x) <= 1e-6);
+    assert!(f64::abs(solver.solution.obj_val - fresh.solution.obj_val) <= 1e-8);
+
+    // a value vector of the wrong length is rejected
+    let err = solver.update_P(&P2.nzval[1..].to_vec()).unwrap_err();
+    assert!(matches!(
+        err,
+        DataUpdateError::BadFormat(SparseFormatError::IncompatibleDimension)
+    ));
+}
@@ -20,6 +20,23 @@ fn api_dim_check_data() -> (
     (P, q, A, b, cones)
 }
 
+fn api_dim_check_mismatch(
+    result: Result<DefaultSolver<f64>, ClarabelError>,
+    what: &str,
+    expected: usize,
+    got: usize,
+) {
+    match result {
+        Err(ClarabelError::DimensionMismatch {
+            what: w,
+            expected: e,
+            got: g,
+        }) => assert_eq!((w, e, g), (what, expected, got)),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("bad dimensions were accepted"),
+    }
+}
+
 #[test]
 fn api_dim_check_working() {
     // This example should work because dimensions are
@@ -29,55 +46,183 @@ fn api_dim_check_working() {
     let (P, q, A, b, cones) = api_dim_check_data();
 
     let settings = DefaultSettings::default();
-    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
 }
 
 #[test]
-#[should_panic]
 fn api_dim_check_bad_P() {
     let (_P, q, A, b, cones) = api_dim_check_data();
     let P = CscMatrix::<f64>::zeros((3, 3));
 
     let settings = DefaultSettings::default();
-    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    api_dim_check_mismatch(result, "columns of P", 4, 3);
 }
 
 #[test]
-#[should_panic]
 fn api_dim_check_bad_A_rows() {
     let (P, q, _A, b, cones) = api_dim_check_data();
     let A = CscMatrix::<f64>::zeros((5, 4));
 
     let settings = DefaultSettings::default();
-    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    api_dim_check_mismatch(result, "rows of A", 6, 5);
 }
 
 #[test]
-#[should_panic]
 fn api_dim_check_bad_A_cols() {
     let (P, q, _A, b, cones) = api_dim_check_data();
     let A = CscMatrix::<f64>::zeros((6, 3));
 
     let settings = DefaultSettings::default();
-    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    api_dim_check_mismatch(result, "columns of A", 4, 3);
 }
 
 #[test]
-#[should_panic]
 fn api_dim_check_P_not_square() {
     let (_P, q, A, b, cones) = api_dim_check_data();
-    let P = CscMatrix::<f64>::zeros((4, 3));
+    let P = CscMatrix::<f64>::zeros((3, 4));
 
     let settings = DefaultSettings::default();
-    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    api_dim_check_mismatch(result, "rows of P", 4, 3);
 }
 
 #[test]
-#[should_panic]
 fn api_dim_check_bad_cones() {
     let (P, q, A, b, _cones) = api_dim_check_data();
     let cones = vec![ZeroConeT(1), NonnegativeConeT(2), NonnegativeConeT(4)];
 
     let settings = DefaultSettings::default();
-    let _solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    api_dim_check_mismatch(result, "sum of cone dimensions", 6, 7);
+}
+
+#[test]
+fn api_dim_check_cone_overflow() {
+    let (P, q, A, b, _cones) = api_dim_check_data();
+    let settings = DefaultSettings::default;
+
+    // the dimensions sum to 6 if the sum is allowed to wrap
+    let cones = vec![NonnegativeConeT(usize::MAX), NonnegativeConeT(7)];
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings());
+    assert!(matches!(
+        result,
+        Err(ClarabelError::DimensionOverflow("sum of cone dimensions"))
+    ));
+
+    // the dimension of a single cone overflows
+    let cones = vec![ZeroConeT(1), GenPowerConeT(vec![0.5, 0.5], usize::MAX)];
+    match DefaultSolver::new(&P, &q, &A, &b, &cones, settings()) {
+        Err(ClarabelError::BadConeSpecification { index, reason }) => {
+            assert_eq!(index, 1);
+            assert!(reason.contains("overflows"), "{}", reason);
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("overflowing cone was accepted"),
+    }
+}
+
+#[test]
+#[cfg(feature = "sdp")]
+fn api_dim_check_psd_overflow() {
+    let (P, q, A, b, _cones) = api_dim_check_data();
+
+    // the cone has fewer than usize::MAX entries, but its
+    // packed Hs block has more
+    let n = 1 << (usize::BITS / 4 + 1);
+    for cones in [
+        vec![PSDTriangleConeT(n)],
+        vec![PSDTriangleConeT(usize::MAX)],
+    ] {
+        let settings = DefaultSettings::default();
+        assert!(matches!(
+            DefaultSolver::new(&P, &q, &A, &b, &cones, settings),
+            Err(ClarabelError::BadConeSpecification { index: 0, .. })
+        ));
+    }
+}
+
+#[test]
+fn api_check_bad_cone_parameters() {
+    let (P, q, A, b, _cones) = api_dim_check_data();
+
+    let bad_cones = [
+        vec![ZeroConeT(1), NonnegativeConeT(4), SecondOrderConeT(1)],
+        vec![ZeroConeT(3), PowerConeT(1.5)],
+        vec![ZeroConeT(3), GenPowerConeT(vec![0.5, 0.4], 1)],
+        vec![ZeroConeT(3), GenPowerConeT(vec![1.5, -0.5], 1)],
+    ];
+    let expected = [
+        (2, "dimension at least 2"),
+        (1, "must be in (0,1)"),
+        (1, "must sum to 1"),
+        (1, "must be positive"),
+    ];
+
+    for (cones, (expected_index, expected_reason)) in bad_cones.iter().zip(expected) {
+        let settings = DefaultSettings::default();
+        match DefaultSolver::new(&P, &q, &A, &b, cones, settings) {
+            Err(ClarabelError::BadConeSpecification { index, reason }) => {
+                assert_eq!(index, expected_index);
+                assert!(reason.contains(expected_reason), "{}", reason);
+            }
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("bad cone was accepted"),
+        }
+    }
+}
+
+#[test]
+fn api_check_bad_matrix_format() {
+    let (P, q, _A, b, cones) = api_dim_check_data();
+
+    // a row index beyond the last row of A
+    let A = CscMatrix {
+        m: 6,
+        n: 4,
+        colptr: vec![0, 1, 1, 1, 1],
+        rowval: vec![6],
+        nzval: vec![1.],
+    };
+
+    let settings = DefaultSettings::default();
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    assert!(matches!(
+        result,
+        Err(ClarabelError::BadMatrixFormat(
+            "A",
+            SparseFormatError::BadRowval
+        ))
+    ));
+
+    // a colptr of the wrong length
+    let mut P = P;
+    P.colptr.pop();
+
+    let settings = DefaultSettings::default();
+    let result = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    assert!(matches!(
+        result,
+        Err(ClarabelError::BadMatrixFormat(
+            "P",
+            SparseFormatError::IncompatibleDimension
+        ))
+    ));
+}
+
+#[test]
+fn api_check_bad_settings() {
+    let (P, q, A, b, cones) = api_dim_check_data();
+
+    let settings = DefaultSettings {
+        direct_solve_method: "nonexistent".to_string(),
+        ..DefaultSettings::default()
+    };
+    match DefaultSolver::new(&P, &q, &A, &b, &cones, settings) {
+        Err(ClarabelError::BadSettings(msg)) => assert!(msg.contains("nonexistent")),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("bad settings were accepted"),
+    }
 }
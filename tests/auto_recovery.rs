@@ -0,0 +1,134 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn recovery_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::<f64>::zeros((3, 3));
+    let q = vec![-1., -2., -3.];
+
+    let I1 = CscMatrix::<f64>::identity(3);
+    let mut I2 = CscMatrix::<f64>::identity(3);
+    I2.negate();
+    let A = CscMatrix::vcat(&I1, &I2);
+    let b = vec![1., 2., 3., 0., 0., 0.];
+
+    let cones = vec![NonnegativeConeT(6)];
+
+    (P, q, A, b, cones)
+}
+
+// a badly scaled linear objective over a second order cone, which
+// fails with a numerical error at default settings
+#[allow(clippy::type_complexity)]
+fn recovery_hard_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::<f64>::zeros((2, 2));
+    let q = vec![1.9380763554385735e-5, 126.88690882293498];
+
+    let A = CscMatrix::from(&[
+        [0.0, -13450.647011133104],
+        [166485.57127529362, 0.0],
+        [-1.058492117895224e-7, -5.388888655541602e-6],
+        [1889.4868516115475, 0.0],
+    ]);
+    let b = vec![
+        0.025772475263588217,
+        -0.6623221613849477,
+        4.773989708634273e-6,
+        -1630.3077592825255,
+    ];
+
+    let cones = vec![SecondOrderConeT(4)];
+
+    (P, q, A, b, cones)
+}
+
+#[test]
+fn test_auto_recovery_disabled() {
+    let (P, q, A, b, cones) = recovery_hard_data();
+
+    let settings = DefaultSettings::default();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::NumericalError);
+    assert!(solver.info.recovery.is_none());
+    assert_eq!(solver.solution.recovery_attempt, 0);
+}
+
+#[test]
+fn test_auto_recovery() {
+    let (P, q, A, b, cones) = recovery_hard_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .auto_recovery(true)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    let refsol = [-1.1510091225549122e-4, 0.12119636204268998];
+    assert!(solver.solution.x.dist(&refsol) <= 1e-6);
+    assert!(f64::abs(solver.solution.obj_val - 15.378231737951479) <= 1e-5);
+
+    // recovery details are reported in both the info and solution
+    let recovery = solver.info.recovery.unwrap();
+    assert_eq!(recovery.attempt, 1);
+    assert_eq!(solver.solution.recovery_attempt, 1);
+    assert!((recovery.max_step_fraction - 0.99 * 0.9).abs() < 1e-12);
+    assert!((recovery.static_regularization_constant - 1e-7).abs() < 1e-20);
+    assert!(recovery.dual_scaling);
+
+    // user settings are restored after the solve
+    assert_eq!(solver.settings.max_step_fraction, 0.99);
+    assert_eq!(solver.settings.static_regularization_constant, 1e-8);
+}
+
+#[test]
+fn test_auto_recovery_no_attempts() {
+    let (P, q, A, b, cones) = recovery_hard_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .auto_recovery(true)
+        .max_recovery_attempts(0)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::NumericalError);
+    assert_eq!(solver.solution.recovery_attempt, 0);
+}
+
+#[test]
+fn test_auto_recovery_not_needed() {
+    let (P, q, A, b, cones) = recovery_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .auto_recovery(true)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.info.recovery.is_none());
+    assert_eq!(solver.solution.recovery_attempt, 0);
+}
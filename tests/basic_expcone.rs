@@ -51,12 +51,18 @@ fn test_expcone_feasible() {
     let (P, c, A, b, cones) = basic_expcone_data();
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
     assert_eq!(solver.solution.status, SolverStatus::Solved);
 
+    #[cfg(feature = "testing")]
+    {
+        let problem = clarabel::qa::Problem::new(&P, &c, &A, &b, &cones);
+        clarabel::qa::verify_solution(&problem, &solver.solution, 1e-6).assert_ok();
+    }
+
     let refsol = vec![5.0, 1.0, f64::exp(5.0)];
     assert!(solver.solution.x.dist(&refsol) <= 1e-6);
 
@@ -78,7 +84,7 @@ fn test_expcone_primal_infeasible() {
     b[4] = -1.; //
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -102,7 +108,7 @@ fn test_expcone_dual_infeasible() {
     let cones = vec![ExponentialConeT()];
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -44,12 +44,70 @@ fn test_powcone() {
     let cones = [cones1, cones2].concat();
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
     assert_eq!(solver.solution.status, SolverStatus::Solved);
 
+    #[cfg(feature = "testing")]
+    {
+        let problem = clarabel::qa::Problem::new(&P, &c, &A, &b, &cones);
+        clarabel::qa::verify_solution(&problem, &solver.solution, 1e-6).assert_ok();
+    }
+
     let refobj = -1.8458;
     assert!(f64::abs(solver.info.cost_primal - refobj) <= 1e-3);
 }
+
+#[test]
+fn test_genpowcone_vector_exponents() {
+    // max  z1 + z2
+    // s.t. (x, z) in K_genpow(α), with x in R^5 and z in R^2
+    //      x1 + ... + x5 == 1
+    // whose solution is x = α and z1 = z2 = G/√2, where
+    // G = ∏ αᵢ^αᵢ is the largest attainable ∏ xᵢ^αᵢ
+
+    // v = (x1, ..., x5, z1, z2)
+    let alpha = vec![0.1, 0.15, 0.2, 0.25, 0.3];
+    let n = 7;
+    let P = CscMatrix::<f64>::zeros((n, n));
+    let c = vec![0., 0., 0., 0., 0., -1., -1.];
+
+    let mut A1 = CscMatrix::<f64>::identity(n);
+    A1.negate();
+    let b1 = vec![0.; n];
+    let cones1 = vec![GenPowerConeT(alpha.clone(), 2)];
+
+    let A2 = CscMatrix::from(&[[1., 1., 1., 1., 1., 0., 0.]]);
+    let b2 = vec![1.];
+    let cones2 = vec![ZeroConeT(1)];
+
+    let A = CscMatrix::vcat(&A1, &A2);
+    let b = [b1, b2].concat();
+    let cones = [cones1, cones2].concat();
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    #[cfg(feature = "testing")]
+    {
+        let problem = clarabel::qa::Problem::new(&P, &c, &A, &b, &cones);
+        clarabel::qa::verify_solution(&problem, &solver.solution, 1e-6).assert_ok();
+    }
+
+    let G: f64 = alpha.iter().map(|&ai| f64::powf(ai, ai)).product();
+    let refobj = -f64::sqrt(2.) * G;
+    assert!(f64::abs(solver.solution.obj_val - refobj) <= 1e-6);
+
+    // the objective is flat near the solution, so x is
+    // less accurate than the objective value
+    assert!(solver.solution.x[0..5].dist(&alpha) <= 1e-3);
+}
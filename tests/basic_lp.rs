@@ -33,12 +33,18 @@ fn test_lp_feasible() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
     assert_eq!(solver.solution.status, SolverStatus::Solved);
 
+    #[cfg(feature = "testing")]
+    {
+        let problem = clarabel::qa::Problem::new(&P, &c, &A, &b, &cones);
+        clarabel::qa::verify_solution(&problem, &solver.solution, 1e-6).assert_ok();
+    }
+
     let refsol = vec![-0.5, 0.5, -0.5];
     assert!(solver.solution.x.dist(&refsol) <= 1e-8);
 
@@ -56,7 +62,7 @@ fn test_lp_primal_infeasible() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -74,7 +80,7 @@ fn test_lp_dual_infeasible() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -93,7 +99,7 @@ fn test_lp_dual_infeasible_ill_cond() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -41,12 +41,52 @@ fn test_powcone() {
     let cones = [cones1, cones2].concat();
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
     assert_eq!(solver.solution.status, SolverStatus::Solved);
 
+    #[cfg(feature = "testing")]
+    {
+        let problem = clarabel::qa::Problem::new(&P, &c, &A, &b, &cones);
+        clarabel::qa::verify_solution(&problem, &solver.solution, 1e-6).assert_ok();
+    }
+
     let refobj = -1.8458;
     assert!(f64::abs(solver.info.cost_primal - refobj) <= 1e-3);
 }
+
+#[test]
+fn test_powcone_geometric_mean() {
+    // max  x^α y^(1-α)  s.t.  x + y = 1, whose solution is
+    // x = α, y = 1 - α with objective α^α (1-α)^(1-α)
+    //
+    // v = (x, y, t), with (x, y, t) in K_pow(α) and x + y == 1
+    for alpha in [0.1, 0.3, 0.5, 0.8] {
+        let P = CscMatrix::zeros((3, 3));
+        let c = vec![0., 0., -1.];
+
+        let mut Aneg = CscMatrix::identity(3);
+        Aneg.negate();
+        let A = CscMatrix::vcat(&Aneg, &CscMatrix::from(&[[1., 1., 0.]]));
+        let b = vec![0., 0., 0., 1.];
+        let cones = vec![PowerConeT(alpha), ZeroConeT(1)];
+
+        let settings = DefaultSettingsBuilder::default()
+            .verbose(false)
+            .build()
+            .unwrap();
+        let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+        solver.solve();
+
+        assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+        let x = &solver.solution.x;
+        let refobj = f64::powf(alpha, alpha) * f64::powf(1. - alpha, 1. - alpha);
+        // the objective is flat near the solution, so x is
+        // less accurate than the objective value
+        assert!(x.dist(&[alpha, 1. - alpha, refobj]) <= 1e-3);
+        assert!(f64::abs(solver.solution.obj_val + refobj) <= 1e-7);
+    }
+}
@@ -83,7 +83,7 @@ fn test_qp_univariate() {
     let cones = [NonnegativeConeT(1)];
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -100,12 +100,18 @@ fn test_qp_feasible() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
     assert_eq!(solver.solution.status, SolverStatus::Solved);
 
+    #[cfg(feature = "testing")]
+    {
+        let problem = clarabel::qa::Problem::new(&P, &c, &A, &b, &cones);
+        clarabel::qa::verify_solution(&problem, &solver.solution, 1e-6).assert_ok();
+    }
+
     let refsol = vec![0.3, 0.7];
     assert!(solver.solution.x.dist(&refsol) <= 1e-6);
 
@@ -123,7 +129,7 @@ fn test_qp_primal_infeasible() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -138,7 +144,7 @@ fn test_qp_dual_infeasible() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -165,7 +171,7 @@ fn test_qp_dual_infeasible_ill_cond() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -0,0 +1,206 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "parallel")]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. lo ≤ x ≤ hi, with a tridiagonal P and
+// bounds that are infeasible if lo > hi.   The unconstrained
+// minimizer varies with k
+fn box_qp(n: usize, k: f64, lo: f64, hi: f64) -> BatchProblem<f64> {
+    let P = CscMatrix::from_banded(n, &[(0, vec![4.; n]), (1, vec![-1.; n - 1])]);
+    let q: Vec<f64> = (0..n).map(|i| ((i as f64) * k).sin()).collect();
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &I);
+    let b = [vec![hi; n], vec![-lo; n]].concat();
+    BatchProblem::new(P, q, A, b, vec![NonnegativeConeT(2 * n)])
+}
+
+fn settings() -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap()
+}
+
+// a mix of feasible and infeasible problems, with every
+// fifth problem infeasible
+fn mixed_batch(count: usize) -> Vec<BatchProblem<f64>> {
+    (0..count)
+        .map(|i| {
+            let k = 0.1 + i as f64 / count as f64;
+            if i % 5 == 4 {
+                box_qp(20, k, 1., -1.)
+            } else {
+                box_qp(20, k, -0.1, 0.1)
+            }
+        })
+        .collect()
+}
+
+fn solve_single(problem: &BatchProblem<f64>) -> DefaultSolution<f64> {
+    let p = problem;
+    let mut solver = DefaultSolver::new(&p.P, &p.q, &p.A, &p.b, &p.cones, settings()).unwrap();
+    solver.solve();
+    solver.solution
+}
+
+#[test]
+fn test_solve_batch_mixed() {
+    let problems = mixed_batch(50);
+    let expected: Vec<_> = problems.iter().map(solve_single).collect();
+
+    for parallelism in [BatchParallelism::Global, BatchParallelism::Threads(3)] {
+        let batch = DefaultSolver::solve_batch(problems.clone(), &settings(), parallelism);
+
+        // results are in input order and match individual solves
+        assert_eq!(batch.results.len(), problems.len());
+        for (i, (result, expected)) in batch.results.iter().zip(&expected).enumerate() {
+            let solution = result.as_ref().unwrap();
+            if i % 5 == 4 {
+                assert_eq!(solution.status, SolverStatus::PrimalInfeasible);
+            } else {
+                assert_eq!(solution.status, SolverStatus::Solved);
+            }
+            assert_eq!(solution.x, expected.x);
+            assert_eq!(solution.iterations, expected.iterations);
+        }
+
+        let stats = &batch.stats;
+        assert_eq!(stats.problems, 50);
+        assert_eq!(stats.solved, 40);
+        assert_eq!(stats.unsolved, 10);
+        assert_eq!(stats.failed, 0);
+        assert_eq!(stats.setups, 50);
+        let iterations: u64 = expected.iter().map(|s| s.iterations as u64).sum();
+        assert_eq!(stats.iterations, iterations);
+    }
+}
+
+#[test]
+fn test_solve_batch_failures_isolated() {
+    let mut problems = mixed_batch(10);
+
+    // inconsistent dimensions
+    problems[3].b.pop();
+
+    // a bad cone, since second order cones must
+    // have dimension at least 2
+    let mut bad = box_qp(2, 1., -1., 1.);
+    bad.cones = vec![NonnegativeConeT(3), SecondOrderConeT(1)];
+    problems[6] = bad;
+
+    let batch = DefaultSolver::solve_batch(problems, &settings(), BatchParallelism::Threads(2));
+
+    assert!(matches!(
+        batch.results[3],
+        Err(BatchSolveError::BadProblem(
+            ClarabelError::DimensionMismatch { .. }
+        ))
+    ));
+    assert!(matches!(
+        batch.results[6],
+        Err(BatchSolveError::BadProblem(
+            ClarabelError::BadConeSpecification { index: 1, .. }
+        ))
+    ));
+    for (i, result) in batch.results.iter().enumerate() {
+        if i != 3 && i != 6 {
+            assert!(result.is_ok());
+        }
+    }
+    assert_eq!(batch.stats.failed, 2);
+    assert_eq!(batch.stats.solved + batch.stats.unsolved, 8);
+}
+
+#[test]
+fn test_solve_batch_shared_pattern() {
+    // problems with a common pattern and upper triangular P
+    let problems: Vec<_> = mixed_batch(40)
+        .into_iter()
+        .map(|mut p| {
+            p.P = p.P.to_triu();
+            p
+        })
+        .collect();
+    let expected: Vec<_> = problems.iter().map(solve_single).collect();
+
+    let batch = DefaultSolver::solve_batch_shared_pattern(
+        problems,
+        &settings(),
+        BatchParallelism::Threads(2),
+    );
+
+    // solvers are reused, with the same solutions up to tolerance
+    assert!(batch.stats.setups < 40);
+    assert_eq!(batch.stats.failed, 0);
+    for (i, (result, expected)) in batch.results.iter().zip(&expected).enumerate() {
+        let solution = result.as_ref().unwrap();
+        assert_eq!(solution.status, expected.status);
+        if i % 5 != 4 {
+            assert!(solution.x.dist(&expected.x) <= 1e-6);
+            assert!(f64::abs(solution.obj_val - expected.obj_val) <= 1e-6);
+        }
+    }
+}
+
+#[test]
+fn test_solve_batch_shared_pattern_mismatch() {
+    // a problem with a different pattern gets a new solver
+    let mut problems: Vec<_> = (0..6)
+        .map(|i| {
+            let mut p = box_qp(5, 0.3 * i as f64 + 0.1, -0.1, 0.1);
+            p.P = p.P.to_triu();
+            p
+        })
+        .collect();
+    problems[3].P = CscMatrix::identity(5);
+    let expected: Vec<_> = problems.iter().map(solve_single).collect();
+
+    let batch = DefaultSolver::solve_batch_shared_pattern(
+        problems,
+        &settings(),
+        BatchParallelism::Threads(1),
+    );
+
+    // solvers for the first problem, the mismatch and the one after it
+    assert_eq!(batch.stats.setups, 3);
+    for (result, expected) in batch.results.iter().zip(&expected) {
+        let solution = result.as_ref().unwrap();
+        assert_eq!(solution.status, SolverStatus::Solved);
+        assert!(solution.x.dist(&expected.x) <= 1e-6);
+    }
+}
+
+// Scaling of batch solves with the number of threads, on 100k problems
+// with n = 20 unless CLARABEL_BENCH_BATCH is set.   Run with
+//
+//   cargo test --release --features parallel --test batch_solve -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_solve_batch_scaling() {
+    let count: usize = std::env::var("CLARABEL_BENCH_BATCH")
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(100_000);
+    let problems: Vec<_> = (0..count)
+        .map(|i| box_qp(20, 0.1 + i as f64 / count as f64, -0.1, 0.1))
+        .collect();
+
+    let mut base = None;
+    for threads in [1, 2, 4, 8] {
+        let batch = DefaultSolver::solve_batch(
+            problems.clone(),
+            &settings(),
+            BatchParallelism::Threads(threads),
+        );
+        let time = batch.stats.wall_time;
+        let base = *base.get_or_insert(time);
+        println!(
+            "{} threads: {:8.3} s, speedup {:5.2}, {} solved",
+            threads,
+            time,
+            base / time,
+            batch.stats.solved
+        );
+    }
+}
@@ -0,0 +1,126 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. -1 ≤ x ≤ 1, with the lower bounds
+// as three dimensional second order cones if `soc` is set
+#[allow(clippy::type_complexity)]
+fn box_problem(
+    n: usize,
+    P: CscMatrix<f64>,
+    soc: bool,
+) -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let q: Vec<f64> = (0..n).map(|i| ((i + 1) as f64).sin()).collect();
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &I);
+    let b = vec![1.; 2 * n];
+    let cones = if soc {
+        let k = n / 3;
+        let mut cones = vec![NonnegativeConeT(2 * n - 3 * k)];
+        cones.extend((0..k).map(|_| SecondOrderConeT(3)));
+        cones
+    } else {
+        vec![NonnegativeConeT(2 * n)]
+    };
+    (P, q, A, b, cones)
+}
+
+fn solve(n: usize, P: CscMatrix<f64>, soc: bool, batched: bool) -> DefaultSolver<f64> {
+    let (P, q, A, b, cones) = box_problem(n, P, soc);
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .batched_kkt_solves(batched)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+#[test]
+fn test_batched_matches_sequential() {
+    let n = 30;
+    let cases = [
+        (CscMatrix::zeros((n, n)), false),
+        (CscMatrix::identity(n), false),
+        (CscMatrix::identity(n), true),
+    ];
+
+    for (P, soc) in cases {
+        let batched = solve(n, P.clone(), soc, true);
+        let sequential = solve(n, P, soc, false);
+
+        // identical iterates, so identical solutions
+        let (s1, s2) = (&batched.solution, &sequential.solution);
+        assert_eq!(s1.status, SolverStatus::Solved);
+        assert_eq!(s1.status, s2.status);
+        assert_eq!(s1.iterations, s2.iterations);
+        assert_eq!(s1.x, s2.x);
+        assert_eq!(s1.z, s2.z);
+        assert_eq!(s1.s, s2.s);
+
+        // the initial constant solve is skipped when batched
+        let (c1, c2) = (batched.info.counters(), sequential.info.counters());
+        assert_eq!(c1.factorizations, c2.factorizations);
+        assert_eq!(c1.kkt_solves + 1, c2.kkt_solves);
+    }
+}
+
+#[test]
+fn test_batched_snapshot_matches_sequential() {
+    let n = 10;
+    let [batched, sequential] = [true, false].map(|batched| {
+        let (P, q, A, b, cones) = box_problem(n, CscMatrix::identity(n), true);
+        let settings = DefaultSettingsBuilder::default()
+            .verbose(false)
+            .batched_kkt_solves(batched)
+            .kkt_snapshot_iteration(2)
+            .build()
+            .unwrap();
+        let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+        solver.solve();
+        solver.kkt_snapshot().unwrap()
+    });
+
+    for (v1, v2) in [
+        (&batched.rhs_constant, &sequential.rhs_constant),
+        (&batched.lhs_constant, &sequential.lhs_constant),
+        (&batched.rhs_predictor, &sequential.rhs_predictor),
+        (&batched.lhs_predictor, &sequential.lhs_predictor),
+        (&batched.rhs_corrector, &sequential.rhs_corrector),
+        (&batched.lhs_corrector, &sequential.lhs_corrector),
+    ] {
+        assert!(!v1.is_empty());
+        assert_eq!(v1, v2);
+    }
+}
+
+// Per-iteration solve times for a box constrained QP with n = 200k
+// variables, with and without batched KKT solves.   Run with
+//
+//   cargo test --release --test batched_kkt_solves -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_batched_kkt_solves() {
+    let n = 200_000;
+    for soc in [false, true] {
+        for batched in [false, true] {
+            let solver = solve(n, CscMatrix::identity(n), soc, batched);
+            let solution = &solver.solution;
+            println!(
+                "soc = {:5}, batched = {:5}: {:3} iterations, {:8.3} ms per iteration",
+                soc,
+                batched,
+                solution.iterations,
+                solution.solve_time * 1e3 / solution.iterations as f64
+            );
+        }
+    }
+}
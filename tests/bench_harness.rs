@@ -0,0 +1,95 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "bench-harness")]
+
+use clarabel::io::bench::*;
+use clarabel::solver::*;
+use std::path::Path;
+
+// copy three of the reader test problems into a single directory,
+// along with a file that fails to load and one that is not a problem
+fn bench_dir() -> tempfile::TempDir {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let dir = tempfile::tempdir().unwrap();
+
+    for file in ["qps/HS21.QPS", "qps/HS35.QPS", "cbf/lp_mixed.cbf"] {
+        let src = data.join(file);
+        std::fs::copy(&src, dir.path().join(src.file_name().unwrap())).unwrap();
+    }
+    std::fs::write(dir.path().join("broken.cbf"), "VER\n3\nVAR\nnot a number\n").unwrap();
+    std::fs::write(dir.path().join("README.txt"), "not a problem").unwrap();
+
+    dir
+}
+
+fn run_bench_dir(dir: &Path) -> BenchReport {
+    let files = problem_files(dir).unwrap();
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    run_benchmarks(&files, &settings, 10.)
+}
+
+#[test]
+fn test_bench_harness_report() {
+    let dir = bench_dir();
+    let report = run_bench_dir(dir.path());
+    assert_eq!(report.time_limit, 10.);
+
+    let names: Vec<&str> = report.results.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["HS21", "HS35", "broken", "lp_mixed"]);
+
+    let refobj = [-99.96, 1. / 9., f64::NAN, 1.9];
+    for (r, obj) in report.results.iter().zip(refobj) {
+        if r.name == "broken" {
+            assert_eq!(r.status, "LoadError");
+            assert!(r.error.is_some());
+            assert!(r.objective.is_none() && r.iterations.is_none());
+            continue;
+        }
+        assert_eq!(r.status, "Solved");
+        assert!(r.is_solved());
+        assert!(f64::abs(r.objective.unwrap() - obj) <= 1e-6);
+        assert!(r.iterations.unwrap() > 0);
+        assert!(r.solve_time.unwrap() >= 0.);
+        assert!(r.r_prim.unwrap() <= 1e-8 && r.r_dual.unwrap() <= 1e-8);
+        assert!(r.error.is_none());
+    }
+
+    let summary = report.summary(10.);
+    assert_eq!((summary.problems, summary.solved), (4, 3));
+    assert!(summary.sgm_solve_time > 0. && summary.sgm_solve_time < 10.);
+    assert!(summary.sgm_iterations >= 1.);
+}
+
+#[test]
+fn test_bench_harness_csv_json() {
+    let dir = bench_dir();
+    let report = run_bench_dir(dir.path());
+
+    let mut csv = Vec::new();
+    report.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[0],
+        "name,status,objective,iterations,load_time,solve_time,r_prim,r_dual,error"
+    );
+    assert!(lines[1].starts_with("HS21,Solved,"));
+    assert!(lines[3].starts_with("broken,LoadError,,,"));
+
+    let mut json = Vec::new();
+    report.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains("\"name\": \"lp_mixed\""));
+    assert!(json.contains("\"status\": \"LoadError\""));
+}
+
+#[test]
+fn test_shifted_geometric_mean() {
+    assert!(f64::abs(shifted_geometric_mean(&[2., 2., 2.], 1.) - 2.) <= 1e-12);
+    assert!(f64::abs(shifted_geometric_mean(&[1., 7.], 1.) - 3.) <= 1e-12);
+    assert!(f64::abs(shifted_geometric_mean(&[4., 16.], 0.) - 8.) <= 1e-12);
+    assert!(shifted_geometric_mean(&[], 10.).is_nan());
+}
@@ -0,0 +1,137 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn borrowed_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // P = [4 1; 1 2] (triu), constraints x₁ + x₂ = 1, 0 ≤ x ≤ 0.7
+    let P = CscMatrix::new(2, 2, vec![0, 1, 3], vec![0, 0, 1], vec![4., 1., 2.]);
+    let A = CscMatrix::new(
+        5,
+        2,
+        vec![0, 3, 6],
+        vec![0, 1, 3, 0, 2, 4],
+        vec![1., -1., 1., 1., -1., 1.],
+    );
+    let q = vec![1., 1.];
+    let b = vec![1., 0., 0., 0.7, 0.7];
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(4)];
+    (P, q, A, b, cones)
+}
+
+fn settings() -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap()
+}
+
+// addresses of the index and value storage of a matrix
+fn ptrs<S: std::ops::Deref<Target = [usize]>>(
+    M: &CscMatrix<f64, S>,
+) -> (*const usize, *const usize, *const f64) {
+    (M.colptr.as_ptr(), M.rowval.as_ptr(), M.nzval.as_ptr())
+}
+
+#[test]
+fn test_borrowed_shares_patterns() {
+    let (P, q, A, b, cones) = borrowed_data();
+    let (P0, A0) = (P.clone(), A.clone());
+    let (Pptrs, Aptrs) = (ptrs(&P), ptrs(&A));
+
+    let mut owned = DefaultSolver::new(&P, &q, &A, &b, &cones, settings()).unwrap();
+    owned.solve();
+
+    let mut solver = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings()).unwrap();
+
+    // patterns are shared and only the values are copied
+    let (Pint, Aint) = (ptrs(&solver.data.P), ptrs(&solver.data.A));
+    assert_eq!((Pint.0, Pint.1), (Pptrs.0, Pptrs.1));
+    assert_eq!((Aint.0, Aint.1), (Aptrs.0, Aptrs.1));
+    assert_ne!(Pint.2, Pptrs.2);
+    assert_ne!(Aint.2, Aptrs.2);
+
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert_eq!(solver.solution.x, owned.solution.x);
+    assert_eq!(solver.solution.z, owned.solution.z);
+    assert_eq!(solver.solution.iterations, owned.solution.iterations);
+    drop(solver);
+
+    assert_eq!((P, A), (P0, A0));
+}
+
+#[test]
+fn test_borrowed_with_modified_pattern() {
+    // a full symmetric P is converted to triu and so can't be
+    // shared, while the pattern of A is still shared
+    let (_, q, A, b, cones) = borrowed_data();
+    let P = CscMatrix::new(2, 2, vec![0, 2, 4], vec![0, 1, 0, 1], vec![4., 1., 1., 2.]);
+    let (P0, A0) = (P.clone(), A.clone());
+
+    let owned = {
+        let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings()).unwrap();
+        solver.solve();
+        solver.solution.x.clone()
+    };
+
+    let mut solver = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings()).unwrap();
+    assert_eq!(solver.data.P.nnz(), 3);
+    assert_ne!(solver.data.P.colptr.as_ptr(), P.colptr.as_ptr());
+    assert_eq!(solver.data.A.colptr.as_ptr(), A.colptr.as_ptr());
+    solver.solve();
+    assert_eq!(solver.solution.x, owned);
+    drop(solver);
+
+    assert_eq!((P, A), (P0, A0));
+}
+
+#[test]
+fn test_borrowed_leaked_solver() {
+    // the caller's matrices are only ever read, so they are intact
+    // even if the solver is never dropped
+    let (P, q, A, b, cones) = borrowed_data();
+    let (P0, A0) = (P.clone(), A.clone());
+
+    let mut solver = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings()).unwrap();
+    solver.solve();
+    std::mem::forget(solver);
+
+    assert_eq!((P, A), (P0, A0));
+}
+
+#[test]
+fn test_borrowed_data_update() {
+    // updating the values of a borrowed pattern leaves the caller's
+    // matrices unchanged, and matches an owned solver's update
+    let (P, q, A, b, cones) = borrowed_data();
+    let (P0, A0) = (P.clone(), A.clone());
+    let Pv = vec![3., 0.5, 1.];
+    let settings = || {
+        DefaultSettingsBuilder::default()
+            .verbose(false)
+            .presolve_enable(false)
+            .presolve_reductions_enable(false)
+            .build()
+            .unwrap()
+    };
+
+    let mut owned = DefaultSolver::new(&P, &q, &A, &b, &cones, settings()).unwrap();
+    owned.update_P(&Pv).unwrap();
+    owned.solve();
+
+    let mut solver = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings()).unwrap();
+    solver.update_P(&Pv).unwrap();
+    assert_eq!(ptrs(&solver.data.P).0, P.colptr.as_ptr());
+    solver.solve();
+    assert_eq!(solver.solution.x, owned.solution.x);
+    drop(solver);
+
+    assert_eq!((P, A), (P0, A0));
+}
@@ -0,0 +1,74 @@
+#![cfg(all(feature = "capi", unix))]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+// The committed include/clarabel.h must match the header generated
+// from src/capi by build.rs.   Regenerate it with
+//
+//   CLARABEL_UPDATE_HEADER=1 cargo build --features capi
+
+#[test]
+fn test_capi_header_is_current() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let committed = std::fs::read_to_string(crate_dir.join("include/clarabel.h")).unwrap();
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/clarabel.h"));
+    assert!(
+        committed == generated,
+        "include/clarabel.h is out of date with src/capi"
+    );
+}
+
+// Compiles tests/capi/test_capi.c against the cdylib and checks that
+// it runs successfully.   The cdylib is not a default crate type, so
+// it is built here into a separate target directory.
+
+#[test]
+fn test_capi_c_program() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let exe = std::env::current_exe().unwrap();
+    let target_dir = exe.parent().unwrap().parent().unwrap().join("capi");
+    let status = Command::new(env!("CARGO"))
+        .arg("rustc")
+        .arg("--lib")
+        .arg("--crate-type")
+        .arg("cdylib")
+        .arg("--features")
+        .arg("capi")
+        .arg("--manifest-path")
+        .arg(crate_dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("unable to run cargo");
+    assert!(status.success());
+    let libdir = target_dir.join("debug");
+
+    let outdir = tempfile::tempdir().unwrap();
+    let program = outdir.path().join("test_capi");
+
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .arg(crate_dir.join("tests/capi/test_capi.c"))
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-L")
+        .arg(&libdir)
+        .arg("-lclarabel")
+        .arg("-lm")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .expect("unable to run the C compiler");
+    assert!(status.success());
+
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &libdir)
+        .env("DYLD_LIBRARY_PATH", &libdir)
+        .output()
+        .unwrap();
+    println!("{}", String::from_utf8_lossy(&output.stdout));
+    eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+}
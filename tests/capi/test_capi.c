@@ -0,0 +1,94 @@
+// Test program for the C interface, compiled and run from tests/capi.rs.
+// Returns zero on success.
+
+#include <math.h>
+#include <stdio.h>
+#include "clarabel.h"
+
+#define CHECK(cond)                                                   \
+    do {                                                              \
+        if (!(cond)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,   \
+                    __LINE__, #cond);                                 \
+            return 1;                                                 \
+        }                                                             \
+    } while (0)
+
+int main(void) {
+    // min ½(6x₁² + 4x₂²) - 4x₁ - x₂  s.t.  x₁ - 2x₂ ≤ 0,  x₁, x₂ ∈ [-1, 1]
+    size_t P_colptr[] = {0, 1, 2};
+    size_t P_rowval[] = {0, 1};
+    double P_nzval[] = {6.0, 4.0};
+    ClarabelCscMatrix P = {2, 2, P_colptr, P_rowval, P_nzval};
+
+    double q[] = {-4.0, -1.0};
+
+    size_t A_colptr[] = {0, 3, 6};
+    size_t A_rowval[] = {0, 1, 3, 0, 2, 4};
+    double A_nzval[] = {1.0, 1.0, -1.0, -2.0, 1.0, -1.0};
+    ClarabelCscMatrix A = {5, 2, A_colptr, A_rowval, A_nzval};
+
+    double b[] = {0.0, 1.0, 1.0, 1.0, 1.0};
+
+    ClarabelCone cones[] = {{ClarabelNonnegativeCone, 5, 0.0, NULL, 0}};
+
+    ClarabelSettings settings = clarabel_default_settings();
+    settings.verbose = false;
+    settings.presolve_enable = false;
+
+    // invalid inputs are rejected
+    CHECK(clarabel_new(NULL, q, &A, b, cones, 1, &settings) == NULL);
+    ClarabelCone bad_cones[] = {{ClarabelNonnegativeCone, 4, 0.0, NULL, 0}};
+    CHECK(clarabel_new(&P, q, &A, b, bad_cones, 1, &settings) == NULL);
+    ClarabelCone bad_tag[] = {{99, 5, 0.0, NULL, 0}};
+    CHECK(clarabel_new(&P, q, &A, b, bad_tag, 1, &settings) == NULL);
+    CHECK(clarabel_solve(NULL) == CLARABEL_ERR_NULL);
+
+    ClarabelSolver *solver = clarabel_new(&P, q, &A, b, cones, 1, &settings);
+    CHECK(solver != NULL);
+
+    CHECK(clarabel_solve(solver) == ClarabelSolved);
+
+    size_t n, m;
+    CHECK(clarabel_get_dims(solver, &n, &m) == 0);
+    CHECK(n == 2 && m == 5);
+
+    double x[2], z[5], s[5];
+    CHECK(clarabel_get_x(solver, x, 2) == 0);
+    CHECK(clarabel_get_z(solver, z, 5) == 0);
+    CHECK(clarabel_get_s(solver, s, 5) == 0);
+    CHECK(clarabel_get_x(solver, x, 3) == CLARABEL_ERR_DATA);
+    CHECK(clarabel_get_x(solver, NULL, 2) == CLARABEL_ERR_NULL);
+    CHECK(fabs(x[0] - 18.0 / 28.0) < 1e-5);
+    CHECK(fabs(x[1] - 9.0 / 28.0) < 1e-5);
+
+    ClarabelInfo info;
+    CHECK(clarabel_get_info(solver, &info) == 0);
+    CHECK(info.status == ClarabelSolved);
+    CHECK(info.iterations > 0);
+    CHECK(fabs(info.obj_val - -81.0 / 56.0) < 1e-5);
+
+    // relax the first constraint to x₁ - 2x₂ ≤ 0.5 and solve again
+    double q2[] = {-1.0, -1.0};
+    double b2[] = {0.5, 1.0, 1.0, 1.0, 1.0};
+    CHECK(clarabel_update_q(solver, q2, 1) == CLARABEL_ERR_DATA);
+    CHECK(clarabel_update_q(solver, NULL, 2) == CLARABEL_ERR_NULL);
+    CHECK(clarabel_update_b(solver, b2, 5) == 0);
+    CHECK(clarabel_solve(solver) == ClarabelSolved);
+    CHECK(clarabel_get_x(solver, x, 2) == 0);
+    CHECK(fabs(x[0] - 2.0 / 3.0) < 1e-5);
+    CHECK(fabs(x[1] - 0.25) < 1e-5);
+
+    clarabel_free(solver);
+    clarabel_free(NULL);
+
+    // updates are rejected with presolve enabled
+    settings.presolve_enable = true;
+    solver = clarabel_new(&P, q, &A, b, cones, 1, &settings);
+    CHECK(solver != NULL);
+    CHECK(clarabel_update_q(solver, q2, 2) == CLARABEL_ERR_UPDATE);
+    clarabel_free(solver);
+
+    printf("capi tests passed\n");
+    return 0;
+}
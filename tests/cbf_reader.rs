@@ -0,0 +1,111 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "io")]
+
+use clarabel::io::*;
+use clarabel::solver::*;
+use std::fs::File;
+use std::io::BufReader;
+
+fn read_test_file(name: &str) -> ConicProblem<f64> {
+    let path = format!("{}/tests/data/cbf/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let file = File::open(path).unwrap();
+    read_cbf(BufReader::new(file)).unwrap()
+}
+
+fn solve_to_objective(problem: &ConicProblem<f64>) -> (f64, Vec<f64>) {
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    let obj = solver.solution.obj_val + problem.objective_constant;
+    (obj, solver.solution.x.clone())
+}
+
+#[test]
+fn test_cbf_lp_mixed() {
+    let problem = read_test_file("lp_mixed.cbf");
+
+    assert_eq!(problem.q.len(), 3);
+    assert_eq!((problem.A.m, problem.A.n), (6, 3));
+    assert_eq!(problem.sense, OptimizationSense::Minimize);
+    assert_eq!(problem.objective_constant, 0.5);
+
+    // the L+ and L- rows merge into one nonnegative cone
+    assert!(matches!(
+        problem.cones[..],
+        [NonnegativeConeT(3), ZeroConeT(1), NonnegativeConeT(2)]
+    ));
+
+    let (obj, x) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - 1.9) <= 1e-6);
+    let refsol = [0.8, 0.6, 1.4];
+    assert!(x.iter().zip(refsol).all(|(a, b)| f64::abs(a - b) <= 1e-6));
+}
+
+#[test]
+fn test_cbf_socp_rotated() {
+    let problem = read_test_file("socp_rotated.cbf");
+
+    assert_eq!(problem.sense, OptimizationSense::Maximize);
+    assert!(matches!(
+        problem.cones[..],
+        [
+            ZeroConeT(1),
+            NonnegativeConeT(1),
+            SecondOrderConeT(3),
+            SecondOrderConeT(3)
+        ]
+    ));
+
+    let (obj, x) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - 2.0) <= 1e-6);
+    let refsol = [1.0, 2.0, 2.0];
+    assert!(x.iter().zip(refsol).all(|(a, b)| f64::abs(a - b) <= 1e-5));
+}
+
+#[test]
+fn test_cbf_expcone() {
+    let problem = read_test_file("expcone.cbf");
+    assert!(matches!(problem.cones[..], [ExponentialConeT()]));
+
+    let (obj, _) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - 5.0) <= 1e-6);
+}
+
+#[test]
+fn test_cbf_unsupported() {
+    let psd = "VER\n3\nPSDVAR\n1\n2\n";
+    let err = read_cbf::<f64, _>(psd.as_bytes()).unwrap_err();
+    assert!(matches!(err, CbfError::Unsupported { line: 3, .. }));
+
+    let dualexp = "VER\n3\nVAR\n3 1\nEXP* 3\n";
+    let err = read_cbf::<f64, _>(dualexp.as_bytes()).unwrap_err();
+    assert!(matches!(err, CbfError::Unsupported { line: 5, .. }));
+    assert_eq!(err.to_string(), "line 5: unsupported cone EXP*");
+
+    let integer = "VER\n3\nVAR\n1 1\nF 1\nINT\n1\n0\n";
+    let err = read_cbf::<f64, _>(integer.as_bytes()).unwrap_err();
+    assert!(matches!(err, CbfError::Unsupported { .. }));
+}
+
+#[test]
+fn test_cbf_bad_input() {
+    // index out of range
+    let bad = "VER\n3\nVAR\n1 1\nF 1\nOBJACOORD\n1\n3 1.0\n";
+    let err = read_cbf::<f64, _>(bad.as_bytes()).unwrap_err();
+    assert!(matches!(err, CbfError::Parse { line: 8, .. }));
+
+    // mismatched cone dimensions
+    let bad = "VER\n3\nVAR\n4 1\nL+ 3\n";
+    let err = read_cbf::<f64, _>(bad.as_bytes()).unwrap_err();
+    assert!(matches!(err, CbfError::Parse { .. }));
+
+    // missing version
+    let bad = "VAR\n1 1\nF 1\n";
+    let err = read_cbf::<f64, _>(bad.as_bytes()).unwrap_err();
+    assert!(matches!(err, CbfError::Parse { .. }));
+}
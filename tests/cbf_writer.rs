@@ -0,0 +1,100 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "io")]
+
+use clarabel::algebra::*;
+use clarabel::io::*;
+use clarabel::solver::*;
+use std::fs::File;
+use std::io::BufReader;
+
+fn read_test_file(name: &str) -> ConicProblem<f64> {
+    let path = format!("{}/tests/data/cbf/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let file = File::open(path).unwrap();
+    read_cbf(BufReader::new(file)).unwrap()
+}
+
+fn roundtrip(problem: &ConicProblem<f64>) -> ConicProblem<f64> {
+    let mut buffer = Vec::new();
+    write_cbf(problem, &mut buffer).unwrap();
+    read_cbf(buffer.as_slice()).unwrap()
+}
+
+fn solve(problem: &ConicProblem<f64>) -> (f64, Vec<f64>) {
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    let obj = solver.solution.obj_val + problem.objective_constant;
+    (obj, solver.solution.x.clone())
+}
+
+#[test]
+fn test_cbf_write_roundtrip() {
+    for name in ["lp_mixed.cbf", "socp_rotated.cbf", "expcone.cbf"] {
+        let problem = read_test_file(name);
+        let copy = roundtrip(&problem);
+
+        assert_eq!(copy.sense, problem.sense);
+        assert_eq!(copy.q, problem.q);
+        assert_eq!(copy.b, problem.b);
+        assert_eq!(copy.objective_constant, problem.objective_constant);
+        assert_eq!(format!("{:?}", copy.cones), format!("{:?}", problem.cones));
+
+        let (obj, x) = solve(&problem);
+        let (objcopy, xcopy) = solve(&copy);
+        assert!(f64::abs(obj - objcopy) <= 1e-8);
+        assert!(x.dist(&xcopy) <= 1e-8);
+    }
+}
+
+#[test]
+fn test_cbf_write_unsupported() {
+    let problem = ConicProblem {
+        P: CscMatrix::zeros((3, 3)),
+        q: vec![1., 0., 0.],
+        A: CscMatrix::identity(3),
+        b: vec![1., 1., 1.],
+        cones: vec![PowerConeT(0.5)],
+        sense: OptimizationSense::Minimize,
+        objective_constant: 0.,
+    };
+    let err = write_cbf(&problem, Vec::new()).unwrap_err();
+    assert!(matches!(err, CbfError::UnsupportedCone(_)));
+}
+
+#[test]
+fn test_cbf_write_quadratic_lift() {
+    // min ½(4x₁² + x₂²) - 4x₁ - x₂  s.t.  x₁ + x₂ <= 1, x >= 0
+    // optimum -2.1 at x = (0.8, 0.2)
+    let problem = ConicProblem {
+        P: CscMatrix::from(&[[4., 0.], [0., 1.]]),
+        q: vec![-4., -1.],
+        A: CscMatrix::from(&[[1., 1.], [-1., 0.], [0., -1.]]),
+        b: vec![1., 0., 0.],
+        cones: vec![NonnegativeConeT(3)],
+        sense: OptimizationSense::Minimize,
+        objective_constant: 0.,
+    };
+
+    let err = write_cbf(&problem, Vec::new()).unwrap_err();
+    assert!(matches!(err, CbfError::QuadraticObjective));
+
+    let F = CscMatrix::from(&[[2., 0.], [0., 1.]]);
+    let lifted = problem.lift_quadratic_objective(&F);
+    assert_eq!((lifted.A.m, lifted.A.n), (7, 3));
+
+    let copy = roundtrip(&lifted);
+    let (obj, x) = solve(&problem);
+    let (objcopy, xcopy) = solve(&copy);
+
+    assert!(f64::abs(obj + 2.1) <= 1e-6);
+    assert!(f64::abs(obj - objcopy) <= 1e-6);
+    assert!(x.dist(&xcopy[0..2]) <= 1e-3);
+
+    // the epigraph variable is tight at the optimum
+    assert!(f64::abs(xcopy[2] - 1.3) <= 1e-3);
+}
@@ -0,0 +1,101 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn conservative_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // an LP whose final iterations stall at short steps
+    // when the full Mehrotra correction is applied
+    let P = CscMatrix::<f64>::zeros((6, 6));
+    let q = vec![-0.014, 0.363, -0.478, -0.778, -0.399, 0.850];
+
+    let A = CscMatrix::from(&[
+        [0.000, 0.000, -0.125, -0.793, 0.000, 0.349],
+        [0.000, 0.000, 0.000, 0.000, 0.000, -0.430],
+        [0.802, -0.523, -0.178, -0.116, -0.395, 0.600],
+        [-0.572, 0.733, 0.158, 0.066, 0.702, -0.420],
+        [0.054, -0.757, 0.000, 0.000, 0.853, 0.861],
+        [0.476, 0.000, 0.000, 0.000, -0.831, -0.186],
+        [-0.699, 0.000, 0.000, -0.496, 0.856, 0.000],
+        [0.603, 0.837, 0.000, 0.473, 0.000, 0.216],
+        [0.000, 0.882, 0.000, -0.957, 0.588, 0.212],
+        [-0.971, 0.000, -0.499, 0.000, 0.886, 0.000],
+        [0.257, -0.772, 0.939, 0.000, 0.000, 0.000],
+        [0.439, 0.000, 0.176, 0.746, -0.472, 0.000],
+    ]);
+    let b = vec![
+        0.262, 1.306, 0.115, 0.524, 0.864, 1.694, 1.798, 1.914, 0.991, 0.170, 1.791, 0.102,
+    ];
+
+    let cones = vec![NonnegativeConeT(9), SecondOrderConeT(3)];
+
+    (P, q, A, b, cones)
+}
+
+#[test]
+fn test_conservative_steps_fewer_iterations() {
+    let (P, q, A, b, cones) = conservative_test_data();
+
+    let settings = DefaultSettings::default();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+    let iters_default = solver.info.iterations;
+    assert!(solver.info.recent_history().iter().all(|r| !r.conservative));
+
+    let settings = DefaultSettingsBuilder::default()
+        .conservative_steps_enable(true)
+        .conservative_steps_gap_rel(1e-3)
+        .conservative_step_fraction(0.95)
+        .postmortem_window(100)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+
+    assert!(solver.info.iterations < iters_default);
+
+    // conservative mode engages once and stays on, with
+    // steps no longer than the capped fraction
+    let history = solver.info.recent_history();
+    let first = history.iter().position(|r| r.conservative).unwrap();
+    assert!(first > 0);
+    assert!(history[first].gap_rel < 1e-3);
+    assert!(history[first..].iter().all(|r| r.conservative));
+    assert!(history[first + 1..]
+        .iter()
+        .all(|r| r.step_length <= 0.95 + 1e-12));
+}
+
+#[test]
+fn test_conservative_steps_disabled_unchanged() {
+    let (P, q, A, b, cones) = conservative_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .postmortem_window(100)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    let history_default = solver.info.recent_history();
+
+    // thresholds have no effect while the heuristic is off
+    let settings = DefaultSettingsBuilder::default()
+        .conservative_steps_enable(false)
+        .conservative_steps_gap_rel(1.0)
+        .conservative_step_fraction(0.5)
+        .postmortem_window(100)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.info.recent_history(), history_default);
+}
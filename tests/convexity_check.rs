@@ -0,0 +1,92 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. -1 ≤ x ≤ 1, with three variables
+fn problem_data() -> (Vec<f64>, CscMatrix<f64>, Vec<f64>, Vec<SupportedConeT<f64>>) {
+    let q = vec![1., -1., 1.];
+    let mut Ineg = CscMatrix::identity(3);
+    Ineg.negate();
+    let A = CscMatrix::vcat(&CscMatrix::identity(3), &Ineg);
+    let b = vec![1.; 6];
+    let cones = vec![NonnegativeConeT(6)];
+    (q, A, b, cones)
+}
+
+fn settings(check_convexity: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .check_convexity(check_convexity)
+        .build()
+        .unwrap()
+}
+
+fn new_solver(
+    P: &CscMatrix<f64>,
+    check_convexity: bool,
+) -> Result<DefaultSolver<f64>, ClarabelError> {
+    let (q, A, b, cones) = problem_data();
+    DefaultSolver::new(P, &q, &A, &b, &cones, settings(check_convexity))
+}
+
+#[test]
+fn test_indefinite_P_caught_by_factorization() {
+    // positive diagonal, but the trailing 2×2 block has
+    // eigenvalues 3 and -1
+    let P = CscMatrix::new(
+        3,
+        3,
+        vec![0, 1, 2, 4],
+        vec![0, 1, 1, 2],
+        vec![1., 1., 2., 1.],
+    );
+
+    // the cheap check passes, and the setup is not checked by default
+    assert!(new_solver(&P, false).is_ok());
+
+    match new_solver(&P, true) {
+        Err(ClarabelError::NonConvexObjective { index }) => assert!(index == 1 || index == 2),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("indefinite P was accepted"),
+    }
+}
+
+#[test]
+fn test_negative_diagonal_P_caught() {
+    let P = CscMatrix::new(3, 3, vec![0, 1, 2, 3], vec![0, 1, 2], vec![1., -1., 1.]);
+
+    for check_convexity in [false, true] {
+        assert!(matches!(
+            new_solver(&P, check_convexity),
+            Err(ClarabelError::NonConvexObjective { index: 1 })
+        ));
+    }
+}
+
+#[test]
+fn test_semidefinite_P_accepted() {
+    // the trailing 2×2 block is singular, with eigenvalues 2 and 0
+    let P = CscMatrix::new(
+        3,
+        3,
+        vec![0, 1, 2, 4],
+        vec![0, 1, 1, 2],
+        vec![2., 1., 1., 1.],
+    );
+
+    for check_convexity in [false, true] {
+        let mut solver = new_solver(&P, check_convexity).unwrap();
+        solver.solve();
+        assert_eq!(solver.solution.status, SolverStatus::Solved);
+    }
+
+    // also for a full symmetric P
+    let Pfull = CscMatrix::new(
+        3,
+        3,
+        vec![0, 1, 3, 5],
+        vec![0, 1, 2, 1, 2],
+        vec![2., 1., 1., 1., 1.],
+    );
+    assert!(new_solver(&Pfull, true).is_ok());
+}
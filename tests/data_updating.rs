@@ -44,7 +44,7 @@ fn updating_test_data() -> (
 fn test_update_P_matrix_form() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     //solver1.solve();
 
     // change P and re-solve
@@ -56,7 +56,7 @@ fn test_update_P_matrix_form() {
     solver1.solve();
 
     //new solver
-    let mut solver2 = DefaultSolver::new(&P2, &q, &A, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P2, &q, &A, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -66,7 +66,7 @@ fn test_update_P_matrix_form() {
 fn test_update_P_vector_form() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // change P and re-solve
@@ -78,7 +78,7 @@ fn test_update_P_vector_form() {
     solver1.solve();
 
     //new solver
-    let mut solver2 = DefaultSolver::new(&P2, &q, &A, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P2, &q, &A, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -88,7 +88,7 @@ fn test_update_P_vector_form() {
 fn test_update_P_tuple() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // revised original solver
@@ -103,7 +103,7 @@ fn test_update_P_tuple() {
         [4., 3.], //
         [0., 5.], //
     ]);
-    let mut solver2 = DefaultSolver::new(&P2, &q, &A, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P2, &q, &A, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -113,7 +113,7 @@ fn test_update_P_tuple() {
 fn test_update_A_matrix_form() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     //solver1.solve();
 
     // change A and re-solve
@@ -128,7 +128,7 @@ fn test_update_A_matrix_form() {
     solver1.solve();
 
     //new solver
-    let mut solver2 = DefaultSolver::new(&P, &q, &A2, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q, &A2, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -138,7 +138,7 @@ fn test_update_A_matrix_form() {
 fn test_update_A_vector_form() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // change A and re-solve
@@ -153,7 +153,7 @@ fn test_update_A_vector_form() {
     solver1.solve();
 
     //new solver
-    let mut solver2 = DefaultSolver::new(&P, &q, &A2, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q, &A2, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -163,7 +163,7 @@ fn test_update_A_vector_form() {
 fn test_update_A_tuple_form() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // revised original solver
@@ -177,17 +177,61 @@ fn test_update_A_tuple_form() {
     let mut A2 = A;
     A2.nzval[1] = 0.5;
     A2.nzval[2] = -0.5;
-    let mut solver2 = DefaultSolver::new(&P, &q, &A2, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q, &A2, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
 }
 
+#[test]
+fn test_update_A_explicit_transpose() {
+    // A dense A, so that A and A' store their entries in different orders
+    let (P, q, _, _, _, settings) = updating_test_data();
+    let A = CscMatrix::from(&[
+        [1., 2.],  //
+        [3., -4.], //
+        [-1., 1.], //
+        [2., 1.],
+    ]);
+    let b = vec![1.; 4];
+    let cones = vec![NonnegativeConeT(4)];
+
+    let mut settings1 = settings.clone();
+    settings1.explicit_transpose_min_nnz = 0;
+    let mut settings2 = settings;
+    settings2.explicit_transpose_min_nnz = usize::MAX;
+
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings1).unwrap();
+    let mut solver2 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings2).unwrap();
+    assert!(solver1.data.A_transpose().is_some());
+    assert!(solver2.data.A_transpose().is_none());
+
+    // change A and re-solve
+    let mut A2 = A;
+    A2.nzval
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, v)| *v *= 1. + i as f64);
+    assert!(solver1.update_A(&A2.nzval).is_ok());
+    assert!(solver2.update_A(&A2.nzval).is_ok());
+
+    // the transpose follows the update of the scaled A
+    let At: CscMatrix<f64> = solver1.data.A.t().into();
+    assert_eq!(solver1.data.A_transpose(), Some(&At));
+
+    // and products with it give identical results
+    solver1.solve();
+    solver2.solve();
+    assert_eq!(solver1.solution.status, SolverStatus::Solved);
+    assert_eq!(solver1.solution.x, solver2.solution.x);
+    assert_eq!(solver1.solution.iterations, solver2.solution.iterations);
+}
+
 #[test]
 fn test_update_q() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // change 1 and re-solve
@@ -199,7 +243,7 @@ fn test_update_q() {
     solver1.solve();
 
     //new solver
-    let mut solver2 = DefaultSolver::new(&P, &q2, &A, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q2, &A, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -209,7 +253,7 @@ fn test_update_q() {
 fn test_update_q_tuple() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // revised original solver
@@ -222,7 +266,7 @@ fn test_update_q_tuple() {
     //new solver
     let mut q2 = q;
     q2[1] = 10.;
-    let mut solver2 = DefaultSolver::new(&P, &q2, &A, &b, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q2, &A, &b, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -232,7 +276,7 @@ fn test_update_q_tuple() {
 fn test_update_b() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // change 1 and re-solve
@@ -244,7 +288,7 @@ fn test_update_b() {
     solver1.solve();
 
     //new solver
-    let mut solver2 = DefaultSolver::new(&P, &q, &A, &b2, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q, &A, &b2, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -254,7 +298,7 @@ fn test_update_b() {
 fn test_update_b_tuple() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone());
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
     solver1.solve();
 
     // revised original solver
@@ -266,7 +310,7 @@ fn test_update_b_tuple() {
 
     //new solver
     let b2 = vec![1., 0., 1., 0.];
-    let mut solver2 = DefaultSolver::new(&P, &q, &A, &b2, &cones, settings);
+    let mut solver2 = DefaultSolver::new(&P, &q, &A, &b2, &cones, settings).unwrap();
     solver2.solve();
 
     assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
@@ -276,7 +320,7 @@ fn test_update_b_tuple() {
 fn test_update_noops() {
     // original problem
     let (P, q, A, b, cones, settings) = updating_test_data();
-    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
     solver.solve();
 
     // apply no-op updates to check for crashes
@@ -308,7 +352,7 @@ fn test_fail_on_presolve_enable() {
     // original problem
     let (P, q, A, b, cones, mut settings) = updating_test_data();
     settings.presolve_enable = true;
-    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
     solver.solve();
 
     // apply no-op updates to check that updates are rejected
@@ -329,3 +373,29 @@ fn test_fail_on_presolve_enable() {
         Some(DataUpdateError::PresolveEnabled)
     ));
 }
+
+#[test]
+fn test_update_with_cost_scaling() {
+    // badly scaled objective, so that equilibration applies a
+    // cost scaling that updates to P and q must reapply
+    let (P, q, A, b, cones, settings) = updating_test_data();
+    let mut P = P.to_triu();
+    P.scale(10.);
+    let q: Vec<f64> = q.iter().map(|&v| -30. * v).collect();
+    let mut solver1 = DefaultSolver::new(&P, &q, &A, &b, &cones, settings.clone()).unwrap();
+    solver1.solve();
+
+    let mut P2 = P.clone();
+    P2.nzval[0] = 400.;
+    let q2 = vec![-300., 7.];
+    solver1.update_P(&P2).unwrap();
+    solver1.update_q(&q2).unwrap();
+    solver1.solve();
+
+    let mut solver2 = DefaultSolver::new(&P2, &q2, &A, &b, &cones, settings).unwrap();
+    solver2.solve();
+
+    assert!(solver1.solution.x.dist(&solver2.solution.x) <= 1e-7);
+    assert!(solver1.solution.z.dist(&solver2.solution.z) <= 1e-6);
+    assert!(f64::abs(solver1.solution.obj_val - solver2.solution.obj_val) <= 1e-6);
+}
@@ -0,0 +1,195 @@
+#![allow(non_snake_case)]
+
+use clarabel::algebra::*;
+use clarabel::solver::*;
+
+// min ½xᵀPx + qᵀx  s.t.  x₁ = x₂ and x within a polygon
+// approximating the unit disk, with many more constraints
+// than variables
+fn polygon_data(nsides: usize) -> (CscMatrix<f64>, Vec<f64>, Vec<SupportedConeT<f64>>) {
+    let (mut I, mut J, mut V) = (vec![0, 0], vec![0, 1], vec![1.0, -1.0]);
+    let mut b = vec![0.0];
+
+    for k in 0..nsides {
+        let theta = 2.0 * std::f64::consts::PI * (k as f64 + 0.5) / (nsides as f64);
+        I.extend([k + 1, k + 1]);
+        J.extend([0, 1]);
+        V.extend([theta.cos(), theta.sin()]);
+        b.push(1.0);
+    }
+
+    let A = CscMatrix::new_from_triplets(nsides + 1, 2, I, J, V);
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(nsides)];
+    (A, b, cones)
+}
+
+fn settings(auto_dualize: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(false)
+        .auto_dualize(auto_dualize)
+        .build()
+        .unwrap()
+}
+
+fn solve(
+    P: &CscMatrix<f64>,
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    cones: &[SupportedConeT<f64>],
+) -> DefaultSolution<f64> {
+    let mut solver = DefaultSolver::new(P, q, A, b, cones, settings(false)).unwrap();
+    solver.solve();
+    solver.solution
+}
+
+fn assert_solutions_match(sol: &DefaultSolution<f64>, reference: &DefaultSolution<f64>) {
+    assert_eq!(sol.status, SolverStatus::Solved);
+    assert_eq!(reference.status, SolverStatus::Solved);
+    assert!(f64::abs(sol.obj_val - reference.obj_val) <= 1e-6);
+    assert!(f64::abs(sol.obj_val_dual - reference.obj_val_dual) <= 1e-6);
+    assert!(sol.x.dist(&reference.x) <= 1e-5);
+    assert!(sol.s.dist(&reference.s) <= 1e-5);
+    assert!(sol.z.dist(&reference.z) <= 1e-5);
+}
+
+#[test]
+fn test_dualize_lp() {
+    let (A, b, cones) = polygon_data(40);
+    let P = CscMatrix::zeros((2, 2));
+    let q = vec![-1.0, -1.0];
+
+    let dual = DualTransform::new(&P, &q, &A, &b, &cones).unwrap();
+
+    // no w variables when P = 0
+    assert_eq!((dual.A.m, dual.A.n), (42, 41));
+    assert_eq!(dual.q, b);
+    assert!(matches!(
+        dual.cones[..],
+        [ZeroConeT(2), NonnegativeConeT(40)]
+    ));
+
+    let mut solver = dual.solver(settings(false)).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    // optimal objectives of the primal and dual agree
+    let primal = solve(&P, &q, &A, &b, &cones);
+    assert!(f64::abs(solver.solution.obj_val + primal.obj_val) <= 1e-6);
+
+    let sol = dual.recover_primal(&solver.solution);
+    assert_solutions_match(&sol, &primal);
+}
+
+#[test]
+fn test_dualize_qp_socp() {
+    // add ‖x‖ ≤ 0.9 to the polygon constraints
+    let (A, mut b, mut cones) = polygon_data(30);
+    let A2 = CscMatrix::from(&[
+        [0.0, 0.0],  //
+        [-1.0, 0.0], //
+        [0.0, -1.0], //
+    ]);
+    let A = CscMatrix::vcat(&A, &A2);
+    b.extend([0.9, 0.0, 0.0]);
+    cones.push(SecondOrderConeT(3));
+
+    let P = CscMatrix::from(&[
+        [2.0, 1.0], //
+        [0.0, 4.0], //
+    ]);
+    let q = vec![-3.0, -1.0];
+
+    let dual = DualTransform::new(&P, &q, &A, &b, &cones).unwrap();
+    assert_eq!((dual.A.m, dual.A.n), (35, 36));
+
+    let mut solver = dual.solver(settings(false)).unwrap();
+    solver.solve();
+
+    let primal = solve(&P, &q, &A, &b, &cones);
+    let sol = dual.recover_primal(&solver.solution);
+    assert_solutions_match(&sol, &primal);
+}
+
+#[test]
+fn test_dualize_setting() {
+    let (A, b, cones) = polygon_data(40);
+    let P = CscMatrix::from(&[
+        [1.0, 0.0], //
+        [0.0, 1.0], //
+    ]);
+    let q = vec![-3.0, -3.0];
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true)).unwrap();
+    solver.solve();
+
+    // the dual problem has a variable for each constraint
+    assert_eq!(solver.variables.x.len(), 43);
+    assert_eq!(solver.solution.x.len(), 2);
+    assert!(matches!(
+        solver.update_q(&vec![1.0, 1.0]),
+        Err(DataUpdateError::Dualized)
+    ));
+
+    let primal = solve(&P, &q, &A, &b, &cones);
+    assert_solutions_match(&solver.solution, &primal);
+
+    // problems with few constraints are not dualized
+    let (A, b, cones) = polygon_data(12);
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true)).unwrap();
+    solver.solve();
+    assert_eq!(solver.variables.x.len(), 2);
+}
+
+#[test]
+fn test_dualize_infeasible() {
+    // x₁ ≥ 5 is outside of the polygon
+    let (A, b, cones) = polygon_data(40);
+    let A = CscMatrix::vcat(&A, &CscMatrix::from(&[[-1.0, 0.0]]));
+    let b = [b, vec![-5.0]].concat();
+    let cones = [cones, vec![NonnegativeConeT(1)]].concat();
+
+    let P = CscMatrix::zeros((2, 2));
+    let q = vec![-1.0, -1.0];
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true)).unwrap();
+    solver.solve();
+    assert_eq!(solver.variables.x.len(), 42);
+    assert_eq!(solver.solution.status, SolverStatus::PrimalInfeasible);
+
+    // the certificate satisfies Aᵀz = 0 and bᵀz < 0, z ≥ 0
+    let z = &solver.solution.z;
+    let Atz: Vec<f64> = (0..A.n)
+        .map(|col| {
+            (A.colptr[col]..A.colptr[col + 1])
+                .map(|k| A.nzval[k] * z[A.rowval[k]])
+                .sum()
+        })
+        .collect();
+    assert!(Atz.norm_inf() <= 1e-6 * z.norm_inf());
+    assert!(b.dot(z) < 0.0);
+    assert!(z[1..].iter().all(|&v| v >= -1e-8));
+}
+
+#[test]
+fn test_dualize_unsupported() {
+    let P = CscMatrix::zeros((3, 3));
+    let A = CscMatrix::identity(3);
+    assert!(matches!(
+        DualTransform::new(&P, &[0.0; 3], &A, &[0.0; 3], &[ExponentialConeT()]),
+        Err(DualizeError::UnsupportedCone(_))
+    ));
+
+    let A = CscMatrix::identity(1);
+    assert!(matches!(
+        DualTransform::new(
+            &CscMatrix::zeros((1, 1)),
+            &[1.0],
+            &A,
+            &[f64::INFINITY],
+            &[NonnegativeConeT(1)]
+        ),
+        Err(DualizeError::InfiniteBound)
+    ));
+}
@@ -47,7 +47,7 @@ fn test_equilibrate_lower_bound() {
 
     P.nzval[0] = 1e-15;
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings.clone());
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings.clone()).unwrap();
 
     solver.solve();
 
@@ -67,7 +67,7 @@ fn test_equilibrate_upper_bound() {
 
     A.nzval[0] = 1e+15;
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings.clone());
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings.clone()).unwrap();
 
     let d = &solver.data.equilibration.d;
     let e = &solver.data.equilibration.e;
@@ -88,7 +88,7 @@ fn test_equilibrate_zero_rows() {
 
     A.nzval.set(0.0);
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -96,3 +96,91 @@ fn test_equilibrate_zero_rows() {
 
     assert!(e.iter().all(|&v| v == 1.));
 }
+
+#[allow(clippy::type_complexity)]
+fn well_scaled_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // P = diag(1.2, 0.9), A = [B; -B] for B with entries near 1
+    let P = CscMatrix::new(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.2, 0.9]);
+    let B = CscMatrix::new(
+        2,
+        2,
+        vec![0, 2, 4],
+        vec![0, 1, 0, 1],
+        vec![1., 0.2, -0.1, 0.8],
+    );
+    let mut Bneg = B.clone();
+    Bneg.negate();
+    let A = CscMatrix::vcat(&B, &Bneg);
+
+    let q = vec![1., -0.9];
+    let b = vec![0.5, 0.6, 0.5, 0.6];
+    let cones = vec![NonnegativeConeT(4)];
+    (P, q, A, b, cones)
+}
+
+#[test]
+fn test_equilibrate_skip_well_scaled() {
+    let (P, q, A, b, cones) = well_scaled_test_data();
+
+    // equilibration is skipped with a threshold above one, and
+    // always applied with the default threshold
+    let solve = |threshold: f64| {
+        let settings = DefaultSettingsBuilder::default()
+            .verbose(false)
+            .equilibrate_skip_threshold(threshold)
+            .build()
+            .unwrap();
+        let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+        solver.solve();
+        assert_eq!(solver.solution.status, SolverStatus::Solved);
+        solver
+    };
+    let skipped = solve(1.5);
+    let scaled = solve(DefaultSettings::<f64>::default().equilibrate_skip_threshold);
+
+    assert!(skipped.data.is_equilibration_skipped());
+    assert!(!scaled.data.is_equilibration_skipped());
+
+    // the skipped path uses identity scalings and the unscaled data
+    let equil = &skipped.data.equilibration;
+    assert!(equil.d.iter().chain(&equil.e).all(|&s| s == 1.));
+    assert_eq!(equil.c, 1.);
+    assert_eq!(skipped.data.A.nzval, A.nzval);
+    assert!(scaled.data.equilibration.d.iter().any(|&s| s != 1.));
+
+    for (x, y) in std::iter::zip(&skipped.solution.x, &scaled.solution.x) {
+        assert!((x - y).abs() < 1e-7);
+    }
+    let tol = 1e-7 * skipped.solution.obj_val.abs();
+    assert!((skipped.solution.obj_val - scaled.solution.obj_val).abs() < tol);
+}
+
+#[test]
+fn test_equilibrate_no_skip_badly_scaled() {
+    let (P, q, mut A, b, cones) = well_scaled_test_data();
+    A.nzval[0] = 100.;
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .equilibrate_skip_threshold(1.5)
+        .build()
+        .unwrap();
+    let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    assert!(!solver.data.is_equilibration_skipped());
+
+    // nor with equilibration disabled, since no scalings are computed
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .equilibrate_enable(false)
+        .equilibrate_skip_threshold(1.5)
+        .build()
+        .unwrap();
+    let (P, q, A, b, cones) = well_scaled_test_data();
+    let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    assert!(!solver.data.is_equilibration_skipped());
+}
@@ -0,0 +1,174 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// standard test problems solved in single precision with
+// precision calibrated settings
+
+fn settings() -> DefaultSettings<f32> {
+    let mut settings = DefaultSettings::<f32>::default_for();
+    settings.verbose = false;
+    settings
+}
+
+fn solve(
+    P: &CscMatrix<f32>,
+    q: &[f32],
+    A: &CscMatrix<f32>,
+    b: &[f32],
+    cones: &[SupportedConeT<f32>],
+) -> DefaultSolution<f32> {
+    let mut solver = DefaultSolver::new(P, q, A, b, cones, settings()).unwrap();
+    solver.solve();
+    solver.solution
+}
+
+#[allow(clippy::type_complexity)]
+fn basic_lp_data() -> (
+    CscMatrix<f32>,
+    Vec<f32>,
+    CscMatrix<f32>,
+    Vec<f32>,
+    Vec<SupportedConeT<f32>>,
+) {
+    let P = CscMatrix::<f32>::zeros((3, 3));
+
+    let I1 = CscMatrix::<f32>::identity(3);
+    let mut I2 = CscMatrix::<f32>::identity(3);
+    I2.negate();
+    let mut A = CscMatrix::vcat(&I1, &I2);
+    A.scale(2.);
+
+    let c = vec![3., -2., 1.];
+    let b = vec![1.; 6];
+
+    let cones = vec![NonnegativeConeT(3), NonnegativeConeT(3)];
+
+    (P, c, A, b, cones)
+}
+
+#[allow(clippy::type_complexity)]
+fn basic_qp_data() -> (
+    CscMatrix<f32>,
+    Vec<f32>,
+    CscMatrix<f32>,
+    Vec<f32>,
+    Vec<SupportedConeT<f32>>,
+) {
+    // P = [4. 1;1 2]
+    let P = CscMatrix::from(&[
+        [4., 1.], //
+        [0., 2.], //
+    ]);
+
+    // A = [1. 1;1 0; 0 1]; A = [-A;A]
+    let A = CscMatrix::from(&[
+        [1., 1.], //
+        [1., 0.], //
+        [0., 1.], //
+    ]);
+    let (mut A1, A2) = (A.clone(), A);
+    A1.negate();
+    let A = CscMatrix::vcat(&A1, &A2);
+
+    let c = vec![1., 1.];
+    let b = vec![-1., 0., 0., 1., 0.7, 0.7];
+
+    let cones = vec![NonnegativeConeT(3), NonnegativeConeT(3)];
+
+    (P, c, A, b, cones)
+}
+
+#[test]
+fn test_f32_default_for_f64_unchanged() {
+    let s1 = DefaultSettings::<f64>::default_for();
+    let s2 = DefaultSettings::<f64>::default();
+
+    assert_eq!(s1.tol_gap_abs, s2.tol_gap_abs);
+    assert_eq!(s1.tol_feas, s2.tol_feas);
+    assert_eq!(s1.tol_ktratio, s2.tol_ktratio);
+    assert_eq!(s1.reduced_tol_feas, s2.reduced_tol_feas);
+    assert_eq!(
+        s1.static_regularization_constant,
+        s2.static_regularization_constant
+    );
+    assert_eq!(s1.dynamic_regularization_eps, s2.dynamic_regularization_eps);
+    assert_eq!(
+        s1.iterative_refinement_reltol,
+        s2.iterative_refinement_reltol
+    );
+
+    // tolerances are loosened for f32, but remain ordered
+    let s = DefaultSettings::<f32>::default_for();
+    assert!(s.tol_gap_abs > 1e-5 && s.tol_gap_abs < 1e-3);
+    assert!(s.reduced_tol_gap_abs > s.tol_gap_abs);
+    assert!(s.reduced_tol_feas > s.tol_feas);
+}
+
+#[test]
+fn test_f32_lp_feasible() {
+    let (P, c, A, b, cones) = basic_lp_data();
+    let sol = solve(&P, &c, &A, &b, &cones);
+
+    assert_eq!(sol.status, SolverStatus::Solved);
+    assert!(sol.x.dist(&[-0.5, 0.5, -0.5]) <= 1e-3);
+    assert!(f32::abs(sol.obj_val + 3.) <= 1e-3);
+}
+
+#[test]
+fn test_f32_lp_primal_infeasible() {
+    let (P, c, A, mut b, cones) = basic_lp_data();
+    b[0] = -1.;
+    b[3] = -1.;
+    let sol = solve(&P, &c, &A, &b, &cones);
+
+    assert_eq!(sol.status, SolverStatus::PrimalInfeasible);
+}
+
+#[test]
+fn test_f32_qp_feasible() {
+    let (P, c, A, b, cones) = basic_qp_data();
+    let sol = solve(&P, &c, &A, &b, &cones);
+
+    assert_eq!(sol.status, SolverStatus::Solved);
+    assert!(sol.x.dist(&[0.3, 0.7]) <= 1e-3);
+    assert!(f32::abs(sol.obj_val - 1.88) <= 1e-3);
+    assert!(f32::abs(sol.obj_val_dual - 1.88) <= 1e-3);
+}
+
+#[test]
+fn test_f32_qp_dual_infeasible() {
+    // P = [1 1;1 1], A = [1 1;1 0], unbounded along x = (t,-t)
+    let P = CscMatrix::from(&[
+        [1., 1.], //
+        [0., 1.], //
+    ]);
+    let A = CscMatrix::from(&[
+        [1., 1.], //
+        [1., 0.], //
+    ]);
+    let c = [1., -1.];
+    let b = [1., 1.];
+    let sol = solve(&P, &c, &A, &b, &[NonnegativeConeT(2)]);
+
+    assert_eq!(sol.status, SolverStatus::DualInfeasible);
+}
+
+#[test]
+fn test_f32_socp_feasible() {
+    // min x₁ + x₂  s.t.  ‖(x₁,x₂)‖ ≤ 1, solution at -(1,1)/√2
+    let P = CscMatrix::<f32>::zeros((2, 2));
+    let A = CscMatrix::from(&[
+        [0., 0.],  //
+        [-1., 0.], //
+        [0., -1.], //
+    ]);
+    let c = [1., 1.];
+    let b = [1., 0., 0.];
+    let sol = solve(&P, &c, &A, &b, &[SecondOrderConeT(3)]);
+
+    let x = -f32::sqrt(0.5);
+    assert_eq!(sol.status, SolverStatus::Solved);
+    assert!(sol.x.dist(&[x, x]) <= 1e-3);
+    assert!(f32::abs(sol.obj_val + f32::sqrt(2.)) <= 1e-3);
+}
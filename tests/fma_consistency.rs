@@ -0,0 +1,53 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// QP with a tridiagonal P, box constraints and a simplex constraint
+#[allow(clippy::type_complexity)]
+fn problem(
+    n: usize,
+) -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::from_banded(n, &[(0, vec![4.; n]), (1, vec![-1.; n - 1])]).to_triu();
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+    let ones = CscMatrix::new(1, n, (0..=n).collect(), vec![0; n], vec![1.; n]);
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&ones, &CscMatrix::vcat(&CscMatrix::identity(n), &I));
+    let mut b = vec![1.];
+    b.extend(vec![0.5; n]);
+    b.extend(vec![0.; n]);
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(2 * n)];
+    (P, q, A, b, cones)
+}
+
+// Builds with and without the fma feature reach the same optimum to
+// within the solver tolerances.   The reference values were found with
+// the feature disabled.
+#[test]
+fn test_fma_solve_matches_unfused() {
+    let (P, q, A, b, cones) = problem(50);
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    let solution = &solver.solution;
+    assert_eq!(solution.status, SolverStatus::Solved);
+
+    // P is strongly convex, so a duality gap of order tol bounds the
+    // error in x by order √tol
+    let tol = solver.settings.tol_gap_rel;
+    let obj_val = -7.789_825_566_367_731e-1;
+    assert!((solution.obj_val - obj_val).abs() <= tol * obj_val.abs());
+    assert!((solution.x[3] - 9.038_012_513_146_472e-2).abs() <= tol.sqrt());
+    assert!((solution.x[4] - 2.311_035_087_563_71e-2).abs() <= tol.sqrt());
+    assert!((solution.x.iter().sum::<f64>() - 1.).abs() <= solver.settings.tol_feas);
+}
@@ -0,0 +1,111 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. x₁ + x₂ = 1, 0 ≤ x ≤ 0.7, with P = [4 1; 1 2]
+fn problem_data() -> (Vec<f64>, CscMatrix<f64>, Vec<f64>, Vec<SupportedConeT<f64>>) {
+    let A = CscMatrix::new(
+        5,
+        2,
+        vec![0, 3, 6],
+        vec![0, 1, 3, 0, 2, 4],
+        vec![1., -1., 1., 1., -1., 1.],
+    );
+    let q = vec![1., 1.];
+    let b = vec![1., 0., 0., 0.7, 0.7];
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(4)];
+    (q, A, b, cones)
+}
+
+fn P_triu() -> CscMatrix<f64> {
+    CscMatrix::new(2, 2, vec![0, 1, 3], vec![0, 0, 1], vec![4., 1., 2.])
+}
+
+fn P_full() -> CscMatrix<f64> {
+    CscMatrix::new(2, 2, vec![0, 2, 4], vec![0, 1, 0, 1], vec![4., 1., 1., 2.])
+}
+
+fn settings(p_auto_triu: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .p_auto_triu(p_auto_triu)
+        .build()
+        .unwrap()
+}
+
+fn solve(P: &CscMatrix<f64>, p_auto_triu: bool) -> Result<DefaultSolver<f64>, ClarabelError> {
+    let (q, A, b, cones) = problem_data();
+    let mut solver = DefaultSolver::new(P, &q, &A, &b, &cones, settings(p_auto_triu))?;
+    solver.solve();
+    Ok(solver)
+}
+
+#[test]
+fn test_full_symmetric_P_converted() {
+    let triu = solve(&P_triu(), true).unwrap();
+    let full = solve(&P_full(), true).unwrap();
+
+    assert_eq!(full.solution.status, SolverStatus::Solved);
+    assert_eq!(full.data.P, triu.data.P);
+    assert_eq!(full.solution.x, triu.solution.x);
+    assert_eq!(full.solution.obj_val, triu.solution.obj_val);
+}
+
+#[test]
+fn test_full_symmetric_P_rejected() {
+    // with conversion disabled, the first entry below
+    // the diagonal is reported
+    let Err(e) = solve(&P_full(), false) else {
+        panic!("full P accepted");
+    };
+    assert!(matches!(
+        e,
+        ClarabelError::PNotUpperTriangular { row: 1, col: 0 }
+    ));
+}
+
+#[test]
+fn test_nonsymmetric_P_rejected() {
+    for p_auto_triu in [true, false] {
+        let mut P = P_full();
+        P.nzval[1] = 1.5;
+        let result = solve(&P, p_auto_triu);
+        let expected = if p_auto_triu {
+            matches!(result, Err(ClarabelError::PNotSymmetric { row: 1, col: 0 }))
+        } else {
+            matches!(
+                result,
+                Err(ClarabelError::PNotUpperTriangular { row: 1, col: 0 })
+            )
+        };
+        assert!(expected);
+    }
+
+    // a lower triangular P is not symmetric either
+    let P = P_triu().t().into();
+    assert!(matches!(
+        solve(&P, true),
+        Err(ClarabelError::PNotSymmetric { row: 1, col: 0 })
+    ));
+}
+
+#[test]
+fn test_triu_P_untouched() {
+    for p_auto_triu in [true, false] {
+        let solver = solve(&P_triu(), p_auto_triu).unwrap();
+        assert_eq!(solver.solution.status, SolverStatus::Solved);
+        assert_eq!(solver.data.P.nnz(), 3);
+    }
+}
+
+#[test]
+fn test_nonsymmetric_P_borrowed() {
+    let (q, A, b, cones) = problem_data();
+    let mut P = P_full();
+    P.nzval[2] = 0.;
+    let result = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings(true));
+    assert!(matches!(
+        result,
+        Err(ClarabelError::PNotSymmetric { row: 1, col: 0 })
+    ));
+}
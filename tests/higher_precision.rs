@@ -0,0 +1,76 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "higher-precision")]
+
+use clarabel::{algebra::*, solver::*};
+
+// an ill-conditioned equality constrained QP solved in
+// double-double precision
+//
+// min ½‖x‖² s.t. Hx = b, x ≤ 2
+//
+// with H a scaled Hilbert matrix with integer entries, so that the
+// data is exact in both f64 and double-double.   The solution is
+// x = 1 and cond(H) ≈ 5e8, which limits the accuracy of an f64 solution
+// to far less than that of the double-double one.
+
+const N: usize = 7;
+
+// lcm(1, ..., 2N - 1)
+const SCALE: f64 = 360360.;
+
+fn hilbert_problem<T: FloatT>() -> (CscMatrix<T>, Vec<T>, CscMatrix<T>, Vec<T>) {
+    let P = CscMatrix::<T>::identity(N);
+    let q = vec![T::zero(); N];
+
+    let rows: Vec<Vec<T>> = (0..N)
+        .map(|i| {
+            (0..N)
+                .map(|j| (SCALE / ((i + j + 1) as f64)).as_T())
+                .collect()
+        })
+        .collect();
+    let H = CscMatrix::from(&rows);
+    let A = CscMatrix::vcat(&H, &CscMatrix::identity(N));
+
+    let mut b: Vec<T> = rows
+        .iter()
+        .map(|r| r.iter().fold(T::zero(), |s, &v| s + v))
+        .collect();
+    b.resize(2 * N, (2.).as_T());
+
+    (P, q, A, b)
+}
+
+fn solve<T: FloatT>() -> (DefaultSolution<T>, T) {
+    let (P, q, A, b) = hilbert_problem::<T>();
+    let cones = [ZeroConeT(N), NonnegativeConeT(N)];
+
+    let mut settings = DefaultSettings::<T>::default_for();
+    settings.verbose = false;
+    settings.presolve_enable = false;
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    let err = solver
+        .solution
+        .x
+        .iter()
+        .fold(T::zero(), |e, &x| T::max(e, T::abs(x - T::one())));
+
+    (solver.solution, err)
+}
+
+#[test]
+fn test_doubledouble_hilbert() {
+    type DD = DoubleDouble;
+
+    let (sol, err) = solve::<DD>();
+    assert_eq!(sol.status, SolverStatus::Solved);
+    assert!(err < DD::from(1e-18));
+    assert!(sol.r_prim < DD::from(1e-20));
+
+    // unattainable in f64
+    let (_, err) = solve::<f64>();
+    assert!(err > 1e-12);
+}
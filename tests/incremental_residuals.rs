@@ -0,0 +1,108 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. -1 ≤ Bx ≤ 1, with B block diagonal in
+// dense 4×4 blocks and the upper bounds as three dimensional
+// second order cones if `soc` is set
+#[allow(clippy::type_complexity)]
+fn block_box_problem(
+    n: usize,
+    P: CscMatrix<f64>,
+    soc: bool,
+) -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    assert_eq!(n % 4, 0);
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for k in (0..n).step_by(4) {
+        for j in 0..4 {
+            for i in 0..4 {
+                I.push(k + i);
+                J.push(k + j);
+                V.push(if i == j { 2. } else { 1. / (1 + i + j) as f64 });
+            }
+        }
+    }
+    let B = CscMatrix::new_from_triplets(n, n, I, J, V);
+    let mut Bneg = B.clone();
+    Bneg.negate();
+
+    let q: Vec<f64> = (0..n).map(|i| ((i + 1) as f64).sin()).collect();
+    let A = CscMatrix::vcat(&B, &Bneg);
+    let b = vec![1.; 2 * n];
+    let cones = if soc {
+        let k = n / 3;
+        let mut cones: Vec<_> = (0..k).map(|_| SecondOrderConeT(3)).collect();
+        cones.push(NonnegativeConeT(2 * n - 3 * k));
+        cones
+    } else {
+        vec![NonnegativeConeT(2 * n)]
+    };
+    (P, q, A, b, cones)
+}
+
+fn solve(n: usize, P: CscMatrix<f64>, soc: bool, incremental: bool) -> DefaultSolver<f64> {
+    let (P, q, A, b, cones) = block_box_problem(n, P, soc);
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .incremental_residuals(incremental)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+// run with `--features residual-check` to also compare every
+// incrementally updated residual against a full recomputation
+#[test]
+fn test_incremental_matches_full() {
+    let n = 40;
+    let cases = [
+        (CscMatrix::zeros((n, n)), false),
+        (CscMatrix::identity(n), false),
+        (CscMatrix::identity(n), true),
+        (
+            CscMatrix::from_banded(n, &[(0, vec![2.; n]), (1, vec![0.5; n - 1])]),
+            false,
+        ),
+    ];
+
+    for (P, soc) in cases {
+        let incremental = solve(n, P.clone(), soc, true);
+        let full = solve(n, P, soc, false);
+
+        let (s1, s2) = (&incremental.solution, &full.solution);
+        assert_eq!(s1.status, SolverStatus::Solved);
+        assert_eq!(s2.status, SolverStatus::Solved);
+        assert!(s1.x.dist(&s2.x) <= 1e-7);
+        assert!(s1.z.dist(&s2.z) <= 1e-6);
+        assert!(f64::abs(s1.obj_val - s2.obj_val) <= 1e-7);
+    }
+}
+
+// Per-iteration solve times for a block box constrained QP with
+// n = 200k variables, with and without incremental residual updates.
+// Run with
+//
+//   cargo test --release --test incremental_residuals -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_incremental_residuals() {
+    let n = 200_000;
+    for incremental in [false, true] {
+        let solver = solve(n, CscMatrix::identity(n), false, incremental);
+        let solution = &solver.solution;
+        println!(
+            "incremental = {:5}: {:3} iterations, {:8.3} ms per iteration",
+            incremental,
+            solution.iterations,
+            solution.solve_time * 1e3 / solution.iterations as f64
+        );
+    }
+}
@@ -0,0 +1,131 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+fn settings(presolve: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .presolve_enable(presolve)
+        .verbose(false)
+        .build()
+        .unwrap()
+}
+
+fn solve(
+    P: &CscMatrix<f64>,
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    cones: &[SupportedConeT<f64>],
+    presolve: bool,
+) -> DefaultSolution<f64> {
+    let mut solver = DefaultSolver::new(P, q, A, b, cones, settings(presolve)).unwrap();
+    solver.solve();
+    solver.solution
+}
+
+// y = Mx
+fn mul(M: &CscMatrix<f64>, x: &[f64]) -> Vec<f64> {
+    let mut y = vec![0.; M.m];
+    for (col, &xj) in x.iter().enumerate() {
+        for k in M.colptr[col]..M.colptr[col + 1] {
+            y[M.rowval[k]] += M.nzval[k] * xj;
+        }
+    }
+    y
+}
+
+#[test]
+fn test_primal_infeasible_certificate() {
+    // min x₁ + x₂ s.t. x₁ + x₂ ≥ 2, x₁ ≤ 0, x₂ ≤ 1, with
+    // certificate z ∝ (1, 1, 1) up to scaling
+    let P = CscMatrix::zeros((2, 2));
+    let q = [1., 1.];
+    let A = CscMatrix::from(&[
+        [-1., -1.], //
+        [1., 0.],   //
+        [0., 1.],   //
+    ]);
+    let b = [-2., 0., 1.];
+    let cones = [NonnegativeConeT(3)];
+
+    for presolve in [false, true] {
+        let sol = solve(&P, &q, &A, &b, &cones, presolve);
+        assert_eq!(sol.status, SolverStatus::PrimalInfeasible);
+
+        // unit infinity norm, with A'z = 0 and b'z < 0
+        assert!((sol.z.norm_inf() - 1.).abs() <= 1e-12);
+        assert!(sol.z.dist(&[1., 1., 1.]) <= 1e-6);
+        let Atz = mul(&CscMatrix::from(A.t()), &sol.z);
+        assert!(Atz.norm_inf() <= 1e-6);
+        assert!(b.dot(&sol.z) <= -1.);
+    }
+}
+
+#[test]
+fn test_primal_infeasible_certificate_presolved() {
+    // as above with an additional bound x₂ ≤ 10^30, which presolve
+    // removes.   Its dual is zero and its slack infinite
+    let P = CscMatrix::zeros((2, 2));
+    let q = [1., 1.];
+    let A = CscMatrix::from(&[
+        [-1., -1.], //
+        [1., 0.],   //
+        [0., 1.],   //
+        [0., 1.],   //
+    ]);
+    let b = [-2., 0., 1., 1e30];
+    let cones = [NonnegativeConeT(4)];
+
+    let sol = solve(&P, &q, &A, &b, &cones, true);
+    assert_eq!(sol.status, SolverStatus::PrimalInfeasible);
+    assert!(sol.z.dist(&[1., 1., 1., 0.]) <= 1e-6);
+    assert_eq!(sol.s[3], get_infinity());
+}
+
+#[test]
+fn test_dual_infeasible_certificate_lp() {
+    // min -x₁ - 2x₂ s.t. x₁ - x₂ = 0, x ≥ 0, unbounded
+    // along the ray x ∝ (1, 1)
+    let P = CscMatrix::zeros((2, 2));
+    let q = [-1., -2.];
+    let A = CscMatrix::from(&[
+        [1., -1.], //
+        [-1., 0.], //
+        [0., -1.], //
+    ]);
+    let b = [0., 0., 0.];
+    let cones = [ZeroConeT(1), NonnegativeConeT(2)];
+
+    let sol = solve(&P, &q, &A, &b, &cones, false);
+    assert_eq!(sol.status, SolverStatus::DualInfeasible);
+
+    // unit infinity norm, with q'x < 0 and s = -Ax in the cone
+    assert!(sol.x.dist(&[1., 1.]) <= 1e-6);
+    assert!(q.dot(&sol.x) < 0.);
+    let Ax = mul(&A, &sol.x);
+    let mut r = sol.s.clone();
+    r.axpby(1., &Ax, 1.);
+    assert!(r.norm_inf() <= 1e-6);
+    assert!(sol.s[0].abs() <= 1e-6 && sol.s[1] > 0. && sol.s[2] > 0.);
+}
+
+#[test]
+fn test_dual_infeasible_certificate_qp() {
+    // min ½x₁² - x₂ s.t. x₂ ≥ 1, unbounded along x ∝ (0, 1), with
+    // Px = 0 along the ray
+    let P = CscMatrix::from(&[
+        [1., 0.], //
+        [0., 0.], //
+    ]);
+    let q = [0., -1.];
+    let A = CscMatrix::from(&[[0., -1.]]);
+    let b = [-1.];
+    let cones = [NonnegativeConeT(1)];
+
+    let sol = solve(&P, &q, &A, &b, &cones, false);
+    assert_eq!(sol.status, SolverStatus::DualInfeasible);
+
+    assert!(sol.x.dist(&[0., 1.]) <= 1e-6);
+    assert!(mul(&P, &sol.x).norm_inf() <= 1e-6);
+    assert!(q.dot(&sol.x) < 0.);
+}
@@ -0,0 +1,122 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½‖x‖² + qᵀx s.t. x ≤ b, with three variables
+fn solve_bounded(b: &[f64], presolve: bool) -> Result<DefaultSolver<f64>, ClarabelError> {
+    let P = CscMatrix::identity(3);
+    let q = [-2., 1., -3.];
+    let A = CscMatrix::identity(3);
+    let cones = [NonnegativeConeT(3)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(presolve)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, b, &cones, settings)?;
+    solver.solve();
+    Ok(solver)
+}
+
+#[test]
+fn test_absent_bounds_removed() {
+    let solver = solve_bounded(&[1., f64::INFINITY, 1e30], true).unwrap();
+    let solution = &solver.solution;
+
+    assert_eq!(solution.status, SolverStatus::Solved);
+    assert_eq!(solver.data.m, 1);
+    assert_eq!(solver.data.count_huge_entries(), 0);
+
+    // only the first bound is active
+    let expected = [1., -1., 3.];
+    for (x, e) in solution.x.iter().zip(expected) {
+        assert!((x - e).abs() < 1e-6);
+    }
+    assert_eq!(solution.z[1], 0.);
+    assert_eq!(solution.z[2], 0.);
+    assert_eq!(solution.s[1], get_infinity());
+}
+
+#[test]
+fn test_absent_bounds_kept_without_presolve() {
+    // absent bounds are capped and reported as huge entries
+    let solver = solve_bounded(&[1., f64::INFINITY, 1e30], false).unwrap();
+
+    assert_eq!(solver.data.m, 3);
+    assert_eq!(solver.data.count_huge_entries(), 2);
+    assert!(solver.data.b.iter().all(|&v| v <= get_infinity()));
+}
+
+#[test]
+fn test_all_bounds_absent() {
+    // the problem is unconstrained after its rows are removed
+    let solver = solve_bounded(&[f64::INFINITY; 3], true).unwrap();
+    let solution = &solver.solution;
+
+    assert_eq!(solution.status, SolverStatus::Solved);
+    assert_eq!(solver.data.m, 0);
+    let expected = [2., -1., 3.];
+    for (x, e) in solution.x.iter().zip(expected) {
+        assert!((x - e).abs() < 1e-6);
+    }
+    assert_eq!(solution.s, vec![get_infinity(); 3]);
+}
+
+#[test]
+fn test_absent_bounds_after_other_cones() {
+    // min ½‖x‖² + qᵀx s.t. x₀ = 1, x ≤ b, with the rows of
+    // the nonnegative cone following those of a zero cone
+    let P = CscMatrix::identity(3);
+    let q = [-2., 1., -3.];
+    let A = CscMatrix::vcat(
+        &CscMatrix::new(1, 3, vec![0, 1, 1, 1], vec![0], vec![1.]),
+        &CscMatrix::identity(3),
+    );
+    let b = [1., 2., f64::INFINITY, 2.];
+    let cones = [ZeroConeT(1), NonnegativeConeT(3)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert_eq!(solver.data.cones, vec![ZeroConeT(1), NonnegativeConeT(2)]);
+
+    let expected = [1., -1., 2.];
+    for (x, e) in solver.solution.x.iter().zip(expected) {
+        assert!((x - e).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_infinite_entries_rejected() {
+    let P = CscMatrix::identity(2);
+    let A = CscMatrix::identity(2);
+    let cones = [ZeroConeT(1), NonnegativeConeT(1)];
+    let new_solver = |q: &[f64], b: &[f64]| {
+        let settings = DefaultSettings::default();
+        DefaultSolver::new(&P, q, &A, b, &cones, settings)
+    };
+
+    let cases = [
+        ([1., f64::INFINITY], [0., 1.], "q", 1),
+        ([1., 1.], [f64::INFINITY, 1.], "b", 0),
+        ([1., 1.], [0., f64::NEG_INFINITY], "b", 1),
+        ([1., 1.], [0., f64::NAN], "b", 1),
+    ];
+    for (q, b, what, index) in cases {
+        match new_solver(&q, &b) {
+            Err(ClarabelError::NonFiniteEntry { what: w, index: i }) => {
+                assert_eq!((w, i), (what, index))
+            }
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("infinite entry was accepted"),
+        }
+    }
+
+    // finite but huge entries are accepted, with a warning
+    let solver = new_solver(&[1e25, 1.], &[1e30, 1.]).unwrap();
+    assert_eq!(solver.data.count_huge_entries(), 2);
+}
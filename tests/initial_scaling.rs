@@ -0,0 +1,129 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min x₁ + x₂ + x₃ s.t. x ∈ K_exp, with the exponential
+// cone stated as -x ∈ K
+#[allow(clippy::type_complexity)]
+fn expcone_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::zeros((3, 3));
+    let c = vec![-1., 1., 1.];
+
+    let mut I = CscMatrix::<f64>::identity(3);
+    I.negate();
+    let A = CscMatrix::vcat(&I, &CscMatrix::identity(3));
+    let b = vec![0., 0., 0., 1., 1., 1.];
+
+    let cones = vec![ExponentialConeT(), NonnegativeConeT(3)];
+    (P, c, A, b, cones)
+}
+
+fn settings() -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(false)
+        .build()
+        .unwrap()
+}
+
+fn solve_with_scaling(Hs: Option<&[f64]>) -> DefaultSolution<f64> {
+    let (P, c, A, b, cones) = expcone_data();
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings()).unwrap();
+    if let Some(Hs) = Hs {
+        solver.set_initial_scaling(0, Hs).unwrap();
+    }
+    solver.solve();
+    solver.solution
+}
+
+// the Hs block of the exponential cone computed by the
+// solver itself at the first iteration
+fn first_iteration_scaling() -> Vec<f64> {
+    let (P, c, A, b, cones) = expcone_data();
+    let mut settings = settings();
+    settings.max_iter = 1;
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver.scaling_data(0).unwrap()
+}
+
+#[test]
+fn test_initial_scaling_good_vs_random() {
+    let default = solve_with_scaling(None);
+
+    // supplying the scaling the solver would compute anyway
+    // reproduces the default solve exactly
+    let good = first_iteration_scaling();
+    assert_eq!(good.len(), 6);
+    let solution = solve_with_scaling(Some(&good));
+    assert_eq!(solution.status, SolverStatus::Solved);
+    assert_eq!(solution.iterations, default.iterations);
+    assert_eq!(solution.x, default.x);
+
+    // a badly conditioned positive definite block, packed by columns
+    let random = [1e6, 0., 1., 0., 0., 1e-6];
+    let solution = solve_with_scaling(Some(&random));
+    assert_eq!(solution.status, SolverStatus::Solved);
+    assert!(solution.iterations > default.iterations);
+    assert!(f64::abs(solution.obj_val - default.obj_val) <= 1e-7);
+}
+
+#[test]
+fn test_initial_scaling_consumed() {
+    let (P, c, A, b, cones) = expcone_data();
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings()).unwrap();
+    solver.solve();
+    let default = solver.solution.iterations;
+
+    let random = [1e6, 0., 1., 0., 0., 1e-6];
+    solver.set_initial_scaling(0, &random).unwrap();
+    solver.solve();
+    assert!(solver.solution.iterations > default);
+
+    // a second solve is unaffected by the earlier scaling
+    solver.solve();
+    assert_eq!(solver.solution.iterations, default);
+}
+
+#[test]
+fn test_initial_scaling_errors() {
+    let (P, c, A, b, cones) = expcone_data();
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings()).unwrap();
+
+    assert!(matches!(
+        solver.set_initial_scaling(2, &[1.; 3]),
+        Err(ScalingError::BadConeIndex(2))
+    ));
+    assert!(matches!(
+        solver.set_initial_scaling(0, &[1.; 3]),
+        Err(ScalingError::BadLength {
+            index: 0,
+            expected: 6,
+            found: 3
+        })
+    ));
+    assert!(matches!(
+        solver.set_initial_scaling(1, &[1., f64::NAN, 1.]),
+        Err(ScalingError::NotFinite(1))
+    ));
+    assert!(solver.set_initial_scaling(1, &[1., 2., 3.]).is_ok());
+    assert!(solver.scaling_data(2).is_err());
+
+    // large second order cones are assembled with a sparse expansion
+    let P = CscMatrix::zeros((10, 10));
+    let c = vec![1.; 10];
+    let A = CscMatrix::identity(10);
+    let b = vec![0.; 10];
+    let cones = vec![SecondOrderConeT(10)];
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings()).unwrap();
+    assert!(matches!(
+        solver.set_initial_scaling(0, &[1.; 10]),
+        Err(ScalingError::SparseExpandable { index: 0, .. })
+    ));
+}
@@ -0,0 +1,102 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½‖x‖² + q'x  s.t.  x₁ + x₂ + x₃ = 1, repeated three times, and
+// x ≥ 0.   The duplicate equality rows make the KKT matrix nearly
+// singular, with only the static regularization separating them
+#[allow(clippy::type_complexity)]
+fn duplicate_constraints_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::identity(3);
+    let q = vec![1., -2., 0.5];
+    let A = CscMatrix::from(&[
+        [1., 1., 1.],  //
+        [1., 1., 1.],  //
+        [1., 1., 1.],  //
+        [-1., 0., 0.], //
+        [0., -1., 0.], //
+        [0., 0., -1.], //
+    ]);
+    let b = vec![1., 1., 1., 0., 0., 0.];
+    let cones = vec![ZeroConeT(3), NonnegativeConeT(3)];
+    (P, q, A, b, cones)
+}
+
+fn solve(builder: &mut DefaultSettingsBuilder<f64>) -> DefaultSolver {
+    let (P, q, A, b, cones) = duplicate_constraints_data();
+    let settings = builder
+        .verbose(false)
+        .presolve_enable(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+#[test]
+fn test_refinement_stats() {
+    let refsol = [0., 1., 0.];
+
+    // without refinement steps, the residuals are those of the direct solves
+    let unrefined = solve(DefaultSettingsBuilder::default().iterative_refinement_max_iter(0));
+    let stats = unrefined.info.refinement();
+    assert_eq!(stats.max_steps, 0);
+    assert!(stats.failures > 0);
+
+    let refined = solve(&mut DefaultSettingsBuilder::default());
+    assert_eq!(refined.solution.status, SolverStatus::Solved);
+    assert!(refined.solution.x.dist(&refsol) <= 1e-6);
+
+    let refstats = refined.info.refinement();
+    assert!(refstats.max_steps > 0);
+    assert!(refstats.last_residual <= refstats.max_residual);
+    assert!(refstats.max_residual < stats.max_residual);
+    assert!(refstats.failures < stats.failures);
+    assert_eq!(refstats.refactorizations, 0);
+    assert!(refined.info.counters().refinement_steps >= refstats.max_steps as u64);
+
+    // no statistics without refinement
+    let disabled = solve(DefaultSettingsBuilder::default().iterative_refinement_enable(false));
+    assert_eq!(disabled.info.refinement(), RefinementStats::default());
+}
+
+#[test]
+fn test_refinement_refactor() {
+    // a refinement target that can never be met, so that every
+    // solve fails to reach it
+    let mut builder = DefaultSettingsBuilder::default();
+    builder
+        .iterative_refinement_reltol(0.)
+        .iterative_refinement_abstol(0.);
+
+    let solver = solve(&mut builder);
+    let stats = solver.info.refinement();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert_eq!(stats.failures, solver.info.counters().kkt_solves);
+    assert_eq!(stats.refactorizations, 0);
+    let factorizations = solver.info.counters().factorizations;
+
+    // one refactorization at each KKT update, i.e. at each iteration
+    // and at the initial point
+    let solver = solve(builder.iterative_refinement_refactor(true));
+    let refactored = solver.info.refinement();
+    let counters = solver.info.counters();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.x.dist(&[0., 1., 0.]) <= 1e-6);
+    assert_eq!(
+        refactored.refactorizations,
+        solver.info.iterations as u64 + 1
+    );
+    assert_eq!(
+        counters.factorizations,
+        solver.info.iterations as u64 + 1 + refactored.refactorizations
+    );
+    assert!(counters.factorizations > factorizations);
+}
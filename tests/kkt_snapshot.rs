@@ -0,0 +1,146 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, qdldl::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn basic_qp_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // P = [4. 1;1 2]
+    let P = CscMatrix::from(&[
+        [4., 1.], //
+        [0., 2.], //
+    ]);
+
+    // A = [1. 1;1 0; 0 1]; A = [-A;A]
+    let A = CscMatrix::from(&[
+        [1., 1.], //
+        [1., 0.], //
+        [0., 1.], //
+    ]);
+    let (mut A1, A2) = (A.clone(), A);
+    A1.negate();
+    let A = CscMatrix::vcat(&A1, &A2);
+
+    let q = vec![1., 1.];
+    let b = vec![-1., 0., 0., 1., 0.7, 0.7];
+    let cones = vec![NonnegativeConeT(3), NonnegativeConeT(3)];
+
+    (P, q, A, b, cones)
+}
+
+fn solve_with_snapshot(iteration: u32) -> DefaultSolver<f64> {
+    let (P, q, A, b, cones) = basic_qp_data();
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .kkt_snapshot_iteration(iteration)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    solver
+}
+
+// solve K x = b with an independent, unregularized factorization of K
+fn factor_and_solve(K: &CscMatrix<f64>, b: &[f64]) -> Vec<f64> {
+    let opts = QDLDLSettingsBuilder::default()
+        .regularize_enable(false)
+        .build()
+        .unwrap();
+    let mut factors = QDLDLFactorisation::new(K, Some(opts)).unwrap();
+    let mut x = b.to_vec();
+    factors.solve(&mut x);
+    x
+}
+
+#[test]
+fn test_kkt_snapshot_disabled() {
+    let solver = solve_with_snapshot(0);
+    assert!(solver.kkt_snapshot().is_none());
+
+    // the solver terminates before this iteration
+    let solver = solve_with_snapshot(1000);
+    assert!(solver.kkt_snapshot().is_none());
+}
+
+#[test]
+fn test_kkt_snapshot_reproduces_solve() {
+    let solver = solve_with_snapshot(3);
+    let snapshot = solver.kkt_snapshot().unwrap();
+    assert_eq!(snapshot.iteration, 3);
+
+    let dim = 2 + 6;
+    assert_eq!((snapshot.K.m, snapshot.K.n), (dim, dim));
+    assert!(snapshot.K.is_triu());
+    for v in [
+        &snapshot.rhs_constant,
+        &snapshot.lhs_constant,
+        &snapshot.rhs_predictor,
+        &snapshot.lhs_predictor,
+        &snapshot.rhs_corrector,
+        &snapshot.lhs_corrector,
+    ] {
+        assert_eq!(v.len(), dim);
+    }
+
+    // the in-solver solutions are refined against the
+    // unregularized matrix, so should match its solution
+    let K = snapshot.matrix(false, false);
+    for (rhs, lhs) in [
+        (&snapshot.rhs_constant, &snapshot.lhs_constant),
+        (&snapshot.rhs_predictor, &snapshot.lhs_predictor),
+        (&snapshot.rhs_corrector, &snapshot.lhs_corrector),
+    ] {
+        let x = factor_and_solve(&K, rhs);
+        assert!(x.dist(lhs) <= 1e-8 * (1. + lhs.norm()));
+    }
+
+    // the permuted system has the permuted solution
+    let Kp = snapshot.matrix(false, true);
+    let bp = snapshot.vector(&snapshot.rhs_predictor, true);
+    let x = factor_and_solve(&Kp, &bp);
+    let lhs = snapshot.vector(&snapshot.lhs_predictor, true);
+    assert!(x.dist(&lhs) <= 1e-8 * (1. + lhs.norm()));
+
+    // the regularized matrix differs only on the diagonal
+    let Kr = snapshot.matrix(true, false);
+    let eps = snapshot.static_regularization;
+    assert!(eps > 0.);
+    assert!(Kr.is_equal_sparsity(&K));
+    for j in 0..dim {
+        let (a, b) = (K.get_entry((j, j)).unwrap(), Kr.get_entry((j, j)).unwrap());
+        let sign = f64::from(snapshot.dsigns[j]);
+        assert!(f64::abs(b - a - sign * eps) <= 1e-12 * (1. + a.abs()));
+    }
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn test_kkt_snapshot_write_mtx() {
+    let solver = solve_with_snapshot(2);
+    let snapshot = solver.kkt_snapshot().unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    snapshot.write_mtx_dir(dir.path(), true, false).unwrap();
+
+    let K = std::fs::read_to_string(dir.path().join("K.mtx")).unwrap();
+    let mut lines = K.lines();
+    assert_eq!(
+        lines.next(),
+        Some("%%MatrixMarket matrix coordinate real symmetric")
+    );
+    assert_eq!(
+        lines.next(),
+        Some(format!("8 8 {}", snapshot.K.nnz()).as_str())
+    );
+    assert_eq!(lines.count(), snapshot.K.nnz());
+
+    let rhs = std::fs::read_to_string(dir.path().join("rhs_corrector.mtx")).unwrap();
+    let values: Vec<f64> = rhs.lines().skip(2).map(|l| l.parse().unwrap()).collect();
+    assert_eq!(values, snapshot.rhs_corrector);
+}
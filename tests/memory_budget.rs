@@ -0,0 +1,86 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀx + qᵀx s.t. -1 ≤ Bx ≤ 1, with B block diagonal
+// in dense 4×4 blocks
+fn block_problem(n: usize) -> (CscMatrix<f64>, Vec<f64>, CscMatrix<f64>, Vec<f64>) {
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for k in (0..n).step_by(4) {
+        for j in 0..4 {
+            for i in 0..4 {
+                I.push(k + i);
+                J.push(k + j);
+                V.push(if i == j { 2. } else { 1. / (1 + i + j) as f64 });
+            }
+        }
+    }
+    let B = CscMatrix::new_from_triplets(n, n, I, J, V);
+    let mut Bneg = B.clone();
+    Bneg.negate();
+
+    let P = CscMatrix::identity(n);
+    let q: Vec<f64> = (0..n).map(|i| ((i + 1) as f64).sin()).collect();
+    let A = CscMatrix::vcat(&B, &Bneg);
+    let b = vec![1.; 2 * n];
+    (P, q, A, b)
+}
+
+fn try_solver(budget: Option<usize>) -> Result<DefaultSolver<f64>, ClarabelError> {
+    let n = 200;
+    let (P, q, A, b) = block_problem(n);
+    let cones = [NonnegativeConeT(2 * n)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .max_memory_bytes(budget)
+        .build()
+        .unwrap();
+    DefaultSolver::new(&P, &q, &A, &b, &cones, settings)
+}
+
+#[test]
+fn test_memory_budget_exceeded() {
+    let result = try_solver(Some(1000));
+    let Err(ClarabelError::MemoryLimitExceeded { estimate, budget }) = result else {
+        panic!("setup did not fail");
+    };
+    assert_eq!(budget, 1000);
+
+    // the factors alone of a KKT system with 200 + 400
+    // rows take several times the budget
+    assert!(estimate > 20 * budget);
+
+    // the budget is inclusive
+    assert!(try_solver(Some(estimate)).is_ok());
+    assert!(try_solver(Some(estimate - 1)).is_err());
+}
+
+#[test]
+fn test_memory_budget_generous() {
+    for budget in [None, Some(usize::MAX), Some(1 << 30)] {
+        let mut solver = try_solver(budget).unwrap();
+        solver.solve();
+        assert_eq!(solver.solution.status, SolverStatus::Solved);
+    }
+}
+
+#[test]
+fn test_memory_budget_borrowed() {
+    // the caller's matrices are left unchanged by the error
+    let (P, q, A, b) = block_problem(8);
+    let (P0, A0) = (P.clone(), A.clone());
+    let cones = [NonnegativeConeT(16)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .max_memory_bytes(Some(1))
+        .build()
+        .unwrap();
+
+    let result = DefaultSolver::new_borrowed(&P, &q, &A, &b, &cones, settings);
+    assert!(matches!(
+        result.err(),
+        Some(ClarabelError::MemoryLimitExceeded { budget: 1, .. })
+    ));
+    assert_eq!(P, P0);
+    assert_eq!(A, A0);
+}
@@ -31,7 +31,7 @@ fn test_mixed_conic_feasible() {
     let b = vec![0.; 5 * n];
 
     let settings = DefaultSettings::default();
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -0,0 +1,95 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "nalgebra")]
+
+use clarabel::algebra::*;
+use clarabel::solver::*;
+use nalgebra::{DMatrix, DVector};
+
+// simple LCG so that tests are reproducible
+fn random_dmatrix(m: usize, n: usize, density: f64, seed: &mut u64) -> DMatrix<f64> {
+    let mut rand = || {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (*seed >> 11) as f64 / (1u64 << 53) as f64
+    };
+    DMatrix::from_fn(m, n, |_, _| {
+        if rand() < density {
+            2.0 * rand() - 1.0
+        } else {
+            0.0
+        }
+    })
+}
+
+#[test]
+fn test_nalgebra_dense_roundtrip() {
+    let mut seed = 1;
+    for &(m, n) in &[(0, 0), (1, 5), (7, 3), (20, 20)] {
+        let M = random_dmatrix(m, n, 0.3, &mut seed);
+        let A = CscMatrix::from_dmatrix(&M);
+        assert!(A.check_format().is_ok());
+        assert_eq!(A.nnz(), M.iter().filter(|&&v| v != 0.0).count());
+        assert_eq!(DMatrix::from(&A), M);
+    }
+
+    // drop tolerance
+    let M = DMatrix::from_row_slice(2, 2, &[1.0, 1e-12, -1e-12, -2.0]);
+    let A = CscMatrix::from_dmatrix_with_tol(&M, 1e-10);
+    assert_eq!(A, CscMatrix::from(&[[1.0, 0.0], [0.0, -2.0]]));
+}
+
+#[test]
+fn test_nalgebra_sparse_roundtrip() {
+    let mut seed = 2;
+    for &(m, n) in &[(0, 0), (4, 1), (9, 13), (30, 30)] {
+        let A = CscMatrix::from_dmatrix(&random_dmatrix(m, n, 0.2, &mut seed));
+
+        let B = nalgebra_sparse::CscMatrix::try_from(&A).unwrap();
+        assert_eq!(B.nnz(), A.nnz());
+        assert_eq!(B.col_offsets(), &A.colptr[..]);
+
+        let B: nalgebra_sparse::CscMatrix<f64> = A.clone().try_into().unwrap();
+        assert_eq!(CscMatrix::from_nalgebra_sparse(B), A);
+    }
+
+    // unsorted row indices are rejected
+    let A = CscMatrix::new(2, 1, vec![0, 2], vec![1, 0], vec![1.0, 2.0]);
+    assert!(matches!(
+        nalgebra_sparse::CscMatrix::try_from(&A),
+        Err(SparseFormatError::BadRowval)
+    ));
+}
+
+#[test]
+fn test_nalgebra_solve() {
+    // min x₁² + x₂² + x₁ + x₂  s.t.  x₁ + x₂ = 1,  0 ≤ x ≤ 0.7
+    let P = DMatrix::from_diagonal(&DVector::from_vec(vec![2.0, 2.0]));
+    let q = DVector::from_vec(vec![1.0, 1.0]);
+    #[rustfmt::skip]
+    let A = DMatrix::from_row_slice(5, 2, &[
+         1.0,  1.0,
+         1.0,  0.0,
+         0.0,  1.0,
+        -1.0,  0.0,
+         0.0, -1.0,
+    ]);
+    let b = DVector::from_vec(vec![1.0, 0.7, 0.7, 0.0, 0.0]);
+
+    let P = CscMatrix::from_dmatrix(&P).to_triu();
+    let A = nalgebra_sparse::CscMatrix::from(&A);
+    let A = CscMatrix::from_nalgebra_sparse(A);
+
+    let cones = [ZeroConeT(1), NonnegativeConeT(4)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    let x = DVector::from_vec(solver.solution.x.clone());
+    assert!((x - DVector::from_vec(vec![0.5, 0.5])).norm() <= 1e-6);
+}
@@ -0,0 +1,83 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "ndarray")]
+
+use clarabel::algebra::*;
+use clarabel::solver::*;
+use ndarray::{arr1, arr2, s, Array1, Array2, ShapeBuilder};
+
+#[test]
+fn test_ndarray_dense_layouts() {
+    let M = arr2(&[
+        [1.0, 0.0, 3.0], //
+        [0.0, 1e-12, -4.0],
+    ]);
+    let expected = CscMatrix::from(&[
+        [1.0, 0.0, 3.0], //
+        [0.0, 0.0, -4.0],
+    ]);
+
+    // standard layout
+    let A = CscMatrix::from_array2(M.view(), 1e-10);
+    assert!(A.check_format().is_ok());
+    assert_eq!(A, expected);
+
+    // Fortran layout
+    let mut F = Array2::zeros((2, 3).f());
+    F.assign(&M);
+    assert!(F.as_slice().is_none());
+    assert_eq!(CscMatrix::from_array2(F.view(), 1e-10), expected);
+
+    // strided view
+    let W = Array2::from_shape_fn((4, 6), |(i, j)| M[(i / 2, j / 2)]);
+    let V = W.slice(s![..;2, ..;2]);
+    assert_eq!(CscMatrix::from_array2(V, 1e-10), expected);
+
+    // dense roundtrip, with repeated entries added
+    let A = CscMatrix::new(2, 2, vec![0, 2, 3], vec![1, 1, 0], vec![1.0, 2.0, 5.0]);
+    assert_eq!(Array2::from(&A), arr2(&[[0.0, 5.0], [3.0, 0.0]]));
+}
+
+#[test]
+fn test_ndarray_solve() {
+    // min x₁² + x₂² + x₁ + x₂  s.t.  x₁ + x₂ = 1,  0 ≤ x ≤ 0.7
+    let P = Array2::from_diag(&arr1(&[2.0, 2.0]));
+    let q = arr1(&[1.0, 1.0]);
+    let A = arr2(&[
+        [1.0, 1.0],  //
+        [1.0, 0.0],  //
+        [0.0, 1.0],  //
+        [-1.0, 0.0], //
+        [0.0, -1.0],
+    ]);
+
+    // b is a non-contiguous view, and is copied
+    let bfull = arr1(&[1.0, 9.0, 0.7, 9.0, 0.7, 9.0, 0.0, 9.0, 0.0]);
+    let b = bfull.slice(s![..;2]);
+
+    let P = CscMatrix::from_array2(P.view(), 0.0).to_triu();
+    let A = CscMatrix::from_array2(A.view(), 0.0);
+
+    let cones = [ZeroConeT(1), NonnegativeConeT(4)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(false)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q.view(), &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    let x: Array1<f64> = solver.solution.x_array();
+    assert!(x.to_vec().dist(&[0.5, 0.5]) <= 1e-6);
+    assert_eq!(solver.solution.s_array().len(), 5);
+
+    // update q from an ndarray vector and resolve
+    let q = arr1(&[1.0, 0.0]);
+    solver.update_q(&q).unwrap();
+    solver.update_b(&b.to_owned()).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.x_array().to_vec().dist(&[0.3, 0.7]) <= 1e-6);
+}
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+// Runs the tests of the #![no_std] crate in tests/no_std, which
+// uses clarabel without its std feature.   It is a separate crate
+// since the tests here are built with the default features, and is
+// built into a separate target directory.
+
+#[test]
+fn test_no_std_crate() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let exe = std::env::current_exe().unwrap();
+    let target_dir = exe.parent().unwrap().parent().unwrap().join("no_std");
+    let status = Command::new(env!("CARGO"))
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(crate_dir.join("tests/no_std/Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("unable to run cargo");
+    assert!(status.success());
+}
@@ -0,0 +1,49 @@
+//! Solves a small QP from a `no_std` crate using clarabel built
+//! without its `std` feature.
+
+#![no_std]
+#![allow(non_snake_case)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use clarabel::{algebra::*, solver::*};
+
+pub fn solve_basic_qp(verbose: bool) -> (SolverStatus, Vec<f64>, f64) {
+    // P = [4. 1;1 2]
+    let P = CscMatrix::new(
+        2,                    // m
+        2,                    // n
+        vec![0, 2, 4],        // colptr
+        vec![0, 1, 0, 1],     // rowval
+        vec![4., 1., 1., 2.], // nzval
+    );
+
+    // A = [-1. -1;-1 0; 0 -1; 1 1;1 0; 0 1]
+    let A = CscMatrix::new(
+        6,                                        // m
+        2,                                        // n
+        vec![0, 4, 8],                            // colptr
+        vec![0, 1, 3, 4, 0, 2, 3, 5],             // rowval
+        vec![-1., -1., 1., 1., -1., -1., 1., 1.], // nzval
+    );
+
+    let c = [1., 1.];
+    let b = [-1., 0., 0., 1., 0.7, 0.7];
+    let cones = [NonnegativeConeT(3), NonnegativeConeT(3)];
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(verbose)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    (
+        solver.solution.status,
+        solver.solution.x,
+        solver.solution.solve_time,
+    )
+}
@@ -0,0 +1,44 @@
+use clarabel::solver::SolverStatus;
+use clarabel::timers::{set_clock, Clock};
+use clarabel_no_std_test::solve_basic_qp;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+static OUTPUT: Mutex<String> = Mutex::new(String::new());
+
+fn capture(s: &str) {
+    OUTPUT.lock().unwrap().push_str(s);
+}
+
+// advances by a millisecond on every reading
+struct TickClock(AtomicU64);
+
+impl Clock for TickClock {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.0.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+static CLOCK: TickClock = TickClock(AtomicU64::new(0));
+
+// both callbacks are global, so everything is checked in one test
+#[test]
+fn test_no_std_solve() {
+    // nothing is printed and no time passes without the callbacks
+    let (status, x, solve_time) = solve_basic_qp(true);
+    assert_eq!(status, SolverStatus::Solved);
+    assert!((x[0] - 0.3).abs() <= 1e-6 && (x[1] - 0.7).abs() <= 1e-6);
+    assert_eq!(solve_time, 0.0);
+
+    clarabel::set_print_callback(capture);
+    set_clock(&CLOCK);
+
+    let (status, _, solve_time) = solve_basic_qp(true);
+    assert_eq!(status, SolverStatus::Solved);
+    assert!(solve_time > 0.0);
+
+    let output = OUTPUT.lock().unwrap();
+    assert!(output.contains("Clarabel.rs"));
+    assert!(output.contains("Terminated with status = Solved"));
+}
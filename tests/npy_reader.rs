@@ -0,0 +1,144 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "io")]
+
+use clarabel::io::*;
+use clarabel::solver::*;
+use std::fs::File;
+
+fn test_file(name: &str) -> String {
+    format!("{}/tests/data/npy/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+// a .npy file with the given header dictionary and data,
+// in the version 1.0 layout written by np.save
+fn npy_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend((header.len() as u16).to_le_bytes());
+    bytes.extend(header.as_bytes());
+    bytes.extend(data);
+    bytes
+}
+
+#[test]
+fn test_read_npy() {
+    let q = read_npy(File::open(test_file("q.npy")).unwrap()).unwrap();
+    assert_eq!(q, NpyArray::F32(vec![1., 1.]));
+
+    let data: Vec<u8> = [3i64, -2].iter().flat_map(|x| x.to_le_bytes()).collect();
+    let header = "{'descr': '<i8', 'fortran_order': False, 'shape': (2,), }\n";
+    let v = read_npy(&npy_bytes(header, &data)[..]).unwrap();
+    assert_eq!(v, NpyArray::I64(vec![3, -2]));
+    assert_eq!(v.to_float::<f64>(), [3., -2.]);
+    assert_eq!(v.to_indices(), None);
+
+    // scalars are read as arrays of length one
+    let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (), }\n";
+    let v = read_npy(&npy_bytes(header, &2.5f64.to_le_bytes())[..]).unwrap();
+    assert_eq!(v, NpyArray::F64(vec![2.5]));
+}
+
+#[test]
+fn test_read_problem_npz() {
+    // min  2x₁² + x₁x₂ + x₂² + x₁ + x₂
+    // s.t. x₁ + x₂ = 1, 0 ≤ x ≤ 0.7
+    let problem = read_problem_npz::<f64>(test_file("qp.npz")).unwrap();
+    assert_eq!((problem.A.m, problem.A.n), (5, 2));
+    assert_eq!(problem.P.nnz(), 3);
+    assert!(matches!(
+        problem.cones[..],
+        [ZeroConeT(1), NonnegativeConeT(4)]
+    ));
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(f64::abs(solver.solution.obj_val - 1.88) <= 1e-7);
+    assert!(f64::abs(solver.solution.x[0] - 0.3) <= 1e-7);
+    assert!(f64::abs(solver.solution.x[1] - 0.7) <= 1e-7);
+}
+
+#[test]
+fn test_read_npz_errors() {
+    let result = read_problem_npz::<f64>(test_file("qp_compressed.npz"));
+    assert!(matches!(result, Err(NpyError::CompressedMember(name)) if name == "P_data.npy"));
+
+    let result = read_problem_npz::<f64>(test_file("qp_missing_b.npz"));
+    assert!(matches!(result, Err(NpyError::MissingMember(name)) if name == "b"));
+
+    // not a zip archive
+    let result = read_npz(File::open(test_file("q.npy")).unwrap());
+    assert!(matches!(result, Err(NpyError::BadArchive(_))));
+
+    // a corrupted member fails the CRC check
+    let mut bytes = std::fs::read(test_file("qp.npz")).unwrap();
+    let at = bytes.windows(6).position(|w| w == b"\x93NUMPY").unwrap();
+    bytes[at + 100] ^= 0xFF;
+    assert!(matches!(read_npz(&bytes[..]), Err(NpyError::BadArchive(_))));
+}
+
+#[test]
+fn test_read_npy_malformed() {
+    let data = [0u8; 16];
+    let header = |descr: &str, fortran: &str, shape: &str| {
+        format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}\n",
+            descr, fortran, shape
+        )
+    };
+    let read = |header: &str| read_npy(&npy_bytes(header, &data)[..]);
+
+    assert!(read(&header("<f8", "False", "(2,)")).is_ok());
+    assert!(matches!(
+        read(&header(">f8", "False", "(2,)")),
+        Err(NpyError::UnsupportedDtype(d)) if d == ">f8"
+    ));
+    assert!(matches!(
+        read(&header("<c16", "False", "(1,)")),
+        Err(NpyError::UnsupportedDtype(_))
+    ));
+    assert!(matches!(
+        read(&header("<f8", "True", "(2,)")),
+        Err(NpyError::FortranOrder)
+    ));
+    assert!(matches!(
+        read(&header("<f8", "False", "(1, 2)")),
+        Err(NpyError::UnsupportedShape(s)) if s == [1, 2]
+    ));
+    assert!(matches!(
+        read(&header("<f8", "False", "(3,)")),
+        Err(NpyError::Truncated {
+            expected: 24,
+            found: 16
+        })
+    ));
+    // a length whose size in bytes overflows
+    let shape = format!("({},)", usize::MAX / 4);
+    assert!(matches!(
+        read(&header("<f8", "False", &shape)),
+        Err(NpyError::BadHeader(_))
+    ));
+    assert!(matches!(
+        read("{'descr': '<f8', 'shape': (2,), }\n"),
+        Err(NpyError::BadHeader(_))
+    ));
+    assert!(matches!(
+        read(&header("<f8", "False", "(two,)")),
+        Err(NpyError::BadHeader(_))
+    ));
+
+    assert!(matches!(
+        read_npy(&b"PK\x03\x04 not npy"[..]),
+        Err(NpyError::BadMagic)
+    ));
+    let mut bytes = npy_bytes(&header("<f8", "False", "(2,)"), &data);
+    bytes[6] = 4;
+    assert!(matches!(
+        read_npy(&bytes[..]),
+        Err(NpyError::UnsupportedVersion(4, 0))
+    ));
+}
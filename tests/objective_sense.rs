@@ -0,0 +1,109 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn sense_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // max x1 + 3x2 - ½(x1² + x2²)  s.t.  x1 + x2 <= 1, x >= 0
+    let P = CscMatrix::<f64>::identity(2);
+    let c = vec![1., 3.];
+
+    let A = CscMatrix::from(&[
+        [1., 1.],  //
+        [-1., 0.], //
+        [0., -1.],
+    ]);
+    let b = vec![1., 0., 0.];
+
+    let cones = vec![NonnegativeConeT(3)];
+
+    (P, c, A, b, cones)
+}
+
+fn solve_with_sense(sense: OptimizationSense, q: &[f64]) -> (Vec<f64>, f64, f64, SolverStatus) {
+    let (P, _, A, b, cones) = sense_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .sense(sense)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    let sol = &solver.solution;
+    (sol.x.clone(), sol.obj_val, sol.obj_val_dual, sol.status)
+}
+
+#[test]
+fn test_objective_sense_maximize() {
+    let (_, c, _, _, _) = sense_test_data();
+    let negc: Vec<f64> = c.iter().map(|v| -v).collect();
+
+    let (xmin, objmin, dualmin, status) = solve_with_sense(OptimizationSense::Minimize, &negc);
+    assert_eq!(status, SolverStatus::Solved);
+
+    let (xmax, objmax, dualmax, status) = solve_with_sense(OptimizationSense::Maximize, &c);
+    assert_eq!(status, SolverStatus::Solved);
+
+    // optimum at x = (0,1) with objective 2.5
+    let refsol = [0., 1.];
+    assert!(xmax.dist(&refsol) <= 1e-6);
+    assert!(xmin.dist(&xmax) <= 1e-12);
+    assert!(f64::abs(objmax - 2.5) <= 1e-6);
+    assert_eq!(objmax, -objmin);
+    assert_eq!(dualmax, -dualmin);
+}
+
+#[test]
+fn test_objective_sense_negate_objective() {
+    let (P, c, A, b, cones) = sense_test_data();
+    let (xref, objref, _, _) = solve_with_sense(OptimizationSense::Maximize, &c);
+
+    let settings = DefaultSettings::default();
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+    assert!(!solver.data.is_maximization());
+    solver.data.negate_objective();
+    assert!(solver.data.is_maximization());
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.x.dist(&xref) <= 1e-12);
+    assert_eq!(solver.solution.obj_val, objref);
+
+    // negating twice gives back the minimization, with optimum at x = 0
+    solver.data.negate_objective();
+    assert!(!solver.data.is_maximization());
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.x.norm_inf() <= 1e-6);
+}
+
+#[test]
+fn test_objective_sense_update_q() {
+    let (P, c, A, b, cones) = sense_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .sense(OptimizationSense::Maximize)
+        .presolve_enable(false)
+        .equilibrate_enable(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
+
+    // updated linear terms are given in the maximization sense
+    let cnew = vec![3., 1.];
+    solver.update_q(&cnew).unwrap();
+    solver.solve();
+
+    // optimum at x = (1,0) with objective 2.5
+    let refsol = [1., 0.];
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.x.dist(&refsol) <= 1e-6);
+    assert!(f64::abs(solver.solution.obj_val - 2.5) <= 1e-6);
+}
@@ -0,0 +1,118 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn counters_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::from(&[
+        [4., 1.], //
+        [1., 2.],
+    ])
+    .to_triu();
+    let q = vec![1., 1.];
+
+    let A = CscMatrix::from(&[
+        [1., 1.],  //
+        [1., 0.],  //
+        [0., 1.],  //
+        [-1., 0.], //
+        [0., -1.],
+    ]);
+    let b = vec![1., 0.7, 0.7, 0., 0.];
+
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(4)];
+
+    (P, q, A, b, cones)
+}
+
+#[test]
+fn test_op_counters_symmetric() {
+    let (P, q, A, b, cones) = counters_test_data();
+
+    let settings = DefaultSettings::default();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+
+    let iters = solver.info.iterations as u64;
+    let counts = solver.info.counters();
+
+    // at least the predictor and corrector solves at every iteration
+    assert!(counts.kkt_solves >= 2 * iters);
+
+    // one factorization per iteration, plus one for the initial point
+    assert!(counts.factorizations <= iters + 1);
+    assert!(counts.factorizations >= iters);
+
+    // one scaling update per iteration
+    assert_eq!(counts.cone_scaling_updates, iters);
+
+    // no backtracking without nonsymmetric cones
+    assert_eq!(counts.backtrack_evaluations, 0);
+}
+
+#[test]
+fn test_op_counters_reset_per_solve() {
+    let (P, q, A, b, cones) = counters_test_data();
+
+    let settings = DefaultSettings::default();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+
+    solver.solve();
+    let first = solver.info.counters();
+
+    solver.solve();
+    let second = solver.info.counters();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_op_counters_refinement() {
+    let (P, q, A, b, cones) = counters_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .iterative_refinement_enable(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.info.counters().refinement_steps, 0);
+}
+
+#[test]
+fn test_op_counters_nonsymmetric() {
+    // max x s.t. y * exp(x / y) <= z, y == 1, z == exp(5)
+    let P = CscMatrix::<f64>::zeros((3, 3));
+    let q = vec![-1., 0., 0.];
+
+    let mut A1 = CscMatrix::<f64>::identity(3);
+    A1.negate();
+    let A2 = CscMatrix::new(2, 3, vec![0, 0, 1, 2], vec![0, 1], vec![1., 1.]);
+    let A = CscMatrix::vcat(&A1, &A2);
+    let b = vec![0., 0., 0., 1., f64::exp(5.)];
+
+    let cones = vec![ExponentialConeT(), ZeroConeT(2)];
+
+    let settings = DefaultSettings::default();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+
+    let iters = solver.info.iterations as u64;
+    let counts = solver.info.counters();
+
+    // no initial KKT factorization for nonsymmetric cones
+    assert!(counts.kkt_solves >= 2 * iters);
+    assert!(counts.factorizations <= iters);
+    assert_eq!(counts.cone_scaling_updates, iters);
+}
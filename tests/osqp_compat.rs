@@ -0,0 +1,120 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "io")]
+
+use clarabel::algebra::*;
+use clarabel::io::*;
+use clarabel::solver::*;
+
+// the example problem from the OSQP documentation, with solution
+// x = (0.3, 0.7), y = (-2.9, 0, 0.2) and objective 1.88
+
+#[allow(clippy::type_complexity)]
+fn osqp_demo_data() -> (CscMatrix<f64>, Vec<f64>, CscMatrix<f64>, Vec<f64>, Vec<f64>) {
+    let P = CscMatrix::from(&[
+        [4., 1.], //
+        [0., 2.], //
+    ]);
+    let q = vec![1., 1.];
+    let A = CscMatrix::from(&[
+        [1., 1.], //
+        [1., 0.], //
+        [0., 1.], //
+    ]);
+    let l = vec![1., 0., 0.];
+    let u = vec![1., 0.7, 0.7];
+    (P, q, A, l, u)
+}
+
+#[test]
+fn test_osqp_demo_problem() {
+    let (P, q, A, l, u) = osqp_demo_data();
+    let problem = from_osqp_data(&P, &q, &A, &l, &u).unwrap();
+
+    let osqp_settings = OsqpSettings {
+        eps_abs: 1e-8,
+        eps_rel: 1e-8,
+        ..OsqpSettings::default()
+    };
+    let mut settings = osqp_settings.to_default_settings();
+    settings.verbose = false;
+
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(f64::abs(solver.solution.obj_val - 1.88) <= 1e-6);
+
+    let sol = osqp_solution(&problem, &solver.solution, true);
+    assert!(sol.x.dist(&[0.3, 0.7]) <= 1e-6);
+    assert!(sol.y.dist(&[-2.9, 0., 0.2]) <= 1e-6);
+
+    let sol = osqp_solution(&problem, &solver.solution, false);
+    assert!(sol.y.dist(&[2.9, 0., -0.2]) <= 1e-6);
+}
+
+#[test]
+fn test_osqp_infinite_bounds() {
+    // the upper bounds of the last two rows are inactive
+    let (P, q, A, l, mut u) = osqp_demo_data();
+    u[1] = 1e30;
+    u[2] = f64::INFINITY;
+
+    let problem = from_osqp_data(&P, &q, &A, &l, &u).unwrap();
+    assert_eq!(problem.u[1], f64::INFINITY);
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    // x = (1/4, 3/4) with only the equality active
+    let sol = osqp_solution(&problem, &solver.solution, true);
+    assert!(sol.x.dist(&[0.25, 0.75]) <= 1e-6);
+    assert!(sol.y.dist(&[-2.75, 0., 0.]) <= 1e-6);
+}
+
+#[test]
+fn test_osqp_settings_translation() {
+    let settings = OsqpSettings::<f64> {
+        eps_abs: 1e-5,
+        eps_rel: 1e-6,
+        max_iter: 100,
+        scaling: 0,
+        polish: true,
+    }
+    .to_default_settings();
+
+    assert_eq!(settings.tol_gap_abs, 1e-5);
+    assert_eq!(settings.tol_gap_rel, 1e-6);
+    assert_eq!(settings.tol_feas, 1e-6);
+    assert_eq!(settings.max_iter, 100);
+    assert!(!settings.equilibrate_enable);
+
+    let settings = OsqpSettings::<f64>::default().to_default_settings();
+    assert!(settings.equilibrate_enable);
+    assert_eq!(settings.equilibrate_max_iter, 10);
+}
+
+#[test]
+fn test_osqp_bad_data() {
+    let (P, q, A, l, u) = osqp_demo_data();
+
+    let Pfull = CscMatrix::from(&[
+        [4., 1.], //
+        [1., 2.], //
+    ]);
+    assert!(matches!(
+        from_osqp_data(&Pfull, &q, &A, &l, &u),
+        Err(OsqpDataError::NotUpperTriangular)
+    ));
+    assert!(matches!(
+        from_osqp_data(&P, &q, &A, &l[..2], &u),
+        Err(OsqpDataError::IncompatibleDimension(_))
+    ));
+    assert!(matches!(
+        from_osqp_data(&P, &q, &A, &u, &l),
+        Err(OsqpDataError::InfeasibleBounds(1))
+    ));
+}
@@ -0,0 +1,81 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// min ½‖x‖² - x₀ s.t. x ≤ 2
+fn new_solver(catch_panics: bool) -> DefaultSolver<f64> {
+    let P = CscMatrix::identity(2);
+    let q = [-1., 0.];
+    let A = CscMatrix::identity(2);
+    let b = [2., 2.];
+    let cones = [NonnegativeConeT(2)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(false)
+        .catch_panics(catch_panics)
+        .build()
+        .unwrap();
+    DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap()
+}
+
+// corrupt the internal data so that copies of b fail
+// with a length mismatch, as for a bug in the solver
+fn corrupt(solver: &mut DefaultSolver<f64>) {
+    solver.data.b.truncate(1);
+}
+
+#[test]
+fn test_panic_classified() {
+    let mut solver = new_solver(true);
+    corrupt(&mut solver);
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::NumericalError);
+    assert!(solver.solution.x.iter().all(|x| x.is_nan()));
+    assert!(solver.is_poisoned());
+
+    let detail = solver.info.termination_detail.as_ref().unwrap();
+    assert!(detail.contains("does not match"), "{}", detail);
+}
+
+#[test]
+fn test_poisoned_solver_rejected() {
+    let mut solver = new_solver(true);
+    corrupt(&mut solver);
+    solver.solve();
+
+    // repairing the data is not enough, since other
+    // state may have been left inconsistent
+    solver.data.b.push(solver.data.b[0]);
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::NumericalError);
+    let detail = solver.info.termination_detail.as_ref().unwrap();
+    assert!(detail.contains("poisoned"), "{}", detail);
+
+    assert!(matches!(
+        solver.update_q(&vec![1., 1.]),
+        Err(DataUpdateError::Poisoned)
+    ));
+}
+
+#[test]
+fn test_panic_reraised() {
+    let mut solver = new_solver(false);
+    corrupt(&mut solver);
+
+    let result = catch_unwind(AssertUnwindSafe(|| solver.solve()));
+    assert!(result.is_err());
+    assert!(!solver.is_poisoned());
+}
+
+#[test]
+fn test_no_panic_unaffected() {
+    let mut solver = new_solver(true);
+    solver.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.info.termination_detail.is_none());
+    assert!(!solver.is_poisoned());
+    assert!(solver.update_q(&vec![1., 1.]).is_ok());
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "parallel")]
+#![allow(non_snake_case)]
+
+// A solve with products computed in parallel makes no allocations
+// per iteration.   This is separate from allocation_regression.rs,
+// since it changes the parallel threshold for the whole process and
+// counts allocations made by the rayon worker threads
+
+use clarabel::{algebra::*, solver::*};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_no_iteration_allocations_parallel() {
+    // box constrained QP with a tridiagonal P
+    let n = 5000;
+    let P = CscMatrix::from_banded(n, &[(0, vec![4.; n]), (1, vec![-1.; n - 1])]).to_triu();
+    let q: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &{
+        let mut I = CscMatrix::identity(n);
+        I.negate();
+        I
+    });
+    let b = vec![0.1; 2 * n];
+    let cones = [NonnegativeConeT(2 * n)];
+
+    // products are only computed in parallel with more than one
+    // thread, so make sure the pool has several on any machine
+    std::env::set_var("RAYON_NUM_THREADS", "4");
+    set_parallel_gemv_threshold(0);
+    let solve = |max_iter| {
+        let settings = DefaultSettingsBuilder::default()
+            .verbose(false)
+            .max_iter(max_iter)
+            .build()
+            .unwrap();
+        let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+        let start = ALLOCATIONS.load(Ordering::Relaxed);
+        solver.solve();
+        let count = ALLOCATIONS.load(Ordering::Relaxed) - start;
+        (count, solver.solution.iterations)
+    };
+
+    // warm up, since the thread pool allocates as it starts
+    solve(1);
+    let (first, _) = solve(1);
+    let (count, iterations) = solve(50);
+    assert!(iterations > 5);
+
+    // rayon allocates internally now and then as it queues
+    // jobs, but far less often than once per iteration
+    assert!(
+        count - first < iterations as usize,
+        "{} allocations after {} iterations, {} after 1",
+        count,
+        iterations,
+        first
+    );
+}
@@ -0,0 +1,149 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "parallel")]
+
+use clarabel::{algebra::*, solver::*};
+use std::sync::Mutex;
+
+// the threshold is set at module level, so solves are serialized
+static THRESHOLD_LOCK: Mutex<()> = Mutex::new(());
+
+// min Σ tₖ + aₖuₖ + bₖvₖ  s.t.  ‖(uₖ,vₖ) - cₖ‖ ≤ tₖ,  uₖ,vₖ ≥ -1
+// for k = 1..nblocks, with one second order and one nonnegative
+// cone per block, or an exponential cone in place of the second
+// order cone for every tenth block if `with_expcones` is set
+fn many_cones_data(
+    nblocks: usize,
+    with_expcones: bool,
+) -> (Vec<f64>, CscMatrix<f64>, Vec<f64>, Vec<SupportedConeT<f64>>) {
+    let n = 3 * nblocks;
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    let (mut q, mut b, mut cones) = (vec![], vec![], vec![]);
+
+    for k in 0..nblocks {
+        let (t, u, v) = (3 * k, 3 * k + 1, 3 * k + 2);
+        let row = 5 * k;
+        let theta = k as f64;
+        q.extend([1., 0.5 * theta.cos(), 0.5 * theta.sin()]);
+
+        // (tₖ, uₖ - c₁, vₖ - c₂) in the second order cone, or
+        // (uₖ - c₁, vₖ - c₂, tₖ) in the exponential cone
+        let expcone = with_expcones && k % 10 == 0;
+        let order = if expcone { [u, v, t] } else { [t, u, v] };
+        for (i, &j) in order.iter().enumerate() {
+            I.push(row + i);
+            J.push(j);
+            V.push(-1.);
+        }
+        let c = [(2. * theta).cos(), (2. * theta).sin()];
+        if expcone {
+            b.extend([-c[0], -c[1], 0.]);
+            cones.push(ExponentialConeT());
+        } else {
+            b.extend([0., -c[0], -c[1]]);
+            cones.push(SecondOrderConeT(3));
+        }
+
+        // uₖ, vₖ ≥ -1
+        I.extend([row + 3, row + 4]);
+        J.extend([u, v]);
+        V.extend([-1., -1.]);
+        b.extend([1., 1.]);
+        cones.push(NonnegativeConeT(2));
+    }
+
+    let A = CscMatrix::new_from_triplets(5 * nblocks, n, I, J, V);
+    (q, A, b, cones)
+}
+
+fn solve(
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    cones: &[SupportedConeT<f64>],
+    threshold: usize,
+) -> DefaultSolution<f64> {
+    let _guard = THRESHOLD_LOCK.lock().unwrap();
+    set_parallel_cones_threshold(threshold);
+    let P = CscMatrix::zeros((q.len(), q.len()));
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, q, A, b, cones, settings).unwrap();
+    solver.solve();
+    default_parallel_cones_threshold();
+    solver.solution
+}
+
+// solve within a pool of several threads, since cone operations
+// are computed serially when there is only one
+fn solve_in_pool(
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    cones: &[SupportedConeT<f64>],
+    threshold: usize,
+) -> DefaultSolution<f64> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    pool.install(|| solve(q, A, b, cones, threshold))
+}
+
+#[test]
+fn test_parallel_cones_symmetric() {
+    let (q, A, b, cones) = many_cones_data(200, false);
+
+    let serial = solve_in_pool(&q, &A, &b, &cones, usize::MAX);
+    let parallel = solve_in_pool(&q, &A, &b, &cones, 0);
+
+    // with symmetric cones only, the steps are identical
+    assert_eq!(serial.status, SolverStatus::Solved);
+    assert_eq!(parallel.status, SolverStatus::Solved);
+    assert_eq!(serial.iterations, parallel.iterations);
+    assert_eq!(serial.x, parallel.x);
+    assert_eq!(serial.z, parallel.z);
+}
+
+#[test]
+fn test_parallel_cones_nonsymmetric() {
+    let (q, A, b, cones) = many_cones_data(200, true);
+
+    let serial = solve_in_pool(&q, &A, &b, &cones, usize::MAX);
+    let parallel = solve_in_pool(&q, &A, &b, &cones, 0);
+
+    assert_eq!(serial.status, SolverStatus::Solved);
+    assert_eq!(parallel.status, SolverStatus::Solved);
+    assert!(f64::abs(serial.obj_val - parallel.obj_val) <= 1e-6);
+    assert!(serial.x.dist(&parallel.x) <= 1e-4);
+}
+
+// Timing of serial and parallel solves of a problem with 10k second
+// order and nonnegative cones.   Run with
+//
+//   cargo test --release --features parallel --test parallel_cones -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_parallel_cones() {
+    use std::time::Instant;
+
+    let (q, A, b, cones) = many_cones_data(5000, false);
+    println!(
+        "{} cones, {} threads",
+        cones.len(),
+        rayon::current_num_threads()
+    );
+
+    for (label, threshold) in [("serial", usize::MAX), ("parallel", 0)] {
+        let start = Instant::now();
+        let solution = solve(&q, &A, &b, &cones, threshold);
+        println!(
+            "{:>10}: {:8.2} ms, {} iterations, status {:?}",
+            label,
+            start.elapsed().as_secs_f64() * 1e3,
+            solution.iterations,
+            solution.status
+        );
+    }
+}
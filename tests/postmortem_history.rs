@@ -0,0 +1,103 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn postmortem_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::<f64>::identity(3);
+
+    let I1 = CscMatrix::<f64>::identity(3);
+    let mut I2 = CscMatrix::<f64>::identity(3);
+    I2.negate();
+    let A = CscMatrix::vcat(&I1, &I2);
+
+    let q = vec![3., -2., 1.];
+    let b = vec![1.; 6];
+
+    let cones = vec![NonnegativeConeT(3), NonnegativeConeT(3)];
+
+    (P, q, A, b, cones)
+}
+
+#[test]
+fn test_postmortem_history_within_window() {
+    let (P, q, A, b, cones) = postmortem_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .max_iter(3)
+        .postmortem_window(10)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.info.status, SolverStatus::MaxIterations);
+
+    // one record for the starting point plus one per iteration
+    let history = solver.info.recent_history();
+    let iters: Vec<u32> = history.iter().map(|r| r.iteration).collect();
+    assert_eq!(iters, vec![0, 1, 2, 3]);
+
+    // the final record should agree with the final info values
+    let last = history.last().unwrap();
+    assert_eq!(last.cost_primal, solver.info.cost_primal);
+    assert_eq!(last.cost_dual, solver.info.cost_dual);
+    assert_eq!(last.res_primal, solver.info.res_primal);
+    assert_eq!(last.res_dual, solver.info.res_dual);
+    assert_eq!(last.gap_abs, solver.info.gap_abs);
+    assert_eq!(last.ktratio, solver.info.ktratio);
+    assert_eq!(last.μ, solver.info.μ);
+    assert_eq!(last.step_length, solver.info.step_length);
+}
+
+#[test]
+fn test_postmortem_history_wraps() {
+    let (P, q, A, b, cones) = postmortem_test_data();
+
+    // full run to compare against
+    let settings = DefaultSettingsBuilder::default()
+        .max_iter(5)
+        .postmortem_window(100)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    let full = solver.info.recent_history();
+    assert_eq!(full.len(), 6);
+
+    // same run, but with a short window
+    let settings = DefaultSettingsBuilder::default()
+        .max_iter(5)
+        .postmortem_window(2)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    let recent = solver.info.recent_history();
+
+    assert_eq!(recent.len(), 2);
+    assert_eq!(recent[..], full[4..]);
+}
+
+#[test]
+fn test_postmortem_history_disabled() {
+    let (P, q, A, b, cones) = postmortem_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .postmortem_window(0)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+    assert!(solver.info.recent_history().is_empty());
+}
@@ -33,7 +33,7 @@ fn test_presolve_single_unbounded() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -53,7 +53,7 @@ fn test_presolve_completely_redundant_cone() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -74,7 +74,7 @@ fn test_presolve_every_constraint_redundant() {
 
     let settings = DefaultSettings::default();
 
-    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings);
+    let mut solver = DefaultSolver::new(&P, &c, &A, &b, &cones, settings).unwrap();
 
     solver.solve();
 
@@ -92,3 +92,164 @@ fn test_presolve_settable_bound() {
     default_infinity();
     assert_eq!(get_infinity(), default_bound);
 }
+
+#[allow(clippy::type_complexity)]
+fn reductions_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // x₀ is coupled to x₁ in the objective, and x₄
+    // appears only in the objective
+    let P = CscMatrix::from(&[
+        [1., 0.5, 0., 0., 0.], //
+        [0., 2., 0., 0., 0.],  //
+        [0., 0., 1., 0., 0.],  //
+        [0., 0., 0., 1., 0.],  //
+        [0., 0., 0., 0., 4.],  //
+    ]);
+    let q = vec![1., -1., 0.5, 2., -2.];
+
+    let A = CscMatrix::from(&[
+        [1., 0., 0., 0., 0.],  // x₀ = 1 : fixes x₀
+        [1., 1., 1., 0., 0.],  // x₀ + x₁ + x₂ = 3
+        [0., 0., 0., 1., 0.],  // x₃ ≤ 0.5
+        [0., 0., 0., -1., 0.], // x₃ ≥ 0.5 : fixes x₃
+        [0., 1., 0., 0., 0.],  // x₁ ≤ 5 : redundant
+        [0., 1., 0., 0., 0.],  // x₁ ≤ 2
+        [0., 0., -1., 0., 0.], // x₂ ≥ 0
+        [0., 0., 0., 0., 0.],  // 0 ≤ 1 : empty
+        [1., 1., 0., 0., 0.],  // x₀ + x₁ ≤ 10
+    ]);
+    let b = vec![1., 3., 0.5, -0.5, 5., 2., 0., 1., 10.];
+    let cones = vec![ZeroConeT(2), NonnegativeConeT(7)];
+
+    (P, q, A, b, cones)
+}
+
+fn reductions_settings(enable: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .presolve_reductions_enable(enable)
+        .verbose(false)
+        .build()
+        .unwrap()
+}
+
+// max violation of the KKT conditions for the original problem
+fn kkt_violation(
+    P: &CscMatrix<f64>,
+    q: &[f64],
+    A: &CscMatrix<f64>,
+    b: &[f64],
+    x: &[f64],
+    z: &[f64],
+    s: &[f64],
+) -> f64 {
+    // r = Px + q + A'z, with P upper triangular
+    let mut r = q.to_vec();
+    let mut rp = b.to_vec();
+    for col in 0..P.n {
+        for k in P.colptr[col]..P.colptr[col + 1] {
+            let row = P.rowval[k];
+            r[row] += P.nzval[k] * x[col];
+            if row != col {
+                r[col] += P.nzval[k] * x[row];
+            }
+        }
+    }
+    // rp = b - Ax - s
+    for col in 0..A.n {
+        for k in A.colptr[col]..A.colptr[col + 1] {
+            r[col] += A.nzval[k] * z[A.rowval[k]];
+            rp[A.rowval[k]] -= A.nzval[k] * x[col];
+        }
+    }
+    rp.axpby(-1., s, 1.);
+    f64::max(r.norm_inf(), rp.norm_inf()).max(f64::abs(z.dot(s)))
+}
+
+#[test]
+fn test_presolve_reductions() {
+    let (P, q, A, b, cones) = reductions_test_data();
+
+    let reductions = PresolveResult::new(&P, &q, &A, &b, &cones);
+
+    // x₀ and x₃ are fixed by constraints, x₄ by the objective
+    assert_eq!(reductions.removed_variables, vec![0, 3, 4]);
+    assert_eq!(reductions.fixed_values, vec![1., 0.5, 0.5]);
+    // x₀ + x₁ ≤ 10 becomes a redundant bound once x₀ is fixed
+    assert_eq!(reductions.removed_constraints, vec![0, 2, 3, 4, 7, 8]);
+
+    // x₁ + x₂ = 2, x₁ ≤ 2, x₂ ≥ 0
+    assert_eq!((reductions.A.m, reductions.A.n), (3, 2));
+    assert_eq!(reductions.b, vec![2., 2., 0.]);
+    assert_eq!(reductions.q, vec![-0.5, 0.5]);
+    assert!(matches!(
+        reductions.cones[..],
+        [ZeroConeT(1), NonnegativeConeT(2)]
+    ));
+
+    // solve the reduced problem and recover the full solution
+    let mut solver = reductions.solver(reductions_settings(false)).unwrap();
+    solver.solve();
+    let solution = reductions.postsolve(&solver.solution);
+
+    assert_eq!(solution.status, SolverStatus::Solved);
+    assert!(solution.x.dist(&[1., 1., 1., 0.5, 0.5]) <= 1e-6);
+    assert!(kkt_violation(&P, &q, &A, &b, &solution.x, &solution.z, &solution.s) <= 1e-6);
+
+    // objective includes the terms of the fixed variables
+    let mut full = DefaultSolver::new(&P, &q, &A, &b, &cones, reductions_settings(false)).unwrap();
+    full.solve();
+    assert!(f64::abs(solution.obj_val - full.solution.obj_val) <= 1e-6);
+    assert!(f64::abs(solution.obj_val_dual - full.solution.obj_val_dual) <= 1e-6);
+}
+
+#[test]
+fn test_presolve_reductions_setting() {
+    let (P, q, A, b, cones) = reductions_test_data();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, reductions_settings(true)).unwrap();
+    solver.solve();
+
+    // solution is reported for the original problem
+    let reductions = solver.presolve_result().unwrap();
+    assert_eq!(reductions.removed_variables, vec![0, 3, 4]);
+    assert_eq!(solver.variables.x.len(), 2);
+    assert_eq!(solver.solution.x.len(), 5);
+    assert_eq!(solver.solution.z.len(), 9);
+
+    let mut full = DefaultSolver::new(&P, &q, &A, &b, &cones, reductions_settings(false)).unwrap();
+    full.solve();
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(solver.solution.x.dist(&full.solution.x) <= 1e-6);
+    assert!(solver.solution.s.dist(&full.solution.s) <= 1e-6);
+    assert!(f64::abs(solver.solution.obj_val - full.solution.obj_val) <= 1e-6);
+
+    let sol = &solver.solution;
+    assert!(kkt_violation(&P, &q, &A, &b, &sol.x, &sol.z, &sol.s) <= 1e-6);
+    assert_eq!(sol.z[4], 0.);
+    assert_eq!(sol.z[7], 0.);
+}
+
+#[test]
+fn test_presolve_reductions_infeasible() {
+    let (P, q, A, mut b, cones) = reductions_test_data();
+
+    // x₃ ≤ 0.5 and x₃ ≥ 1 are left for the solver to detect
+    b[3] = -1.;
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, reductions_settings(true)).unwrap();
+    solver.solve();
+
+    let mut full = DefaultSolver::new(&P, &q, &A, &b, &cones, reductions_settings(false)).unwrap();
+    full.solve();
+
+    assert_eq!(full.solution.status, SolverStatus::PrimalInfeasible);
+    assert_eq!(solver.solution.status, SolverStatus::PrimalInfeasible);
+    assert_eq!(solver.solution.x.len(), 5);
+    assert!(solver.solution.obj_val.is_nan());
+}
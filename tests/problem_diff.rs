@@ -0,0 +1,127 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "io")]
+
+use clarabel::algebra::*;
+use clarabel::io::*;
+use clarabel::solver::*;
+
+fn test_problem() -> ConicProblem<f64> {
+    // P = [4 1; 1 2] (triu), A = [1 1; -I; I]
+    let P = CscMatrix::new(2, 2, vec![0, 1, 3], vec![0, 0, 1], vec![4., 1., 2.]);
+    let A = CscMatrix::new(
+        5,
+        2,
+        vec![0, 3, 6],
+        vec![0, 1, 3, 0, 2, 4],
+        vec![1., -1., 1., 1., -1., 1.],
+    );
+    ConicProblem {
+        P,
+        q: vec![1., 1.],
+        A,
+        b: vec![1., 0., 0., 0.7, 0.7],
+        cones: vec![ZeroConeT(1), NonnegativeConeT(4)],
+        sense: OptimizationSense::Minimize,
+        objective_constant: 0.,
+    }
+}
+
+#[test]
+fn test_problem_diff_identical() {
+    let problem = test_problem();
+    let diff = problem.diff(&problem.clone(), 0.);
+    assert!(diff.is_empty());
+    assert_eq!(diff.max_deviation(), 0.);
+    assert_eq!(diff.to_string(), "problems are identical\n");
+}
+
+#[test]
+fn test_problem_diff_perturbed() {
+    let problem = test_problem();
+
+    let mut other = problem.clone();
+    other.A.set_entry((3, 1), 0.5);
+    other.A.set_entry((4, 1), 1. + 1e-12);
+    other.P.set_entry((0, 1), 0.);
+    other.q[1] = 1.25;
+    other.cones[1] = NonnegativeConeT(3);
+    other.cones.push(ExponentialConeT());
+    other.objective_constant = 2.;
+
+    let diff = problem.diff(&other, 1e-9);
+    assert!(!diff.is_empty());
+    assert!(diff.dimensions.is_empty());
+    assert!(!diff.sense_differs);
+    assert_eq!(diff.objective_constant, Some((0., 2.)));
+
+    assert_eq!(diff.cones.len(), 2);
+    assert_eq!(diff.cones[0].index, 1);
+    assert_eq!(diff.cones[0].right, Some(NonnegativeConeT(3)));
+    assert_eq!(diff.cones[1].index, 2);
+    assert_eq!(diff.cones[1].left, None);
+
+    // the new entry of A is the only pattern difference
+    assert_eq!(diff.pattern.len(), 1);
+    assert_eq!(diff.pattern[0].name, "A");
+    assert_eq!(diff.pattern[0].index, (3, 1));
+    assert_eq!(diff.pattern[0].left, None);
+
+    // values by decreasing deviation, ignoring the change below tol
+    let values: Vec<_> = diff
+        .values
+        .iter()
+        .map(|e| (e.name, e.index, e.deviation()))
+        .collect();
+    assert_eq!(
+        values,
+        [("P", (0, 1), 1.), ("A", (3, 1), 0.5), ("q", (1, 0), 0.25)]
+    );
+    assert_eq!(diff.max_deviation(), 1.);
+
+    let text = diff.to_string();
+    assert!(text.contains("cone 2 differs: (none) vs ExponentialConeT"));
+    assert!(text.contains("3 value differences"));
+}
+
+#[test]
+fn test_problem_diff_dimensions() {
+    let problem = test_problem();
+    let mut other = problem.clone();
+    other.b.push(1.);
+    other.A = CscMatrix::vcat(&other.A, &CscMatrix::identity(2));
+    other.sense = OptimizationSense::Maximize;
+
+    let diff = problem.diff(&other, 0.);
+    assert!(diff.sense_differs);
+    assert_eq!(diff.dimensions.len(), 2);
+    assert_eq!(diff.dimensions[0].name, "A");
+    assert_eq!(diff.dimensions[0].right, (7, 2));
+    assert_eq!(diff.dimensions[1].name, "b");
+    assert_eq!(diff.dimensions[1].right, (6, 1));
+    assert!(diff.values.is_empty());
+}
+
+#[test]
+fn test_problem_pretty_print() {
+    let problem = test_problem();
+    let mut out = Vec::new();
+    problem.pretty_print(&mut out, 2).unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    assert!(text.contains("constraints m = 5"));
+    assert!(text.contains("nnz(P) = 3, nnz(A) = 6"));
+    assert!(text.contains("rows        1 ..       5  NonnegativeConeT(4)"));
+    assert!(text.contains("largest entries of P (2 of 3)"));
+    assert!(text.contains("largest entries of b (2 of 5)"));
+
+    // the largest entry of P comes first
+    let lines: Vec<&str> = text.lines().collect();
+    let at = lines
+        .iter()
+        .position(|l| l.starts_with("largest entries of P"))
+        .unwrap();
+    assert_eq!(
+        lines[at + 2].split_whitespace().collect::<Vec<_>>(),
+        ["0", "0", "4.000000e0"]
+    );
+}
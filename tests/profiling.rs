@@ -0,0 +1,75 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "profiling")]
+
+use clarabel::{algebra::*, solver::*, timers::*};
+
+// min ½xᵀPx + qᵀx s.t. -1 ≤ Bx ≤ 1, with P diagonal, B block
+// diagonal in dense 4×4 blocks and the upper bounds as three
+// dimensional second order cones
+fn solve_block_problem(n: usize) -> DefaultSolver<f64> {
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for k in (0..n).step_by(4) {
+        for j in 0..4 {
+            for i in 0..4 {
+                I.push(k + i);
+                J.push(k + j);
+                V.push(if i == j { 2. } else { 1. / (1 + i + j) as f64 });
+            }
+        }
+    }
+    let B = CscMatrix::new_from_triplets(n, n, I, J, V);
+    let mut Bneg = B.clone();
+    Bneg.negate();
+
+    let P = CscMatrix::from_banded(n, &[(0, vec![2.; n])]);
+    let q: Vec<f64> = (0..n).map(|i| ((i + 1) as f64).sin()).collect();
+    let A = CscMatrix::vcat(&B, &Bneg);
+    let b = vec![1.; 2 * n];
+    let k = n / 3;
+    let mut cones: Vec<_> = (0..k).map(|_| SecondOrderConeT(3)).collect();
+    cones.push(NonnegativeConeT(2 * n - 3 * k));
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+#[test]
+fn test_profile_phases() {
+    let solver = solve_block_problem(400);
+    let info = &solver.info;
+    assert_eq!(info.status, SolverStatus::Solved);
+
+    let profile = info.profile();
+    for phase in Phase::ALL {
+        assert!(
+            !profile.get(phase).is_zero(),
+            "no time recorded for phase {}",
+            phase.name()
+        );
+    }
+
+    // the phases cover everything but printing and bookkeeping
+    let total = profile.total().as_secs_f64();
+    assert!(total <= info.solve_time);
+    assert!(total >= 0.95 * info.solve_time, "{}", profile);
+}
+
+#[test]
+fn test_profile_resolve() {
+    // a second solve replaces the solve phases but keeps setup
+    let mut solver = solve_block_problem(40);
+    let first = solver.info.profile().clone();
+    solver.solve();
+    let second = solver.info.profile();
+
+    for phase in [Phase::Setup, Phase::Equilibration] {
+        assert_eq!(first.get(phase), second.get(phase));
+    }
+    assert_ne!(first.get(Phase::Solve), second.get(Phase::Solve));
+    assert!(format!("{}", second).contains("linesearch"));
+}
@@ -0,0 +1,258 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "io")]
+
+use clarabel::algebra::*;
+use clarabel::io::*;
+use clarabel::solver::*;
+use std::fs::File;
+use std::io::BufReader;
+
+fn read_test_file(name: &str) -> QpProblem<f64> {
+    let path = format!("{}/tests/data/qps/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let file = File::open(path).unwrap();
+    read_qps(BufReader::new(file)).unwrap()
+}
+
+fn read_snippet(text: &str) -> Result<QpProblem<f64>, QpsError> {
+    read_qps(text.as_bytes())
+}
+
+fn solve_to_objective(problem: &QpProblem<f64>) -> (f64, Vec<f64>) {
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    let obj = solver.solution.obj_val + problem.objective_constant;
+    (obj, solver.solution.x.clone())
+}
+
+#[test]
+fn test_qps_rows_columns_rhs() {
+    let problem = read_snippet(
+        "\
+NAME          ROWTEST
+* a comment line
+ROWS
+ N  COST
+ E  EQ
+ L  LE
+ G  GE
+ N  UNUSED
+COLUMNS
+    X  COST  1.0  EQ  1.0
+    X  LE  2.0
+    Y  COST  -2.5  GE  3.0
+    Y  UNUSED  7.0
+RHS
+    RHS  COST  -4.0  EQ  1.0
+    RHS  LE  5.0
+    GE  -1.0
+ENDATA
+",
+    )
+    .unwrap();
+
+    assert_eq!(problem.q, vec![1.0, -2.5]);
+    assert_eq!(problem.objective_constant, 4.0);
+    assert_eq!(problem.sense, OptimizationSense::Minimize);
+
+    // three constraint rows, then the default bounds x,y ≥ 0
+    let inf = f64::INFINITY;
+    assert_eq!((problem.A.m, problem.A.n), (5, 2));
+    assert_eq!(problem.l, vec![1.0, -inf, -1.0, 0.0, 0.0]);
+    assert_eq!(problem.u, vec![1.0, 5.0, inf, inf, inf]);
+
+    let A = CscMatrix::from(&[
+        [1.0, 0.0], //
+        [2.0, 0.0], //
+        [0.0, 3.0], //
+        [1.0, 0.0], //
+        [0.0, 1.0], //
+    ]);
+    assert_eq!(problem.A, A);
+    assert_eq!(problem.P.nnz(), 0);
+}
+
+#[test]
+fn test_qps_ranges() {
+    let problem = read_snippet(
+        "\
+NAME
+ROWS
+ N  OBJ
+ E  E1
+ E  E2
+ L  L1
+ G  G1
+COLUMNS
+    X  E1  1.0  E2  1.0
+    X  L1  1.0  G1  1.0
+RHS
+    RHS  E1  2.0  E2  2.0
+    RHS  L1  2.0  G1  2.0
+RANGES
+    RNG  E1  3.0  E2  -3.0
+    RNG  L1  -3.0
+    G1  3.0
+BOUNDS
+ FR BND  X
+ENDATA
+",
+    )
+    .unwrap();
+
+    assert_eq!(problem.l, vec![2.0, -1.0, -1.0, 2.0]);
+    assert_eq!(problem.u, vec![5.0, 2.0, 2.0, 5.0]);
+}
+
+#[test]
+fn test_qps_bounds() {
+    let problem = read_snippet(
+        "\
+NAME
+ROWS
+ N  OBJ
+COLUMNS
+    X1  OBJ  1.0
+    X2  OBJ  1.0
+    X3  OBJ  1.0
+    X4  OBJ  1.0
+    X5  OBJ  1.0
+    X6  OBJ  1.0
+    X7  OBJ  1.0
+    X8  OBJ  1.0
+BOUNDS
+ UP BND  X1  4.0
+ UP BND  X2  -4.0
+ LO BND  X3  -1.0
+ UP BND  X3  -0.5
+ FX BND  X4  3.0
+ FR BND  X5
+ MI BND  X6
+ UP BND  X6  1e30
+ PL BND  X7
+ LO  X8  1.0
+ENDATA
+",
+    )
+    .unwrap();
+
+    // a row for each variable except the free X5 and X6
+    let inf = f64::INFINITY;
+    assert_eq!((problem.A.m, problem.A.n), (6, 8));
+    assert_eq!(problem.l, vec![0.0, -inf, -1.0, 3.0, 0.0, 1.0]);
+    assert_eq!(problem.u, vec![4.0, -4.0, -0.5, 3.0, inf, inf]);
+    assert_eq!(problem.A.rowval, vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(problem.A.colptr, vec![0, 1, 2, 3, 4, 4, 4, 5, 6]);
+}
+
+#[test]
+fn test_qps_quadobj_qmatrix() {
+    let header = "\
+NAME
+OBJSENSE
+    MIN
+ROWS
+ N  OBJ
+COLUMNS
+    X  OBJ  1.0
+    Y  OBJ  1.0
+";
+    let quadobj = "\
+QUADOBJ
+    X  X  2.0
+    Y  X  -1.0
+    Y  Y  4.0
+ENDATA
+";
+    let qmatrix = "\
+QMATRIX
+    X  X  2.0
+    X  Y  -1.0
+    Y  X  -1.0
+    Y  Y  4.0
+ENDATA
+";
+    let P1 = read_snippet(&(header.to_string() + quadobj)).unwrap().P;
+    let P2 = read_snippet(&(header.to_string() + qmatrix)).unwrap().P;
+
+    let P = CscMatrix::from(&[
+        [2.0, -1.0], //
+        [0.0, 4.0],  //
+    ]);
+    assert_eq!(P1, P);
+    assert_eq!(P2, P);
+
+    // maximization problems negate the quadratic term
+    let max = header.replace("MIN", "MAX") + quadobj;
+    let problem = read_snippet(&max).unwrap();
+    assert_eq!(problem.sense, OptimizationSense::Maximize);
+    assert_eq!(problem.P.nzval, vec![-2.0, 1.0, -4.0]);
+}
+
+#[test]
+fn test_qps_errors() {
+    let base = "\
+NAME
+ROWS
+ N  OBJ
+COLUMNS
+";
+    let marker = base.to_string() + "    M  'MARKER'  'INTORG'\n    X  OBJ  1.0\nENDATA\n";
+    assert!(matches!(
+        read_snippet(&marker),
+        Err(QpsError::Unsupported { line: 5, .. })
+    ));
+
+    let unknown = base.to_string() + "    X  C1  1.0\nENDATA\n";
+    assert!(matches!(
+        read_snippet(&unknown),
+        Err(QpsError::Parse { line: 5, .. })
+    ));
+
+    let integer = base.to_string() + "    X  OBJ  1.0\nBOUNDS\n BV BND  X\nENDATA\n";
+    assert!(matches!(
+        read_snippet(&integer),
+        Err(QpsError::Unsupported { line: 7, .. })
+    ));
+
+    assert!(matches!(
+        read_snippet("ROWS\n E  C1\nENDATA\n"),
+        Err(QpsError::Parse { .. })
+    ));
+}
+
+#[test]
+fn test_qps_to_conic() {
+    let problem = read_test_file("HS21.QPS");
+    let conic = problem.to_conic();
+
+    // one inequality row and two doubly bounded variables
+    assert!(matches!(conic.cones[..], [NonnegativeConeT(5)]));
+    assert_eq!(conic.b, vec![50.0, 50.0, -10.0, -2.0, 50.0]);
+    assert_eq!(conic.P, problem.P);
+}
+
+#[test]
+fn test_qps_maros_meszaros_hs21() {
+    let problem = read_test_file("HS21.QPS");
+    assert_eq!(problem.objective_constant, -100.0);
+
+    let (obj, x) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - -99.96) <= 1e-6);
+    assert!(x.dist(&[2.0, 0.0]) <= 1e-5);
+}
+
+#[test]
+fn test_qps_maros_meszaros_hs35() {
+    let problem = read_test_file("HS35.QPS");
+    assert_eq!(problem.objective_constant, 9.0);
+
+    let (obj, x) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - 1.0 / 9.0) <= 1e-6);
+    assert!(x.dist(&[4.0 / 3.0, 7.0 / 9.0, 4.0 / 9.0]) <= 1e-5);
+}
@@ -0,0 +1,125 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. -1 ≤ x ≤ 1
+#[allow(clippy::type_complexity)]
+fn box_problem(
+    n: usize,
+    P: CscMatrix<f64>,
+) -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let q: Vec<f64> = (0..n).map(|i| ((i + 1) as f64).sin()).collect();
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&CscMatrix::identity(n), &I);
+    let b = vec![1.; 2 * n];
+    let cones = vec![NonnegativeConeT(2 * n)];
+    (P, q, A, b, cones)
+}
+
+fn solve(n: usize, P: CscMatrix<f64>) -> DefaultSolver<f64> {
+    let (P, q, A, b, cones) = box_problem(n, P);
+    // presolve disabled to allow data updates
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .presolve_enable(false)
+        .presolve_reductions_enable(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+// P with the given diagonal and an explicitly stored
+// zero at (0,1), so that P has general structure
+fn with_stored_zero(diag: Vec<f64>) -> CscMatrix<f64> {
+    let n = diag.len();
+    let I = (0..n).chain([0]).collect();
+    let J = (0..n).chain([1]).collect();
+    let V = diag.into_iter().chain([0.]).collect();
+    CscMatrix::new_from_triplets(n, n, I, J, V)
+}
+
+#[test]
+fn test_structure_detection() {
+    let n = 6;
+    let cases = [
+        (CscMatrix::zeros((n, n)), QuadraticStructure::Zero),
+        (CscMatrix::identity(n), QuadraticStructure::Diagonal),
+        (with_stored_zero(vec![1.; n]), QuadraticStructure::General),
+    ];
+    for (P, expected) in cases {
+        let solver = solve(n, P);
+        assert_eq!(solver.data.P_structure(), expected);
+    }
+}
+
+#[test]
+fn test_zero_P_matches_general() {
+    let n = 20;
+    let zero = solve(n, CscMatrix::zeros((n, n)));
+    let general = solve(n, with_stored_zero(vec![0.; n]));
+
+    assert_eq!(zero.solution.status, SolverStatus::Solved);
+    assert_eq!(general.solution.status, SolverStatus::Solved);
+    assert!(zero.solution.x.dist(&general.solution.x) <= 1e-7);
+    assert!(f64::abs(zero.solution.obj_val - general.solution.obj_val) <= 1e-7);
+}
+
+#[test]
+fn test_diagonal_P_matches_general() {
+    let n = 20;
+    let diag: Vec<f64> = (0..n).map(|i| 1. + (i % 3) as f64).collect();
+
+    let P = CscMatrix::from_banded(n, &[(0, diag.clone())]);
+    let mut diagonal = solve(n, P);
+    let mut general = solve(n, with_stored_zero(diag.clone()));
+
+    assert_eq!(diagonal.solution.status, SolverStatus::Solved);
+    assert!(diagonal.solution.x.dist(&general.solution.x) <= 1e-7);
+    assert!(f64::abs(diagonal.solution.obj_val - general.solution.obj_val) <= 1e-7);
+
+    // the dense diagonal follows updates to the values of P
+    let diag2: Vec<f64> = diag.iter().map(|d| 1. / d).collect();
+    diagonal.update_P(&diag2).unwrap();
+    general.update_P(&with_stored_zero(diag2)).unwrap();
+    diagonal.solve();
+    general.solve();
+
+    assert_eq!(diagonal.data.P_structure(), QuadraticStructure::Diagonal);
+    assert_eq!(diagonal.solution.status, SolverStatus::Solved);
+    assert!(diagonal.solution.x.dist(&general.solution.x) <= 1e-7);
+}
+
+// Per-iteration solve times for a box constrained problem with
+// n = 200k variables and P structurally zero, diagonal or general.
+// Run with
+//
+//   cargo test --release --test quadratic_structure -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_quadratic_structure() {
+    let n = 200_000;
+    let cases = [
+        ("zero", CscMatrix::zeros((n, n))),
+        ("diagonal", CscMatrix::identity(n)),
+        ("general", with_stored_zero(vec![1.; n])),
+    ];
+    for (name, P) in cases {
+        let solver = solve(n, P);
+        let solution = &solver.solution;
+        println!(
+            "{:>8} P: {:3} iterations, {:8.3} ms per iteration",
+            name,
+            solution.iterations,
+            solution.solve_time * 1e3 / solution.iterations as f64
+        );
+    }
+}
@@ -0,0 +1,102 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn regularization_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    // P = [4. 1;1 2]
+    let P = CscMatrix::from(&[
+        [4., 1.], //
+        [0., 2.], //
+    ]);
+
+    // A = [1. 1;1 0; 0 1]; A = [-A;A]
+    let A = CscMatrix::from(&[
+        [1., 1.], //
+        [1., 0.], //
+        [0., 1.], //
+    ]);
+    let (mut A1, A2) = (A.clone(), A);
+    A1.negate();
+    let A = CscMatrix::vcat(&A1, &A2);
+
+    let q = vec![1., 1.];
+    let b = vec![-1., 0., 0., 1., 0.7, 0.7];
+    let cones = vec![NonnegativeConeT(3), NonnegativeConeT(3)];
+
+    (P, q, A, b, cones)
+}
+
+fn solve_with_schedule(schedule: Option<RegularizationSchedule<f64>>) -> DefaultSolver {
+    let (P, q, A, b, cones) = regularization_test_data();
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .static_regularization_schedule(schedule)
+        .build()
+        .unwrap();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+#[test]
+fn test_regularization_schedule_values() {
+    let schedule = RegularizationSchedule::Constant(1e-7);
+    assert_eq!(schedule.value_at_iteration(0), 1e-7);
+    assert_eq!(schedule.value_at_iteration(100), 1e-7);
+
+    let schedule = RegularizationSchedule::Decreasing {
+        initial: 1e-2,
+        decay: 0.5,
+    };
+    assert_eq!(schedule.value_at_iteration(0), 1e-2);
+    assert_eq!(schedule.value_at_iteration(3), 1e-2 * 0.125);
+    assert_eq!(schedule.value_at_iteration(usize::MAX), 0.);
+
+    let schedule = RegularizationSchedule::Adaptive { initial: 1e-8 };
+    assert_eq!(schedule.value_at_iteration(5), 1e-8);
+}
+
+#[test]
+fn test_regularization_schedule_constant_vs_decreasing() {
+    let refsol = vec![0.3, 0.7];
+
+    let unset = solve_with_schedule(None);
+    let constant = solve_with_schedule(Some(RegularizationSchedule::Constant(1e-8)));
+
+    // the default constant as a schedule is the same as no schedule
+    assert_eq!(constant.solution.status, SolverStatus::Solved);
+    assert_eq!(constant.info.iterations, unset.info.iterations);
+    assert!(constant.solution.x.dist(&refsol) <= 1e-6);
+
+    // heavy regularization throughout distorts the Newton directions,
+    // and the solver stalls before reaching full accuracy
+    let heavy = solve_with_schedule(Some(RegularizationSchedule::Constant(1e-2)));
+    assert_ne!(heavy.solution.status, SolverStatus::Solved);
+
+    // ... but is harmless if it decays as the solver converges
+    let decreasing = solve_with_schedule(Some(RegularizationSchedule::Decreasing {
+        initial: 1e-2,
+        decay: 0.1,
+    }));
+    assert_eq!(decreasing.solution.status, SolverStatus::Solved);
+    assert!(decreasing.solution.x.dist(&refsol) <= 1e-6);
+    assert!(decreasing.info.iterations <= constant.info.iterations + 1);
+}
+
+#[test]
+fn test_regularization_schedule_adaptive() {
+    let solver = solve_with_schedule(Some(RegularizationSchedule::Adaptive { initial: 1e-8 }));
+
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    let refsol = vec![0.3, 0.7];
+    assert!(solver.solution.x.dist(&refsol) <= 1e-6);
+}
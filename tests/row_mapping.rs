@@ -0,0 +1,159 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn mapping_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::from(&[
+        [1., 0.5, 0., 0., 0.], //
+        [0., 2., 0., 0., 0.],  //
+        [0., 0., 1., 0., 0.],  //
+        [0., 0., 0., 1., 0.],  //
+        [0., 0., 0., 0., 4.],  //
+    ]);
+    let q = vec![1., -1., 0.5, 2., -2.];
+
+    let A = CscMatrix::from(&[
+        [1., 0., 0., 0., 0.],  // x₀ = 1 : fixes x₀
+        [1., 1., 1., 0., 0.],  // x₀ + x₁ + x₂ = 3
+        [0., 0., 0., 1., 0.],  // x₃ ≤ 0.5
+        [0., 0., 0., -1., 0.], // x₃ ≥ 0.5 : fixes x₃
+        [0., 1., 0., 0., 0.],  // x₁ ≤ 5 : redundant
+        [0., 1., 0., 0., 0.],  // x₁ ≤ 2
+        [0., 0., -1., 0., 0.], // x₂ ≥ 0
+        [0., 0., 0., 0., 0.],  // 0 ≤ 1 : empty
+        [1., 1., 0., 0., 0.],  // x₀ + x₁ ≤ 10 : redundant once x₀ is fixed
+        [0., 0., 1., 0., 0.],  // x₂ ≤ ∞ : absent bound
+    ]);
+    let b = vec![1., 3., 0.5, -0.5, 5., 2., 0., 1., 10., 1e30];
+    let cones = vec![ZeroConeT(2), NonnegativeConeT(8)];
+
+    (P, q, A, b, cones)
+}
+
+fn settings(presolve: bool, reductions: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .presolve_enable(presolve)
+        .presolve_reductions_enable(reductions)
+        .verbose(false)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_row_mapping_presolve() {
+    let (P, q, A, b, cones) = mapping_test_data();
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true, true)).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    let mapping = solver.row_mapping();
+    assert_eq!(mapping.original_rows(), 10);
+    assert_eq!(mapping.internal_rows(), 3);
+    assert_eq!(mapping.internal_rows(), solver.variables.z.len());
+
+    use RowFate::*;
+    use RowRemoval::*;
+    let expected = [
+        Removed(FixedVariable(0)),
+        Row(0),
+        Removed(FixedVariable(3)),
+        Removed(FixedVariable(3)),
+        Removed(RedundantBound(1)),
+        Row(1),
+        Row(2),
+        Removed(EmptyRow),
+        Removed(RedundantBound(1)),
+        Removed(InfiniteBound),
+    ];
+    for (i, &fate) in expected.iter().enumerate() {
+        assert_eq!(mapping.original_to_internal(i), fate);
+    }
+
+    // round trip between the original and internal rows
+    for k in 0..mapping.internal_rows() {
+        let i = mapping.internal_to_original(k).unwrap();
+        assert_eq!(mapping.original_to_internal(i), Row(k));
+        assert_eq!(solver.solution.z[i], solver.variables.z[k]);
+    }
+
+    // removed rows are filled with known values
+    let sol = &solver.solution;
+    assert_eq!((sol.s[9], sol.z[9]), (get_infinity(), 0.));
+    assert_eq!((sol.z[4], sol.z[7], sol.z[8]), (0., 0., 0.));
+
+    let mut full = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true, false)).unwrap();
+    full.solve();
+    assert!(sol.x.dist(&full.solution.x) <= 1e-6);
+    assert!(sol.s.dist(&full.solution.s) <= 1e-6);
+
+    #[cfg(feature = "testing")]
+    {
+        use clarabel::qa::*;
+        // the absent bound is not checked
+        let mut b = b.clone();
+        b[9] = f64::INFINITY;
+        let problem = Problem::new(&P, &q, &A, &b, &cones);
+        verify_solution(&problem, sol, 1e-6).assert_ok();
+    }
+}
+
+#[test]
+fn test_row_mapping_unreduced() {
+    let (P, q, A, b, cones) = mapping_test_data();
+
+    let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(false, false)).unwrap();
+    let mapping = solver.row_mapping();
+
+    assert_eq!(mapping.internal_rows(), 10);
+    for i in 0..10 {
+        assert_eq!(mapping.original_to_internal(i), RowFate::Row(i));
+        assert_eq!(mapping.internal_to_original(i), Some(i));
+    }
+}
+
+#[test]
+fn test_row_mapping_dualized() {
+    // min ½‖x‖² + x₀ s.t. |x₀| ≤ 1 + k/10 and |x₁| ≤ 1 + k/10, with
+    // many more constraints than variables
+    let P = CscMatrix::identity(2);
+    let q = [1., 0.];
+    let (mut I, mut J, mut V, mut b) = (vec![], vec![], vec![], vec![]);
+    for k in 0..40 {
+        I.push(k);
+        J.push(k % 2);
+        V.push(if k % 4 < 2 { 1. } else { -1. });
+        b.push(1. + (k / 4) as f64 / 10.);
+    }
+    let A = CscMatrix::new_from_triplets(40, 2, I, J, V);
+    let cones = [NonnegativeConeT(40)];
+
+    let settings = DefaultSettingsBuilder::default()
+        .auto_dualize(true)
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    // the dual of each row follows the two variables w of the dual problem
+    let mapping = solver.row_mapping();
+    for i in 0..40 {
+        assert_eq!(
+            mapping.original_to_internal(i),
+            RowFate::DualVariable(2 + i)
+        );
+        assert_eq!(solver.solution.z[i], solver.variables.x[2 + i]);
+    }
+    for k in 0..mapping.internal_rows() {
+        assert_eq!(mapping.internal_to_original(k), None);
+    }
+}
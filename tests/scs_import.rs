@@ -0,0 +1,130 @@
+#![allow(non_snake_case)]
+#![cfg(all(feature = "io", feature = "serde"))]
+
+use clarabel::algebra::*;
+use clarabel::io::*;
+use clarabel::solver::*;
+use std::fs::File;
+use std::io::BufReader;
+
+fn read_test_file(name: &str) -> ConicProblem<f64> {
+    let path = format!("{}/tests/data/scs/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let file = File::open(path).unwrap();
+    read_scs_json(BufReader::new(file)).unwrap()
+}
+
+fn solve_to_objective(problem: &ConicProblem<f64>) -> (f64, Vec<f64>) {
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    let obj = solver.solution.obj_val + problem.objective_constant;
+    (obj, solver.solution.x.clone())
+}
+
+#[test]
+fn test_scs_json_mixed_cones() {
+    // min  ‖(a,b)‖ + exp(a) + 0.5
+    // s.t. a + b = 2, a ≥ 0.5, b ≥ 0
+    let problem = read_test_file("mixed_cones.json");
+
+    assert_eq!((problem.A.m, problem.A.n), (9, 4));
+    assert_eq!(problem.objective_constant, 0.5);
+    assert!(matches!(
+        problem.cones[..],
+        [
+            ZeroConeT(1),
+            NonnegativeConeT(2),
+            SecondOrderConeT(3),
+            ExponentialConeT()
+        ]
+    ));
+
+    let (obj, x) = solve_to_objective(&problem);
+    let refobj = f64::sqrt(2.5) + f64::exp(0.5) + 0.5;
+    assert!(f64::abs(obj - refobj) <= 1e-6);
+    assert!(f64::abs(x[0] - 0.5) <= 1e-6);
+    assert!(f64::abs(x[1] - 1.5) <= 1e-6);
+}
+
+#[test]
+fn test_scs_data_power_cone() {
+    // max  x^0.6 y^0.4  s.t. x + y = 1
+    let A = CscMatrix::from(&[
+        [1., 1., 0.],  //
+        [-1., 0., 0.], //
+        [0., -1., 0.], //
+        [0., 0., -1.], //
+    ]);
+    let b = [1., 0., 0., 0.];
+    let c = [0., 0., -1.];
+    let cones = ScsConeSpec {
+        z: 1,
+        p: vec![0.6],
+        ..ScsConeSpec::default()
+    };
+
+    let problem = from_scs_data(A, &b, &c, cones).unwrap();
+    assert!(matches!(problem.cones[..], [ZeroConeT(1), PowerConeT(_)]));
+
+    let (obj, _) = solve_to_objective(&problem);
+    let refobj = -f64::powf(0.6, 0.6) * f64::powf(0.4, 0.4);
+    assert!(f64::abs(obj - refobj) <= 1e-6);
+}
+
+#[test]
+fn test_scs_unsupported_cones() {
+    let A = CscMatrix::<f64>::identity(3);
+    let (b, c) = ([0.; 3], [1.; 3]);
+
+    let cones = ScsConeSpec {
+        s: vec![2],
+        ..ScsConeSpec::default()
+    };
+    let err = from_scs_data(A.clone(), &b, &c, cones).unwrap_err();
+    assert!(matches!(err, ScsError::UnsupportedCone(ref name) if name == "s"));
+
+    let cones = ScsConeSpec {
+        ed: 1,
+        ..ScsConeSpec::default()
+    };
+    let err = from_scs_data(A, &b, &c, cones).unwrap_err();
+    assert!(matches!(err, ScsError::UnsupportedCone(ref name) if name == "ed"));
+
+    // box cones are reported by their SCS name
+    let json = r#"{
+        "data": {
+            "A": {"shape": [1, 1], "indptr": [0, 1], "indices": [0], "data": [1.0]},
+            "b": [1.0],
+            "c": [1.0]
+        },
+        "cone": {"l": 0, "bl": [0.0], "bu": [1.0], "s": []}
+    }"#;
+    let err = read_scs_json::<f64, _>(json.as_bytes()).unwrap_err();
+    assert!(matches!(err, ScsError::UnsupportedCone(ref name) if name == "bl"));
+    assert_eq!(err.to_string(), "unsupported SCS cone \"bl\"");
+}
+
+#[test]
+fn test_scs_dimension_mismatch() {
+    let A = CscMatrix::<f64>::identity(3);
+    let (b, c) = ([0.; 3], [1.; 3]);
+    let cones = ScsConeSpec {
+        l: 2,
+        ..ScsConeSpec::default()
+    };
+    let err = from_scs_data(A, &b, &c, cones).unwrap_err();
+    assert!(matches!(err, ScsError::IncompatibleDimension(_)));
+
+    // the total dimension saturates rather than wrapping
+    let cones = ScsConeSpec::<f64> {
+        l: usize::MAX,
+        q: vec![4],
+        ..ScsConeSpec::default()
+    };
+    assert_eq!(cones.dim(), usize::MAX);
+}
@@ -0,0 +1,86 @@
+#![allow(non_snake_case)]
+#![cfg(all(feature = "io", feature = "sdp"))]
+
+use clarabel::algebra::*;
+use clarabel::io::*;
+use clarabel::solver::*;
+use std::fs::File;
+use std::io::BufReader;
+
+fn read_test_file(name: &str) -> ConicProblem<f64> {
+    let path = format!("{}/tests/data/sdpa/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let file = File::open(path).unwrap();
+    read_sdpa(BufReader::new(file)).unwrap()
+}
+
+fn solve_to_objective(problem: &ConicProblem<f64>) -> (f64, Vec<f64>) {
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = problem.solver(settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    (solver.solution.obj_val, solver.solution.x.clone())
+}
+
+#[test]
+fn test_sdpa_mixed_blocks() {
+    let problem = read_test_file("mixed_blocks.dat-s");
+
+    assert_eq!(problem.q, vec![1.0, 1.0]);
+    assert!(matches!(
+        problem.cones[..],
+        [PSDTriangleConeT(2), NonnegativeConeT(1)]
+    ));
+
+    // the off diagonal entry is scaled by √2 in
+    // the triangular packed form
+    let A = CscMatrix::from(&[
+        [-1.0, 0.0], //
+        [0.0, 0.0],  //
+        [0.0, -1.0], //
+        [-1.0, 0.0], //
+    ]);
+    assert_eq!(problem.A, A);
+    assert_eq!(problem.b, vec![0.0, f64::sqrt(2.0), 0.0, -2.0]);
+
+    let (obj, x) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - 2.5) <= 1e-6);
+    assert!(x.dist(&[2.0, 0.5]) <= 1e-5);
+}
+
+#[test]
+fn test_sdpa_theta_c5() {
+    let problem = read_test_file("theta_c5.dat-s");
+
+    assert_eq!((problem.A.m, problem.A.n), (15, 6));
+    assert!(matches!(problem.cones[..], [PSDTriangleConeT(5)]));
+
+    let (obj, _) = solve_to_objective(&problem);
+    assert!(f64::abs(obj - f64::sqrt(5.0)) <= 1e-6);
+}
+
+#[test]
+fn test_sdpa_errors() {
+    // off diagonal entry in a diagonal block
+    let text = "1\n1\n-2\n1.0\n1 1 1 2 1.0\n";
+    assert!(matches!(
+        read_sdpa::<f64, _>(text.as_bytes()),
+        Err(SdpaError::Parse { line: 5, .. })
+    ));
+
+    // entry outside of its block
+    let text = "\"comment\n1\n1\n2\n1.0\n1 1 1 3 1.0\n";
+    assert!(matches!(
+        read_sdpa::<f64, _>(text.as_bytes()),
+        Err(SdpaError::Parse { line: 6, .. })
+    ));
+
+    // missing objective
+    assert!(matches!(
+        read_sdpa::<f64, _>("2\n1\n2\n".as_bytes()),
+        Err(SdpaError::Parse { .. })
+    ));
+}
@@ -0,0 +1,155 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "testing")]
+
+use clarabel::qa::*;
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. x₁ + x₂ = 1, x ≥ 0 and ‖(x₁,x₂)‖ ≤ 1,
+// with solution x = (0.3, 0.7)
+#[allow(clippy::type_complexity)]
+fn verification_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::from(&[
+        [4., 1.], //
+        [0., 2.],
+    ]);
+    let q = vec![1., 1.];
+
+    let A = CscMatrix::from(&[
+        [1., 1.],  //
+        [-1., 0.], //
+        [0., -1.], //
+        [0., 0.],  //
+        [-1., 0.], //
+        [0., -1.],
+    ]);
+    let b = vec![1., 0., 0., 1., 0., 0.];
+
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(2), SecondOrderConeT(3)];
+
+    (P, q, A, b, cones)
+}
+
+fn solve() -> DefaultSolution<f64> {
+    let (P, q, A, b, cones) = verification_test_data();
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver.solution
+}
+
+#[test]
+fn test_verify_solution_accepts_solution() {
+    let (P, q, A, b, cones) = verification_test_data();
+    let problem = Problem::new(&P, &q, &A, &b, &cones);
+    let solution = solve();
+    assert_eq!(solution.status, SolverStatus::Solved);
+
+    let report = verify_solution(&problem, &solution, 1e-7);
+    report.assert_ok();
+    assert_eq!(report.primal_cone_violations.len(), 3);
+    assert!(report.primal_residual <= 1e-7);
+    assert!(report.stationarity <= 1e-7);
+
+    // a full symmetric P is handled
+    let Pfull = CscMatrix::from(&[
+        [4., 1.], //
+        [1., 2.],
+    ]);
+    let problem = Problem::new(&Pfull, &q, &A, &b, &cones);
+    assert!(verify_solution(&problem, &solution, 1e-7).is_ok());
+}
+
+#[test]
+fn test_verify_solution_flags_corruption() {
+    let (P, q, A, b, cones) = verification_test_data();
+    let problem = Problem::new(&P, &q, &A, &b, &cones);
+
+    // x moved outside the feasible set, with slacks that still
+    // satisfy Ax + s = b
+    let mut solution = solve();
+    solution.x.copy_from_slice(&[-0.5, 1.5]);
+    solution.s.copy_from_slice(&[0., -0.5, 1.5, 1., -0.5, 1.5]);
+    let report = verify_solution(&problem, &solution, 1e-7);
+    assert!(report
+        .violations
+        .contains(&Violation::PrimalCone { index: 1 }));
+    assert!(report
+        .violations
+        .contains(&Violation::PrimalCone { index: 2 }));
+    assert!(!report
+        .violations
+        .contains(&Violation::PrimalCone { index: 0 }));
+    assert!(!report.violations.contains(&Violation::PrimalResidual));
+    assert_eq!(report.primal_cone_violations[1], 0.5);
+
+    // a dual variable of the nonnegative cone made negative
+    let mut solution = solve();
+    solution.z[1] = -1.;
+    let report = verify_solution(&problem, &solution, 1e-7);
+    assert!(report
+        .violations
+        .contains(&Violation::DualCone { index: 1 }));
+    assert!(report.violations.contains(&Violation::Stationarity));
+    assert!(!report.violations.contains(&Violation::PrimalResidual));
+
+    // a perturbed primal solution
+    let mut solution = solve();
+    solution.x[0] += 1e-3;
+    let report = verify_solution(&problem, &solution, 1e-7);
+    assert!(report.violations.contains(&Violation::PrimalResidual));
+    assert!(report.violations.contains(&Violation::Stationarity));
+    assert!(!report
+        .violations
+        .contains(&Violation::DualCone { index: 1 }));
+}
+
+#[test]
+fn test_cone_violations() {
+    let exp = ExponentialConeT();
+    assert_eq!(primal_cone_violation(&exp, &[0., 1., 1.]), 0.);
+    assert!(primal_cone_violation(&exp, &[1., 1., 1.]) > 0.9);
+    assert_eq!(primal_cone_violation(&exp, &[-1., 0., 0.]), 0.);
+    assert_eq!(dual_cone_violation(&exp, &[-1., 0., 1.]), 0.);
+    assert!(dual_cone_violation(&exp, &[1., 0., 1.]) > 0.);
+
+    let pow = PowerConeT(0.5);
+    assert_eq!(primal_cone_violation(&pow, &[4., 1., 2.]), 0.);
+    assert!(f64::abs(primal_cone_violation(&pow, &[4., 1., 3.]) - 1.) < 1e-12);
+    assert_eq!(dual_cone_violation(&pow, &[2., 0.5, 2.]), 0.);
+    assert!(dual_cone_violation(&pow, &[2., 0.5, 3.]) > 0.);
+
+    let genpow = GenPowerConeT(vec![0.5, 0.5], 2);
+    assert_eq!(primal_cone_violation(&genpow, &[4., 1., 1.2, 1.6]), 0.);
+    assert!(primal_cone_violation(&genpow, &[4., -1., 0., 0.]) >= 1.);
+
+    let soc = SecondOrderConeT(3);
+    assert_eq!(primal_cone_violation(&soc, &[5., 3., 4.]), 0.);
+    assert_eq!(dual_cone_violation(&soc, &[4., 3., 4.]), 1.);
+
+    assert_eq!(primal_cone_violation(&ZeroConeT(2), &[0., -2.]), 2.);
+    assert_eq!(dual_cone_violation(&ZeroConeT(2), &[1., -2.]), 0.);
+}
+
+#[test]
+fn test_assert_solution_close() {
+    let a = solve();
+    let mut b = solve();
+    assert_solution_close(&a, &b, 1e-9);
+
+    // duals are not compared
+    b.z[1] += 1.;
+    assert_solution_close(&a, &b, 1e-9);
+
+    b.x[0] += 1e-3;
+    let result = std::panic::catch_unwind(|| assert_solution_close(&a, &b, 1e-6));
+    assert!(result.is_err());
+}
@@ -0,0 +1,104 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "sprs")]
+
+use clarabel::algebra::*;
+use sprs::{CsMat, CsMatI, TriMat};
+
+// simple LCG so that tests are reproducible
+fn random_trimat(m: usize, n: usize, density: f64, seed: &mut u64) -> TriMat<f64> {
+    let mut rand = || {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (*seed >> 11) as f64 / (1u64 << 53) as f64
+    };
+    let mut T = TriMat::new((m, n));
+    for i in 0..m {
+        for j in 0..n {
+            if rand() < density {
+                T.add_triplet(i, j, 2.0 * rand() - 1.0);
+            }
+        }
+    }
+    T
+}
+
+// y = M*x, computed from the sprs entries directly
+fn sprs_mul(M: &CsMat<f64>, x: &[f64]) -> Vec<f64> {
+    let mut y = vec![0.0; M.rows()];
+    for (&v, (i, j)) in M.iter() {
+        y[i] += v * x[j];
+    }
+    y
+}
+
+// y = A*x, computed from the CscMatrix fields directly
+fn csc_mul(A: &CscMatrix<f64>, x: &[f64]) -> Vec<f64> {
+    let mut y = vec![0.0; A.m];
+    for (j, &xj) in x.iter().enumerate() {
+        for k in A.colptr[j]..A.colptr[j + 1] {
+            y[A.rowval[k]] += A.nzval[k] * xj;
+        }
+    }
+    y
+}
+
+#[test]
+fn test_sprs_csc_and_csr_sources() {
+    let mut seed = 1;
+    for &(m, n) in &[(1, 6), (8, 3), (25, 25)] {
+        let T = random_trimat(m, n, 0.3, &mut seed);
+        let Mcsc: CsMat<f64> = T.to_csc();
+        let Mcsr: CsMat<f64> = T.to_csr();
+
+        let A1: CscMatrix<f64> = CscMatrix::try_from_sprs(&Mcsc).unwrap();
+        let A2 = CscMatrix::try_from_sprs(&Mcsr).unwrap();
+        assert_eq!(A1, A2);
+        assert_eq!(A1.nnz(), Mcsc.nnz());
+
+        let x: Vec<f64> = (0..n).map(|i| (i as f64) - 2.5).collect();
+        assert!(csc_mul(&A1, &x).dist(&sprs_mul(&Mcsr, &x)) <= 1e-12);
+
+        // and back again, always in CSC form
+        let B: CsMat<f64> = CsMat::try_from(&A1).unwrap();
+        assert!(B.is_csc());
+        assert_eq!(B, Mcsc);
+    }
+}
+
+#[test]
+fn test_sprs_empty() {
+    let M: CsMat<f64> = CsMat::zero((3, 0));
+    let A = CscMatrix::try_from_sprs(&M).unwrap();
+    assert_eq!(A, CscMatrix::zeros((3, 0)));
+
+    let M: CsMat<f64> = CsMat::zero((0, 0)).to_csc();
+    let A = CscMatrix::try_from_sprs(&M).unwrap();
+    assert_eq!(A, CscMatrix::zeros((0, 0)));
+
+    let B: CsMat<f64> = CsMat::try_from(&CscMatrix::<f64>::zeros((2, 4))).unwrap();
+    assert_eq!((B.rows(), B.cols(), B.nnz()), (2, 4, 0));
+}
+
+#[test]
+fn test_sprs_index_types() {
+    let A = CscMatrix::from(&[
+        [1.0, 0.0], //
+        [2.0, 3.0], //
+    ]);
+
+    // narrow index types
+    let B: CsMatI<f64, u16> = CsMatI::try_from(&A).unwrap();
+    assert_eq!(CscMatrix::try_from_sprs(&B).unwrap(), A);
+
+    // too many rows for the index type
+    let A = CscMatrix::<f64>::identity(40000);
+    assert!(matches!(
+        CsMatI::<f64, i16>::try_from(&A),
+        Err(SparseFormatError::IndexOverflow)
+    ));
+
+    // unsorted row indices are rejected
+    let A = CscMatrix::new(2, 1, vec![0, 2], vec![1, 0], vec![1.0, 2.0]);
+    assert!(CsMat::try_from(&A).is_err());
+}
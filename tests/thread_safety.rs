@@ -0,0 +1,92 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn test_public_types_send_sync() {
+    assert_send::<DefaultSolver<f64>>();
+    assert_sync::<DefaultSolver<f64>>();
+    assert_send::<DefaultSolver<f32>>();
+    assert_sync::<DefaultSolver<f32>>();
+    assert_send::<BorrowedSolver<f64>>();
+    assert_sync::<BorrowedSolver<f64>>();
+
+    assert_send::<DefaultSettings<f64>>();
+    assert_sync::<DefaultSettings<f64>>();
+    assert_send::<DefaultSolution<f64>>();
+    assert_sync::<DefaultSolution<f64>>();
+    assert_send::<DefaultInfo<f64>>();
+    assert_sync::<DefaultInfo<f64>>();
+    assert_send::<CscMatrix<f64>>();
+    assert_sync::<CscMatrix<f64>>();
+    assert_send::<SupportedConeT<f64>>();
+    assert_sync::<SupportedConeT<f64>>();
+}
+
+// min ½‖x‖² + qᵀx s.t. x ≤ 1, with qⱼ = -k(j+1) and solution xⱼ = min(k(j+1), 1)
+fn box_solver(k: f64) -> DefaultSolver<f64> {
+    let n = 3;
+    let P = CscMatrix::identity(n);
+    let q: Vec<f64> = (0..n).map(|i| -k * (i + 1) as f64).collect();
+    let A = CscMatrix::identity(n);
+    let b = vec![1.; n];
+    let cones = [NonnegativeConeT(n)];
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .build()
+        .unwrap();
+    DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap()
+}
+
+#[test]
+fn test_solve_on_thread_pool() {
+    const NPROBLEMS: usize = 100;
+    const NTHREADS: usize = 4;
+
+    // solvers are created on this thread and solved by the workers
+    let ks: Vec<f64> = (0..NPROBLEMS).map(|i| (i as f64 + 0.5) / 100.).collect();
+    let solvers: Vec<Mutex<Option<DefaultSolver<f64>>>> = ks
+        .iter()
+        .map(|&k| Mutex::new(Some(box_solver(k))))
+        .collect();
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, DefaultSolution<f64>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..NTHREADS)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut solved = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= NPROBLEMS {
+                            return solved;
+                        }
+                        let mut solver = solvers[i].lock().unwrap().take().unwrap();
+                        solver.solve();
+                        solved.push((i, solver.solution));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    assert_eq!(results.len(), NPROBLEMS);
+
+    for (i, solution) in &results {
+        assert_eq!(solution.status, SolverStatus::Solved);
+        for (j, &xj) in solution.x.iter().enumerate() {
+            let expected = f64::min(ks[*i] * (j + 1) as f64, 1.);
+            assert!(f64::abs(xj - expected) <= 1e-6);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*, timers::*};
+
+// min ½xᵀPx + qᵀx s.t. the first three entries of x - 1 lie in
+// a second order cone and 0 ≤ x ≤ 10
+fn make_solver(timing: bool) -> DefaultSolver<f64> {
+    let n = 20;
+    let P = CscMatrix::identity(n);
+    let q: Vec<f64> = (0..n).map(|i| ((i + 1) as f64).sin()).collect();
+
+    let mut I = CscMatrix::identity(n);
+    I.negate();
+    let A = CscMatrix::vcat(&I, &CscMatrix::identity(n));
+    let mut b = vec![0.; 2 * n];
+    b[0..3].fill(-1.);
+    b[n..].fill(10.);
+    let cones = [
+        SecondOrderConeT(3),
+        NonnegativeConeT(n - 3),
+        NonnegativeConeT(n),
+    ];
+
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .timing(timing)
+        .build()
+        .unwrap();
+    DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap()
+}
+
+#[test]
+fn test_timings_enabled() {
+    let mut solver = make_solver(true);
+    solver.solve();
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+
+    let t = solver.timings();
+    for (name, time) in [
+        ("setup", t.setup),
+        ("equilibration", t.equilibration),
+        ("factorization", t.factorization),
+        ("affine_solve", t.affine_solve),
+        ("combined_solve", t.combined_solve),
+        ("cone_scaling", t.cone_scaling),
+    ] {
+        assert!(!time.is_zero(), "no time recorded for {}", name);
+    }
+    assert!(t.equilibration <= t.setup);
+
+    let solve = t.factorization + t.affine_solve + t.combined_solve + t.cone_scaling;
+    assert!(solve.as_secs_f64() <= solver.info.solve_time);
+}
+
+#[test]
+fn test_timings_disabled() {
+    let mut solver = make_solver(false);
+    solver.solve();
+    assert_eq!(solver.timings(), SolveTimings::default());
+}
+
+#[test]
+fn test_timings_resolve() {
+    // a second solve replaces the solve phases but keeps setup
+    let mut solver = make_solver(true);
+    solver.solve();
+    let first = solver.timings();
+    solver.solve();
+    let second = solver.timings();
+
+    assert_eq!(first.setup, second.setup);
+    assert_eq!(first.equilibration, second.equilibration);
+    assert_ne!(first.combined_solve, second.combined_solve);
+
+    // enabling timing after setup records the solve phases only
+    let mut solver = make_solver(false);
+    solver.settings.timing = true;
+    solver.solve();
+    let t = solver.timings();
+    assert!(t.setup.is_zero());
+    assert!(!t.affine_solve.is_zero());
+}
@@ -0,0 +1,86 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min x₁ + x₂ s.t. 0 ≤ x ≤ 2 and x₁ + x₂ ≥ 1, in single precision
+#[allow(clippy::type_complexity)]
+fn tolerance_test_data() -> (
+    CscMatrix<f32>,
+    Vec<f32>,
+    CscMatrix<f32>,
+    Vec<f32>,
+    Vec<SupportedConeT<f32>>,
+) {
+    let P = CscMatrix::<f32>::zeros((2, 2));
+    let q = vec![1., 1.];
+
+    let A = CscMatrix::from(&[
+        [-1., 0.], //
+        [0., -1.], //
+        [1., 0.],  //
+        [0., 1.],  //
+        [-1., -1.],
+    ]);
+    let b = vec![0., 0., 2., 2., -1.];
+
+    let cones = vec![NonnegativeConeT(5)];
+
+    (P, q, A, b, cones)
+}
+
+// single precision defaults, with f64 level termination tolerances
+fn settings(policy: TolerancePolicy) -> DefaultSettings<f32> {
+    let mut settings = DefaultSettings::<f32>::default_for();
+    settings.verbose = false;
+    settings.tol_gap_abs = 1e-12;
+    settings.tol_gap_rel = 1e-12;
+    settings.tol_feas = 1e-12;
+    settings.tolerance_policy = policy;
+    settings
+}
+
+#[test]
+fn test_tolerance_policy_clamp() {
+    let (P, q, A, b, cones) = tolerance_test_data();
+    let settings = settings(TolerancePolicy::Clamp);
+    let (tol_infeas_abs, tol_ktratio) = (settings.tol_infeas_abs, settings.tol_ktratio);
+
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert!(f32::abs(solver.solution.obj_val - 1.) <= 1e-3);
+
+    // the floor is 10ε, scaled by the largest entry of b for
+    // the absolute tolerance
+    let tols = &solver.info.tolerances;
+    let floor = 10. * f32::EPSILON;
+    assert_eq!(tols.clamped, ["tol_gap_abs", "tol_gap_rel", "tol_feas"]);
+    assert_eq!(tols.tol_gap_abs, 2. * floor);
+    assert_eq!(tols.tol_gap_rel, floor);
+    assert_eq!(tols.tol_feas, floor);
+
+    // attainable tolerances are unchanged
+    assert_eq!(tols.tol_infeas_abs, tol_infeas_abs);
+    assert_eq!(tols.tol_ktratio, tol_ktratio);
+
+    // and the clamped values are used by the solver
+    assert_eq!(solver.settings.tol_gap_abs, tols.tol_gap_abs);
+}
+
+#[test]
+fn test_tolerance_policy_strict() {
+    let (P, q, A, b, cones) = tolerance_test_data();
+
+    match DefaultSolver::new(&P, &q, &A, &b, &cones, settings(TolerancePolicy::Strict)) {
+        Err(ClarabelError::BadSettings(msg)) => assert!(msg.contains("tol_gap_abs")),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("unattainable tolerances were accepted"),
+    }
+
+    // the calibrated single precision tolerances are attainable
+    let mut settings = DefaultSettings::<f32>::default_for();
+    settings.verbose = false;
+    settings.tolerance_policy = TolerancePolicy::Strict;
+    let solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    assert!(solver.info.tolerances.clamped.is_empty());
+}
@@ -0,0 +1,113 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qᵀx s.t. -1 ≤ x ≤ 1, with P = [2 1; 1 2]
+fn sorted_data() -> (CscMatrix<f64>, CscMatrix<f64>) {
+    let P = CscMatrix::new(2, 2, vec![0, 1, 3], vec![0, 0, 1], vec![2., 1., 2.]);
+    let A = CscMatrix::new(
+        4,
+        2,
+        vec![0, 2, 4],
+        vec![0, 2, 1, 3],
+        vec![1., -1., 1., -1.],
+    );
+    (P, A)
+}
+
+// the same P and A, with unsorted columns and entries split in two
+fn unsorted_data() -> (CscMatrix<f64>, CscMatrix<f64>) {
+    let P = CscMatrix::new(
+        2,
+        2,
+        vec![0, 2, 4],
+        vec![0, 0, 1, 0],
+        vec![1.5, 0.5, 2., 1.],
+    );
+    let A = CscMatrix::new(
+        4,
+        2,
+        vec![0, 3, 5],
+        vec![2, 0, 0, 3, 1],
+        vec![-1., 0.25, 0.75, -1., 1.],
+    );
+    (P, A)
+}
+
+fn settings(canonicalize: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .verbose(false)
+        .input_sparse_canonicalize(canonicalize)
+        .build()
+        .unwrap()
+}
+
+fn new_solver(
+    P: &CscMatrix<f64>,
+    A: &CscMatrix<f64>,
+    canonicalize: bool,
+) -> Result<DefaultSolver<f64>, ClarabelError> {
+    let (q, b) = ([1., -3.], [1.; 4]);
+    let cones = [NonnegativeConeT(4)];
+    DefaultSolver::new(P, &q, A, &b, &cones, settings(canonicalize))
+}
+
+#[test]
+fn test_unsorted_input_repaired() {
+    let (P, A) = sorted_data();
+    let mut expected = new_solver(&P, &A, true).unwrap();
+    expected.solve();
+    assert_eq!(expected.solution.status, SolverStatus::Solved);
+
+    let (Pu, Au) = unsorted_data();
+    let mut solver = new_solver(&Pu, &Au, true).unwrap();
+    solver.solve();
+
+    // the internal data is the same as for the sorted problem
+    assert_eq!(solver.data.P, expected.data.P);
+    assert_eq!(solver.data.A, expected.data.A);
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+    assert_eq!(solver.solution.x, expected.solution.x);
+}
+
+#[test]
+fn test_unsorted_input_borrowed() {
+    let (P, A) = sorted_data();
+    let mut expected = new_solver(&P, &A, true).unwrap();
+    expected.solve();
+
+    // the caller's matrices are copied and left unchanged
+    let (Pu, Au) = unsorted_data();
+    let (q, b) = ([1., -3.], [1.; 4]);
+    let cones = [NonnegativeConeT(4)];
+    let mut solver = DefaultSolver::new_borrowed(&Pu, &q, &Au, &b, &cones, settings(true)).unwrap();
+    solver.solve();
+    assert_eq!(solver.solution.x, expected.solution.x);
+    drop(solver);
+    assert_eq!((Pu, Au), unsorted_data());
+}
+
+#[test]
+fn test_unsorted_input_rejected() {
+    let (P, A) = sorted_data();
+    let (Pu, Au) = unsorted_data();
+
+    let check = |P, A, which, error| match new_solver(P, A, false) {
+        Err(ClarabelError::BadMatrixFormat(name, e)) => {
+            assert_eq!(name, which);
+            assert_eq!(e, error);
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("unsorted data was accepted"),
+    };
+
+    check(&Pu, &A, "P", SparseFormatError::BadRowOrdering);
+    check(&P, &Au, "A", SparseFormatError::BadRowOrdering);
+
+    // sorted, but with a repeated entry
+    let mut Ad = A.clone();
+    Ad.rowval[1] = 0;
+    check(&P, &Ad, "A", SparseFormatError::DuplicateEntries);
+
+    assert!(new_solver(&P, &A, false).is_ok());
+}
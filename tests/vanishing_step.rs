@@ -0,0 +1,81 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+#[allow(clippy::type_complexity)]
+fn vanishing_step_test_data() -> (
+    CscMatrix<f64>,
+    Vec<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::zeros((2, 2));
+    let q = vec![-1., -1.];
+
+    let A = CscMatrix::from(&[
+        [1., -1.], //
+        [1., 0.],  //
+        [0., 1.],  //
+        [-1., 0.], //
+        [0., -1.],
+    ]);
+    let b = vec![0., 1., 1., 0., 0.];
+
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(4)];
+
+    (P, q, A, b, cones)
+}
+
+fn solve(min_terminate_step_length: f64) -> DefaultSolver<f64> {
+    let (P, q, A, b, cones) = vanishing_step_test_data();
+    // tight reduced tolerances, so that an early termination
+    // is not reported as almost solved
+    let settings = DefaultSettingsBuilder::default()
+        .verbose(false)
+        .min_terminate_step_length(min_terminate_step_length)
+        .reduced_tol_gap_abs(1e-12)
+        .reduced_tol_gap_rel(1e-12)
+        .reduced_tol_feas(1e-12)
+        .reduced_tol_ktratio(1e-12)
+        .build()
+        .unwrap();
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings).unwrap();
+    solver.solve();
+    solver
+}
+
+#[test]
+fn test_vanishing_step_fallback_sequence() {
+    // every step is shorter than the floor, so the solver retries once
+    // without a corrector, once as a centering step, and then stops
+    let solver = solve(1.0);
+
+    assert_eq!(solver.info.status, SolverStatus::InsufficientProgress);
+
+    let counts = solver.info.counters();
+    assert_eq!(counts.uncorrected_step_retries, 1);
+    assert_eq!(counts.centering_step_retries, 1);
+
+    let vanishing = solver.info.vanishing_step.unwrap();
+    assert_eq!(vanishing.iteration, 3);
+    assert_eq!(vanishing.iteration, solver.info.iterations);
+    assert!(vanishing.step_length <= 1.0);
+
+    // a centering step from the initial point is not limited by the cones
+    assert_eq!(vanishing.cone, None);
+    assert!(solver.info.termination_detail.is_some());
+}
+
+#[test]
+fn test_vanishing_step_not_triggered() {
+    let solver = solve(1e-4);
+
+    assert_eq!(solver.info.status, SolverStatus::Solved);
+    assert!(solver.info.vanishing_step.is_none());
+    assert!(solver.info.termination_detail.is_none());
+
+    let counts = solver.info.counters();
+    assert_eq!(counts.uncorrected_step_retries, 0);
+    assert_eq!(counts.centering_step_retries, 0);
+}
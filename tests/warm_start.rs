@@ -0,0 +1,161 @@
+#![allow(non_snake_case)]
+
+use clarabel::{algebra::*, solver::*};
+
+// min ½xᵀPx + qₖᵀx  s.t.  -1 ≤ x ≤ 1,  Σx = 1, with P tridiagonal and
+// a linear cost qₖ that drifts slowly with k, as in a sequence of
+// model predictive control problems
+fn mpc_data(
+    n: usize,
+) -> (
+    CscMatrix<f64>,
+    CscMatrix<f64>,
+    Vec<f64>,
+    Vec<SupportedConeT<f64>>,
+) {
+    let P = CscMatrix::from_banded(n, &[(0, vec![4.; n]), (1, vec![-1.; n - 1])]);
+
+    let (mut I, mut J, mut V) = (vec![], vec![], vec![]);
+    for j in 0..n {
+        I.extend([0, 1 + j, 1 + n + j]);
+        J.extend([j, j, j]);
+        V.extend([1., 1., -1.]);
+    }
+    let A = CscMatrix::new_from_triplets(1 + 2 * n, n, I, J, V);
+    let mut b = vec![1.];
+    b.extend(vec![1.; 2 * n]);
+    let cones = vec![ZeroConeT(1), NonnegativeConeT(2 * n)];
+    (P, A, b, cones)
+}
+
+fn mpc_cost(n: usize, k: usize) -> Vec<f64> {
+    (0..n)
+        .map(|j| 3. * f64::sin(j as f64 + 0.02 * k as f64))
+        .collect()
+}
+
+fn settings(presolve: bool) -> DefaultSettings<f64> {
+    DefaultSettingsBuilder::default()
+        .presolve_enable(presolve)
+        .presolve_reductions_enable(presolve)
+        .verbose(false)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_warm_start_mpc_sequence() {
+    let n = 30;
+    let (P, A, b, cones) = mpc_data(n);
+    let q = mpc_cost(n, 0);
+
+    let mut cold = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(false)).unwrap();
+    let mut warm = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(false)).unwrap();
+    warm.solve();
+
+    let (mut cold_iters, mut warm_iters) = (0, 0);
+    for k in 1..=10 {
+        let q = mpc_cost(n, k);
+        cold.update_q(&q).unwrap();
+        cold.solve();
+
+        // start from the solution of the previous problem
+        let sol = &warm.solution;
+        let (x, s, z) = (sol.x.clone(), sol.s.clone(), sol.z.clone());
+        warm.update_q(&q).unwrap();
+        warm.warm_start(&x, Some(&s), Some(&z)).unwrap();
+        warm.solve();
+
+        assert_eq!(cold.solution.status, SolverStatus::Solved);
+        assert_eq!(warm.solution.status, SolverStatus::Solved);
+        assert!(cold.solution.x.dist(&warm.solution.x) <= 1e-5);
+        cold_iters += cold.solution.iterations;
+        warm_iters += warm.solution.iterations;
+    }
+    assert!(2 * warm_iters < 3 * cold_iters / 2);
+}
+
+#[test]
+fn test_warm_start_presolved() {
+    // the same problem with a fixed last variable and an absent
+    // bound, which are removed from the internal problem
+    let n = 30;
+    let (P, A, mut b, cones) = mpc_data(n);
+    let q = mpc_cost(n, 0);
+    b[n] = 0.;
+    b[2 * n] = 0.;
+    b[n + 1] = 1e30;
+
+    let mut cold = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true)).unwrap();
+    cold.solve();
+    assert_eq!(cold.solution.status, SolverStatus::Solved);
+    assert!(cold.presolve_result().is_some());
+    assert!(cold.row_mapping().internal_rows() < b.len());
+
+    let sol = &cold.solution;
+    let (x, s, z) = (sol.x.clone(), sol.s.clone(), sol.z.clone());
+
+    // with and without the slacks and duals
+    for (s, z) in [(Some(&s[..]), Some(&z[..])), (None, None)] {
+        let mut warm = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(true)).unwrap();
+        warm.warm_start(&x, s, z).unwrap();
+        warm.solve();
+        assert_eq!(warm.solution.status, SolverStatus::Solved);
+        assert!(warm.solution.x.dist(&x) <= 1e-5);
+        assert!(warm.solution.iterations < cold.solution.iterations);
+    }
+}
+
+#[test]
+fn test_warm_start_errors() {
+    let n = 5;
+    let (P, A, b, cones) = mpc_data(n);
+    let q = mpc_cost(n, 0);
+    let mut solver = DefaultSolver::new(&P, &q, &A, &b, &cones, settings(false)).unwrap();
+
+    let (x, s) = (vec![0.; n], vec![1.; b.len()]);
+    assert_eq!(
+        solver.warm_start(&x[1..], None, None),
+        Err(WarmStartError::DimensionMismatch {
+            what: "x",
+            expected: n,
+            got: n - 1
+        })
+    );
+    assert_eq!(
+        solver.warm_start(&x, None, Some(&s[1..])),
+        Err(WarmStartError::DimensionMismatch {
+            what: "z",
+            expected: b.len(),
+            got: b.len() - 1
+        })
+    );
+    let mut bad = s.clone();
+    bad[3] = f64::NAN;
+    assert_eq!(
+        solver.warm_start(&x, Some(&bad), None),
+        Err(WarmStartError::NonFiniteEntry {
+            what: "s",
+            index: 3
+        })
+    );
+
+    // a rejected start leaves the default start in place
+    solver.solve();
+    assert_eq!(solver.solution.status, SolverStatus::Solved);
+
+    // many more constraints than variables, solved in dual form
+    let settings = DefaultSettingsBuilder::default()
+        .auto_dualize(true)
+        .verbose(false)
+        .build()
+        .unwrap();
+    let P = CscMatrix::identity(1);
+    let A = CscMatrix::new_from_triplets(40, 1, (0..40).collect(), vec![0; 40], vec![1.; 40]);
+    let cones = [NonnegativeConeT(40)];
+    let mut solver = DefaultSolver::new(&P, &[1.], &A, &[1.; 40], &cones, settings).unwrap();
+    assert_eq!(
+        solver.warm_start(&[0.], None, None),
+        Err(WarmStartError::Dualized)
+    );
+}